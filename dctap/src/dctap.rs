@@ -54,6 +54,11 @@ impl DCTap {
             let shape = maybe_shape?;
             dctap.add_shape(&shape)
         }
+        if tap_reader.has_warnings() {
+            for warning in tap_reader.warnings() {
+                info!("Warning: {warning}");
+            }
+        }
         Ok(dctap)
     }
 
@@ -29,6 +29,10 @@ impl ExtendsId {
         self.label = Some(label.to_string())
     }
 
+    pub fn label(&self) -> Option<String> {
+        self.label.clone()
+    }
+
     pub fn line(&self) -> u64 {
         self.line
     }
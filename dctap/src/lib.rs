@@ -9,6 +9,7 @@ pub mod datatype_id;
 pub mod dctap;
 pub mod dctap_format;
 pub mod extends_id;
+pub mod lint;
 pub mod node_type;
 pub mod placeholder_resolver;
 pub mod prefix_cc;
@@ -23,11 +24,13 @@ pub mod tap_reader_state;
 pub mod tap_reader_warning;
 pub mod tap_shape;
 pub mod tap_statement;
+pub mod tap_writer;
 pub mod value_constraint;
 
 pub use crate::datatype_id::*;
 pub use crate::dctap_format::*;
 pub use crate::extends_id::*;
+pub use crate::lint::*;
 pub use crate::node_type::*;
 pub use crate::placeholder_resolver::*;
 pub use crate::prefix_cc::*;
@@ -41,5 +44,6 @@ pub use crate::tap_reader_state::*;
 pub use crate::tap_reader_warning::*;
 pub use crate::tap_shape::*;
 pub use crate::tap_statement::*;
+pub use crate::tap_writer::*;
 pub use crate::value_constraint::*;
 pub use dctap::*;
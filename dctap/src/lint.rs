@@ -0,0 +1,424 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+use serde_derive::Serialize;
+use thiserror::Error;
+
+use crate::{DCTap, PrefixCC, TapShape};
+
+const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+
+// Datatypes defined in XML Schema 1.1 Part 2
+const XSD_DATATYPES: [&str; 50] = [
+    "anyType",
+    "anySimpleType",
+    "anyAtomicType",
+    "anyURI",
+    "base64Binary",
+    "boolean",
+    "byte",
+    "date",
+    "dateTime",
+    "dateTimeStamp",
+    "dayTimeDuration",
+    "decimal",
+    "double",
+    "duration",
+    "ENTITY",
+    "ENTITIES",
+    "float",
+    "gDay",
+    "gMonth",
+    "gMonthDay",
+    "gYear",
+    "gYearMonth",
+    "hexBinary",
+    "ID",
+    "IDREF",
+    "IDREFS",
+    "int",
+    "integer",
+    "language",
+    "long",
+    "Name",
+    "NCName",
+    "negativeInteger",
+    "NMTOKEN",
+    "NMTOKENS",
+    "nonNegativeInteger",
+    "nonPositiveInteger",
+    "normalizedString",
+    "NOTATION",
+    "positiveInteger",
+    "QName",
+    "short",
+    "string",
+    "time",
+    "token",
+    "unsignedByte",
+    "unsignedInt",
+    "unsignedLong",
+    "unsignedShort",
+    "yearMonthDuration",
+];
+
+/// Problem found in a DCTAP profile by [`lint`]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LintFinding {
+    line: u64,
+
+    #[serde(rename = "shapeID", skip_serializing_if = "Option::is_none")]
+    shape_id: Option<String>,
+
+    #[serde(flatten)]
+    kind: LintFindingKind,
+}
+
+impl LintFinding {
+    pub fn new(line: u64, shape_id: Option<String>, kind: LintFindingKind) -> LintFinding {
+        LintFinding {
+            line,
+            shape_id,
+            kind,
+        }
+    }
+
+    pub fn line(&self) -> u64 {
+        self.line
+    }
+
+    pub fn shape_id(&self) -> Option<&str> {
+        self.shape_id.as_deref()
+    }
+
+    pub fn kind(&self) -> &LintFindingKind {
+        &self.kind
+    }
+}
+
+impl Display for LintFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.shape_id {
+            Some(shape_id) => write!(f, "Line {} (shape {shape_id}): {}", self.line, self.kind),
+            None => write!(f, "Line {}: {}", self.line, self.kind),
+        }
+    }
+}
+
+#[derive(Error, Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type")]
+pub enum LintFindingKind {
+    #[error("valueShape {value_shape} is not defined in the profile")]
+    UndefinedValueShape { value_shape: String },
+
+    #[error("Duplicate propertyID {property_id}, already declared at line {first_line}")]
+    DuplicatePropertyId {
+        property_id: String,
+        first_line: u64,
+    },
+
+    #[error("Prefix {prefix} in {value} can't be resolved")]
+    UnresolvedPrefix { prefix: String, value: String },
+
+    #[error("Both valueDataType {datatype} and valueShape {value_shape} declared in the same row")]
+    DatatypeAndValueShape {
+        datatype: String,
+        value_shape: String,
+    },
+
+    #[error("Unknown XSD datatype {datatype}")]
+    UnknownXsdDatatype { datatype: String },
+
+    #[error("Cycle in extends: {}", cycle.join(" -> "))]
+    ExtendsCycle { cycle: Vec<String> },
+}
+
+/// Checks a DCTAP profile looking for semantic problems
+///
+/// The prefixes used in prefixed names are resolved with `prefix_cc`
+///
+/// # Example
+/// ```
+/// use dctap::{lint, DCTap, PrefixCC, TapConfig};
+///
+/// let data = "shapeID,propertyID,valueShape\nPerson,knows,Company\n";
+/// let dctap = DCTap::from_reader(data.as_bytes(), &TapConfig::default()).unwrap();
+/// let findings = lint(&dctap, &PrefixCC::default());
+/// assert_eq!(findings.len(), 1);
+/// assert_eq!(findings[0].line(), 2);
+/// ```
+pub fn lint(dctap: &DCTap, prefix_cc: &PrefixCC) -> Vec<LintFinding> {
+    let shape_ids: HashSet<String> = dctap
+        .shapes()
+        .filter_map(|shape| shape.shape_id())
+        .map(|shape_id| shape_id.str().to_string())
+        .collect();
+    let mut findings = Vec::new();
+    for shape in dctap.shapes() {
+        lint_shape(shape, &shape_ids, prefix_cc, &mut findings)
+    }
+    findings.extend(extends_cycles(dctap));
+    findings.sort_by_key(|finding| finding.line);
+    findings
+}
+
+fn lint_shape(
+    shape: &TapShape,
+    shape_ids: &HashSet<String>,
+    prefix_cc: &PrefixCC,
+    findings: &mut Vec<LintFinding>,
+) {
+    let shape_id = shape.shape_id().map(|id| id.str().to_string());
+    let mut finding = |line: u64, kind: LintFindingKind| {
+        findings.push(LintFinding::new(line, shape_id.clone(), kind))
+    };
+    if let Some(id) = shape.shape_id() {
+        if let Some(kind) = check_prefix(id.str(), prefix_cc) {
+            finding(id.line(), kind)
+        }
+    }
+    for extends in shape.extends() {
+        if let Some(kind) = check_prefix(extends.str(), prefix_cc) {
+            finding(extends.line(), kind)
+        }
+    }
+    let mut property_lines: HashMap<String, u64> = HashMap::new();
+    for statement in shape.statements() {
+        let property_id = statement.property_id();
+        let line = property_id.line();
+        match property_lines.get(property_id.str()) {
+            Some(first_line) => finding(
+                line,
+                LintFindingKind::DuplicatePropertyId {
+                    property_id: property_id.str().to_string(),
+                    first_line: *first_line,
+                },
+            ),
+            None => {
+                property_lines.insert(property_id.str().to_string(), line);
+            }
+        }
+        if let Some(kind) = check_prefix(property_id.str(), prefix_cc) {
+            finding(line, kind)
+        }
+        if let Some(value_shape) = statement.value_shape() {
+            if let Some(kind) = check_prefix(value_shape.str(), prefix_cc) {
+                finding(line, kind)
+            }
+            if !shape_ids.contains(value_shape.str()) {
+                finding(
+                    line,
+                    LintFindingKind::UndefinedValueShape {
+                        value_shape: value_shape.str().to_string(),
+                    },
+                )
+            }
+        }
+        if let Some(datatype) = statement.value_datatype() {
+            if let Some(kind) = check_prefix(datatype.str(), prefix_cc) {
+                finding(line, kind)
+            }
+            if !is_known_datatype(datatype.str(), prefix_cc) {
+                finding(
+                    line,
+                    LintFindingKind::UnknownXsdDatatype {
+                        datatype: datatype.str().to_string(),
+                    },
+                )
+            }
+            if let Some(value_shape) = statement.value_shape() {
+                finding(
+                    line,
+                    LintFindingKind::DatatypeAndValueShape {
+                        datatype: datatype.str().to_string(),
+                        value_shape: value_shape.str().to_string(),
+                    },
+                )
+            }
+        }
+    }
+}
+
+// Names without prefix or with the empty prefix are resolved against a base IRI so they are not checked
+fn check_prefix(value: &str, prefix_cc: &PrefixCC) -> Option<LintFindingKind> {
+    match split_prefixed_name(value) {
+        Some((prefix, _)) if !prefix.is_empty() && prefix_cc.get(prefix).is_none() => {
+            Some(LintFindingKind::UnresolvedPrefix {
+                prefix: prefix.to_string(),
+                value: value.to_string(),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn split_prefixed_name(value: &str) -> Option<(&str, &str)> {
+    if value.starts_with("http://") || value.starts_with("https://") {
+        None
+    } else {
+        value.split_once(':')
+    }
+}
+
+// Only the datatypes in the XSD namespace are checked
+fn is_known_datatype(datatype: &str, prefix_cc: &PrefixCC) -> bool {
+    let local_name = match datatype.strip_prefix(XSD) {
+        Some(local_name) => local_name,
+        None => match split_prefixed_name(datatype) {
+            Some((prefix, local_name)) if prefix_cc.get(prefix).as_deref() == Some(XSD) => {
+                local_name
+            }
+            _ => return true,
+        },
+    };
+    XSD_DATATYPES.contains(&local_name)
+}
+
+fn extends_cycles(dctap: &DCTap) -> Vec<LintFinding> {
+    let mut graph: HashMap<String, (u64, Vec<String>)> = HashMap::new();
+    for shape in dctap.shapes() {
+        if let Some(shape_id) = shape.shape_id() {
+            let extends = shape.extends().map(|e| e.str().to_string()).collect();
+            graph.insert(shape_id.str().to_string(), (shape_id.line(), extends));
+        }
+    }
+    let mut findings = Vec::new();
+    let mut reported: HashSet<Vec<String>> = HashSet::new();
+    let mut ids: Vec<&String> = graph.keys().collect();
+    ids.sort();
+    for id in ids {
+        let mut path = vec![id.clone()];
+        find_cycles(&graph, &mut path, &mut |cycle| {
+            // The same cycle is found from each of its shapes, it is reported from the smallest one
+            let mut normalized = cycle.to_vec();
+            let min_pos = (0..normalized.len())
+                .min_by_key(|i| &normalized[*i])
+                .unwrap_or(0);
+            normalized.rotate_left(min_pos);
+            if reported.insert(normalized.clone()) {
+                let first = normalized[0].clone();
+                let line = graph.get(&first).map(|(line, _)| *line).unwrap_or(0);
+                let mut cycle = normalized;
+                cycle.push(first.clone());
+                findings.push(LintFinding::new(
+                    line,
+                    Some(first),
+                    LintFindingKind::ExtendsCycle { cycle },
+                ))
+            }
+        });
+    }
+    findings
+}
+
+fn find_cycles(
+    graph: &HashMap<String, (u64, Vec<String>)>,
+    path: &mut Vec<String>,
+    on_cycle: &mut dyn FnMut(&[String]),
+) {
+    let current = path[path.len() - 1].clone();
+    if let Some((_, extends)) = graph.get(&current) {
+        for next in extends {
+            if let Some(pos) = path.iter().position(|id| id == next) {
+                if pos == 0 {
+                    on_cycle(path)
+                }
+            } else {
+                path.push(next.clone());
+                find_cycles(graph, path, on_cycle);
+                path.pop();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TapConfig;
+
+    fn lint_str(data: &str) -> Vec<LintFinding> {
+        let dctap = DCTap::from_reader(data.as_bytes(), &TapConfig::default()).unwrap();
+        lint(&dctap, &PrefixCC::default())
+    }
+
+    #[test]
+    fn test_lint_statements() {
+        let findings = lint_str(
+            "\
+shapeID,propertyID,valueDataType,valueShape
+Person,foaf:name,xsd:strin,
+,foaf:name,xsd:string,
+,foaf:knows,,Company
+,qqq:bar,xsd:string,Person
+",
+        );
+        let kinds: Vec<_> = findings
+            .iter()
+            .map(|f| (f.line(), f.kind().clone()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                (
+                    2,
+                    LintFindingKind::UnknownXsdDatatype {
+                        datatype: "xsd:strin".to_string()
+                    }
+                ),
+                (
+                    3,
+                    LintFindingKind::DuplicatePropertyId {
+                        property_id: "foaf:name".to_string(),
+                        first_line: 2
+                    }
+                ),
+                (
+                    4,
+                    LintFindingKind::UndefinedValueShape {
+                        value_shape: "Company".to_string()
+                    }
+                ),
+                (
+                    5,
+                    LintFindingKind::UnresolvedPrefix {
+                        prefix: "qqq".to_string(),
+                        value: "qqq:bar".to_string()
+                    }
+                ),
+                (
+                    5,
+                    LintFindingKind::DatatypeAndValueShape {
+                        datatype: "xsd:string".to_string(),
+                        value_shape: "Person".to_string()
+                    }
+                ),
+            ]
+        )
+    }
+
+    #[test]
+    fn test_lint_extends_cycle() {
+        let findings = lint_str(
+            "\
+shapeID,propertyID,extendsID
+A,,B
+B,,C
+C,,A
+D,,A
+",
+        );
+        assert_eq!(findings.len(), 1);
+        assert_eq!(
+            findings[0].kind(),
+            &LintFindingKind::ExtendsCycle {
+                cycle: vec![
+                    "A".to_string(),
+                    "B".to_string(),
+                    "C".to_string(),
+                    "A".to_string()
+                ]
+            }
+        )
+    }
+}
@@ -9,6 +9,9 @@ use std::{
 
 use serde::{Deserialize, Serialize};
 
+// Snapshot of the context published by prefix.cc
+const PREFIX_CC_CONTEXT: &str = include_str!("../config/prefix_cc_context.jsonld");
+
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct PrefixCC {
     #[serde(rename = "@context")]
@@ -33,6 +36,13 @@ impl PrefixCC {
     }
 }
 
+/// The default value contains the prefixes bundled with this crate
+impl Default for PrefixCC {
+    fn default() -> Self {
+        PrefixCC::from_str(PREFIX_CC_CONTEXT).expect("Bundled prefix.cc context should be valid")
+    }
+}
+
 impl FromStr for PrefixCC {
     type Err = Box<dyn Error>;
 
@@ -76,4 +86,13 @@ mod tests {
             Some("http://www.w3.org/1999/02/22-rdf-syntax-ns#".to_string())
         )
     }
+
+    #[test]
+    fn test_prefixcc_default() {
+        let prefix_cc = PrefixCC::default();
+        assert_eq!(
+            prefix_cc.get("xsd"),
+            Some("http://www.w3.org/2001/XMLSchema#".to_string())
+        )
+    }
 }
@@ -12,6 +12,16 @@ pub struct TapConfig {
     picklist_delimiter: Option<char>,
     property_placeholders: HashMap<String, PlaceholderResolver>,
     empty_property_placeholder: Option<PlaceholderResolver>,
+
+    /// Alternative names for the DCTAP headers, e.g. `Propiedad: propertyID`
+    header_aliases: Option<HashMap<String, String>>,
+
+    /// Columns which are not part of DCTAP whose values are kept in the statements
+    extension_columns: Option<Vec<String>>,
+
+    /// Columns which are not part of DCTAP whose values are kept in the shapes.
+    /// Their values are taken from the first row of each shape
+    shape_extension_columns: Option<Vec<String>>,
 }
 
 impl TapConfig {
@@ -79,6 +89,50 @@ impl TapConfig {
     pub fn empty_property_placeholder(&self) -> Option<PlaceholderResolver> {
         self.empty_property_placeholder.clone()
     }
+
+    pub fn with_header_aliases(mut self, header_aliases: HashMap<String, String>) -> Self {
+        self.header_aliases = Some(header_aliases);
+        self
+    }
+
+    pub fn with_extension_columns(mut self, columns: Vec<String>) -> Self {
+        self.extension_columns = Some(columns);
+        self
+    }
+
+    pub fn with_shape_extension_columns(mut self, columns: Vec<String>) -> Self {
+        self.shape_extension_columns = Some(columns);
+        self
+    }
+
+    /// Returns the DCTAP header that corresponds to a header alias ignoring case
+    pub fn header_alias(&self, header: &str) -> Option<&str> {
+        self.header_aliases.as_ref().and_then(|aliases| {
+            aliases
+                .iter()
+                .find(|(alias, _)| alias.to_uppercase() == header.to_uppercase())
+                .map(|(_, name)| name.as_str())
+        })
+    }
+
+    /// Returns the name of the extension column declared for a header ignoring case
+    pub fn extension_column(&self, header: &str) -> Option<&str> {
+        find_column(&self.extension_columns, header)
+    }
+
+    /// Returns the name of the shape extension column declared for a header ignoring case
+    pub fn shape_extension_column(&self, header: &str) -> Option<&str> {
+        find_column(&self.shape_extension_columns, header)
+    }
+}
+
+fn find_column<'a>(columns: &'a Option<Vec<String>>, header: &str) -> Option<&'a str> {
+    columns.as_ref().and_then(|columns| {
+        columns
+            .iter()
+            .find(|column| column.to_uppercase() == header.to_uppercase())
+            .map(|column| column.as_str())
+    })
 }
 
 #[cfg(test)]
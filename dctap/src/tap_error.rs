@@ -42,6 +42,15 @@ pub enum TapError {
     #[error("Error reading config file from path {path}: {error}")]
     TapConfigFromPathError { path: String, error: io::Error },
 
+    #[error("Error writing DCTAP: {error}")]
+    WritingError { error: io::Error },
+
+    #[error("Error generating XLSX: {err}")]
+    XlsxError {
+        #[from]
+        err: rust_xlsxwriter::XlsxError,
+    },
+
     #[error("Error reading config file from path {path}: {error}")]
     TapConfigYamlError {
         path: String,
@@ -1,4 +1,5 @@
 use crate::tap_error::Result;
+use crate::{TapConfig, TapReaderWarning};
 use csv::StringRecord;
 use tracing::debug;
 
@@ -19,6 +20,10 @@ pub struct TapHeaders {
     // The following headers are not part of DCTAP standard but they useful when there are cases of inheritance
     extends_id: Option<usize>,
     extends_label: Option<usize>,
+    // Columns declared as extensions in the config with their positions
+    extensions: Vec<(String, usize)>,
+    shape_extensions: Vec<(String, usize)>,
+    unknown_headers: Vec<(String, usize)>,
 }
 
 impl TapHeaders {
@@ -26,7 +31,7 @@ impl TapHeaders {
         TapHeaders::default()
     }
 
-    pub(crate) fn from_record(record: &StringRecord) -> Result<TapHeaders> {
+    pub(crate) fn from_record(record: &StringRecord, config: &TapConfig) -> Result<TapHeaders> {
         let mut shape_id = None;
         let mut shape_label = None;
         let mut property_id = None;
@@ -41,9 +46,13 @@ impl TapHeaders {
         let mut note = None;
         let mut extends_id = None;
         let mut extends_label = None;
+        let mut extensions = Vec::new();
+        let mut shape_extensions = Vec::new();
+        let mut unknown_headers = Vec::new();
 
         for (idx, field) in record.iter().enumerate() {
-            match clean(field).as_str() {
+            let header = config.header_alias(field).unwrap_or(field);
+            match clean(header).as_str() {
                 "SHAPEID" => shape_id = Some(idx),
                 "PROPERTYID" => property_id = Some(idx),
                 "PROPERTYLABEL" => property_label = Some(idx),
@@ -59,7 +68,14 @@ impl TapHeaders {
                 "EXTENDSID" => extends_id = Some(idx),
                 "EXTENDSLABEL" => extends_label = Some(idx),
                 _ => {
-                    debug!("Unknown field reading headers: {field}")
+                    if let Some(column) = config.extension_column(field) {
+                        extensions.push((column.to_string(), idx))
+                    } else if let Some(column) = config.shape_extension_column(field) {
+                        shape_extensions.push((column.to_string(), idx))
+                    } else {
+                        debug!("Unknown field reading headers: {field}");
+                        unknown_headers.push((field.to_string(), idx))
+                    }
                 }
             }
         }
@@ -78,9 +94,22 @@ impl TapHeaders {
             note,
             extends_id,
             extends_label,
+            extensions,
+            shape_extensions,
+            unknown_headers,
         })
     }
 
+    /// Warnings about the headers that have not been recognized
+    pub(crate) fn warnings(&self) -> impl Iterator<Item = TapReaderWarning> + '_ {
+        self.unknown_headers
+            .iter()
+            .map(|(header, idx)| TapReaderWarning::UnknownHeader {
+                header: header.clone(),
+                column: idx + 1,
+            })
+    }
+
     pub fn shape_id(&self, rcd: &StringRecord) -> Option<String> {
         self.shape_id.and_then(|idx| get_str_from_rcd(rcd, idx))
     }
@@ -132,6 +161,16 @@ impl TapHeaders {
         self.extends_label
             .and_then(|idx| get_str_from_rcd(rcd, idx))
     }
+
+    /// Non-empty values of the extension columns
+    pub fn extensions(&self, rcd: &StringRecord) -> Vec<(String, String)> {
+        get_extensions_from_rcd(rcd, &self.extensions)
+    }
+
+    /// Non-empty values of the shape extension columns
+    pub fn shape_extensions(&self, rcd: &StringRecord) -> Vec<(String, String)> {
+        get_extensions_from_rcd(rcd, &self.shape_extensions)
+    }
 }
 
 fn clean(str: &str) -> String {
@@ -141,3 +180,17 @@ fn clean(str: &str) -> String {
 fn get_str_from_rcd(rcd: &StringRecord, idx: usize) -> Option<String> {
     rcd.get(idx).map(|str| str.to_string())
 }
+
+fn get_extensions_from_rcd(
+    rcd: &StringRecord,
+    columns: &[(String, usize)],
+) -> Vec<(String, String)> {
+    columns
+        .iter()
+        .filter_map(|(name, idx)| {
+            get_str_from_rcd(rcd, *idx)
+                .filter(|value| !value.trim().is_empty())
+                .map(|value| (name.clone(), value))
+        })
+        .collect()
+}
@@ -42,9 +42,11 @@ impl<R: io::Read> TapReader<R> {
             if let Some(shape_id) = &maybe_shape_id {
                 self.state.current_shape().set_shape_id(shape_id);
                 self.state.current_shape().set_start_line(pos.line());
-                self.state.current_shape().reset_extends()
+                self.state.current_shape().reset_extends();
+                self.state.current_shape().reset_extensions()
             }
             self.read_shape_label(&record)?;
+            self.read_shape_extensions(&record);
             self.read_extends_id(&record, pos.line());
             self.read_extends_label(&record, &pos);
             let maybe_statement = self.record2statement(&record, &pos)?;
@@ -207,6 +209,7 @@ impl<R: io::Read> TapReader<R> {
             self.read_value_shape(&mut statement, rcd, pos.line());
             self.read_value_constraint(&mut statement, rcd, pos)?;
             self.read_note(&mut statement, rcd);
+            self.read_extensions(&mut statement, rcd);
             Ok(Some(statement))
         } else {
             Ok(None)
@@ -230,6 +233,18 @@ impl<R: io::Read> TapReader<R> {
         }
     }
 
+    fn read_extensions(&self, statement: &mut TapStatement, rcd: &StringRecord) {
+        for (column, value) in self.state.headers().extensions(rcd) {
+            statement.add_extension(&column, &value)
+        }
+    }
+
+    fn read_shape_extensions(&mut self, rcd: &StringRecord) {
+        for (column, value) in self.state.headers().shape_extensions(rcd) {
+            self.state.current_shape().add_extension(&column, &value)
+        }
+    }
+
     fn read_extends_id(&mut self, rcd: &StringRecord, line: u64) {
         if let Some(str) = self.state.headers().extends_id(rcd) {
             if let Some(clean_str) = strip_whitespace(&str) {
@@ -496,6 +511,7 @@ mod tests {
     use crate::{TapReaderBuilder, TapShape};
 
     use super::*;
+    use std::collections::HashMap;
     use tracing_test::traced_test;
 
     #[test]
@@ -569,4 +585,35 @@ Company,CompanyLabel,founder,FounderLabel
         let next_shape2 = tap_reader.shapes().next().unwrap().unwrap();
         assert_eq!(next_shape2, expected_shape2);
     }
+
+    #[test]
+    fn test_header_aliases_and_extensions() {
+        let data = "\
+Forma,Propiedad,severity,sh:message,level,comments
+Person,knows,Warning,Should know someone,gold,unused
+";
+        let config = TapConfig::default()
+            .with_header_aliases(HashMap::from([
+                ("forma".to_string(), "shapeID".to_string()),
+                ("Propiedad".to_string(), "propertyID".to_string()),
+            ]))
+            .with_extension_columns(vec!["severity".to_string(), "sh:message".to_string()])
+            .with_shape_extension_columns(vec!["level".to_string()]);
+        let mut tap_reader = TapReaderBuilder::from_reader(data.as_bytes(), &config).unwrap();
+        let mut expected_shape = TapShape::new(2);
+        expected_shape.set_shape_id(&ShapeId::new("Person", 2));
+        expected_shape.add_extension("level", "gold");
+        let mut statement = TapStatement::new(PropertyId::new("knows", 2));
+        statement.add_extension("severity", "Warning");
+        statement.add_extension("sh:message", "Should know someone");
+        expected_shape.add_statement(statement);
+        let next_shape = tap_reader.shapes().next().unwrap().unwrap();
+        assert_eq!(next_shape, expected_shape);
+        let warnings: Vec<_> = tap_reader.warnings().collect();
+        assert_eq!(warnings.len(), 1);
+        assert!(matches!(
+            warnings[0],
+            TapReaderWarning::UnknownHeader { header, column: 6 } if header == "comments"
+        ));
+    }
 }
@@ -67,8 +67,12 @@ impl TapReaderBuilder {
             .flexible(config.flexible())
             .from_path(path)?;
         let rcd_headers = reader.headers()?;
-        let headers = TapHeaders::from_record(rcd_headers)?;
-        let state = TapReaderState::new().with_headers(headers);
+        let headers = TapHeaders::from_record(rcd_headers, config)?;
+        let mut state = TapReaderState::new();
+        for warning in headers.warnings() {
+            state.add_warning(warning)
+        }
+        let state = state.with_headers(headers);
         Ok(TapReader::new(reader, state, config))
     }
 
@@ -79,8 +83,12 @@ impl TapReaderBuilder {
             .flexible(config.flexible())
             .from_reader(rdr);
         let rcd_headers = reader.headers()?;
-        let headers = TapHeaders::from_record(rcd_headers)?;
-        let state = TapReaderState::new().with_headers(headers);
+        let headers = TapHeaders::from_record(rcd_headers, config)?;
+        let mut state = TapReaderState::new();
+        for warning in headers.warnings() {
+            state.add_warning(warning)
+        }
+        let state = state.with_headers(headers);
         Ok(TapReader::new(reader, state, config))
     }
 }
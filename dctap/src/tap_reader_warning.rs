@@ -8,4 +8,7 @@ pub enum TapReaderWarning {
 
     #[error("Extends label found: {label} without extends ID at line {line}")]
     ExtendsLabelWithoutExtendsId { label: String, line: u64 },
+
+    #[error("Unknown header {header} at column {column}. It has been ignored")]
+    UnknownHeader { header: String, column: usize },
 }
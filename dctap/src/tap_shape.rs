@@ -2,6 +2,7 @@ use std::fmt::Display;
 
 use crate::{tap_statement::TapStatement, ExtendsId};
 use crate::{ShapeId, TapReaderWarning};
+use indexmap::IndexMap;
 use serde_derive::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, Default, PartialEq, Clone)]
@@ -17,6 +18,10 @@ pub struct TapShape {
     extends: Vec<ExtendsId>,
 
     start_line: u64,
+
+    // Values of the columns declared as shape extensions in the config
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    extensions: IndexMap<String, String>,
 }
 
 impl TapShape {
@@ -27,6 +32,7 @@ impl TapShape {
             statements: Vec::new(),
             start_line: line,
             extends: Vec::new(),
+            extensions: IndexMap::new(),
         }
     }
 
@@ -74,6 +80,19 @@ impl TapShape {
         self.extends.clear();
     }
 
+    pub fn add_extension(&mut self, column: &str, value: &str) {
+        self.extensions
+            .insert(column.to_string(), value.to_string());
+    }
+
+    pub fn reset_extensions(&mut self) {
+        self.extensions.clear();
+    }
+
+    pub fn extensions(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.extensions.iter()
+    }
+
     pub fn add_statement(&mut self, statement: TapStatement) {
         self.statements.push(statement.clone());
     }
@@ -1,3 +1,4 @@
+use indexmap::IndexMap;
 use serde_derive::{Deserialize, Serialize};
 use std::fmt::Display;
 
@@ -31,6 +32,10 @@ pub struct TapStatement {
 
     #[serde(rename = "note", skip_serializing_if = "Option::is_none")]
     note: Option<String>,
+
+    // Values of the columns declared as extensions in the config
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    extensions: IndexMap<String, String>,
 }
 
 impl TapStatement {
@@ -91,12 +96,27 @@ impl TapStatement {
     pub fn value_shape(&self) -> Option<ShapeId> {
         self.value_shape.clone()
     }
+    pub fn value_nodetype(&self) -> Option<NodeType> {
+        self.value_nodetype.clone()
+    }
     pub fn value_constraint(&self) -> &Option<ValueConstraint> {
         &self.value_constraint
     }
     pub fn property_label(&self) -> &Option<String> {
         &self.property_label
     }
+    pub fn note(&self) -> &Option<String> {
+        &self.note
+    }
+
+    pub fn add_extension(&mut self, column: &str, value: &str) {
+        self.extensions
+            .insert(column.to_string(), value.to_string());
+    }
+
+    pub fn extensions(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.extensions.iter()
+    }
 }
 
 impl Display for TapStatement {
@@ -0,0 +1,336 @@
+use crate::tap_error::Result;
+use crate::{
+    BasicNodeType, DCTap, NodeType, TapConfig, TapError, TapShape, TapStatement, Value,
+    ValueConstraint,
+};
+use csv::WriterBuilder;
+use indexmap::IndexSet;
+use rust_xlsxwriter::Workbook;
+use std::io;
+
+const HEADERS: [&str; 12] = [
+    "shapeID",
+    "shapeLabel",
+    "propertyID",
+    "propertyLabel",
+    "mandatory",
+    "repeatable",
+    "valueNodeType",
+    "valueDataType",
+    "valueConstraint",
+    "valueConstraintType",
+    "valueShape",
+    "note",
+];
+
+const EXTENDS_HEADERS: [&str; 2] = ["extendsID", "extendsLabel"];
+
+/// Writes DCTAP profiles as tables
+///
+/// The generated tables use the same headers that are recognized by the `TapReader`,
+/// so the output can be read back with the same `TapConfig`
+pub struct TapWriter {
+    config: TapConfig,
+}
+
+impl TapWriter {
+    pub fn new(config: &TapConfig) -> TapWriter {
+        TapWriter {
+            config: config.clone(),
+        }
+    }
+
+    /// Write a DCTAP profile as CSV honouring the delimiter, quote and picklist delimiter of the config
+    ///
+    /// # Example
+    /// ```
+    /// use dctap::{DCTap, TapConfig, TapWriter};
+    ///
+    /// let data = "shapeId,propertyId\nPerson,name\n";
+    /// let dctap = DCTap::from_reader(data.as_bytes(), &TapConfig::default()).unwrap();
+    /// let mut output = Vec::new();
+    /// TapWriter::new(&TapConfig::default()).write_csv(&dctap, &mut output).unwrap();
+    /// assert!(String::from_utf8(output).unwrap().starts_with("shapeID,shapeLabel,propertyID"));
+    /// ```
+    pub fn write_csv<W: io::Write>(&self, dctap: &DCTap, writer: W) -> Result<()> {
+        let mut csv_writer = WriterBuilder::new()
+            .delimiter(self.config.delimiter())
+            .quote(self.config.quote())
+            .from_writer(writer);
+        for row in self.rows(dctap) {
+            csv_writer.write_record(&row)?;
+        }
+        csv_writer
+            .flush()
+            .map_err(|e| TapError::WritingError { error: e })?;
+        Ok(())
+    }
+
+    /// Write a DCTAP profile as an XLSX workbook with a single worksheet
+    pub fn write_xlsx<W: io::Write>(&self, dctap: &DCTap, mut writer: W) -> Result<()> {
+        let mut workbook = Workbook::new();
+        let worksheet = workbook.add_worksheet();
+        for (row_idx, row) in self.rows(dctap).iter().enumerate() {
+            for (col_idx, value) in row.iter().enumerate() {
+                if !value.is_empty() {
+                    worksheet.write_string(row_idx as u32, col_idx as u16, value)?;
+                }
+            }
+        }
+        let buffer = workbook.save_to_buffer()?;
+        writer
+            .write_all(&buffer)
+            .map_err(|e| TapError::WritingError { error: e })?;
+        Ok(())
+    }
+
+    fn rows(&self, dctap: &DCTap) -> Vec<Vec<String>> {
+        let with_extends = dctap.shapes().any(|shape| shape.has_extends());
+        let mut headers: Vec<String> = HEADERS.iter().map(|h| h.to_string()).collect();
+        if with_extends {
+            headers.extend(EXTENDS_HEADERS.iter().map(|h| h.to_string()));
+        }
+        let columns = ExtensionColumns::from_dctap(dctap);
+        headers.extend(columns.shape.iter().cloned());
+        headers.extend(columns.statement.iter().cloned());
+        let mut rows = vec![headers];
+        for shape in dctap.shapes() {
+            let mut statements = shape.statements().peekable();
+            if statements.peek().is_none() {
+                rows.push(self.row(Some(shape), None, with_extends, &columns));
+            } else {
+                let mut first = true;
+                for statement in statements {
+                    let shape = if first { Some(shape) } else { None };
+                    rows.push(self.row(shape, Some(statement), with_extends, &columns));
+                    first = false;
+                }
+            }
+        }
+        rows
+    }
+
+    // Generates one row. The shape columns are only filled in the first row of each shape
+    fn row(
+        &self,
+        shape: Option<&TapShape>,
+        statement: Option<&TapStatement>,
+        with_extends: bool,
+        columns: &ExtensionColumns,
+    ) -> Vec<String> {
+        let mut row = Vec::new();
+        row.push(
+            shape
+                .and_then(|s| s.shape_id())
+                .map(|id| id.str().to_string())
+                .unwrap_or_default(),
+        );
+        row.push(shape.and_then(|s| s.shape_label()).unwrap_or_default());
+        match statement {
+            Some(statement) => {
+                row.push(statement.property_id().str().to_string());
+                row.push(statement.property_label().clone().unwrap_or_default());
+                row.push(show_boolean(statement.mandatory()));
+                row.push(show_boolean(statement.repeatable()));
+                row.push(
+                    statement
+                        .value_nodetype()
+                        .map(|nt| show_node_type(&nt))
+                        .unwrap_or_default(),
+                );
+                row.push(
+                    statement
+                        .value_datatype()
+                        .map(|dt| dt.str().to_string())
+                        .unwrap_or_default(),
+                );
+                let (value_constraint, value_constraint_type) = match statement.value_constraint() {
+                    Some(vc) => self.show_value_constraint(vc),
+                    None => (String::new(), String::new()),
+                };
+                row.push(value_constraint);
+                row.push(value_constraint_type);
+                row.push(
+                    statement
+                        .value_shape()
+                        .map(|s| s.str().to_string())
+                        .unwrap_or_default(),
+                );
+                row.push(statement.note().clone().unwrap_or_default());
+            }
+            None => row.extend(std::iter::repeat_n(String::new(), HEADERS.len() - 2)),
+        }
+        if with_extends {
+            // The reader only takes into account the extends declared in the first row of a shape,
+            // so the other extends are dropped, which converters report as a warning
+            let extends = shape.and_then(|s| s.extends().next());
+            row.push(extends.map(|e| e.str().to_string()).unwrap_or_default());
+            row.push(extends.and_then(|e| e.label()).unwrap_or_default());
+        }
+        for column in columns.shape.iter() {
+            row.push(
+                shape
+                    .and_then(|s| s.extensions().find(|(c, _)| *c == column))
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default(),
+            )
+        }
+        for column in columns.statement.iter() {
+            row.push(
+                statement
+                    .and_then(|s| s.extensions().find(|(c, _)| *c == column))
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default(),
+            )
+        }
+        row
+    }
+
+    fn show_value_constraint(&self, value_constraint: &ValueConstraint) -> (String, String) {
+        match value_constraint {
+            ValueConstraint::PickList(values) => {
+                let delimiter = self.config.picklist_delimiter().to_string();
+                let str = values
+                    .iter()
+                    .map(show_value)
+                    .collect::<Vec<_>>()
+                    .join(delimiter.as_str());
+                (str, "picklist".to_string())
+            }
+            ValueConstraint::Pattern(p) => (p.clone(), "pattern".to_string()),
+            ValueConstraint::IRIStem(iri) => (iri.to_string(), "IRIstem".to_string()),
+            ValueConstraint::LanguageTag(tag) => (tag.clone(), "languageTag".to_string()),
+            ValueConstraint::MinLength(n) => (n.to_string(), "minLength".to_string()),
+            ValueConstraint::MaxLength(n) => (n.to_string(), "maxLength".to_string()),
+            ValueConstraint::MinExclusive(n) => (n.to_string(), "minExclusive".to_string()),
+            ValueConstraint::MinInclusive(n) => (n.to_string(), "minInclusive".to_string()),
+            ValueConstraint::MaxExclusive(n) => (n.to_string(), "maxExclusive".to_string()),
+            ValueConstraint::MaxInclusive(n) => (n.to_string(), "maxInclusive".to_string()),
+        }
+    }
+}
+
+// Names of the extension columns in order of appearance
+struct ExtensionColumns {
+    shape: IndexSet<String>,
+    statement: IndexSet<String>,
+}
+
+impl ExtensionColumns {
+    fn from_dctap(dctap: &DCTap) -> ExtensionColumns {
+        let mut shape = IndexSet::new();
+        let mut statement = IndexSet::new();
+        for tap_shape in dctap.shapes() {
+            shape.extend(tap_shape.extensions().map(|(column, _)| column.clone()));
+            for tap_statement in tap_shape.statements() {
+                statement.extend(tap_statement.extensions().map(|(column, _)| column.clone()));
+            }
+        }
+        ExtensionColumns { shape, statement }
+    }
+}
+
+fn show_boolean(value: Option<bool>) -> String {
+    match value {
+        None => String::new(),
+        Some(true) => "TRUE".to_string(),
+        Some(false) => "FALSE".to_string(),
+    }
+}
+
+// The names must be the ones accepted by the reader, which splits node types by spaces
+fn show_node_type(node_type: &NodeType) -> String {
+    match node_type {
+        NodeType::Basic(b) => show_basic_node_type(b).to_string(),
+        NodeType::Or(bs) => bs
+            .iter()
+            .map(show_basic_node_type)
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+fn show_basic_node_type(node_type: &BasicNodeType) -> &'static str {
+    match node_type {
+        BasicNodeType::IRI => "IRI",
+        BasicNodeType::BNode => "BNODE",
+        BasicNodeType::Literal => "LITERAL",
+    }
+}
+
+fn show_value(value: &Value) -> String {
+    match value {
+        Value::Iri(iri) => iri.to_string(),
+        Value::Str(s) => s.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_csv_round_trip() {
+        let data = "\
+shapeID,shapeLabel,propertyID,propertyLabel,mandatory,repeatable,valueNodeType,valueDataType,valueConstraint,valueConstraintType,valueShape,note
+Person,Person label,name,Name,TRUE,FALSE,,xsd:string,,,,A note
+,,knows,,FALSE,TRUE,IRI,,,,Person,
+,,status,,FALSE,FALSE,,,active|inactive,picklist,,
+Company,,,,,,,,,,,
+";
+        let dctap = DCTap::from_reader(data.as_bytes(), &TapConfig::default()).unwrap();
+        let mut output = Vec::new();
+        TapWriter::new(&TapConfig::default())
+            .write_csv(&dctap, &mut output)
+            .unwrap();
+        let written = String::from_utf8(output).unwrap();
+        assert_eq!(written, data);
+    }
+
+    #[test]
+    fn test_csv_config_delimiters() {
+        let data = "\
+shapeID;propertyID;valueConstraint;valueConstraintType
+Person;status;active,inactive;picklist
+";
+        let config: TapConfig = serde_yml::from_str(
+            "delimiter: ';'\npicklist_delimiter: ','\nproperty_placeholders: {}\n",
+        )
+        .unwrap();
+        let dctap = DCTap::from_reader(data.as_bytes(), &config).unwrap();
+        let mut output = Vec::new();
+        TapWriter::new(&config)
+            .write_csv(&dctap, &mut output)
+            .unwrap();
+        let written = String::from_utf8(output).unwrap();
+        assert!(written.contains(";status;"));
+        assert!(written.contains(";active,inactive;picklist;"));
+        let dctap_read = DCTap::from_reader(written.as_bytes(), &config).unwrap();
+        let statement = dctap_read
+            .shapes()
+            .next()
+            .unwrap()
+            .statements()
+            .next()
+            .unwrap();
+        assert_eq!(
+            statement.value_constraint(),
+            &Some(ValueConstraint::picklist(vec![
+                Value::new("active"),
+                Value::new("inactive")
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_xlsx_signature() {
+        let data = "shapeID,propertyID\nPerson,name\n";
+        let dctap = DCTap::from_reader(data.as_bytes(), &TapConfig::default()).unwrap();
+        let mut output = Vec::new();
+        TapWriter::new(&TapConfig::default())
+            .write_xlsx(&dctap, &mut output)
+            .unwrap();
+        // XLSX files are ZIP archives
+        assert_eq!(&output[0..2], b"PK");
+    }
+}
@@ -24,7 +24,9 @@ pub fn dctap2shex(input: &str, py: Python<'_>) -> PyResult<String> {
             .convert(&dctap)
             .map_err(|e| PyValueError::new_err(format!("Error converting DCTAP to ShEx: {e}")))?;
         let formatter = ShExFormatter::default().without_colors();
-        let str = formatter.format_schema(&schema);
+        let str = formatter
+            .format_schema(&schema)
+            .map_err(|e| PyValueError::new_err(format!("Error formatting ShEx: {e}")))?;
         Ok(str)
     })
 }
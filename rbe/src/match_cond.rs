@@ -55,7 +55,7 @@ where
 
     pub fn simple(
         name: &str,
-        cond: impl Fn(&V) -> Result<Pending<V, R>, RbeError<K, V, R>> + Clone + 'static,
+        cond: impl Fn(&V) -> Result<Pending<V, R>, RbeError<K, V, R>> + Clone + Send + Sync + 'static,
     ) -> Self {
         MatchCond::single(SingleCond::new().with_name(name).with_cond(cond))
     }
@@ -120,7 +120,8 @@ where
 /// capture some values in the condition closure.
 /// This pattern is inspired by the answer in this thread:
 /// https://users.rust-lang.org/t/how-to-clone-a-boxed-closure/31035
-trait Cond<K, V, R>
+/// Conditions are `Send` and `Sync` so compiled schemas can be shared between threads.
+trait Cond<K, V, R>: Send + Sync
 where
     K: Key,
     V: Value,
@@ -135,7 +136,7 @@ where
     K: Key,
     V: Value,
     R: Ref,
-    F: 'static + Fn(&V) -> Result<Pending<V, R>, RbeError<K, V, R>> + Clone,
+    F: 'static + Fn(&V) -> Result<Pending<V, R>, RbeError<K, V, R>> + Clone + Send + Sync,
 {
     fn clone_box(&self) -> Box<dyn Cond<K, V, R>> {
         Box::new(self.clone())
@@ -223,7 +224,7 @@ where
 
     pub fn with_cond(
         mut self,
-        cond: impl Fn(&V) -> Result<Pending<V, R>, RbeError<K, V, R>> + Clone + 'static,
+        cond: impl Fn(&V) -> Result<Pending<V, R>, RbeError<K, V, R>> + Clone + Send + Sync + 'static,
     ) -> Self {
         self.cond.push(Box::new(cond));
         self
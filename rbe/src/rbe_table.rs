@@ -64,6 +64,17 @@ where
         c
     }
 
+    /// Components associated with the key `k` and their matching conditions
+    pub fn components_for_key(&self, k: &K) -> Vec<(Component, &MatchCond<K, V, R>)> {
+        match self.key_components.get(k) {
+            Some(components) => components
+                .iter()
+                .filter_map(|c| self.component_cond.get(c).map(|cond| (*c, cond)))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
     pub fn with_rbe(&mut self, rbe: Rbe<Component>) {
         self.rbe = rbe;
     }
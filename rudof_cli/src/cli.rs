@@ -1,7 +1,9 @@
 use crate::input_spec::InputSpec;
+use crate::server::DEFAULT_CACHE_SIZE;
 use crate::{InputConvertFormat, OutputConvertFormat};
 use clap::{Parser, Subcommand, ValueEnum};
-use shacl_validation::validate::ShaclValidationMode;
+use serde_derive::{Deserialize, Serialize};
+use shacl_validation::validate::{Entailment, ShaclValidationMode};
 use srdf::{RDFFormat, ReaderMode};
 use std::fmt::Display;
 use std::{fmt::Formatter, path::PathBuf};
@@ -89,6 +91,18 @@ pub enum Command {
         #[arg(long = "statistics", default_value_t = false)]
         show_statistics: bool,
 
+        /// Format a ShExC schema keeping its comments
+        #[arg(long = "fmt", default_value_t = false)]
+        fmt: bool,
+
+        /// Indentation used by the ShExC formatter
+        #[arg(long = "indent", value_name = "Number of spaces", default_value_t = 4)]
+        indent: usize,
+
+        /// Maximum line width used by the ShExC formatter
+        #[arg(long = "width", value_name = "Line width", default_value_t = 100)]
+        width: usize,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -114,6 +128,8 @@ pub enum Command {
     },
 
     /// Validate RDF data using ShEx or SHACL
+    ///
+    /// Exits with 0 if the data conforms, 1 if there is an error and 2 if some node doesn't conform
     Validate {
         #[clap(value_parser = clap::value_parser!(InputSpec))]
         data: Vec<InputSpec>,
@@ -145,6 +161,23 @@ pub enum Command {
         )]
         shapemap_format: ShapeMapFormat,
 
+        /// Format of the result shapemap, only used in ShEx validation
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Result shapemap format",
+            default_value_t = ResultShapeMapFormat::Details
+        )]
+        result_format: ResultShapeMapFormat,
+
+        /// How to obtain the nodes and shapes to validate with ShEx
+        #[arg(
+            long = "shex-mode",
+            value_name = "ShEx validation mode",
+            default_value_t = ShExValidationMode::ShapeMap
+        )]
+        shex_mode: ShExValidationMode,
+
         #[arg(short = 'n', long = "node")]
         node: Option<String>,
 
@@ -167,6 +200,15 @@ pub enum Command {
         #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
         endpoint: Option<String>,
 
+        /// Named graph of the data that is validated, by default the default graph
+        #[arg(long = "graph", value_name = "Named graph IRI")]
+        graph: Option<String>,
+
+        /// Folder of a persistent store with the RDF data, which is created if it doesn't exist.
+        /// The data given with --data is loaded into the store before validating it
+        #[arg(long = "store", value_name = "Store folder")]
+        store: Option<PathBuf>,
+
         #[arg(
             long = "max-steps",
             value_name = "max steps to run",
@@ -184,6 +226,15 @@ pub enum Command {
         )]
         shacl_validation_mode: ShaclValidationMode,
 
+        /// Entailment regime applied to the data before the SHACL validation
+        #[arg(
+            long = "entailment",
+            value_name = "Entailment regime",
+            default_value_t = Entailment::None,
+            value_enum
+        )]
+        entailment: Entailment,
+
         /// RDF Reader mode
         #[arg(
             long = "reader-mode",
@@ -193,6 +244,18 @@ pub enum Command {
         )]
         reader_mode: RDFReaderMode,
 
+        /// Config file path, if unset it assumes default config
+        #[arg(short = 'c', long = "config-file", value_name = "Config file name")]
+        config: Option<PathBuf>,
+
+        /// Show a summary of the validation with counts per shape and severity
+        #[arg(long = "summary", value_name = "Summary format")]
+        summary: Option<SummaryFormat>,
+
+        /// Write a JUnit XML report of the validation to a file
+        #[arg(long = "junit", value_name = "JUnit XML file name")]
+        junit: Option<PathBuf>,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -209,6 +272,8 @@ pub enum Command {
     },
 
     /// Validate RDF using ShEx schemas
+    ///
+    /// Exits with 0 if the data conforms, 1 if there is an error and 2 if some node doesn't conform
     ShexValidate {
         #[clap(value_parser = clap::value_parser!(InputSpec))]
         data: Vec<InputSpec>,
@@ -238,6 +303,131 @@ pub enum Command {
         )]
         shapemap_format: ShapeMapFormat,
 
+        /// Format of the result shapemap, only used in ShEx validation
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Result shapemap format",
+            default_value_t = ResultShapeMapFormat::Details
+        )]
+        result_format: ResultShapeMapFormat,
+
+        /// How to obtain the nodes and shapes to validate with ShEx
+        #[arg(
+            long = "shex-mode",
+            value_name = "ShEx validation mode",
+            default_value_t = ShExValidationMode::ShapeMap
+        )]
+        shex_mode: ShExValidationMode,
+
+        #[arg(short = 'n', long = "node")]
+        node: Option<String>,
+
+        #[arg(
+            short = 'l',
+            long = "shape-label",
+            value_name = "shape label (default = START)",
+            group = "node_shape"
+        )]
+        shape: Option<String>,
+
+        #[arg(
+            short = 't',
+            long = "data-format",
+            value_name = "RDF Data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        data_format: DataFormat,
+
+        /// RDF Reader mode
+        #[arg(
+            long = "reader-mode",
+            value_name = "RDF Reader mode",
+            default_value_t = RDFReaderMode::default(),
+            value_enum
+        )]
+        reader_mode: RDFReaderMode,
+
+        #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
+        endpoint: Option<String>,
+
+        /// Named graph of the data that is validated, by default the default graph
+        #[arg(long = "graph", value_name = "Named graph IRI")]
+        graph: Option<String>,
+
+        /// Folder of a persistent store with the RDF data, which is created if it doesn't exist.
+        /// The data given with --data is loaded into the store before validating it
+        #[arg(long = "store", value_name = "Store folder")]
+        store: Option<PathBuf>,
+
+        /// Show a summary of the validation with counts per shape and severity
+        #[arg(long = "summary", value_name = "Summary format")]
+        summary: Option<SummaryFormat>,
+
+        /// Write a JUnit XML report of the validation to a file
+        #[arg(long = "junit", value_name = "JUnit XML file name")]
+        junit: Option<PathBuf>,
+
+        #[arg(
+            short = 'o',
+            long = "output-file",
+            value_name = "Output file name, default = terminal"
+        )]
+        output: Option<PathBuf>,
+
+        /// Config file path, if unset it assumes default config
+        #[arg(short = 'c', long = "config-file", value_name = "Config file name")]
+        config: Option<PathBuf>,
+
+        #[arg(
+            long = "force-overwrite",
+            value_name = "Force overwrite mode",
+            default_value_t = false
+        )]
+        force_overwrite: bool,
+    },
+
+    /// Extract the triples of RDF data that conform to some ShEx shapes
+    ///
+    /// The nodes are validated and only the triples matched by the shapes they conform to are kept,
+    /// following the references to other shapes
+    Extract {
+        #[clap(value_parser = clap::value_parser!(InputSpec))]
+        data: Vec<InputSpec>,
+
+        #[arg(
+            short = 's',
+            long = "schema",
+            value_name = "Schema file name, URI or -"
+        )]
+        schema: InputSpec,
+
+        #[arg(
+            short = 'f',
+            long = "schema-format",
+            value_name = "Schema format",
+            default_value_t = ShExFormat::ShExC
+        )]
+        schema_format: ShExFormat,
+
+        #[arg(short = 'm', long = "shapemap", value_name = "ShapeMap file name")]
+        shapemap: Option<PathBuf>,
+
+        #[arg(
+            long = "shapemap-format",
+            value_name = "ShapeMap format",
+            default_value_t = ShapeMapFormat::Compact,
+        )]
+        shapemap_format: ShapeMapFormat,
+
+        /// How to obtain the nodes and shapes to validate with ShEx
+        #[arg(
+            long = "shex-mode",
+            value_name = "ShEx validation mode",
+            default_value_t = ShExValidationMode::ShapeMap
+        )]
+        shex_mode: ShExValidationMode,
+
         #[arg(short = 'n', long = "node")]
         node: Option<String>,
 
@@ -269,6 +459,138 @@ pub enum Command {
         #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
         endpoint: Option<String>,
 
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Ouput RDF data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        result_format: DataFormat,
+
+        /// Write the extracted data as JSON-LD framed by the shapes of the schema instead of using the result format
+        #[arg(long = "framed", default_value_t = false)]
+        framed: bool,
+
+        #[arg(
+            short = 'o',
+            long = "output-file",
+            value_name = "Output file name, default = terminal"
+        )]
+        output: Option<PathBuf>,
+
+        /// Config file path, if unset it assumes default config
+        #[arg(short = 'c', long = "config-file", value_name = "Config file name")]
+        config: Option<PathBuf>,
+
+        #[arg(
+            long = "force-overwrite",
+            value_name = "Force overwrite mode",
+            default_value_t = false
+        )]
+        force_overwrite: bool,
+    },
+
+    /// Transform RDF data described by a ShEx schema into data described by another ShEx schema
+    ///
+    /// The data is validated with the source schema and the matched values are materialised
+    /// following a mapping between the shapes and predicates of both schemas
+    Transform {
+        #[clap(value_parser = clap::value_parser!(InputSpec))]
+        data: Vec<InputSpec>,
+
+        #[arg(
+            short = 's',
+            long = "schema",
+            value_name = "Source schema file name, URI or -"
+        )]
+        schema: InputSpec,
+
+        #[arg(
+            short = 'f',
+            long = "schema-format",
+            value_name = "Source schema format",
+            default_value_t = ShExFormat::ShExC
+        )]
+        schema_format: ShExFormat,
+
+        #[arg(
+            long = "target-schema",
+            value_name = "Target schema file name, URI or -"
+        )]
+        target_schema: InputSpec,
+
+        #[arg(
+            long = "target-schema-format",
+            value_name = "Target schema format",
+            default_value_t = ShExFormat::ShExC
+        )]
+        target_schema_format: ShExFormat,
+
+        /// Mapping between the shapes of both schemas in YAML or JSON
+        #[arg(short = 'M', long = "mapping", value_name = "Mapping file name")]
+        mapping: PathBuf,
+
+        #[arg(short = 'm', long = "shapemap", value_name = "ShapeMap file name")]
+        shapemap: Option<PathBuf>,
+
+        #[arg(
+            long = "shapemap-format",
+            value_name = "ShapeMap format",
+            default_value_t = ShapeMapFormat::Compact,
+        )]
+        shapemap_format: ShapeMapFormat,
+
+        /// How to obtain the nodes and shapes to validate with the source schema
+        #[arg(
+            long = "shex-mode",
+            value_name = "ShEx validation mode",
+            default_value_t = ShExValidationMode::ShapeMap
+        )]
+        shex_mode: ShExValidationMode,
+
+        #[arg(short = 'n', long = "node")]
+        node: Option<String>,
+
+        #[arg(
+            short = 'l',
+            long = "shape-label",
+            value_name = "shape label (default = START)",
+            group = "node_shape"
+        )]
+        shape: Option<String>,
+
+        #[arg(
+            short = 't',
+            long = "data-format",
+            value_name = "RDF Data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        data_format: DataFormat,
+
+        /// RDF Reader mode
+        #[arg(
+            long = "reader-mode",
+            value_name = "RDF Reader mode",
+            default_value_t = RDFReaderMode::default(),
+            value_enum
+        )]
+        reader_mode: RDFReaderMode,
+
+        #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
+        endpoint: Option<String>,
+
+        /// Validate the transformed nodes against the target schema
+        #[arg(long = "check-target", default_value_t = false)]
+        check_target: bool,
+
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Ouput RDF data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        result_format: DataFormat,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -289,16 +611,23 @@ pub enum Command {
     },
 
     /// Validate RDF data using SHACL shapes
+    ///
+    /// Exits with 0 if the data conforms, 1 if there is an error and 2 if some node doesn't conform
     ShaclValidate {
         #[clap(value_parser = clap::value_parser!(InputSpec))]
         data: Vec<InputSpec>,
 
+        /// Shapes graph. If it is not given, the graphs declared with sh:shapesGraph in the data are used
         #[arg(
             short = 's',
             long = "shapes",
             value_name = "Shapes graph: file, URI or -"
         )]
-        shapes: InputSpec,
+        shapes: Option<InputSpec>,
+
+        /// Folder in which the graphs imported with owl:imports are looked for
+        #[arg(long = "imports-dir", value_name = "Folder with imported graphs")]
+        imports_dir: Vec<PathBuf>,
 
         #[arg(
             short = 'f',
@@ -328,6 +657,15 @@ pub enum Command {
         #[arg(short = 'e', long = "endpoint", value_name = "Endpoint with RDF data")]
         endpoint: Option<String>,
 
+        /// Named graph of the data that is validated, by default the default graph
+        #[arg(long = "graph", value_name = "Named graph IRI")]
+        graph: Option<String>,
+
+        /// Folder of a persistent store with the RDF data, which is created if it doesn't exist.
+        /// The data given with --data is loaded into the store before validating it
+        #[arg(long = "store", value_name = "Store folder")]
+        store: Option<PathBuf>,
+
         /// Execution mode
         #[arg(
             short = 'm',
@@ -338,6 +676,96 @@ pub enum Command {
         )]
         mode: ShaclValidationMode,
 
+        /// Entailment regime applied to the data before the SHACL validation
+        #[arg(
+            long = "entailment",
+            value_name = "Entailment regime",
+            default_value_t = Entailment::None,
+            value_enum
+        )]
+        entailment: Entailment,
+
+        /// Show a summary of the validation with counts per shape and severity
+        #[arg(long = "summary", value_name = "Summary format")]
+        summary: Option<SummaryFormat>,
+
+        /// Write a JUnit XML report of the validation to a file
+        #[arg(long = "junit", value_name = "JUnit XML file name")]
+        junit: Option<PathBuf>,
+
+        /// Write the conforming target nodes as JSON-LD framed by their shapes instead of the validation report
+        #[arg(long = "framed", default_value_t = false)]
+        framed: bool,
+
+        #[arg(
+            short = 'o',
+            long = "output-file",
+            value_name = "Output file name, default = terminal"
+        )]
+        output: Option<PathBuf>,
+
+        #[arg(
+            long = "force-overwrite",
+            value_name = "Force overwrite mode",
+            default_value_t = false
+        )]
+        force_overwrite: bool,
+    },
+
+    /// Infer triples from RDF data executing the SHACL rules of a shapes graph
+    ShaclInfer {
+        #[clap(value_parser = clap::value_parser!(InputSpec))]
+        data: Vec<InputSpec>,
+
+        /// Shapes graph with the rules. If it is not given, the rules are read from the data
+        #[arg(
+            short = 's',
+            long = "shapes",
+            value_name = "Shapes graph: file, URI or -"
+        )]
+        shapes: Option<InputSpec>,
+
+        #[arg(
+            short = 'f',
+            long = "shapes-format",
+            value_name = "Shapes file format",
+            default_value_t = ShaclFormat::Turtle
+        )]
+        shapes_format: ShaclFormat,
+
+        #[arg(
+            short = 't',
+            long = "data-format",
+            value_name = "RDF Data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        data_format: DataFormat,
+
+        /// RDF Reader mode
+        #[arg(
+            long = "reader-mode",
+            value_name = "RDF Reader mode",
+            default_value_t = RDFReaderMode::default(),
+            value_enum
+        )]
+        reader_mode: RDFReaderMode,
+
+        /// Write the data together with the inferred triples instead of only the inferred triples
+        #[arg(long = "include-data", default_value_t = false)]
+        include_data: bool,
+
+        /// Maximum number of iterations over the rules looking for a fixpoint
+        #[arg(long = "max-iterations", value_name = "Number of iterations")]
+        max_iterations: Option<usize>,
+
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Ouput result format",
+            default_value_t = DataFormat::Turtle
+        )]
+        result_format: DataFormat,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -436,12 +864,47 @@ pub enum Command {
         )]
         show_node_mode: ShowNodeMode,
 
-        #[arg(long = "show hyperlinks")]
+        #[arg(long = "show-hyperlinks")]
         show_hyperlinks: bool,
 
         #[arg(short = 'p', long = "predicates")]
         predicates: Vec<String>,
 
+        /// Number of steps followed from the node when collecting its neighbourhood
+        #[arg(long = "depth", value_name = "Depth", default_value_t = 1)]
+        depth: usize,
+
+        /// SHACL path (in SPARQL property path syntax) whose values are obtained from the node
+        #[arg(long = "path", value_name = "SHACL path")]
+        path: Option<String>,
+
+        /// Shows only the triples that match a ShEx shape and the shapes that they match
+        #[arg(long = "shape", value_name = "Shape label")]
+        shape: Option<String>,
+
+        #[arg(
+            short = 's',
+            long = "schema",
+            value_name = "Schema file name, URI or -"
+        )]
+        schema: Option<InputSpec>,
+
+        #[arg(
+            short = 'f',
+            long = "schema-format",
+            value_name = "Schema format",
+            default_value_t = ShExFormat::ShExC
+        )]
+        schema_format: ShExFormat,
+
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Result format",
+            default_value_t = ShowNodeFormat::Text
+        )]
+        result_format: ShowNodeFormat,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -535,6 +998,10 @@ pub enum Command {
         #[arg(short = 'c', long = "config-file", value_name = "Config file name")]
         config: Option<PathBuf>,
 
+        /// Check the profile and report the problems found instead of showing it
+        #[arg(long = "lint", default_value_t = false)]
+        lint: bool,
+
         #[arg(
             short = 'o',
             long = "output-file",
@@ -614,6 +1081,105 @@ pub enum Command {
         #[arg(short = 'x', long = "export-mode", value_name = "Result mode")]
         output_mode: OutputConvertMode,
     },
+
+    /// Generate example RDF data that conforms to (or violates) ShEx or SHACL shapes
+    #[command(name = "generate")]
+    Generate {
+        #[arg(
+            short = 's',
+            long = "schema",
+            value_name = "Schema file name, URI or -"
+        )]
+        schema: InputSpec,
+
+        #[arg(
+            short = 'm',
+            long = "schema-mode",
+            value_name = "Schema mode (shex or shacl)",
+            default_value_t = InputConvertMode::ShEx
+        )]
+        schema_mode: InputConvertMode,
+
+        /// Schema format, if unset it is shexc for ShEx and turtle for SHACL
+        #[arg(short = 'f', long = "schema-format", value_name = "Schema format")]
+        schema_format: Option<InputConvertFormat>,
+
+        /// Seed of the random choices, the same seed generates the same data
+        #[arg(long = "seed", value_name = "Seed")]
+        seed: Option<u64>,
+
+        /// Number of nodes generated for each shape
+        #[arg(short = 'n', long = "instances", value_name = "Number of instances")]
+        instances: Option<usize>,
+
+        /// Make each generated node violate one constraint of its shape
+        #[arg(long = "violations", default_value_t = false)]
+        violations: bool,
+
+        #[arg(
+            short = 'r',
+            long = "result-format",
+            value_name = "Ouput RDF data format",
+            default_value_t = DataFormat::Turtle
+        )]
+        result_format: DataFormat,
+
+        /// Config file path, if unset it assumes default config
+        #[arg(short = 'c', long = "config-file", value_name = "Config file name")]
+        config: Option<PathBuf>,
+
+        #[arg(
+            short = 'o',
+            long = "output-file",
+            value_name = "Output file name, default = terminal"
+        )]
+        output: Option<PathBuf>,
+
+        /// RDF Reader mode
+        #[arg(
+            long = "reader-mode",
+            value_name = "RDF Reader mode",
+            default_value_t = RDFReaderMode::default(),
+            value_enum
+        )]
+        reader_mode: RDFReaderMode,
+
+        #[arg(
+            long = "force-overwrite",
+            value_name = "Force overwrite mode",
+            default_value_t = false
+        )]
+        force_overwrite: bool,
+    },
+
+    /// Serve validation and conversion as an HTTP JSON API
+    ///
+    /// The endpoints are described by the OpenAPI document served at /openapi.yaml
+    #[command(name = "serve")]
+    Serve {
+        #[arg(
+            long = "host",
+            value_name = "Host name or address",
+            default_value = "127.0.0.1"
+        )]
+        host: String,
+
+        #[arg(
+            short = 'p',
+            long = "port",
+            value_name = "Port",
+            default_value_t = 8080
+        )]
+        port: u16,
+
+        /// Number of compiled schemas of each kind kept in memory between requests
+        #[arg(
+            long = "cache-size",
+            value_name = "Number of schemas",
+            default_value_t = DEFAULT_CACHE_SIZE
+        )]
+        cache_size: usize,
+    },
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
@@ -636,6 +1202,27 @@ impl Display for ShowNodeMode {
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
 #[clap(rename_all = "lower")]
+pub enum ShowNodeFormat {
+    Text,
+    Turtle,
+    JSON,
+    DOT,
+}
+
+impl Display for ShowNodeFormat {
+    fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ShowNodeFormat::Text => write!(dest, "text"),
+            ShowNodeFormat::Turtle => write!(dest, "turtle"),
+            ShowNodeFormat::JSON => write!(dest, "json"),
+            ShowNodeFormat::DOT => write!(dest, "dot"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Serialize, Deserialize)]
+#[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
 pub enum ShExFormat {
     Internal,
     Simple,
@@ -666,8 +1253,9 @@ impl Display for ShExFormat {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Serialize, Deserialize)]
 #[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
 pub enum ShapeMapFormat {
     Compact,
     Internal,
@@ -684,6 +1272,66 @@ impl Display for ShapeMapFormat {
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
 #[clap(rename_all = "lower")]
+pub enum ResultShapeMapFormat {
+    Details,
+    Compact,
+    JSON,
+    Framed,
+}
+
+impl Display for ResultShapeMapFormat {
+    fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ResultShapeMapFormat::Details => write!(dest, "details"),
+            ResultShapeMapFormat::Compact => write!(dest, "compact"),
+            ResultShapeMapFormat::JSON => write!(dest, "json"),
+            ResultShapeMapFormat::Framed => write!(dest, "framed"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Serialize, Deserialize)]
+#[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
+pub enum ShExValidationMode {
+    /// Validate the nodes and shapes of the shapemap
+    ShapeMap,
+    /// Validate the instances of the classes declared in the type/shape mappings of the config
+    Types,
+    /// Validate every subject against the START shape
+    Subjects,
+    /// Validate every subject against every shape and report the ones it conforms to
+    Best,
+}
+
+impl Display for ShExValidationMode {
+    fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            ShExValidationMode::ShapeMap => write!(dest, "shapemap"),
+            ShExValidationMode::Types => write!(dest, "types"),
+            ShExValidationMode::Subjects => write!(dest, "subjects"),
+            ShExValidationMode::Best => write!(dest, "best"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[clap(rename_all = "lower")]
+pub enum SummaryFormat {
+    JSON,
+}
+
+impl Display for SummaryFormat {
+    fn fmt(&self, dest: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
+        match self {
+            SummaryFormat::JSON => write!(dest, "json"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Serialize, Deserialize)]
+#[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
 pub enum DataFormat {
     Turtle,
     NTriples,
@@ -691,6 +1339,7 @@ pub enum DataFormat {
     TriG,
     N3,
     NQuads,
+    JsonLd,
 }
 
 impl From<DataFormat> for RDFFormat {
@@ -702,6 +1351,7 @@ impl From<DataFormat> for RDFFormat {
             DataFormat::TriG => RDFFormat::TriG,
             DataFormat::N3 => RDFFormat::N3,
             DataFormat::NQuads => RDFFormat::NQuads,
+            DataFormat::JsonLd => RDFFormat::JsonLd,
         }
     }
 }
@@ -715,12 +1365,14 @@ impl Display for DataFormat {
             DataFormat::TriG => write!(dest, "trig"),
             DataFormat::N3 => write!(dest, "n3"),
             DataFormat::NQuads => write!(dest, "nquads"),
+            DataFormat::JsonLd => write!(dest, "jsonld"),
         }
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Serialize, Deserialize)]
 #[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
 pub enum ShaclFormat {
     Internal,
     Turtle,
@@ -729,6 +1381,7 @@ pub enum ShaclFormat {
     TriG,
     N3,
     NQuads,
+    JsonLd,
 }
 
 impl Display for ShaclFormat {
@@ -741,6 +1394,7 @@ impl Display for ShaclFormat {
             ShaclFormat::TriG => write!(dest, "trig"),
             ShaclFormat::N3 => write!(dest, "n3"),
             ShaclFormat::NQuads => write!(dest, "nquads"),
+            ShaclFormat::JsonLd => write!(dest, "jsonld"),
         }
     }
 }
@@ -791,8 +1445,9 @@ impl Display for ValidationMode {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Serialize, Deserialize)]
 #[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
 pub enum InputConvertMode {
     SHACL,
     ShEx,
@@ -809,13 +1464,15 @@ impl Display for InputConvertMode {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Serialize, Deserialize)]
 #[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
 pub enum OutputConvertMode {
     SPARQL,
     ShEx,
     UML,
     HTML,
+    DCTAP,
 }
 
 impl Display for OutputConvertMode {
@@ -825,6 +1482,7 @@ impl Display for OutputConvertMode {
             OutputConvertMode::ShEx => write!(dest, "shex"),
             OutputConvertMode::UML => write!(dest, "uml"),
             OutputConvertMode::HTML => write!(dest, "html"),
+            OutputConvertMode::DCTAP => write!(dest, "dctap"),
         }
     }
 }
@@ -1,11 +1,13 @@
 use prefixmap::PrefixMap;
 use srdf::srdf_graph::SRDFGraph;
 use srdf::srdf_sparql::SRDFSparql;
+use srdf::srdf_store::SRDFStore;
 
 #[derive(Debug)]
 pub enum Data {
     Endpoint(SRDFSparql),
     RDFData(SRDFGraph),
+    Store(SRDFStore),
 }
 
 impl Data {
@@ -13,6 +15,7 @@ impl Data {
         match self {
             Data::RDFData(data) => Some(data.prefixmap()),
             Data::Endpoint(_) => None,
+            Data::Store(store) => Some(store.prefixmap()),
         }
     }
 }
@@ -1,12 +1,14 @@
 use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
 
 use std::{
     fmt::{Display, Formatter},
     str::FromStr,
 };
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Serialize, Deserialize)]
 #[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
 pub enum InputConvertFormat {
     CSV,
     ShExC,
@@ -1,9 +1,10 @@
 use either::Either;
 use std::{
     fs,
-    io::{self, BufReader, StdinLock},
+    io::{self, BufReader, Cursor, StdinLock},
     path::{Path, PathBuf},
     str::FromStr,
+    time::UNIX_EPOCH,
 };
 use thiserror::Error;
 
@@ -13,6 +14,8 @@ pub enum InputSpec {
     Path(PathBuf),
     Stdin,
     Url(String),
+    /// Contents given inline, like the bodies of the requests to `rudof serve`
+    Str(String),
 }
 
 impl InputSpec {
@@ -30,8 +33,36 @@ impl InputSpec {
             InputSpec::Url(str) => {
                 let resp = reqwest::blocking::get(str)?;
                 let reader = BufReader::new(resp);
-                Ok(Either::Right(Either::Right(reader)))
+                Ok(Either::Right(Either::Right(Either::Left(reader))))
             }
+            InputSpec::Str(str) => Ok(Either::Right(Either::Right(Either::Right(Cursor::new(
+                str.clone().into_bytes(),
+            ))))),
+        }
+    }
+}
+
+impl InputSpec {
+    /// Key that identifies the contents of the input, if they can be identified without reading them
+    ///
+    /// Files are identified by their canonical path, size and modification time, and URLs by themselves
+    pub fn source_key(&self) -> Result<Option<String>, InputSpecError> {
+        match self {
+            InputSpec::Path(p) => {
+                let metadata = fs::metadata(p)?;
+                let modified = metadata
+                    .modified()?
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_nanos())
+                    .unwrap_or_default();
+                Ok(Some(format!(
+                    "{} {} {modified}",
+                    fs::canonicalize(p)?.display(),
+                    metadata.len()
+                )))
+            }
+            InputSpec::Url(url) => Ok(Some(url.clone())),
+            InputSpec::Stdin | InputSpec::Str(_) => Ok(None),
         }
     }
 }
@@ -54,8 +85,10 @@ impl FromStr for InputSpec {
 }
 
 /// This type implements [`std::io::BufRead`].
-pub type InputSpecReader =
-    Either<StdinLock<'static>, Either<BufReader<fs::File>, BufReader<reqwest::blocking::Response>>>;
+pub type InputSpecReader = Either<
+    StdinLock<'static>,
+    Either<BufReader<fs::File>, Either<BufReader<reqwest::blocking::Response>, Cursor<Vec<u8>>>>,
+>;
 
 #[derive(Error, Debug)]
 pub enum InputSpecError {
@@ -20,43 +20,62 @@ extern crate tracing_subscriber;
 
 use anyhow::*;
 use clap::Parser;
-use dctap::{DCTap, TapConfig};
+use dctap::{DCTap, PrefixCC, TapConfig, TapWriter};
+use iri_s::IriS;
+use oxiri::Iri;
 use prefixmap::IriRef;
-use shacl_ast::{Schema as ShaclSchema, ShaclParser, ShaclWriter};
+use shacl_ast::{
+    declared_shapes_graph, resolve_imports, LocalResolver, Schema as ShaclSchema, ShaclParser,
+    ShaclWriter,
+};
+use shacl_validation::rules::RuleEngine;
+use shacl_validation::store::persistent::Persistent;
 use shacl_validation::store::ShaclDataManager;
-use shacl_validation::validate::{GraphValidator, ShaclValidationMode, SparqlValidator};
+use shacl_validation::validate::{
+    Entailment, GraphValidator, PersistentValidator, ShaclValidationMode, SparqlValidator,
+};
+use shacl_validation::validation_report::report::ValidationReport;
 use shapemap::{query_shape_map::QueryShapeMap, NodeSelector, ShapeSelector};
 use shapes_converter::{shex_to_sparql::ShEx2SparqlConfig, ShEx2Sparql};
 use shapes_converter::{
-    ConverterConfig, ImageFormat, ShEx2Html, ShEx2HtmlConfig, ShEx2Uml, ShEx2UmlConfig, Shacl2ShEx,
-    Shacl2ShExConfig, Tap2ShEx,
+    ConverterConfig, DataGeneratorConfig, DataMapping, ImageFormat, JsonLdFrame, JsonLdFramer,
+    ShEx2Data, ShEx2Html, ShEx2HtmlConfig, ShEx2Tap, ShEx2Uml, ShEx2UmlConfig, ShExTransformer,
+    Shacl2Data, Shacl2ShEx, Shacl2ShExConfig, Shacl2Tap, Tap2ShEx,
 };
 use shex_ast::SimpleReprSchema;
-use shex_ast::{object_value::ObjectValue, shexr::shexr_parser::ShExRParser};
-use shex_compact::{ShExFormatter, ShExParser, ShapeMapParser, ShapemapFormatter};
-use shex_validation::{Validator, ValidatorConfig};
-use srdf::srdf_graph::SRDFGraph;
-use srdf::{RDFFormat, SRDFBuilder, SRDFSparql, SRDF};
+use shex_ast::{compiled::shape_label::ShapeLabel, Node};
+use shex_ast::{shexr::shexr_parser::ShExRParser, shexr::shexr_writer::ShExRWriter};
+use shex_compact::{ShExComments, ShExFormatter, ShExParser, ShapeMapParser, ShapemapFormatter};
+use shex_validation::{ResultValue, Validator, ValidatorConfig, ValidatorError};
+use srdf::srdf_graph::{JsonLdContextLoader, SRDFGraph};
+use srdf::{Object, RDFFormat, SRDFBasic, SRDFBuilder, SRDFDataset, SRDFSparql, SRDFStore, SRDF};
+use std::collections::HashSet;
 use std::fs::{File, OpenOptions};
-use std::io::{self, BufWriter, Write};
+use std::io::{self, BufWriter, Read, Write};
 use std::path::{Path, PathBuf};
 use std::result::Result::Ok;
 use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Instant;
 use supports_color::Stream;
-use tracing::debug;
+use tracing::{debug, warn};
 
 pub mod cli;
 pub mod data;
 pub mod input_convert_format;
 pub mod input_spec;
+pub mod node_explorer;
 pub mod output_convert_format;
+pub mod server;
+pub mod validation_summary;
 
 pub use cli::*;
 pub use data::*;
 pub use input_convert_format::InputConvertFormat;
 pub use input_spec::*;
+pub use node_explorer::*;
 pub use output_convert_format::OutputConvertFormat;
+pub use validation_summary::*;
 
 use shex_ast::{ast::Schema as SchemaJson, compiled::compiled_schema::CompiledSchema};
 use tracing_subscriber::prelude::*;
@@ -91,18 +110,34 @@ fn main() -> Result<()> {
             output,
             show_time,
             show_statistics,
+            fmt,
+            indent,
+            width,
             force_overwrite,
             reader_mode,
-        }) => run_shex(
-            schema,
-            schema_format,
-            result_schema_format,
-            output,
-            *show_time,
-            *show_statistics,
-            *force_overwrite,
-            reader_mode,
-        ),
+        }) => {
+            if *fmt {
+                run_shex_fmt(
+                    schema,
+                    schema_format,
+                    output,
+                    *indent,
+                    *width,
+                    *force_overwrite,
+                )
+            } else {
+                run_shex(
+                    schema,
+                    schema_format,
+                    result_schema_format,
+                    output,
+                    *show_time,
+                    *show_statistics,
+                    *force_overwrite,
+                    reader_mode,
+                )
+            }
+        }
         Some(Command::Validate {
             validation_mode,
             schema,
@@ -111,31 +146,47 @@ fn main() -> Result<()> {
             data_format,
             reader_mode,
             endpoint,
+            graph,
+            store,
             node,
             shape,
             shapemap,
             shapemap_format,
+            result_format,
+            shex_mode,
             max_steps,
             shacl_validation_mode,
+            entailment,
+            config,
+            summary,
+            junit,
             output,
             force_overwrite,
         }) => match validation_mode {
-            ValidationMode::ShEx => run_validate_shex(
-                schema,
-                schema_format,
-                data,
-                data_format,
-                endpoint,
-                reader_mode,
-                node,
-                shape,
-                shapemap,
-                shapemap_format,
-                cli.debug,
-                output,
-                &ValidatorConfig::default(),
-                *force_overwrite,
-            ),
+            ValidationMode::ShEx => get_validator_config(config).and_then(|config| {
+                run_validate_shex(
+                    schema,
+                    schema_format,
+                    data,
+                    data_format,
+                    endpoint,
+                    graph,
+                    store,
+                    reader_mode,
+                    node,
+                    shape,
+                    shapemap,
+                    shapemap_format,
+                    result_format,
+                    shex_mode,
+                    summary,
+                    junit,
+                    cli.debug,
+                    output,
+                    &config,
+                    *force_overwrite,
+                )
+            }),
             ValidationMode::SHACL => {
                 let shacl_format = match schema_format {
                     ShExFormat::Internal => Ok(ShaclFormat::Internal),
@@ -156,18 +207,27 @@ fn main() -> Result<()> {
                     ShExFormat::NQuads => Ok(ShaclFormat::NQuads),
                 }?;
                 run_validate_shacl(
-                    schema,
+                    Some(schema),
                     &shacl_format,
+                    &[],
                     data,
                     data_format,
+                    reader_mode,
                     endpoint,
+                    graph,
+                    store,
                     *shacl_validation_mode,
+                    *entailment,
+                    summary,
+                    junit,
+                    *result_format == ResultShapeMapFormat::Framed,
                     cli.debug,
                     output,
                     *force_overwrite,
                 )
             }
-        },
+        }
+        .map(exit_if_nonconformant),
         Some(Command::ShexValidate {
             schema,
             schema_format,
@@ -175,35 +235,123 @@ fn main() -> Result<()> {
             data_format,
             reader_mode,
             endpoint,
+            graph,
+            store,
             node,
             shape,
             shapemap,
             shapemap_format,
+            result_format,
+            shex_mode,
+            summary,
+            junit,
             output,
             config,
             force_overwrite,
         }) => {
-            let config = match config {
-                Some(config_path) => match ValidatorConfig::from_path(config_path) {
-                    Ok(c) => Ok(c),
-                    Err(e) => Err(anyhow!(
-                        "Error obtaining ShEx validation confir from {}: {e}",
-                        config_path.display()
-                    )),
-                },
-                None => Ok(ValidatorConfig::default()),
-            }?;
+            let config = get_validator_config(config)?;
             run_validate_shex(
                 schema,
                 schema_format,
                 data,
                 data_format,
                 endpoint,
+                graph,
+                store,
+                reader_mode,
+                node,
+                shape,
+                shapemap,
+                shapemap_format,
+                result_format,
+                shex_mode,
+                summary,
+                junit,
+                cli.debug,
+                output,
+                &config,
+                *force_overwrite,
+            )
+            .map(exit_if_nonconformant)
+        }
+        Some(Command::Extract {
+            schema,
+            schema_format,
+            data,
+            data_format,
+            reader_mode,
+            endpoint,
+            node,
+            shape,
+            shapemap,
+            shapemap_format,
+            shex_mode,
+            result_format,
+            framed,
+            output,
+            config,
+            force_overwrite,
+        }) => {
+            let config = get_validator_config(config)?;
+            run_extract(
+                schema,
+                schema_format,
+                data,
+                data_format,
+                endpoint,
+                reader_mode,
+                node,
+                shape,
+                shapemap,
+                shapemap_format,
+                shex_mode,
+                result_format,
+                *framed,
+                cli.debug,
+                output,
+                &config,
+                *force_overwrite,
+            )
+        }
+        Some(Command::Transform {
+            schema,
+            schema_format,
+            target_schema,
+            target_schema_format,
+            mapping,
+            data,
+            data_format,
+            reader_mode,
+            endpoint,
+            node,
+            shape,
+            shapemap,
+            shapemap_format,
+            shex_mode,
+            check_target,
+            result_format,
+            output,
+            config,
+            force_overwrite,
+        }) => {
+            let config = get_validator_config(config)?;
+            run_transform(
+                schema,
+                schema_format,
+                target_schema,
+                target_schema_format,
+                mapping,
+                data,
+                data_format,
+                endpoint,
                 reader_mode,
                 node,
                 shape,
                 shapemap,
                 shapemap_format,
+                shex_mode,
+                *check_target,
+                result_format,
                 cli.debug,
                 output,
                 &config,
@@ -212,24 +360,63 @@ fn main() -> Result<()> {
         }
         Some(Command::ShaclValidate {
             shapes,
+            imports_dir,
             shapes_format,
             data,
             data_format,
             reader_mode,
             endpoint,
+            graph,
+            store,
             mode,
+            entailment,
+            summary,
+            junit,
+            framed,
             output,
             force_overwrite,
         }) => run_validate_shacl(
-            shapes,
+            shapes.as_ref(),
             shapes_format,
+            imports_dir,
             data,
             data_format,
+            reader_mode,
             endpoint,
+            graph,
+            store,
             *mode,
+            *entailment,
+            summary,
+            junit,
+            *framed,
             cli.debug,
             output,
             *force_overwrite,
+        )
+        .map(exit_if_nonconformant),
+        Some(Command::ShaclInfer {
+            data,
+            shapes,
+            shapes_format,
+            data_format,
+            reader_mode,
+            include_data,
+            max_iterations,
+            result_format,
+            output,
+            force_overwrite,
+        }) => run_shacl_infer(
+            data,
+            shapes.as_ref(),
+            shapes_format,
+            data_format,
+            reader_mode,
+            *include_data,
+            *max_iterations,
+            result_format,
+            output,
+            *force_overwrite,
         ),
         Some(Command::Data {
             data,
@@ -256,6 +443,12 @@ fn main() -> Result<()> {
             predicates,
             show_node_mode,
             show_hyperlinks,
+            depth,
+            path,
+            shape,
+            schema,
+            schema_format,
+            result_format,
             output,
             config,
             force_overwrite,
@@ -268,6 +461,14 @@ fn main() -> Result<()> {
             predicates,
             show_node_mode,
             show_hyperlinks,
+            &NodeExplorerOptions {
+                depth: *depth,
+                path: path.clone(),
+                shape: shape.clone(),
+                schema: schema.clone(),
+                schema_format: *schema_format,
+                result_format: *result_format,
+            },
             cli.debug,
             output,
             config,
@@ -306,6 +507,7 @@ fn main() -> Result<()> {
             format,
             result_format,
             config,
+            lint,
             output,
             force_overwrite,
         }) => run_dctap(
@@ -314,6 +516,7 @@ fn main() -> Result<()> {
             result_format,
             output,
             config,
+            *lint,
             *force_overwrite,
         ),
         Some(Command::Convert {
@@ -341,12 +544,60 @@ fn main() -> Result<()> {
             *force_overwrite,
             reader_mode,
         ),
+        Some(Command::Generate {
+            schema,
+            schema_mode,
+            schema_format,
+            seed,
+            instances,
+            violations,
+            result_format,
+            config,
+            output,
+            reader_mode,
+            force_overwrite,
+        }) => {
+            let mut generator_config = match config {
+                None => ConverterConfig::default(),
+                Some(config_path) => ConverterConfig::from_path(config_path)?,
+            }
+            .data_generator_config();
+            if let Some(seed) = seed {
+                generator_config = generator_config.with_seed(*seed)
+            }
+            if let Some(instances) = instances {
+                generator_config = generator_config.with_instances(*instances)
+            }
+            if *violations {
+                generator_config = generator_config.with_violations(true)
+            }
+            run_generate(
+                schema,
+                schema_mode,
+                schema_format,
+                &generator_config,
+                result_format,
+                output,
+                *force_overwrite,
+                reader_mode,
+            )
+        }
+        Some(Command::Serve {
+            host,
+            port,
+            cache_size,
+        }) => run_serve(host, *port, *cache_size),
         None => {
             bail!("Command not specified")
         }
     }
 }
 
+fn run_serve(host: &str, port: u16, cache_size: usize) -> Result<()> {
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(server::serve(&format!("{host}:{port}"), cache_size))
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_shex(
     input: &InputSpec,
@@ -359,9 +610,30 @@ fn run_shex(
     reader_mode: &RDFReaderMode,
 ) -> Result<()> {
     let begin = Instant::now();
-    let (writer, color) = get_writer(output, force_overwrite)?;
-    let schema_json = parse_schema(input, schema_format, reader_mode)?;
-    show_schema(&schema_json, result_schema_format, writer, color)?;
+    let (mut writer, color) = get_writer(output, force_overwrite)?;
+    let schema_json = match (schema_format, result_schema_format) {
+        // Keep the comments when the schema is converted from ShExC to ShExC
+        (ShExFormat::ShExC, ShExFormat::ShExC) => {
+            let (schema, comments) = parse_shexc_with_comments(input)?;
+            let formatter = match color {
+                ColorSupport::NoColor => ShExFormatter::default().without_colors(),
+                ColorSupport::WithColor => ShExFormatter::default(),
+            };
+            let str = formatter.format_schema_with_comments(&schema, &comments)?;
+            writeln!(writer, "{str}")?;
+            schema
+        }
+        (ShExFormat::ShExC, _) => {
+            let (schema, _) = parse_shexc_with_comments(input)?;
+            show_schema(&schema, result_schema_format, writer, color)?;
+            schema
+        }
+        _ => {
+            let schema = parse_schema(input, schema_format, reader_mode)?;
+            show_schema(&schema, result_schema_format, writer, color)?;
+            schema
+        }
+    };
     if show_time {
         let elapsed = begin.elapsed();
         let _ = writeln!(io::stderr(), "elapsed: {:.03?} sec", elapsed.as_secs_f64());
@@ -376,6 +648,45 @@ fn run_shex(
     Ok(())
 }
 
+fn run_shex_fmt(
+    input: &InputSpec,
+    schema_format: &ShExFormat,
+    output: &Option<PathBuf>,
+    indent: usize,
+    width: usize,
+    force_overwrite: bool,
+) -> Result<()> {
+    if *schema_format != ShExFormat::ShExC {
+        bail!("Only ShExC schemas can be formatted, found {schema_format}")
+    }
+    let (schema, comments) = parse_shexc_with_comments(input)?;
+    let (mut writer, _color) = get_writer(output, force_overwrite)?;
+    let formatter = ShExFormatter::default()
+        .without_colors()
+        .with_indent(indent)
+        .with_width(width);
+    let str = formatter.format_schema_with_comments(&schema, &comments)?;
+    writeln!(writer, "{str}")?;
+    Ok(())
+}
+
+// Parses in recovery mode first to report all the syntax errors at once
+fn parse_shexc_with_comments(input: &InputSpec) -> Result<(SchemaJson, ShExComments)> {
+    let mut reader = input.open_read()?;
+    let mut src = String::new();
+    reader.read_to_string(&mut src)?;
+    let (_, diagnostics) = ShExParser::parse_with_recovery(&src, None);
+    if diagnostics.is_empty() {
+        let (schema, comments) = ShExParser::parse_with_comments(&src, None)?;
+        Ok((schema, comments))
+    } else {
+        for diagnostic in diagnostics.iter() {
+            let _ = writeln!(io::stderr(), "{diagnostic}");
+        }
+        bail!("Found {} errors parsing ShExC schema", diagnostics.len())
+    }
+}
+
 fn show_schema(
     schema: &SchemaJson,
     result_schema_format: &ShExFormat,
@@ -392,7 +703,7 @@ fn show_schema(
                 ColorSupport::NoColor => ShExFormatter::default().without_colors(),
                 ColorSupport::WithColor => ShExFormatter::default(),
             };
-            let str = formatter.format_schema(schema);
+            let str = formatter.format_schema(schema)?;
             writeln!(writer, "{str}")?;
             Ok(())
         }
@@ -408,9 +719,13 @@ fn show_schema(
             writeln!(writer, "{str}")?;
             Ok(())
         }
-        _ => Err(anyhow!(
-            "Not implemented conversion to {result_schema_format} yet"
-        )),
+        _ => {
+            let data_format = shex_format_to_data_format(result_schema_format)?;
+            let mut shexr_writer: ShExRWriter<SRDFGraph> = ShExRWriter::new();
+            shexr_writer.write(schema)?;
+            shexr_writer.serialize(data_format.into(), writer)?;
+            Ok(())
+        }
     }
 }
 
@@ -421,24 +736,128 @@ fn run_validate_shex(
     data: &Vec<InputSpec>,
     data_format: &DataFormat,
     endpoint: &Option<String>,
+    graph: &Option<String>,
+    store: &Option<PathBuf>,
     reader_mode: &RDFReaderMode,
     maybe_node: &Option<String>,
     maybe_shape: &Option<String>,
     shapemap_path: &Option<PathBuf>,
     shapemap_format: &ShapeMapFormat,
+    result_format: &ResultShapeMapFormat,
+    shex_mode: &ShExValidationMode,
+    summary: &Option<SummaryFormat>,
+    junit: &Option<PathBuf>,
     debug: u8,
     output: &Option<PathBuf>,
     config: &ValidatorConfig,
     force_overwrite: bool,
-) -> Result<()> {
+) -> Result<bool> {
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
     let schema_json = parse_schema(schema, schema_format, reader_mode)?;
     let mut schema: CompiledSchema = CompiledSchema::new();
     schema.from_schema_json(&schema_json)?;
-    let data = get_data(data, data_format, endpoint, reader_mode, debug)?;
-    let mut shapemap = match shapemap_path {
+    let mut data = get_data(data, data_format, endpoint, store, reader_mode, debug)?;
+    if let Some(graph) = graph {
+        match &mut data {
+            Data::RDFData(rdf_data) => select_graph(rdf_data, graph)?,
+            Data::Endpoint(_) => {
+                bail!("Named graphs are only supported for local data, not for endpoints")
+            }
+            Data::Store(_) => {
+                bail!("Named graphs are not supported by stores, the graphs of the data are merged when it is loaded")
+            }
+        }
+    }
+    let shapemap = get_query_shapemap(shapemap_path, shapemap_format, maybe_node, maybe_shape)?;
+    let mut validator = Validator::new(schema, config);
+    let result = match &data {
+        Data::Endpoint(endpoint) => validator
+            .validate_shapemap_query(&shapemap, endpoint)
+            .and_then(|_| validate_shex_mode(&mut validator, shex_mode, endpoint)),
+        Data::RDFData(data) => validator
+            .validate_shapemap_query(&shapemap, data)
+            .and_then(|_| validate_shex_mode(&mut validator, shex_mode, data)),
+        Data::Store(store) => validator
+            .validate_shapemap_query(&shapemap, store)
+            .and_then(|_| validate_shex_mode(&mut validator, shex_mode, store)),
+    };
+    match result {
+        Result::Ok(_t) => match validator.result_map(data.prefixmap()) {
+            Result::Ok(result_map) => {
+                let result_map = match shex_mode {
+                    ShExValidationMode::Best => result_map.best_shapes(),
+                    _ => result_map,
+                };
+                let validation_summary = ValidationSummary::from_result_map(&result_map);
+                match summary {
+                    Some(summary_format) => {
+                        show_summary(&mut writer, &validation_summary, summary_format)?
+                    }
+                    None => match result_format {
+                        ResultShapeMapFormat::Details => {
+                            writeln!(writer, "Result:\n{}", result_map)?
+                        }
+                        ResultShapeMapFormat::Compact => {
+                            writeln!(writer, "{}", result_map.show_compact())?
+                        }
+                        ResultShapeMapFormat::JSON => {
+                            writeln!(writer, "{}", result_map.as_json()?)?
+                        }
+                        ResultShapeMapFormat::Framed => {
+                            let framed = match &data {
+                                Data::Endpoint(endpoint) => {
+                                    frame_conforming_roots(&schema_json, &validator, endpoint)?
+                                }
+                                Data::RDFData(data) => {
+                                    frame_conforming_roots(&schema_json, &validator, data)?
+                                }
+                                Data::Store(store) => {
+                                    frame_conforming_roots(&schema_json, &validator, store)?
+                                }
+                            };
+                            writeln!(writer, "{}", serde_json::to_string_pretty(&framed)?)?
+                        }
+                    },
+                }
+                write_junit(&validation_summary, junit, force_overwrite)?;
+                Ok(validation_summary.conforms())
+            }
+            Err(err) => {
+                bail!("Error generating result_map after validation: {err}");
+            }
+        },
+        Result::Err(err) => {
+            bail!("{err}");
+        }
+    }
+}
+
+/// ShapeMap obtained from a shapemap file and the node and shape label options
+fn get_query_shapemap(
+    shapemap_path: &Option<PathBuf>,
+    shapemap_format: &ShapeMapFormat,
+    maybe_node: &Option<String>,
+    maybe_shape: &Option<String>,
+) -> Result<QueryShapeMap> {
+    let shapemap_input = shapemap_path.as_ref().map(InputSpec::path);
+    query_shapemap(
+        shapemap_input.as_ref(),
+        shapemap_format,
+        maybe_node,
+        maybe_shape,
+    )
+}
+
+/// Same as [`get_query_shapemap`] for a shapemap that can also be given inline
+fn query_shapemap(
+    shapemap: Option<&InputSpec>,
+    shapemap_format: &ShapeMapFormat,
+    maybe_node: &Option<String>,
+    maybe_shape: &Option<String>,
+) -> Result<QueryShapeMap> {
+    let mut shapemap = match shapemap {
         None => QueryShapeMap::new(),
-        Some(shapemap_buf) => parse_shapemap(shapemap_buf, shapemap_format)?,
+        Some(input) => parse_shapemap_input(input, shapemap_format)?,
     };
     match (maybe_node, maybe_shape) {
         (None, None) => {
@@ -459,98 +878,474 @@ fn run_validate_shex(
             )
         }
     };
+    Ok(shapemap)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_extract(
+    schema: &InputSpec,
+    schema_format: &ShExFormat,
+    data: &Vec<InputSpec>,
+    data_format: &DataFormat,
+    endpoint: &Option<String>,
+    reader_mode: &RDFReaderMode,
+    maybe_node: &Option<String>,
+    maybe_shape: &Option<String>,
+    shapemap_path: &Option<PathBuf>,
+    shapemap_format: &ShapeMapFormat,
+    shex_mode: &ShExValidationMode,
+    result_format: &DataFormat,
+    framed: bool,
+    debug: u8,
+    output: &Option<PathBuf>,
+    config: &ValidatorConfig,
+    force_overwrite: bool,
+) -> Result<()> {
+    let schema_json = parse_schema(schema, schema_format, reader_mode)?;
+    let mut schema: CompiledSchema = CompiledSchema::new();
+    schema.from_schema_json(&schema_json)?;
+    let data = get_data(data, data_format, endpoint, &None, reader_mode, debug)?;
+    let shapemap = get_query_shapemap(shapemap_path, shapemap_format, maybe_node, maybe_shape)?;
     let mut validator = Validator::new(schema, config);
     let result = match &data {
-        Data::Endpoint(endpoint) => validator.validate_shapemap(&shapemap, endpoint),
-        Data::RDFData(data) => validator.validate_shapemap(&shapemap, data),
+        Data::Endpoint(endpoint) => validator
+            .validate_shapemap_query(&shapemap, endpoint)
+            .and_then(|_| validate_shex_mode(&mut validator, shex_mode, endpoint)),
+        Data::RDFData(data) => validator
+            .validate_shapemap_query(&shapemap, data)
+            .and_then(|_| validate_shex_mode(&mut validator, shex_mode, data)),
+        Data::Store(store) => validator
+            .validate_shapemap_query(&shapemap, store)
+            .and_then(|_| validate_shex_mode(&mut validator, shex_mode, store)),
     };
-    match result {
-        Result::Ok(_t) => match validator.result_map(data.prefixmap()) {
-            Result::Ok(result_map) => {
-                writeln!(writer, "Result:\n{}", result_map)?;
-                Ok(())
+    if let Err(err) = result {
+        bail!("{err}");
+    }
+    let mut extracted = SRDFGraph::new();
+    if let Some(prefixmap) = data.prefixmap() {
+        extracted.add_prefix_map(prefixmap)?;
+    }
+    for (subject, pred, object) in validator.conforming_triples() {
+        let subject = match SRDFGraph::object_as_subject(subject.as_object()) {
+            Some(subject) => subject,
+            None => bail!("Extracted triple with a literal as subject: {subject}"),
+        };
+        extracted.add_triple(
+            &subject,
+            &SRDFGraph::iri_s2iri(pred.iri()),
+            &SRDFGraph::object_as_term(object.as_object()),
+        )?;
+    }
+    let (mut writer, _color) = get_writer(output, force_overwrite)?;
+    if framed {
+        let framed = frame_conforming_roots(&schema_json, &validator, &extracted)?;
+        writeln!(writer, "{}", serde_json::to_string_pretty(&framed)?)?;
+    } else {
+        extracted.serialize(RDFFormat::from(*result_format), writer)?;
+    }
+    Ok(())
+}
+
+/// JSON-LD document with the nodes requested to the validator that conform, framed by their shapes
+fn frame_conforming_roots<S: SRDF>(
+    schema: &SchemaJson,
+    validator: &Validator,
+    data: &S,
+) -> Result<serde_json::Value> {
+    let framer = JsonLdFramer::new(&JsonLdFrame::from_shex(schema)?);
+    let nodes: Vec<_> = validator
+        .conforming_roots()?
+        .into_iter()
+        .filter_map(|(node, shape)| match shape {
+            ShapeLabel::Iri(iri) => Some((node.as_object().clone(), Object::iri(iri))),
+            _ => None,
+        })
+        .collect();
+    Ok(framer.frame(&nodes, data)?)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_transform(
+    schema: &InputSpec,
+    schema_format: &ShExFormat,
+    target_schema: &InputSpec,
+    target_schema_format: &ShExFormat,
+    mapping: &PathBuf,
+    data: &Vec<InputSpec>,
+    data_format: &DataFormat,
+    endpoint: &Option<String>,
+    reader_mode: &RDFReaderMode,
+    maybe_node: &Option<String>,
+    maybe_shape: &Option<String>,
+    shapemap_path: &Option<PathBuf>,
+    shapemap_format: &ShapeMapFormat,
+    shex_mode: &ShExValidationMode,
+    check_target: bool,
+    result_format: &DataFormat,
+    debug: u8,
+    output: &Option<PathBuf>,
+    config: &ValidatorConfig,
+    force_overwrite: bool,
+) -> Result<()> {
+    let source_json = parse_schema(schema, schema_format, reader_mode)?;
+    let target_json = parse_schema(target_schema, target_schema_format, reader_mode)?;
+    let mapping = DataMapping::from_path(mapping)?;
+    let mut source: CompiledSchema = CompiledSchema::new();
+    source.from_schema_json(&source_json)?;
+    let data = get_data(data, data_format, endpoint, &None, reader_mode, debug)?;
+    let shapemap = get_query_shapemap(shapemap_path, shapemap_format, maybe_node, maybe_shape)?;
+    let mut validator = Validator::new(source, config);
+    let result = match &data {
+        Data::Endpoint(endpoint) => validator
+            .validate_shapemap_query(&shapemap, endpoint)
+            .and_then(|_| validate_shex_mode(&mut validator, shex_mode, endpoint)),
+        Data::RDFData(data) => validator
+            .validate_shapemap_query(&shapemap, data)
+            .and_then(|_| validate_shex_mode(&mut validator, shex_mode, data)),
+        Data::Store(store) => validator
+            .validate_shapemap_query(&shapemap, store)
+            .and_then(|_| validate_shex_mode(&mut validator, shex_mode, store)),
+    };
+    if let Err(err) = result {
+        bail!("{err}");
+    }
+    let matches = match validator.shape_matches() {
+        Ok(matches) => matches,
+        Err(err) => bail!("{err}"),
+    };
+    let mut transformer = ShExTransformer::new(&mapping);
+    let mut transformed = SRDFGraph::new();
+    transformer.transform(&matches, &source_json, &target_json, &mut transformed)?;
+    for warning in transformer.warnings() {
+        warn!("{warning}")
+    }
+    if check_target {
+        let mut target: CompiledSchema = CompiledSchema::new();
+        target.from_schema_json(&target_json)?;
+        let mut target_validator = Validator::new(target, config);
+        let mut failed = Vec::new();
+        for (node, shape) in transformer.target_shapemap() {
+            if let Err(err) = target_validator.validate_node_shape(node, shape, &transformed) {
+                bail!("{err}");
             }
-            Err(err) => {
-                bail!("Error generating result_map after validation: {err}");
+            match target_validator.get_result(node, shape) {
+                Ok(ResultValue::Ok) => {}
+                Ok(_) => failed.push(format!("{node}@{shape}")),
+                Err(err) => bail!("{err}"),
             }
-        },
-        Result::Err(err) => {
-            bail!("{err}");
+        }
+        if !failed.is_empty() {
+            bail!(
+                "Transformed nodes don't conform to the target schema: {}",
+                failed.join(", ")
+            )
         }
     }
+    let (writer, _color) = get_writer(output, force_overwrite)?;
+    transformed.serialize(RDFFormat::from(*result_format), writer)?;
+    Ok(())
+}
+
+fn validate_shex_mode<S: SRDF>(
+    validator: &mut Validator,
+    shex_mode: &ShExValidationMode,
+    rdf: &S,
+) -> Result<(), ValidatorError> {
+    match shex_mode {
+        ShExValidationMode::ShapeMap => Ok(()),
+        ShExValidationMode::Types => validator.validate_type_shapes(rdf),
+        ShExValidationMode::Subjects => validator.validate_subjects_start(rdf),
+        ShExValidationMode::Best => validator.validate_best_shapes(rdf),
+    }
+}
+
+fn get_validator_config(config: &Option<PathBuf>) -> Result<ValidatorConfig> {
+    match config {
+        Some(config_path) => match ValidatorConfig::from_path(config_path) {
+            Ok(c) => Ok(c),
+            Err(e) => Err(anyhow!(
+                "Error obtaining ShEx validation confir from {}: {e}",
+                config_path.display()
+            )),
+        },
+        None => Ok(ValidatorConfig::default()),
+    }
 }
 
 #[allow(clippy::too_many_arguments)]
 fn run_validate_shacl(
-    input: &InputSpec,
+    input: Option<&InputSpec>,
     shapes_format: &ShaclFormat,
+    imports_dir: &[PathBuf],
     data: &Vec<InputSpec>,
     data_format: &DataFormat,
+    reader_mode: &RDFReaderMode,
     endpoint: &Option<String>,
+    graph: &Option<String>,
+    store: &Option<PathBuf>,
     mode: ShaclValidationMode,
+    entailment: Entailment,
+    summary: &Option<SummaryFormat>,
+    junit: &Option<PathBuf>,
+    framed: bool,
     _debug: u8,
     output: &Option<PathBuf>,
     force_overwrite: bool,
-) -> Result<()> {
+) -> Result<bool> {
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
 
+    if let Some(store) = store {
+        let Some(input) = input else {
+            bail!("Please provide the shapes graph, sh:shapesGraph declarations are not read from stores")
+        };
+        if endpoint.is_some() {
+            bail!("Only one of 'endpoint' or 'store' supported at the same time")
+        }
+        if entailment != Entailment::None {
+            bail!("Entailment regimes are only supported for in-memory data, not for stores")
+        }
+        if graph.is_some() {
+            bail!("Named graphs are not supported by stores, the graphs of the data are merged when it is loaded")
+        }
+        if framed {
+            bail!("Framed output is only supported for in-memory data, not for stores")
+        }
+        let schema = read_shacl_shapes(input, shapes_format, imports_dir)?;
+        let store = Persistent::from_store(load_store(store, data, data_format)?);
+        let validator = match PersistentValidator::from_store(store, mode) {
+            Ok(validator) => validator,
+            Err(e) => bail!("Error during the creation of the store: {e}"),
+        };
+        let result = match shacl_validation::validate::Validator::validate(&validator, &schema) {
+            Ok(result) => result,
+            Err(e) => bail!("Error validating the store: {e}"),
+        };
+        return show_shacl_report(&mut writer, &result, summary, junit, force_overwrite);
+    }
+
     // TODO: Remove the following cast by refactoring the validate_shex to support more types of data
     let data = cast_to_data_path(data)?;
-    let reader = input.open_read()?;
 
-    let schema = ShaclDataManager::load(
-        reader,
-        match shapes_format {
-            ShaclFormat::Internal => todo!(),
-            ShaclFormat::Turtle => srdf::RDFFormat::Turtle,
-            ShaclFormat::NTriples => srdf::RDFFormat::NTriples,
-            ShaclFormat::RDFXML => srdf::RDFFormat::RDFXML,
-            ShaclFormat::TriG => srdf::RDFFormat::TriG,
-            ShaclFormat::N3 => srdf::RDFFormat::N3,
-            ShaclFormat::NQuads => srdf::RDFFormat::NQuads,
-        },
-        None,
-    )?;
+    let schema = match input {
+        Some(input) => read_shacl_shapes(input, shapes_format, imports_dir)?,
+        None => {
+            let Some(data) = &data else {
+                bail!("Please provide the shapes graph, sh:shapesGraph declarations are only read from local data")
+            };
+            let data_graph = parse_data(
+                &vec![InputSpec::Path(data.clone())],
+                data_format,
+                reader_mode,
+            )?;
+            let resolver = import_resolver(&InputSpec::Path(data.clone()), imports_dir);
+            match declared_shapes_graph(&data_graph, &resolver)? {
+                Some(shapes) => ShaclDataManager::parse(shapes)?,
+                None => bail!(
+                    "Please provide the shapes graph, the data in {} doesn't declare any with sh:shapesGraph",
+                    data.display()
+                ),
+            }
+        }
+    };
 
     if let Some(data) = data {
-        let validator = match GraphValidator::new(
-            &data,
-            match data_format {
-                DataFormat::Turtle => srdf::RDFFormat::Turtle,
-                DataFormat::NTriples => srdf::RDFFormat::NTriples,
-                DataFormat::RDFXML => srdf::RDFFormat::RDFXML,
-                DataFormat::TriG => srdf::RDFFormat::TriG,
-                DataFormat::N3 => srdf::RDFFormat::N3,
-                DataFormat::NQuads => srdf::RDFFormat::NQuads,
-            },
-            None,
-            mode,
-        ) {
+        let mut data_graph = parse_data(
+            &vec![InputSpec::Path(data.clone())],
+            data_format,
+            reader_mode,
+        )?;
+        if let Some(graph) = graph {
+            select_graph(&mut data_graph, graph)?;
+        }
+        let frame = if framed {
+            Some((JsonLdFrame::from_shacl(&schema)?, data_graph.clone()))
+        } else {
+            None
+        };
+        let validator = match GraphValidator::from_graph(data_graph, mode)
+            .and_then(|validator| validator.with_entailment(entailment))
+        {
             Ok(validator) => validator,
             Err(e) => bail!("Error during the creation of the Graph: {e}"),
         };
-        let result = match shacl_validation::validate::Validator::validate(&validator, schema) {
+        let result = match shacl_validation::validate::Validator::validate(&validator, &schema) {
             Ok(result) => result,
             Err(e) => bail!("Error validating the graph: {e}"),
         };
-        writeln!(writer, "Result:\n{}", result)?;
-        Ok(())
+        match frame {
+            Some((frame, data_graph)) => {
+                write_junit(
+                    &ValidationSummary::from_shacl_report(&result),
+                    junit,
+                    force_overwrite,
+                )?;
+                show_shacl_framed(&mut writer, &frame, &result, &data_graph)
+            }
+            None => show_shacl_report(&mut writer, &result, summary, junit, force_overwrite),
+        }
     } else if let Some(endpoint) = endpoint {
+        if entailment != Entailment::None {
+            bail!("Entailment regimes are only supported for local data, not for endpoints")
+        }
+        if graph.is_some() {
+            bail!("Named graphs are only supported for local data, not for endpoints")
+        }
+        if framed {
+            bail!("Framed output is only supported for local data, not for endpoints")
+        }
         let validator = match SparqlValidator::new(endpoint, mode) {
             Ok(validator) => validator,
             Err(e) => bail!("Error during the creation of the Graph: {e}"),
         };
-        let result = match shacl_validation::validate::Validator::validate(&validator, schema) {
+        let result = match shacl_validation::validate::Validator::validate(&validator, &schema) {
             Ok(result) => result,
             Err(e) => bail!("Error validating the graph: {e}"),
         };
-        writeln!(writer, "Result:\n{}", result)?;
-        Ok(())
+        show_shacl_report(&mut writer, &result, summary, junit, force_overwrite)
     } else {
         bail!("Please provide either a local data source or an endpoint")
     }
 }
 
+/// Shapes graph of the input together with the graphs that it imports
+fn read_shacl_shapes(
+    input: &InputSpec,
+    shapes_format: &ShaclFormat,
+    imports_dir: &[PathBuf],
+) -> Result<ShaclSchema> {
+    let resolver = import_resolver(input, imports_dir);
+    Ok(ShaclDataManager::load_with_imports(
+        input.open_read()?,
+        match shapes_format {
+            ShaclFormat::Internal => todo!(),
+            ShaclFormat::Turtle => srdf::RDFFormat::Turtle,
+            ShaclFormat::NTriples => srdf::RDFFormat::NTriples,
+            ShaclFormat::RDFXML => srdf::RDFFormat::RDFXML,
+            ShaclFormat::TriG => srdf::RDFFormat::TriG,
+            ShaclFormat::N3 => srdf::RDFFormat::N3,
+            ShaclFormat::NQuads => srdf::RDFFormat::NQuads,
+            ShaclFormat::JsonLd => srdf::RDFFormat::JsonLd,
+        },
+        None,
+        &resolver,
+    )?)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn run_shacl_infer(
+    data: &Vec<InputSpec>,
+    shapes: Option<&InputSpec>,
+    shapes_format: &ShaclFormat,
+    data_format: &DataFormat,
+    reader_mode: &RDFReaderMode,
+    include_data: bool,
+    max_iterations: Option<usize>,
+    result_format: &DataFormat,
+    output: &Option<PathBuf>,
+    force_overwrite: bool,
+) -> Result<()> {
+    let (writer, _color) = get_writer(output, force_overwrite)?;
+    let mut graph = parse_data(data, data_format, reader_mode)?;
+    let schema = match shapes {
+        Some(shapes) => parse_shacl(shapes, shapes_format, reader_mode)?,
+        None => ShaclParser::new(graph.clone()).parse()?,
+    };
+    let mut engine = RuleEngine::new(&schema);
+    if let Some(max_iterations) = max_iterations {
+        engine = engine.with_max_iterations(max_iterations);
+    }
+    let inferred = engine.infer(&mut graph)?;
+    let result = if include_data { graph } else { inferred };
+    result.serialize(RDFFormat::from(*result_format), writer)?;
+    Ok(())
+}
+
+/// Resolver of the graphs imported by the shapes, which looks for them next to the input and in the imports folders
+fn import_resolver(input: &InputSpec, imports_dir: &[PathBuf]) -> LocalResolver {
+    let mut resolver = LocalResolver::new();
+    if let InputSpec::Path(path) = input {
+        if let Some(folder) = path.parent() {
+            resolver = resolver.with_folder(folder)
+        }
+    }
+    imports_dir
+        .iter()
+        .fold(resolver, |resolver, folder| resolver.with_folder(folder))
+}
+
+fn show_shacl_report<S: SRDFBasic>(
+    writer: &mut Box<dyn Write>,
+    report: &ValidationReport<S>,
+    summary: &Option<SummaryFormat>,
+    junit: &Option<PathBuf>,
+    force_overwrite: bool,
+) -> Result<bool> {
+    let validation_summary = ValidationSummary::from_shacl_report(report);
+    match summary {
+        Some(summary_format) => show_summary(writer, &validation_summary, summary_format)?,
+        None => writeln!(writer, "Result:\n{}", report)?,
+    }
+    write_junit(&validation_summary, junit, force_overwrite)?;
+    Ok(report.conforms())
+}
+
+/// Writes the target nodes without validation results as JSON-LD framed by their shapes
+fn show_shacl_framed<S: SRDF>(
+    writer: &mut Box<dyn Write>,
+    frame: &JsonLdFrame,
+    report: &ValidationReport<S>,
+    data: &S,
+) -> Result<bool> {
+    let failed: HashSet<Object> = report
+        .results()
+        .iter()
+        .filter_map(|result| result.focus_node())
+        .map(|node| S::term_as_object(&node))
+        .collect();
+    let framer = JsonLdFramer::new(frame);
+    let nodes: Vec<_> = framer
+        .target_nodes(data)?
+        .into_iter()
+        .filter(|(node, _)| !failed.contains(node))
+        .collect();
+    let framed = framer.frame(&nodes, data)?;
+    writeln!(writer, "{}", serde_json::to_string_pretty(&framed)?)?;
+    Ok(report.conforms())
+}
+
+fn show_summary(
+    writer: &mut Box<dyn Write>,
+    validation_summary: &ValidationSummary,
+    summary_format: &SummaryFormat,
+) -> Result<()> {
+    match summary_format {
+        SummaryFormat::JSON => writeln!(
+            writer,
+            "{}",
+            serde_json::to_string_pretty(&validation_summary.as_json())?
+        )?,
+    }
+    Ok(())
+}
+
+fn write_junit(
+    validation_summary: &ValidationSummary,
+    junit: &Option<PathBuf>,
+    force_overwrite: bool,
+) -> Result<()> {
+    if let Some(junit_path) = junit {
+        let (mut junit_writer, _color) = get_writer(&Some(junit_path.clone()), force_overwrite)?;
+        write!(junit_writer, "{}", validation_summary.as_junit())?;
+    }
+    Ok(())
+}
+
+fn exit_if_nonconformant(conforms: bool) {
+    if !conforms {
+        std::process::exit(NONCONFORMANT_EXIT_CODE)
+    }
+}
+
 fn run_shacl(
     input: &InputSpec,
     shapes_format: &ShaclFormat,
@@ -582,6 +1377,7 @@ fn run_dctap(
     result_format: &DCTapResultFormat,
     output: &Option<PathBuf>,
     config: &Option<PathBuf>,
+    lint: bool,
     force_overwrite: bool,
 ) -> Result<()> {
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
@@ -590,6 +1386,9 @@ fn run_dctap(
         None => Ok(TapConfig::default()),
     }?;
     let dctap = parse_dctap(input, format, &tap_config)?;
+    if lint {
+        return run_dctap_lint(&dctap, result_format, &mut writer);
+    }
     match result_format {
         DCTapResultFormat::Internal => {
             writeln!(writer, "{dctap}")?;
@@ -604,6 +1403,31 @@ fn run_dctap(
     }
 }
 
+fn run_dctap_lint(
+    dctap: &DCTap,
+    result_format: &DCTapResultFormat,
+    writer: &mut Box<dyn Write>,
+) -> Result<()> {
+    let findings = dctap::lint(dctap, &PrefixCC::default());
+    match result_format {
+        DCTapResultFormat::Internal => {
+            for finding in findings.iter() {
+                writeln!(writer, "{finding}")?;
+            }
+        }
+        DCTapResultFormat::JSON => {
+            let str = serde_json::to_string_pretty(&findings)
+                .context("Error converting lint findings to JSON")?;
+            writeln!(writer, "{str}")?;
+        }
+    }
+    if findings.is_empty() {
+        Ok(())
+    } else {
+        bail!("DCTAP profile has {} problems", findings.len())
+    }
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_convert(
     input: &InputSpec,
@@ -618,14 +1442,46 @@ fn run_convert(
     force_overwrite: bool,
     reader_mode: &RDFReaderMode,
 ) -> Result<()> {
-    // let mut writer = get_writer(output)?;
     let converter_config = match config {
         None => Ok(ConverterConfig::default()),
         Some(config_path) => ConverterConfig::from_path(config_path),
     }?;
+    let (writer, color) = get_writer(output, force_overwrite)?;
+    convert(
+        input,
+        format,
+        input_mode,
+        maybe_shape_str,
+        result_format,
+        output_mode,
+        target_folder,
+        &converter_config,
+        reader_mode,
+        writer,
+        color,
+    )
+}
+
+/// Converts between shapes formalisms writing the result to `writer`
+///
+/// Conversions to HTML write their pages to `target_folder` instead
+#[allow(clippy::too_many_arguments)]
+fn convert(
+    input: &InputSpec,
+    format: &InputConvertFormat,
+    input_mode: &InputConvertMode,
+    maybe_shape_str: &Option<String>,
+    result_format: &OutputConvertFormat,
+    output_mode: &OutputConvertMode,
+    target_folder: &Option<PathBuf>,
+    converter_config: &ConverterConfig,
+    reader_mode: &RDFReaderMode,
+    writer: Box<dyn Write>,
+    color: ColorSupport,
+) -> Result<()> {
     match (input_mode, output_mode) {
         (InputConvertMode::DCTAP, OutputConvertMode::ShEx) => {
-            run_tap2shex(input, format, output, result_format, &converter_config, force_overwrite)
+            run_tap2shex(input, format, writer, color, result_format, converter_config)
         }
         (InputConvertMode::ShEx, OutputConvertMode::SPARQL) => {
             let maybe_shape = match maybe_shape_str {
@@ -635,13 +1491,13 @@ fn run_convert(
                     Some(iri_shape)
                 }
             };
-            run_shex2sparql(input, format, maybe_shape, output, result_format, &converter_config.shex2sparql_config(), force_overwrite, reader_mode)
+            run_shex2sparql(input, format, maybe_shape, writer, result_format, &converter_config.shex2sparql_config(), reader_mode)
         }
         (InputConvertMode::ShEx, OutputConvertMode::UML) => {
-            run_shex2uml(input, format, output, result_format, &converter_config.shex2uml_config(), force_overwrite, reader_mode)
+            run_shex2uml(input, format, writer, result_format, &converter_config.shex2uml_config(), reader_mode)
         }
         (InputConvertMode::SHACL, OutputConvertMode::ShEx) => {
-            run_shacl2shex(input, format, output, result_format, &converter_config.shacl2shex_config(), force_overwrite, reader_mode)
+            run_shacl2shex(input, format, writer, color, result_format, &converter_config.shacl2shex_config(), reader_mode)
         }
         (InputConvertMode::ShEx, OutputConvertMode::HTML) => {
             match target_folder {
@@ -653,8 +1509,14 @@ fn run_convert(
                 }
             }
         }
+        (InputConvertMode::ShEx, OutputConvertMode::DCTAP) => {
+            run_shex2tap(input, format, writer, result_format, converter_config, reader_mode)
+        }
+        (InputConvertMode::SHACL, OutputConvertMode::DCTAP) => {
+            run_shacl2tap(input, format, writer, result_format, converter_config, reader_mode)
+        }
         (InputConvertMode::DCTAP, OutputConvertMode::UML, ) => {
-            run_tap2uml(input, format, output, result_format, &converter_config, force_overwrite)
+            run_tap2uml(input, format, writer, result_format, converter_config)
         }
         (InputConvertMode::DCTAP, OutputConvertMode::HTML) => {
             match target_folder {
@@ -662,7 +1524,7 @@ fn run_convert(
             "Conversion from DCTAP to HTML requires an output parameter to indicate where to write the generated HTML files"
                 )),
                 Some(output_path) => {
-                    run_tap2html(input, format, output_path, &converter_config)
+                    run_tap2html(input, format, output_path, converter_config)
                 }
             }
         }
@@ -672,13 +1534,59 @@ fn run_convert(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
+fn run_generate(
+    schema: &InputSpec,
+    schema_mode: &InputConvertMode,
+    schema_format: &Option<InputConvertFormat>,
+    config: &DataGeneratorConfig,
+    result_format: &DataFormat,
+    output: &Option<PathBuf>,
+    force_overwrite: bool,
+    reader_mode: &RDFReaderMode,
+) -> Result<()> {
+    let mut rdf = SRDFGraph::new();
+    match schema_mode {
+        InputConvertMode::ShEx => {
+            let schema_format = match schema_format.unwrap_or(InputConvertFormat::ShExC) {
+                InputConvertFormat::ShExC => ShExFormat::ShExC,
+                InputConvertFormat::ShExJ => ShExFormat::ShExJ,
+                InputConvertFormat::Turtle => ShExFormat::Turtle,
+                format => bail!("Can't obtain ShEx format from {format}"),
+            };
+            let schema = parse_schema(schema, &schema_format, reader_mode)?;
+            let mut generator = ShEx2Data::new(config);
+            generator.generate(&schema, &mut rdf)?;
+            for warning in generator.warnings() {
+                warn!("{warning}")
+            }
+        }
+        InputConvertMode::SHACL => {
+            let shapes_format = match schema_format.unwrap_or(InputConvertFormat::Turtle) {
+                InputConvertFormat::Turtle => ShaclFormat::Turtle,
+                format => bail!("Can't obtain SHACL format from {format}"),
+            };
+            let schema = parse_shacl(schema, &shapes_format, reader_mode)?;
+            let mut generator = Shacl2Data::new(config);
+            generator.generate(&schema, &mut rdf)?;
+            for warning in generator.warnings() {
+                warn!("{warning}")
+            }
+        }
+        InputConvertMode::DCTAP => bail!("Data generation from {schema_mode} is not supported"),
+    }
+    let (writer, _color) = get_writer(output, force_overwrite)?;
+    rdf.serialize(RDFFormat::from(*result_format), writer)?;
+    Ok(())
+}
+
 fn run_shacl2shex(
     input: &InputSpec,
     format: &InputConvertFormat,
-    output: &Option<PathBuf>,
+    writer: Box<dyn Write>,
+    color: ColorSupport,
     result_format: &OutputConvertFormat,
     config: &Shacl2ShExConfig,
-    force_overwrite: bool,
     reader_mode: &RDFReaderMode,
 ) -> Result<()> {
     let schema_format = match format {
@@ -688,7 +1596,6 @@ fn run_shacl2shex(
     let schema = parse_shacl(input, &schema_format, reader_mode)?;
     let mut converter = Shacl2ShEx::new(config);
     converter.convert(&schema)?;
-    let (writer, color) = get_writer(output, force_overwrite)?;
     let result_schema_format = match &result_format {
         OutputConvertFormat::Default => ShExFormat::ShExC,
         OutputConvertFormat::Internal => ShExFormat::Internal,
@@ -707,13 +1614,86 @@ fn run_shacl2shex(
     Ok(())
 }
 
+fn run_shex2tap(
+    input: &InputSpec,
+    format: &InputConvertFormat,
+    writer: Box<dyn Write>,
+    result_format: &OutputConvertFormat,
+    config: &ConverterConfig,
+    reader_mode: &RDFReaderMode,
+) -> Result<()> {
+    let schema_format = match format {
+        InputConvertFormat::ShExC => Ok(ShExFormat::ShExC),
+        _ => Err(anyhow!("Can't obtain ShEx format from {format}")),
+    }?;
+    let schema = parse_schema(input, &schema_format, reader_mode)?;
+    let mut converter = ShEx2Tap::new(&config.shex2tap_config());
+    converter.convert(&schema)?;
+    for warning in converter.warnings() {
+        warn!("{warning}")
+    }
+    write_dctap(
+        converter.current_tap(),
+        result_format,
+        &config.tap_config(),
+        writer,
+    )
+}
+
+fn run_shacl2tap(
+    input: &InputSpec,
+    format: &InputConvertFormat,
+    writer: Box<dyn Write>,
+    result_format: &OutputConvertFormat,
+    config: &ConverterConfig,
+    reader_mode: &RDFReaderMode,
+) -> Result<()> {
+    let schema_format = match format {
+        InputConvertFormat::Turtle => Ok(ShaclFormat::Turtle),
+        _ => Err(anyhow!("Can't obtain SHACL format from {format}")),
+    }?;
+    let schema = parse_shacl(input, &schema_format, reader_mode)?;
+    let mut converter = Shacl2Tap::new(&config.shacl2tap_config());
+    converter.convert(&schema)?;
+    for warning in converter.warnings() {
+        warn!("{warning}")
+    }
+    write_dctap(
+        converter.current_tap(),
+        result_format,
+        &config.tap_config(),
+        writer,
+    )
+}
+
+fn write_dctap(
+    dctap: &DCTap,
+    result_format: &OutputConvertFormat,
+    tap_config: &TapConfig,
+    writer: Box<dyn Write>,
+) -> Result<()> {
+    let tap_writer = TapWriter::new(tap_config);
+    match result_format {
+        OutputConvertFormat::Default | OutputConvertFormat::CSV => {
+            tap_writer.write_csv(dctap, writer)?;
+            Ok(())
+        }
+        OutputConvertFormat::XLSX => {
+            tap_writer.write_xlsx(dctap, writer)?;
+            Ok(())
+        }
+        _ => Err(anyhow!(
+            "Conversion to DCTAP does not support output format {result_format}"
+        )),
+    }
+}
+
 fn run_shex2uml(
     input: &InputSpec,
     format: &InputConvertFormat,
-    output: &Option<PathBuf>,
+    mut writer: Box<dyn Write>,
     result_format: &OutputConvertFormat,
     config: &ShEx2UmlConfig,
-    force_overwrite: bool,
     reader_mode: &RDFReaderMode,
 ) -> Result<()> {
     let schema_format = match format {
@@ -723,7 +1703,6 @@ fn run_shex2uml(
     let schema = parse_schema(input, &schema_format, reader_mode)?;
     let mut converter = ShEx2Uml::new(config);
     converter.convert(&schema)?;
-    let (mut writer, _color) = get_writer(output, force_overwrite)?;
     generate_uml_output(converter, &mut writer, result_format)?;
     Ok(())
 }
@@ -798,7 +1777,9 @@ fn run_tap2html<P: AsRef<Path>>(
     let shex = converter_tap.convert(&dctap)?;
     debug!(
         "Converted ShEx: {}",
-        ShExFormatter::default().format_schema(&shex)
+        ShExFormatter::default()
+            .format_schema(&shex)
+            .unwrap_or_else(|e| e.to_string())
     );
     let shex2html_config = config
         .shex2html_config()
@@ -822,10 +1803,9 @@ fn run_shex2sparql(
     input: &InputSpec,
     format: &InputConvertFormat,
     shape: Option<IriRef>,
-    output: &Option<PathBuf>,
+    mut writer: Box<dyn Write>,
     _result_format: &OutputConvertFormat,
     config: &ShEx2SparqlConfig,
-    force_overwrite: bool,
     reader_mode: &RDFReaderMode,
 ) -> Result<()> {
     let schema_format = match format {
@@ -835,7 +1815,6 @@ fn run_shex2sparql(
     let schema = parse_schema(input, &schema_format, reader_mode)?;
     let converter = ShEx2Sparql::new(config);
     let sparql = converter.convert(&schema, shape)?;
-    let (mut writer, _color) = get_writer(output, force_overwrite)?;
     write!(writer, "{}", sparql)?;
     Ok(())
 }
@@ -843,10 +1822,10 @@ fn run_shex2sparql(
 fn run_tap2shex(
     input_path: &InputSpec,
     format: &InputConvertFormat,
-    output: &Option<PathBuf>,
+    writer: Box<dyn Write>,
+    color: ColorSupport,
     result_format: &OutputConvertFormat,
     config: &ConverterConfig,
-    force_overwrite: bool,
 ) -> Result<()> {
     let tap_format = match format {
         InputConvertFormat::CSV => Ok(DCTapFormat::CSV),
@@ -862,7 +1841,6 @@ fn run_tap2shex(
         OutputConvertFormat::Turtle => Ok(ShExFormat::Turtle),
         _ => Err(anyhow!("Can't write ShEx in {result_format} format")),
     }?;
-    let (writer, color) = get_writer(output, force_overwrite)?;
     show_schema(&shex, &result_schema_format, writer, color)?;
     Ok(())
 }
@@ -870,10 +1848,9 @@ fn run_tap2shex(
 fn run_tap2uml(
     input_path: &InputSpec,
     format: &InputConvertFormat,
-    output: &Option<PathBuf>,
+    mut writer: Box<dyn Write>,
     result_format: &OutputConvertFormat,
     config: &ConverterConfig,
-    force_overwrite: bool,
 ) -> Result<()> {
     let tap_format = match format {
         InputConvertFormat::CSV => Ok(DCTapFormat::CSV),
@@ -884,7 +1861,6 @@ fn run_tap2uml(
     let shex = converter_shex.convert(&dctap)?;
     let mut converter_uml = ShEx2Uml::new(&config.shex2uml_config());
     converter_uml.convert(&shex)?;
-    let (mut writer, _color) = get_writer(output, force_overwrite)?;
     generate_uml_output(converter_uml, &mut writer, result_format)?;
     Ok(())
 }
@@ -929,9 +1905,16 @@ fn get_data(
     data: &Vec<InputSpec>,
     data_format: &DataFormat,
     endpoint: &Option<String>,
+    store: &Option<PathBuf>,
     reader_mode: &RDFReaderMode,
     _debug: u8,
 ) -> Result<Data> {
+    if let Some(store) = store {
+        if endpoint.is_some() {
+            bail!("Only one of 'endpoint' or 'store' supported at the same time")
+        }
+        return Ok(Data::Store(load_store(store, data, data_format)?));
+    }
     match (data.is_empty(), endpoint) {
         (true, None) => {
             bail!("None of `data` or `endpoint` parameters have been specified for validation")
@@ -951,6 +1934,25 @@ fn get_data(
     }
 }
 
+/// Opens a persistent store and loads the data into it, so the next runs only need the store
+/// Opens a store loading the data that hasn't been loaded in it before
+///
+/// Files that haven't changed and URLs are only loaded once, while standard input is always loaded
+fn load_store(dir: &Path, data: &Vec<InputSpec>, data_format: &DataFormat) -> Result<SRDFStore> {
+    let mut store = SRDFStore::open(dir)?;
+    let format = RDFFormat::from(*data_format);
+    for d in data {
+        match d.source_key()? {
+            Some(key) if store.is_loaded(&key) => {
+                debug!("Skipping {d:?}, which was already loaded in the store")
+            }
+            Some(key) => store.load_source(&key, d.open_read()?, &format, None)?,
+            None => store.load_reader(d.open_read()?, &format, None)?,
+        }
+    }
+    Ok(store)
+}
+
 /*fn make_node_selector(node: Node) -> Result<NodeSelector> {
     let object = node.as_object();
     match object {
@@ -969,6 +1971,16 @@ fn start() -> ShapeSelector {
     ShapeSelector::start()
 }
 
+/// Options of the node explorer which select what is collected and how it is shown
+struct NodeExplorerOptions {
+    depth: usize,
+    path: Option<String>,
+    shape: Option<String>,
+    schema: Option<InputSpec>,
+    schema_format: ShExFormat,
+    result_format: ShowNodeFormat,
+}
+
 #[allow(clippy::too_many_arguments)]
 fn run_node(
     data: &Vec<InputSpec>,
@@ -979,13 +1991,35 @@ fn run_node(
     predicates: &Vec<String>,
     show_node_mode: &ShowNodeMode,
     show_hyperlinks: &bool,
+    options: &NodeExplorerOptions,
     debug: u8,
     output: &Option<PathBuf>,
-    _config: &Option<PathBuf>,
+    config: &Option<PathBuf>,
     force_overwrite: bool,
 ) -> Result<()> {
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
-    let data = get_data(data, data_format, endpoint, reader_mode, debug)?;
+    let schema = match (&options.shape, &options.schema) {
+        (None, _) => None,
+        (Some(_), None) => bail!("The option --shape requires a ShEx schema (--schema)"),
+        (Some(shape_str), Some(schema)) => {
+            if options.path.is_some() {
+                bail!("The options --shape and --path can't be used at the same time")
+            }
+            let schema_json = parse_schema(schema, &options.schema_format, reader_mode)?;
+            let mut schema = CompiledSchema::new();
+            schema.from_schema_json(&schema_json)?;
+            let idx = match parse_shape_label(shape_str)?.iter_shape().next() {
+                Some(label) => schema.find_ref(label)?,
+                None => bail!("No shape label found in {shape_str}"),
+            };
+            let label = match schema.find_shape_idx(&idx) {
+                Some((label, _)) => label.clone(),
+                None => bail!("Shape with index {idx} not found in schema"),
+            };
+            Some((Arc::new(schema), label, get_validator_config(config)?))
+        }
+    };
+    let data = get_data(data, data_format, endpoint, &None, reader_mode, debug)?;
     let node_selector = parse_node_selector(node_str)?;
     match data {
         Data::Endpoint(endpoint) => show_node_info(
@@ -994,6 +2028,8 @@ fn run_node(
             &endpoint,
             show_node_mode,
             show_hyperlinks,
+            options,
+            &schema,
             &mut writer,
         ),
         Data::RDFData(data) => show_node_info(
@@ -1002,85 +2038,69 @@ fn run_node(
             &data,
             show_node_mode,
             show_hyperlinks,
+            options,
+            &schema,
+            &mut writer,
+        ),
+        Data::Store(store) => show_node_info(
+            node_selector,
+            predicates,
+            &store,
+            show_node_mode,
+            show_hyperlinks,
+            options,
+            &schema,
             &mut writer,
         ),
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn show_node_info<S, W: Write>(
     node_selector: NodeSelector,
     predicates: &Vec<String>,
     rdf: &S,
     show_node_mode: &ShowNodeMode,
-    _show_hyperlinks: &bool,
+    show_hyperlinks: &bool,
+    options: &NodeExplorerOptions,
+    schema: &Option<(Arc<CompiledSchema>, ShapeLabel, ValidatorConfig)>,
     writer: &mut W,
 ) -> Result<()>
 where
     S: SRDF,
 {
-    for node in node_selector.iter_node(rdf) {
-        let subject = node_to_subject(node, rdf)?;
-        writeln!(writer, "Information about node")?;
-
-        // Show outgoing arcs
-        match show_node_mode {
-            ShowNodeMode::Outgoing | ShowNodeMode::Both => {
-                writeln!(writer, "Outgoing arcs")?;
-                let map = if predicates.is_empty() {
-                    match rdf.outgoing_arcs(&subject) {
-                        Result::Ok(rs) => rs,
-                        Err(e) => bail!("Error obtaining outgoing arcs of {subject}: {e}"),
-                    }
-                } else {
-                    let preds = cnv_predicates(predicates, rdf)?;
-                    match rdf.outgoing_arcs_from_list(&subject, preds) {
-                        Result::Ok((rs, _)) => rs,
-                        Err(e) => bail!("Error obtaining outgoing arcs of {subject}: {e}"),
-                    }
-                };
-                writeln!(writer, "{}", rdf.qualify_subject(&subject))?;
-                for pred in map.keys() {
-                    writeln!(writer, " -{}-> ", rdf.qualify_iri(pred))?;
-                    if let Some(objs) = map.get(pred) {
-                        for o in objs {
-                            writeln!(writer, "      {}", rdf.qualify_term(o))?;
-                        }
-                    } else {
-                        bail!("Not found values for {pred} in map")
-                    }
-                }
-            }
-            _ => {
-                // Nothing to do
-            }
-        }
-
-        // Show incoming arcs
-        match show_node_mode {
-            ShowNodeMode::Incoming | ShowNodeMode::Both => {
-                writeln!(writer, "Incoming arcs")?;
-                let object = S::subject_as_term(&subject);
-                let map = match rdf.incoming_arcs(&object) {
-                    Result::Ok(m) => m,
-                    Err(e) => bail!("Can't get outgoing arcs of node {subject}: {e}"),
-                };
-                writeln!(writer, "{}", rdf.qualify_term(&object))?;
-                for pred in map.keys() {
-                    writeln!(writer, "  <-{}-", rdf.qualify_iri(pred))?;
-                    if let Some(subjs) = map.get(pred) {
-                        for s in subjs {
-                            writeln!(writer, "      {}", rdf.qualify_subject(s))?;
-                        }
-                    } else {
-                        bail!("Not found values for {pred} in map")
-                    }
-                }
-            }
-            _ => {
-                // Nothing to do
+    let path = match &options.path {
+        Some(path_str) => Some(ShapeMapParser::parse_path(path_str)?.resolve(rdf)?),
+        None => None,
+    };
+    let preds = cnv_predicates(predicates, rdf)?;
+    let mut exploration = NodeExploration::new(rdf.prefixmap().unwrap_or_default());
+    for node in node_selector.select(rdf)? {
+        let term = S::subject_as_term(&node_to_subject::<S>(&node)?);
+        match (&path, schema) {
+            (Some(path), _) => exploration.explore_path(rdf, &term, path)?,
+            (None, Some((schema, shape, config))) => {
+                exploration.explore_shape(rdf, &term, schema, shape, config, options.depth)?
             }
+            (None, None) => exploration.explore_neighbourhood(
+                rdf,
+                &term,
+                show_node_mode,
+                &preds,
+                options.depth,
+            )?,
         }
     }
+    match options.result_format {
+        ShowNodeFormat::Text => exploration.write_text(writer, *show_hyperlinks)?,
+        ShowNodeFormat::Turtle => exploration.write_turtle(writer)?,
+        ShowNodeFormat::JSON => writeln!(
+            writer,
+            "{}",
+            serde_json::to_string_pretty(&exploration.as_json())?
+        )?,
+        ShowNodeFormat::DOT => exploration.write_dot(writer)?,
+    }
     Ok(())
 }
 
@@ -1125,27 +2145,13 @@ fn run_shapemap(
     }
 }
 
-fn node_to_subject<S>(node: &ObjectValue, rdf: &S) -> Result<S::Subject>
+fn node_to_subject<S>(node: &Node) -> Result<S::Subject>
 where
     S: SRDF,
 {
-    match node {
-        ObjectValue::IriRef(iri_ref) => {
-            let iri = match iri_ref {
-                IriRef::Iri(iri_s) => S::iri_s2iri(iri_s),
-                IriRef::Prefixed { prefix, local } => {
-                    let iri_s = rdf.resolve_prefix_local(prefix, local)?;
-
-                    S::iri_s2iri(&iri_s)
-                }
-            };
-            let term = S::iri_as_term(iri);
-            match S::term_as_subject(&term) {
-                None => bail!("node_to_subject: Can't convert term {term} to subject"),
-                Some(subject) => Ok(subject),
-            }
-        }
-        ObjectValue::Literal(_lit) => Err(anyhow!("Node must be an IRI")),
+    match S::object_as_subject(node.as_object()) {
+        None => bail!("node_to_subject: Can't convert node {node} to subject"),
+        Some(subject) => Ok(subject),
     }
 }
 
@@ -1159,19 +2165,30 @@ fn run_data(
     reader_mode: &RDFReaderMode,
 ) -> Result<()> {
     let (mut writer, _color) = get_writer(output, force_overwrite)?;
-    let data = get_data(data, data_format, &None, reader_mode, debug)?;
+    let data = get_data(data, data_format, &None, &None, reader_mode, debug)?;
     match data {
         Data::Endpoint(e) => writeln!(writer, "Endpoint {e:?}")?,
         Data::RDFData(graph) => graph.serialize(RDFFormat::from(*result_format), writer)?,
+        Data::Store(store) => store.serialize(RDFFormat::from(*result_format), writer)?,
     }
     Ok(())
 }
 
 fn parse_shapemap(shapemap_path: &Path, shapemap_format: &ShapeMapFormat) -> Result<QueryShapeMap> {
+    parse_shapemap_input(&InputSpec::path(shapemap_path), shapemap_format)
+}
+
+fn parse_shapemap_input(
+    input: &InputSpec,
+    shapemap_format: &ShapeMapFormat,
+) -> Result<QueryShapeMap> {
     match shapemap_format {
         ShapeMapFormat::Internal => Err(anyhow!("Cannot read internal ShapeMap format yet")),
         ShapeMapFormat::Compact => {
-            let shapemap = ShapeMapParser::parse_buf(shapemap_path, &None, &None)?;
+            let mut reader = input.open_read()?;
+            let mut src = String::new();
+            reader.read_to_string(&mut src)?;
+            let shapemap = ShapeMapParser::parse(&src, &None, &None)?;
             Ok(shapemap)
         }
     }
@@ -1194,12 +2211,12 @@ fn parse_schema(
             let schema_json = SchemaJson::from_reader(reader)?;
             Ok(schema_json)
         }
-        ShExFormat::Turtle => {
-            let rdf = parse_data(&vec![input.clone()], &DataFormat::Turtle, reader_mode)?;
+        _ => {
+            let data_format = shex_format_to_data_format(schema_format)?;
+            let rdf = parse_data(&vec![input.clone()], &data_format, reader_mode)?;
             let schema = ShExRParser::new(rdf).parse()?;
             Ok(schema)
         }
-        _ => Err(anyhow!("Not suppported parsing from {schema_format} yet")),
     }
 }
 
@@ -1212,7 +2229,8 @@ fn parse_shacl(
         ShaclFormat::Internal => Err(anyhow!("Cannot read internal ShEx format yet")),
         _ => {
             let data_format = shacl_format_to_data_format(shapes_format)?;
-            let rdf = parse_data(&vec![input.clone()], &data_format, reader_mode)?;
+            let mut rdf = parse_data(&vec![input.clone()], &data_format, reader_mode)?;
+            resolve_imports(&mut rdf, &import_resolver(input, &[]))?;
             let schema = ShaclParser::new(rdf).parse()?;
             Ok(schema)
         }
@@ -1229,6 +2247,18 @@ fn parse_dctap(input: &InputSpec, format: &DCTapFormat, config: &TapConfig) -> R
     }
 }
 
+fn shex_format_to_data_format(shex_format: &ShExFormat) -> Result<DataFormat> {
+    match shex_format {
+        ShExFormat::Turtle => Ok(DataFormat::Turtle),
+        ShExFormat::RDFXML => Ok(DataFormat::RDFXML),
+        ShExFormat::NTriples => Ok(DataFormat::NTriples),
+        ShExFormat::TriG => Ok(DataFormat::TriG),
+        ShExFormat::N3 => Ok(DataFormat::N3),
+        ShExFormat::NQuads => Ok(DataFormat::NQuads),
+        _ => bail!("Cannot convert ShEx format {shex_format} to RDF data format"),
+    }
+}
+
 fn shacl_format_to_data_format(shacl_format: &ShaclFormat) -> Result<DataFormat> {
     match shacl_format {
         ShaclFormat::Turtle => Ok(DataFormat::Turtle),
@@ -1237,6 +2267,7 @@ fn shacl_format_to_data_format(shacl_format: &ShaclFormat) -> Result<DataFormat>
         ShaclFormat::TriG => Ok(DataFormat::TriG),
         ShaclFormat::N3 => Ok(DataFormat::N3),
         ShaclFormat::NQuads => Ok(DataFormat::NQuads),
+        ShaclFormat::JsonLd => Ok(DataFormat::JsonLd),
         ShaclFormat::Internal => bail!("Cannot convert internal SHACL format to RDF data format"),
     }
 }
@@ -1254,15 +2285,57 @@ fn parse_data(
         DataFormat::RDFXML => RDFFormat::RDFXML,
         DataFormat::TriG => RDFFormat::TriG,
         DataFormat::Turtle => RDFFormat::Turtle,
+        DataFormat::JsonLd => {
+            graph = graph.with_jsonld_contexts(jsonld_context_loader(data));
+            RDFFormat::JsonLd
+        }
     };
     for d in data {
         use std::convert::Into;
         let reader = d.open_read()?;
-        graph.merge_from_reader(reader, &rdf_format, None, &(*reader_mode).into())?;
+        let base = match (&rdf_format, d) {
+            (RDFFormat::JsonLd, InputSpec::Path(path)) => file_base(path),
+            _ => None,
+        };
+        graph.merge_from_reader(reader, &rdf_format, base, &(*reader_mode).into())?;
     }
     Ok(graph)
 }
 
+/// Contexts of JSON-LD documents are never fetched from the network,
+/// they are looked up in the folders of the data files and in the current folder
+fn jsonld_context_loader(data: &[InputSpec]) -> JsonLdContextLoader {
+    let mut loader = JsonLdContextLoader::new();
+    for d in data {
+        if let InputSpec::Path(path) = d {
+            if let Some(folder) = path.parent() {
+                loader = loader.with_folder(folder);
+            }
+        }
+    }
+    match std::env::current_dir() {
+        Ok(cwd) => loader.with_folder(&cwd),
+        Err(_) => loader,
+    }
+}
+
+fn file_base(path: &Path) -> Option<Iri<String>> {
+    let path = std::fs::canonicalize(path).ok()?;
+    Iri::parse(format!("file://{}", path.display())).ok()
+}
+
+/// Selects the named graph of the data on which the validation is done
+fn select_graph(data: &mut SRDFGraph, graph: &str) -> Result<()> {
+    let iri = IriS::from_str(graph)?;
+    data.set_active_graph_iri(&iri).map_err(|e| {
+        let names = data
+            .graph_names()
+            .map(|names| names.iter().map(|n| n.to_string()).collect::<Vec<_>>())
+            .unwrap_or_default();
+        anyhow!("{e}. Available named graphs: [{}]", names.join(", "))
+    })
+}
+
 fn parse_node_selector(node_str: &str) -> Result<NodeSelector> {
     let ns = ShapeMapParser::parse_node_selector(node_str)?;
     Ok(ns)
@@ -1284,6 +2357,7 @@ fn cast_to_data_path(data: &Vec<InputSpec>) -> Result<Option<PathBuf>> {
             InputSpec::Path(path) => Ok(Some(path.clone())),
             InputSpec::Stdin => bail!("Not supported data from stdin yet"),
             InputSpec::Url(url) => bail!("Not supported data from url yet. Url: {url}"),
+            InputSpec::Str(_) => bail!("Not supported inline data yet"),
         },
         [] => Ok(None),
         _ => bail!("More than one value for data: {data:?}"),
@@ -0,0 +1,812 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::Write;
+
+use anyhow::{anyhow, bail, Result};
+use iri_s::IriS;
+use prefixmap::PrefixMap;
+use serde_json::{json, Value};
+use shex_ast::compiled::{compiled_schema::CompiledSchema, shape_label::ShapeLabel};
+use shex_ast::Node;
+use shex_validation::{ResultValue, ShapeMatch, Validator, ValidatorConfig};
+use srdf::srdf_graph::SRDFGraph;
+use srdf::{Object, RDFFormat, SHACLPath, SRDFBasic, SRDFBuilder, SRDF};
+
+use crate::ShowNodeMode;
+
+/// Triples collected while exploring the neighbourhood of some RDF nodes
+///
+/// Arcs keep the direction in which they were reached and, when the exploration is driven by a ShEx shape,
+/// the shapes that each triple matched. Nodes without a match of the shape are kept with their validation result
+pub struct NodeExploration {
+    prefixmap: PrefixMap,
+    nodes: Vec<Object>,
+    arcs: Vec<Arc>,
+    arcs_index: HashMap<(Object, IriS, Object, Direction), usize>,
+    paths: Vec<PathValues>,
+    unmatched: Vec<(Object, String, ResultValue)>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+enum Direction {
+    Outgoing,
+    Incoming,
+}
+
+struct Arc {
+    subject: Object,
+    predicate: IriS,
+    object: Object,
+    direction: Direction,
+    matches: Vec<String>,
+}
+
+/// Arcs grouped by focus node and predicate
+type ArcGroups<'a> = Vec<(&'a Object, Vec<(&'a IriS, Vec<&'a Arc>)>)>;
+
+struct PathValues {
+    node: Object,
+    path: SHACLPath,
+    values: Vec<Object>,
+}
+
+impl NodeExploration {
+    pub fn new(prefixmap: PrefixMap) -> NodeExploration {
+        NodeExploration {
+            prefixmap,
+            nodes: Vec::new(),
+            arcs: Vec::new(),
+            arcs_index: HashMap::new(),
+            paths: Vec::new(),
+            unmatched: Vec::new(),
+        }
+    }
+
+    /// Collects the arcs of `node` and of the nodes reachable from it up to `depth` steps
+    ///
+    /// If `preds` is not empty, only the outgoing arcs with those predicates are followed
+    pub fn explore_neighbourhood<S>(
+        &mut self,
+        rdf: &S,
+        node: &S::Term,
+        mode: &ShowNodeMode,
+        preds: &[S::IRI],
+        depth: usize,
+    ) -> Result<()>
+    where
+        S: SRDF,
+    {
+        self.nodes.push(S::term_as_object(node));
+        let mut visited = HashSet::from([node.clone()]);
+        let mut current = vec![node.clone()];
+        for _ in 0..depth {
+            let mut next = Vec::new();
+            for term in current.iter() {
+                let mut reached = Vec::new();
+                if matches!(mode, ShowNodeMode::Outgoing | ShowNodeMode::Both) {
+                    reached.extend(self.outgoing(rdf, term, preds)?);
+                }
+                if matches!(mode, ShowNodeMode::Incoming | ShowNodeMode::Both) {
+                    reached.extend(self.incoming(rdf, term)?);
+                }
+                for t in reached {
+                    if visited.insert(t.clone()) {
+                        next.push(t)
+                    }
+                }
+            }
+            current = next;
+        }
+        Ok(())
+    }
+
+    /// Collects the values reached from `node` following a SHACL `path` and the triples traversed to reach them
+    pub fn explore_path<S>(&mut self, rdf: &S, node: &S::Term, path: &SHACLPath) -> Result<()>
+    where
+        S: SRDF,
+    {
+        self.nodes.push(S::term_as_object(node));
+        let values = self.follow_path(rdf, &HashSet::from([node.clone()]), path, false)?;
+        let mut values: Vec<Object> = values.iter().map(S::term_as_object).collect();
+        values.sort_by_key(|v| v.to_string());
+        self.paths.push(PathValues {
+            node: S::term_as_object(node),
+            path: path.clone(),
+            values,
+        });
+        Ok(())
+    }
+
+    /// Validates `node` against `shape` and collects the arcs that took part in the match,
+    /// annotated with the shape that they matched and the shape that their value conforms to, if any
+    ///
+    /// The matches of referenced values are explored up to `depth` steps.
+    /// Incoming arcs are collected for inverse triple constraints
+    pub fn explore_shape<S>(
+        &mut self,
+        rdf: &S,
+        node: &S::Term,
+        schema: &std::sync::Arc<CompiledSchema>,
+        shape: &ShapeLabel,
+        config: &ValidatorConfig,
+        depth: usize,
+    ) -> Result<()>
+    where
+        S: SRDF,
+    {
+        let root = Node::from(S::term_as_object(node));
+        self.nodes.push(root.as_object().clone());
+        let mut validator = Validator::new(std::sync::Arc::clone(schema), config);
+        validator
+            .validate_node_shape(&root, shape, rdf)
+            .map_err(|e| anyhow!("Error validating {root}@{shape}: {e}"))?;
+        let matches: HashMap<(Node, ShapeLabel), ShapeMatch> = validator
+            .shape_matches()
+            .map_err(|e| anyhow!("Error obtaining the matches of {root}@{shape}: {e}"))?
+            .into_iter()
+            .map(|m| ((m.node.clone(), m.shape.clone()), m))
+            .collect();
+        if !matches.contains_key(&(root.clone(), shape.clone())) {
+            // The result is `Ok` when some referenced node doesn't conform or is still pending
+            let result = validator
+                .get_result(&root, shape)
+                .map_err(|e| anyhow!("Error obtaining the result of {root}@{shape}: {e}"))?;
+            self.unmatched
+                .push((root.as_object().clone(), schema.show_label(shape), result));
+            return Ok(());
+        }
+        let mut visited = HashSet::new();
+        let mut pending = VecDeque::from([(root, shape.clone(), 1)]);
+        while let Some((node, shape, level)) = pending.pop_front() {
+            if !visited.insert((node.clone(), shape.clone())) {
+                continue;
+            }
+            let shape_match = match matches.get(&(node.clone(), shape.clone())) {
+                Some(shape_match) => shape_match,
+                None => continue,
+            };
+            let label = schema.show_label(&shape);
+            let arcs = shape_match
+                .arcs
+                .iter()
+                .map(|arc| (arc, Direction::Outgoing))
+                .chain(
+                    shape_match
+                        .inverse_arcs
+                        .iter()
+                        .map(|arc| (arc, Direction::Incoming)),
+                );
+            for ((pred, value), direction) in arcs {
+                let refs: Vec<_> = shape_match
+                    .refs
+                    .iter()
+                    .filter(|(ref_node, _)| ref_node == value)
+                    .collect();
+                let mut annotations = Vec::new();
+                if refs.is_empty() {
+                    annotations.push(label.clone());
+                }
+                for (ref_node, ref_shape) in refs {
+                    annotations.push(format!("{label} @{}", schema.show_label(ref_shape)));
+                    if level < depth {
+                        pending.push_back((ref_node.clone(), ref_shape.clone(), level + 1))
+                    }
+                }
+                let (subject, object) = match direction {
+                    Direction::Outgoing => (node.as_object(), value.as_object()),
+                    Direction::Incoming => (value.as_object(), node.as_object()),
+                };
+                self.add_arc(
+                    subject.clone(),
+                    pred.iri().clone(),
+                    object.clone(),
+                    direction,
+                    annotations,
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn outgoing<S>(&mut self, rdf: &S, term: &S::Term, preds: &[S::IRI]) -> Result<Vec<S::Term>>
+    where
+        S: SRDF,
+    {
+        let subject = match S::term_as_subject(term) {
+            Some(subject) => subject,
+            None => return Ok(Vec::new()),
+        };
+        let map = if preds.is_empty() {
+            rdf.outgoing_arcs(&subject)
+        } else {
+            rdf.outgoing_arcs_from_list(&subject, preds.to_vec())
+                .map(|(map, _)| map)
+        }
+        .map_err(|e| anyhow!("Error obtaining outgoing arcs of {subject}: {e}"))?;
+        let mut reached = Vec::new();
+        for (pred, objects) in map {
+            for object in objects {
+                self.add_arc(
+                    S::subject_as_object(&subject),
+                    S::iri2iri_s(&pred),
+                    S::term_as_object(&object),
+                    Direction::Outgoing,
+                    Vec::new(),
+                );
+                reached.push(object)
+            }
+        }
+        Ok(reached)
+    }
+
+    fn incoming<S>(&mut self, rdf: &S, term: &S::Term) -> Result<Vec<S::Term>>
+    where
+        S: SRDF,
+    {
+        let map = rdf
+            .incoming_arcs(term)
+            .map_err(|e| anyhow!("Error obtaining incoming arcs of {term}: {e}"))?;
+        let mut reached = Vec::new();
+        for (pred, subjects) in map {
+            for subject in subjects {
+                self.add_arc(
+                    S::subject_as_object(&subject),
+                    S::iri2iri_s(&pred),
+                    S::term_as_object(term),
+                    Direction::Incoming,
+                    Vec::new(),
+                );
+                reached.push(S::subject_as_term(&subject))
+            }
+        }
+        Ok(reached)
+    }
+
+    /// Follows `path` from `nodes` (backwards if `inverse` is true) recording the traversed triples
+    fn follow_path<S>(
+        &mut self,
+        rdf: &S,
+        nodes: &HashSet<S::Term>,
+        path: &SHACLPath,
+        inverse: bool,
+    ) -> Result<HashSet<S::Term>>
+    where
+        S: SRDF,
+    {
+        match path {
+            SHACLPath::Predicate { pred } => {
+                let iri = S::iri_s2iri(pred);
+                let mut result = HashSet::new();
+                for node in nodes {
+                    if inverse {
+                        let subjects = rdf
+                            .subjects_with_predicate_object(&iri, node)
+                            .map_err(|e| anyhow!("Error obtaining subjects of {node}: {e}"))?;
+                        for subject in subjects {
+                            self.add_arc(
+                                S::subject_as_object(&subject),
+                                pred.clone(),
+                                S::term_as_object(node),
+                                Direction::Incoming,
+                                Vec::new(),
+                            );
+                            result.insert(S::subject_as_term(&subject));
+                        }
+                    } else if let Some(subject) = S::term_as_subject(node) {
+                        let objects = rdf
+                            .objects_for_subject_predicate(&subject, &iri)
+                            .map_err(|e| anyhow!("Error obtaining values of {node}: {e}"))?;
+                        for object in objects {
+                            self.add_arc(
+                                S::subject_as_object(&subject),
+                                pred.clone(),
+                                S::term_as_object(&object),
+                                Direction::Outgoing,
+                                Vec::new(),
+                            );
+                            result.insert(object);
+                        }
+                    }
+                }
+                Ok(result)
+            }
+            SHACLPath::Alternative { paths } => {
+                let mut result = HashSet::new();
+                for path in paths {
+                    result.extend(self.follow_path(rdf, nodes, path, inverse)?)
+                }
+                Ok(result)
+            }
+            SHACLPath::Sequence { paths } => {
+                let mut current = nodes.clone();
+                let steps: Vec<&SHACLPath> = if inverse {
+                    paths.iter().rev().collect()
+                } else {
+                    paths.iter().collect()
+                };
+                for path in steps {
+                    current = self.follow_path(rdf, &current, path, inverse)?
+                }
+                Ok(current)
+            }
+            SHACLPath::Inverse { path } => self.follow_path(rdf, nodes, path, !inverse),
+            SHACLPath::ZeroOrMore { path } => {
+                let mut result = self.closure(rdf, nodes, path, inverse)?;
+                result.extend(nodes.iter().cloned());
+                Ok(result)
+            }
+            SHACLPath::OneOrMore { path } => self.closure(rdf, nodes, path, inverse),
+            SHACLPath::ZeroOrOne { path } => {
+                let mut result = self.follow_path(rdf, nodes, path, inverse)?;
+                result.extend(nodes.iter().cloned());
+                Ok(result)
+            }
+        }
+    }
+
+    fn closure<S>(
+        &mut self,
+        rdf: &S,
+        nodes: &HashSet<S::Term>,
+        path: &SHACLPath,
+        inverse: bool,
+    ) -> Result<HashSet<S::Term>>
+    where
+        S: SRDF,
+    {
+        let mut visited = HashSet::new();
+        let mut current = self.follow_path(rdf, nodes, path, inverse)?;
+        loop {
+            let new: HashSet<S::Term> = current.difference(&visited).cloned().collect();
+            if new.is_empty() {
+                break;
+            }
+            visited.extend(new.iter().cloned());
+            current = self.follow_path(rdf, &new, path, inverse)?;
+        }
+        Ok(visited)
+    }
+
+    fn add_arc(
+        &mut self,
+        subject: Object,
+        predicate: IriS,
+        object: Object,
+        direction: Direction,
+        matches: Vec<String>,
+    ) {
+        let key = (subject, predicate, object, direction);
+        let pos = match self.arcs_index.get(&key) {
+            Some(pos) => *pos,
+            None => {
+                let (subject, predicate, object, direction) = key.clone();
+                self.arcs_index.insert(key, self.arcs.len());
+                self.arcs.push(Arc {
+                    subject,
+                    predicate,
+                    object,
+                    direction,
+                    matches: Vec::new(),
+                });
+                self.arcs.len() - 1
+            }
+        };
+        let arc = &mut self.arcs[pos];
+        for m in matches {
+            if !arc.matches.contains(&m) {
+                arc.matches.push(m)
+            }
+        }
+    }
+
+    /// Shows the arcs grouped by the node from which they were reached and by predicate
+    pub fn write_text<W: Write>(&self, writer: &mut W, show_hyperlinks: bool) -> Result<()> {
+        let prefixmap = self.prefixmap.clone().with_hyperlink(show_hyperlinks);
+        let nodes: Vec<String> = self
+            .nodes
+            .iter()
+            .map(|n| show_object(n, &prefixmap))
+            .collect();
+        writeln!(writer, "Information about node {}", nodes.join(", "))?;
+        for (node, shape, result) in self.unmatched.iter() {
+            writeln!(
+                writer,
+                "No match of {} with {shape} (result {result})",
+                show_object(node, &prefixmap)
+            )?;
+        }
+        for path_values in self.paths.iter() {
+            writeln!(writer, "Path {}", show_path(&path_values.path, &prefixmap))?;
+            writeln!(writer, "{}", show_object(&path_values.node, &prefixmap))?;
+            for value in path_values.values.iter() {
+                writeln!(writer, "      {}", show_object(value, &prefixmap))?;
+            }
+        }
+        if self.paths.is_empty() {
+            self.write_arcs(writer, Direction::Outgoing, &prefixmap)?;
+            self.write_arcs(writer, Direction::Incoming, &prefixmap)?;
+        }
+        Ok(())
+    }
+
+    fn write_arcs<W: Write>(
+        &self,
+        writer: &mut W,
+        direction: Direction,
+        prefixmap: &PrefixMap,
+    ) -> Result<()> {
+        // Groups of arcs by focus node and predicate keeping the order in which they were found
+        let mut groups: ArcGroups = Vec::new();
+        for arc in self.arcs.iter().filter(|a| a.direction == direction) {
+            let focus = match direction {
+                Direction::Outgoing => &arc.subject,
+                Direction::Incoming => &arc.object,
+            };
+            let pos = match groups.iter().position(|(node, _)| *node == focus) {
+                Some(pos) => pos,
+                None => {
+                    groups.push((focus, Vec::new()));
+                    groups.len() - 1
+                }
+            };
+            let preds = &mut groups[pos].1;
+            match preds.iter_mut().find(|(pred, _)| **pred == arc.predicate) {
+                Some((_, arcs)) => arcs.push(arc),
+                None => preds.push((&arc.predicate, vec![arc])),
+            }
+        }
+        if groups.is_empty() {
+            return Ok(());
+        }
+        match direction {
+            Direction::Outgoing => writeln!(writer, "Outgoing arcs")?,
+            Direction::Incoming => writeln!(writer, "Incoming arcs")?,
+        }
+        for (focus, preds) in groups {
+            writeln!(writer, "{}", show_object(focus, prefixmap))?;
+            for (pred, arcs) in preds {
+                match direction {
+                    Direction::Outgoing => writeln!(writer, " -{}-> ", prefixmap.qualify(pred))?,
+                    Direction::Incoming => writeln!(writer, "  <-{}-", prefixmap.qualify(pred))?,
+                }
+                for arc in arcs {
+                    let other = match direction {
+                        Direction::Outgoing => &arc.object,
+                        Direction::Incoming => &arc.subject,
+                    };
+                    if arc.matches.is_empty() {
+                        writeln!(writer, "      {}", show_object(other, prefixmap))?;
+                    } else {
+                        writeln!(
+                            writer,
+                            "      {} matches {}",
+                            show_object(other, prefixmap),
+                            arc.matches.join(", ")
+                        )?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Serializes the collected triples as Turtle
+    ///
+    /// Turtle can't represent the matched shapes, which are only shown in the other formats
+    pub fn write_turtle<W: Write>(&self, writer: W) -> Result<()> {
+        let mut graph = SRDFGraph::empty();
+        graph.add_prefix_map(self.prefixmap.clone())?;
+        for arc in self.arcs.iter() {
+            let subject = match SRDFGraph::object_as_subject(&arc.subject) {
+                Some(subject) => subject,
+                None => bail!("Can't use {} as subject of a triple", arc.subject),
+            };
+            graph.add_triple(
+                &subject,
+                &SRDFGraph::iri_s2iri(&arc.predicate),
+                &SRDFGraph::object_as_term(&arc.object),
+            )?;
+        }
+        graph.serialize(RDFFormat::Turtle, writer)?;
+        Ok(())
+    }
+
+    pub fn as_json(&self) -> Value {
+        let arcs: Vec<Value> = self
+            .arcs
+            .iter()
+            .map(|arc| {
+                let mut value = json!({
+                    "subject": json_object(&arc.subject),
+                    "predicate": arc.predicate.as_str(),
+                    "object": json_object(&arc.object),
+                    "direction": match arc.direction {
+                        Direction::Outgoing => "outgoing",
+                        Direction::Incoming => "incoming",
+                    },
+                });
+                if !arc.matches.is_empty() {
+                    value["matches"] = json!(arc.matches);
+                }
+                value
+            })
+            .collect();
+        let mut value = json!({
+            "nodes": self.nodes.iter().map(json_object).collect::<Vec<_>>(),
+            "arcs": arcs,
+        });
+        if !self.unmatched.is_empty() {
+            value["unmatched"] = self
+                .unmatched
+                .iter()
+                .map(|(node, shape, result)| {
+                    json!({
+                        "node": json_object(node),
+                        "shape": shape,
+                        "result": result.to_string(),
+                    })
+                })
+                .collect();
+        }
+        if !self.paths.is_empty() {
+            value["paths"] = self
+                .paths
+                .iter()
+                .map(|p| {
+                    json!({
+                        "node": json_object(&p.node),
+                        "path": p.path.to_string(),
+                        "values": p.values.iter().map(json_object).collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+        }
+        value
+    }
+
+    /// Graphviz DOT graph where the explored nodes are highlighted and the edges are labelled with
+    /// the predicates and the matched shapes
+    pub fn write_dot<W: Write>(&self, writer: &mut W) -> Result<()> {
+        writeln!(writer, "digraph {{")?;
+        for node in self.nodes.iter() {
+            writeln!(
+                writer,
+                "  \"{}\" [style=filled];",
+                escape_dot(&show_object(node, &self.prefixmap))
+            )?;
+        }
+        for arc in self.arcs.iter() {
+            let mut label = self.prefixmap.qualify(&arc.predicate);
+            for m in arc.matches.iter() {
+                label.push('\n');
+                label.push_str(m);
+            }
+            writeln!(
+                writer,
+                "  \"{}\" -> \"{}\" [label=\"{}\"];",
+                escape_dot(&show_object(&arc.subject, &self.prefixmap)),
+                escape_dot(&show_object(&arc.object, &self.prefixmap)),
+                escape_dot(&label)
+            )?;
+        }
+        for path_values in self.paths.iter() {
+            let node = show_object(&path_values.node, &self.prefixmap);
+            let path = show_path(&path_values.path, &self.prefixmap);
+            for value in path_values.values.iter() {
+                writeln!(
+                    writer,
+                    "  \"{}\" -> \"{}\" [label=\"{}\", style=dashed];",
+                    escape_dot(&node),
+                    escape_dot(&show_object(value, &self.prefixmap)),
+                    escape_dot(&path)
+                )?;
+            }
+        }
+        writeln!(writer, "}}")?;
+        Ok(())
+    }
+}
+
+fn show_object(object: &Object, prefixmap: &PrefixMap) -> String {
+    match object {
+        Object::Iri(iri) => prefixmap.qualify(iri),
+        Object::BlankNode(bnode) => format!("_:{bnode}"),
+        Object::Literal(lit) => lit.to_string(),
+        Object::Triple {
+            subject,
+            predicate,
+            object,
+        } => format!(
+            "<< {} {} {} >>",
+            show_object(subject, prefixmap),
+            prefixmap.qualify(predicate),
+            show_object(object, prefixmap)
+        ),
+    }
+}
+
+fn show_path(path: &SHACLPath, prefixmap: &PrefixMap) -> String {
+    let show_all = |paths: &Vec<SHACLPath>, sep: &str| {
+        let paths: Vec<String> = paths.iter().map(|p| show_path(p, prefixmap)).collect();
+        format!("({})", paths.join(sep))
+    };
+    match path {
+        SHACLPath::Predicate { pred } => prefixmap.qualify(pred),
+        SHACLPath::Alternative { paths } => show_all(paths, " | "),
+        SHACLPath::Sequence { paths } => show_all(paths, " / "),
+        SHACLPath::Inverse { path } => format!("^{}", show_path(path, prefixmap)),
+        SHACLPath::ZeroOrMore { path } => format!("{}*", show_path(path, prefixmap)),
+        SHACLPath::OneOrMore { path } => format!("{}+", show_path(path, prefixmap)),
+        SHACLPath::ZeroOrOne { path } => format!("{}?", show_path(path, prefixmap)),
+    }
+}
+
+fn json_object(object: &Object) -> String {
+    match object {
+        Object::Iri(iri) => iri.as_str().to_string(),
+        Object::BlankNode(bnode) => format!("_:{bnode}"),
+        Object::Literal(lit) => lit.to_string(),
+        Object::Triple {
+            subject,
+            predicate,
+            object,
+        } => format!(
+            "<< {} <{predicate}> {} >>",
+            json_object(subject),
+            json_object(object)
+        ),
+    }
+}
+
+fn escape_dot(str: &str) -> String {
+    str.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use srdf::ReaderMode;
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+:alice :knows :bob ; :name "Alice" .
+:bob :knows :carol ; :name "Bob" .
+:carol :name "Carol" .
+"#;
+
+    const SCHEMA: &str = r#"prefix : <http://example.org/>
+:Person { :name . ; :knows @:Known ? }
+:Known { :name . ; ^:knows @:Person }
+"#;
+
+    fn graph() -> SRDFGraph {
+        SRDFGraph::from_str(DATA, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap()
+    }
+
+    fn schema() -> std::sync::Arc<CompiledSchema> {
+        let schema_json = shex_compact::ShExParser::parse(SCHEMA, None).unwrap();
+        let mut schema = CompiledSchema::new();
+        schema.from_schema_json(&schema_json).unwrap();
+        std::sync::Arc::new(schema)
+    }
+
+    fn shape(local: &str) -> ShapeLabel {
+        ShapeLabel::iri(IriS::new_unchecked(&format!("http://example.org/{local}")))
+    }
+
+    fn explore_shape(node: &str, label: &str, depth: usize) -> NodeExploration {
+        let rdf = graph();
+        let mut exploration = NodeExploration::new(rdf.prefixmap());
+        exploration
+            .explore_shape(
+                &rdf,
+                &term(node),
+                &schema(),
+                &shape(label),
+                &ValidatorConfig::default(),
+                depth,
+            )
+            .unwrap();
+        exploration
+    }
+
+    fn term(local: &str) -> <SRDFGraph as SRDFBasic>::Term {
+        SRDFGraph::iri_s2term(&IriS::new_unchecked(&format!("http://example.org/{local}")))
+    }
+
+    #[test]
+    fn neighbourhood_depth() {
+        let rdf = graph();
+        let mut exploration = NodeExploration::new(rdf.prefixmap());
+        exploration
+            .explore_neighbourhood(&rdf, &term("alice"), &ShowNodeMode::Outgoing, &[], 1)
+            .unwrap();
+        assert_eq!(exploration.arcs.len(), 2);
+        let mut exploration = NodeExploration::new(rdf.prefixmap());
+        exploration
+            .explore_neighbourhood(&rdf, &term("alice"), &ShowNodeMode::Outgoing, &[], 2)
+            .unwrap();
+        assert_eq!(exploration.arcs.len(), 4);
+    }
+
+    #[test]
+    fn path_values_and_triples() {
+        let rdf = graph();
+        let knows = IriS::new_unchecked("http://example.org/knows");
+        let path = SHACLPath::OneOrMore {
+            path: Box::new(SHACLPath::iri(knows)),
+        };
+        let mut exploration = NodeExploration::new(PrefixMap::new());
+        exploration
+            .explore_path(&rdf, &term("alice"), &path)
+            .unwrap();
+        let json = exploration.as_json();
+        assert_eq!(
+            json["paths"][0]["values"],
+            json!(["http://example.org/bob", "http://example.org/carol"])
+        );
+        assert_eq!(exploration.arcs.len(), 2);
+    }
+
+    #[test]
+    fn shape_matches() {
+        let exploration = explore_shape("alice", "Person", 1);
+        let json = exploration.as_json();
+        let arcs = json["arcs"].as_array().unwrap();
+        assert_eq!(arcs.len(), 2);
+        let knows = arcs
+            .iter()
+            .find(|arc| arc["predicate"] == "http://example.org/knows")
+            .unwrap();
+        assert_eq!(knows["matches"], json!([":Person @:Known"]));
+
+        // The match of :bob with :Known includes the incoming arc from :alice
+        let exploration = explore_shape("alice", "Person", 2);
+        let json = exploration.as_json();
+        let incoming: Vec<_> = json["arcs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|arc| arc["direction"] == "incoming")
+            .collect();
+        assert_eq!(incoming.len(), 1);
+        assert_eq!(incoming[0]["subject"], "http://example.org/alice");
+        assert_eq!(incoming[0]["matches"], json!([":Known @:Person"]));
+        assert_eq!(json["arcs"].as_array().unwrap().len(), 4);
+
+        // Nobody knows :alice
+        let exploration = explore_shape("alice", "Known", 1);
+        assert!(exploration.arcs.is_empty());
+        assert_eq!(
+            exploration.as_json()["unmatched"],
+            json!([{ "node": "http://example.org/alice", "shape": ":Known", "result": "Failed" }])
+        );
+    }
+
+    #[test]
+    fn dot_output() {
+        let exploration = explore_shape("alice", "Person", 1);
+        let mut dot = Vec::new();
+        exploration.write_dot(&mut dot).unwrap();
+        let dot = String::from_utf8(dot).unwrap();
+        assert!(dot.starts_with("digraph {\n  \":alice\" [style=filled];\n"));
+        assert!(dot.contains("\":alice\" -> \":bob\" [label=\":knows\\n:Person @:Known\"];"));
+        assert!(dot.contains("\":alice\" -> \"\\\"Alice\\\"\" [label=\":name\\n:Person\"];"));
+    }
+
+    #[test]
+    fn turtle_output() {
+        let exploration = explore_shape("alice", "Person", 2);
+        let mut turtle = Vec::new();
+        exploration.write_turtle(&mut turtle).unwrap();
+        let written = SRDFGraph::from_str(
+            &String::from_utf8(turtle).unwrap(),
+            &RDFFormat::Turtle,
+            None,
+            &ReaderMode::Strict,
+        )
+        .unwrap();
+        // The outgoing and incoming arcs between :alice and :bob are the same triple
+        assert_eq!(written.len(), 3);
+    }
+}
@@ -1,9 +1,11 @@
 use clap::ValueEnum;
+use serde_derive::{Deserialize, Serialize};
 
 use std::fmt::{Display, Formatter};
 
-#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug, Serialize, Deserialize)]
 #[clap(rename_all = "lower")]
+#[serde(rename_all = "lowercase")]
 pub enum OutputConvertFormat {
     Default,
     Internal,
@@ -15,6 +17,8 @@ pub enum OutputConvertFormat {
     HTML,
     SVG,
     PNG,
+    CSV,
+    XLSX,
 }
 
 impl Display for OutputConvertFormat {
@@ -30,6 +34,8 @@ impl Display for OutputConvertFormat {
             OutputConvertFormat::HTML => write!(dest, "html"),
             OutputConvertFormat::PNG => write!(dest, "png"),
             OutputConvertFormat::SVG => write!(dest, "svg"),
+            OutputConvertFormat::CSV => write!(dest, "csv"),
+            OutputConvertFormat::XLSX => write!(dest, "xlsx"),
         }
     }
 }
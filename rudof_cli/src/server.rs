@@ -0,0 +1,594 @@
+//! HTTP JSON API of `rudof serve`
+//!
+//! The endpoints take the same options as the corresponding commands, with the schemas and data written inline
+//! in the body of the requests, and answer with the same JSON documents that the commands generate.
+//! Compiled schemas are cached by their contents and format, so the services that validate against the same
+//! schemas don't parse and compile them on every request.
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+
+use anyhow::{bail, Result};
+use axum::extract::{Json, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use serde_derive::Deserialize;
+use serde_json::{json, Value};
+use shacl_ast::Schema as ShaclSchema;
+use shacl_validation::validate::{GraphValidator, ShaclValidationMode, Validator as _};
+use shex_ast::compiled::compiled_schema::CompiledSchema;
+use shex_compact::ShExParser;
+use shex_validation::{Validator, ValidatorConfig};
+
+use crate::{
+    convert, parse_data, parse_schema, parse_shacl, query_shapemap, show_schema,
+    validate_shex_mode, ColorSupport, ConverterConfig, DataFormat, InputConvertFormat,
+    InputConvertMode, InputSpec, OutputConvertFormat, OutputConvertMode, RDFReaderMode, ShExFormat,
+    ShExValidationMode, ShaclFormat, ShapeMapFormat, ValidationSummary,
+};
+
+/// OpenAPI description of the endpoints
+pub const OPENAPI: &str = include_str!("../openapi.yaml");
+
+/// Default number of compiled schemas of each kind kept in the cache
+pub const DEFAULT_CACHE_SIZE: usize = 64;
+
+#[derive(Deserialize, Debug)]
+pub struct ShExValidateRequest {
+    pub schema: String,
+    #[serde(default = "default_shex_format")]
+    pub schema_format: ShExFormat,
+    pub data: String,
+    #[serde(default = "default_data_format")]
+    pub data_format: DataFormat,
+    pub shapemap: Option<String>,
+    #[serde(default = "default_shapemap_format")]
+    pub shapemap_format: ShapeMapFormat,
+    pub node: Option<String>,
+    pub shape: Option<String>,
+    #[serde(default = "default_shex_mode")]
+    pub shex_mode: ShExValidationMode,
+    /// Same contents as the config file of `rudof shex-validate`
+    pub config: Option<ValidatorConfig>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ShaclValidateRequest {
+    pub shapes: String,
+    #[serde(default = "default_shacl_format")]
+    pub shapes_format: ShaclFormat,
+    pub data: String,
+    #[serde(default = "default_data_format")]
+    pub data_format: DataFormat,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ConvertRequest {
+    pub input: String,
+    pub format: InputConvertFormat,
+    pub input_mode: InputConvertMode,
+    pub output_mode: OutputConvertMode,
+    #[serde(default = "default_output_convert_format")]
+    pub result_format: OutputConvertFormat,
+    pub shape: Option<String>,
+    /// Same contents as the config file of `rudof convert`
+    pub config: Option<ConverterConfig>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ShExParseRequest {
+    pub schema: String,
+    #[serde(default = "default_shex_format")]
+    pub schema_format: ShExFormat,
+    #[serde(default = "default_result_shex_format")]
+    pub result_format: ShExFormat,
+}
+
+fn default_shex_format() -> ShExFormat {
+    ShExFormat::ShExC
+}
+
+fn default_result_shex_format() -> ShExFormat {
+    ShExFormat::ShExJ
+}
+
+fn default_data_format() -> DataFormat {
+    DataFormat::Turtle
+}
+
+fn default_shapemap_format() -> ShapeMapFormat {
+    ShapeMapFormat::Compact
+}
+
+fn default_shex_mode() -> ShExValidationMode {
+    ShExValidationMode::ShapeMap
+}
+
+fn default_shacl_format() -> ShaclFormat {
+    ShaclFormat::Turtle
+}
+
+fn default_output_convert_format() -> OutputConvertFormat {
+    OutputConvertFormat::Default
+}
+
+/// Router with the endpoints of the API, which can also be called in-process
+pub fn router(cache_size: usize) -> Router {
+    let state = ServerState {
+        cache: Arc::new(SchemaCache::new(cache_size)),
+    };
+    Router::new()
+        .route("/validate/shex", post(validate_shex))
+        .route("/validate/shacl", post(validate_shacl))
+        .route("/convert", post(convert_shapes))
+        .route("/shex/parse", post(parse_shex))
+        .route("/openapi.yaml", get(openapi))
+        .with_state(state)
+}
+
+/// Serves the API until the process is stopped
+pub async fn serve(address: &str, cache_size: usize) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    let _ = writeln!(io::stderr(), "Listening on http://{address}");
+    axum::serve(listener, router(cache_size)).await?;
+    Ok(())
+}
+
+#[derive(Clone)]
+struct ServerState {
+    cache: Arc<SchemaCache>,
+}
+
+async fn validate_shex(
+    State(state): State<ServerState>,
+    Json(request): Json<ShExValidateRequest>,
+) -> Result<Json<Value>, ApiError> {
+    run_blocking(move || state.validate_shex(&request)).await
+}
+
+async fn validate_shacl(
+    State(state): State<ServerState>,
+    Json(request): Json<ShaclValidateRequest>,
+) -> Result<Json<Value>, ApiError> {
+    run_blocking(move || state.validate_shacl(&request)).await
+}
+
+async fn convert_shapes(Json(request): Json<ConvertRequest>) -> Result<Json<Value>, ApiError> {
+    run_blocking(move || convert_request(&request)).await
+}
+
+async fn parse_shex(Json(request): Json<ShExParseRequest>) -> Result<Json<Value>, ApiError> {
+    run_blocking(move || parse_shex_request(&request)).await
+}
+
+async fn openapi() -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "application/yaml")], OPENAPI)
+}
+
+/// Parsing and validation are CPU bound and the RDF readers are blocking, so they don't run in the async workers
+async fn run_blocking<F>(f: F) -> Result<Json<Value>, ApiError>
+where
+    F: FnOnce() -> Result<Value> + Send + 'static,
+{
+    match tokio::task::spawn_blocking(f).await {
+        Ok(Ok(value)) => Ok(Json(value)),
+        Ok(Err(err)) => match err.downcast::<ApiError>() {
+            Ok(api_error) => Err(api_error),
+            Err(err) => Err(ApiError::bad_request(err.to_string())),
+        },
+        Err(err) => Err(ApiError {
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            message: err.to_string(),
+            details: Vec::new(),
+        }),
+    }
+}
+
+impl ServerState {
+    fn validate_shex(&self, request: &ShExValidateRequest) -> Result<Value> {
+        let schema = self
+            .cache
+            .compiled_shex(&request.schema, &request.schema_format)?;
+        let data = parse_data(
+            &vec![InputSpec::Str(request.data.clone())],
+            &request.data_format,
+            &RDFReaderMode::default(),
+        )?;
+        let shapemap_input = request.shapemap.clone().map(InputSpec::Str);
+        let shapemap = query_shapemap(
+            shapemap_input.as_ref(),
+            &request.shapemap_format,
+            &request.node,
+            &request.shape,
+        )?;
+        let config = request.config.clone().unwrap_or_default();
+        let mut validator = Validator::new(schema, &config);
+        let result = validator
+            .validate_shapemap_query(&shapemap, &data)
+            .and_then(|_| validate_shex_mode(&mut validator, &request.shex_mode, &data))
+            .and_then(|_| validator.result_map(Some(data.prefixmap())));
+        let result_map = match result {
+            Ok(result_map) => result_map,
+            Err(err) => bail!("{err}"),
+        };
+        let result_map = match request.shex_mode {
+            ShExValidationMode::Best => result_map.best_shapes(),
+            _ => result_map,
+        };
+        let summary = ValidationSummary::from_result_map(&result_map);
+        Ok(json!({
+            "conforms": summary.conforms(),
+            "summary": summary.as_json(),
+            "results": result_map.associations(),
+        }))
+    }
+
+    fn validate_shacl(&self, request: &ShaclValidateRequest) -> Result<Value> {
+        let schema = self
+            .cache
+            .shacl_schema(&request.shapes, &request.shapes_format)?;
+        let data = parse_data(
+            &vec![InputSpec::Str(request.data.clone())],
+            &request.data_format,
+            &RDFReaderMode::default(),
+        )?;
+        let report = GraphValidator::from_graph(data, ShaclValidationMode::Default)
+            .and_then(|validator| validator.validate(&schema));
+        let report = match report {
+            Ok(report) => report,
+            Err(err) => bail!("Error validating the graph: {err}"),
+        };
+        let summary = ValidationSummary::from_shacl_report(&report);
+        Ok(json!({
+            "conforms": report.conforms(),
+            "summary": summary.as_json(),
+            "results": summary.results_as_json(),
+        }))
+    }
+}
+
+fn convert_request(request: &ConvertRequest) -> Result<Value> {
+    let buffer = SharedBuffer::default();
+    convert(
+        &InputSpec::Str(request.input.clone()),
+        &request.format,
+        &request.input_mode,
+        &request.shape,
+        &request.result_format,
+        &request.output_mode,
+        &None,
+        &request.config.clone().unwrap_or_default(),
+        &RDFReaderMode::default(),
+        Box::new(buffer.clone()),
+        ColorSupport::NoColor,
+    )?;
+    Ok(json!({ "result": buffer.into_string()? }))
+}
+
+fn parse_shex_request(request: &ShExParseRequest) -> Result<Value> {
+    // ShExC schemas are parsed in recovery mode first to report all the syntax errors at once
+    if request.schema_format == ShExFormat::ShExC {
+        let (_, diagnostics) = ShExParser::parse_with_recovery(&request.schema, None);
+        if !diagnostics.is_empty() {
+            let details = diagnostics.iter().map(|d| d.to_string()).collect();
+            return Err(ApiError::bad_request(format!(
+                "Found {} errors parsing ShExC schema",
+                diagnostics.len()
+            ))
+            .with_details(details)
+            .into());
+        }
+    }
+    let schema = parse_schema(
+        &InputSpec::Str(request.schema.clone()),
+        &request.schema_format,
+        &RDFReaderMode::default(),
+    )?;
+    let buffer = SharedBuffer::default();
+    show_schema(
+        &schema,
+        &request.result_format,
+        Box::new(buffer.clone()),
+        ColorSupport::NoColor,
+    )?;
+    Ok(json!({
+        "shapes": schema.shapes().map(|shapes| shapes.len()).unwrap_or(0),
+        "result": buffer.into_string()?,
+    }))
+}
+
+/// Compiled schemas indexed by their contents and format
+///
+/// When a cache is full the least recently used schema is removed
+struct SchemaCache {
+    shex: Mutex<LruCache<SchemaKey, CompiledSchema>>,
+    shacl: Mutex<LruCache<SchemaKey, ShaclSchema>>,
+}
+
+/// Format and contents of a schema
+#[derive(PartialEq, Eq, Hash)]
+struct SchemaKey {
+    format: String,
+    content: String,
+}
+
+impl SchemaKey {
+    fn new(content: &str, format: &str) -> SchemaKey {
+        SchemaKey {
+            format: format.to_string(),
+            content: content.to_string(),
+        }
+    }
+}
+
+impl SchemaCache {
+    fn new(capacity: usize) -> SchemaCache {
+        SchemaCache {
+            shex: Mutex::new(LruCache::new(capacity)),
+            shacl: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    fn compiled_shex(&self, schema: &str, format: &ShExFormat) -> Result<Arc<CompiledSchema>> {
+        let key = SchemaKey::new(schema, &format.to_string());
+        if let Some(compiled) = lock(&self.shex).get(&key) {
+            return Ok(compiled);
+        }
+        let schema_json = parse_schema(
+            &InputSpec::Str(schema.to_string()),
+            format,
+            &RDFReaderMode::default(),
+        )?;
+        let mut compiled = CompiledSchema::new();
+        compiled.from_schema_json(&schema_json)?;
+        Ok(lock(&self.shex).insert(key, compiled))
+    }
+
+    fn shacl_schema(&self, shapes: &str, format: &ShaclFormat) -> Result<Arc<ShaclSchema>> {
+        let key = SchemaKey::new(shapes, &format.to_string());
+        if let Some(schema) = lock(&self.shacl).get(&key) {
+            return Ok(schema);
+        }
+        let schema = parse_shacl(
+            &InputSpec::Str(shapes.to_string()),
+            format,
+            &RDFReaderMode::default(),
+        )?;
+        Ok(lock(&self.shacl).insert(key, schema))
+    }
+}
+
+/// A request that panics while holding the lock leaves the cache usable, as entries are only inserted when complete
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn key_hash<K: Hash>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Values indexed by the hash of their keys
+///
+/// The key of each value is kept with it and compared on every hit, so the values of keys whose hashes
+/// collide are never confused, and only the hashes are copied to keep the order of use
+struct LruCache<K, T> {
+    capacity: usize,
+    entries: HashMap<u64, (K, Arc<T>)>,
+    order: VecDeque<u64>,
+}
+
+impl<K: Hash + Eq, T> LruCache<K, T> {
+    fn new(capacity: usize) -> LruCache<K, T> {
+        LruCache {
+            capacity,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    fn get(&mut self, key: &K) -> Option<Arc<T>> {
+        let hash = key_hash(key);
+        let value = match self.entries.get(&hash) {
+            Some((k, value)) if k == key => value.clone(),
+            _ => return None,
+        };
+        self.order.retain(|h| *h != hash);
+        self.order.push_back(hash);
+        Some(value)
+    }
+
+    /// Inserts a value, replacing the value of a key with the same hash
+    fn insert(&mut self, key: K, value: T) -> Arc<T> {
+        let value = Arc::new(value);
+        if self.capacity == 0 {
+            return value;
+        }
+        let hash = key_hash(&key);
+        if self.entries.insert(hash, (key, value.clone())).is_none() {
+            self.order.push_back(hash);
+        }
+        while self.order.len() > self.capacity {
+            if let Some(oldest) = self.order.pop_front() {
+                self.entries.remove(&oldest);
+            }
+        }
+        value
+    }
+}
+
+/// Writer whose contents can be read after being boxed as the output of a command
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn into_string(self) -> Result<String> {
+        let bytes = lock(&self.0).clone();
+        match String::from_utf8(bytes) {
+            Ok(str) => Ok(str),
+            Err(_) => bail!("Binary output formats are not supported by the server"),
+        }
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        lock(&self.0).write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Error answered as `{ "error": message, "details": [...] }`
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    message: String,
+    details: Vec<String>,
+}
+
+impl ApiError {
+    fn bad_request(message: String) -> ApiError {
+        ApiError {
+            status: StatusCode::BAD_REQUEST,
+            message,
+            details: Vec::new(),
+        }
+    }
+
+    fn with_details(mut self, details: Vec<String>) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+impl std::fmt::Display for ApiError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ApiError {}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = json!({ "error": self.message, "details": self.details });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    const SCHEMA: &str = r#"prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:Person { :name xsd:string ; :age xsd:integer ? }"#;
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+:alice :name "Alice" ; :age 23 .
+:bob :age "unknown" ."#;
+
+    async fn post_json(app: Router, uri: &str, body: Value) -> (StatusCode, Value) {
+        let request = Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap())
+    }
+
+    #[tokio::test]
+    async fn validate_shex_endpoint() {
+        let app = router(DEFAULT_CACHE_SIZE);
+        let request = json!({
+            "schema": SCHEMA,
+            "data": DATA,
+            "shapemap": ":alice@:Person, :bob@:Person",
+        });
+        let (status, body) = post_json(app, "/validate/shex", request).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["conforms"], false);
+        assert_eq!(body["results"].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn compiled_schemas_are_cached() {
+        let app = router(DEFAULT_CACHE_SIZE);
+        let request = json!({ "schema": SCHEMA, "data": DATA, "shapemap": ":alice@:Person" });
+        for _ in 0..2 {
+            let (status, body) = post_json(app.clone(), "/validate/shex", request.clone()).await;
+            assert_eq!(status, StatusCode::OK);
+            assert_eq!(body["conforms"], true);
+        }
+        let cache = SchemaCache::new(DEFAULT_CACHE_SIZE);
+        let first = cache.compiled_shex(SCHEMA, &ShExFormat::ShExC).unwrap();
+        let second = cache.compiled_shex(SCHEMA, &ShExFormat::ShExC).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+    }
+
+    #[test]
+    fn lru_cache_removes_least_recently_used() {
+        let mut cache = LruCache::new(2);
+        cache.insert(1, "a");
+        cache.insert(2, "b");
+        cache.get(&1);
+        cache.insert(3, "c");
+        assert!(cache.get(&1).is_some());
+        assert!(cache.get(&2).is_none());
+        assert!(cache.get(&3).is_some());
+    }
+
+    /// Key whose hash is the same for every value
+    #[derive(PartialEq, Eq)]
+    struct Colliding(&'static str);
+
+    impl Hash for Colliding {
+        fn hash<H: Hasher>(&self, state: &mut H) {
+            0.hash(state)
+        }
+    }
+
+    #[test]
+    fn lru_cache_compares_keys_with_the_same_hash() {
+        let mut cache = LruCache::new(2);
+        cache.insert(Colliding("a"), "a");
+        assert!(cache.get(&Colliding("b")).is_none());
+        cache.insert(Colliding("b"), "b");
+        assert_eq!(cache.get(&Colliding("b")).as_deref(), Some(&"b"));
+        assert!(cache.get(&Colliding("a")).is_none());
+    }
+
+    #[test]
+    fn schemas_are_cached_by_format() {
+        let cache = SchemaCache::new(DEFAULT_CACHE_SIZE);
+        let shexc = cache.compiled_shex(SCHEMA, &ShExFormat::ShExC).unwrap();
+        assert!(cache.compiled_shex(SCHEMA, &ShExFormat::ShExJ).is_err());
+        let again = cache.compiled_shex(SCHEMA, &ShExFormat::ShExC).unwrap();
+        assert!(Arc::ptr_eq(&shexc, &again));
+    }
+
+    #[tokio::test]
+    async fn parse_shex_reports_syntax_errors() {
+        let app = router(DEFAULT_CACHE_SIZE);
+        let request = json!({ "schema": "prefix : <http://example.org/> :S { :p . ; " });
+        let (status, body) = post_json(app, "/shex/parse", request).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(!body["details"].as_array().unwrap().is_empty());
+    }
+}
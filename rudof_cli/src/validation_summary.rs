@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+use shacl_validation::validation_report::report::ValidationReport;
+use shex_validation::{ResultMap, ResultStatus};
+use srdf::{Object, SRDFBasic};
+
+/// Exit code used by validation commands when some node doesn't conform
+pub const NONCONFORMANT_EXIT_CODE: i32 = 2;
+
+/// Summary of a ShEx or SHACL validation
+///
+/// Each case corresponds to a node/shape pair in ShEx or to a focus node and source shape in SHACL
+pub struct ValidationSummary {
+    suite: String,
+    cases: Vec<ValidationCase>,
+}
+
+struct ValidationCase {
+    node: String,
+    shape: String,
+    status: ResultStatus,
+    severity: Option<String>,
+    messages: Vec<String>,
+}
+
+impl ValidationSummary {
+    pub fn from_result_map(result_map: &ResultMap) -> ValidationSummary {
+        let cases = result_map
+            .associations()
+            .into_iter()
+            .map(|association| {
+                let severity = match association.status {
+                    ResultStatus::Nonconformant => Some("Violation".to_string()),
+                    _ => None,
+                };
+                ValidationCase {
+                    node: association.node,
+                    shape: association.shape,
+                    status: association.status,
+                    severity,
+                    messages: association.reason.into_iter().collect(),
+                }
+            })
+            .collect();
+        ValidationSummary {
+            suite: "shex".to_string(),
+            cases,
+        }
+    }
+
+    /// SHACL reports only contain the results which don't conform, so every case is nonconformant
+    pub fn from_shacl_report<S: SRDFBasic>(report: &ValidationReport<S>) -> ValidationSummary {
+        let mut cases: BTreeMap<(String, String), ValidationCase> = BTreeMap::new();
+        for result in report.results() {
+            let node = show_term::<S>(result.focus_node());
+            let shape = show_term::<S>(result.source_shape());
+            let severity = result
+                .result_severity()
+                .map(|s| local_name(&S::term_as_object(&s)))
+                .unwrap_or_else(|| "Violation".to_string());
+            let message = format!(
+                "{severity}: {} {}",
+                show_term::<S>(result.source_constraint_component()),
+                result
+                    .value()
+                    .map(|v| format!("(value: {})", S::term_as_object(&v)))
+                    .unwrap_or_default()
+            );
+            let case = cases
+                .entry((node.clone(), shape.clone()))
+                .or_insert_with(|| ValidationCase {
+                    node,
+                    shape,
+                    status: ResultStatus::Nonconformant,
+                    severity: Some(severity),
+                    messages: Vec::new(),
+                });
+            case.messages.push(message.trim_end().to_string());
+        }
+        ValidationSummary {
+            suite: "shacl".to_string(),
+            cases: cases.into_values().collect(),
+        }
+    }
+
+    /// Pending nodes are not considered conformant because their validation was not finished
+    pub fn conforms(&self) -> bool {
+        self.cases
+            .iter()
+            .all(|case| case.status == ResultStatus::Conformant)
+    }
+
+    fn count(&self, status: ResultStatus) -> usize {
+        self.cases.iter().filter(|c| c.status == status).count()
+    }
+
+    /// Counts of results per shape and per severity
+    pub fn as_json(&self) -> Value {
+        let mut shapes: BTreeMap<&str, BTreeMap<&str, usize>> = BTreeMap::new();
+        let mut severities: BTreeMap<&str, usize> = BTreeMap::new();
+        for case in &self.cases {
+            *shapes
+                .entry(case.shape.as_str())
+                .or_default()
+                .entry(status_name(&case.status))
+                .or_default() += 1;
+            if let Some(severity) = &case.severity {
+                *severities.entry(severity.as_str()).or_default() += 1;
+            }
+        }
+        json!({
+            "conforms": self.conforms(),
+            "total": self.cases.len(),
+            "conformant": self.count(ResultStatus::Conformant),
+            "nonconformant": self.count(ResultStatus::Nonconformant),
+            "pending": self.count(ResultStatus::Pending),
+            "shapes": shapes,
+            "severities": severities,
+        })
+    }
+
+    /// Status, severity and messages of each case
+    pub fn results_as_json(&self) -> Value {
+        self.cases
+            .iter()
+            .map(|case| {
+                json!({
+                    "node": case.node,
+                    "shape": case.shape,
+                    "status": status_name(&case.status),
+                    "severity": case.severity,
+                    "messages": case.messages,
+                })
+            })
+            .collect()
+    }
+
+    /// JUnit XML report where each case is a test case whose class name is the shape
+    pub fn as_junit(&self) -> String {
+        let failures = self.count(ResultStatus::Nonconformant);
+        let skipped = self.count(ResultStatus::Pending);
+        let mut str = String::new();
+        // Writing to a String can't fail
+        let _ = writeln!(str, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        let _ = writeln!(
+            str,
+            r#"<testsuites name="rudof" tests="{}" failures="{failures}" skipped="{skipped}">"#,
+            self.cases.len()
+        );
+        let _ = writeln!(
+            str,
+            r#"  <testsuite name="{}" tests="{}" failures="{failures}" skipped="{skipped}">"#,
+            self.suite,
+            self.cases.len()
+        );
+        for case in &self.cases {
+            let _ = write!(
+                str,
+                r#"    <testcase classname="{}" name="{}""#,
+                escape_xml(&case.shape),
+                escape_xml(&case.node)
+            );
+            match case.status {
+                ResultStatus::Conformant => {
+                    let _ = writeln!(str, "/>");
+                }
+                ResultStatus::Nonconformant => {
+                    let severity = case.severity.as_deref().unwrap_or("Violation");
+                    let _ = writeln!(str, ">");
+                    let _ = writeln!(
+                        str,
+                        r#"      <failure message="{}" type="{}">{}</failure>"#,
+                        escape_xml(first_line(&case.messages)),
+                        escape_xml(severity),
+                        escape_xml(&case.messages.join("\n"))
+                    );
+                    let _ = writeln!(str, "    </testcase>");
+                }
+                ResultStatus::Pending => {
+                    let _ = writeln!(str, ">");
+                    let _ = writeln!(str, r#"      <skipped message="pending"/>"#);
+                    let _ = writeln!(str, "    </testcase>");
+                }
+            }
+        }
+        let _ = writeln!(str, "  </testsuite>");
+        let _ = writeln!(str, "</testsuites>");
+        str
+    }
+}
+
+fn status_name(status: &ResultStatus) -> &'static str {
+    match status {
+        ResultStatus::Conformant => "conformant",
+        ResultStatus::Nonconformant => "nonconformant",
+        ResultStatus::Pending => "pending",
+    }
+}
+
+fn show_term<S: SRDFBasic>(term: Option<S::Term>) -> String {
+    match term.map(|t| S::term_as_object(&t)) {
+        Some(Object::BlankNode(bnode)) => format!("_:{bnode}"),
+        Some(object) => object.to_string(),
+        None => String::new(),
+    }
+}
+
+fn local_name(object: &Object) -> String {
+    match object {
+        Object::Iri(iri) => {
+            let str = iri.as_str();
+            str.rsplit(['#', '/']).next().unwrap_or(str).to_string()
+        }
+        _ => object.to_string(),
+    }
+}
+
+fn first_line(messages: &[String]) -> &str {
+    messages
+        .first()
+        .and_then(|m| m.lines().next())
+        .unwrap_or_default()
+}
+
+fn escape_xml(str: &str) -> String {
+    str.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iri_s::IriS;
+    use shex_ast::{compiled::shape_label::ShapeLabel, Node};
+
+    #[test]
+    fn summary_of_result_map() {
+        let mut result_map = ResultMap::new();
+        let shape = ShapeLabel::iri(IriS::new_unchecked("http://example.org/S"));
+        result_map.add_ok(
+            Node::iri(IriS::new_unchecked("http://example.org/a")),
+            shape.clone(),
+            Vec::new(),
+        );
+        result_map.add_fail(
+            Node::iri(IriS::new_unchecked("http://example.org/b")),
+            shape,
+            Vec::new(),
+        );
+        let summary = ValidationSummary::from_result_map(&result_map);
+        assert!(!summary.conforms());
+        let json = summary.as_json();
+        assert_eq!(json["shapes"]["http://example.org/S"]["conformant"], 1);
+        assert_eq!(json["severities"]["Violation"], 1);
+        let junit = summary.as_junit();
+        assert!(junit.contains(
+            r#"<testcase classname="http://example.org/S" name="http://example.org/a"/>"#
+        ));
+        assert!(junit.contains(r#"failures="1""#));
+    }
+}
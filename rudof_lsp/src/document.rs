@@ -0,0 +1,281 @@
+use std::fmt::Display;
+
+use iri_s::IriS;
+use lsp_types::{
+    CompletionItem, CompletionItemKind, Diagnostic, DiagnosticSeverity, Hover, HoverContents,
+    MarkupContent, MarkupKind, Position, Range, TextEdit, Url,
+};
+use prefixmap::PrefixMap;
+use shex_compact::{ShExFormatter, ShExParser};
+
+use crate::tokens::{Token, TokenKind};
+use crate::{shacl_analysis, shex_analysis, LineIndex};
+
+/// Languages of the documents handled by the server
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    ShExC,
+
+    /// SHACL shapes written in Turtle
+    Turtle,
+}
+
+impl Language {
+    /// Obtains the language from the language id sent by the editor or, if it is unknown, from the file extension
+    pub fn from_document(language_id: &str, uri: &Url) -> Option<Language> {
+        match language_id.to_lowercase().as_str() {
+            "shex" | "shexc" => Some(Language::ShExC),
+            "turtle" | "ttl" | "shacl" => Some(Language::Turtle),
+            _ => match uri.path().rsplit_once('.').map(|(_, ext)| ext) {
+                Some("shex") => Some(Language::ShExC),
+                Some("ttl") => Some(Language::Turtle),
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Name of a node, which can be used as a shape label
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Name {
+    Iri(IriS),
+    BNode(String),
+}
+
+impl Name {
+    /// Resolves the name written by a token with the prefixes and base of the document
+    pub(crate) fn from_token(
+        token: &Token,
+        src: &str,
+        prefixmap: &PrefixMap,
+        base: &Option<IriS>,
+    ) -> Option<Name> {
+        let text = token.text(src);
+        match token.kind {
+            TokenKind::PrefixedName => prefixmap.resolve(text).ok().map(Name::Iri),
+            TokenKind::BlankNode => Some(Name::BNode(text[2..].to_string())),
+            TokenKind::IriRef => {
+                let iri = IriS::new_unchecked(&text[1..text.len() - 1]);
+                match base {
+                    Some(base) if !text.contains(':') => base.resolve(iri).ok().map(Name::Iri),
+                    _ => Some(Name::Iri(iri)),
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Name::Iri(iri) => write!(f, "<{iri}>"),
+            Name::BNode(id) => write!(f, "_:{id}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Role {
+    /// The name is the label of a shape declaration
+    Declaration,
+
+    /// The name refers to a shape
+    Reference,
+
+    Other,
+}
+
+/// Occurrence of a name between the byte offsets `start` and `end`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Symbol {
+    pub name: Name,
+    pub role: Role,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Problem found in a document between the byte offsets `start` and `end`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub message: String,
+    pub severity: DiagnosticSeverity,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Results of analysing the contents of a document
+#[derive(Debug, Clone, Default)]
+pub struct Analysis {
+    pub problems: Vec<Problem>,
+    pub prefixmap: PrefixMap,
+    pub symbols: Vec<Symbol>,
+}
+
+impl Analysis {
+    /// Names of the declared shapes, in document order
+    pub fn shapes(&self) -> Vec<&Name> {
+        let mut shapes: Vec<&Name> = Vec::new();
+        for symbol in self.symbols.iter() {
+            if symbol.role == Role::Declaration && !shapes.contains(&&symbol.name) {
+                shapes.push(&symbol.name)
+            }
+        }
+        shapes
+    }
+}
+
+/// Open document with the results of its last analysis
+#[derive(Debug, Clone)]
+pub struct Document {
+    text: String,
+    language: Language,
+    line_index: LineIndex,
+    analysis: Analysis,
+}
+
+impl Document {
+    pub fn new(text: String, language: Language) -> Document {
+        let analysis = match language {
+            Language::ShExC => shex_analysis::analyze(&text),
+            Language::Turtle => shacl_analysis::analyze(&text),
+        };
+        Document {
+            line_index: LineIndex::new(&text),
+            text,
+            language,
+            analysis,
+        }
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn analysis(&self) -> &Analysis {
+        &self.analysis
+    }
+
+    pub fn range(&self, start: usize, end: usize) -> Range {
+        self.line_index.range(&self.text, start, end)
+    }
+
+    pub fn diagnostics(&self) -> Vec<Diagnostic> {
+        self.analysis
+            .problems
+            .iter()
+            .map(|problem| Diagnostic {
+                range: self.range(problem.start, problem.end),
+                severity: Some(problem.severity),
+                source: Some("rudof".to_string()),
+                message: problem.message.clone(),
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    /// Symbol which contains a position or ends just before it
+    pub fn symbol_at(&self, position: Position) -> Option<&Symbol> {
+        let offset = self.line_index.offset(&self.text, position);
+        self.analysis
+            .symbols
+            .iter()
+            .find(|symbol| symbol.start <= offset && offset <= symbol.end)
+    }
+
+    pub fn definition(&self, name: &Name) -> Option<&Symbol> {
+        self.analysis
+            .symbols
+            .iter()
+            .find(|symbol| symbol.role == Role::Declaration && &symbol.name == name)
+    }
+
+    pub fn references(&self, name: &Name, include_declaration: bool) -> Vec<&Symbol> {
+        self.analysis
+            .symbols
+            .iter()
+            .filter(|symbol| &symbol.name == name)
+            .filter(|symbol| match symbol.role {
+                Role::Reference => true,
+                Role::Declaration => include_declaration,
+                Role::Other => false,
+            })
+            .collect()
+    }
+
+    /// Qualified name with the prefixes of the document, or the IRI between angle brackets
+    pub fn qualify(&self, name: &Name) -> String {
+        match name {
+            Name::Iri(iri) => self.analysis.prefixmap.qualify(iri),
+            Name::BNode(_) => name.to_string(),
+        }
+    }
+
+    pub fn hover(&self, position: Position) -> Option<Hover> {
+        let symbol = self.symbol_at(position)?;
+        let mut lines = vec![format!("`{}`", self.qualify(&symbol.name))];
+        if let Name::Iri(iri) = &symbol.name {
+            lines.push(format!("`<{iri}>`"))
+        }
+        if let Some(declaration) = self.definition(&symbol.name) {
+            let line = self.range(declaration.start, declaration.end).start.line + 1;
+            lines.push(format!("Shape declared at line {line}"))
+        }
+        lines.dedup();
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: lines.join("\n\n"),
+            }),
+            range: Some(self.range(symbol.start, symbol.end)),
+        })
+    }
+
+    /// Declared prefixes and shape labels
+    pub fn completions(&self) -> Vec<CompletionItem> {
+        let prefixes = self
+            .analysis
+            .prefixmap
+            .iter()
+            .map(|(alias, iri)| CompletionItem {
+                label: format!("{alias}:"),
+                kind: Some(CompletionItemKind::MODULE),
+                detail: Some(iri.to_string()),
+                ..Default::default()
+            });
+        let shapes = self
+            .analysis
+            .shapes()
+            .into_iter()
+            .map(|name| CompletionItem {
+                label: self.qualify(name),
+                kind: Some(CompletionItemKind::CLASS),
+                detail: Some(name.to_string()),
+                ..Default::default()
+            });
+        prefixes.chain(shapes).collect()
+    }
+
+    /// Edit that replaces the document with the output of [`ShExFormatter`]
+    ///
+    /// Only ShExC schemas without syntax errors can be formatted, and schemas that the formatter
+    /// can't write give no edits instead of failing the request
+    pub fn formatting(&self) -> Option<Vec<TextEdit>> {
+        if self.language != Language::ShExC {
+            return None;
+        }
+        let (schema, comments) = ShExParser::parse_with_comments(&self.text, None).ok()?;
+        let formatted = ShExFormatter::default()
+            .without_colors()
+            .format_schema_with_comments(&schema, &comments)
+            .ok()?;
+        Some(vec![TextEdit {
+            range: self.range(0, self.text.len()),
+            new_text: formatted,
+        }])
+    }
+}
@@ -0,0 +1,15 @@
+//! Language server for ShExC schemas and SHACL shapes written in Turtle
+//!
+//! The server publishes diagnostics with the syntax errors of the documents and supports go to definition
+//! and find references of shape labels, hover, completion of prefixes and shape labels, and formatting
+//! of ShExC schemas.
+pub mod document;
+pub mod line_index;
+pub mod server;
+pub mod shacl_analysis;
+pub mod shex_analysis;
+pub mod tokens;
+
+pub use crate::document::*;
+pub use crate::line_index::*;
+pub use crate::server::*;
@@ -0,0 +1,65 @@
+use lsp_types::{Position, Range};
+
+/// Converts between byte offsets in a text and LSP positions
+///
+/// LSP positions count the characters of a line in UTF-16 code units
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    line_starts: Vec<usize>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> LineIndex {
+        let mut line_starts = vec![0];
+        line_starts.extend(text.match_indices('\n').map(|(n, _)| n + 1));
+        LineIndex { line_starts }
+    }
+
+    pub fn position(&self, text: &str, offset: usize) -> Position {
+        let offset = offset.min(text.len());
+        let line = self.line_starts.partition_point(|start| *start <= offset) - 1;
+        let line_start = self.line_starts[line];
+        let character = text
+            .get(line_start..offset)
+            .map(|before| before.encode_utf16().count())
+            .unwrap_or(0);
+        Position::new(line as u32, character as u32)
+    }
+
+    pub fn range(&self, text: &str, start: usize, end: usize) -> Range {
+        Range::new(self.position(text, start), self.position(text, end))
+    }
+
+    /// Offset of a position. Positions after the end of a line are moved to the end of the line
+    pub fn offset(&self, text: &str, position: Position) -> usize {
+        let Some(line_start) = self.line_starts.get(position.line as usize) else {
+            return text.len();
+        };
+        let mut units = 0;
+        for (n, c) in text[*line_start..].char_indices() {
+            if units >= position.character as usize || c == '\n' {
+                return line_start + n;
+            }
+            units += c.len_utf16();
+        }
+        text.len()
+    }
+
+    /// Offset of a line and column counted in characters, both starting at 1
+    pub fn offset_of_line_column(&self, text: &str, line: usize, column: usize) -> usize {
+        let Some(line_start) = self.line_starts.get(line.saturating_sub(1)) else {
+            return text.len();
+        };
+        text[*line_start..]
+            .char_indices()
+            .take_while(|(_, c)| *c != '\n')
+            .nth(column.saturating_sub(1))
+            .map(|(n, _)| line_start + n)
+            .unwrap_or_else(|| {
+                line_start
+                    + text[*line_start..]
+                        .find('\n')
+                        .unwrap_or(text.len() - line_start)
+            })
+    }
+}
@@ -0,0 +1,22 @@
+use anyhow::Result;
+use lsp_server::Connection;
+use tracing_subscriber::{filter::EnvFilter, fmt, prelude::*};
+
+// Editors usually launch the server with `--stdio`, which is the only transport supported,
+// so the arguments are ignored
+fn main() -> Result<()> {
+    // The standard output is used by the protocol, so the logs are written to stderr
+    let fmt_layer = fmt::layer().with_writer(std::io::stderr).without_time();
+    let filter_layer = EnvFilter::try_from_default_env()
+        .or_else(|_| EnvFilter::try_new("info"))
+        .unwrap();
+    tracing_subscriber::registry()
+        .with(filter_layer)
+        .with(fmt_layer)
+        .init();
+
+    let (connection, io_threads) = Connection::stdio();
+    rudof_lsp::run(connection)?;
+    io_threads.join()?;
+    Ok(())
+}
@@ -0,0 +1,479 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as LspNotification, PublishDiagnostics,
+};
+use lsp_types::request::{
+    Completion, Formatting, GotoDefinition, HoverRequest, References, Request as LspRequest,
+};
+use lsp_types::{
+    CompletionOptions, CompletionParams, CompletionResponse, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DocumentFormattingParams,
+    GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverParams, HoverProviderCapability,
+    Location, OneOf, PublishDiagnosticsParams, ReferenceParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Url,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::debug;
+
+use crate::{Document, Language};
+
+/// Runs the language server on a connection until the client asks it to shut down
+///
+/// The connection is usually [`Connection::stdio`], and [`Connection::memory`] allows running
+/// the server in the same process as the client
+pub fn run(connection: Connection) -> Result<()> {
+    let capabilities = serde_json::to_value(server_capabilities())?;
+    connection.initialize(capabilities)?;
+    let mut server = Server {
+        connection: &connection,
+        documents: HashMap::new(),
+    };
+    server.main_loop()
+}
+
+pub fn server_capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![":".to_string(), "@".to_string()]),
+            ..Default::default()
+        }),
+        document_formatting_provider: Some(OneOf::Left(true)),
+        ..Default::default()
+    }
+}
+
+struct Server<'a> {
+    connection: &'a Connection,
+    documents: HashMap<Url, Document>,
+}
+
+impl Server<'_> {
+    fn main_loop(&mut self) -> Result<()> {
+        let connection = self.connection;
+        for message in &connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    let response = self.handle_request(request);
+                    connection.sender.send(Message::Response(response))?
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_request(&self, request: Request) -> Response {
+        debug!("Request {}", request.method);
+        match request.method.as_str() {
+            GotoDefinition::METHOD => self.respond::<GotoDefinition>(request, Self::definition),
+            References::METHOD => self.respond::<References>(request, Self::references),
+            HoverRequest::METHOD => self.respond::<HoverRequest>(request, Self::hover),
+            Completion::METHOD => self.respond::<Completion>(request, Self::completion),
+            Formatting::METHOD => self.respond::<Formatting>(request, Self::formatting),
+            method => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("Unsupported request {method}"),
+            ),
+        }
+    }
+
+    fn respond<R>(&self, request: Request, handler: fn(&Self, R::Params) -> R::Result) -> Response
+    where
+        R: LspRequest,
+        R::Params: DeserializeOwned,
+        R::Result: Serialize,
+    {
+        match serde_json::from_value::<R::Params>(request.params) {
+            Ok(params) => Response::new_ok(request.id, handler(self, params)),
+            Err(err) => {
+                Response::new_err(request.id, ErrorCode::InvalidParams as i32, err.to_string())
+            }
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<()> {
+        debug!("Notification {}", notification.method);
+        match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let document = params.text_document;
+                if let Some(language) =
+                    Language::from_document(&document.language_id, &document.uri)
+                {
+                    self.update(document.uri, Document::new(document.text, language))?
+                }
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                let language = self.documents.get(&uri).map(|document| document.language());
+                if let (Some(language), Some(change)) = (language, params.content_changes.last()) {
+                    self.update(uri, Document::new(change.text.clone(), language))?
+                }
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let uri = params.text_document.uri;
+                if self.documents.remove(&uri).is_some() {
+                    self.publish_diagnostics(uri, Vec::new())?
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn update(&mut self, uri: Url, document: Document) -> Result<()> {
+        let diagnostics = document.diagnostics();
+        self.documents.insert(uri.clone(), document);
+        self.publish_diagnostics(uri, diagnostics)
+    }
+
+    fn publish_diagnostics(&self, uri: Url, diagnostics: Vec<lsp_types::Diagnostic>) -> Result<()> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        let notification = Notification::new(PublishDiagnostics::METHOD.to_string(), params);
+        self.connection
+            .sender
+            .send(Message::Notification(notification))?;
+        Ok(())
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let position = params.text_document_position_params;
+        let uri = position.text_document.uri;
+        let document = self.documents.get(&uri)?;
+        let symbol = document.symbol_at(position.position)?;
+        let declaration = document.definition(&symbol.name)?;
+        Some(GotoDefinitionResponse::Scalar(Location::new(
+            uri.clone(),
+            document.range(declaration.start, declaration.end),
+        )))
+    }
+
+    fn references(&self, params: ReferenceParams) -> Option<Vec<Location>> {
+        let position = params.text_document_position;
+        let uri = position.text_document.uri;
+        let document = self.documents.get(&uri)?;
+        let symbol = document.symbol_at(position.position)?;
+        let references = document
+            .references(&symbol.name, params.context.include_declaration)
+            .into_iter()
+            .map(|symbol| Location::new(uri.clone(), document.range(symbol.start, symbol.end)))
+            .collect();
+        Some(references)
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let position = params.text_document_position_params;
+        let document = self.documents.get(&position.text_document.uri)?;
+        document.hover(position.position)
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+        let document = self
+            .documents
+            .get(&params.text_document_position.text_document.uri)?;
+        Some(CompletionResponse::Array(document.completions()))
+    }
+
+    fn formatting(&self, params: DocumentFormattingParams) -> Option<Vec<TextEdit>> {
+        let document = self.documents.get(&params.text_document.uri)?;
+        document.formatting()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread::{self, JoinHandle};
+    use std::time::Duration;
+
+    use lsp_server::RequestId;
+    use lsp_types::notification::{Exit, Initialized};
+    use lsp_types::request::{Initialize, Shutdown};
+    use lsp_types::{
+        CompletionItem, Diagnostic, DiagnosticSeverity, HoverContents, InitializeParams,
+        InitializedParams, PartialResultParams, Position, ReferenceContext, TextDocumentIdentifier,
+        TextDocumentItem, TextDocumentPositionParams, WorkDoneProgressParams,
+    };
+    use serde_json::Value;
+
+    /// LSP client connected to a server that runs in another thread of the same process
+    struct TestClient {
+        connection: Connection,
+        server: JoinHandle<Result<()>>,
+        next_id: i32,
+    }
+
+    impl TestClient {
+        fn start() -> TestClient {
+            let (server, connection) = Connection::memory();
+            let server = thread::spawn(move || run(server));
+            let mut client = TestClient {
+                connection,
+                server,
+                next_id: 0,
+            };
+            client.request::<Initialize>(InitializeParams::default());
+            client.notify::<Initialized>(InitializedParams {});
+            client
+        }
+
+        fn request<R: LspRequest>(&mut self, params: R::Params) -> Value {
+            self.next_id += 1;
+            let id = RequestId::from(self.next_id);
+            let request = Request::new(id.clone(), R::METHOD.to_string(), params);
+            self.connection
+                .sender
+                .send(Message::Request(request))
+                .unwrap();
+            loop {
+                match self.receive() {
+                    Message::Response(response) if response.id == id => {
+                        return response.result.unwrap_or(Value::Null);
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        fn notify<N: LspNotification>(&self, params: N::Params) {
+            let notification = Notification::new(N::METHOD.to_string(), params);
+            self.connection
+                .sender
+                .send(Message::Notification(notification))
+                .unwrap();
+        }
+
+        fn receive(&self) -> Message {
+            self.connection
+                .receiver
+                .recv_timeout(Duration::from_secs(10))
+                .unwrap()
+        }
+
+        /// Opens a document and waits for its diagnostics
+        fn open(&self, uri: &Url, language_id: &str, text: &str) -> Vec<Diagnostic> {
+            self.notify::<DidOpenTextDocument>(DidOpenTextDocumentParams {
+                text_document: TextDocumentItem::new(
+                    uri.clone(),
+                    language_id.to_string(),
+                    1,
+                    text.to_string(),
+                ),
+            });
+            loop {
+                if let Message::Notification(notification) = self.receive() {
+                    if notification.method == PublishDiagnostics::METHOD {
+                        let params: PublishDiagnosticsParams =
+                            serde_json::from_value(notification.params).unwrap();
+                        return params.diagnostics;
+                    }
+                }
+            }
+        }
+
+        fn position(uri: &Url, line: u32, character: u32) -> TextDocumentPositionParams {
+            TextDocumentPositionParams::new(
+                TextDocumentIdentifier::new(uri.clone()),
+                Position::new(line, character),
+            )
+        }
+
+        fn shutdown(mut self) {
+            self.request::<Shutdown>(());
+            self.notify::<Exit>(());
+            self.server.join().unwrap().unwrap();
+        }
+    }
+
+    const SCHEMA: &str = r#"prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+
+:Person { :name xsd:string ; :knows @:Person * ; :address @:Address }
+:Address { :city xsd:string }
+"#;
+
+    fn uri(name: &str) -> Url {
+        Url::parse(&format!("file:///tmp/{name}")).unwrap()
+    }
+
+    #[test]
+    fn shex_diagnostics() {
+        let client = TestClient::start();
+        let diagnostics = client.open(&uri("ok.shex"), "shex", SCHEMA);
+        assert!(diagnostics.is_empty());
+
+        let undeclared = "prefix : <http://example.org/>\n:S { :p @:T }";
+        let diagnostics = client.open(&uri("undeclared.shex"), "shex", undeclared);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostics[0].range.start, Position::new(1, 9));
+
+        let wrong = "prefix : <http://example.org/>\n:S { :p @:T ;\n  :q }\n:T { }";
+        let diagnostics = client.open(&uri("wrong.shex"), "shex", wrong);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, Some(DiagnosticSeverity::ERROR));
+        assert_eq!(diagnostics[0].range.start.line, 2);
+        client.shutdown()
+    }
+
+    #[test]
+    fn shex_definition_and_references() {
+        let mut client = TestClient::start();
+        let uri = uri("person.shex");
+        client.open(&uri, "shex", SCHEMA);
+
+        // @:Address in the declaration of :Person
+        let definition = client.request::<GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: TestClient::position(&uri, 3, 60),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        });
+        let location: Location = serde_json::from_value(definition).unwrap();
+        assert_eq!(location.range.start, Position::new(4, 0));
+        assert_eq!(location.range.end, Position::new(4, 8));
+
+        let references = client.request::<References>(ReferenceParams {
+            text_document_position: TestClient::position(&uri, 3, 2),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+            context: ReferenceContext {
+                include_declaration: true,
+            },
+        });
+        let references: Vec<Location> = serde_json::from_value(references).unwrap();
+        let lines: Vec<_> = references
+            .iter()
+            .map(|location| (location.range.start.line, location.range.start.character))
+            .collect();
+        assert_eq!(lines, vec![(3, 0), (3, 37)]);
+        client.shutdown()
+    }
+
+    #[test]
+    fn shex_hover_completion_and_formatting() {
+        let mut client = TestClient::start();
+        let uri = uri("person.shex");
+        client.open(&uri, "shex", SCHEMA);
+
+        let hover = client.request::<HoverRequest>(HoverParams {
+            text_document_position_params: TestClient::position(&uri, 3, 12),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        let hover: Hover = serde_json::from_value(hover).unwrap();
+        let HoverContents::Markup(contents) = hover.contents else {
+            panic!("Expected markup")
+        };
+        assert!(contents.value.contains("`<http://example.org/name>`"));
+
+        let completion = client.request::<Completion>(CompletionParams {
+            text_document_position: TestClient::position(&uri, 4, 0),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+            context: None,
+        });
+        let items: Vec<CompletionItem> = serde_json::from_value(completion).unwrap();
+        let labels: Vec<_> = items.iter().map(|item| item.label.as_str()).collect();
+        assert!(labels.contains(&"xsd:"));
+        assert!(labels.contains(&":Person"));
+        assert!(labels.contains(&":Address"));
+
+        let edits = client.request::<Formatting>(DocumentFormattingParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            options: Default::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        });
+        let edits: Vec<TextEdit> = serde_json::from_value(edits).unwrap();
+        assert_eq!(edits.len(), 1);
+        assert!(edits[0].new_text.contains(":Address {"));
+        client.shutdown()
+    }
+
+    #[test]
+    fn shex_formatting_one_of() {
+        let mut client = TestClient::start();
+        let one_of = "prefix : <http://example.org/>\n:S { :p . | :q [true] ; :r [@en~] }";
+        let one_of_uri = uri("one_of.shex");
+        client.open(&one_of_uri, "shex", one_of);
+        let wrong_uri = uri("wrong.shex");
+        client.open(
+            &wrong_uri,
+            "shex",
+            "prefix : <http://example.org/>\n:S { :p",
+        );
+
+        let formatting = |uri: &Url| DocumentFormattingParams {
+            text_document: TextDocumentIdentifier::new(uri.clone()),
+            options: Default::default(),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+        };
+        let edits = client.request::<Formatting>(formatting(&one_of_uri));
+        let edits: Vec<TextEdit> = serde_json::from_value(edits).unwrap();
+        assert_eq!(edits.len(), 1);
+        assert!(edits[0].new_text.contains(" |"));
+        assert!(edits[0].new_text.contains("@en~"));
+
+        // A schema that can't be formatted gives no edits and the server keeps running
+        let edits = client.request::<Formatting>(formatting(&wrong_uri));
+        assert_eq!(edits, Value::Null);
+        let edits = client.request::<Formatting>(formatting(&one_of_uri));
+        assert!(!edits.is_null());
+        client.shutdown()
+    }
+
+    #[test]
+    fn shacl_diagnostics_and_definition() {
+        let mut client = TestClient::start();
+        let shapes = r#"@prefix sh: <http://www.w3.org/ns/shacl#> .
+@prefix : <http://example.org/> .
+
+:PersonShape a sh:NodeShape ;
+  sh:property [ sh:path :address ; sh:node :AddressShape ] .
+
+:AddressShape a sh:NodeShape ;
+  sh:property [ sh:path :city ; sh:minCount 1 ] .
+"#;
+        let shapes_uri = uri("shapes.ttl");
+        let diagnostics = client.open(&shapes_uri, "turtle", shapes);
+        assert!(diagnostics.is_empty());
+
+        let definition = client.request::<GotoDefinition>(GotoDefinitionParams {
+            text_document_position_params: TestClient::position(&shapes_uri, 4, 45),
+            work_done_progress_params: WorkDoneProgressParams::default(),
+            partial_result_params: PartialResultParams::default(),
+        });
+        let location: Location = serde_json::from_value(definition).unwrap();
+        assert_eq!(location.range.start, Position::new(6, 0));
+
+        let diagnostics = client.open(
+            &uri("wrong.ttl"),
+            "turtle",
+            "@prefix : <http://example.org/> .\n:a :b .",
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].range.start.line, 1);
+        client.shutdown()
+    }
+}
@@ -0,0 +1,121 @@
+use iri_s::IriS;
+use lsp_types::DiagnosticSeverity;
+use oxttl::TurtleParser;
+use prefixmap::PrefixMap;
+use shacl_ast::{Schema, ShaclParser};
+use srdf::srdf_graph::SRDFGraph;
+use srdf::{Object, RDFFormat, ReaderMode};
+
+use crate::tokens::{tokenize, TokenKind};
+use crate::{Analysis, Language, Name, Problem, Role, Symbol};
+
+/// Analyses SHACL shapes written in Turtle
+///
+/// The declaration of a shape is the occurrence of its node as the subject of a statement
+/// and the references are the other occurrences
+pub fn analyze(text: &str) -> Analysis {
+    let mut problems = Vec::new();
+    let mut reader = TurtleParser::new().for_slice(text.as_bytes());
+    for result in reader.by_ref() {
+        if let Err(err) = result {
+            let location = err.location();
+            problems.push(Problem {
+                message: err.message().to_string(),
+                severity: DiagnosticSeverity::ERROR,
+                start: location.start.offset as usize,
+                end: location.end.offset as usize,
+            })
+        }
+    }
+    let mut prefixmap = PrefixMap::new();
+    for (alias, iri) in reader.prefixes() {
+        let _ = prefixmap.insert(alias, &IriS::new_unchecked(iri));
+    }
+
+    let shapes = if problems.is_empty() {
+        match parse_shapes(text) {
+            Ok(schema) => schema.iter().filter_map(|(node, _)| name(node)).collect(),
+            Err(message) => {
+                problems.push(Problem {
+                    message,
+                    severity: DiagnosticSeverity::ERROR,
+                    start: 0,
+                    end: text.find('\n').unwrap_or(text.len()),
+                });
+                Vec::new()
+            }
+        }
+    } else {
+        Vec::new()
+    };
+
+    let tokens = tokenize(text, Language::Turtle);
+    let mut symbols = Vec::new();
+    let mut statement_start = true;
+    let mut directive = false;
+    for token in tokens.iter() {
+        match token.kind {
+            TokenKind::Symbol('.') => {
+                statement_start = true;
+                directive = false;
+                continue;
+            }
+            TokenKind::Symbol('@') if statement_start => {
+                directive = true;
+                continue;
+            }
+            TokenKind::Word
+                if statement_start
+                    && (token.is_keyword(text, "prefix") || token.is_keyword(text, "base")) =>
+            {
+                directive = true;
+                statement_start = false;
+                continue;
+            }
+            // SPARQL style directives end with their IRI
+            TokenKind::IriRef if directive => {
+                directive = false;
+                statement_start = true;
+                continue;
+            }
+            _ => {}
+        }
+        if let Some(name) = Name::from_token(token, text, &prefixmap, &None) {
+            let role = if !shapes.contains(&name) || directive {
+                Role::Other
+            } else if statement_start {
+                Role::Declaration
+            } else {
+                Role::Reference
+            };
+            symbols.push(Symbol {
+                name,
+                role,
+                start: token.start,
+                end: token.end,
+            })
+        }
+        statement_start = false;
+    }
+    Analysis {
+        problems,
+        prefixmap,
+        symbols,
+    }
+}
+
+fn parse_shapes(text: &str) -> Result<Schema, String> {
+    let graph = SRDFGraph::from_str(text, &RDFFormat::Turtle, None, &ReaderMode::default())
+        .map_err(|e| e.to_string())?;
+    ShaclParser::new(graph)
+        .parse()
+        .map_err(|e| format!("Error parsing SHACL shapes: {e}"))
+}
+
+fn name(node: &Object) -> Option<Name> {
+    match node {
+        Object::Iri(iri) => Some(Name::Iri(iri.clone())),
+        Object::BlankNode(id) => Some(Name::BNode(id.clone())),
+        Object::Literal(_) | Object::Triple { .. } => None,
+    }
+}
@@ -0,0 +1,101 @@
+use lsp_types::DiagnosticSeverity;
+use shex_compact::{ShExParser, StatementKind};
+
+use crate::tokens::{tokenize, TokenKind};
+use crate::{Analysis, Language, LineIndex, Name, Problem, Role, Symbol};
+
+/// Analyses a ShExC schema with the parser in error recovery mode
+///
+/// The label of a shape declaration is the first name of its statement and the references are the names
+/// that follow `@` or `&`
+pub fn analyze(text: &str) -> Analysis {
+    let (schema, diagnostics, statements) = ShExParser::parse_with_spans(text, None);
+    let line_index = LineIndex::new(text);
+    let mut problems: Vec<Problem> = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let mut message = diagnostic.message.clone();
+            if !diagnostic.expected.is_empty() {
+                message.push_str(&format!(
+                    ". Expected one of: {}",
+                    diagnostic.expected.join(", ")
+                ))
+            }
+            Problem {
+                message,
+                severity: DiagnosticSeverity::ERROR,
+                start: line_index.offset_of_line_column(
+                    text,
+                    diagnostic.start.line as usize,
+                    diagnostic.start.column,
+                ),
+                end: line_index.offset_of_line_column(
+                    text,
+                    diagnostic.end.line as usize,
+                    diagnostic.end.column,
+                ),
+            }
+        })
+        .collect();
+
+    let prefixmap = schema.prefixmap().unwrap_or_default();
+    let base = schema.base();
+    let tokens = tokenize(text, Language::ShExC);
+    let declarations: Vec<usize> = statements
+        .iter()
+        .filter(|statement| matches!(statement.kind, StatementKind::ShapeDecl(_)))
+        .filter_map(|statement| {
+            tokens.iter().position(|token| {
+                token.is_name() && token.start >= statement.start && token.start < statement.end
+            })
+        })
+        .collect();
+    let mut symbols = Vec::new();
+    for (n, token) in tokens.iter().enumerate() {
+        let Some(name) = Name::from_token(token, text, &prefixmap, &base) else {
+            continue;
+        };
+        let role = if declarations.contains(&n) {
+            Role::Declaration
+        } else if n > 0
+            && matches!(
+                tokens[n - 1].kind,
+                TokenKind::Symbol('@') | TokenKind::Symbol('&')
+            )
+        {
+            Role::Reference
+        } else {
+            Role::Other
+        };
+        symbols.push(Symbol {
+            name,
+            role,
+            start: token.start,
+            end: token.end,
+        })
+    }
+    let mut analysis = Analysis {
+        problems: Vec::new(),
+        prefixmap,
+        symbols,
+    };
+
+    // Shapes of imported schemas are not known, and declarations with syntax errors are missing
+    let has_imports = tokens.iter().any(|token| token.is_keyword(text, "import"));
+    if !has_imports && problems.is_empty() {
+        let shapes = analysis.shapes();
+        for symbol in analysis.symbols.iter() {
+            if symbol.role == Role::Reference && !shapes.contains(&&symbol.name) {
+                let label = &text[symbol.start..symbol.end];
+                problems.push(Problem {
+                    message: format!("Shape {label} is not declared"),
+                    severity: DiagnosticSeverity::WARNING,
+                    start: symbol.start,
+                    end: symbol.end,
+                })
+            }
+        }
+    }
+    analysis.problems = problems;
+    analysis
+}
@@ -0,0 +1,297 @@
+//! Lexical scanner for the names of ShExC and Turtle documents
+//!
+//! The scanner doesn't check the syntax of the documents. It only finds the IRIs, prefixed names and blank nodes
+//! with their offsets, skipping comments, strings and regular expressions, so the names can be located
+//! even when the document has syntax errors.
+
+use crate::Language;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// IRI between angle brackets, including the brackets
+    IriRef,
+
+    /// Prefixed name like `foaf:name`, or the prefix of a prefix declaration like `foaf:`
+    PrefixedName,
+
+    BlankNode,
+
+    /// Keywords, numbers and other words
+    Word,
+
+    Symbol(char),
+}
+
+/// Token between the byte offsets `start` and `end`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Token {
+    pub fn text<'a>(&self, src: &'a str) -> &'a str {
+        &src[self.start..self.end]
+    }
+
+    /// The token is an IRI, a prefixed name or a blank node
+    pub fn is_name(&self) -> bool {
+        matches!(
+            self.kind,
+            TokenKind::IriRef | TokenKind::PrefixedName | TokenKind::BlankNode
+        )
+    }
+
+    /// The token is a word equal to `keyword` ignoring case
+    pub fn is_keyword(&self, src: &str, keyword: &str) -> bool {
+        self.kind == TokenKind::Word && self.text(src).eq_ignore_ascii_case(keyword)
+    }
+}
+
+pub fn tokenize(src: &str, language: Language) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut pos = 0;
+    while let Some(c) = char_at(src, pos) {
+        let start = pos;
+        let kind = match c {
+            c if c.is_whitespace() => {
+                pos += c.len_utf8();
+                continue;
+            }
+            '#' => {
+                pos = line_end(src, pos);
+                continue;
+            }
+            '"' | '\'' => {
+                pos = string_end(src, pos, c);
+                continue;
+            }
+            '/' if language == Language::ShExC && char_at(src, pos + 1) != Some('/') => {
+                pos = regex_end(src, pos);
+                continue;
+            }
+            '%' if language == Language::ShExC => {
+                pos = semantic_action_end(src, pos);
+                continue;
+            }
+            '<' => match iri_end(src, pos) {
+                Some(end) => {
+                    pos = end;
+                    TokenKind::IriRef
+                }
+                None => {
+                    pos += 1;
+                    TokenKind::Symbol('<')
+                }
+            },
+            '_' if char_at(src, pos + 1) == Some(':') => {
+                pos = name_end(src, pos + 2, true);
+                TokenKind::BlankNode
+            }
+            c if c.is_ascii_digit() => {
+                pos = number_end(src, pos);
+                TokenKind::Word
+            }
+            c if is_name_char(c) || c == ':' => {
+                pos = name_end(src, pos, false);
+                if char_at(src, pos) == Some(':') {
+                    pos = name_end(src, pos + 1, true);
+                    TokenKind::PrefixedName
+                } else {
+                    TokenKind::Word
+                }
+            }
+            c => {
+                pos += c.len_utf8();
+                TokenKind::Symbol(c)
+            }
+        };
+        tokens.push(Token {
+            kind,
+            start,
+            end: pos,
+        })
+    }
+    tokens
+}
+
+fn char_at(src: &str, pos: usize) -> Option<char> {
+    src.get(pos..).and_then(|rest| rest.chars().next())
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '-'
+}
+
+// Names can contain dots, but not at the end, where they end the statement in Turtle
+fn name_end(src: &str, mut pos: usize, local: bool) -> usize {
+    while let Some(c) = char_at(src, pos) {
+        let continues = is_name_char(c)
+            || (local && c == ':')
+            || (c == '.' && char_at(src, pos + 1).is_some_and(is_name_char));
+        if continues {
+            pos += c.len_utf8()
+        } else if local && c == '\\' {
+            pos += 1 + char_at(src, pos + 1).map(|c| c.len_utf8()).unwrap_or(0)
+        } else {
+            break;
+        }
+    }
+    pos
+}
+
+fn number_end(src: &str, mut pos: usize) -> usize {
+    while let Some(c) = char_at(src, pos) {
+        let continues = c.is_ascii_alphanumeric()
+            || (c == '.' && char_at(src, pos + 1).is_some_and(|c| c.is_ascii_digit()))
+            || (matches!(c, '+' | '-') && matches!(char_at(src, pos - 1), Some('e' | 'E')));
+        if continues {
+            pos += 1
+        } else {
+            break;
+        }
+    }
+    pos
+}
+
+fn line_end(src: &str, pos: usize) -> usize {
+    src[pos..].find('\n').map(|n| pos + n).unwrap_or(src.len())
+}
+
+// IRIs can't contain spaces, so a `<` which isn't closed in the same word is a symbol
+fn iri_end(src: &str, pos: usize) -> Option<usize> {
+    for (n, c) in src[pos + 1..].char_indices() {
+        match c {
+            '>' => return Some(pos + 1 + n + 1),
+            c if c.is_whitespace() || c == '<' => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+// Unterminated short strings end at the end of the line
+fn string_end(src: &str, pos: usize, quote: char) -> usize {
+    let long_quote: String = [quote; 3].iter().collect();
+    if src[pos..].starts_with(&long_quote) {
+        let mut n = pos + 3;
+        while n < src.len() {
+            if src[n..].starts_with('\\') {
+                n += 2
+            } else if src[n..].starts_with(&long_quote) {
+                return n + 3;
+            } else {
+                n += char_at(src, n).map(|c| c.len_utf8()).unwrap_or(1)
+            }
+        }
+        return src.len();
+    }
+    let mut escaped = false;
+    for (n, c) in src[pos + 1..].char_indices() {
+        match c {
+            '\n' => return pos + 1 + n,
+            c if c == quote && !escaped => return pos + 1 + n + 1,
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    src.len()
+}
+
+// Regular expressions of ShExC like `/^[a-z]+$/i`
+fn regex_end(src: &str, pos: usize) -> usize {
+    let mut escaped = false;
+    for (n, c) in src[pos + 1..].char_indices() {
+        match c {
+            '\n' => return pos + 1 + n,
+            '/' if !escaped => {
+                let end = pos + 1 + n + 1;
+                return end + src[end..].chars().take_while(|c| c.is_alphabetic()).count();
+            }
+            '\\' => escaped = !escaped,
+            _ => escaped = false,
+        }
+    }
+    src.len()
+}
+
+// Semantic actions of ShExC like `%ex:action{ code %}`, whose code can contain any character
+fn semantic_action_end(src: &str, pos: usize) -> usize {
+    let name_end = match char_at(src, pos + 1) {
+        Some('<') => iri_end(src, pos + 1).unwrap_or(pos + 1),
+        _ => {
+            let prefix_end = name_end(src, pos + 1, false);
+            if char_at(src, prefix_end) == Some(':') {
+                name_end(src, prefix_end + 1, true)
+            } else {
+                prefix_end
+            }
+        }
+    };
+    let code_start = name_end + src[name_end..].len() - src[name_end..].trim_start().len();
+    if char_at(src, code_start) == Some('{') {
+        match src[code_start..].find("%}") {
+            Some(n) => code_start + n + 2,
+            None => src.len(),
+        }
+    } else {
+        name_end
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(src: &str, language: Language) -> Vec<(&str, TokenKind)> {
+        tokenize(src, language)
+            .into_iter()
+            .filter(|t| t.is_name())
+            .map(|t| (t.text(src), t.kind))
+            .collect()
+    }
+
+    #[test]
+    fn shexc_names_skip_strings_comments_and_regexes() {
+        let src = r#"prefix : <http://example.org/>
+# :Comment
+:S { :p /:[a-z]+/i ; # :q
+     :r [ "a :b" ] ; %:act{ :code %} :t @_:x }"#;
+        assert_eq!(
+            names(src, Language::ShExC),
+            vec![
+                (":", TokenKind::PrefixedName),
+                ("<http://example.org/>", TokenKind::IriRef),
+                (":S", TokenKind::PrefixedName),
+                (":p", TokenKind::PrefixedName),
+                (":r", TokenKind::PrefixedName),
+                (":t", TokenKind::PrefixedName),
+                ("_:x", TokenKind::BlankNode),
+            ]
+        );
+    }
+
+    #[test]
+    fn turtle_names_end_before_dots() {
+        let src = "@prefix ex: <http://example.org/> .\nex:a ex:b 1.5, ex:c.d.";
+        let tokens = tokenize(src, Language::Turtle);
+        let texts: Vec<_> = tokens.iter().map(|t| t.text(src)).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "@",
+                "prefix",
+                "ex:",
+                "<http://example.org/>",
+                ".",
+                "ex:a",
+                "ex:b",
+                "1.5",
+                ",",
+                "ex:c.d",
+                "."
+            ]
+        );
+    }
+}
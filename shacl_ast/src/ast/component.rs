@@ -1,11 +1,13 @@
 use crate::{
-    node_kind::NodeKind, value::Value, SH_AND_STR, SH_CLASS_STR, SH_CLOSED_STR, SH_DATATYPE_STR,
-    SH_DISJOINT_STR, SH_EQUALS_STR, SH_FLAGS_STR, SH_HAS_VALUE_STR, SH_IGNORED_PROPERTIES_STR,
-    SH_IN_STR, SH_IRI_STR, SH_LANGUAGE_IN_STR, SH_LESS_THAN_OR_EQUALS_STR, SH_LESS_THAN_STR,
-    SH_MAX_COUNT_STR, SH_MAX_EXCLUSIVE_STR, SH_MAX_INCLUSIVE_STR, SH_MAX_LENGTH_STR,
-    SH_MIN_COUNT_STR, SH_MIN_EXCLUSIVE_STR, SH_MIN_INCLUSIVE_STR, SH_MIN_LENGTH_STR, SH_NODE_STR,
-    SH_NOT_STR, SH_OR_STR, SH_PATTERN_STR, SH_QUALIFIED_MAX_COUNT_STR, SH_QUALIFIED_MIN_COUNT_STR,
-    SH_QUALIFIED_VALUE_SHAPE_STR, SH_UNIQUE_LANG_STR, SH_XONE_STR,
+    node_kind::NodeKind, value::Value, SH_AND_STR, SH_BLANKNODE_STR, SH_BLANK_NODE_OR_IRI_STR,
+    SH_BLANK_NODE_OR_LITERAL_STR, SH_CLASS_STR, SH_CLOSED_STR, SH_DATATYPE_STR, SH_DISJOINT_STR,
+    SH_EQUALS_STR, SH_FLAGS_STR, SH_HAS_VALUE_STR, SH_IGNORED_PROPERTIES_STR, SH_IN_STR,
+    SH_IRI_OR_LITERAL_STR, SH_IRI_STR, SH_LANGUAGE_IN_STR, SH_LESS_THAN_OR_EQUALS_STR,
+    SH_LESS_THAN_STR, SH_LITERAL_STR, SH_MAX_COUNT_STR, SH_MAX_EXCLUSIVE_STR, SH_MAX_INCLUSIVE_STR,
+    SH_MAX_LENGTH_STR, SH_MIN_COUNT_STR, SH_MIN_EXCLUSIVE_STR, SH_MIN_INCLUSIVE_STR,
+    SH_MIN_LENGTH_STR, SH_NODE_KIND_STR, SH_NODE_STR, SH_NOT_STR, SH_OR_STR, SH_PATTERN_STR,
+    SH_QUALIFIED_MAX_COUNT_STR, SH_QUALIFIED_MIN_COUNT_STR, SH_QUALIFIED_VALUE_SHAPE_STR,
+    SH_TRIPLE_TERM_STR, SH_UNIQUE_LANG_STR, SH_XONE_STR,
 };
 use iri_s::{iri, IriS};
 use itertools::Itertools;
@@ -70,6 +72,12 @@ pub enum Component {
         qualified_max_count: Option<isize>,
         qualified_value_shapes_disjoint: Option<bool>,
     },
+    /// Constraint component declared with `sh:parameter` in the shapes graph,
+    /// with the values of its parameters in the shape
+    Custom {
+        component: IriS,
+        parameters: Vec<(IriS, RDFNode)>,
+    },
 }
 
 impl Component {
@@ -87,11 +95,15 @@ impl Component {
             Self::NodeKind(node_kind) => {
                 let iri = match &node_kind {
                     NodeKind::Iri => SH_IRI_STR,
-
-                    _ => unimplemented!(),
+                    NodeKind::Literal => SH_LITERAL_STR,
+                    NodeKind::BlankNode => SH_BLANKNODE_STR,
+                    NodeKind::BlankNodeOrIri => SH_BLANK_NODE_OR_IRI_STR,
+                    NodeKind::BlankNodeOrLiteral => SH_BLANK_NODE_OR_LITERAL_STR,
+                    NodeKind::IRIOrLiteral => SH_IRI_OR_LITERAL_STR,
+                    NodeKind::TripleTerm => SH_TRIPLE_TERM_STR,
                 };
 
-                Self::write_iri(&IriRef::Iri(iri!(iri)), SH_DATATYPE_STR, rdf_node, rdf)?;
+                Self::write_iri(&IriRef::Iri(iri!(iri)), SH_NODE_KIND_STR, rdf_node, rdf)?;
             }
             Self::MinCount(value) => {
                 Self::write_integer(*value, SH_MIN_COUNT_STR, rdf_node, rdf)?;
@@ -228,6 +240,16 @@ impl Component {
                     Self::write_boolean(*value, SH_QUALIFIED_MAX_COUNT_STR, rdf_node, rdf)?;
                 }
             }
+            Self::Custom { parameters, .. } => {
+                parameters.iter().try_for_each(|(parameter, value)| {
+                    Self::write_term(
+                        &RDF::object_as_term(value),
+                        parameter.as_str(),
+                        rdf_node,
+                        rdf,
+                    )
+                })?;
+            }
         }
         Ok(())
     }
@@ -360,6 +382,16 @@ impl Display for Component {
                 write!(f, "In [{str}]")
             }
             Component::QualifiedValueShape { .. } => todo!(),
+            Component::Custom {
+                component,
+                parameters,
+            } => {
+                let str = parameters
+                    .iter()
+                    .map(|(parameter, value)| format!("{parameter}={value}"))
+                    .join(" ");
+                write!(f, "{component}({str})")
+            }
         }
     }
 }
@@ -396,6 +428,7 @@ impl From<Component> for IriS {
             Component::QualifiedValueShape { .. } => {
                 IriS::new_unchecked(SH_QUALIFIED_VALUE_SHAPE_STR)
             }
+            Component::Custom { component, .. } => component,
         }
     }
 }
@@ -1,8 +1,10 @@
 pub mod component;
 pub mod message_map;
+pub mod node_expr;
 pub mod node_kind;
 pub mod node_shape;
 pub mod property_shape;
+pub mod rule;
 pub mod schema;
 pub mod severity;
 pub mod shacl_error;
@@ -0,0 +1,64 @@
+use std::fmt::Display;
+
+use iri_s::IriS;
+use srdf::{RDFNode, SHACLPath};
+
+/// [Node expressions](https://www.w3.org/TR/shacl-af/#node-expressions) of SHACL Advanced Features
+///
+/// They are evaluated for a focus node to obtain a set of nodes
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeExpr {
+    /// `sh:this`, the focus node
+    FocusNode,
+
+    /// An IRI or a literal, which evaluates to itself
+    Constant(RDFNode),
+
+    /// `[ sh:path path ; sh:nodes nodes ]`, the values of the path for the nodes,
+    /// or for the focus node if there is no `sh:nodes`
+    Path {
+        path: SHACLPath,
+        nodes: Option<Box<NodeExpr>>,
+    },
+
+    /// `[ sh:filterShape shape ; sh:nodes nodes ]`, the nodes that conform to the shape
+    Filter {
+        shape: RDFNode,
+        nodes: Box<NodeExpr>,
+    },
+
+    /// `[ sh:intersection (exprs) ]`
+    Intersection { exprs: Vec<NodeExpr> },
+
+    /// `[ sh:union (exprs) ]`
+    Union { exprs: Vec<NodeExpr> },
+
+    /// `[ function (args) ]`, the results of calling the function with every combination of
+    /// the values of the arguments
+    Function { function: IriS, args: Vec<NodeExpr> },
+}
+
+impl Display for NodeExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeExpr::FocusNode => write!(f, "this"),
+            NodeExpr::Constant(node) => write!(f, "{node}"),
+            NodeExpr::Path { path, nodes } => match nodes {
+                Some(nodes) => write!(f, "path({path}, {nodes})"),
+                None => write!(f, "path({path})"),
+            },
+            NodeExpr::Filter { shape, nodes } => write!(f, "filter({shape}, {nodes})"),
+            NodeExpr::Intersection { exprs } => write!(f, "intersection({})", list(exprs)),
+            NodeExpr::Union { exprs } => write!(f, "union({})", list(exprs)),
+            NodeExpr::Function { function, args } => write!(f, "<{function}>({})", list(args)),
+        }
+    }
+}
+
+fn list(exprs: &[NodeExpr]) -> String {
+    exprs
+        .iter()
+        .map(|expr| expr.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
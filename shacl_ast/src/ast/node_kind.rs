@@ -8,6 +8,9 @@ pub enum NodeKind {
     BlankNodeOrIri,
     BlankNodeOrLiteral,
     IRIOrLiteral,
+
+    /// Triple terms (quoted triples in RDF-star)
+    TripleTerm,
 }
 
 impl Display for NodeKind {
@@ -19,6 +22,7 @@ impl Display for NodeKind {
             NodeKind::BlankNodeOrIri => crate::SH_BLANK_NODE_OR_IRI.as_named_node(),
             NodeKind::BlankNodeOrLiteral => crate::SH_BLANK_NODE_OR_LITERAL.as_named_node(),
             NodeKind::IRIOrLiteral => crate::SH_IRI_OR_LITERAL.as_named_node(),
+            NodeKind::TripleTerm => crate::SH_TRIPLE_TERM.as_named_node(),
         };
         write!(f, "{}", node)
     }
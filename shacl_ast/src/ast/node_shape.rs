@@ -1,5 +1,5 @@
 use crate::{
-    component::Component, message_map::MessageMap, severity::Severity, target::Target,
+    component::Component, message_map::MessageMap, rule::Rule, severity::Severity, target::Target,
     SH_CLOSED_STR, SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR, SH_NAME_STR,
     SH_NODE_SHAPE, SH_PROPERTY_STR, SH_SEVERITY_STR, SH_VIOLATION_STR, SH_WARNING_STR,
 };
@@ -23,6 +23,7 @@ pub struct NodeShape {
     description: MessageMap,
     group: Option<RDFNode>,
     // source_iri: Option<IriRef>,
+    rules: Vec<Rule>,
 }
 
 impl NodeShape {
@@ -41,6 +42,7 @@ impl NodeShape {
             description: MessageMap::new(),
             group: None,
             // source_iri: None,
+            rules: Vec::new(),
         }
     }
 
@@ -68,6 +70,11 @@ impl NodeShape {
         self
     }
 
+    pub fn with_rules(mut self, rules: Vec<Rule>) -> Self {
+        self.rules = rules;
+        self
+    }
+
     pub fn id(&self) -> &RDFNode {
         &self.id
     }
@@ -92,6 +99,11 @@ impl NodeShape {
         &self.property_shapes
     }
 
+    /// SHACL-AF rules declared with `sh:rule`
+    pub fn rules(&self) -> &Vec<Rule> {
+        &self.rules
+    }
+
     pub fn write<RDF>(&self, rdf: &mut RDF) -> Result<(), RDF::Err>
     where
         RDF: SRDFBuilder,
@@ -192,6 +204,9 @@ impl Display for NodeShape {
         for component in self.components.iter() {
             writeln!(f, "       {component}")?
         }
+        for rule in self.rules.iter() {
+            writeln!(f, "       {rule}")?
+        }
         write!(f, "}}")?;
         Ok(())
     }
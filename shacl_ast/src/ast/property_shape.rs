@@ -1,15 +1,17 @@
 use iri_s::{iri, IriS};
-use oxrdf::{BlankNode, Literal as OxLiteral, NamedNode, Subject, Term as OxTerm};
+use oxrdf::{BlankNode, Literal as OxLiteral, NamedNode, Term as OxTerm};
 use srdf::{
-    numeric_literal::NumericLiteral, RDFNode, SHACLPath, SRDFBuilder, SRDFGraph, SRDF,
-    XSD_DECIMAL_STR,
+    numeric_literal::NumericLiteral, RDFNode, SHACLPath, SRDFBasic, SRDFBuilder, SRDFGraph,
+    RDF_FIRST, RDF_NIL, RDF_REST, SRDF, XSD_DECIMAL_STR,
 };
 use std::{collections::HashSet, fmt::Display};
 
 use crate::{
     component::Component, message_map::MessageMap, severity::Severity, target::Target,
-    SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR, SH_NAME_STR, SH_ORDER_STR,
-    SH_PATH_STR, SH_PROPERTY_SHAPE, SH_SEVERITY_STR, SH_VIOLATION_STR, SH_WARNING_STR,
+    SH_ALTERNATIVE_PATH, SH_DEACTIVATED_STR, SH_DESCRIPTION_STR, SH_GROUP_STR, SH_INFO_STR,
+    SH_INVERSE_PATH, SH_NAME_STR, SH_ONE_OR_MORE_PATH, SH_ORDER_STR, SH_PATH_STR,
+    SH_PROPERTY_SHAPE, SH_SEVERITY_STR, SH_VIOLATION_STR, SH_WARNING_STR, SH_ZERO_OR_MORE_PATH,
+    SH_ZERO_OR_ONE_PATH,
 };
 
 #[derive(Debug, Clone)]
@@ -142,25 +144,16 @@ impl PropertyShape {
     ) -> HashSet<RDFNode> {
         match path {
             SHACLPath::Predicate { pred } => {
-                let subject = match focus_node {
-                    RDFNode::Iri(iri_s) => Subject::NamedNode(iri_s.as_named_node().to_owned()),
-                    RDFNode::BlankNode(id) => Subject::BlankNode(BlankNode::new_unchecked(id)),
-                    RDFNode::Literal(_) => todo!(),
+                // Literals have no outgoing arcs
+                let Some(subject) = SRDFGraph::object_as_subject(focus_node) else {
+                    return HashSet::new();
                 };
                 if let Ok(objects) =
                     data_graph.objects_for_subject_predicate(&subject, pred.as_named_node())
                 {
                     objects
-                        .into_iter()
-                        .map(|object| match object {
-                            OxTerm::NamedNode(node) => {
-                                RDFNode::iri(IriS::new_unchecked(node.as_str()))
-                            }
-                            OxTerm::BlankNode(node) => RDFNode::bnode(node.to_string()),
-                            OxTerm::Literal(literal) => RDFNode::literal(literal.into()),
-                            #[cfg(feature = "rdf-star")]
-                            OxTerm::Triple(_) => unimplemented!(),
-                        })
+                        .iter()
+                        .map(SRDFGraph::term_as_object)
                         .collect::<HashSet<RDFNode>>()
                 } else {
                     HashSet::new()
@@ -215,15 +208,12 @@ impl PropertyShape {
             )?;
         }
 
-        if let SHACLPath::Predicate { pred } = &self.path {
-            rdf.add_triple(
-                &RDF::object_as_subject(&self.id).unwrap(),
-                &RDF::iri_s2iri(&iri!(SH_PATH_STR)),
-                &RDF::iri_s2term(pred),
-            )?;
-        } else {
-            unimplemented!()
-        }
+        let path = write_path(&self.path, rdf)?;
+        rdf.add_triple(
+            &RDF::object_as_subject(&self.id).unwrap(),
+            &RDF::iri_s2iri(&iri!(SH_PATH_STR)),
+            &path,
+        )?;
 
         self.components
             .iter()
@@ -262,6 +252,75 @@ impl PropertyShape {
     }
 }
 
+/// Writes a SHACL path and returns the node which represents it
+///
+/// Complex paths are written with fresh blank nodes, as described in
+/// [SHACL property paths](https://www.w3.org/TR/shacl/#property-paths)
+fn write_path<RDF>(path: &SHACLPath, rdf: &mut RDF) -> Result<RDF::Term, RDF::Err>
+where
+    RDF: SRDFBuilder,
+{
+    match path {
+        SHACLPath::Predicate { pred } => Ok(RDF::iri_s2term(pred)),
+        SHACLPath::Sequence { paths } => write_path_list(paths, rdf),
+        SHACLPath::Alternative { paths } => {
+            let list = write_path_list(paths, rdf)?;
+            write_path_node(&SH_ALTERNATIVE_PATH, list, rdf)
+        }
+        SHACLPath::Inverse { path } => {
+            let path = write_path(path, rdf)?;
+            write_path_node(&SH_INVERSE_PATH, path, rdf)
+        }
+        SHACLPath::ZeroOrMore { path } => {
+            let path = write_path(path, rdf)?;
+            write_path_node(&SH_ZERO_OR_MORE_PATH, path, rdf)
+        }
+        SHACLPath::OneOrMore { path } => {
+            let path = write_path(path, rdf)?;
+            write_path_node(&SH_ONE_OR_MORE_PATH, path, rdf)
+        }
+        SHACLPath::ZeroOrOne { path } => {
+            let path = write_path(path, rdf)?;
+            write_path_node(&SH_ZERO_OR_ONE_PATH, path, rdf)
+        }
+    }
+}
+
+/// Writes a blank node whose value for `property` is `value`
+fn write_path_node<RDF>(
+    property: &IriS,
+    value: RDF::Term,
+    rdf: &mut RDF,
+) -> Result<RDF::Term, RDF::Err>
+where
+    RDF: SRDFBuilder,
+{
+    let id = BlankNode::default();
+    rdf.add_triple(
+        &RDF::bnode_id2subject(id.as_str()),
+        &RDF::iri_s2iri(property),
+        &value,
+    )?;
+    Ok(RDF::bnode_id2term(id.as_str()))
+}
+
+/// Writes the paths as an RDF list
+fn write_path_list<RDF>(paths: &[SHACLPath], rdf: &mut RDF) -> Result<RDF::Term, RDF::Err>
+where
+    RDF: SRDFBuilder,
+{
+    let mut list = RDF::iri_s2term(&RDF_NIL);
+    for path in paths.iter().rev() {
+        let first = write_path(path, rdf)?;
+        let id = BlankNode::default();
+        let subject = RDF::bnode_id2subject(id.as_str());
+        rdf.add_triple(&subject, &RDF::iri_s2iri(&RDF_FIRST), &first)?;
+        rdf.add_triple(&subject, &RDF::iri_s2iri(&RDF_REST), &list)?;
+        list = RDF::bnode_id2term(id.as_str());
+    }
+    Ok(list)
+}
+
 impl Display for PropertyShape {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "{{")?;
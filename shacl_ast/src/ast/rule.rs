@@ -0,0 +1,125 @@
+use std::fmt::Display;
+
+use iri_s::IriS;
+use srdf::RDFNode;
+
+use crate::node_expr::NodeExpr;
+
+/// [Rules](https://www.w3.org/TR/shacl-af/#rules) of SHACL Advanced Features, which are declared
+/// with `sh:rule` in shapes and infer triples for the focus nodes of the shapes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    id: RDFNode,
+    kind: RuleKind,
+    order: Option<f64>,
+    conditions: Vec<RDFNode>,
+    deactivated: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RuleKind {
+    /// `sh:TripleRule`, which infers the triples obtained from its node expressions
+    Triple {
+        subject: NodeExpr,
+        predicate: IriS,
+        object: NodeExpr,
+    },
+
+    /// `sh:SPARQLRule`, which infers the triples of a SPARQL `CONSTRUCT` query
+    /// with the focus node bound to `$this`
+    Sparql {
+        construct: String,
+        prefixes: Vec<(String, IriS)>,
+    },
+}
+
+impl Rule {
+    pub fn new(id: RDFNode, kind: RuleKind) -> Self {
+        Rule {
+            id,
+            kind,
+            order: None,
+            conditions: Vec::new(),
+            deactivated: false,
+        }
+    }
+
+    pub fn with_order(mut self, order: Option<f64>) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// Shapes that the focus node must conform to for the rule to be applied
+    pub fn with_conditions(mut self, conditions: Vec<RDFNode>) -> Self {
+        self.conditions = conditions;
+        self
+    }
+
+    pub fn with_deactivated(mut self, deactivated: bool) -> Self {
+        self.deactivated = deactivated;
+        self
+    }
+
+    pub fn id(&self) -> &RDFNode {
+        &self.id
+    }
+
+    pub fn kind(&self) -> &RuleKind {
+        &self.kind
+    }
+
+    /// Value of `sh:order`, rules without it are executed as if it was 0
+    pub fn order(&self) -> f64 {
+        self.order.unwrap_or(0.0)
+    }
+
+    pub fn conditions(&self) -> &Vec<RDFNode> {
+        &self.conditions
+    }
+
+    pub fn is_deactivated(&self) -> bool {
+        self.deactivated
+    }
+}
+
+impl RuleKind {
+    /// The `CONSTRUCT` query of a SPARQL rule preceded by its prefix declarations
+    pub fn query(&self) -> Option<String> {
+        match self {
+            RuleKind::Triple { .. } => None,
+            RuleKind::Sparql {
+                construct,
+                prefixes,
+            } => {
+                let declarations: String = prefixes
+                    .iter()
+                    .map(|(prefix, namespace)| format!("PREFIX {prefix}: <{namespace}>\n"))
+                    .collect();
+                Some(format!("{declarations}{construct}"))
+            }
+        }
+    }
+}
+
+impl Display for Rule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            RuleKind::Triple {
+                subject,
+                predicate,
+                object,
+            } => write!(f, "TripleRule({subject}, <{predicate}>, {object})")?,
+            RuleKind::Sparql { construct, .. } => write!(f, "SPARQLRule({})", construct.trim())?,
+        }
+        if let Some(order) = self.order {
+            write!(f, " order: {order}")?
+        }
+        for condition in self.conditions.iter() {
+            write!(f, " condition: {condition}")?
+        }
+        if self.deactivated {
+            write!(f, " deactivated")?
+        }
+        Ok(())
+    }
+}
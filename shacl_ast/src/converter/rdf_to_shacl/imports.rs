@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use iri_s::IriS;
+use oxiri::Iri;
+use srdf::{RDFFormat, ReaderMode, SRDFBasic, SRDFGraph, OWL_IMPORTS, SRDF};
+
+use super::imports_error::ImportError;
+use crate::SH_SHAPES_GRAPH;
+
+/// Obtains the graphs identified by IRIs in `owl:imports` and `sh:shapesGraph` declarations
+pub trait ImportResolver {
+    /// Merges the graph identified by `iri` into `graph`. Returns `false` if the graph is unknown to the resolver
+    fn resolve(&self, iri: &IriS, graph: &mut SRDFGraph) -> Result<bool, ImportError>;
+}
+
+/// Resolver that reads the graphs from local files
+///
+/// The file of a graph is obtained from an explicit mapping, from the path of a `file:` IRI
+/// or looking in some folders for a file named like the last segment of the IRI,
+/// with or without one of the extensions of the RDF formats.
+/// The format of the files is obtained from their extension, or Turtle by default.
+#[derive(Debug, Clone, Default)]
+pub struct LocalResolver {
+    mappings: HashMap<IriS, PathBuf>,
+    folders: Vec<PathBuf>,
+}
+
+const EXTENSIONS: [&str; 6] = ["ttl", "nt", "rdf", "trig", "n3", "nq"];
+
+impl LocalResolver {
+    pub fn new() -> LocalResolver {
+        LocalResolver::default()
+    }
+
+    /// Reads the graph identified by `iri` from the file `path`
+    pub fn with_mapping<P: AsRef<Path>>(mut self, iri: IriS, path: P) -> Self {
+        self.mappings.insert(iri, path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Adds a folder in which the graphs are looked for
+    pub fn with_folder<P: AsRef<Path>>(mut self, folder: P) -> Self {
+        self.folders.push(folder.as_ref().to_path_buf());
+        self
+    }
+
+    /// Local file of the graph identified by `iri`
+    pub fn path(&self, iri: &IriS) -> Option<PathBuf> {
+        if let Some(path) = self.mappings.get(iri) {
+            return Some(path.clone());
+        }
+        if let Some(path) = iri.as_str().strip_prefix("file://") {
+            let path = PathBuf::from(path);
+            if path.is_file() {
+                return Some(path);
+            }
+        }
+        let name = iri
+            .as_str()
+            .trim_end_matches(['/', '#'])
+            .rsplit(['/', '#', ':'])
+            .next()
+            .filter(|name| !name.is_empty())?;
+        let names = std::iter::once(name.to_string())
+            .chain(EXTENSIONS.iter().map(|ext| format!("{name}.{ext}")));
+        names
+            .flat_map(|name| self.folders.iter().map(move |folder| folder.join(&name)))
+            .find(|path| path.is_file())
+    }
+}
+
+impl ImportResolver for LocalResolver {
+    fn resolve(&self, iri: &IriS, graph: &mut SRDFGraph) -> Result<bool, ImportError> {
+        let Some(path) = self.path(iri) else {
+            return Ok(false);
+        };
+        let format = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| RDFFormat::from_str(ext).ok())
+            .unwrap_or(RDFFormat::Turtle);
+        let base = Iri::parse(iri.as_str().to_string()).ok();
+        graph
+            .merge_from_path(&path, &format, base, &ReaderMode::default())
+            .map_err(|err| ImportError::ReadingGraph {
+                iri: iri.clone(),
+                err: Box::new(err),
+            })?;
+        Ok(true)
+    }
+}
+
+/// Merges into `graph` the graphs that it imports with `owl:imports`, and the graphs imported by them.
+///
+/// Returns the IRIs of the imported graphs. Graphs that can't be resolved are an error.
+pub fn resolve_imports(
+    graph: &mut SRDFGraph,
+    resolver: &dyn ImportResolver,
+) -> Result<Vec<IriS>, ImportError> {
+    let mut imported = Vec::new();
+    let mut visited = HashSet::new();
+    loop {
+        let pending: Vec<IriS> = iri_values(graph, &OWL_IMPORTS)?
+            .into_iter()
+            .filter(|iri| !visited.contains(iri))
+            .collect();
+        if pending.is_empty() {
+            return Ok(imported);
+        }
+        for iri in pending {
+            visited.insert(iri.clone());
+            if !resolver.resolve(&iri, graph)? {
+                return Err(ImportError::NotFound { iri });
+            }
+            imported.push(iri)
+        }
+    }
+}
+
+/// Loads the shapes graphs declared with `sh:shapesGraph` in a data graph, with their imports.
+///
+/// Returns `None` if the data graph doesn't declare any shapes graph
+pub fn declared_shapes_graph(
+    data: &SRDFGraph,
+    resolver: &dyn ImportResolver,
+) -> Result<Option<SRDFGraph>, ImportError> {
+    let iris = iri_values(data, &SH_SHAPES_GRAPH)?;
+    if iris.is_empty() {
+        return Ok(None);
+    }
+    let mut shapes = SRDFGraph::new();
+    for iri in iris {
+        if !resolver.resolve(&iri, &mut shapes)? {
+            return Err(ImportError::NotFound { iri });
+        }
+    }
+    resolve_imports(&mut shapes, resolver)?;
+    Ok(Some(shapes))
+}
+
+// IRIs that are values of `predicate`, sorted so the graphs are always loaded in the same order
+fn iri_values(graph: &SRDFGraph, predicate: &IriS) -> Result<Vec<IriS>, ImportError> {
+    let triples = graph
+        .triples_with_predicate(&SRDFGraph::iri_s2iri(predicate))
+        .map_err(|err| ImportError::Graph { err: Box::new(err) })?;
+    let mut iris: Vec<IriS> = triples
+        .iter()
+        .filter_map(|triple| SRDFGraph::term_as_iri(&triple.obj()))
+        .map(|iri| SRDFGraph::iri2iri_s(&iri))
+        .collect();
+    iris.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    iris.dedup();
+    Ok(iris)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn ex(name: &str) -> IriS {
+        IriS::new_unchecked(format!("http://example.org/{name}").as_str())
+    }
+
+    fn graph(str: &str) -> SRDFGraph {
+        SRDFGraph::from_str(str, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap()
+    }
+
+    /// Folder with Turtle files named like the local names of the graphs
+    fn folder(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (name, content) in files {
+            fs::write(dir.path().join(format!("{name}.ttl")), content).unwrap();
+        }
+        dir
+    }
+
+    /// Resolver that fails for every graph
+    struct FailingResolver;
+
+    impl ImportResolver for FailingResolver {
+        fn resolve(&self, iri: &IriS, _graph: &mut SRDFGraph) -> Result<bool, ImportError> {
+            Err(ImportError::ReadingGraph {
+                iri: iri.clone(),
+                err: Box::new(srdf::SRDFGraphError::IOError {
+                    err: std::io::Error::other("connection refused"),
+                }),
+            })
+        }
+    }
+
+    #[test]
+    fn import_cycles_are_loaded_once() {
+        let dir = folder(&[
+            (
+                "a",
+                "prefix : <http://example.org/>
+                 prefix owl: <http://www.w3.org/2002/07/owl#>
+                 :a owl:imports :b ; :p 1 .",
+            ),
+            (
+                "b",
+                "prefix : <http://example.org/>
+                 prefix owl: <http://www.w3.org/2002/07/owl#>
+                 :b owl:imports :a ; :p 2 .",
+            ),
+        ]);
+        let resolver = LocalResolver::new().with_folder(dir.path());
+        let mut shapes = graph(
+            "prefix : <http://example.org/>
+             prefix owl: <http://www.w3.org/2002/07/owl#>
+             :shapes owl:imports :a .",
+        );
+        let imported = resolve_imports(&mut shapes, &resolver).unwrap();
+        assert_eq!(imported, vec![ex("a"), ex("b")]);
+        assert_eq!(shapes.len(), 5);
+    }
+
+    #[test]
+    fn unresolved_imports_are_errors() {
+        let dir = folder(&[]);
+        let resolver = LocalResolver::new().with_folder(dir.path());
+        let mut shapes = graph(
+            "prefix : <http://example.org/>
+             prefix owl: <http://www.w3.org/2002/07/owl#>
+             :shapes owl:imports :missing .",
+        );
+        let err = resolve_imports(&mut shapes, &resolver).unwrap_err();
+        assert!(matches!(err, ImportError::NotFound { iri } if iri == ex("missing")));
+    }
+
+    #[test]
+    fn errors_reading_imported_graphs() {
+        let dir = folder(&[("broken", "prefix : <http://example.org/> :a :b")]);
+        let resolver =
+            LocalResolver::new().with_mapping(ex("broken"), dir.path().join("broken.ttl"));
+        let mut shapes = graph(
+            "prefix : <http://example.org/>
+             prefix owl: <http://www.w3.org/2002/07/owl#>
+             :shapes owl:imports :broken .",
+        );
+        let err = resolve_imports(&mut shapes, &resolver).unwrap_err();
+        assert!(matches!(err, ImportError::ReadingGraph { iri, .. } if iri == ex("broken")));
+    }
+
+    #[test]
+    fn declared_shapes_graphs() {
+        let dir = folder(&[
+            (
+                "shapes",
+                "prefix : <http://example.org/>
+                 prefix owl: <http://www.w3.org/2002/07/owl#>
+                 :shapes owl:imports :common .",
+            ),
+            ("common", "prefix : <http://example.org/> :common :p 1 ."),
+        ]);
+        let resolver = LocalResolver::new().with_folder(dir.path());
+        let data = graph(
+            "prefix : <http://example.org/>
+             prefix sh: <http://www.w3.org/ns/shacl#>
+             :data sh:shapesGraph :shapes .",
+        );
+        let shapes = declared_shapes_graph(&data, &resolver).unwrap().unwrap();
+        assert_eq!(shapes.len(), 2);
+        assert!(declared_shapes_graph(&graph(""), &resolver)
+            .unwrap()
+            .is_none());
+        let err = declared_shapes_graph(&data, &FailingResolver).unwrap_err();
+        assert!(matches!(err, ImportError::ReadingGraph { iri, .. } if iri == ex("shapes")));
+    }
+}
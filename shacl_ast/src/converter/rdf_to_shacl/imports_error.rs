@@ -0,0 +1,15 @@
+use iri_s::IriS;
+use srdf::SRDFGraphError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("Graph {iri} not found by the import resolver")]
+    NotFound { iri: IriS },
+
+    #[error("Error reading graph {iri}: {err}")]
+    ReadingGraph { iri: IriS, err: Box<SRDFGraphError> },
+
+    #[error("Error obtaining the imported graphs: {err}")]
+    Graph { err: Box<SRDFGraphError> },
+}
@@ -1,4 +1,8 @@
+mod imports;
+mod imports_error;
 mod shacl_parser;
 mod shacl_parser_error;
 
+pub use imports::*;
+pub use imports_error::*;
 pub use shacl_parser::*;
@@ -1,15 +1,28 @@
+use iri_s::IriS;
+use lazy_static::lazy_static;
 use prefixmap::{IriRef, PrefixMap};
 use srdf::{
-    combine_parsers, combine_vec, has_type, not, ok, optional, parse_nodes, property_bool,
-    property_value, property_values, property_values_int, property_values_iri,
+    apply_rdf, combine_parsers, combine_vec, has_type, not, ok, optional, parse_nodes,
+    property_bool, property_value, property_values, property_values_int, property_values_iri,
     property_values_non_empty, rdf_list, term, FocusRDF, Object, PResult, RDFNode, RDFNodeParse,
-    RDFParseError, RDFParser, SHACLPath, SRDFBasic, Triple, RDF_TYPE,
+    RDFParseError, RDFParser, SHACLPath, SRDFBasic, Triple, RDFS_SUBCLASS_OF, RDF_FIRST, RDF_TYPE,
 };
 use std::collections::{HashMap, HashSet};
+use std::marker::PhantomData;
+use std::str::FromStr;
 
 use crate::{
-    component::Component, node_kind::NodeKind, node_shape::NodeShape,
-    property_shape::PropertyShape, schema::Schema, shape::Shape, target::Target, value::Value, *,
+    component::Component,
+    node_expr::NodeExpr,
+    node_kind::NodeKind,
+    node_shape::NodeShape,
+    property_shape::PropertyShape,
+    rule::{Rule, RuleKind},
+    schema::Schema,
+    shape::Shape,
+    target::Target,
+    value::Value,
+    *,
 };
 use std::fmt::Debug;
 
@@ -17,10 +30,64 @@ use super::shacl_parser_error::ShaclParserError;
 
 type Result<A> = std::result::Result<A, ShaclParserError>;
 
+lazy_static! {
+    static ref TARGETS: Vec<IriS> = vec![
+        SH_TARGET_CLASS.clone(),
+        SH_TARGET_NODE.clone(),
+        SH_TARGET_OBJECTS_OF.clone(),
+        SH_TARGET_SUBJECTS_OF.clone(),
+    ];
+
+    /// Parameters of the SHACL Core constraint components
+    static ref PARAMETERS: Vec<IriS> = vec![
+        SH_CLASS.clone(),
+        SH_DATATYPE.clone(),
+        SH_NODE_KIND.clone(),
+        SH_MIN_COUNT.clone(),
+        SH_MAX_COUNT.clone(),
+        SH_MIN_EXCLUSIVE.clone(),
+        SH_MIN_INCLUSIVE.clone(),
+        SH_MAX_EXCLUSIVE.clone(),
+        SH_MAX_INCLUSIVE.clone(),
+        SH_MIN_LENGTH.clone(),
+        SH_MAX_LENGTH.clone(),
+        SH_PATTERN.clone(),
+        SH_LANGUAGE_IN.clone(),
+        SH_UNIQUE_LANG.clone(),
+        SH_EQUALS.clone(),
+        SH_DISJOINT.clone(),
+        SH_LESS_THAN.clone(),
+        SH_LESS_THAN_OR_EQUALS.clone(),
+        SH_NOT.clone(),
+        SH_AND.clone(),
+        SH_OR.clone(),
+        SH_XONE.clone(),
+        SH_NODE.clone(),
+        SH_PROPERTY.clone(),
+        SH_QUALIFIED_VALUE_SHAPE.clone(),
+        SH_CLOSED.clone(),
+        SH_HAS_VALUE.clone(),
+        SH_IN.clone(),
+    ];
+}
+
 struct State {
     pending: Vec<RDFNode>,
 }
 
+/// Constraint component declared in the shapes graph with `sh:parameter`
+#[derive(Debug, Clone)]
+struct DeclaredComponent {
+    id: IriS,
+    parameters: Vec<Parameter>,
+}
+
+#[derive(Debug, Clone)]
+struct Parameter {
+    path: IriS,
+    optional: bool,
+}
+
 impl State {
     fn from(pending: Vec<RDFNode>) -> Self {
         State { pending }
@@ -53,12 +120,13 @@ where
     pub fn parse(&mut self) -> Result<Schema> {
         let prefixmap: PrefixMap = self.rdf_parser.prefixmap().unwrap_or_default();
 
-        let mut state = State::from(self.shapes_candidates()?);
+        let declared = self.declared_components()?;
+        let mut state = State::from(self.shapes_candidates(&declared)?);
         while let Some(node) = state.pop_pending() {
             if let std::collections::hash_map::Entry::Vacant(e) = self.shapes.entry(node.clone()) {
                 let term = RDF::object_as_term(&node);
                 self.rdf_parser.rdf.set_focus(&term);
-                let shape = Self::shape(&mut state)
+                let shape = Self::shape(&mut state, &declared)
                     .parse_impl(&mut self.rdf_parser.rdf)
                     .map_err(|e| ShaclParserError::RDFParseError { err: e })?;
                 e.insert(shape);
@@ -70,19 +138,40 @@ where
             .with_shapes(self.shapes.clone()))
     }
 
-    fn shapes_candidates(&mut self) -> Result<Vec<RDFNode>> {
-        // subjects with type `sh:NodeShape`
-        let node_shape_instances = self
-            .rdf_parser
-            .rdf
-            .subjects_with_predicate_object(&Self::rdf_type(), &Self::sh_node_shape())
-            .map_err(|e| ShaclParserError::Custom {
-                msg: format!("Error obtaining values with type sh:NodeShape: {e}"),
-            })?;
+    fn shapes_candidates(&mut self, declared: &[DeclaredComponent]) -> Result<Vec<RDFNode>> {
+        // SHACL instances of `sh:NodeShape`, `sh:PropertyShape` or `sh:Shape`
+        let mut shape_instances = HashSet::new();
+        for class in [&*SH_NODE_SHAPE, &*SH_PROPERTY_SHAPE, &*SH_SHAPE] {
+            shape_instances.extend(self.shacl_instances_of(class)?);
+        }
+
+        // subjects of targets, constraint parameters and rules
+        let mut subjects_with_parameters = HashSet::new();
+        let declared_parameters = declared
+            .iter()
+            .flat_map(|component| component.parameters.iter().map(|p| &p.path));
+        for pred in TARGETS
+            .iter()
+            .chain(PARAMETERS.iter())
+            .chain(declared_parameters)
+            .chain([&*SH_RULE])
+        {
+            subjects_with_parameters.extend(self.subjects_with_predicate(RDF::iri_s2iri(pred))?);
+        }
 
-        // subjects with property `sh:property`
+        // values of `sh:property`
         let subjects_property = self.objects_with_predicate(Self::sh_property())?;
 
+        // values of `sh:qualifiedValueShape`, and of `sh:condition` and `sh:filterShape` in rules
+        let mut shape_values = HashSet::new();
+        for pred in [
+            &*SH_QUALIFIED_VALUE_SHAPE,
+            &*SH_CONDITION,
+            &*SH_FILTER_SHAPE,
+        ] {
+            shape_values.extend(self.objects_with_predicate(RDF::iri_s2iri(pred))?);
+        }
+
         // elements of `sh:or` list
         let sh_or_values = self.get_sh_or_values()?;
 
@@ -92,30 +181,24 @@ where
         // elements of `sh:and` list
         let sh_and_values = self.get_sh_and_values()?;
 
-        // elements of `sh:not` list
+        // values of `sh:not`
         let sh_not_values = self.get_sh_not_values()?;
 
-        // elements of `sh:not` list
+        // values of `sh:node`
         let sh_node_values = self.get_sh_node_values()?;
 
-        // TODO: subjects with type `sh:PropertyShape`
-        let property_shapes_instances = HashSet::new();
-
-        // TODO: subjects with type `sh:Shape`
-        let shape_instances = HashSet::new();
-
-        // I would prefer a code like: node_shape_instances.union(subjects_property).union(...)
+        // I would prefer a code like: shape_instances.union(subjects_property).union(...)
         // But looking to the union API in HashSet, I think it can't be chained
         let mut candidates = HashSet::new();
-        candidates.extend(node_shape_instances);
+        candidates.extend(shape_instances);
+        candidates.extend(subjects_with_parameters);
         candidates.extend(subjects_property);
+        candidates.extend(shape_values);
         candidates.extend(sh_or_values);
         candidates.extend(sh_xone_values);
         candidates.extend(sh_and_values);
         candidates.extend(sh_not_values);
         candidates.extend(sh_node_values);
-        candidates.extend(property_shapes_instances);
-        candidates.extend(shape_instances);
 
         let result: Vec<_> = candidates
             .iter()
@@ -124,6 +207,81 @@ where
         Ok(result)
     }
 
+    /// Constraint components declared with `sh:parameter`, leaving out the components of SHACL
+    /// Core in case that the shapes graph includes the SHACL vocabulary
+    fn declared_components(&mut self) -> Result<Vec<DeclaredComponent>> {
+        let triples = self
+            .rdf_parser
+            .rdf
+            .triples_with_predicate(&RDF::iri_s2iri(&SH_PARAMETER))
+            .map_err(|e| ShaclParserError::Custom {
+                msg: format!("Error obtaining values with predicate sh:parameter: {e}"),
+            })?;
+        let mut components: Vec<DeclaredComponent> = Vec::new();
+        for triple in triples {
+            let Some(id) = RDF::subject_as_iri(&triple.subj()) else {
+                continue;
+            };
+            let id = RDF::iri2iri_s(&id);
+            if id.as_str().starts_with(SH_STR) {
+                continue;
+            }
+            let parameter = parse_parameter(&mut self.rdf_parser.rdf, &triple.obj())?;
+            match components.iter_mut().find(|component| component.id == id) {
+                Some(component) => component.parameters.push(parameter),
+                None => components.push(DeclaredComponent {
+                    id,
+                    parameters: vec![parameter],
+                }),
+            }
+        }
+        Ok(components)
+    }
+
+    /// Subjects whose type is `class` or one of its subclasses in the shapes graph
+    fn shacl_instances_of(&self, class: &IriS) -> Result<HashSet<RDF::Subject>> {
+        let subclass_of = RDF::iri_s2iri(&RDFS_SUBCLASS_OF);
+        let mut classes = vec![RDF::iri_s2term(class)];
+        let mut visited = HashSet::new();
+        let mut instances = HashSet::new();
+        while let Some(class) = classes.pop() {
+            if !visited.insert(class.clone()) {
+                continue;
+            }
+            instances.extend(self.subjects_with_predicate_object(&Self::rdf_type(), &class)?);
+            for subclass in self.subjects_with_predicate_object(&subclass_of, &class)? {
+                classes.push(RDF::subject_as_term(&subclass));
+            }
+        }
+        Ok(instances)
+    }
+
+    fn subjects_with_predicate_object(
+        &self,
+        pred: &RDF::IRI,
+        object: &RDF::Term,
+    ) -> Result<HashSet<RDF::Subject>> {
+        self.rdf_parser
+            .rdf
+            .subjects_with_predicate_object(pred, object)
+            .map_err(|e| ShaclParserError::Custom {
+                msg: format!(
+                    "Error obtaining subjects with predicate {pred} and value {object}: {e}"
+                ),
+            })
+    }
+
+    fn subjects_with_predicate(&self, pred: RDF::IRI) -> Result<HashSet<RDF::Subject>> {
+        let triples = self
+            .rdf_parser
+            .rdf
+            .triples_with_predicate(&pred)
+            .map_err(|e| ShaclParserError::Custom {
+                msg: format!("Error obtaining subjects with predicate {pred}: {e}"),
+            })?;
+        Ok(triples.into_iter().map(|t| t.subj()).collect())
+    }
+
     fn get_sh_or_values(&mut self) -> Result<HashSet<RDF::Subject>> {
         let mut rs = HashSet::new();
         for s in self.objects_with_predicate(Self::sh_or())? {
@@ -221,10 +379,6 @@ where
         RDF::iri_s2iri(&RDF_TYPE)
     }
 
-    fn sh_node_shape() -> RDF::Term {
-        RDF::iri_s2term(&SH_NODE_SHAPE)
-    }
-
     fn sh_property() -> RDF::IRI {
         RDF::iri_s2iri(&SH_PROPERTY)
     }
@@ -260,17 +414,22 @@ where
         RDF::subject_as_object(subject)
     }
 
-    fn shape<'a>(state: &'a mut State) -> impl RDFNodeParse<RDF, Output = Shape> + 'a
+    fn shape<'a>(
+        state: &'a mut State,
+        declared: &[DeclaredComponent],
+    ) -> impl RDFNodeParse<RDF, Output = Shape> + 'a
     where
         RDF: FocusRDF + 'a,
     {
-        node_shape()
+        node_shape(declared.to_vec())
             .then(move |ns| ok(&Shape::NodeShape(Box::new(ns))))
-            .or(property_shape(state).then(|ps| ok(&Shape::PropertyShape(ps))))
+            .or(property_shape(state, declared.to_vec()).then(|ps| ok(&Shape::PropertyShape(ps))))
     }
 }
 
-fn components<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+fn components<RDF>(
+    declared: &[DeclaredComponent],
+) -> impl RDFNodeParse<RDF, Output = Vec<Component>>
 where
     RDF: FocusRDF,
 {
@@ -288,12 +447,78 @@ where
         node(),
         min_length(),
         max_length(),
-        has_value()
+        has_value(),
+        custom_components(declared.to_vec())
     )
 }
 
+/// Components declared in the shapes graph whose mandatory parameters have values in the focus node
+///
+/// Each combination of the values of the parameters is a different component
+fn custom_components<RDF>(
+    declared: Vec<DeclaredComponent>,
+) -> impl RDFNodeParse<RDF, Output = Vec<Component>>
+where
+    RDF: FocusRDF,
+{
+    apply_rdf(move |rdf: &mut RDF| {
+        let mut components = Vec::new();
+        for component in declared.iter() {
+            let mut combinations = vec![Vec::new()];
+            for parameter in component.parameters.iter() {
+                let values = property_values(&parameter.path).parse_impl(rdf)?;
+                if values.is_empty() {
+                    if parameter.optional {
+                        continue;
+                    }
+                    combinations.clear();
+                    break;
+                }
+                combinations = combinations
+                    .into_iter()
+                    .flat_map(|combination: Vec<(IriS, RDFNode)>| {
+                        values.iter().map(move |value| {
+                            let mut combination = combination.clone();
+                            combination.push((parameter.path.clone(), RDF::term_as_object(value)));
+                            combination
+                        })
+                    })
+                    .collect();
+            }
+            components.extend(
+                combinations
+                    .into_iter()
+                    .filter(|parameters| !parameters.is_empty())
+                    .map(|parameters| Component::Custom {
+                        component: component.id.clone(),
+                        parameters,
+                    }),
+            )
+        }
+        Ok(components)
+    })
+}
+
+fn parse_parameter<RDF>(rdf: &mut RDF, term: &RDF::Term) -> PResult<Parameter>
+where
+    RDF: FocusRDF,
+{
+    let path = required_value(rdf, term, &SH_PATH)?;
+    let path = RDF::term_as_iri(&path).ok_or_else(|| RDFParseError::Custom {
+        msg: format!("Expected IRI as sh:path of parameter {term}, found {path}"),
+    })?;
+    let optional = optional(property_bool(&SH_OPTIONAL))
+        .parse_impl(rdf)?
+        .unwrap_or(false);
+    Ok(Parameter {
+        path: RDF::iri2iri_s(&path),
+        optional,
+    })
+}
+
 fn property_shape<'a, RDF>(
     _state: &'a mut State,
+    declared: Vec<DeclaredComponent>,
 ) -> impl RDFNodeParse<RDF, Output = PropertyShape> + 'a
 where
     RDF: FocusRDF + 'a,
@@ -317,19 +542,20 @@ where
             property_shapes()
                 .flat_map(move |prop_shapes| Ok(ps.clone().with_property_shapes(prop_shapes)))
         })
-        .then(move |ps| property_shape_components(ps))
+        .then(move |ps| property_shape_components(ps, &declared))
 }
 
 fn property_shape_components<RDF>(
     ps: PropertyShape,
+    declared: &[DeclaredComponent],
 ) -> impl RDFNodeParse<RDF, Output = PropertyShape>
 where
     RDF: FocusRDF,
 {
-    components().flat_map(move |cs| Ok(ps.clone().with_components(cs)))
+    components(declared).flat_map(move |cs| Ok(ps.clone().with_components(cs)))
 }
 
-fn node_shape<RDF>() -> impl RDFNodeParse<RDF, Output = NodeShape>
+fn node_shape<RDF>(declared: Vec<DeclaredComponent>) -> impl RDFNodeParse<RDF, Output = NodeShape>
 where
     RDF: FocusRDF,
 {
@@ -352,7 +578,10 @@ where
             .then(|ns| {
                 property_shapes().flat_map(move |ps| Ok(ns.clone().with_property_shapes(ps)))
             })
-            .then(|ns| components().flat_map(move |cs| Ok(ns.clone().with_components(cs)))),
+            .then(move |ns| {
+                components(&declared).flat_map(move |cs| Ok(ns.clone().with_components(cs)))
+            })
+            .then(|ns| rules().flat_map(move |rs| Ok(ns.clone().with_rules(rs)))),
     )
 }
 
@@ -441,24 +670,340 @@ where
     property_value(&SH_PATH).then(shacl_path)
 }
 
-/// Parses the current focus node as a SHACL path
+/// Parses a node as a SHACL path
 fn shacl_path<RDF>(term: RDF::Term) -> impl RDFNodeParse<RDF, Output = SHACLPath>
 where
     RDF: FocusRDF,
 {
-    let obj = RDF::term_as_object(&term);
-    match obj {
-        Object::Iri(iri) => ok(&SHACLPath::iri(iri)),
-        Object::BlankNode(_) => todo!(),
-        Object::Literal(_) => todo!(),
+    ShaclPathParser { term }
+}
+
+/// Parser of [SHACL paths](https://www.w3.org/TR/shacl/#property-paths)
+///
+/// Complex paths are nested blank nodes, so they are parsed recursively.
+/// The focus node is restored when the path has been parsed
+struct ShaclPathParser<RDF>
+where
+    RDF: FocusRDF,
+{
+    term: RDF::Term,
+}
+
+impl<RDF> RDFNodeParse<RDF> for ShaclPathParser<RDF>
+where
+    RDF: FocusRDF,
+{
+    type Output = SHACLPath;
+
+    fn parse_impl(&mut self, rdf: &mut RDF) -> PResult<SHACLPath> {
+        let focus = rdf.get_focus().clone();
+        let path = parse_shacl_path(rdf, &self.term);
+        if let Some(focus) = focus {
+            rdf.set_focus(&focus)
+        }
+        path
     }
 }
 
+/// Constructor of the paths that have a single inner path
+type UnaryPath = fn(Box<SHACLPath>) -> SHACLPath;
+
+fn parse_shacl_path<RDF>(rdf: &mut RDF, term: &RDF::Term) -> PResult<SHACLPath>
+where
+    RDF: FocusRDF,
+{
+    match RDF::term_as_object(term) {
+        Object::Iri(iri) => Ok(SHACLPath::iri(iri)),
+        Object::Literal(lit) => Err(RDFParseError::Custom {
+            msg: format!("Expected SHACL path, found literal {lit}"),
+        }),
+        triple @ Object::Triple { .. } => Err(RDFParseError::Custom {
+            msg: format!("Expected SHACL path, found triple term {triple}"),
+        }),
+        Object::BlankNode(_) => {
+            rdf.set_focus(term);
+            if !property_values(&RDF_FIRST).parse_impl(rdf)?.is_empty() {
+                let paths = parse_path_list(rdf, term)?;
+                return Ok(SHACLPath::Sequence { paths });
+            }
+            if let Some(list) = focus_value(rdf, &SH_ALTERNATIVE_PATH)? {
+                let paths = parse_path_list(rdf, &list)?;
+                return Ok(SHACLPath::Alternative { paths });
+            }
+            let unary: [(&IriS, UnaryPath); 4] = [
+                (&SH_INVERSE_PATH, |path| SHACLPath::Inverse { path }),
+                (&SH_ZERO_OR_MORE_PATH, |path| SHACLPath::ZeroOrMore { path }),
+                (&SH_ONE_OR_MORE_PATH, |path| SHACLPath::OneOrMore { path }),
+                (&SH_ZERO_OR_ONE_PATH, |path| SHACLPath::ZeroOrOne { path }),
+            ];
+            for (property, make_path) in unary {
+                rdf.set_focus(term);
+                if let Some(value) = focus_value(rdf, property)? {
+                    let path = parse_shacl_path(rdf, &value)?;
+                    return Ok(make_path(Box::new(path)));
+                }
+            }
+            Err(RDFParseError::Custom {
+                msg: format!("Expected SHACL path, found blank node {term} which is not a path"),
+            })
+        }
+    }
+}
+
+/// Value of `property` for the current focus node, if it has one
+fn focus_value<RDF>(rdf: &mut RDF, property: &IriS) -> PResult<Option<RDF::Term>>
+where
+    RDF: FocusRDF,
+{
+    Ok(property_values(property)
+        .parse_impl(rdf)?
+        .into_iter()
+        .next())
+}
+
+fn parse_path_list<RDF>(rdf: &mut RDF, list: &RDF::Term) -> PResult<Vec<SHACLPath>>
+where
+    RDF: FocusRDF,
+{
+    rdf.set_focus(list);
+    let nodes = rdf_list().parse_impl(rdf)?;
+    if nodes.len() < 2 {
+        return Err(RDFParseError::Custom {
+            msg: format!("Expected a list with at least two SHACL paths, found {list}"),
+        });
+    }
+    nodes
+        .iter()
+        .map(|node| parse_shacl_path(rdf, node))
+        .collect()
+}
+
+/// Parses the [SHACL-AF rules](https://www.w3.org/TR/shacl-af/#rules) declared with `sh:rule`
+fn rules<RDF>() -> RulesParser<RDF>
+where
+    RDF: FocusRDF,
+{
+    RulesParser {
+        _marker_rdf: PhantomData,
+    }
+}
+
+/// Parser of the rules of the focus node
+///
+/// Rules and node expressions are nested nodes, so they are parsed recursively.
+/// The focus node is restored when the rules have been parsed
+struct RulesParser<RDF> {
+    _marker_rdf: PhantomData<RDF>,
+}
+
+impl<RDF> RDFNodeParse<RDF> for RulesParser<RDF>
+where
+    RDF: FocusRDF,
+{
+    type Output = Vec<Rule>;
+
+    fn parse_impl(&mut self, rdf: &mut RDF) -> PResult<Vec<Rule>> {
+        let focus = rdf.get_focus().clone();
+        let values = property_values(&SH_RULE).parse_impl(rdf)?;
+        let rules = values.iter().map(|value| parse_rule(rdf, value)).collect();
+        if let Some(focus) = focus {
+            rdf.set_focus(&focus)
+        }
+        rules
+    }
+}
+
+fn parse_rule<RDF>(rdf: &mut RDF, term: &RDF::Term) -> PResult<Rule>
+where
+    RDF: FocusRDF,
+{
+    rdf.set_focus(term);
+    let order = match focus_value(rdf, &SH_ORDER)? {
+        Some(order) => Some(parse_order::<RDF>(&order)?),
+        None => None,
+    };
+    let conditions = property_values(&SH_CONDITION)
+        .parse_impl(rdf)?
+        .iter()
+        .map(RDF::term_as_object)
+        .collect();
+    let deactivated = optional(property_bool(&SH_DEACTIVATED))
+        .parse_impl(rdf)?
+        .unwrap_or(false);
+    let kind = match focus_value(rdf, &SH_CONSTRUCT)? {
+        Some(construct) => {
+            let construct =
+                RDF::term_as_string(&construct).ok_or_else(|| RDFParseError::Custom {
+                    msg: format!("Expected SPARQL CONSTRUCT query as string, found {construct}"),
+                })?;
+            let prefixes = parse_prefixes(rdf, term)?;
+            RuleKind::Sparql {
+                construct,
+                prefixes,
+            }
+        }
+        None => {
+            let subject = required_value(rdf, term, &SH_SUBJECT)?;
+            let predicate = required_value(rdf, term, &SH_PREDICATE)?;
+            let object = required_value(rdf, term, &SH_OBJECT)?;
+            let predicate = match RDF::term_as_iri(&predicate) {
+                Some(iri) => RDF::iri2iri_s(&iri),
+                None => {
+                    return Err(RDFParseError::Custom {
+                        msg: format!(
+                            "Expected IRI as sh:predicate of rule {term}, found {predicate}"
+                        ),
+                    })
+                }
+            };
+            RuleKind::Triple {
+                subject: parse_node_expr(rdf, &subject)?,
+                predicate,
+                object: parse_node_expr(rdf, &object)?,
+            }
+        }
+    };
+    Ok(Rule::new(RDF::term_as_object(term), kind)
+        .with_order(order)
+        .with_conditions(conditions)
+        .with_deactivated(deactivated))
+}
+
+fn parse_order<RDF>(term: &RDF::Term) -> PResult<f64>
+where
+    RDF: FocusRDF,
+{
+    RDF::term_as_literal(term)
+        .and_then(|literal| RDF::lexical_form(&literal).parse().ok())
+        .ok_or_else(|| RDFParseError::Custom {
+            msg: format!("Expected number as sh:order, found {term}"),
+        })
+}
+
+/// Prefix declarations of a SPARQL rule, which are the `sh:declare` values of its `sh:prefixes`
+fn parse_prefixes<RDF>(rdf: &mut RDF, rule: &RDF::Term) -> PResult<Vec<(String, IriS)>>
+where
+    RDF: FocusRDF,
+{
+    rdf.set_focus(rule);
+    let mut prefixes = Vec::new();
+    for declarations in property_values(&SH_PREFIXES).parse_impl(rdf)? {
+        rdf.set_focus(&declarations);
+        for declaration in property_values(&SH_DECLARE).parse_impl(rdf)? {
+            let prefix = required_value(rdf, &declaration, &SH_PREFIX)?;
+            let namespace = required_value(rdf, &declaration, &SH_NAMESPACE)?;
+            let prefix = RDF::term_as_string(&prefix).ok_or_else(|| RDFParseError::Custom {
+                msg: format!("Expected string as sh:prefix, found {prefix}"),
+            })?;
+            let namespace = match RDF::term_as_iri(&namespace) {
+                Some(iri) => RDF::iri2iri_s(&iri),
+                None => RDF::term_as_string(&namespace)
+                    .and_then(|str| IriS::from_str(&str).ok())
+                    .ok_or_else(|| RDFParseError::Custom {
+                        msg: format!("Expected IRI as sh:namespace, found {namespace}"),
+                    })?,
+            };
+            prefixes.push((prefix, namespace));
+        }
+    }
+    Ok(prefixes)
+}
+
+/// Parses a node as a [node expression](https://www.w3.org/TR/shacl-af/#node-expressions)
+fn parse_node_expr<RDF>(rdf: &mut RDF, term: &RDF::Term) -> PResult<NodeExpr>
+where
+    RDF: FocusRDF,
+{
+    let node = RDF::term_as_object(term);
+    match node {
+        Object::Iri(iri) if iri == *SH_THIS => Ok(NodeExpr::FocusNode),
+        Object::Iri(_) | Object::Literal(_) | Object::Triple { .. } => Ok(NodeExpr::Constant(node)),
+        Object::BlankNode(_) => {
+            rdf.set_focus(term);
+            let nodes = focus_value(rdf, &SH_NODES)?;
+            if let Some(path) = focus_value(rdf, &SH_PATH)? {
+                let path = parse_shacl_path(rdf, &path)?;
+                let nodes = match nodes {
+                    Some(nodes) => Some(Box::new(parse_node_expr(rdf, &nodes)?)),
+                    None => None,
+                };
+                return Ok(NodeExpr::Path { path, nodes });
+            }
+            if let Some(shape) = focus_value(rdf, &SH_FILTER_SHAPE)? {
+                let nodes = nodes.ok_or_else(|| RDFParseError::Custom {
+                    msg: format!("Expected sh:nodes in filter shape expression {term}"),
+                })?;
+                return Ok(NodeExpr::Filter {
+                    shape: RDF::term_as_object(&shape),
+                    nodes: Box::new(parse_node_expr(rdf, &nodes)?),
+                });
+            }
+            if let Some(list) = focus_value(rdf, &SH_INTERSECTION)? {
+                let exprs = parse_node_expr_list(rdf, &list)?;
+                return Ok(NodeExpr::Intersection { exprs });
+            }
+            if let Some(list) = focus_value(rdf, &SH_UNION)? {
+                let exprs = parse_node_expr_list(rdf, &list)?;
+                return Ok(NodeExpr::Union { exprs });
+            }
+            // function expressions are blank nodes with a single triple whose predicate is the function
+            let subject = RDF::term_as_subject(term).ok_or_else(|| RDFParseError::Custom {
+                msg: format!("Expected node expression, found {term}"),
+            })?;
+            let predicates: Vec<_> = rdf
+                .predicates_for_subject(&subject)
+                .map_err(|e| RDFParseError::SRDFError { err: e.to_string() })?
+                .into_iter()
+                .collect();
+            match predicates.as_slice() {
+                [function] => {
+                    let function = RDF::iri2iri_s(function);
+                    rdf.set_focus(term);
+                    let args = match focus_value(rdf, &function)? {
+                        Some(list) => parse_node_expr_list(rdf, &list)?,
+                        None => Vec::new(),
+                    };
+                    Ok(NodeExpr::Function { function, args })
+                }
+                _ => Err(RDFParseError::Custom {
+                    msg: format!("Expected node expression, found blank node {term}"),
+                }),
+            }
+        }
+    }
+}
+
+fn parse_node_expr_list<RDF>(rdf: &mut RDF, list: &RDF::Term) -> PResult<Vec<NodeExpr>>
+where
+    RDF: FocusRDF,
+{
+    rdf.set_focus(list);
+    let nodes = rdf_list().parse_impl(rdf)?;
+    nodes
+        .iter()
+        .map(|node| parse_node_expr(rdf, node))
+        .collect()
+}
+
+/// Value of `property` for `node`, which must have one
+fn required_value<RDF>(rdf: &mut RDF, node: &RDF::Term, property: &IriS) -> PResult<RDF::Term>
+where
+    RDF: FocusRDF,
+{
+    rdf.set_focus(node);
+    focus_value(rdf, property)?.ok_or_else(|| RDFParseError::Custom {
+        msg: format!("Expected value of {property} for {node}"),
+    })
+}
+
 fn targets<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Target>>
 where
     RDF: FocusRDF,
 {
-    combine_vec(targets_class(), targets_node())
+    combine_vec(
+        combine_vec(targets_class(), targets_node()),
+        combine_vec(targets_subjects_of(), targets_objects_of()),
+    )
 }
 
 fn closed<RDF>() -> impl RDFNodeParse<RDF, Output = bool>
@@ -590,6 +1135,9 @@ where
             bnode: bn.to_string(),
         }),
         Object::Literal(lit) => Ok(Value::Literal(lit)),
+        triple @ Object::Triple { .. } => Err(RDFParseError::Custom {
+            msg: format!("Triple term {triple} can not be used as a value"),
+        }),
     }
 }
 
@@ -665,6 +1213,7 @@ where
                 SH_BLANK_NODE_OR_IRI_STR => Ok(NodeKind::BlankNodeOrIri),
                 SH_BLANK_NODE_OR_LITERAL_STR => Ok(NodeKind::BlankNodeOrLiteral),
                 SH_IRI_OR_LITERAL_STR => Ok(NodeKind::IRIOrLiteral),
+                SH_TRIPLE_TERM_STR => Ok(NodeKind::TripleTerm),
                 _ => Err(ShaclParserError::UnknownNodeKind {
                     term: format!("{term}"),
                 }),
@@ -707,3 +1256,25 @@ where
         Ok(result)
     })
 }
+
+fn targets_subjects_of<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Target>>
+where
+    RDF: FocusRDF,
+{
+    property_values_iri(&SH_TARGET_SUBJECTS_OF).map(|ps| {
+        ps.iter()
+            .map(|p| Target::TargetSubjectsOf(IriRef::iri(p.clone())))
+            .collect()
+    })
+}
+
+fn targets_objects_of<RDF>() -> impl RDFNodeParse<RDF, Output = Vec<Target>>
+where
+    RDF: FocusRDF,
+{
+    property_values_iri(&SH_TARGET_OBJECTS_OF).map(|ps| {
+        ps.iter()
+            .map(|p| Target::TargetObjectsOf(IriRef::iri(p.clone())))
+            .collect()
+    })
+}
@@ -14,10 +14,12 @@ pub const SH_NODE_SHAPE_STR: &str = concatcp!(SH_STR, "NodeShape");
 pub const SH_PROPERTY_SHAPE_STR: &str = concatcp!(SH_STR, "PropertyShape");
 pub const SH_SHAPE_STR: &str = concatcp!(SH_STR, "Shape");
 pub const SH_SCHEMA_STR: &str = concatcp!(SH_STR, "Schema");
+pub const SH_TRIPLE_TERM_STR: &str = concatcp!(SH_STR, "TripleTerm");
 pub const SH_VALIDATION_REPORT_STR: &str = concatcp!(SH_STR, "ValidationReport");
 pub const SH_VALIDATION_RESULT_STR: &str = concatcp!(SH_STR, "ValidationResult");
 pub const SH_VIOLATION_STR: &str = concatcp!(SH_STR, "Violation");
 pub const SH_WARNING_STR: &str = concatcp!(SH_STR, "Warning");
+pub const SH_ALTERNATIVE_PATH_STR: &str = concatcp!(SH_STR, "alternativePath");
 pub const SH_AND_STR: &str = concatcp!(SH_STR, "and");
 pub const SH_CLASS_STR: &str = concatcp!(SH_STR, "class");
 pub const SH_CLOSED_STR: &str = concatcp!(SH_STR, "closed");
@@ -34,6 +36,7 @@ pub const SH_GROUP_STR: &str = concatcp!(SH_STR, "group");
 pub const SH_HAS_VALUE_STR: &str = concatcp!(SH_STR, "hasValue");
 pub const SH_IGNORED_PROPERTIES_STR: &str = concatcp!(SH_STR, "ignoredProperties");
 pub const SH_IN_STR: &str = concatcp!(SH_STR, "in");
+pub const SH_INVERSE_PATH_STR: &str = concatcp!(SH_STR, "inversePath");
 pub const SH_LANGUAGE_IN_STR: &str = concatcp!(SH_STR, "languageIn");
 pub const SH_LESS_THAN_STR: &str = concatcp!(SH_STR, "lessThan");
 pub const SH_LESS_THAN_OR_EQUALS_STR: &str = concatcp!(SH_STR, "lessThanOrEquals");
@@ -50,6 +53,7 @@ pub const SH_NAME_STR: &str = concatcp!(SH_STR, "name");
 pub const SH_NODE_KIND_STR: &str = concatcp!(SH_STR, "nodeKind");
 pub const SH_NODE_STR: &str = concatcp!(SH_STR, "node");
 pub const SH_NOT_STR: &str = concatcp!(SH_STR, "not");
+pub const SH_ONE_OR_MORE_PATH_STR: &str = concatcp!(SH_STR, "oneOrMorePath");
 pub const SH_OR_STR: &str = concatcp!(SH_STR, "or");
 pub const SH_ORDER_STR: &str = concatcp!(SH_STR, "order");
 pub const SH_PATH_STR: &str = concatcp!(SH_STR, "path");
@@ -76,7 +80,30 @@ pub const SH_TARGET_OBJECTS_OF_STR: &str = concatcp!(SH_STR, "targetObjectsOf");
 pub const SH_TEXT_STR: &str = concatcp!(SH_STR, "text");
 pub const SH_UNIQUE_LANG_STR: &str = concatcp!(SH_STR, "uniqueLang");
 pub const SH_XONE_STR: &str = concatcp!(SH_STR, "xone");
+pub const SH_ZERO_OR_MORE_PATH_STR: &str = concatcp!(SH_STR, "zeroOrMorePath");
+pub const SH_ZERO_OR_ONE_PATH_STR: &str = concatcp!(SH_STR, "zeroOrOnePath");
 pub const SH_SOURCE_CONSTRAINT_STR: &str = concatcp!(SH_STR, "sourceConstraint");
+pub const SH_PARAMETER_STR: &str = concatcp!(SH_STR, "parameter");
+pub const SH_OPTIONAL_STR: &str = concatcp!(SH_STR, "optional");
+
+// Vocabulary of the SHACL Advanced Features: https://www.w3.org/TR/shacl-af/
+pub const SH_TRIPLE_RULE_STR: &str = concatcp!(SH_STR, "TripleRule");
+pub const SH_SPARQL_RULE_STR: &str = concatcp!(SH_STR, "SPARQLRule");
+pub const SH_RULE_STR: &str = concatcp!(SH_STR, "rule");
+pub const SH_SUBJECT_STR: &str = concatcp!(SH_STR, "subject");
+pub const SH_PREDICATE_STR: &str = concatcp!(SH_STR, "predicate");
+pub const SH_OBJECT_STR: &str = concatcp!(SH_STR, "object");
+pub const SH_CONDITION_STR: &str = concatcp!(SH_STR, "condition");
+pub const SH_CONSTRUCT_STR: &str = concatcp!(SH_STR, "construct");
+pub const SH_PREFIXES_STR: &str = concatcp!(SH_STR, "prefixes");
+pub const SH_DECLARE_STR: &str = concatcp!(SH_STR, "declare");
+pub const SH_PREFIX_STR: &str = concatcp!(SH_STR, "prefix");
+pub const SH_NAMESPACE_STR: &str = concatcp!(SH_STR, "namespace");
+pub const SH_THIS_STR: &str = concatcp!(SH_STR, "this");
+pub const SH_FILTER_SHAPE_STR: &str = concatcp!(SH_STR, "filterShape");
+pub const SH_NODES_STR: &str = concatcp!(SH_STR, "nodes");
+pub const SH_INTERSECTION_STR: &str = concatcp!(SH_STR, "intersection");
+pub const SH_UNION_STR: &str = concatcp!(SH_STR, "union");
 
 lazy_static! {
     pub static ref SH: IriS = IriS::new_unchecked(SH_STR);
@@ -92,10 +119,12 @@ lazy_static! {
     pub static ref SH_PROPERTY_SHAPE: IriS = IriS::new_unchecked(SH_PROPERTY_SHAPE_STR);
     pub static ref SH_SHAPE: IriS = IriS::new_unchecked(SH_SHAPE_STR);
     pub static ref SH_SCHEMA: IriS = IriS::new_unchecked(SH_SCHEMA_STR);
+    pub static ref SH_TRIPLE_TERM: IriS = IriS::new_unchecked(SH_TRIPLE_TERM_STR);
     pub static ref SH_VALIDATION_REPORT: IriS = IriS::new_unchecked(SH_VALIDATION_REPORT_STR);
     pub static ref SH_VALIDATION_RESULT: IriS = IriS::new_unchecked(SH_VALIDATION_RESULT_STR);
     pub static ref SH_VIOLATION: IriS = IriS::new_unchecked(SH_VIOLATION_STR);
     pub static ref SH_WARNING: IriS = IriS::new_unchecked(SH_WARNING_STR);
+    pub static ref SH_ALTERNATIVE_PATH: IriS = IriS::new_unchecked(SH_ALTERNATIVE_PATH_STR);
     pub static ref SH_AND: IriS = IriS::new_unchecked(SH_AND_STR);
     pub static ref SH_CLASS: IriS = IriS::new_unchecked(SH_CLASS_STR);
     pub static ref SH_CLOSED: IriS = IriS::new_unchecked(SH_CLOSED_STR);
@@ -112,6 +141,7 @@ lazy_static! {
     pub static ref SH_HAS_VALUE: IriS = IriS::new_unchecked(SH_HAS_VALUE_STR);
     pub static ref SH_IGNORED_PROPERTIES: IriS = IriS::new_unchecked(SH_IGNORED_PROPERTIES_STR);
     pub static ref SH_IN: IriS = IriS::new_unchecked(SH_IN_STR);
+    pub static ref SH_INVERSE_PATH: IriS = IriS::new_unchecked(SH_INVERSE_PATH_STR);
     pub static ref SH_LANGUAGE_IN: IriS = IriS::new_unchecked(SH_LANGUAGE_IN_STR);
     pub static ref SH_LESS_THAN: IriS = IriS::new_unchecked(SH_LESS_THAN_STR);
     pub static ref SH_LESS_THAN_OR_EQUALS: IriS = IriS::new_unchecked(SH_LESS_THAN_OR_EQUALS_STR);
@@ -128,6 +158,7 @@ lazy_static! {
     pub static ref SH_NODE_KIND: IriS = IriS::new_unchecked(SH_NODE_KIND_STR);
     pub static ref SH_NODE: IriS = IriS::new_unchecked(SH_NODE_STR);
     pub static ref SH_NOT: IriS = IriS::new_unchecked(SH_NOT_STR);
+    pub static ref SH_ONE_OR_MORE_PATH: IriS = IriS::new_unchecked(SH_ONE_OR_MORE_PATH_STR);
     pub static ref SH_OR: IriS = IriS::new_unchecked(SH_OR_STR);
     pub static ref SH_ORDER: IriS = IriS::new_unchecked(SH_ORDER_STR);
     pub static ref SH_PATH: IriS = IriS::new_unchecked(SH_PATH_STR);
@@ -156,5 +187,30 @@ lazy_static! {
     pub static ref SH_TEXT: IriS = IriS::new_unchecked(SH_TEXT_STR);
     pub static ref SH_UNIQUE_LANG: IriS = IriS::new_unchecked(SH_UNIQUE_LANG_STR);
     pub static ref SH_XONE: IriS = IriS::new_unchecked(SH_XONE_STR);
+    pub static ref SH_ZERO_OR_MORE_PATH: IriS = IriS::new_unchecked(SH_ZERO_OR_MORE_PATH_STR);
+    pub static ref SH_ZERO_OR_ONE_PATH: IriS = IriS::new_unchecked(SH_ZERO_OR_ONE_PATH_STR);
     pub static ref SH_SOURCE_CONSTRAINT: IriS = IriS::new_unchecked(SH_SOURCE_CONSTRAINT_STR);
 }
+
+// In a separate block because of the recursion limit of the macro
+lazy_static! {
+    pub static ref SH_TRIPLE_RULE: IriS = IriS::new_unchecked(SH_TRIPLE_RULE_STR);
+    pub static ref SH_SPARQL_RULE: IriS = IriS::new_unchecked(SH_SPARQL_RULE_STR);
+    pub static ref SH_RULE: IriS = IriS::new_unchecked(SH_RULE_STR);
+    pub static ref SH_SUBJECT: IriS = IriS::new_unchecked(SH_SUBJECT_STR);
+    pub static ref SH_PREDICATE: IriS = IriS::new_unchecked(SH_PREDICATE_STR);
+    pub static ref SH_OBJECT: IriS = IriS::new_unchecked(SH_OBJECT_STR);
+    pub static ref SH_CONDITION: IriS = IriS::new_unchecked(SH_CONDITION_STR);
+    pub static ref SH_CONSTRUCT: IriS = IriS::new_unchecked(SH_CONSTRUCT_STR);
+    pub static ref SH_PREFIXES: IriS = IriS::new_unchecked(SH_PREFIXES_STR);
+    pub static ref SH_DECLARE: IriS = IriS::new_unchecked(SH_DECLARE_STR);
+    pub static ref SH_PREFIX: IriS = IriS::new_unchecked(SH_PREFIX_STR);
+    pub static ref SH_NAMESPACE: IriS = IriS::new_unchecked(SH_NAMESPACE_STR);
+    pub static ref SH_THIS: IriS = IriS::new_unchecked(SH_THIS_STR);
+    pub static ref SH_FILTER_SHAPE: IriS = IriS::new_unchecked(SH_FILTER_SHAPE_STR);
+    pub static ref SH_NODES: IriS = IriS::new_unchecked(SH_NODES_STR);
+    pub static ref SH_INTERSECTION: IriS = IriS::new_unchecked(SH_INTERSECTION_STR);
+    pub static ref SH_UNION: IriS = IriS::new_unchecked(SH_UNION_STR);
+    pub static ref SH_PARAMETER: IriS = IriS::new_unchecked(SH_PARAMETER_STR);
+    pub static ref SH_OPTIONAL: IriS = IriS::new_unchecked(SH_OPTIONAL_STR);
+}
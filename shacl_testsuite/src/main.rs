@@ -2,12 +2,13 @@ use std::{fs::File, io::BufReader, path::Path};
 
 use clap::Parser;
 use manifest::{GraphManifest, Manifest};
+use shacl_ast::LocalResolver;
 use shacl_validation::{
     store::ShaclDataManager,
     validate::{GraphValidator, ShaclValidationMode, Validator},
     validation_report::report::ValidationReport,
 };
-use srdf::{RDFFormat, SRDFBasic, SRDF};
+use srdf::{RDFFormat, SRDFBasic, SRDFGraph, SRDF};
 use testsuite_error::TestSuiteError;
 
 mod helper;
@@ -66,7 +67,14 @@ impl<S: SRDF + SRDFBasic> ShaclTest<S> {
 #[allow(clippy::result_large_err)]
 fn main() -> Result<(), TestSuiteError> {
     let cli = Cli::parse(); // we obtain the CLI...
-    let path = Path::new(&cli.manifest_filename);
+    let (count, total) = run(Path::new(&cli.manifest_filename), cli.mode)?;
+    println!("{}/{}", count, total);
+    Ok(())
+}
+
+/// Collects the tests of a manifest and of the manifests that it includes
+#[allow(clippy::result_large_err)]
+fn collect_tests(path: &Path) -> Result<Vec<ShaclTest<SRDFGraph>>, TestSuiteError> {
     let manifest = GraphManifest::load(path)?;
     let mut manifests = Vec::new();
     let mut tests = Vec::new();
@@ -77,24 +85,41 @@ fn main() -> Result<(), TestSuiteError> {
         tests.extend(manifest.collect_tests()?);
     }
 
+    Ok(tests)
+}
+
+/// Runs the tests of a manifest, returning the number of tests that succeeded and the total number of tests
+#[allow(clippy::result_large_err)]
+fn run(path: &Path, mode: ShaclValidationMode) -> Result<(usize, usize), TestSuiteError> {
+    let tests = collect_tests(path)?;
     let total = tests.len();
     let mut count = 0;
     for test in tests {
         let validator = GraphValidator::new(
             Path::new(&test.data),
-            RDFFormat::NTriples,
+            RDFFormat::Turtle,
             test.base.as_deref(),
-            cli.mode,
+            mode,
         )?;
         let file = File::open(test.shapes.as_str())
             .unwrap_or_else(|_| panic!("Unable to open file: {}", test.shapes));
         let reader = BufReader::new(file);
-        let schema = ShaclDataManager::load(reader, srdf::RDFFormat::Turtle, test.base.as_deref())?;
+        // Imported graphs are looked for next to the shapes graph
+        let mut resolver = LocalResolver::new();
+        if let Some(folder) = Path::new(&test.shapes).parent() {
+            resolver = resolver.with_folder(folder);
+        }
+        let schema = ShaclDataManager::load_with_imports(
+            reader,
+            srdf::RDFFormat::Turtle,
+            test.base.as_deref(),
+            &resolver,
+        )?;
         let label = match test.label {
             Some(label) => label,
             None => String::from("Test"),
         };
-        match validator.validate(schema) {
+        match validator.validate(&schema) {
             Ok(actual) => {
                 if actual == test.result {
                     println!("{} succeeded", label);
@@ -111,7 +136,293 @@ fn main() -> Result<(), TestSuiteError> {
         };
     }
 
-    println!("{}/{}", count, total);
+    Ok((count, total))
+}
 
-    Ok(())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use iri_s::IriS;
+    use shacl_ast::ShaclWriter;
+    use shacl_validation::rules::RuleEngine;
+    use shacl_validation::validate::ConstraintComponentRegistry;
+    use shacl_validation::validate::Entailment;
+    use srdf::ReaderMode;
+    use std::collections::HashMap;
+
+    fn local_manifest() -> &'static Path {
+        Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/localTest/manifest.ttl"
+        ))
+    }
+
+    fn path_manifest() -> &'static Path {
+        Path::new(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/localTest/core/path/manifest.ttl"
+        ))
+    }
+
+    #[test]
+    fn local_tests() {
+        let (count, total) = run(local_manifest(), ShaclValidationMode::Default).unwrap();
+        assert_eq!(total, 15);
+        assert_eq!(count, total);
+    }
+
+    #[test]
+    fn path_tests_in_sparql_mode() {
+        let (count, total) = run(path_manifest(), ShaclValidationMode::SPARQL).unwrap();
+        assert_eq!(total, 7);
+        assert_eq!(count, total);
+    }
+
+    #[test]
+    fn written_paths_can_be_parsed_again() {
+        for test in collect_tests(path_manifest()).unwrap() {
+            let file = File::open(&test.shapes).unwrap();
+            let reader = BufReader::new(file);
+            let schema =
+                ShaclDataManager::load(reader, RDFFormat::Turtle, test.base.as_deref()).unwrap();
+            let mut writer: ShaclWriter<SRDFGraph> = ShaclWriter::new();
+            writer.write(&schema).unwrap();
+            let mut turtle = Vec::new();
+            writer.serialize(RDFFormat::Turtle, &mut turtle).unwrap();
+            let written =
+                ShaclDataManager::load(turtle.as_slice(), RDFFormat::Turtle, None).unwrap();
+
+            let validator = GraphValidator::new(
+                Path::new(&test.data),
+                RDFFormat::Turtle,
+                test.base.as_deref(),
+                ShaclValidationMode::Default,
+            )
+            .unwrap();
+            let actual = validator.validate(&written).unwrap();
+            assert!(actual == test.result, "{actual} != {}", test.result);
+        }
+    }
+
+    #[test]
+    fn rdfs_entailment_adds_types() {
+        let data = r#"prefix : <http://example.org/>
+            prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+            :Dog    rdfs:subClassOf :Animal .
+            :owner  rdfs:domain :Person ;
+                    rdfs:range  :Pet .
+            :Pet    rdfs:subClassOf :Animal .
+            :alice  :owner :rex, :tom .
+            :rex    a :Dog .
+        "#;
+        let shapes = r#"prefix : <http://example.org/>
+            prefix sh: <http://www.w3.org/ns/shacl#>
+            :Named a sh:NodeShape ;
+                sh:targetClass :Person, :Animal ;
+                sh:property [ sh:path :name ; sh:minCount 1 ] .
+        "#;
+        let count_results = |entailment| {
+            let graph =
+                SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+            let schema =
+                ShaclDataManager::load(shapes.as_bytes(), RDFFormat::Turtle, None).unwrap();
+            GraphValidator::from_graph(graph, ShaclValidationMode::Default)
+                .and_then(|validator| validator.with_entailment(entailment))
+                .and_then(|validator| validator.validate(&schema))
+                .unwrap()
+                .results()
+                .len()
+        };
+        // Without entailment only :rex is an instance of :Animal
+        assert_eq!(count_results(Entailment::None), 1);
+        // :alice is a :Person by the domain of :owner and :tom is an :Animal by its range
+        assert_eq!(count_results(Entailment::RDFS), 3);
+    }
+
+    fn infer(rdf: &str) -> SRDFGraph {
+        let mut graph =
+            SRDFGraph::from_str(rdf, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let schema = ShaclDataManager::load(rdf.as_bytes(), RDFFormat::Turtle, None).unwrap();
+        RuleEngine::new(&schema).infer(&mut graph).unwrap()
+    }
+
+    fn values(graph: &SRDFGraph, subject: &str, predicate: &str) -> Vec<String> {
+        let iri = |str: &str| IriS::new_unchecked(&format!("http://example.org/{str}"));
+        let mut values: Vec<_> = graph
+            .objects_for_subject_predicate(
+                &SRDFGraph::iri_s2subject(&iri(subject)),
+                &SRDFGraph::iri_s2iri(&iri(predicate)),
+            )
+            .unwrap()
+            .iter()
+            .map(|value| value.to_string())
+            .collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn triple_rules_follow_order_and_conditions() {
+        let rdf = r#"prefix : <http://example.org/>
+            prefix sh: <http://www.w3.org/ns/shacl#>
+            :alice a :Person ; :parent :bob .
+            :bob   a :Person ; :parent :carol .
+            :carol a :Person .
+            :HasParent a sh:NodeShape ;
+                sh:property [ sh:path :parent ; sh:minCount 1 ] .
+            :PersonShape a sh:NodeShape ;
+                sh:targetClass :Person ;
+                sh:rule [ a sh:TripleRule ; sh:order 2 ;
+                    sh:subject sh:this ; sh:predicate :ancestor ;
+                    sh:object [ sh:path ( :ancestor :ancestor ) ] ] ;
+                sh:rule [ a sh:TripleRule ; sh:order 1 ;
+                    sh:subject sh:this ; sh:predicate :ancestor ;
+                    sh:object [ sh:path :parent ] ] ;
+                sh:rule [ a sh:TripleRule ; sh:condition :HasParent ;
+                    sh:subject sh:this ; sh:predicate :status ; sh:object :Child ] ;
+                sh:rule [ a sh:TripleRule ; sh:deactivated true ;
+                    sh:subject sh:this ; sh:predicate :status ; sh:object :Unknown ] .
+        "#;
+        let inferred = infer(rdf);
+        assert_eq!(
+            values(&inferred, "alice", "ancestor"),
+            vec!["<http://example.org/bob>", "<http://example.org/carol>"]
+        );
+        assert_eq!(
+            values(&inferred, "bob", "status"),
+            vec!["<http://example.org/Child>"]
+        );
+        assert!(values(&inferred, "carol", "status").is_empty());
+    }
+
+    #[test]
+    fn sparql_rules_construct_triples() {
+        let rdf = r#"prefix : <http://example.org/>
+            prefix sh: <http://www.w3.org/ns/shacl#>
+            prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+            :alice a :Person ; :name "Alice" ; :age 30 .
+            :bob   a :Person ; :age 12 .
+            : sh:declare [ sh:prefix "ex" ; sh:namespace "http://example.org/"^^xsd:anyURI ] .
+            :PersonShape a sh:NodeShape ;
+                sh:targetClass :Person ;
+                sh:rule [ a sh:SPARQLRule ; sh:prefixes : ;
+                    sh:construct """
+                        CONSTRUCT { $this ex:label ?label ; a ?type }
+                        WHERE {
+                            OPTIONAL { $this ex:name ?name }
+                            $this ex:age ?age
+                            BIND (COALESCE(UCASE(?name), "ANONYMOUS") AS ?label)
+                            BIND (IF(?age >= 18, ex:Adult, ex:Minor) AS ?type)
+                        }
+                    """ ] .
+        "#;
+        let inferred = infer(rdf);
+        assert_eq!(values(&inferred, "alice", "label"), vec!["\"ALICE\""]);
+        assert_eq!(values(&inferred, "bob", "label"), vec!["\"ANONYMOUS\""]);
+        let mut types: Vec<_> = inferred
+            .triples_with_predicate(&SRDFGraph::iri_s2iri(&srdf::RDF_TYPE))
+            .unwrap()
+            .iter()
+            .map(|triple| triple.obj().to_string())
+            .collect();
+        types.sort();
+        assert_eq!(
+            types,
+            vec!["<http://example.org/Adult>", "<http://example.org/Minor>"]
+        );
+    }
+
+    #[test]
+    fn node_expressions_filter_and_combine_values() {
+        let rdf = r#"prefix : <http://example.org/>
+            prefix sh: <http://www.w3.org/ns/shacl#>
+            prefix fn: <http://www.w3.org/2005/xpath-functions#>
+            :alice a :Person ; :knows :bob, :carol ; :likes :carol, :dave ; :name "alice" .
+            :bob   :email "bob@example.org" .
+            :HasEmail a sh:NodeShape ;
+                sh:property [ sh:path :email ; sh:minCount 1 ] .
+            :PersonShape a sh:NodeShape ;
+                sh:targetClass :Person ;
+                sh:rule [ a sh:TripleRule ;
+                    sh:subject sh:this ; sh:predicate :contact ;
+                    sh:object [ sh:filterShape :HasEmail ; sh:nodes [ sh:path :knows ] ] ] ;
+                sh:rule [ a sh:TripleRule ;
+                    sh:subject sh:this ; sh:predicate :friend ;
+                    sh:object [ sh:intersection ( [ sh:path :knows ] [ sh:path :likes ] ) ] ] ;
+                sh:rule [ a sh:TripleRule ;
+                    sh:subject sh:this ; sh:predicate :related ;
+                    sh:object [ sh:union ( [ sh:path :knows ] [ sh:path :likes ] ) ] ] ;
+                sh:rule [ a sh:TripleRule ;
+                    sh:subject sh:this ; sh:predicate :label ;
+                    sh:object [ fn:upper-case ( [ sh:path :name ] ) ] ] .
+        "#;
+        let inferred = infer(rdf);
+        assert_eq!(
+            values(&inferred, "alice", "contact"),
+            vec!["<http://example.org/bob>"]
+        );
+        assert_eq!(
+            values(&inferred, "alice", "friend"),
+            vec!["<http://example.org/carol>"]
+        );
+        assert_eq!(values(&inferred, "alice", "related").len(), 3);
+        assert_eq!(values(&inferred, "alice", "label"), vec!["\"ALICE\""]);
+    }
+
+    #[test]
+    fn custom_components_use_registered_checks() {
+        let data = r#"prefix : <http://example.org/>
+            :alice :age 30 .
+            :bob   :age 31 .
+            :carol :age 40 .
+        "#;
+        let shapes = r#"prefix : <http://example.org/>
+            prefix sh: <http://www.w3.org/ns/shacl#>
+            :DivisibleByConstraintComponent a sh:ConstraintComponent ;
+                sh:parameter [ sh:path :divisibleBy ] .
+            :UnknownConstraintComponent a sh:ConstraintComponent ;
+                sh:parameter [ sh:path :unknown ] .
+            :AgeShape a sh:NodeShape ;
+                sh:targetNode :alice, :bob, :carol ;
+                sh:property [ sh:path :age ; :divisibleBy 10 ; :unknown true ] .
+        "#;
+        let divisible_by = |node: &srdf::Object, parameters: &HashMap<IriS, srdf::Object>| {
+            let number = |object: &srdf::Object| match object {
+                srdf::Object::Literal(literal) => literal.lexical_form().parse::<i64>().ok(),
+                _ => None,
+            };
+            let divisor = parameters
+                .get(&IriS::new_unchecked("http://example.org/divisibleBy"))
+                .and_then(number)
+                .ok_or("divisibleBy must be an integer")?;
+            match number(node) {
+                Some(n) if n % divisor == 0 => Ok(()),
+                _ => Err(format!("{node} is not divisible by {divisor}")),
+            }
+        };
+        let components = ConstraintComponentRegistry::new().with_check(
+            IriS::new_unchecked("http://example.org/DivisibleByConstraintComponent"),
+            divisible_by,
+        );
+        let graph =
+            SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let schema = ShaclDataManager::load(shapes.as_bytes(), RDFFormat::Turtle, None).unwrap();
+        let report = GraphValidator::from_graph(graph, ShaclValidationMode::Default)
+            .unwrap()
+            .with_components(components)
+            .validate(&schema)
+            .unwrap();
+        // :UnknownConstraintComponent has no registered check so it is ignored
+        assert_eq!(report.results().len(), 1);
+        let result = &report.results()[0];
+        assert_eq!(
+            result.focus_node().unwrap().to_string(),
+            "<http://example.org/bob>"
+        );
+        assert!(result
+            .result_message()
+            .unwrap()
+            .to_string()
+            .contains("is not divisible by 10"));
+    }
 }
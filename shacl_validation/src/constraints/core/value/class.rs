@@ -2,17 +2,13 @@ use indoc::formatdoc;
 use srdf::QuerySRDF;
 use srdf::RDFNode;
 use srdf::SRDFBasic;
-use srdf::RDFS_SUBCLASS_OF;
-use srdf::RDF_TYPE;
 use srdf::SRDF;
-use std::collections::HashSet;
 
 use crate::constraints::constraint_error::ConstraintError;
 use crate::constraints::DefaultConstraintComponent;
 use crate::constraints::SparqlConstraintComponent;
 use crate::context::EvaluationContext;
 use crate::context::ValidationContext;
-use crate::helper::srdf::get_objects_for;
 use crate::validation_report::result::{ValidationResult, ValidationResults};
 use crate::ValueNodes;
 
@@ -47,25 +43,10 @@ impl<S: SRDF + 'static> DefaultConstraintComponent<S> for Class<S> {
                         ValidationResult::new(focus_node, &evaluation_context, Some(value_node));
                     Some(result)
                 } else {
-                    let objects = match get_objects_for(
-                        validation_context.store(),
-                        value_node,
-                        &S::iri_s2iri(&RDF_TYPE),
-                    ) {
-                        Ok(objects) => objects,
-                        Err(_) => HashSet::new(),
-                    };
-
-                    let is_class_valid = objects.iter().any(|ctype| {
-                        ctype == &self.class_rule
-                            || get_objects_for(
-                                validation_context.store(),
-                                ctype,
-                                &S::iri_s2iri(&RDFS_SUBCLASS_OF),
-                            )
-                            .unwrap_or_default()
-                            .contains(&self.class_rule)
-                    });
+                    let is_class_valid = validation_context
+                        .subclasses()
+                        .is_instance_of(validation_context.store(), value_node, &self.class_rule)
+                        .unwrap_or(false);
 
                     if !is_class_valid {
                         Some(ValidationResult::new(
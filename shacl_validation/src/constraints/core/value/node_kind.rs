@@ -41,6 +41,7 @@ impl<S: SRDF + 'static> DefaultConstraintComponent<S> for Nodekind {
                     S::term_is_iri(value_node),
                     S::term_is_literal(value_node),
                 ) {
+                    _ if S::term_is_triple(value_node) => self.node_kind == NodeKind::TripleTerm,
                     (true, false, false) => matches!(
                         self.node_kind,
                         NodeKind::BlankNode
@@ -81,7 +82,13 @@ impl<S: QuerySRDF + 'static> SparqlConstraintComponent<S> for Nodekind {
     ) -> Result<ValidationResults<S>, ConstraintError> {
         let results = value_nodes.iter_value_nodes()
             .filter_map(move |(focus_node, value_node)| {
-                let query = if S::term_is_iri(value_node) {
+                let query = if S::term_is_triple(value_node) {
+                    formatdoc! {"
+                            PREFIX sh: <http://www.w3.org/ns/shacl#>
+                            ASK {{ FILTER ({} IN ( sh:TripleTerm ) ) }}
+                        ", self.node_kind
+                    }
+                } else if S::term_is_iri(value_node) {
                     formatdoc! {"
                             PREFIX sh: <http://www.w3.org/ns/shacl#>
                             ASK {{ FILTER ({} IN ( sh:IRI, sh:BlankNodeOrIRI, sh:IRIOrLiteral ) ) }}
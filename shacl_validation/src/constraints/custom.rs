@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+use iri_s::IriS;
+use srdf::literal::Literal;
+use srdf::Object;
+use srdf::QuerySRDF;
+use srdf::RDFNode;
+use srdf::SRDFBasic;
+use srdf::SRDF;
+
+use crate::constraints::constraint_error::ConstraintError;
+use crate::constraints::DefaultConstraintComponent;
+use crate::constraints::SparqlConstraintComponent;
+use crate::context::EvaluationContext;
+use crate::context::ValidationContext;
+use crate::validation_report::result::ValidationResult;
+use crate::validation_report::result::ValidationResults;
+use crate::ValueNodes;
+
+/// Constraint component declared in the shapes graph whose validator is a
+/// check registered in the [`ConstraintComponentRegistry`](crate::validate::ConstraintComponentRegistry).
+///
+/// Components without a registered check are ignored.
+///
+/// https://www.w3.org/TR/shacl/#constraint-components
+pub(crate) struct Custom {
+    component: IriS,
+    parameters: HashMap<IriS, Object>,
+}
+
+impl Custom {
+    pub fn new(component: IriS, parameters: Vec<(IriS, RDFNode)>) -> Self {
+        Custom {
+            component,
+            parameters: parameters.into_iter().collect(),
+        }
+    }
+
+    fn evaluate<S: SRDFBasic>(
+        &self,
+        validation_context: &ValidationContext<S>,
+        evaluation_context: EvaluationContext,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<ValidationResults<S>, ConstraintError> {
+        let Some(check) = validation_context
+            .components()
+            .and_then(|components| components.get(&self.component))
+        else {
+            return Ok(ValidationResults::default());
+        };
+
+        let results = value_nodes
+            .iter_value_nodes()
+            .filter_map(|(focus_node, value_node)| {
+                match check.check(&S::term_as_object(value_node), &self.parameters) {
+                    Ok(()) => None,
+                    Err(msg) => {
+                        let msg = S::object_as_term(&Object::literal(Literal::str(&msg)));
+                        Some(
+                            ValidationResult::new(
+                                focus_node,
+                                &evaluation_context,
+                                Some(value_node),
+                            )
+                            .with_result_message(msg),
+                        )
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
+        Ok(ValidationResults::new(results.into_iter()))
+    }
+}
+
+impl<S: SRDF + 'static> DefaultConstraintComponent<S> for Custom {
+    fn evaluate_default(
+        &self,
+        validation_context: &ValidationContext<S>,
+        evaluation_context: EvaluationContext,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<ValidationResults<S>, ConstraintError> {
+        self.evaluate(validation_context, evaluation_context, value_nodes)
+    }
+}
+
+impl<S: QuerySRDF + 'static> SparqlConstraintComponent<S> for Custom {
+    fn evaluate_sparql(
+        &self,
+        validation_context: &ValidationContext<S>,
+        evaluation_context: EvaluationContext,
+        value_nodes: &ValueNodes<S>,
+    ) -> Result<ValidationResults<S>, ConstraintError> {
+        self.evaluate(validation_context, evaluation_context, value_nodes)
+    }
+}
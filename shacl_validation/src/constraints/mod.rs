@@ -26,6 +26,7 @@ use core::value_range::max_exclusive::MaxExclusive;
 use core::value_range::max_inclusive::MaxInclusive;
 use core::value_range::min_exclusive::MinExclusive;
 use core::value_range::min_inclusive::MinInclusive;
+use custom::Custom;
 use shacl_ast::component::Component;
 use srdf::QuerySRDF;
 use srdf::SRDFBasic;
@@ -38,6 +39,7 @@ use crate::ValueNodes;
 
 pub mod constraint_error;
 pub mod core;
+pub(crate) mod custom;
 
 pub(crate) trait ConstraintComponent<S: SRDFBasic> {
     fn evaluate(
@@ -109,6 +111,10 @@ impl<S: SRDF + 'static> From<&Component> for Box<dyn DefaultConstraintComponent<
                 qualified_max_count,
                 qualified_value_shapes_disjoint,
             )),
+            Component::Custom {
+                component,
+                parameters,
+            } => Box::new(Custom::new(component, parameters)),
         }
     }
 }
@@ -156,6 +162,10 @@ impl<S: QuerySRDF + 'static> From<&Component> for Box<dyn SparqlConstraintCompon
                 qualified_max_count,
                 qualified_value_shapes_disjoint,
             )),
+            Component::Custom {
+                component,
+                parameters,
+            } => Box::new(Custom::new(component, parameters)),
         }
     }
 }
@@ -5,15 +5,19 @@ use srdf::QuerySRDF;
 use srdf::SRDFBasic;
 use srdf::SRDF;
 
+use crate::helper::subclass::SubClassClosure;
 use crate::runner::default_runner::DefaultValidatorRunner;
 use crate::runner::query_runner::QueryValidatorRunner;
 use crate::runner::ValidatorRunner;
 use crate::store::Store;
+use crate::validate::ConstraintComponentRegistry;
 
 pub struct ValidationContext<'a, S: SRDFBasic> {
     store: &'a dyn Store<S>,
     schema: &'a Schema,
     runner: &'a dyn ValidatorRunner<S>,
+    subclasses: SubClassClosure<S>,
+    components: Option<&'a ConstraintComponentRegistry>,
 }
 
 impl<'a, S: SRDF + 'static> ValidationContext<'a, S> {
@@ -22,8 +26,15 @@ impl<'a, S: SRDF + 'static> ValidationContext<'a, S> {
             store,
             schema,
             runner: &DefaultValidatorRunner,
+            subclasses: SubClassClosure::default(),
+            components: None,
         }
     }
+
+    /// Closure of `rdfs:subClassOf` in the data graph, shared by all the shapes of the validation
+    pub(crate) fn subclasses(&self) -> &SubClassClosure<S> {
+        &self.subclasses
+    }
 }
 
 impl<'a, S: QuerySRDF + 'static> ValidationContext<'a, S> {
@@ -32,11 +43,23 @@ impl<'a, S: QuerySRDF + 'static> ValidationContext<'a, S> {
             store,
             schema,
             runner: &QueryValidatorRunner,
+            subclasses: SubClassClosure::default(),
+            components: None,
         }
     }
 }
 
 impl<'a, S: SRDFBasic> ValidationContext<'a, S> {
+    pub(crate) fn with_components(mut self, components: &'a ConstraintComponentRegistry) -> Self {
+        self.components = Some(components);
+        self
+    }
+
+    /// Constraint components implemented in Rust that are available in the validation
+    pub(crate) fn components(&self) -> Option<&ConstraintComponentRegistry> {
+        self.components
+    }
+
     pub(crate) fn store(&self) -> &S {
         self.store.store()
     }
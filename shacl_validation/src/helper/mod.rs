@@ -2,3 +2,4 @@ pub(crate) mod helper_error;
 pub(crate) mod shapes;
 pub(crate) mod sparql;
 pub(crate) mod srdf;
+pub(crate) mod subclass;
@@ -0,0 +1,100 @@
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+use srdf::{SRDFBasic, RDFS_SUBCLASS_OF, RDF_TYPE, SRDF};
+
+use super::helper_error::SRDFError;
+use super::srdf::{get_objects_for, get_subjects_for};
+
+/// Transitive closure of `rdfs:subClassOf`
+///
+/// The closure of a class is computed the first time that it is needed and cached, so it is
+/// obtained only once per validation without traversing the whole data graph.
+pub(crate) struct SubClassClosure<S: SRDFBasic> {
+    superclasses: RefCell<HashMap<S::Term, HashSet<S::Term>>>,
+    subclasses: RefCell<HashMap<S::Term, HashSet<S::Term>>>,
+}
+
+impl<S: SRDFBasic> Default for SubClassClosure<S> {
+    fn default() -> Self {
+        SubClassClosure {
+            superclasses: RefCell::new(HashMap::new()),
+            subclasses: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+impl<S: SRDF> SubClassClosure<S> {
+    /// Classes of which `class` is a subclass, including `class`
+    pub fn superclasses(&self, store: &S, class: &S::Term) -> Result<HashSet<S::Term>, SRDFError> {
+        if let Some(classes) = self.superclasses.borrow().get(class) {
+            return Ok(classes.clone());
+        }
+        let classes = closure::<S>(class, |class| {
+            get_objects_for(store, class, &S::iri_s2iri(&RDFS_SUBCLASS_OF))
+        })?;
+        self.superclasses
+            .borrow_mut()
+            .insert(class.clone(), classes.clone());
+        Ok(classes)
+    }
+
+    /// Classes that are subclasses of `class`, including `class`
+    pub fn subclasses(&self, store: &S, class: &S::Term) -> Result<HashSet<S::Term>, SRDFError> {
+        if let Some(classes) = self.subclasses.borrow().get(class) {
+            return Ok(classes.clone());
+        }
+        let classes = closure::<S>(class, |class| {
+            get_subjects_for(store, &S::iri_s2iri(&RDFS_SUBCLASS_OF), class)
+        })?;
+        self.subclasses
+            .borrow_mut()
+            .insert(class.clone(), classes.clone());
+        Ok(classes)
+    }
+
+    /// Checks if `node` is a [SHACL instance](https://www.w3.org/TR/shacl/#dfn-shacl-instance) of `class`,
+    /// i.e. if it has as `rdf:type` the class or one of its subclasses
+    pub fn is_instance_of(
+        &self,
+        store: &S,
+        node: &S::Term,
+        class: &S::Term,
+    ) -> Result<bool, SRDFError> {
+        if S::term_is_literal(node) {
+            return Ok(false);
+        }
+        let classes = self.subclasses(store, class)?;
+        let types = get_objects_for(store, node, &S::iri_s2iri(&RDF_TYPE))?;
+        Ok(types.iter().any(|t| classes.contains(t)))
+    }
+
+    /// [SHACL instances](https://www.w3.org/TR/shacl/#dfn-shacl-instance) of `class`
+    pub fn instances(&self, store: &S, class: &S::Term) -> Result<HashSet<S::Term>, SRDFError> {
+        let mut instances = HashSet::new();
+        for class in self.subclasses(store, class)? {
+            instances.extend(get_subjects_for(store, &S::iri_s2iri(&RDF_TYPE), &class)?)
+        }
+        Ok(instances)
+    }
+}
+
+// Nodes reachable from `start` following `next` zero or more times. Cycles are allowed
+fn closure<S: SRDF>(
+    start: &S::Term,
+    next: impl Fn(&S::Term) -> Result<HashSet<S::Term>, SRDFError>,
+) -> Result<HashSet<S::Term>, SRDFError> {
+    let mut visited = HashSet::from([start.clone()]);
+    let mut pending = vec![start.clone()];
+    while let Some(current) = pending.pop() {
+        if S::term_is_literal(&current) {
+            continue;
+        }
+        for node in next(&current)? {
+            if visited.insert(node.clone()) {
+                pending.push(node)
+            }
+        }
+    }
+    Ok(visited)
+}
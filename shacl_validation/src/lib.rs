@@ -6,6 +6,7 @@ use srdf::SRDFBasic;
 pub(crate) mod constraints;
 pub(crate) mod context;
 pub mod helper;
+pub mod rules;
 pub(crate) mod runner;
 pub mod shacl_validation_vocab;
 pub(crate) mod shape;
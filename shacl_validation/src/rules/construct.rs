@@ -0,0 +1,47 @@
+use spargebra::algebra::GraphPattern;
+use spargebra::term::TriplePattern;
+use spargebra::Query;
+use srdf::{Bindings, ConstructedTriple, RDFNode, SparqlEvaluator, SRDF};
+
+use super::rule_error::RuleError;
+
+/// SPARQL `CONSTRUCT` query of a SPARQL rule
+///
+/// The queries are evaluated directly over the RDF data with [`SparqlEvaluator`], so SPARQL rules
+/// can be applied to any RDF source.
+pub(crate) struct Construct {
+    template: Vec<TriplePattern>,
+    pattern: GraphPattern,
+}
+
+impl Construct {
+    pub fn parse(rule: &RDFNode, query: &str) -> Result<Self, RuleError> {
+        let query = Query::parse(query, None).map_err(|err| RuleError::SparqlSyntax {
+            rule: rule.clone(),
+            err: err.to_string(),
+        })?;
+        match query {
+            Query::Construct {
+                template,
+                dataset: None,
+                pattern,
+                ..
+            } => Ok(Construct { template, pattern }),
+            Query::Construct { .. } => Err(RuleError::UnsupportedSparql {
+                feature: "FROM".to_string(),
+            }),
+            _ => Err(RuleError::NotConstruct { rule: rule.clone() }),
+        }
+    }
+
+    /// Triples constructed for a focus node, which is bound to the variable `$this`
+    pub fn triples<S: SRDF>(
+        &self,
+        store: &S,
+        focus: &S::Term,
+    ) -> Result<Vec<ConstructedTriple<S>>, RuleError> {
+        let mut input = Bindings::<S>::new();
+        input.insert("this".to_string(), focus.clone());
+        Ok(SparqlEvaluator::new(store).construct(&self.template, &self.pattern, &input)?)
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+
+use iri_s::IriS;
+use shacl_ast::rule::Rule;
+use shacl_ast::rule::RuleKind;
+use shacl_ast::shape::Shape;
+use shacl_ast::Schema;
+use srdf::Object;
+use srdf::SRDFBuilder;
+
+use crate::context::ValidationContext;
+use crate::helper::shapes::get_shape_ref;
+use crate::shape::FocusNodesOps;
+use crate::store::Store;
+
+use self::construct::Construct;
+use self::node_expr::builtin_functions;
+use self::node_expr::NodeExprEvaluator;
+pub use self::node_expr::NodeFunction;
+use self::rule_error::RuleError;
+
+mod construct;
+mod node_expr;
+pub mod rule_error;
+
+const DEFAULT_MAX_ITERATIONS: usize = 100;
+
+/// Executes the [rules](https://www.w3.org/TR/shacl-af/#rules) of a shapes graph over RDF data
+///
+/// The rules of all the shapes are executed following their `sh:order` until no new triple is
+/// inferred, each rule sees the triples inferred by the rules executed before it.
+pub struct RuleEngine<'a> {
+    schema: &'a Schema,
+    functions: HashMap<IriS, NodeFunction>,
+    max_iterations: usize,
+}
+
+struct ScheduledRule<'a> {
+    shape: &'a Shape,
+    rule: &'a Rule,
+    construct: Option<Construct>,
+}
+
+impl<'a> RuleEngine<'a> {
+    pub fn new(schema: &'a Schema) -> Self {
+        RuleEngine {
+            schema,
+            functions: builtin_functions(),
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+        }
+    }
+
+    /// Registers a function that can be called from the function node expressions of triple rules
+    pub fn with_function(
+        mut self,
+        iri: IriS,
+        function: impl Fn(&[Object]) -> Option<Object> + 'static,
+    ) -> Self {
+        self.functions.insert(iri, Box::new(function));
+        self
+    }
+
+    /// Maximum number of times that the rules are executed looking for a fixpoint
+    pub fn with_max_iterations(mut self, max_iterations: usize) -> Self {
+        self.max_iterations = max_iterations;
+        self
+    }
+
+    /// Adds the inferred triples to the data and returns them in a new graph
+    pub fn infer<S: SRDFBuilder + 'static>(&self, data: &mut S) -> Result<S, RuleError> {
+        let rules = self.scheduled_rules()?;
+        let mut inferred = S::empty();
+        if let Some(prefixmap) = data.prefixmap() {
+            inferred.add_prefix_map(prefixmap).map_err(srdf_error)?;
+        }
+
+        for _ in 0..self.max_iterations {
+            let mut changed = false;
+            for scheduled in rules.iter() {
+                for (subject, predicate, object) in self.apply(scheduled, data)? {
+                    let exists = data
+                        .objects_for_subject_predicate(&subject, &predicate)
+                        .map_err(srdf_error)?
+                        .contains(&object);
+                    if !exists {
+                        data.add_triple(&subject, &predicate, &object)
+                            .map_err(srdf_error)?;
+                        inferred
+                            .add_triple(&subject, &predicate, &object)
+                            .map_err(srdf_error)?;
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                return Ok(inferred);
+            }
+        }
+        Err(RuleError::NoFixpoint {
+            iterations: self.max_iterations,
+        })
+    }
+
+    /// Active rules of the active node shapes, in the order in which they are executed
+    fn scheduled_rules(&self) -> Result<Vec<ScheduledRule<'a>>, RuleError> {
+        let mut rules = Vec::new();
+        for (_, shape) in self.schema.iter() {
+            let Shape::NodeShape(node_shape) = shape else {
+                continue;
+            };
+            if *node_shape.is_deactivated() {
+                continue;
+            }
+            for rule in node_shape.rules().iter().filter(|r| !r.is_deactivated()) {
+                let construct = match rule.kind().query() {
+                    Some(query) => Some(Construct::parse(rule.id(), &query)?),
+                    None => None,
+                };
+                rules.push(ScheduledRule {
+                    shape,
+                    rule,
+                    construct,
+                })
+            }
+        }
+        rules.sort_by(|a, b| a.rule.order().total_cmp(&b.rule.order()));
+        Ok(rules)
+    }
+
+    /// Triples inferred by a rule for the focus nodes of its shape
+    #[allow(clippy::type_complexity)]
+    fn apply<S: SRDFBuilder + 'static>(
+        &self,
+        scheduled: &ScheduledRule,
+        data: &S,
+    ) -> Result<Vec<(S::Subject, S::IRI, S::Term)>, RuleError> {
+        let store = BorrowedStore(data);
+        let context = ValidationContext::new_default(&store, self.schema);
+        let evaluator = NodeExprEvaluator::new(&context, &self.functions);
+
+        let mut triples = Vec::new();
+        for focus in scheduled.shape.focus_nodes(&context) {
+            if !self.conditions_hold(&evaluator, scheduled.rule, &focus)? {
+                continue;
+            }
+            match (scheduled.rule.kind(), &scheduled.construct) {
+                (_, Some(construct)) => triples.extend(construct.triples(data, &focus)?),
+                (
+                    RuleKind::Triple {
+                        subject,
+                        predicate,
+                        object,
+                    },
+                    None,
+                ) => {
+                    let predicate = S::iri_s2iri(predicate);
+                    let objects = evaluator.eval(object, &focus)?;
+                    for subject in evaluator.eval(subject, &focus)? {
+                        // literals can't be subjects of the inferred triples
+                        let Some(subject) = S::term_as_subject(&subject) else {
+                            continue;
+                        };
+                        for object in objects.iter() {
+                            triples.push((subject.clone(), predicate.clone(), object.clone()))
+                        }
+                    }
+                }
+                (RuleKind::Sparql { .. }, None) => {}
+            }
+        }
+        Ok(triples)
+    }
+
+    /// Checks that the focus node conforms to all the `sh:condition` shapes of the rule
+    fn conditions_hold<S: SRDFBuilder + 'static>(
+        &self,
+        evaluator: &NodeExprEvaluator<S>,
+        rule: &Rule,
+        focus: &S::Term,
+    ) -> Result<bool, RuleError> {
+        for condition in rule.conditions() {
+            let Some(shape) = get_shape_ref(condition, self.schema) else {
+                return Err(RuleError::ShapeNotFound {
+                    shape: condition.clone(),
+                });
+            };
+            if !evaluator.conforms(shape, condition, focus)? {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Data that is being extended by the rules, which is validated without taking ownership of it
+struct BorrowedStore<'a, S>(&'a S);
+
+impl<S> Store<S> for BorrowedStore<'_, S> {
+    fn store(&self) -> &S {
+        self.0
+    }
+}
+
+fn srdf_error(err: impl std::fmt::Display) -> RuleError {
+    RuleError::Srdf {
+        err: err.to_string(),
+    }
+}
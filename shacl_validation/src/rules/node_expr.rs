@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use iri_s::IriS;
+use shacl_ast::node_expr::NodeExpr;
+use srdf::literal::Literal;
+use srdf::Object;
+use srdf::SRDF;
+
+use crate::context::ValidationContext;
+use crate::helper::shapes::get_shape_ref;
+use crate::shape::ShapeValidator;
+use crate::Targets;
+
+use super::rule_error::RuleError;
+
+/// Implementation of a function that can be called from function node expressions
+///
+/// It returns `None` when the function is not defined for the arguments
+pub type NodeFunction = Box<dyn Fn(&[Object]) -> Option<Object>>;
+
+const FN: &str = "http://www.w3.org/2005/xpath-functions#";
+
+/// Functions available in every rule engine, from the
+/// [XPath functions](https://www.w3.org/TR/xpath-functions/) namespace
+pub(crate) fn builtin_functions() -> HashMap<IriS, NodeFunction> {
+    let mut functions: HashMap<IriS, NodeFunction> = HashMap::new();
+    functions.insert(
+        IriS::new_unchecked(&format!("{FN}concat")),
+        Box::new(|args| {
+            let strs: Option<Vec<_>> = args.iter().map(lexical_form).collect();
+            Some(Object::literal(Literal::str(&strs?.concat())))
+        }),
+    );
+    functions.insert(
+        IriS::new_unchecked(&format!("{FN}upper-case")),
+        Box::new(|args| match args {
+            [arg] => Some(Object::literal(Literal::str(
+                &lexical_form(arg)?.to_uppercase(),
+            ))),
+            _ => None,
+        }),
+    );
+    functions.insert(
+        IriS::new_unchecked(&format!("{FN}lower-case")),
+        Box::new(|args| match args {
+            [arg] => Some(Object::literal(Literal::str(
+                &lexical_form(arg)?.to_lowercase(),
+            ))),
+            _ => None,
+        }),
+    );
+    functions
+}
+
+fn lexical_form(object: &Object) -> Option<String> {
+    match object {
+        Object::Literal(literal) => Some(literal.lexical_form()),
+        Object::Iri(iri) => Some(iri.as_str().to_string()),
+        Object::BlankNode(_) | Object::Triple { .. } => None,
+    }
+}
+
+/// Evaluates [node expressions](https://www.w3.org/TR/shacl-af/#node-expressions) for a focus node
+pub(crate) struct NodeExprEvaluator<'a, S: SRDF> {
+    context: &'a ValidationContext<'a, S>,
+    functions: &'a HashMap<IriS, NodeFunction>,
+}
+
+impl<'a, S: SRDF + 'static> NodeExprEvaluator<'a, S> {
+    pub fn new(
+        context: &'a ValidationContext<'a, S>,
+        functions: &'a HashMap<IriS, NodeFunction>,
+    ) -> Self {
+        NodeExprEvaluator { context, functions }
+    }
+
+    pub fn eval(&self, expr: &NodeExpr, focus: &S::Term) -> Result<HashSet<S::Term>, RuleError> {
+        match expr {
+            NodeExpr::FocusNode => Ok(HashSet::from([focus.clone()])),
+            NodeExpr::Constant(node) => Ok(HashSet::from([S::object_as_term(node)])),
+            NodeExpr::Path { path, nodes } => {
+                let nodes = match nodes {
+                    Some(nodes) => self.eval(nodes, focus)?,
+                    None => HashSet::from([focus.clone()]),
+                };
+                let mut values = HashSet::new();
+                for node in nodes {
+                    values.extend(
+                        self.context
+                            .store()
+                            .objects_for_shacl_path(&node, path)
+                            .map_err(|err| RuleError::Srdf {
+                                err: err.to_string(),
+                            })?,
+                    )
+                }
+                Ok(values)
+            }
+            NodeExpr::Filter { shape, nodes } => {
+                let Some(shape_ref) = get_shape_ref(shape, self.context.schema()) else {
+                    return Err(RuleError::ShapeNotFound {
+                        shape: shape.clone(),
+                    });
+                };
+                let mut values = HashSet::new();
+                for node in self.eval(nodes, focus)? {
+                    if self.conforms(shape_ref, shape, &node)? {
+                        values.insert(node);
+                    }
+                }
+                Ok(values)
+            }
+            NodeExpr::Intersection { exprs } => {
+                let mut values: Option<HashSet<S::Term>> = None;
+                for expr in exprs {
+                    let other = self.eval(expr, focus)?;
+                    values = Some(match values {
+                        Some(values) => values.intersection(&other).cloned().collect(),
+                        None => other,
+                    })
+                }
+                Ok(values.unwrap_or_default())
+            }
+            NodeExpr::Union { exprs } => {
+                let mut values = HashSet::new();
+                for expr in exprs {
+                    values.extend(self.eval(expr, focus)?)
+                }
+                Ok(values)
+            }
+            NodeExpr::Function { function, args } => {
+                // unknown functions don't produce any value
+                let Some(implementation) = self.functions.get(function) else {
+                    return Ok(HashSet::new());
+                };
+                let mut combinations = vec![Vec::new()];
+                for arg in args {
+                    let values = self.eval(arg, focus)?;
+                    combinations = combinations
+                        .into_iter()
+                        .flat_map(|combination: Vec<Object>| {
+                            values.iter().map(move |value| {
+                                let mut combination = combination.clone();
+                                combination.push(S::term_as_object(value));
+                                combination
+                            })
+                        })
+                        .collect()
+                }
+                Ok(combinations
+                    .iter()
+                    .filter_map(|combination| implementation(combination))
+                    .map(|value| S::object_as_term(&value))
+                    .collect())
+            }
+        }
+    }
+
+    /// Checks whether a node conforms to a shape
+    pub fn conforms(
+        &self,
+        shape: &shacl_ast::shape::Shape,
+        id: &srdf::RDFNode,
+        node: &S::Term,
+    ) -> Result<bool, RuleError> {
+        let focus = Targets::new(std::iter::once(node.clone()));
+        let results = ShapeValidator::new(shape, self.context, Some(&focus))
+            .validate()
+            .map_err(|err| RuleError::Validation {
+                shape: id.clone(),
+                err: Box::new(err),
+            })?;
+        Ok(results.is_empty())
+    }
+}
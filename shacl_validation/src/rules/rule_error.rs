@@ -0,0 +1,39 @@
+use srdf::{RDFNode, SparqlEvalError};
+use thiserror::Error;
+
+use crate::validate_error::ValidateError;
+
+#[derive(Error, Debug)]
+pub enum RuleError {
+    #[error("Error accessing the RDF data: {err}")]
+    Srdf { err: String },
+
+    #[error("Shape {shape} not found in the shapes graph")]
+    ShapeNotFound { shape: RDFNode },
+
+    #[error("Error validating the nodes of shape {shape}: {err}")]
+    Validation {
+        shape: RDFNode,
+        err: Box<ValidateError>,
+    },
+
+    #[error("Error parsing the SPARQL query of rule {rule}: {err}")]
+    SparqlSyntax { rule: RDFNode, err: String },
+
+    #[error("The query of SPARQL rule {rule} is not a CONSTRUCT query")]
+    NotConstruct { rule: RDFNode },
+
+    #[error("SPARQL feature not supported in rules: {feature}")]
+    UnsupportedSparql { feature: String },
+
+    #[error(transparent)]
+    SparqlEval {
+        #[from]
+        err: SparqlEvalError,
+    },
+
+    #[error(
+        "No fixpoint reached after {iterations} iterations, some rule may keep creating new nodes"
+    )]
+    NoFixpoint { iterations: usize },
+}
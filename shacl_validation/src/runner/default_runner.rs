@@ -1,15 +1,12 @@
 use shacl_ast::property_shape::PropertyShape;
 use srdf::SHACLPath;
 use srdf::RDFS_CLASS;
-use srdf::RDFS_SUBCLASS_OF;
-use srdf::RDF_TYPE;
 use srdf::SRDF;
 
 use crate::constraints::DefaultConstraintComponent;
 use crate::context::EvaluationContext;
 use crate::context::ValidationContext;
 use crate::helper::srdf::get_objects_for;
-use crate::helper::srdf::get_subjects_for;
 use crate::validate_error::ValidateError;
 use crate::validation_report::result::ValidationResults;
 use crate::Targets;
@@ -33,7 +30,11 @@ impl<S: SRDF + 'static> ValidatorRunner<S> for DefaultValidatorRunner {
 
     /// If s is a shape in a shapes graph SG and s has value t for sh:targetNode
     /// in SG then { t } is a target from any data graph for s in SG.
-    fn target_node(&self, _store: &S, node: &S::Term) -> Result<Targets<S>, ValidateError> {
+    fn target_node(
+        &self,
+        _validation_context: &ValidationContext<S>,
+        node: &S::Term,
+    ) -> Result<Targets<S>, ValidateError> {
         if S::term_is_bnode(node) {
             Err(ValidateError::TargetNodeBlankNode)
         } else {
@@ -41,27 +42,28 @@ impl<S: SRDF + 'static> ValidatorRunner<S> for DefaultValidatorRunner {
         }
     }
 
-    fn target_class(&self, store: &S, class: &S::Term) -> Result<Targets<S>, ValidateError> {
+    fn target_class(
+        &self,
+        validation_context: &ValidationContext<S>,
+        class: &S::Term,
+    ) -> Result<Targets<S>, ValidateError> {
         if !S::term_is_iri(class) {
             return Err(ValidateError::TargetClassNotIri);
         }
 
-        let subjects = match store.subjects_with_predicate_object(&S::iri_s2iri(&RDF_TYPE), class) {
-            Ok(subjects) => subjects,
-            Err(_) => return Err(ValidateError::SRDF),
-        };
-
-        let targets = subjects.iter().map(|subject| S::subject_as_term(subject));
+        let instances = validation_context
+            .subclasses()
+            .instances(validation_context.store(), class)?;
 
-        Ok(Targets::new(targets))
+        Ok(Targets::new(instances.into_iter()))
     }
 
     fn target_subject_of(
         &self,
-        store: &S,
+        validation_context: &ValidationContext<S>,
         predicate: &S::IRI,
     ) -> Result<Targets<S>, ValidateError> {
-        let triples = match store.triples_with_predicate(predicate) {
+        let triples = match validation_context.store().triples_with_predicate(predicate) {
             Ok(triples) => triples,
             Err(_) => return Err(ValidateError::SRDF),
         };
@@ -73,8 +75,12 @@ impl<S: SRDF + 'static> ValidatorRunner<S> for DefaultValidatorRunner {
         Ok(Targets::new(targets))
     }
 
-    fn target_object_of(&self, store: &S, predicate: &S::IRI) -> Result<Targets<S>, ValidateError> {
-        let triples = match store.triples_with_predicate(predicate) {
+    fn target_object_of(
+        &self,
+        validation_context: &ValidationContext<S>,
+        predicate: &S::IRI,
+    ) -> Result<Targets<S>, ValidateError> {
+        let triples = match validation_context.store().triples_with_predicate(predicate) {
             Ok(triples) => triples,
             Err(_) => return Err(ValidateError::SRDF),
         };
@@ -86,33 +92,15 @@ impl<S: SRDF + 'static> ValidatorRunner<S> for DefaultValidatorRunner {
 
     fn implicit_target_class(
         &self,
-        store: &S,
+        validation_context: &ValidationContext<S>,
         shape: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        let ctypes = get_objects_for(store, shape, &S::iri_s2iri(&RDF_TYPE))?;
-
-        let mut subclasses = get_subjects_for(
-            store,
-            &S::iri_s2iri(&RDFS_SUBCLASS_OF),
-            &S::iri_s2term(&RDFS_CLASS),
-        )?;
-
-        subclasses.insert(S::iri_s2term(&RDFS_CLASS));
-
-        if ctypes.iter().any(|t| subclasses.contains(t)) {
-            let actual_class_nodes = get_subjects_for(store, &S::iri_s2iri(&RDF_TYPE), shape)?;
-
-            let subclass_targets =
-                get_subjects_for(store, &S::iri_s2iri(&RDFS_SUBCLASS_OF), shape)?
-                    .into_iter()
-                    .flat_map(move |subclass| {
-                        get_subjects_for(store, &S::iri_s2iri(&RDF_TYPE), &subclass)
-                            .into_iter()
-                            .flatten()
-                    });
-
-            let targets = actual_class_nodes.into_iter().chain(subclass_targets);
-            Ok(Targets::new(targets))
+        let store = validation_context.store();
+        let subclasses = validation_context.subclasses();
+
+        if subclasses.is_instance_of(store, shape, &S::iri_s2term(&RDFS_CLASS))? {
+            let instances = subclasses.instances(store, shape)?;
+            Ok(Targets::new(instances.into_iter()))
         } else {
             Ok(Targets::default())
         }
@@ -132,61 +120,90 @@ impl<S: SRDF + 'static> ValidatorRunner<S> for DefaultValidatorRunner {
 
     fn alternative(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _paths: &[SHACLPath],
-        _focus_node: &S::Term,
+        paths: &[SHACLPath],
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Alternative {
+            paths: paths.to_vec(),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn sequence(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _paths: &[SHACLPath],
-        _focus_node: &S::Term,
+        paths: &[SHACLPath],
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Sequence {
+            paths: paths.to_vec(),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn inverse(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Inverse {
+            path: Box::new(path.clone()),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn zero_or_more(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::ZeroOrMore {
+            path: Box::new(path.clone()),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn one_or_more(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::OneOrMore {
+            path: Box::new(path.clone()),
+        };
+        path_values(store, &path, focus_node)
     }
 
     fn zero_or_one(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::ZeroOrOne {
+            path: Box::new(path.clone()),
+        };
+        path_values(store, &path, focus_node)
+    }
+}
+
+fn path_values<S: SRDF>(
+    store: &S,
+    path: &SHACLPath,
+    focus_node: &S::Term,
+) -> Result<Targets<S>, ValidateError> {
+    match store.objects_for_shacl_path(focus_node, path) {
+        Ok(values) => Ok(Targets::new(values.into_iter())),
+        Err(_) => Err(ValidateError::SRDF),
     }
 }
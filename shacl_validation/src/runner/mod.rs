@@ -23,7 +23,7 @@ pub trait ValidatorRunner<S: SRDFBasic> {
 
     fn focus_nodes(
         &self,
-        store: &S,
+        validation_context: &ValidationContext<S>,
         shape: &S::Term,
         targets: &[Target],
     ) -> Result<Targets<S>, ValidateError> {
@@ -31,13 +31,13 @@ pub trait ValidatorRunner<S: SRDFBasic> {
             .iter()
             .filter_map(move |target| match target {
                 Target::TargetNode(node) => {
-                    match self.target_node(store, &S::object_as_term(node)) {
+                    match self.target_node(validation_context, &S::object_as_term(node)) {
                         Ok(target_node) => Some(target_node),
                         Err(_) => None,
                     }
                 }
                 Target::TargetClass(class) => {
-                    match self.target_class(store, &S::object_as_term(class)) {
+                    match self.target_class(validation_context, &S::object_as_term(class)) {
                         Ok(target_node) => Some(target_node),
                         Err(_) => None,
                     }
@@ -47,7 +47,7 @@ pub trait ValidatorRunner<S: SRDFBasic> {
                         Ok(predicate) => S::iri_s2iri(&predicate),
                         Err(_) => return None,
                     };
-                    match self.target_subject_of(store, &predicate) {
+                    match self.target_subject_of(validation_context, &predicate) {
                         Ok(target_subject_of) => Some(target_subject_of),
                         Err(_) => None,
                     }
@@ -57,7 +57,7 @@ pub trait ValidatorRunner<S: SRDFBasic> {
                         Ok(predicate) => S::iri_s2iri(&predicate),
                         Err(_) => return None,
                     };
-                    match self.target_object_of(store, &predicate) {
+                    match self.target_object_of(validation_context, &predicate) {
                         Ok(target_node) => Some(target_node),
                         Err(_) => None,
                     }
@@ -67,25 +67,42 @@ pub trait ValidatorRunner<S: SRDFBasic> {
 
         // we have to also look for implicit class targets, which are a "special"
         // kind of target declarations...
-        let implicit = self.implicit_target_class(store, shape)?;
+        let implicit = self.implicit_target_class(validation_context, shape)?;
 
         Ok(Targets::new(implicit.into_iter().chain(explicit)))
     }
 
     /// If s is a shape in a shapes graph SG and s has value t for sh:targetNode
     /// in SG then { t } is a target from any data graph for s in SG.
-    fn target_node(&self, store: &S, node: &S::Term) -> Result<Targets<S>, ValidateError>;
+    fn target_node(
+        &self,
+        validation_context: &ValidationContext<S>,
+        node: &S::Term,
+    ) -> Result<Targets<S>, ValidateError>;
 
-    fn target_class(&self, store: &S, class: &S::Term) -> Result<Targets<S>, ValidateError>;
+    /// The targets are the SHACL instances of the class, following `rdfs:subClassOf` transitively
+    fn target_class(
+        &self,
+        validation_context: &ValidationContext<S>,
+        class: &S::Term,
+    ) -> Result<Targets<S>, ValidateError>;
 
-    fn target_subject_of(&self, store: &S, predicate: &S::IRI)
-        -> Result<Targets<S>, ValidateError>;
+    fn target_subject_of(
+        &self,
+        validation_context: &ValidationContext<S>,
+        predicate: &S::IRI,
+    ) -> Result<Targets<S>, ValidateError>;
 
-    fn target_object_of(&self, store: &S, predicate: &S::IRI) -> Result<Targets<S>, ValidateError>;
+    fn target_object_of(
+        &self,
+        validation_context: &ValidationContext<S>,
+        predicate: &S::IRI,
+    ) -> Result<Targets<S>, ValidateError>;
 
+    /// A shape which is also a class has as targets its SHACL instances
     fn implicit_target_class(
         &self,
-        store: &S,
+        validation_context: &ValidationContext<S>,
         shape: &S::Term,
     ) -> Result<Targets<S>, ValidateError>;
 
@@ -30,7 +30,11 @@ impl<S: QuerySRDF + 'static> ValidatorRunner<S> for QueryValidatorRunner {
 
     /// If s is a shape in a shapes graph SG and s has value t for sh:targetNode
     /// in SG then { t } is a target from any data graph for s in SG.
-    fn target_node(&self, store: &S, node: &S::Term) -> Result<Targets<S>, ValidateError> {
+    fn target_node(
+        &self,
+        validation_context: &ValidationContext<S>,
+        node: &S::Term,
+    ) -> Result<Targets<S>, ValidateError> {
         if S::term_is_bnode(node) {
             return Err(ValidateError::TargetNodeBlankNode);
         }
@@ -42,12 +46,16 @@ impl<S: QuerySRDF + 'static> ValidatorRunner<S> for QueryValidatorRunner {
             }}
         ", node};
 
-        select(store, query, "this")?;
+        let nodes = select(validation_context.store(), query, "this")?;
 
-        Err(ValidateError::NotImplemented)
+        Ok(Targets::new(nodes.into_iter()))
     }
 
-    fn target_class(&self, store: &S, class: &S::Term) -> Result<Targets<S>, ValidateError> {
+    fn target_class(
+        &self,
+        validation_context: &ValidationContext<S>,
+        class: &S::Term,
+    ) -> Result<Targets<S>, ValidateError> {
         if !S::term_is_iri(class) {
             return Err(ValidateError::TargetClassNotIri);
         }
@@ -62,14 +70,14 @@ impl<S: QuerySRDF + 'static> ValidatorRunner<S> for QueryValidatorRunner {
             }}
         ", class};
 
-        select(store, query, "this")?;
+        let instances = select(validation_context.store(), query, "this")?;
 
-        Err(ValidateError::NotImplemented)
+        Ok(Targets::new(instances.into_iter()))
     }
 
     fn target_subject_of(
         &self,
-        store: &S,
+        validation_context: &ValidationContext<S>,
         predicate: &S::IRI,
     ) -> Result<Targets<S>, ValidateError> {
         let query = formatdoc! {"
@@ -79,12 +87,16 @@ impl<S: QuerySRDF + 'static> ValidatorRunner<S> for QueryValidatorRunner {
             }}
         ", predicate};
 
-        select(store, query, "this")?;
+        let nodes = select(validation_context.store(), query, "this")?;
 
-        Err(ValidateError::NotImplemented)
+        Ok(Targets::new(nodes.into_iter()))
     }
 
-    fn target_object_of(&self, store: &S, predicate: &S::IRI) -> Result<Targets<S>, ValidateError> {
+    fn target_object_of(
+        &self,
+        validation_context: &ValidationContext<S>,
+        predicate: &S::IRI,
+    ) -> Result<Targets<S>, ValidateError> {
         let query = formatdoc! {"
             SELECT DISTINCT ?this
             WHERE {{
@@ -92,86 +104,135 @@ impl<S: QuerySRDF + 'static> ValidatorRunner<S> for QueryValidatorRunner {
             }}
         ", predicate};
 
-        select(store, query, "this")?;
+        let nodes = select(validation_context.store(), query, "this")?;
 
-        Err(ValidateError::NotImplemented)
+        Ok(Targets::new(nodes.into_iter()))
     }
 
     fn implicit_target_class(
         &self,
-        _store: &S,
-        _shape: &S::Term,
+        validation_context: &ValidationContext<S>,
+        shape: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let query = formatdoc! {"
+            PREFIX rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>
+            PREFIX rdfs: <http://www.w3.org/2000/01/rdf-schema#>
+
+            SELECT DISTINCT ?this
+            WHERE {{
+                {shape} rdf:type/rdfs:subClassOf* rdfs:Class .
+                ?this rdf:type/rdfs:subClassOf* {shape} .
+            }}
+        "};
+
+        let instances = select(validation_context.store(), query, "this")?;
+
+        Ok(Targets::new(instances.into_iter()))
     }
 
     fn predicate(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _predicate: &S::IRI,
-        _focus_node: &S::Term,
+        predicate: &S::IRI,
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        path_values(store, &predicate.to_string(), focus_node)
     }
 
     fn alternative(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _paths: &[SHACLPath],
-        _focus_node: &S::Term,
+        paths: &[SHACLPath],
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Alternative {
+            paths: paths.to_vec(),
+        };
+        path_values(store, &path.to_string(), focus_node)
     }
 
     fn sequence(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _paths: &[SHACLPath],
-        _focus_node: &S::Term,
+        paths: &[SHACLPath],
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Sequence {
+            paths: paths.to_vec(),
+        };
+        path_values(store, &path.to_string(), focus_node)
     }
 
     fn inverse(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::Inverse {
+            path: Box::new(path.clone()),
+        };
+        path_values(store, &path.to_string(), focus_node)
     }
 
     fn zero_or_more(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::ZeroOrMore {
+            path: Box::new(path.clone()),
+        };
+        path_values(store, &path.to_string(), focus_node)
     }
 
     fn one_or_more(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::OneOrMore {
+            path: Box::new(path.clone()),
+        };
+        path_values(store, &path.to_string(), focus_node)
     }
 
     fn zero_or_one(
         &self,
-        _store: &S,
+        store: &S,
         _shape: &PropertyShape,
-        _path: &SHACLPath,
-        _focus_node: &S::Term,
+        path: &SHACLPath,
+        focus_node: &S::Term,
     ) -> Result<Targets<S>, ValidateError> {
-        Err(ValidateError::NotImplemented)
+        let path = SHACLPath::ZeroOrOne {
+            path: Box::new(path.clone()),
+        };
+        path_values(store, &path.to_string(), focus_node)
     }
 }
+
+/// Values of a path, written as a SPARQL property path, for the focus node
+fn path_values<S: QuerySRDF>(
+    store: &S,
+    path: &str,
+    focus_node: &S::Term,
+) -> Result<Targets<S>, ValidateError> {
+    let query = formatdoc! {"
+        SELECT DISTINCT ?value
+        WHERE {{
+            {} {} ?value .
+        }}
+    ", focus_node, path};
+
+    let values = select(store, query, "value")?;
+
+    Ok(Targets::new(values.into_iter()))
+}
@@ -128,7 +128,7 @@ impl<S: SRDFBasic> FocusNodesOps<S> for Shape {
         validation_context
             .runner()
             .focus_nodes(
-                validation_context.store(),
+                validation_context,
                 &S::object_as_term(self.id()),
                 self.targets(),
             )
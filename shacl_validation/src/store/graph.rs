@@ -1,8 +1,13 @@
+use std::collections::HashSet;
 use std::{path::Path, str::FromStr};
 
 use oxiri::Iri;
-use srdf::{RDFFormat, ReaderMode, SRDFGraph};
+use srdf::{
+    RDFFormat, ReaderMode, SRDFBasic, SRDFBuilder, SRDFGraph, Triple, RDFS_DOMAIN, RDFS_RANGE,
+    RDF_TYPE, SRDF,
+};
 
+use crate::helper::subclass::SubClassClosure;
 use crate::validate_error::ValidateError;
 
 use super::Store;
@@ -34,6 +39,61 @@ impl Graph {
         };
         Ok(Self { store })
     }
+
+    pub fn from_graph(store: SRDFGraph) -> Self {
+        Self { store }
+    }
+
+    /// Adds the `rdf:type` triples entailed by the RDFS rules `rdfs2`, `rdfs3` and `rdfs9` from the
+    /// [RDF semantics](https://www.w3.org/TR/rdf11-mt/#patterns-of-rdfs-entailment-informative),
+    /// i.e. the types of the `rdfs:domain` and `rdfs:range` declarations and the superclasses of every type
+    pub(crate) fn add_rdfs_types(&mut self) -> Result<(), ValidateError> {
+        let rdf_type = SRDFGraph::iri_s2iri(&RDF_TYPE);
+        let asserted: HashSet<_> = self
+            .triples_with(&rdf_type)?
+            .into_iter()
+            .map(|triple| (SRDFGraph::subject_as_term(&triple.subj()), triple.obj()))
+            .collect();
+        let mut types = asserted.clone();
+        for (declaration, range) in [(&*RDFS_DOMAIN, false), (&*RDFS_RANGE, true)] {
+            for triple in self.triples_with(&SRDFGraph::iri_s2iri(declaration))? {
+                let Some(property) = SRDFGraph::subject_as_iri(&triple.subj()) else {
+                    continue;
+                };
+                for typed in self.triples_with(&property)? {
+                    let node = if range {
+                        typed.obj()
+                    } else {
+                        SRDFGraph::subject_as_term(&typed.subj())
+                    };
+                    if !SRDFGraph::term_is_literal(&node) {
+                        types.insert((node, triple.obj()));
+                    }
+                }
+            }
+        }
+
+        let subclasses = SubClassClosure::default();
+        let mut entailed = HashSet::new();
+        for (node, class) in types.iter() {
+            for superclass in subclasses.superclasses(&self.store, class)? {
+                entailed.insert((node.clone(), superclass));
+            }
+        }
+        for (node, class) in entailed.difference(&asserted) {
+            if let Some(subject) = SRDFGraph::term_as_subject(node) {
+                self.store.add_triple(&subject, &rdf_type, class)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn triples_with(
+        &self,
+        predicate: &<SRDFGraph as SRDFBasic>::IRI,
+    ) -> Result<Vec<Triple<SRDFGraph>>, ValidateError> {
+        Ok(self.store.triples_with_predicate(predicate)?)
+    }
 }
 
 impl Store<SRDFGraph> for Graph {
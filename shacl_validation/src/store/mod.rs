@@ -2,6 +2,8 @@ use std::io::BufRead;
 use std::str::FromStr;
 
 use oxiri::Iri;
+use shacl_ast::resolve_imports;
+use shacl_ast::ImportResolver;
 use shacl_ast::Schema;
 use shacl_ast::ShaclParser;
 use srdf::RDFFormat;
@@ -11,6 +13,7 @@ use srdf::SRDFGraph;
 use crate::validate_error::ValidateError;
 
 pub mod graph;
+pub mod persistent;
 pub mod sparql;
 
 pub trait Store<S> {
@@ -25,7 +28,28 @@ impl ShaclDataManager {
         rdf_format: RDFFormat,
         base: Option<&str>,
     ) -> Result<Schema, ValidateError> {
-        let rdf = SRDFGraph::from_reader(
+        let rdf = Self::read_graph(reader, rdf_format, base)?;
+        Self::parse(rdf)
+    }
+
+    /// Loads the shapes graph together with the graphs that it imports with `owl:imports`
+    pub fn load_with_imports<R: BufRead>(
+        reader: R,
+        rdf_format: RDFFormat,
+        base: Option<&str>,
+        resolver: &dyn ImportResolver,
+    ) -> Result<Schema, ValidateError> {
+        let mut rdf = Self::read_graph(reader, rdf_format, base)?;
+        resolve_imports(&mut rdf, resolver)?;
+        Self::parse(rdf)
+    }
+
+    fn read_graph<R: BufRead>(
+        reader: R,
+        rdf_format: RDFFormat,
+        base: Option<&str>,
+    ) -> Result<SRDFGraph, ValidateError> {
+        Ok(SRDFGraph::from_reader(
             reader,
             &rdf_format,
             match base {
@@ -33,8 +57,11 @@ impl ShaclDataManager {
                 None => None,
             },
             &ReaderMode::default(),
-        )?;
+        )?)
+    }
 
+    /// Parses the shapes of a graph that has already been loaded
+    pub fn parse(rdf: SRDFGraph) -> Result<Schema, ValidateError> {
         match ShaclParser::new(rdf).parse() {
             Ok(schema) => Ok(schema),
             Err(_) => Err(ValidateError::GraphCreation),
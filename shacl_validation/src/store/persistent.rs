@@ -0,0 +1,31 @@
+use std::path::Path;
+
+use srdf::SRDFStore;
+
+use crate::validate_error::ValidateError;
+
+use super::Store;
+
+pub struct Persistent {
+    store: SRDFStore,
+}
+
+impl Persistent {
+    pub fn new(path: &Path) -> Result<Self, ValidateError> {
+        let store = match SRDFStore::open(path) {
+            Ok(rdf) => rdf,
+            Err(_) => return Err(ValidateError::StoreCreation),
+        };
+        Ok(Self { store })
+    }
+
+    pub fn from_store(store: SRDFStore) -> Self {
+        Self { store }
+    }
+}
+
+impl Store<SRDFStore> for Persistent {
+    fn store(&self) -> &SRDFStore {
+        &self.store
+    }
+}
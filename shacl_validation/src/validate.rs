@@ -2,14 +2,17 @@ use std::path::Path;
 
 use clap::ValueEnum;
 use shacl_ast::Schema;
+use srdf::NodeChecks;
 use srdf::RDFFormat;
 use srdf::SRDFBasic;
 use srdf::SRDFGraph;
 use srdf::SRDFSparql;
+use srdf::SRDFStore;
 
 use crate::context::ValidationContext;
 use crate::shape::ShapeValidator;
 use crate::store::graph::Graph;
+use crate::store::persistent::Persistent;
 use crate::store::sparql::Sparql;
 use crate::validate_error::ValidateError;
 use crate::validation_report::report::ValidationReport;
@@ -20,11 +23,29 @@ pub enum ShaclValidationMode {
     SPARQL,
 }
 
+/// Entailment regime applied to the data graph before validating it
+#[derive(ValueEnum, Copy, Clone, Debug, PartialEq, Default)]
+pub enum Entailment {
+    /// The data graph is validated as it is
+    #[default]
+    None,
+
+    /// The `rdf:type` triples entailed by `rdfs:subClassOf`, `rdfs:domain` and `rdfs:range` are added to the data graph
+    RDFS,
+}
+
+/// Registry of the constraint components implemented in Rust, keyed by the IRI of the component
+///
+/// The components are declared in the shapes graph with `sh:parameter`, and their checks receive
+/// the values of the parameters in each shape.
+/// It is the same registry that the ShEx validator uses for external shapes.
+pub type ConstraintComponentRegistry = NodeChecks;
+
 pub trait Validator<S: SRDFBasic> {
     fn validation_context<'a>(&'a self, schema: &'a Schema) -> ValidationContext<'a, S>;
 
-    fn validate(&self, schema: Schema) -> Result<ValidationReport<S>, ValidateError> {
-        let validation_context = self.validation_context(&schema);
+    fn validate(&self, schema: &Schema) -> Result<ValidationReport<S>, ValidateError> {
+        let validation_context = self.validation_context(schema);
         let mut report = ValidationReport::default();
 
         for (_, shape) in schema.iter() {
@@ -47,6 +68,7 @@ pub trait Validator<S: SRDFBasic> {
 pub struct GraphValidator {
     store: Graph,
     mode: ShaclValidationMode,
+    components: ConstraintComponentRegistry,
 }
 
 impl GraphValidator {
@@ -56,29 +78,90 @@ impl GraphValidator {
         base: Option<&str>,
         mode: ShaclValidationMode,
     ) -> Result<Self, ValidateError> {
-        if mode == ShaclValidationMode::SPARQL {
-            return Err(ValidateError::UnsupportedMode);
-        }
-
         Ok(GraphValidator {
             store: Graph::new(data, data_format, base)?,
             mode,
+            components: ConstraintComponentRegistry::default(),
         })
     }
+
+    /// Validator of RDF data that has already been parsed
+    pub fn from_graph(graph: SRDFGraph, mode: ShaclValidationMode) -> Result<Self, ValidateError> {
+        Ok(GraphValidator {
+            store: Graph::from_graph(graph),
+            mode,
+            components: ConstraintComponentRegistry::default(),
+        })
+    }
+
+    /// Applies an entailment regime to the data graph
+    pub fn with_entailment(mut self, entailment: Entailment) -> Result<Self, ValidateError> {
+        match entailment {
+            Entailment::None => {}
+            Entailment::RDFS => self.store.add_rdfs_types()?,
+        }
+        Ok(self)
+    }
+
+    /// Constraint components implemented in Rust that can be used by the shapes
+    pub fn with_components(mut self, components: ConstraintComponentRegistry) -> Self {
+        self.components = components;
+        self
+    }
 }
 
 impl Validator<SRDFGraph> for GraphValidator {
     fn validation_context<'a>(&'a self, schema: &'a Schema) -> ValidationContext<'a, SRDFGraph> {
         match self.mode {
             ShaclValidationMode::Default => ValidationContext::new_default(&self.store, schema),
-            ShaclValidationMode::SPARQL => todo!(),
+            ShaclValidationMode::SPARQL => ValidationContext::new_sparql(&self.store, schema),
+        }
+        .with_components(&self.components)
+    }
+}
+
+/// Validator of the RDF data of a persistent store, which is not loaded in memory
+pub struct PersistentValidator {
+    store: Persistent,
+    mode: ShaclValidationMode,
+    components: ConstraintComponentRegistry,
+}
+
+impl PersistentValidator {
+    pub fn new(path: &Path, mode: ShaclValidationMode) -> Result<Self, ValidateError> {
+        Self::from_store(Persistent::new(path)?, mode)
+    }
+
+    /// Validator of a store that has already been opened
+    pub fn from_store(store: Persistent, mode: ShaclValidationMode) -> Result<Self, ValidateError> {
+        Ok(PersistentValidator {
+            store,
+            mode,
+            components: ConstraintComponentRegistry::default(),
+        })
+    }
+
+    /// Constraint components implemented in Rust that can be used by the shapes
+    pub fn with_components(mut self, components: ConstraintComponentRegistry) -> Self {
+        self.components = components;
+        self
+    }
+}
+
+impl Validator<SRDFStore> for PersistentValidator {
+    fn validation_context<'a>(&'a self, schema: &'a Schema) -> ValidationContext<'a, SRDFStore> {
+        match self.mode {
+            ShaclValidationMode::Default => ValidationContext::new_default(&self.store, schema),
+            ShaclValidationMode::SPARQL => ValidationContext::new_sparql(&self.store, schema),
         }
+        .with_components(&self.components)
     }
 }
 
 pub struct SparqlValidator {
     store: Sparql,
     mode: ShaclValidationMode,
+    components: ConstraintComponentRegistry,
 }
 
 impl SparqlValidator {
@@ -86,8 +169,15 @@ impl SparqlValidator {
         Ok(SparqlValidator {
             store: Sparql::new(data)?,
             mode,
+            components: ConstraintComponentRegistry::default(),
         })
     }
+
+    /// Constraint components implemented in Rust that can be used by the shapes
+    pub fn with_components(mut self, components: ConstraintComponentRegistry) -> Self {
+        self.components = components;
+        self
+    }
 }
 
 impl Validator<SRDFSparql> for SparqlValidator {
@@ -96,5 +186,6 @@ impl Validator<SRDFSparql> for SparqlValidator {
             ShaclValidationMode::Default => ValidationContext::new_default(&self.store, schema),
             ShaclValidationMode::SPARQL => ValidationContext::new_sparql(&self.store, schema),
         }
+        .with_components(&self.components)
     }
 }
@@ -1,5 +1,6 @@
 use oxiri::IriParseError;
 use prefixmap::Underef;
+use shacl_ast::ImportError;
 use srdf::SRDFGraphError;
 use thiserror::Error;
 
@@ -28,10 +29,14 @@ pub enum ValidateError {
     IO(#[from] std::io::Error),
     #[error("Error loading the Shapes")]
     Shapes(#[from] SRDFError),
+    #[error("Error resolving the imported graphs: {0}")]
+    Imports(#[from] ImportError),
     #[error("Error creating the SPARQL endpoint")]
     SPARQLCreation,
     #[error("Error creating the Graph in-memory")]
     GraphCreation,
+    #[error("Error opening the persistent store")]
+    StoreCreation,
     #[error("Error obtaining the underlying IRI")]
     Underef(#[from] Underef),
     #[error("The provided mode is not supported for the data structure")]
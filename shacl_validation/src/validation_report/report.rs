@@ -15,6 +15,14 @@ pub struct ValidationReport<S: SRDFBasic> {
 }
 
 impl<S: SRDFBasic> ValidationReport<S> {
+    pub fn conforms(&self) -> bool {
+        self.conforms
+    }
+
+    pub fn results(&self) -> &Vec<ValidationResult<S>> {
+        &self.results
+    }
+
     pub(crate) fn add_result(&mut self, result: ValidationResult<S>) {
         if self.conforms {
             self.conforms = false; // we add a result --> make the Report non-conformant
@@ -88,6 +96,9 @@ impl<S: SRDFBasic> fmt::Display for ValidationReport<S> {
             if let Some(term) = &result.value() {
                 writeln!(f, "\t\t\tvalue: {},", term)?;
             }
+            if let Some(term) = &result.result_message() {
+                writeln!(f, "\t\t\tresult_message: {},", term)?;
+            }
             writeln!(f, "\t\t],")?;
         }
         writeln!(f, "]")
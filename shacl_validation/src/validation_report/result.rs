@@ -45,6 +45,7 @@ pub struct ValidationResultBuilder<S: SRDFBasic> {
     source_constraint_component: Option<S::Term>,
     source_shape: Option<S::Term>,
     value: Option<S::Term>,
+    result_message: Option<S::Term>,
 }
 
 impl<S: SRDFBasic> ValidationResultBuilder<S> {
@@ -72,6 +73,10 @@ impl<S: SRDFBasic> ValidationResultBuilder<S> {
         self.value = Some(value);
     }
 
+    pub fn result_message(&mut self, result_message: S::Term) {
+        self.result_message = Some(result_message);
+    }
+
     pub fn build(self) -> ValidationResult<S> {
         ValidationResult {
             focus_node: self.focus_node,
@@ -80,6 +85,7 @@ impl<S: SRDFBasic> ValidationResultBuilder<S> {
             source_constraint_component: self.source_constraint_component,
             source_shape: self.source_shape,
             value: self.value,
+            result_message: self.result_message,
         }
     }
 }
@@ -93,6 +99,7 @@ impl<S: SRDFBasic> Default for ValidationResultBuilder<S> {
             source_constraint_component: None,
             source_shape: None,
             value: None,
+            result_message: None,
         }
     }
 }
@@ -105,6 +112,7 @@ pub struct ValidationResult<S: SRDFBasic> {
     source_constraint_component: Option<S::Term>,
     source_shape: Option<S::Term>,
     value: Option<S::Term>,
+    result_message: Option<S::Term>,
 }
 
 impl<S: SRDFBasic> ValidationResult<S> {
@@ -129,29 +137,38 @@ impl<S: SRDFBasic> ValidationResult<S> {
         builder.build()
     }
 
-    pub(crate) fn focus_node(&self) -> Option<S::Term> {
+    pub(crate) fn with_result_message(mut self, result_message: S::Term) -> Self {
+        self.result_message = Some(result_message);
+        self
+    }
+
+    pub fn focus_node(&self) -> Option<S::Term> {
         self.focus_node.to_owned()
     }
 
-    pub(crate) fn result_severity(&self) -> Option<S::Term> {
+    pub fn result_severity(&self) -> Option<S::Term> {
         self.result_severity.to_owned()
     }
 
-    pub(crate) fn result_path(&self) -> Option<S::Term> {
+    pub fn result_path(&self) -> Option<S::Term> {
         self.result_path.to_owned()
     }
 
-    pub(crate) fn source_constraint_component(&self) -> Option<S::Term> {
+    pub fn source_constraint_component(&self) -> Option<S::Term> {
         self.source_constraint_component.to_owned()
     }
 
-    pub(crate) fn source_shape(&self) -> Option<S::Term> {
+    pub fn source_shape(&self) -> Option<S::Term> {
         self.source_shape.to_owned()
     }
 
-    pub(crate) fn value(&self) -> Option<S::Term> {
+    pub fn value(&self) -> Option<S::Term> {
         self.value.to_owned()
     }
+
+    pub fn result_message(&self) -> Option<S::Term> {
+        self.result_message.to_owned()
+    }
 }
 
 impl<S: SRDF> ValidationResult<S> {
@@ -180,6 +197,9 @@ impl<S: SRDF> ValidationResult<S> {
         if let Some(term) = get_object_for(store, subject, &S::iri_s2iri(&SH_VALUE))? {
             builder.value(term)
         };
+        if let Some(term) = get_object_for(store, subject, &S::iri_s2iri(&SH_RESULT_MESSAGE))? {
+            builder.result_message(term)
+        };
 
         Ok(builder.build())
     }
@@ -1,9 +1,7 @@
-use std::iter::once;
+use shex_ast::{Node, ShapeExprLabel};
+use srdf::{QuerySRDF, SRDF};
 
-use shex_ast::{object_value::ObjectValue, ShapeExprLabel};
-use srdf::SRDF;
-
-use crate::{NodeSelector, ShapeSelector};
+use crate::{NodeSelector, NodeSelectorError, ShapeSelector};
 
 /// Combines a [`NodeSelector`] with a [`ShapeExprLabel`]
 #[derive(Debug, PartialEq)]
@@ -20,17 +18,38 @@ impl Association {
         }
     }
 
-    pub fn iter_node_shape<S>(
+    /// Obtains the pairs of nodes and shape labels selected by this association in `rdf`
+    pub fn select_node_shape<S>(
         &self,
         rdf: &S,
-    ) -> impl Iterator<Item = (&ObjectValue, &ShapeExprLabel)>
+    ) -> Result<Vec<(Node, &ShapeExprLabel)>, NodeSelectorError>
     where
         S: SRDF,
     {
-        self.node_selector.iter_node(rdf).flat_map(move |node| {
-            self.shape_selector
-                .iter_shape()
-                .flat_map(move |label| once((node, label)))
-        })
+        let nodes = self.node_selector.select(rdf)?;
+        Ok(self.combine(nodes))
+    }
+
+    /// Same as [`Association::select_node_shape`] for RDF sources that support SPARQL node selectors
+    pub fn select_node_shape_query<S>(
+        &self,
+        rdf: &S,
+    ) -> Result<Vec<(Node, &ShapeExprLabel)>, NodeSelectorError>
+    where
+        S: SRDF + QuerySRDF,
+    {
+        let nodes = self.node_selector.select_query(rdf)?;
+        Ok(self.combine(nodes))
+    }
+
+    fn combine(&self, nodes: Vec<Node>) -> Vec<(Node, &ShapeExprLabel)> {
+        nodes
+            .into_iter()
+            .flat_map(|node| {
+                self.shape_selector
+                    .iter_shape()
+                    .map(move |label| (node.clone(), label))
+            })
+            .collect()
     }
 }
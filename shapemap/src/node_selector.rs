@@ -1,8 +1,11 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+
 use iri_s::IriS;
 use prefixmap::IriRef;
 use shex_ast::{object_value::ObjectValue, Node};
 use srdf::shacl_path::SHACLPath;
-use srdf::SRDF;
+use srdf::{Object, QuerySRDF, SRDFBasic, SRDF};
 use thiserror::Error;
 
 /// A NodeSelector following [ShapeMap spec](https://shexspec.github.io/shape-map/#shapemap-structure) can be used to select RDF Nodes
@@ -12,12 +15,12 @@ pub enum NodeSelector {
     Node(ObjectValue),
     TriplePattern {
         subject: Pattern,
-        pred: IriS,
+        pred: IriRef,
         object: Pattern,
     },
     TriplePatternPath {
         subject: Pattern,
-        pred: SHACLPath,
+        pred: PropertyPath,
         object: Pattern,
     },
     Sparql {
@@ -42,57 +45,458 @@ impl NodeSelector {
         NodeSelector::Node(ObjectValue::prefixed(alias, local))
     }
 
-    pub fn iter_node<S>(&self, _rdf: &S) -> impl Iterator<Item = &ObjectValue>
+    /// Obtains the nodes selected in `rdf`
+    ///
+    /// SPARQL selectors need an RDF source that can run queries, see [`NodeSelector::select_query`]
+    pub fn select<S>(&self, rdf: &S) -> Result<Vec<Node>, NodeSelectorError>
     where
         S: SRDF,
     {
         match self {
-            NodeSelector::Node(value) => std::iter::once(value),
-            _ => todo!(),
+            NodeSelector::Node(value) => Ok(vec![object_value_node(value, rdf)?]),
+            NodeSelector::TriplePattern {
+                subject,
+                pred,
+                object,
+            } => {
+                let path = SHACLPath::iri(resolve_iri_ref(pred, rdf)?);
+                self.select_path(subject, &path, object, rdf)
+            }
+            NodeSelector::TriplePatternPath {
+                subject,
+                pred,
+                object,
+            } => self.select_path(subject, &pred.resolve(rdf)?, object, rdf),
+            NodeSelector::Sparql { query } => Err(NodeSelectorError::SparqlNotSupported {
+                query: query.clone(),
+            }),
+            NodeSelector::Generic { iri, .. } => {
+                Err(NodeSelectorError::UnknownGenericSelector { iri: iri.clone() })
+            }
         }
     }
-}
 
-#[derive(Debug, Error)]
-pub enum NodeSelectorError {}
+    /// Obtains the nodes selected in an RDF source that supports SPARQL queries
+    ///
+    /// The nodes selected by a SPARQL selector are the values of the first variable of the query
+    pub fn select_query<S>(&self, rdf: &S) -> Result<Vec<Node>, NodeSelectorError>
+    where
+        S: SRDF + QuerySRDF,
+    {
+        match self {
+            NodeSelector::Sparql { query } => {
+                let solutions = rdf.query_select(query).map_err(|e| self.rdf_error(e))?;
+                let mut terms = Vec::new();
+                for solution in solutions {
+                    let solution = solution.map_err(|e| self.rdf_error(e))?;
+                    if let Some(term) = solution.find_solution(0) {
+                        if !terms.contains(term) {
+                            terms.push(term.clone())
+                        }
+                    }
+                }
+                Ok(terms.iter().map(|t| S::term_as_object(t).into()).collect())
+            }
+            _ => self.select(rdf),
+        }
+    }
 
-impl NodeSelect for NodeSelector {
-    fn select<S>(&self, _rdf: S) -> Result<Vec<S::Term>, NodeSelectorError>
+    fn select_path<S>(
+        &self,
+        subject: &Pattern,
+        path: &SHACLPath,
+        object: &Pattern,
+        rdf: &S,
+    ) -> Result<Vec<Node>, NodeSelectorError>
     where
         S: SRDF,
     {
-        match self {
-            NodeSelector::Node(_node) => {
-                todo!()
+        let subject = subject.resolve(rdf)?;
+        let object = object.resolve(rdf)?;
+        let terms = match (&subject, &object) {
+            (ResolvedPattern::Focus, ResolvedPattern::Wildcard) => path_subjects(path, rdf),
+            (ResolvedPattern::Focus, ResolvedPattern::Node(node)) => {
+                rdf.subjects_for_shacl_path(&S::object_as_term(node.as_object()), path)
+            }
+            (ResolvedPattern::Wildcard, ResolvedPattern::Focus) => path_objects(path, rdf),
+            (ResolvedPattern::Node(node), ResolvedPattern::Focus) => {
+                rdf.objects_for_shacl_path(&S::object_as_term(node.as_object()), path)
             }
+            (ResolvedPattern::Focus, ResolvedPattern::Focus) => {
+                path_subjects(path, rdf).and_then(|subjects| {
+                    let mut result = HashSet::new();
+                    for subject in subjects {
+                        if rdf
+                            .objects_for_shacl_path(&subject, path)?
+                            .contains(&subject)
+                        {
+                            result.insert(subject);
+                        }
+                    }
+                    Ok(result)
+                })
+            }
+            (_, _) => {
+                return Err(NodeSelectorError::NoFocus {
+                    selector: self.to_string(),
+                })
+            }
+        }
+        .map_err(|e| self.rdf_error(e))?;
+        Ok(terms.iter().map(|t| S::term_as_object(t).into()).collect())
+    }
+
+    fn rdf_error(&self, err: impl Display) -> NodeSelectorError {
+        NodeSelectorError::RDFError {
+            selector: self.to_string(),
+            err: err.to_string(),
+        }
+    }
+}
+
+impl Display for NodeSelector {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NodeSelector::Node(value) => write_object_value(f, value),
             NodeSelector::TriplePattern {
                 subject,
                 pred,
                 object,
-            } => match (subject, pred, object) {
-                (Pattern::Focus, _pred, Pattern::Wildcard) => todo!(),
-                (Pattern::Focus, _pred, Pattern::Node(_node)) => todo!(),
-                (Pattern::Wildcard, _pred, Pattern::Focus) => todo!(),
-                (Pattern::Node(_n), _pred, Pattern::Focus) => todo!(),
-                (_, _, _) => todo!(),
-            },
-            NodeSelector::TriplePatternPath { .. } => todo!(),
-            NodeSelector::Sparql { .. } => todo!(),
-            NodeSelector::Generic { .. } => todo!(),
+            } => write!(f, "{{{subject} {pred} {object}}}"),
+            NodeSelector::TriplePatternPath {
+                subject,
+                pred,
+                object,
+            } => write!(f, "{{{subject} {pred} {object}}}"),
+            NodeSelector::Sparql { query } => write!(f, "SPARQL \"\"\"{query}\"\"\""),
+            NodeSelector::Generic { iri, param } => write!(f, "{iri} \"\"\"{param}\"\"\""),
         }
     }
 }
-#[derive(Debug, PartialEq)]
 
+#[derive(Debug, Error, Clone)]
+pub enum NodeSelectorError {
+    #[error("Error resolving node {node}: {err}")]
+    ResolvingNode { node: String, err: String },
+
+    #[error("Error obtaining the nodes selected by {selector}: {err}")]
+    RDFError { selector: String, err: String },
+
+    #[error("SPARQL node selectors need an RDF source that supports SPARQL queries: {query}")]
+    SparqlNotSupported { query: String },
+
+    #[error("Unknown node selector {iri}")]
+    UnknownGenericSelector { iri: IriS },
+
+    #[error("Node selector {selector} doesn't contain FOCUS")]
+    NoFocus { selector: String },
+}
+
+#[derive(Debug, PartialEq)]
 pub enum Pattern {
+    Node(ObjectValue),
+    Wildcard,
+    Focus,
+}
+
+impl Pattern {
+    fn resolve<S>(&self, rdf: &S) -> Result<ResolvedPattern, NodeSelectorError>
+    where
+        S: SRDF,
+    {
+        match self {
+            Pattern::Node(value) => Ok(ResolvedPattern::Node(object_value_node(value, rdf)?)),
+            Pattern::Wildcard => Ok(ResolvedPattern::Wildcard),
+            Pattern::Focus => Ok(ResolvedPattern::Focus),
+        }
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Pattern::Node(value) => write_object_value(f, value),
+            Pattern::Wildcard => write!(f, "_"),
+            Pattern::Focus => write!(f, "FOCUS"),
+        }
+    }
+}
+
+enum ResolvedPattern {
     Node(Node),
     Wildcard,
     Focus,
 }
 
-#[allow(dead_code)]
-trait NodeSelect {
-    fn select<S>(&self, rdf: S) -> Result<Vec<S::Term>, NodeSelectorError>
+/// Property path of a triple pattern node selector following [SHACL property paths](https://www.w3.org/TR/shacl/#property-paths)
+///
+/// The IRIs can be prefixed names which are resolved with the prefixes of the RDF data
+#[derive(Debug, PartialEq, Clone)]
+pub enum PropertyPath {
+    Predicate(IriRef),
+    Alternative(Vec<PropertyPath>),
+    Sequence(Vec<PropertyPath>),
+    Inverse(Box<PropertyPath>),
+    ZeroOrMore(Box<PropertyPath>),
+    OneOrMore(Box<PropertyPath>),
+    ZeroOrOne(Box<PropertyPath>),
+}
+
+impl PropertyPath {
+    /// Obtains the SHACL path resolving prefixed names with the prefixes of `rdf`
+    pub fn resolve<S>(&self, rdf: &S) -> Result<SHACLPath, NodeSelectorError>
     where
-        S: SRDF;
+        S: SRDFBasic,
+    {
+        let resolve_all = |paths: &Vec<PropertyPath>| {
+            paths
+                .iter()
+                .map(|p| p.resolve(rdf))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let path = match self {
+            PropertyPath::Predicate(iri_ref) => SHACLPath::iri(resolve_iri_ref(iri_ref, rdf)?),
+            PropertyPath::Alternative(paths) => SHACLPath::Alternative {
+                paths: resolve_all(paths)?,
+            },
+            PropertyPath::Sequence(paths) => SHACLPath::Sequence {
+                paths: resolve_all(paths)?,
+            },
+            PropertyPath::Inverse(path) => SHACLPath::Inverse {
+                path: Box::new(path.resolve(rdf)?),
+            },
+            PropertyPath::ZeroOrMore(path) => SHACLPath::ZeroOrMore {
+                path: Box::new(path.resolve(rdf)?),
+            },
+            PropertyPath::OneOrMore(path) => SHACLPath::OneOrMore {
+                path: Box::new(path.resolve(rdf)?),
+            },
+            PropertyPath::ZeroOrOne(path) => SHACLPath::ZeroOrOne {
+                path: Box::new(path.resolve(rdf)?),
+            },
+        };
+        Ok(path)
+    }
+}
+
+impl Display for PropertyPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let write_all = |f: &mut std::fmt::Formatter<'_>, paths: &Vec<PropertyPath>, sep: &str| {
+            let paths: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+            write!(f, "({})", paths.join(sep))
+        };
+        match self {
+            PropertyPath::Predicate(iri_ref) => write!(f, "{iri_ref}"),
+            PropertyPath::Alternative(paths) => write_all(f, paths, " | "),
+            PropertyPath::Sequence(paths) => write_all(f, paths, " / "),
+            PropertyPath::Inverse(path) => write!(f, "^{path}"),
+            PropertyPath::ZeroOrMore(path) => write!(f, "{path}*"),
+            PropertyPath::OneOrMore(path) => write!(f, "{path}+"),
+            PropertyPath::ZeroOrOne(path) => write!(f, "{path}?"),
+        }
+    }
+}
+
+fn write_object_value(f: &mut std::fmt::Formatter<'_>, value: &ObjectValue) -> std::fmt::Result {
+    match value {
+        ObjectValue::IriRef(iri) => write!(f, "{iri}"),
+        ObjectValue::Literal(lit) => write!(f, "{lit}"),
+    }
+}
+
+fn resolve_iri_ref<S>(iri_ref: &IriRef, rdf: &S) -> Result<IriS, NodeSelectorError>
+where
+    S: SRDFBasic,
+{
+    match iri_ref {
+        IriRef::Iri(iri) => Ok(iri.clone()),
+        IriRef::Prefixed { prefix, local } => {
+            rdf.resolve_prefix_local(prefix, local)
+                .map_err(|e| NodeSelectorError::ResolvingNode {
+                    node: format!("{prefix}:{local}"),
+                    err: e.to_string(),
+                })
+        }
+    }
+}
+
+fn object_value_node<S>(value: &ObjectValue, rdf: &S) -> Result<Node, NodeSelectorError>
+where
+    S: SRDF,
+{
+    match value {
+        ObjectValue::IriRef(iri_ref) => Ok(Node::iri(resolve_iri_ref(iri_ref, rdf)?)),
+        ObjectValue::Literal(lit) => Ok(Object::literal(lit.clone()).into()),
+    }
+}
+
+// Nodes that have some value for `path`.
+// Zero length paths match every node in the graph, but only the nodes connected by the inner path are selected
+fn path_subjects<S>(path: &SHACLPath, rdf: &S) -> Result<HashSet<S::Term>, S::Err>
+where
+    S: SRDF,
+{
+    match path {
+        SHACLPath::Predicate { pred } => Ok(rdf
+            .triples_with_predicate(&S::iri_s2iri(pred))?
+            .iter()
+            .map(|triple| S::subject_as_term(&triple.subj()))
+            .collect()),
+        SHACLPath::Alternative { paths } => {
+            let mut result = HashSet::new();
+            for path in paths {
+                result.extend(path_subjects(path, rdf)?)
+            }
+            Ok(result)
+        }
+        SHACLPath::Sequence { paths } => {
+            let mut result = HashSet::new();
+            if let Some(last) = paths.last() {
+                for node in path_objects(last, rdf)? {
+                    result.extend(rdf.subjects_for_shacl_path(&node, path)?)
+                }
+            }
+            Ok(result)
+        }
+        SHACLPath::Inverse { path } => path_objects(path, rdf),
+        SHACLPath::OneOrMore { path } => path_subjects(path, rdf),
+        SHACLPath::ZeroOrMore { path } | SHACLPath::ZeroOrOne { path } => {
+            let mut result = path_subjects(path, rdf)?;
+            result.extend(path_objects(path, rdf)?);
+            Ok(result)
+        }
+    }
+}
+
+// Nodes that are values of `path` for some node
+fn path_objects<S>(path: &SHACLPath, rdf: &S) -> Result<HashSet<S::Term>, S::Err>
+where
+    S: SRDF,
+{
+    match path {
+        SHACLPath::Predicate { pred } => Ok(rdf
+            .triples_with_predicate(&S::iri_s2iri(pred))?
+            .iter()
+            .map(|triple| triple.obj())
+            .collect()),
+        SHACLPath::Alternative { paths } => {
+            let mut result = HashSet::new();
+            for path in paths {
+                result.extend(path_objects(path, rdf)?)
+            }
+            Ok(result)
+        }
+        SHACLPath::Sequence { paths } => {
+            let mut result = HashSet::new();
+            if let Some(first) = paths.first() {
+                for node in path_subjects(first, rdf)? {
+                    result.extend(rdf.objects_for_shacl_path(&node, path)?)
+                }
+            }
+            Ok(result)
+        }
+        SHACLPath::Inverse { path } => path_subjects(path, rdf),
+        SHACLPath::OneOrMore { path } => path_objects(path, rdf),
+        SHACLPath::ZeroOrMore { path } | SHACLPath::ZeroOrOne { path } => {
+            let mut result = path_subjects(path, rdf)?;
+            result.extend(path_objects(path, rdf)?);
+            Ok(result)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use srdf::{RDFFormat, ReaderMode, SRDFGraph};
+
+    fn example_graph() -> SRDFGraph {
+        let data = r#"prefix : <http://example.org/>
+        :alice :worksFor :acme .
+        :bob   :worksFor :acme .
+        :carol :knows    :alice .
+        "#;
+        SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap()
+    }
+
+    fn select_sorted(selector: &NodeSelector, rdf: &SRDFGraph) -> Vec<String> {
+        let mut nodes: Vec<String> = selector
+            .select(rdf)
+            .unwrap()
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        nodes.sort();
+        nodes
+    }
+
+    #[test]
+    fn test_select_triple_patterns() {
+        let graph = example_graph();
+        let subjects = NodeSelector::TriplePattern {
+            subject: Pattern::Focus,
+            pred: IriRef::prefixed("", "worksFor"),
+            object: Pattern::Wildcard,
+        };
+        assert_eq!(
+            select_sorted(&subjects, &graph),
+            vec!["http://example.org/alice", "http://example.org/bob"]
+        );
+        let objects = NodeSelector::TriplePattern {
+            subject: Pattern::Wildcard,
+            pred: IriRef::prefixed("", "worksFor"),
+            object: Pattern::Focus,
+        };
+        assert_eq!(
+            select_sorted(&objects, &graph),
+            vec!["http://example.org/acme"]
+        );
+    }
+
+    #[test]
+    fn test_select_path() {
+        let graph = example_graph();
+        let selector = NodeSelector::TriplePatternPath {
+            subject: Pattern::Focus,
+            pred: PropertyPath::Sequence(vec![
+                PropertyPath::Predicate(IriRef::prefixed("", "knows")),
+                PropertyPath::Predicate(IriRef::prefixed("", "worksFor")),
+            ]),
+            object: Pattern::Node(ObjectValue::prefixed("", "acme")),
+        };
+        assert_eq!(
+            select_sorted(&selector, &graph),
+            vec!["http://example.org/carol"]
+        );
+    }
+
+    #[test]
+    fn test_select_sparql() {
+        let selector = NodeSelector::Sparql {
+            query: "prefix : <http://example.org/> select ?x where { ?x :worksFor :acme }"
+                .to_string(),
+        };
+        let mut nodes: Vec<String> = selector
+            .select_query(&example_graph())
+            .unwrap()
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        nodes.sort();
+        assert_eq!(
+            nodes,
+            vec!["http://example.org/alice", "http://example.org/bob"]
+        );
+    }
+
+    #[test]
+    fn test_select_sparql_without_endpoint() {
+        let selector = NodeSelector::Sparql {
+            query: "select ?x where { ?x ?p ?o }".to_string(),
+        };
+        assert!(matches!(
+            selector.select(&example_graph()),
+            Err(NodeSelectorError::SparqlNotSupported { .. })
+        ))
+    }
 }
@@ -1,7 +1,7 @@
-use crate::{Association, NodeSelector, ShapeSelector};
+use crate::{Association, NodeSelector, NodeSelectorError, ShapeSelector};
 use prefixmap::PrefixMap;
-use shex_ast::{object_value::ObjectValue, ShapeExprLabel};
-use srdf::SRDF;
+use shex_ast::{Node, ShapeExprLabel};
+use srdf::{QuerySRDF, SRDF};
 
 #[derive(Debug, Default, PartialEq)]
 pub struct QueryShapeMap {
@@ -42,13 +42,33 @@ impl QueryShapeMap {
         self.associations.iter()
     }
 
-    pub fn iter_node_shape<'a, S>(
-        &'a self,
-        rdf: &'a S,
-    ) -> impl Iterator<Item = (&ObjectValue, &ShapeExprLabel)> + 'a
+    /// Expands the node selectors of this shapemap obtaining the pairs of nodes and shape labels to validate
+    pub fn select_node_shape<S>(
+        &self,
+        rdf: &S,
+    ) -> Result<Vec<(Node, &ShapeExprLabel)>, NodeSelectorError>
     where
         S: SRDF,
     {
-        self.iter().flat_map(|assoc| assoc.iter_node_shape(rdf))
+        let mut result = Vec::new();
+        for assoc in self.iter() {
+            result.extend(assoc.select_node_shape(rdf)?)
+        }
+        Ok(result)
+    }
+
+    /// Same as [`QueryShapeMap::select_node_shape`] for RDF sources that support SPARQL node selectors
+    pub fn select_node_shape_query<S>(
+        &self,
+        rdf: &S,
+    ) -> Result<Vec<(Node, &ShapeExprLabel)>, NodeSelectorError>
+    where
+        S: SRDF + QuerySRDF,
+    {
+        let mut result = Vec::new();
+        for assoc in self.iter() {
+            result.extend(assoc.select_node_shape_query(rdf)?)
+        }
+        Ok(result)
     }
 }
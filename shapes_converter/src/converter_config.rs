@@ -4,8 +4,8 @@ use dctap::TapConfig;
 use serde_derive::{Deserialize, Serialize};
 
 use crate::{
-    ConverterError, ShEx2HtmlConfig, ShEx2SparqlConfig, ShEx2UmlConfig, Shacl2ShExConfig,
-    Tap2ShExConfig,
+    ConverterError, DataGeneratorConfig, ShEx2HtmlConfig, ShEx2SparqlConfig, ShEx2TapConfig,
+    ShEx2UmlConfig, Shacl2ShExConfig, Shacl2TapConfig, Tap2ShExConfig,
 };
 
 #[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
@@ -16,6 +16,9 @@ pub struct ConverterConfig {
     shex2sparql: Option<ShEx2SparqlConfig>,
     shacl2shex: Option<Shacl2ShExConfig>,
     shex2uml: Option<ShEx2UmlConfig>,
+    shex2tap: Option<ShEx2TapConfig>,
+    shacl2tap: Option<Shacl2TapConfig>,
+    data_generator: Option<DataGeneratorConfig>,
 }
 
 impl ConverterConfig {
@@ -77,4 +80,25 @@ impl ConverterConfig {
             None => ShEx2SparqlConfig::default(),
         }
     }
+
+    pub fn shex2tap_config(&self) -> ShEx2TapConfig {
+        match &self.shex2tap {
+            Some(c) => c.clone(),
+            None => ShEx2TapConfig::default(),
+        }
+    }
+
+    pub fn shacl2tap_config(&self) -> Shacl2TapConfig {
+        match &self.shacl2tap {
+            Some(c) => c.clone(),
+            None => Shacl2TapConfig::default(),
+        }
+    }
+
+    pub fn data_generator_config(&self) -> DataGeneratorConfig {
+        match &self.data_generator {
+            Some(c) => c.clone(),
+            None => DataGeneratorConfig::default(),
+        }
+    }
 }
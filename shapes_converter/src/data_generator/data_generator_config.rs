@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use serde_derive::{Deserialize, Serialize};
+
+pub const DEFAULT_DATA_NAMESPACE: &str = "http://data.example/";
+pub const DEFAULT_INSTANCES: usize = 1;
+pub const DEFAULT_MAX_REPEAT: usize = 2;
+pub const DEFAULT_SEED: u64 = 0;
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+pub struct DataGeneratorConfig {
+    /// Seed of the random generator, the same schema and seed always generate the same data
+    seed: Option<u64>,
+
+    /// Number of nodes generated for each shape
+    instances: Option<usize>,
+
+    /// Number of nodes generated for some shapes, indexed by shape label (IRI or prefixed name)
+    shape_instances: Option<HashMap<String, usize>>,
+
+    /// Maximum number of values added over the minimum cardinality when the maximum is unbounded
+    max_repeat: Option<usize>,
+
+    /// Namespace of the IRIs of the generated nodes
+    data_namespace: Option<String>,
+
+    /// Generate nodes that violate one constraint of their shape instead of conforming nodes
+    violations: Option<bool>,
+}
+
+impl DataGeneratorConfig {
+    pub fn seed(&self) -> u64 {
+        self.seed.unwrap_or(DEFAULT_SEED)
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Number of nodes to generate for a shape
+    ///
+    /// `labels` contains the ways to write the shape label (full IRI, prefixed name...) looked up in `shape_instances`
+    pub fn instances(&self, labels: &[String]) -> usize {
+        self.shape_instances
+            .as_ref()
+            .and_then(|map| labels.iter().find_map(|label| map.get(label)))
+            .copied()
+            .or(self.instances)
+            .unwrap_or(DEFAULT_INSTANCES)
+    }
+
+    pub fn with_instances(mut self, instances: usize) -> Self {
+        self.instances = Some(instances);
+        self
+    }
+
+    pub fn max_repeat(&self) -> usize {
+        self.max_repeat.unwrap_or(DEFAULT_MAX_REPEAT)
+    }
+
+    pub fn data_namespace(&self) -> String {
+        match &self.data_namespace {
+            Some(ns) => ns.clone(),
+            None => DEFAULT_DATA_NAMESPACE.to_string(),
+        }
+    }
+
+    pub fn violations(&self) -> bool {
+        self.violations.unwrap_or(false)
+    }
+
+    pub fn with_violations(mut self, violations: bool) -> Self {
+        self.violations = Some(violations);
+        self
+    }
+}
@@ -0,0 +1,32 @@
+use prefixmap::PrefixMapError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DataGeneratorError {
+    #[error("Error adding generated triples to RDF data: {error}")]
+    RDFError { error: String },
+
+    #[error("Generated node {node} can't be the subject of a triple")]
+    NoSubject { node: String },
+
+    #[error(transparent)]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
+}
+
+/// Constructs of the schema that the data generator doesn't take into account
+///
+/// The generation goes on when they are found, so the generated data may not behave as expected for them
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum DataGeneratorWarning {
+    #[error("Node {node}: no constraint of shape {shape} can be violated, the node has been generated conformant")]
+    NotViolable { node: String, shape: String },
+
+    #[error("Shape {shape}: {construct} is not supported by the data generator")]
+    NotSupported { shape: String, construct: String },
+
+    #[error("Shape {shape} referenced but not found in the schema")]
+    ShapeNotFound { shape: String },
+}
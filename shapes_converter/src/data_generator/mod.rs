@@ -0,0 +1,13 @@
+//! Generators of example RDF data from ShEx and SHACL schemas
+//!
+//!
+pub mod data_generator_config;
+pub mod data_generator_error;
+pub mod shacl2data;
+pub mod shex2data;
+mod value_generator;
+
+pub use data_generator_config::*;
+pub use data_generator_error::*;
+pub use shacl2data::*;
+pub use shex2data::*;
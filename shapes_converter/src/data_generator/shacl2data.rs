@@ -0,0 +1,542 @@
+//! Struct that generates RDF data from SHACL shapes graphs
+//!
+//! Each node shape gets a number of focus nodes, selected by its targets, whose triples follow
+//! the value components of its property shapes. Only property shapes with predicate or inverse paths are generated.
+use std::collections::HashMap;
+
+use iri_s::IriS;
+use prefixmap::PrefixMap;
+use shacl_ast::{
+    component::Component, node_kind::NodeKind, node_shape::NodeShape,
+    property_shape::PropertyShape, shape::Shape, target::Target, value::Value, Schema,
+};
+use srdf::{literal::Literal, Object, RDFNode, SHACLPath, SRDFBuilder, RDF_TYPE};
+
+use super::value_generator::{local_name, write_triples, ValueGenerator, ValueKind, ValueSpec};
+use super::{DataGeneratorConfig, DataGeneratorError, DataGeneratorWarning};
+
+type Result<T> = std::result::Result<T, DataGeneratorError>;
+
+pub struct Shacl2Data {
+    config: DataGeneratorConfig,
+    warnings: Vec<DataGeneratorWarning>,
+}
+
+impl Shacl2Data {
+    pub fn new(config: &DataGeneratorConfig) -> Shacl2Data {
+        Shacl2Data {
+            config: config.clone(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Constructs of the last shapes graph that were not taken into account by the generator
+    pub fn warnings(&self) -> impl Iterator<Item = &DataGeneratorWarning> {
+        self.warnings.iter()
+    }
+
+    /// Adds to `rdf` the triples of the focus nodes generated for the node shapes of `schema`
+    pub fn generate<RDF>(&mut self, schema: &Schema, rdf: &mut RDF) -> Result<()>
+    where
+        RDF: SRDFBuilder,
+    {
+        let prefixmap = schema.prefix_map();
+        let mut generator = ShaclGenerator::new(&self.config, &prefixmap, schema);
+        generator.generate()?;
+        self.warnings = generator.warnings;
+        write_triples(&generator.triples, &prefixmap, &self.config, rdf)
+    }
+}
+
+/// Constraint that is violated by a generated focus node
+#[derive(Clone, Copy)]
+enum Violation<'a> {
+    /// Less values than the `sh:minCount` of a property shape
+    Omit(&'a PropertyShape),
+    /// A value that doesn't satisfy the value components of a property shape
+    WrongValue(&'a PropertyShape),
+    /// More values than the `sh:maxCount` of a property shape
+    ExtraValue(&'a PropertyShape),
+    /// A triple whose predicate is not declared in a closed node shape
+    Closed,
+}
+
+impl<'a> Violation<'a> {
+    fn is_about(&self, ps: &PropertyShape) -> bool {
+        match self {
+            Violation::Omit(p) | Violation::WrongValue(p) | Violation::ExtraValue(p) => {
+                std::ptr::eq(*p, ps)
+            }
+            Violation::Closed => false,
+        }
+    }
+}
+
+/// Value components of a property shape
+#[derive(Default)]
+struct PropertySpec {
+    min_count: usize,
+    max_count: Option<usize>,
+    value: ValueSpec,
+    class: Option<Object>,
+    node: Option<String>,
+}
+
+struct ShaclGenerator<'a> {
+    config: &'a DataGeneratorConfig,
+    prefixmap: &'a PrefixMap,
+    schema: &'a Schema,
+    instances: HashMap<String, Vec<Object>>,
+    classes: HashMap<Object, String>,
+    values: ValueGenerator,
+    triples: Vec<(Object, IriS, Object)>,
+    warnings: Vec<DataGeneratorWarning>,
+    current_shape: String,
+}
+
+impl<'a> ShaclGenerator<'a> {
+    fn new(
+        config: &'a DataGeneratorConfig,
+        prefixmap: &'a PrefixMap,
+        schema: &'a Schema,
+    ) -> ShaclGenerator<'a> {
+        ShaclGenerator {
+            config,
+            prefixmap,
+            schema,
+            instances: HashMap::new(),
+            classes: HashMap::new(),
+            values: ValueGenerator::new(config),
+            triples: Vec::new(),
+            warnings: Vec::new(),
+            current_shape: String::new(),
+        }
+    }
+
+    fn generate(&mut self) -> Result<()> {
+        // Shapes are stored in a HashMap, they are sorted to obtain a stable output
+        let mut node_shapes: Vec<(String, &NodeShape)> = self
+            .schema
+            .iter()
+            .filter_map(|(id, shape)| match shape {
+                Shape::NodeShape(ns) => Some((node_key(id), ns.as_ref())),
+                Shape::PropertyShape(_) => None,
+            })
+            .collect();
+        node_shapes.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
+
+        // The focus nodes of every shape are named before generating their triples so sh:node and sh:class can point to them
+        for (key, ns) in node_shapes.iter() {
+            let target_nodes: Vec<Object> = ns
+                .targets()
+                .iter()
+                .filter_map(|target| match target {
+                    Target::TargetNode(node) => Some(node.clone()),
+                    _ => None,
+                })
+                .collect();
+            let nodes = if target_nodes.is_empty() {
+                let labels = [
+                    key.clone(),
+                    self.prefixmap.qualify(&IriS::new_unchecked(key)),
+                ];
+                let name = local_name(key);
+                (1..=self.config.instances(&labels))
+                    .map(|n| self.values.node_iri(&name, n))
+                    .collect()
+            } else {
+                target_nodes
+            };
+            for target in ns.targets() {
+                if let Target::TargetClass(class) = target {
+                    self.classes.entry(class.clone()).or_insert(key.clone());
+                }
+            }
+            self.instances.insert(key.clone(), nodes);
+        }
+
+        for (key, ns) in node_shapes {
+            self.current_shape = key.clone();
+            let property_shapes = self.property_shapes(ns);
+            let subjects_of: Vec<IriS> = ns
+                .targets()
+                .iter()
+                .filter_map(|target| match target {
+                    Target::TargetSubjectsOf(pred) => self.prefixmap.resolve_iriref(pred).ok(),
+                    _ => None,
+                })
+                .collect();
+            let nodes = self.instances.get(&key).cloned().unwrap_or_default();
+            for node in nodes {
+                let violation = if self.config.violations() {
+                    self.choose_violation(ns, &property_shapes, &subjects_of, &node, &key)
+                } else {
+                    None
+                };
+                self.targets(&node, ns)?;
+                for pred in subjects_of.iter() {
+                    if !property_shapes.iter().any(|ps| path_pred(ps) == Some(pred)) {
+                        let value = Object::literal(Literal::str("value"));
+                        self.triples.push((node.clone(), pred.clone(), value))
+                    }
+                }
+                for ps in property_shapes.iter() {
+                    let required = path_pred(ps).is_some_and(|p| subjects_of.contains(p));
+                    self.property_shape(&node, ps, violation, required)?
+                }
+                if matches!(violation, Some(Violation::Closed)) {
+                    let pred = self.values.namespace_iri("undeclaredProperty");
+                    let value = Object::literal(Literal::str("undeclared"));
+                    self.triples.push((node.clone(), pred, value))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn property_shapes(&mut self, ns: &'a NodeShape) -> Vec<&'a PropertyShape> {
+        let mut result = Vec::new();
+        for id in ns.property_shapes() {
+            match self.schema.get_shape(id) {
+                Some(Shape::PropertyShape(ps)) => match ps.path() {
+                    SHACLPath::Predicate { .. } => result.push(ps),
+                    SHACLPath::Inverse { path }
+                        if matches!(path.as_ref(), SHACLPath::Predicate { .. }) =>
+                    {
+                        result.push(ps)
+                    }
+                    path => self.not_supported(&format!("path {path}")),
+                },
+                Some(Shape::NodeShape(_)) => self.not_supported("node shapes as property shapes"),
+                None => self.shape_not_found(&node_key(id)),
+            }
+        }
+        result
+    }
+
+    /// Adds the triples that make `node` a focus node of the targets of `ns`
+    fn targets(&mut self, node: &Object, ns: &NodeShape) -> Result<()> {
+        for target in ns.targets() {
+            match target {
+                Target::TargetClass(class) => {
+                    self.triples
+                        .push((node.clone(), RDF_TYPE.clone(), class.clone()))
+                }
+                Target::TargetObjectsOf(pred) => {
+                    let pred = self.prefixmap.resolve_iriref(pred)?;
+                    let subject = self.values.fresh_iri();
+                    self.triples.push((subject, pred, node.clone()))
+                }
+                Target::TargetNode(_) | Target::TargetSubjectsOf(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn choose_violation(
+        &mut self,
+        ns: &NodeShape,
+        property_shapes: &[&'a PropertyShape],
+        subjects_of: &[IriS],
+        node: &Object,
+        key: &str,
+    ) -> Option<Violation<'a>> {
+        let mut candidates = Vec::new();
+        if ns.components().iter().any(|c| {
+            matches!(
+                c,
+                Component::Closed {
+                    is_closed: true,
+                    ..
+                }
+            )
+        }) {
+            candidates.push(Violation::Closed)
+        }
+        for ps in property_shapes {
+            let spec = self.property_spec(ps);
+            // Omitting the predicate of a sh:targetSubjectsOf would make the node stop being a focus node
+            let required = path_pred(ps).is_some_and(|p| subjects_of.contains(p));
+            if spec.min_count > 0 && !required {
+                candidates.push(Violation::Omit(ps))
+            }
+            if spec.value.can_be_violated() || spec.class.is_some() {
+                candidates.push(Violation::WrongValue(ps))
+            }
+            if spec.max_count.is_some()
+                && spec.class.is_none()
+                && spec.node.is_none()
+                && spec.value.generates_distinct_values()
+            {
+                candidates.push(Violation::ExtraValue(ps))
+            }
+        }
+        let violation = self.values.choose(&candidates).copied();
+        if violation.is_none() {
+            self.warnings.push(DataGeneratorWarning::NotViolable {
+                node: node.to_string(),
+                shape: key.to_string(),
+            })
+        }
+        violation
+    }
+
+    fn property_shape(
+        &mut self,
+        node: &Object,
+        ps: &PropertyShape,
+        violation: Option<Violation<'a>>,
+        required: bool,
+    ) -> Result<()> {
+        let spec = self.property_spec(ps);
+        let (pred, inverse) = match ps.path() {
+            SHACLPath::Predicate { pred } => (pred.clone(), false),
+            SHACLPath::Inverse { path } => match path.as_ref() {
+                SHACLPath::Predicate { pred } => (pred.clone(), true),
+                _ => return Ok(()),
+            },
+            _ => return Ok(()),
+        };
+        let min_count = if required {
+            spec.min_count.max(1)
+        } else {
+            spec.min_count
+        };
+        let target = violation.filter(|v| v.is_about(ps));
+        let count = match target {
+            Some(Violation::Omit(_)) => 0,
+            Some(Violation::ExtraValue(_)) => spec.max_count.unwrap_or(0) + 1,
+            Some(Violation::WrongValue(_)) => {
+                self.values.cardinality(min_count, spec.max_count).max(1)
+            }
+            _ => self.values.cardinality(min_count, spec.max_count),
+        };
+        for n in 0..count {
+            let value = if n == 0 && matches!(target, Some(Violation::WrongValue(_))) {
+                self.wrong_value(&spec)
+            } else {
+                self.value(&spec)
+            };
+            if inverse {
+                let subject = match value {
+                    Object::Literal(_) => self.values.fresh_iri(),
+                    _ => value,
+                };
+                self.triples.push((subject, pred.clone(), node.clone()))
+            } else {
+                self.triples.push((node.clone(), pred.clone(), value))
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates a value that satisfies the value components of a property shape
+    fn value(&mut self, spec: &PropertySpec) -> Object {
+        if let Some(shape) = &spec.node {
+            let nodes = self.instances.get(shape).cloned().unwrap_or_default();
+            if let Some(node) = self.values.choose(&nodes) {
+                return node.clone();
+            }
+        }
+        if let Some(class) = &spec.class {
+            let nodes = self
+                .classes
+                .get(class)
+                .and_then(|shape| self.instances.get(shape))
+                .cloned()
+                .unwrap_or_default();
+            return match self.values.choose(&nodes) {
+                Some(node) => node.clone(),
+                None => {
+                    let node = self.values.fresh_iri();
+                    self.triples
+                        .push((node.clone(), RDF_TYPE.clone(), class.clone()));
+                    node
+                }
+            };
+        }
+        if spec.node.is_some() && !spec.value.is_constrained() {
+            return self.values.fresh_iri();
+        }
+        self.values.value(&spec.value)
+    }
+
+    fn wrong_value(&mut self, spec: &PropertySpec) -> Object {
+        match self.values.violating_value(&spec.value) {
+            Some(value) => value,
+            // A node without rdf:type is not an instance of the sh:class
+            None => self.values.fresh_iri(),
+        }
+    }
+
+    fn property_spec(&mut self, ps: &PropertyShape) -> PropertySpec {
+        let mut spec = PropertySpec::default();
+        for component in ps.components() {
+            match component {
+                Component::MinCount(n) => spec.min_count = (*n).max(0) as usize,
+                Component::MaxCount(n) => spec.max_count = Some((*n).max(0) as usize),
+                Component::Datatype(dt) => match self.prefixmap.resolve_iriref(dt) {
+                    Ok(iri) => spec.value.datatype = Some(iri),
+                    Err(_) => self.not_supported(&format!("datatype {dt}")),
+                },
+                Component::NodeKind(kind) => {
+                    spec.value.kind = Some(match kind {
+                        NodeKind::Iri | NodeKind::IRIOrLiteral => ValueKind::Iri,
+                        NodeKind::BlankNode | NodeKind::BlankNodeOrLiteral => ValueKind::BNode,
+                        NodeKind::BlankNodeOrIri => ValueKind::NonLiteral,
+                        NodeKind::Literal => ValueKind::Literal,
+                        NodeKind::TripleTerm => ValueKind::Triple,
+                    })
+                }
+                Component::MinInclusive(lit) => spec.value.min_inclusive = literal2f64(lit),
+                Component::MinExclusive(lit) => spec.value.min_exclusive = literal2f64(lit),
+                Component::MaxInclusive(lit) => spec.value.max_inclusive = literal2f64(lit),
+                Component::MaxExclusive(lit) => spec.value.max_exclusive = literal2f64(lit),
+                Component::MinLength(n) => spec.value.min_length = Some((*n).max(0) as usize),
+                Component::MaxLength(n) => spec.value.max_length = Some((*n).max(0) as usize),
+                Component::LanguageIn { langs } => {
+                    spec.value.languages = langs.iter().map(|l| l.value()).collect()
+                }
+                Component::In { values } => {
+                    for value in values {
+                        if let Some(obj) = self.value2object(value) {
+                            spec.value.values.push(obj)
+                        }
+                    }
+                }
+                Component::HasValue { value } => {
+                    if let Some(obj) = self.value2object(value) {
+                        spec.value.values = vec![obj];
+                        spec.min_count = spec.min_count.max(1)
+                    }
+                }
+                Component::Class(class) => spec.class = Some(class.clone()),
+                Component::Node { shape } => spec.node = Some(node_key(shape)),
+                other => self.not_supported(&other.to_string()),
+            }
+        }
+        spec
+    }
+
+    fn value2object(&mut self, value: &Value) -> Option<Object> {
+        match value {
+            Value::Iri(iri_ref) => match self.prefixmap.resolve_iriref(iri_ref) {
+                Ok(iri) => Some(Object::iri(iri)),
+                Err(_) => {
+                    self.not_supported(&format!("value {iri_ref}"));
+                    None
+                }
+            },
+            Value::Literal(lit) => Some(Object::literal(lit.clone())),
+        }
+    }
+
+    fn not_supported(&mut self, construct: &str) {
+        let warning = DataGeneratorWarning::NotSupported {
+            shape: self.current_shape.clone(),
+            construct: construct.to_string(),
+        };
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning)
+        }
+    }
+
+    fn shape_not_found(&mut self, shape: &str) {
+        let warning = DataGeneratorWarning::ShapeNotFound {
+            shape: shape.to_string(),
+        };
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning)
+        }
+    }
+}
+
+fn path_pred(ps: &PropertyShape) -> Option<&IriS> {
+    match ps.path() {
+        SHACLPath::Predicate { pred } => Some(pred),
+        _ => None,
+    }
+}
+
+/// Key of a shape, which is the full IRI for IRIs
+fn node_key(node: &RDFNode) -> String {
+    match node {
+        Object::Iri(iri) => iri.as_str().to_string(),
+        other => other.to_string(),
+    }
+}
+
+fn literal2f64(lit: &Literal) -> Option<f64> {
+    lit.lexical_form().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shacl_ast::ShaclParser;
+    use shacl_validation::validate::{GraphValidator, ShaclValidationMode, Validator};
+    use srdf::{srdf_graph::SRDFGraph, RDFFormat, ReaderMode};
+    use std::collections::HashSet;
+    use std::io::Write;
+
+    const SHAPES: &str = r#"prefix : <http://example.org/>
+prefix sh: <http://www.w3.org/ns/shacl#>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:Person a sh:NodeShape ; sh:targetClass :Person ;
+   sh:property [ sh:path :name ; sh:datatype xsd:string ; sh:minCount 1 ; sh:maxCount 1 ] ;
+   sh:property [ sh:path :status ; sh:in ( :active :inactive ) ; sh:minCount 1 ] ;
+   sh:property [ sh:path :worksFor ; sh:class :Org ; sh:maxCount 1 ] .
+:Org a sh:NodeShape ; sh:targetClass :Org ;
+   sh:property [ sh:path :name ; sh:datatype xsd:string ; sh:minCount 1 ] .
+"#;
+
+    /// Generates data for `SHAPES` and returns the focus nodes with validation results
+    fn generate_and_validate(config: &DataGeneratorConfig) -> HashSet<String> {
+        let rdf =
+            SRDFGraph::from_str(SHAPES, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let schema = ShaclParser::new(rdf).parse().unwrap();
+        let mut data = SRDFGraph::new();
+        Shacl2Data::new(config)
+            .generate(&schema, &mut data)
+            .unwrap();
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        data.serialize(RDFFormat::Turtle, &mut file).unwrap();
+        file.flush().unwrap();
+        let validator = GraphValidator::new(
+            file.path(),
+            RDFFormat::Turtle,
+            None,
+            ShaclValidationMode::Default,
+        )
+        .unwrap();
+        let report = validator.validate(&schema).unwrap();
+        report
+            .results()
+            .iter()
+            .filter_map(|result| result.focus_node())
+            .map(|node| node.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn generated_data_conforms() {
+        let config = DataGeneratorConfig::default()
+            .with_seed(3)
+            .with_instances(3);
+        assert_eq!(generate_and_validate(&config), HashSet::new())
+    }
+
+    #[test]
+    fn generated_violations_dont_conform() {
+        let config = DataGeneratorConfig::default()
+            .with_seed(3)
+            .with_instances(3)
+            .with_violations(true);
+        let ns = config.data_namespace();
+        let expected: HashSet<String> = ["Person1", "Person2", "Person3", "Org1", "Org2", "Org3"]
+            .iter()
+            .map(|node| format!("<{ns}{node}>"))
+            .collect();
+        assert_eq!(generate_and_validate(&config), expected)
+    }
+}
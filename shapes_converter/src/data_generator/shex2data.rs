@@ -0,0 +1,612 @@
+//! Struct that generates RDF data from ShEx schemas
+//!
+//! Each shape declaration gets a number of nodes whose triples follow the cardinalities and the
+//! value expressions of its triple constraints. References to other shapes point to the nodes generated for them.
+use std::collections::HashMap;
+
+use iri_s::IriS;
+use prefixmap::PrefixMap;
+use shex_ast::{
+    NodeConstraint, NodeKind, NumericFacet, ObjectValue, Schema, ShapeDecl, ShapeExpr,
+    ShapeExprLabel, StringFacet, TripleExpr, ValueSetValue, XsFacet,
+};
+use srdf::{literal::Literal, Object, SRDFBuilder};
+
+use super::value_generator::{local_name, write_triples, ValueGenerator, ValueKind, ValueSpec};
+use super::{DataGeneratorConfig, DataGeneratorError, DataGeneratorWarning};
+
+type Result<T> = std::result::Result<T, DataGeneratorError>;
+
+/// Maximum number of nested references followed when generating a node, to avoid cycles
+const MAX_REF_DEPTH: usize = 16;
+
+pub struct ShEx2Data {
+    config: DataGeneratorConfig,
+    warnings: Vec<DataGeneratorWarning>,
+}
+
+impl ShEx2Data {
+    pub fn new(config: &DataGeneratorConfig) -> ShEx2Data {
+        ShEx2Data {
+            config: config.clone(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Constructs of the last schema that were not taken into account by the generator
+    pub fn warnings(&self) -> impl Iterator<Item = &DataGeneratorWarning> {
+        self.warnings.iter()
+    }
+
+    /// Adds to `rdf` the triples of the nodes generated for the shapes of `schema`
+    pub fn generate<RDF>(&mut self, schema: &Schema, rdf: &mut RDF) -> Result<()>
+    where
+        RDF: SRDFBuilder,
+    {
+        let prefixmap = schema.prefixmap().unwrap_or_default();
+        let decls = schema.shapes().unwrap_or_default();
+        let mut generator = ShExGenerator::new(&self.config, &prefixmap, &decls)?;
+        generator.generate(&decls)?;
+        self.warnings = generator.warnings;
+        write_triples(&generator.triples, &prefixmap, &self.config, rdf)
+    }
+}
+
+/// Constraint that is violated by a generated node
+#[derive(Clone, Copy)]
+enum Violation<'a> {
+    /// No value for a triple constraint whose minimum cardinality is not zero
+    Omit(&'a TripleExpr),
+    /// A value that doesn't satisfy the node constraint of a triple constraint
+    WrongValue(&'a TripleExpr),
+    /// One value more than the maximum cardinality of a triple constraint
+    ExtraValue(&'a TripleExpr),
+    /// A triple whose predicate is not mentioned in a closed shape
+    Closed,
+}
+
+struct ShExGenerator<'a> {
+    config: &'a DataGeneratorConfig,
+    prefixmap: &'a PrefixMap,
+    shapes: HashMap<String, &'a ShapeExpr>,
+    instances: HashMap<String, Vec<Object>>,
+    values: ValueGenerator,
+    triples: Vec<(Object, IriS, Object)>,
+    warnings: Vec<DataGeneratorWarning>,
+    current_shape: String,
+}
+
+impl<'a> ShExGenerator<'a> {
+    fn new(
+        config: &'a DataGeneratorConfig,
+        prefixmap: &'a PrefixMap,
+        decls: &'a [ShapeDecl],
+    ) -> Result<ShExGenerator<'a>> {
+        let mut shapes = HashMap::new();
+        for decl in decls {
+            shapes.insert(label_key(&decl.id, prefixmap)?, &decl.shape_expr);
+        }
+        Ok(ShExGenerator {
+            config,
+            prefixmap,
+            shapes,
+            instances: HashMap::new(),
+            values: ValueGenerator::new(config),
+            triples: Vec::new(),
+            warnings: Vec::new(),
+            current_shape: String::new(),
+        })
+    }
+
+    fn generate(&mut self, decls: &'a [ShapeDecl]) -> Result<()> {
+        // The nodes of every shape are named before generating their triples so references can point to them
+        for decl in decls {
+            if decl.is_abstract || !self.is_shape(&decl.shape_expr, 0) {
+                continue;
+            }
+            let key = label_key(&decl.id, self.prefixmap)?;
+            let mut labels = vec![key.clone()];
+            if let ShapeExprLabel::IriRef { value } = &decl.id {
+                labels.push(value.to_string());
+                labels.push(self.prefixmap.qualify(&IriS::new_unchecked(&key)));
+            }
+            let name = local_name(&key);
+            let nodes = (1..=self.config.instances(&labels))
+                .map(|n| self.values.node_iri(&name, n))
+                .collect();
+            self.instances.insert(key, nodes);
+        }
+        for decl in decls {
+            let key = label_key(&decl.id, self.prefixmap)?;
+            let nodes = match self.instances.get(&key) {
+                Some(nodes) => nodes.clone(),
+                None => continue,
+            };
+            for node in nodes {
+                self.current_shape = key.clone();
+                let violation = if self.config.violations() {
+                    let mut candidates = Vec::new();
+                    self.violations(&decl.shape_expr, &mut candidates, 0);
+                    let violation = self.values.choose(&candidates).copied();
+                    if violation.is_none() {
+                        self.warnings.push(DataGeneratorWarning::NotViolable {
+                            node: node.to_string(),
+                            shape: key.clone(),
+                        })
+                    }
+                    violation
+                } else {
+                    None
+                };
+                self.shape_expr(&node, &decl.shape_expr, violation, 0)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks if a shape expression describes the triples of a node, so nodes can be generated for it
+    fn is_shape(&self, se: &ShapeExpr, depth: usize) -> bool {
+        match se {
+            ShapeExpr::Shape(_) => true,
+            ShapeExpr::ShapeAnd { shape_exprs } | ShapeExpr::ShapeOr { shape_exprs } => {
+                shape_exprs.iter().any(|e| self.is_shape(&e.se, depth))
+            }
+            ShapeExpr::Ref(label) if depth < MAX_REF_DEPTH => match self.lookup(label) {
+                Some(se) => self.is_shape(se, depth + 1),
+                None => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn lookup(&self, label: &ShapeExprLabel) -> Option<&'a ShapeExpr> {
+        label_key(label, self.prefixmap)
+            .ok()
+            .and_then(|key| self.shapes.get(&key).copied())
+    }
+
+    /// Constraints that can be violated by a node of the shape expression
+    ///
+    /// Only triple constraints that are always generated are considered, so the ones inside `OneOf`
+    /// or optional groups are skipped
+    fn violations(&mut self, se: &'a ShapeExpr, result: &mut Vec<Violation<'a>>, depth: usize) {
+        match se {
+            ShapeExpr::Shape(shape) => {
+                if shape.closed == Some(true) {
+                    result.push(Violation::Closed)
+                }
+                if let Some(expr) = &shape.expression {
+                    self.triple_expr_violations(&expr.te, result)
+                }
+            }
+            ShapeExpr::ShapeAnd { shape_exprs } => {
+                for e in shape_exprs {
+                    self.violations(&e.se, result, depth)
+                }
+            }
+            ShapeExpr::Ref(label) if depth < MAX_REF_DEPTH => {
+                if let Some(se) = self.lookup(label) {
+                    self.violations(se, result, depth + 1)
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn triple_expr_violations(&mut self, te: &'a TripleExpr, result: &mut Vec<Violation<'a>>) {
+        match te {
+            TripleExpr::EachOf {
+                expressions, min, ..
+            } if min.unwrap_or(1) > 0 => {
+                for e in expressions {
+                    self.triple_expr_violations(&e.te, result)
+                }
+            }
+            TripleExpr::TripleConstraint {
+                negated,
+                value_expr,
+                min,
+                max,
+                ..
+            } if negated != &Some(true) => {
+                if min.unwrap_or(1) > 0 {
+                    result.push(Violation::Omit(te))
+                }
+                let spec = self.value_spec(value_expr.as_deref(), 0);
+                if spec.as_ref().is_some_and(|s| s.can_be_violated()) {
+                    result.push(Violation::WrongValue(te))
+                }
+                let distinct = match value_expr {
+                    None => true,
+                    Some(_) => spec.is_some_and(|s| s.generates_distinct_values()),
+                };
+                if max.unwrap_or(1) != -1 && distinct {
+                    result.push(Violation::ExtraValue(te))
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn shape_expr(
+        &mut self,
+        node: &Object,
+        se: &'a ShapeExpr,
+        violation: Option<Violation<'a>>,
+        depth: usize,
+    ) -> Result<()> {
+        match se {
+            ShapeExpr::Shape(shape) => {
+                if let Some(expr) = &shape.expression {
+                    self.triple_expr(node, &expr.te, violation, depth)?
+                }
+                if shape.closed == Some(true) && matches!(violation, Some(Violation::Closed)) {
+                    let pred = self.values.namespace_iri("undeclaredProperty");
+                    let value = Object::literal(Literal::str("undeclared"));
+                    self.triples.push((node.clone(), pred, value))
+                }
+                for label in shape.extends.iter().flatten() {
+                    match self.lookup(label) {
+                        Some(se) if depth < MAX_REF_DEPTH => {
+                            self.shape_expr(node, se, violation, depth + 1)?
+                        }
+                        Some(_) => {}
+                        None => self.shape_not_found(label),
+                    }
+                }
+                Ok(())
+            }
+            ShapeExpr::ShapeAnd { shape_exprs } => {
+                for e in shape_exprs {
+                    self.shape_expr(node, &e.se, violation, depth)?
+                }
+                Ok(())
+            }
+            ShapeExpr::ShapeOr { shape_exprs } => {
+                if let Some(e) = self.values.choose(shape_exprs) {
+                    self.shape_expr(node, &e.se, violation, depth)?
+                }
+                Ok(())
+            }
+            ShapeExpr::ShapeNot { .. } => {
+                self.not_supported("ShapeNot");
+                Ok(())
+            }
+            ShapeExpr::Ref(label) => {
+                match self.lookup(label) {
+                    Some(se) if depth < MAX_REF_DEPTH => {
+                        self.shape_expr(node, se, violation, depth + 1)?
+                    }
+                    Some(_) => {}
+                    None => self.shape_not_found(label),
+                }
+                Ok(())
+            }
+            ShapeExpr::NodeConstraint(_) | ShapeExpr::External => Ok(()),
+        }
+    }
+
+    fn triple_expr(
+        &mut self,
+        node: &Object,
+        te: &'a TripleExpr,
+        violation: Option<Violation<'a>>,
+        depth: usize,
+    ) -> Result<()> {
+        match te {
+            TripleExpr::EachOf {
+                expressions,
+                min,
+                max,
+                ..
+            } => {
+                for _ in 0..self.cardinality(min, max) {
+                    for e in expressions {
+                        self.triple_expr(node, &e.te, violation, depth)?
+                    }
+                }
+                Ok(())
+            }
+            TripleExpr::OneOf {
+                expressions,
+                min,
+                max,
+                ..
+            } => {
+                for _ in 0..self.cardinality(min, max) {
+                    if let Some(e) = self.values.choose(expressions) {
+                        self.triple_expr(node, &e.te, violation, depth)?
+                    }
+                }
+                Ok(())
+            }
+            TripleExpr::TripleConstraint {
+                negated,
+                inverse,
+                predicate,
+                value_expr,
+                min,
+                max,
+                ..
+            } => {
+                if negated == &Some(true) {
+                    return Ok(());
+                }
+                let pred = self.prefixmap.resolve_iriref(predicate)?;
+                let target = violation.filter(|v| match v {
+                    Violation::Omit(t) | Violation::WrongValue(t) | Violation::ExtraValue(t) => {
+                        std::ptr::eq(*t, te)
+                    }
+                    Violation::Closed => false,
+                });
+                let count = match target {
+                    Some(Violation::Omit(_)) => 0,
+                    Some(Violation::ExtraValue(_)) => max.unwrap_or(1).max(0) as usize + 1,
+                    Some(Violation::WrongValue(_)) => self.cardinality(min, max).max(1),
+                    _ => self.cardinality(min, max),
+                };
+                for n in 0..count {
+                    let value = if n == 0 && matches!(target, Some(Violation::WrongValue(_))) {
+                        self.wrong_value(value_expr.as_deref())
+                    } else {
+                        self.value(value_expr.as_deref(), depth)?
+                    };
+                    if inverse == &Some(true) {
+                        let subject = match value {
+                            Object::Literal(_) => self.values.fresh_iri(),
+                            _ => value,
+                        };
+                        self.triples.push((subject, pred.clone(), node.clone()))
+                    } else {
+                        self.triples.push((node.clone(), pred.clone(), value))
+                    }
+                }
+                Ok(())
+            }
+            TripleExpr::TripleExprRef(_) => {
+                self.not_supported("references to triple expressions");
+                Ok(())
+            }
+        }
+    }
+
+    fn cardinality(&mut self, min: &Option<i32>, max: &Option<i32>) -> usize {
+        let min = min.unwrap_or(1).max(0) as usize;
+        let max = match max.unwrap_or(1) {
+            -1 => None,
+            max => Some(max.max(0) as usize),
+        };
+        self.values.cardinality(min, max)
+    }
+
+    /// Generates a value that conforms to a value expression
+    fn value(&mut self, value_expr: Option<&'a ShapeExpr>, depth: usize) -> Result<Object> {
+        match value_expr {
+            None => Ok(self.values.value(&ValueSpec::default())),
+            Some(ShapeExpr::NodeConstraint(nc)) => {
+                let spec = self.node_constraint_spec(nc);
+                Ok(self.values.value(&spec))
+            }
+            Some(ShapeExpr::Ref(label)) => {
+                let key = label_key(label, self.prefixmap)?;
+                match self.instances.get(&key) {
+                    Some(nodes) => {
+                        let nodes = nodes.clone();
+                        match self.values.choose(&nodes) {
+                            Some(node) => Ok(node.clone()),
+                            None => Ok(self.values.fresh_iri()),
+                        }
+                    }
+                    None => match self.lookup(label) {
+                        Some(se) if depth < MAX_REF_DEPTH => self.value(Some(se), depth + 1),
+                        Some(_) => Ok(self.values.fresh_iri()),
+                        None => {
+                            self.shape_not_found(label);
+                            Ok(self.values.fresh_iri())
+                        }
+                    },
+                }
+            }
+            Some(se @ ShapeExpr::Shape(_)) => {
+                let node = self.values.fresh_iri();
+                if depth < MAX_REF_DEPTH {
+                    self.shape_expr(&node, se, None, depth + 1)?;
+                }
+                Ok(node)
+            }
+            Some(ShapeExpr::ShapeAnd { shape_exprs }) => {
+                let se = shape_exprs
+                    .iter()
+                    .find(|e| self.is_shape(&e.se, depth))
+                    .or(shape_exprs.first());
+                match se {
+                    Some(e) => self.value(Some(&e.se), depth),
+                    None => Ok(self.values.fresh_iri()),
+                }
+            }
+            Some(ShapeExpr::ShapeOr { shape_exprs }) => match self.values.choose(shape_exprs) {
+                Some(e) => self.value(Some(&e.se), depth),
+                None => Ok(self.values.fresh_iri()),
+            },
+            Some(ShapeExpr::ShapeNot { .. }) | Some(ShapeExpr::External) => {
+                Ok(self.values.fresh_iri())
+            }
+        }
+    }
+
+    fn wrong_value(&mut self, value_expr: Option<&'a ShapeExpr>) -> Object {
+        self.value_spec(value_expr, 0)
+            .and_then(|spec| self.values.violating_value(&spec))
+            .unwrap_or_else(|| self.values.fresh_iri())
+    }
+
+    /// Constraints on the values of a value expression which is a node constraint
+    fn value_spec(&mut self, value_expr: Option<&'a ShapeExpr>, depth: usize) -> Option<ValueSpec> {
+        match value_expr {
+            Some(ShapeExpr::NodeConstraint(nc)) => Some(self.node_constraint_spec(nc)),
+            Some(ShapeExpr::Ref(label)) if depth < MAX_REF_DEPTH => match self.lookup(label) {
+                Some(se) => self.value_spec(Some(se), depth + 1),
+                None => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn node_constraint_spec(&mut self, nc: &NodeConstraint) -> ValueSpec {
+        let mut spec = ValueSpec {
+            kind: nc.node_kind().map(|kind| match kind {
+                NodeKind::Iri => ValueKind::Iri,
+                NodeKind::BNode => ValueKind::BNode,
+                NodeKind::NonLiteral => ValueKind::NonLiteral,
+                NodeKind::Literal => ValueKind::Literal,
+                NodeKind::Triple => ValueKind::Triple,
+            }),
+            ..Default::default()
+        };
+        if let Some(datatype) = nc.datatype() {
+            match self.prefixmap.resolve_iriref(&datatype) {
+                Ok(iri) => spec.datatype = Some(iri),
+                Err(_) => self.not_supported(&format!("datatype {datatype}")),
+            }
+        }
+        for facet in nc.xs_facet().unwrap_or_default() {
+            match facet {
+                XsFacet::StringFacet(StringFacet::Length(len)) => spec.length = Some(len),
+                XsFacet::StringFacet(StringFacet::MinLength(len)) => spec.min_length = Some(len),
+                XsFacet::StringFacet(StringFacet::MaxLength(len)) => spec.max_length = Some(len),
+                XsFacet::StringFacet(StringFacet::Pattern(_)) => self.not_supported("pattern"),
+                XsFacet::NumericFacet(NumericFacet::MinInclusive(n)) => {
+                    spec.min_inclusive = n.lexical_form().parse().ok()
+                }
+                XsFacet::NumericFacet(NumericFacet::MinExclusive(n)) => {
+                    spec.min_exclusive = n.lexical_form().parse().ok()
+                }
+                XsFacet::NumericFacet(NumericFacet::MaxInclusive(n)) => {
+                    spec.max_inclusive = n.lexical_form().parse().ok()
+                }
+                XsFacet::NumericFacet(NumericFacet::MaxExclusive(n)) => {
+                    spec.max_exclusive = n.lexical_form().parse().ok()
+                }
+                XsFacet::NumericFacet(NumericFacet::TotalDigits(_)) => {
+                    self.not_supported("totalDigits")
+                }
+                XsFacet::NumericFacet(NumericFacet::FractionDigits(_)) => {
+                    self.not_supported("fractionDigits")
+                }
+            }
+        }
+        for value in nc.values().unwrap_or_default() {
+            match value {
+                ValueSetValue::ObjectValue(ObjectValue::IriRef(iri_ref)) => {
+                    match self.prefixmap.resolve_iriref(&iri_ref) {
+                        Ok(iri) => spec.values.push(Object::iri(iri)),
+                        Err(_) => self.not_supported(&format!("value {iri_ref}")),
+                    }
+                }
+                ValueSetValue::ObjectValue(ObjectValue::Literal(lit)) => {
+                    spec.values.push(Object::literal(lit))
+                }
+                ValueSetValue::IriStem { stem } => match self.prefixmap.resolve_iriref(&stem) {
+                    Ok(iri) => spec.iri_stems.push(iri.as_str().to_string()),
+                    Err(_) => self.not_supported(&format!("IRI stem {stem}")),
+                },
+                ValueSetValue::LiteralStem { stem } => spec
+                    .values
+                    .push(Object::literal(Literal::str(&format!("{stem}value")))),
+                ValueSetValue::Language { language_tag } => {
+                    spec.languages.push(language_tag.value())
+                }
+                ValueSetValue::LanguageStem { stem } => spec.languages.push(stem.value()),
+                _ => self.not_supported("stem ranges"),
+            }
+        }
+        spec
+    }
+
+    fn not_supported(&mut self, construct: &str) {
+        let warning = DataGeneratorWarning::NotSupported {
+            shape: self.current_shape.clone(),
+            construct: construct.to_string(),
+        };
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning)
+        }
+    }
+
+    fn shape_not_found(&mut self, label: &ShapeExprLabel) {
+        let warning = DataGeneratorWarning::ShapeNotFound {
+            shape: label.to_string(),
+        };
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning)
+        }
+    }
+}
+
+/// Key of a shape label, which is the full IRI for IRIs
+fn label_key(label: &ShapeExprLabel, prefixmap: &PrefixMap) -> Result<String> {
+    match label {
+        ShapeExprLabel::IriRef { value } => {
+            Ok(prefixmap.resolve_iriref(value)?.as_str().to_string())
+        }
+        ShapeExprLabel::BNode { value } => Ok(value.to_string()),
+        ShapeExprLabel::Start => Ok("START".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shex_ast::Node;
+    use shex_ast::{compiled::compiled_schema::CompiledSchema, compiled::shape_label::ShapeLabel};
+    use shex_compact::ShExParser;
+    use shex_validation::{ResultValue, Validator, ValidatorConfig};
+    use srdf::srdf_graph::SRDFGraph;
+
+    const SCHEMA: &str = r#"prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:Person { :name xsd:string ; :status [ :active :inactive ] ; :knows IRI * ; :worksFor @:Org ? }
+:Org { :name xsd:string MINLENGTH 3 }
+"#;
+
+    /// Generates data for `SCHEMA` and validates each generated node against its shape
+    fn generate_and_validate(config: &DataGeneratorConfig) -> Vec<ResultValue> {
+        let schema = ShExParser::parse(SCHEMA, None).unwrap();
+        let mut rdf = SRDFGraph::new();
+        ShEx2Data::new(config).generate(&schema, &mut rdf).unwrap();
+        let mut compiled = CompiledSchema::new();
+        compiled.from_schema_json(&schema).unwrap();
+        let mut validator = Validator::new(compiled, &ValidatorConfig::default());
+        let mut results = Vec::new();
+        for shape in ["Person", "Org"] {
+            let label =
+                ShapeLabel::iri(IriS::new_unchecked(&format!("http://example.org/{shape}")));
+            for n in 1..=3 {
+                let node = Node::iri(IriS::new_unchecked(&format!(
+                    "{}{shape}{n}",
+                    config.data_namespace()
+                )));
+                validator.validate_node_shape(&node, &label, &rdf).unwrap();
+                results.push(validator.get_result(&node, &label).unwrap())
+            }
+        }
+        results
+    }
+
+    #[test]
+    fn generated_data_conforms() {
+        let config = DataGeneratorConfig::default()
+            .with_seed(7)
+            .with_instances(3);
+        assert_eq!(generate_and_validate(&config), vec![ResultValue::Ok; 6])
+    }
+
+    #[test]
+    fn generated_violations_dont_conform() {
+        let config = DataGeneratorConfig::default()
+            .with_seed(7)
+            .with_instances(3)
+            .with_violations(true);
+        assert_eq!(generate_and_validate(&config), vec![ResultValue::Failed; 6])
+    }
+}
@@ -0,0 +1,383 @@
+//! Generation of values that satisfy (or violate) the constraints on the objects of a triple
+//!
+//! Both ShEx node constraints and SHACL value components are translated to a [`ValueSpec`]
+//! so the ShEx and SHACL generators share the way values are created
+use iri_s::IriS;
+use prefixmap::{IriRef, PrefixMap};
+use srdf::{lang::Lang, literal::Literal, Object, SRDFBuilder, RDF, XSD, XSD_INTEGER_STR};
+
+use super::{DataGeneratorConfig, DataGeneratorError};
+
+/// Kinds of nodes, following ShEx node kinds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum ValueKind {
+    Iri,
+    BNode,
+    NonLiteral,
+    Literal,
+    Triple,
+}
+
+/// Constraints on a value
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ValueSpec {
+    pub(crate) kind: Option<ValueKind>,
+    pub(crate) datatype: Option<IriS>,
+    pub(crate) values: Vec<Object>,
+    pub(crate) iri_stems: Vec<String>,
+    pub(crate) languages: Vec<String>,
+    pub(crate) min_inclusive: Option<f64>,
+    pub(crate) min_exclusive: Option<f64>,
+    pub(crate) max_inclusive: Option<f64>,
+    pub(crate) max_exclusive: Option<f64>,
+    pub(crate) length: Option<usize>,
+    pub(crate) min_length: Option<usize>,
+    pub(crate) max_length: Option<usize>,
+}
+
+impl ValueSpec {
+    /// Checks if there is some constraint on the values, otherwise any value is valid
+    pub(crate) fn is_constrained(&self) -> bool {
+        self.kind.is_some()
+            || self.datatype.is_some()
+            || !self.values.is_empty()
+            || !self.iri_stems.is_empty()
+            || !self.languages.is_empty()
+            || self.has_range()
+            || self.has_length()
+    }
+
+    /// Checks if two generated values are always different, so the number of generated values
+    /// is the number of triples added
+    pub(crate) fn generates_distinct_values(&self) -> bool {
+        self.values.is_empty()
+            && !self.has_range()
+            && !self.has_length()
+            && match &self.datatype {
+                None => true,
+                Some(dt) => dt.as_str() == xsd("string") || dt.as_str() == rdf("langString"),
+            }
+    }
+
+    /// Checks if [`ValueGenerator::violating_value`] can generate a value that doesn't satisfy the constraints
+    pub(crate) fn can_be_violated(&self) -> bool {
+        self.kind.is_some()
+            || self.datatype.is_some()
+            || !self.values.is_empty()
+            || !self.iri_stems.is_empty()
+            || !self.languages.is_empty()
+            || self.has_length()
+    }
+
+    fn has_range(&self) -> bool {
+        self.min_inclusive.is_some()
+            || self.min_exclusive.is_some()
+            || self.max_inclusive.is_some()
+            || self.max_exclusive.is_some()
+    }
+
+    fn has_length(&self) -> bool {
+        self.length.is_some() || self.min_length.is_some() || self.max_length.is_some()
+    }
+}
+
+/// Source of the random choices and of the fresh nodes of a generation
+pub(crate) struct ValueGenerator {
+    rng: fastrand::Rng,
+    namespace: String,
+    max_repeat: usize,
+    counter: usize,
+}
+
+impl ValueGenerator {
+    pub(crate) fn new(config: &DataGeneratorConfig) -> ValueGenerator {
+        ValueGenerator {
+            rng: fastrand::Rng::with_seed(config.seed()),
+            namespace: config.data_namespace(),
+            max_repeat: config.max_repeat(),
+            counter: 0,
+        }
+    }
+
+    /// IRI of the `n`-th node generated for a shape whose local name is `name`
+    pub(crate) fn node_iri(&self, name: &str, n: usize) -> Object {
+        Object::iri(IriS::new_unchecked(&format!("{}{name}{n}", self.namespace)))
+    }
+
+    pub(crate) fn fresh_iri(&mut self) -> Object {
+        self.counter += 1;
+        Object::iri(IriS::new_unchecked(&format!(
+            "{}node{}",
+            self.namespace, self.counter
+        )))
+    }
+
+    pub(crate) fn fresh_bnode(&mut self) -> Object {
+        self.counter += 1;
+        Object::bnode(format!("b{}", self.counter))
+    }
+
+    /// Triple term whose subject and object are fresh IRIs
+    pub(crate) fn fresh_triple(&mut self) -> Object {
+        let subject = self.fresh_iri();
+        let object = self.fresh_iri();
+        Object::triple(subject, self.namespace_iri("p"), object)
+    }
+
+    pub(crate) fn namespace_iri(&self, local: &str) -> IriS {
+        IriS::new_unchecked(&format!("{}{local}", self.namespace))
+    }
+
+    pub(crate) fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.rng.usize(..items.len()))
+        }
+    }
+
+    /// Number of values for a cardinality where `max = None` means unbounded
+    pub(crate) fn cardinality(&mut self, min: usize, max: Option<usize>) -> usize {
+        let max = max.unwrap_or(min + self.max_repeat).max(min);
+        self.rng.usize(min..=max)
+    }
+
+    pub(crate) fn value(&mut self, spec: &ValueSpec) -> Object {
+        if let Some(value) = self.choose(&spec.values) {
+            return value.clone();
+        }
+        if let Some(stem) = self.choose(&spec.iri_stems).cloned() {
+            self.counter += 1;
+            return Object::iri(IriS::new_unchecked(&format!("{stem}{}", self.counter)));
+        }
+        if let Some(lang) = self.choose(&spec.languages).cloned() {
+            let str = self.string(spec);
+            return Object::literal(Literal::lang_str(&str, Lang::new(&lang)));
+        }
+        match &spec.datatype {
+            Some(datatype) => Object::literal(self.datatype_literal(datatype, spec)),
+            None => match spec.kind {
+                Some(ValueKind::Iri) | Some(ValueKind::NonLiteral) => self.fresh_iri(),
+                Some(ValueKind::BNode) => self.fresh_bnode(),
+                Some(ValueKind::Triple) => self.fresh_triple(),
+                Some(ValueKind::Literal) | None => {
+                    Object::literal(Literal::str(&self.string(spec)))
+                }
+            },
+        }
+    }
+
+    /// Generates a value that doesn't satisfy `spec` if there is some constraint that can be violated
+    pub(crate) fn violating_value(&mut self, spec: &ValueSpec) -> Option<Object> {
+        if !spec.values.is_empty() || !spec.iri_stems.is_empty() {
+            return Some(Object::literal(Literal::str("invalid value")));
+        }
+        if !spec.languages.is_empty() {
+            return Some(Object::literal(Literal::str("no language")));
+        }
+        if let Some(datatype) = &spec.datatype {
+            return if datatype.as_str() == XSD_INTEGER_STR {
+                Some(Object::literal(Literal::str("not an integer")))
+            } else {
+                Some(Object::literal(Literal::datatype(
+                    "1",
+                    &IriRef::Iri(IriS::new_unchecked(XSD_INTEGER_STR)),
+                )))
+            };
+        }
+        match spec.kind {
+            Some(ValueKind::Iri) | Some(ValueKind::NonLiteral) => {
+                return Some(Object::literal(Literal::str("not an IRI")))
+            }
+            Some(ValueKind::Triple) => return Some(self.fresh_iri()),
+            Some(ValueKind::BNode) | Some(ValueKind::Literal) => return Some(self.fresh_iri()),
+            None => {}
+        }
+        match (spec.length, spec.min_length, spec.max_length) {
+            (Some(len), _, _) | (_, _, Some(len)) => {
+                Some(Object::literal(Literal::str(&"x".repeat(len + 1))))
+            }
+            (_, Some(len), _) if len > 0 => {
+                Some(Object::literal(Literal::str(&"x".repeat(len - 1))))
+            }
+            _ => None,
+        }
+    }
+
+    fn string(&mut self, spec: &ValueSpec) -> String {
+        let len = match (spec.length, spec.min_length, spec.max_length) {
+            (Some(len), _, _) => len,
+            (None, min, max) => {
+                let min = min.unwrap_or(4);
+                let max = max.unwrap_or(min.max(4) + 6).max(min);
+                self.rng.usize(min..=max)
+            }
+        };
+        (0..len).map(|_| self.rng.lowercase()).collect()
+    }
+
+    fn datatype_literal(&mut self, datatype: &IriS, spec: &ValueSpec) -> Literal {
+        let lexical_form = match datatype.as_str().strip_prefix(XSD) {
+            Some("string") => return Literal::str(&self.string(spec)),
+            Some("boolean") => self.rng.bool().to_string(),
+            Some(
+                "integer" | "int" | "long" | "short" | "byte" | "nonNegativeInteger"
+                | "positiveInteger" | "unsignedInt" | "unsignedLong" | "unsignedShort"
+                | "unsignedByte",
+            ) => {
+                let (min, max) = self.integer_range(datatype, spec);
+                self.rng.i64(min..=max).to_string()
+            }
+            Some("negativeInteger" | "nonPositiveInteger") => {
+                let (min, max) = self.integer_range(datatype, spec);
+                self.rng.i64(min..=max).to_string()
+            }
+            Some("decimal" | "double" | "float") => {
+                let min = spec
+                    .min_inclusive
+                    .or(spec.min_exclusive.map(|m| m + 0.01))
+                    .unwrap_or(0.0);
+                let max = spec
+                    .max_inclusive
+                    .or(spec.max_exclusive.map(|m| m - 0.01))
+                    .unwrap_or(min + 100.0)
+                    .max(min);
+                format!("{:.2}", min + self.rng.f64() * (max - min))
+            }
+            Some("date") => self.date(),
+            Some("dateTime") => format!("{}T{:02}:00:00", self.date(), self.rng.u8(0..24)),
+            Some("anyURI") => match self.fresh_iri() {
+                Object::Iri(iri) => iri.to_string(),
+                other => other.to_string(),
+            },
+            _ if datatype.as_str() == rdf("langString") => {
+                return Literal::lang_str(&self.string(spec), Lang::new("en"))
+            }
+            _ => self.string(spec),
+        };
+        Literal::datatype(&lexical_form, &IriRef::Iri(datatype.clone()))
+    }
+
+    fn integer_range(&self, datatype: &IriS, spec: &ValueSpec) -> (i64, i64) {
+        let (default_min, default_max) = match datatype.as_str().strip_prefix(XSD) {
+            Some("positiveInteger") => (1, 100),
+            Some("negativeInteger") => (-100, -1),
+            Some("nonPositiveInteger") => (-100, 0),
+            Some("byte") => (0, 127),
+            _ => (0, 100),
+        };
+        let min = spec
+            .min_inclusive
+            .map(|m| m.ceil() as i64)
+            .or(spec.min_exclusive.map(|m| m.floor() as i64 + 1));
+        let max = spec
+            .max_inclusive
+            .map(|m| m.floor() as i64)
+            .or(spec.max_exclusive.map(|m| m.ceil() as i64 - 1));
+        match (min, max) {
+            (Some(min), Some(max)) => (min, max.max(min)),
+            (Some(min), None) => (min, min + (default_max - default_min)),
+            (None, Some(max)) => (max - (default_max - default_min), max),
+            (None, None) => (default_min, default_max),
+        }
+    }
+
+    fn date(&mut self) -> String {
+        format!(
+            "{}-{:02}-{:02}",
+            self.rng.u16(1970..2030),
+            self.rng.u8(1..=12),
+            self.rng.u8(1..=28)
+        )
+    }
+}
+
+/// Adds the generated triples to `rdf` with the prefixes of the schema and a `data` prefix for the generated nodes
+pub(crate) fn write_triples<RDF>(
+    triples: &[(Object, IriS, Object)],
+    prefixmap: &PrefixMap,
+    config: &DataGeneratorConfig,
+    rdf: &mut RDF,
+) -> Result<(), DataGeneratorError>
+where
+    RDF: SRDFBuilder,
+{
+    let rdf_error = |e: RDF::Err| DataGeneratorError::RDFError {
+        error: e.to_string(),
+    };
+    let mut prefixmap = prefixmap.clone();
+    let namespace = IriS::new_unchecked(&config.data_namespace());
+    if prefixmap.find("data").is_none() && !prefixmap.iter().any(|(_, iri)| *iri == namespace) {
+        prefixmap.insert("data", &namespace)?;
+    }
+    rdf.add_prefix_map(prefixmap).map_err(rdf_error)?;
+    for (subject, predicate, object) in triples {
+        let subject =
+            RDF::object_as_subject(subject).ok_or_else(|| DataGeneratorError::NoSubject {
+                node: subject.to_string(),
+            })?;
+        rdf.add_triple(
+            &subject,
+            &RDF::iri_s2iri(predicate),
+            &RDF::object_as_term(object),
+        )
+        .map_err(rdf_error)?;
+    }
+    Ok(())
+}
+
+pub(crate) fn xsd(local: &str) -> String {
+    format!("{XSD}{local}")
+}
+
+fn rdf(local: &str) -> String {
+    format!("{RDF}{local}")
+}
+
+/// Local name of an IRI used to name the nodes generated for a shape
+pub(crate) fn local_name(iri: &str) -> String {
+    let name = iri.rsplit(['#', '/', ':']).next().unwrap_or(iri);
+    name.chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_in_range() {
+        let mut generator = ValueGenerator::new(&DataGeneratorConfig::default());
+        let spec = ValueSpec {
+            datatype: Some(IriS::new_unchecked(XSD_INTEGER_STR)),
+            min_inclusive: Some(10.0),
+            max_exclusive: Some(13.0),
+            ..Default::default()
+        };
+        for _ in 0..20 {
+            match generator.value(&spec) {
+                Object::Literal(lit) => {
+                    let n: i64 = lit.lexical_form().parse().unwrap();
+                    assert!((10..13).contains(&n))
+                }
+                other => panic!("Expected literal, found {other}"),
+            }
+        }
+    }
+
+    #[test]
+    fn same_seed_same_values() {
+        let config = DataGeneratorConfig::default().with_seed(42);
+        let spec = ValueSpec {
+            min_length: Some(3),
+            max_length: Some(8),
+            ..Default::default()
+        };
+        let mut g1 = ValueGenerator::new(&config);
+        let mut g2 = ValueGenerator::new(&config);
+        let v1: Vec<_> = (0..5).map(|_| g1.value(&spec)).collect();
+        let v2: Vec<_> = (0..5).map(|_| g2.value(&spec)).collect();
+        assert_eq!(v1, v2)
+    }
+}
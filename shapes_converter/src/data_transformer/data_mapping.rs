@@ -0,0 +1,147 @@
+use std::{collections::HashMap, path::Path, str::FromStr};
+
+use indexmap::IndexMap;
+use iri_s::IriS;
+use prefixmap::PrefixMap;
+use serde_derive::{Deserialize, Serialize};
+
+use super::DataTransformerError;
+
+/// Declarative mapping between the shapes of a source and a target ShEx schema
+///
+/// Shapes and predicates are written as prefixed names or as IRIs between angle brackets.
+/// The prefixes of the mapping are added to the ones declared by each schema.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+pub struct DataMapping {
+    /// Prefix declarations used by the names of the mapping
+    prefixes: Option<HashMap<String, String>>,
+
+    /// Namespaces of the source node IRIs replaced by namespaces of the target data
+    namespaces: Option<IndexMap<String, String>>,
+
+    /// Correspondences between source and target shapes
+    shapes: Vec<ShapeMapping>,
+}
+
+/// Correspondence between a shape of the source schema and a shape of the target schema
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct ShapeMapping {
+    pub source: String,
+    pub target: String,
+
+    /// Classes added with `rdf:type` to the nodes of the target shape
+    pub types: Option<Vec<String>>,
+
+    /// Correspondences between the predicates of the triple constraints of both shapes
+    pub properties: Vec<PropertyMapping>,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct PropertyMapping {
+    pub source: String,
+    pub target: String,
+}
+
+impl DataMapping {
+    pub fn new() -> DataMapping {
+        DataMapping::default()
+    }
+
+    /// Reads a mapping in YAML (which also accepts JSON)
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<DataMapping, DataTransformerError> {
+        let path_name = path.as_ref().display().to_string();
+        let f =
+            std::fs::File::open(path).map_err(|e| DataTransformerError::MappingFromPathError {
+                path: path_name.clone(),
+                error: e,
+            })?;
+        let mapping: DataMapping =
+            serde_yml::from_reader(f).map_err(|e| DataTransformerError::MappingFromYAMLError {
+                path: path_name.clone(),
+                error: e,
+            })?;
+        Ok(mapping)
+    }
+
+    pub fn with_shape(mut self, shape: ShapeMapping) -> Self {
+        self.shapes.push(shape);
+        self
+    }
+
+    pub fn with_namespace(mut self, source: &str, target: &str) -> Self {
+        self.namespaces
+            .get_or_insert_with(IndexMap::new)
+            .insert(source.to_string(), target.to_string());
+        self
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = &ShapeMapping> {
+        self.shapes.iter()
+    }
+
+    pub fn namespaces(&self) -> impl Iterator<Item = (&String, &String)> {
+        self.namespaces.iter().flat_map(|ns| ns.iter())
+    }
+
+    /// Prefix map of a schema extended with the prefixes of the mapping
+    pub fn prefixmap(
+        &self,
+        schema_prefixmap: PrefixMap,
+    ) -> Result<PrefixMap, DataTransformerError> {
+        let mut prefixmap = schema_prefixmap;
+        if let Some(prefixes) = &self.prefixes {
+            for (alias, iri) in prefixes {
+                prefixmap.insert(alias, &IriS::from_str(iri)?)?
+            }
+        }
+        Ok(prefixmap)
+    }
+}
+
+impl FromStr for DataMapping {
+    type Err = DataTransformerError;
+
+    fn from_str(str: &str) -> Result<DataMapping, DataTransformerError> {
+        serde_yml::from_str(str).map_err(|e| DataTransformerError::MappingFromYAMLError {
+            path: "<string>".to_string(),
+            error: e,
+        })
+    }
+}
+
+impl ShapeMapping {
+    pub fn new(source: &str, target: &str) -> ShapeMapping {
+        ShapeMapping {
+            source: source.to_string(),
+            target: target.to_string(),
+            types: None,
+            properties: Vec::new(),
+        }
+    }
+
+    pub fn with_type(mut self, class: &str) -> Self {
+        self.types
+            .get_or_insert_with(Vec::new)
+            .push(class.to_string());
+        self
+    }
+
+    pub fn with_property(mut self, source: &str, target: &str) -> Self {
+        self.properties.push(PropertyMapping {
+            source: source.to_string(),
+            target: target.to_string(),
+        });
+        self
+    }
+}
+
+/// Resolves a name of the mapping, which can be an IRI between angle brackets or a prefixed name
+pub(crate) fn resolve_name(
+    name: &str,
+    prefixmap: &PrefixMap,
+) -> Result<IriS, DataTransformerError> {
+    match name.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+        Some(iri) => Ok(IriS::from_str(iri)?),
+        None => Ok(prefixmap.resolve(name)?),
+    }
+}
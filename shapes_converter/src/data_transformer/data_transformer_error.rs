@@ -0,0 +1,55 @@
+use iri_s::IriSError;
+use prefixmap::PrefixMapError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DataTransformerError {
+    #[error("Error reading data mapping from path {path}: {error}")]
+    MappingFromPathError { path: String, error: std::io::Error },
+
+    #[error("Error reading data mapping in YAML from path {path}: {error}")]
+    MappingFromYAMLError {
+        path: String,
+        error: serde_yml::Error,
+    },
+
+    #[error("Shape {shape} of the mapping not found in the {schema} schema")]
+    ShapeNotFound { shape: String, schema: String },
+
+    #[error("Predicate {predicate} of the mapping is not used by the triple constraints of shape {shape} in the {schema} schema")]
+    PredicateNotFound {
+        predicate: String,
+        shape: String,
+        schema: String,
+    },
+
+    #[error("Transformed node {node} can't be the subject of a triple")]
+    NoSubject { node: String },
+
+    #[error("Error adding transformed triples to RDF data: {error}")]
+    RDFError { error: String },
+
+    #[error(transparent)]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
+
+    #[error(transparent)]
+    IriSError {
+        #[from]
+        err: IriSError,
+    },
+}
+
+/// Parts of the source data that are not covered by the mapping and are left out of the target data
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum DataTransformerWarning {
+    #[error(
+        "Nodes conforming to shape {shape} are not transformed because the shape is not mapped"
+    )]
+    UnmappedShape { shape: String },
+
+    #[error("Values of predicate {predicate} in shape {shape} are not transformed because the predicate is not mapped")]
+    UnmappedPredicate { predicate: String, shape: String },
+}
@@ -0,0 +1,10 @@
+//! Transformation of RDF data between two ShEx schemas
+//!
+//!
+pub mod data_mapping;
+pub mod data_transformer_error;
+pub mod shex_transformer;
+
+pub use data_mapping::*;
+pub use data_transformer_error::*;
+pub use shex_transformer::*;
@@ -0,0 +1,417 @@
+//! Struct that transforms RDF data described by a ShEx schema into data described by another one
+//!
+//! The source data is validated first, so the transformer works on the shape matches of the validator.
+//! Each match of a mapped shape becomes a node of the target shape whose arcs are the matched arcs
+//! with the predicates renamed by the mapping, in the same way as ShExMap binds and materialises values.
+use std::collections::{HashMap, HashSet};
+
+use iri_s::IriS;
+use prefixmap::PrefixMap;
+use shex_ast::{
+    compiled::shape_label::ShapeLabel, Node, Schema, ShapeExpr, ShapeExprLabel, TripleExpr,
+};
+use shex_validation::ShapeMatch;
+use srdf::{Object, SRDFBuilder, RDF_TYPE};
+
+use super::data_mapping::resolve_name;
+use super::{DataMapping, DataTransformerError, DataTransformerWarning};
+
+type Result<T> = std::result::Result<T, DataTransformerError>;
+
+/// Maximum number of nested references followed when collecting the predicates of a shape
+const MAX_REF_DEPTH: usize = 16;
+
+pub struct ShExTransformer {
+    mapping: DataMapping,
+    warnings: Vec<DataTransformerWarning>,
+    target_shapemap: Vec<(Node, ShapeLabel)>,
+}
+
+/// Shape mapping with its names resolved against the prefixes of the schemas
+struct ResolvedShape {
+    target: IriS,
+    types: Vec<IriS>,
+    properties: HashMap<IriS, IriS>,
+}
+
+impl ShExTransformer {
+    pub fn new(mapping: &DataMapping) -> ShExTransformer {
+        ShExTransformer {
+            mapping: mapping.clone(),
+            warnings: Vec::new(),
+            target_shapemap: Vec::new(),
+        }
+    }
+
+    /// Parts of the source data left out of the last transformation
+    pub fn warnings(&self) -> impl Iterator<Item = &DataTransformerWarning> {
+        self.warnings.iter()
+    }
+
+    /// Node/shape pairs of the last transformation that are expected to conform to the target schema
+    pub fn target_shapemap(&self) -> impl Iterator<Item = &(Node, ShapeLabel)> {
+        self.target_shapemap.iter()
+    }
+
+    /// Adds to `rdf` the target triples obtained from the matches of the source data against `source`
+    ///
+    /// The shapes and predicates of the mapping are checked against the triple constraints of both schemas before transforming
+    pub fn transform<RDF>(
+        &mut self,
+        matches: &[ShapeMatch],
+        source: &Schema,
+        target: &Schema,
+        rdf: &mut RDF,
+    ) -> Result<()>
+    where
+        RDF: SRDFBuilder,
+    {
+        self.warnings = Vec::new();
+        self.target_shapemap = Vec::new();
+        let source_prefixmap = self
+            .mapping
+            .prefixmap(source.prefixmap().unwrap_or_default())?;
+        let target_prefixmap = self
+            .mapping
+            .prefixmap(target.prefixmap().unwrap_or_default())?;
+        let shapes = self.resolve_shapes(source, &source_prefixmap, target, &target_prefixmap)?;
+        let mut triples = Vec::new();
+        let mut transformed = HashSet::new();
+        for m in matches {
+            let source_shape = match &m.shape {
+                ShapeLabel::Iri(iri) => iri,
+                _ => continue,
+            };
+            let shape = match shapes.get(source_shape) {
+                Some(shape) => shape,
+                None => {
+                    self.warn(DataTransformerWarning::UnmappedShape {
+                        shape: source_prefixmap.qualify(source_shape),
+                    });
+                    continue;
+                }
+            };
+            if !transformed.insert((m.node.clone(), source_shape.clone())) {
+                continue;
+            }
+            let node = self.rename(m.node.as_object());
+            for class in &shape.types {
+                triples.push((node.clone(), RDF_TYPE.clone(), Object::iri(class.clone())))
+            }
+            for (pred, value) in &m.arcs {
+                match shape.properties.get(pred.iri()) {
+                    Some(target_pred) => triples.push((
+                        node.clone(),
+                        target_pred.clone(),
+                        self.rename(value.as_object()),
+                    )),
+                    None => self.warn(DataTransformerWarning::UnmappedPredicate {
+                        predicate: source_prefixmap.qualify(pred.iri()),
+                        shape: source_prefixmap.qualify(source_shape),
+                    }),
+                }
+            }
+            self.target_shapemap
+                .push((Node::from(node), ShapeLabel::iri(shape.target.clone())));
+        }
+        write_triples(&triples, target_prefixmap, rdf)
+    }
+
+    fn resolve_shapes(
+        &self,
+        source: &Schema,
+        source_prefixmap: &PrefixMap,
+        target: &Schema,
+        target_prefixmap: &PrefixMap,
+    ) -> Result<HashMap<IriS, ResolvedShape>> {
+        let source_preds = SchemaPredicates::new(source)?;
+        let target_preds = SchemaPredicates::new(target)?;
+        let mut shapes = HashMap::new();
+        for shape_mapping in self.mapping.shapes() {
+            let source_shape = resolve_name(&shape_mapping.source, source_prefixmap)?;
+            let target_shape = resolve_name(&shape_mapping.target, target_prefixmap)?;
+            let source_shape_preds = source_preds.predicates(&source_shape, "source")?;
+            let target_shape_preds = target_preds.predicates(&target_shape, "target")?;
+            let mut properties = HashMap::new();
+            for property in &shape_mapping.properties {
+                let source_pred = resolve_name(&property.source, source_prefixmap)?;
+                let target_pred = resolve_name(&property.target, target_prefixmap)?;
+                check_predicate(
+                    &source_pred,
+                    &source_shape_preds,
+                    &shape_mapping.source,
+                    "source",
+                )?;
+                check_predicate(
+                    &target_pred,
+                    &target_shape_preds,
+                    &shape_mapping.target,
+                    "target",
+                )?;
+                properties.insert(source_pred, target_pred);
+            }
+            let mut types = Vec::new();
+            for class in shape_mapping.types.iter().flatten() {
+                types.push(resolve_name(class, target_prefixmap)?)
+            }
+            shapes.insert(
+                source_shape,
+                ResolvedShape {
+                    target: target_shape,
+                    types,
+                    properties,
+                },
+            );
+        }
+        Ok(shapes)
+    }
+
+    /// Replaces the namespace of an IRI by the target one declared in the mapping
+    fn rename(&self, object: &Object) -> Object {
+        if let Object::Iri(iri) = object {
+            for (source_ns, target_ns) in self.mapping.namespaces() {
+                if let Some(local) = iri.as_str().strip_prefix(source_ns.as_str()) {
+                    return Object::iri(IriS::new_unchecked(&format!("{target_ns}{local}")));
+                }
+            }
+        }
+        object.clone()
+    }
+
+    fn warn(&mut self, warning: DataTransformerWarning) {
+        if !self.warnings.contains(&warning) {
+            self.warnings.push(warning)
+        }
+    }
+}
+
+fn check_predicate(pred: &IriS, preds: &HashSet<IriS>, shape: &str, schema: &str) -> Result<()> {
+    if preds.contains(pred) {
+        Ok(())
+    } else {
+        Err(DataTransformerError::PredicateNotFound {
+            predicate: pred.to_string(),
+            shape: shape.to_string(),
+            schema: schema.to_string(),
+        })
+    }
+}
+
+/// Predicates of the triple constraints of the shapes of a schema
+struct SchemaPredicates {
+    prefixmap: PrefixMap,
+    shapes: HashMap<IriS, ShapeExpr>,
+}
+
+impl SchemaPredicates {
+    fn new(schema: &Schema) -> Result<SchemaPredicates> {
+        let prefixmap = schema.prefixmap().unwrap_or_default();
+        let mut shapes = HashMap::new();
+        for decl in schema.shapes().unwrap_or_default() {
+            if let ShapeExprLabel::IriRef { value } = &decl.id {
+                shapes.insert(prefixmap.resolve_iriref(value)?, decl.shape_expr);
+            }
+        }
+        Ok(SchemaPredicates { prefixmap, shapes })
+    }
+
+    fn predicates(&self, shape: &IriS, schema: &str) -> Result<HashSet<IriS>> {
+        match self.shapes.get(shape) {
+            Some(se) => {
+                let mut preds = HashSet::new();
+                self.shape_expr(se, &mut preds, 0)?;
+                Ok(preds)
+            }
+            None => Err(DataTransformerError::ShapeNotFound {
+                shape: shape.to_string(),
+                schema: schema.to_string(),
+            }),
+        }
+    }
+
+    fn shape_expr(&self, se: &ShapeExpr, preds: &mut HashSet<IriS>, depth: usize) -> Result<()> {
+        match se {
+            ShapeExpr::Shape(shape) => {
+                if let Some(expr) = &shape.expression {
+                    self.triple_expr(&expr.te, preds)?
+                }
+                Ok(())
+            }
+            ShapeExpr::ShapeAnd { shape_exprs } | ShapeExpr::ShapeOr { shape_exprs } => {
+                for e in shape_exprs {
+                    self.shape_expr(&e.se, preds, depth)?
+                }
+                Ok(())
+            }
+            ShapeExpr::Ref(ShapeExprLabel::IriRef { value }) if depth < MAX_REF_DEPTH => {
+                let iri = self.prefixmap.resolve_iriref(value)?;
+                if let Some(se) = self.shapes.get(&iri) {
+                    self.shape_expr(se, preds, depth + 1)?
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn triple_expr(&self, te: &TripleExpr, preds: &mut HashSet<IriS>) -> Result<()> {
+        match te {
+            TripleExpr::EachOf { expressions, .. } | TripleExpr::OneOf { expressions, .. } => {
+                for e in expressions {
+                    self.triple_expr(&e.te, preds)?
+                }
+                Ok(())
+            }
+            TripleExpr::TripleConstraint { predicate, .. } => {
+                preds.insert(self.prefixmap.resolve_iriref(predicate)?);
+                Ok(())
+            }
+            TripleExpr::TripleExprRef(_) => Ok(()),
+        }
+    }
+}
+
+fn write_triples<RDF>(
+    triples: &[(Object, IriS, Object)],
+    prefixmap: PrefixMap,
+    rdf: &mut RDF,
+) -> Result<()>
+where
+    RDF: SRDFBuilder,
+{
+    let rdf_error = |e: RDF::Err| DataTransformerError::RDFError {
+        error: e.to_string(),
+    };
+    rdf.add_prefix_map(prefixmap).map_err(rdf_error)?;
+    for (subject, predicate, object) in triples {
+        let subject =
+            RDF::object_as_subject(subject).ok_or_else(|| DataTransformerError::NoSubject {
+                node: subject.to_string(),
+            })?;
+        rdf.add_triple(
+            &subject,
+            &RDF::iri_s2iri(predicate),
+            &RDF::object_as_term(object),
+        )
+        .map_err(rdf_error)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shex_ast::compiled::compiled_schema::CompiledSchema;
+    use shex_compact::ShExParser;
+    use shex_validation::{ResultValue, Validator, ValidatorConfig};
+    use srdf::{srdf_graph::SRDFGraph, RDFFormat, ReaderMode, SRDFBasic, SRDF};
+    use std::str::FromStr;
+
+    const SOURCE: &str = r#"prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:Person { :name xsd:string ; :age xsd:integer ? ; :worksFor @:Company }
+:Company { :name xsd:string }
+"#;
+
+    const TARGET: &str = r#"prefix schema: <http://schema.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+schema:Person { a [ schema:Person ] ; schema:name xsd:string ; schema:worksFor @schema:Organization }
+schema:Organization { schema:legalName xsd:string }
+"#;
+
+    const MAPPING: &str = r#"
+prefixes:
+  ex: http://example.org/
+  schema: http://schema.org/
+namespaces:
+  http://example.org/data/: http://schema.example/
+shapes:
+  - source: ex:Person
+    target: schema:Person
+    types: [ schema:Person ]
+    properties:
+      - source: ex:name
+        target: schema:name
+      - source: ex:worksFor
+        target: schema:worksFor
+  - source: ex:Company
+    target: schema:Organization
+    properties:
+      - source: ex:name
+        target: schema:legalName
+"#;
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+prefix d: <http://example.org/data/>
+d:alice :name "Alice" ; :age 30 ; :worksFor d:acme .
+d:acme :name "ACME" .
+"#;
+
+    fn transform(mapping: &str) -> Result<(ShExTransformer, SRDFGraph)> {
+        let source = ShExParser::parse(SOURCE, None).unwrap();
+        let target = ShExParser::parse(TARGET, None).unwrap();
+        let mut compiled = CompiledSchema::new();
+        compiled.from_schema_json(&source).unwrap();
+        let mut validator = Validator::new(compiled, &ValidatorConfig::default());
+        let data =
+            SRDFGraph::from_str(DATA, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        validator
+            .validate_node_shape(
+                &Node::iri(IriS::new_unchecked("http://example.org/data/alice")),
+                &ShapeLabel::iri(IriS::new_unchecked("http://example.org/Person")),
+                &data,
+            )
+            .unwrap();
+        let mut transformer = ShExTransformer::new(&DataMapping::from_str(mapping)?);
+        let mut rdf = SRDFGraph::new();
+        transformer.transform(
+            &validator.shape_matches().unwrap(),
+            &source,
+            &target,
+            &mut rdf,
+        )?;
+        Ok((transformer, rdf))
+    }
+
+    #[test]
+    fn transformed_data_conforms_to_target() {
+        let (transformer, rdf) = transform(MAPPING).unwrap();
+        let alice = IriS::new_unchecked("http://schema.example/alice");
+        let names = rdf
+            .objects_for_subject_predicate(
+                &SRDFGraph::iri_s2subject(&alice),
+                &SRDFGraph::iri_s2iri(&IriS::new_unchecked("http://schema.org/name")),
+            )
+            .unwrap();
+        assert_eq!(names.len(), 1);
+        assert_eq!(
+            transformer.warnings().collect::<Vec<_>>(),
+            vec![&DataTransformerWarning::UnmappedPredicate {
+                predicate: ":age".to_string(),
+                shape: ":Person".to_string()
+            }]
+        );
+        let target = ShExParser::parse(TARGET, None).unwrap();
+        let mut compiled = CompiledSchema::new();
+        compiled.from_schema_json(&target).unwrap();
+        let mut validator = Validator::new(compiled, &ValidatorConfig::default());
+        let shapemap: Vec<_> = transformer.target_shapemap().cloned().collect();
+        assert_eq!(shapemap.len(), 2);
+        for (node, shape) in shapemap {
+            validator.validate_node_shape(&node, &shape, &rdf).unwrap();
+            assert_eq!(
+                validator.get_result(&node, &shape).unwrap(),
+                ResultValue::Ok
+            )
+        }
+    }
+
+    #[test]
+    fn unknown_target_predicate_is_an_error() {
+        let mapping = MAPPING.replace("target: schema:legalName", "target: schema:name");
+        assert!(matches!(
+            transform(&mapping),
+            Err(DataTransformerError::PredicateNotFound { .. })
+        ))
+    }
+}
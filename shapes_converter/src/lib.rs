@@ -3,10 +3,15 @@
 //!
 pub mod converter_config;
 pub mod converter_error;
+pub mod data_generator;
+pub mod data_transformer;
 pub mod landing_html_template;
 pub mod shacl_to_shex;
+pub mod shacl_to_tap;
+pub mod shapes_to_jsonld;
 pub mod shex_to_html;
 pub mod shex_to_sparql;
+pub mod shex_to_tap;
 pub mod shex_to_uml;
 pub mod tap_to_shex;
 
@@ -15,18 +20,35 @@ use prefixmap::PrefixMap;
 use prefixmap::PrefixMapError;
 use shex_ast::Annotation;
 use shex_ast::ObjectValue;
+use srdf::numeric_literal::NumericLiteral;
 
 pub use crate::converter_config::*;
 pub use crate::converter_error::*;
+pub use crate::data_generator::data_generator_config::*;
+pub use crate::data_generator::data_generator_error::*;
+pub use crate::data_generator::shacl2data::*;
+pub use crate::data_generator::shex2data::*;
+pub use crate::data_transformer::data_mapping::*;
+pub use crate::data_transformer::data_transformer_error::*;
+pub use crate::data_transformer::shex_transformer::*;
 pub use crate::shacl_to_shex::shacl2shex::*;
 pub use crate::shacl_to_shex::shacl2shex_config::*;
 pub use crate::shacl_to_shex::shacl2shex_error::*;
+pub use crate::shacl_to_tap::shacl2tap::*;
+pub use crate::shacl_to_tap::shacl2tap_config::*;
+pub use crate::shacl_to_tap::shacl2tap_error::*;
+pub use crate::shapes_to_jsonld::jsonld_frame::*;
+pub use crate::shapes_to_jsonld::jsonld_framer::*;
+pub use crate::shapes_to_jsonld::jsonld_framer_error::*;
 pub use crate::shex_to_html::shex2html::*;
 pub use crate::shex_to_html::shex2html_config::*;
 pub use crate::shex_to_html::shex2html_error::*;
 pub use crate::shex_to_sparql::shex2sparql::*;
 pub use crate::shex_to_sparql::shex2sparql_config::*;
 pub use crate::shex_to_sparql::shex2sparql_error::*;
+pub use crate::shex_to_tap::shex2tap::*;
+pub use crate::shex_to_tap::shex2tap_config::*;
+pub use crate::shex_to_tap::shex2tap_error::*;
 pub use crate::shex_to_uml::shex2uml::*;
 pub use crate::shex_to_uml::shex2uml_config::*;
 pub use crate::shex_to_uml::shex2uml_error::*;
@@ -60,3 +82,26 @@ fn object_value2string(object_value: &ObjectValue) -> String {
         ObjectValue::Literal(lit) => lit.lexical_form(),
     }
 }
+
+// DCTAP cells contain either prefixed names or full IRIs without angle brackets
+fn iri2tap_str(iri: &IriS, prefixmap: &PrefixMap) -> String {
+    let qualified = prefixmap.qualify(iri);
+    match qualified
+        .strip_prefix('<')
+        .and_then(|str| str.strip_suffix('>'))
+    {
+        Some(str) => str.to_string(),
+        None => qualified,
+    }
+}
+
+fn numeric_literal2number(n: &NumericLiteral) -> dctap::Number {
+    match n {
+        NumericLiteral::Integer(n) => dctap::Number::Int(*n as i64),
+        NumericLiteral::Double(d) => dctap::Number::Double(*d),
+        NumericLiteral::Decimal(d) => match d.to_string().parse() {
+            Ok(d) => dctap::Number::Double(d),
+            Err(_) => dctap::Number::Double(f64::NAN),
+        },
+    }
+}
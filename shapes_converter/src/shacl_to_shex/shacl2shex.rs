@@ -44,9 +44,36 @@ impl Shacl2ShEx {
                 }
             }
         }
+        for component in self.custom_components(schema) {
+            self.current_shex.add_shape(
+                ShapeExprLabel::iri(component),
+                ShapeExpr::external(),
+                false,
+            )
+        }
         Ok(())
     }
 
+    /// IRIs of the custom constraint components of the property shapes
+    ///
+    /// Each one is declared as an `EXTERNAL` shape with the IRI of the component as label,
+    /// which is the key of the checks of external shapes, so the same check is used by ShEx.
+    fn custom_components(&self, schema: &ShaclSchema) -> Vec<IriS> {
+        let mut components = Vec::new();
+        for (_, shape) in schema.iter() {
+            if let ShaclShape::PropertyShape(ps) = shape {
+                for component in ps.components() {
+                    if let Component::Custom { component, .. } = component {
+                        if !components.contains(component) {
+                            components.push(component.clone())
+                        }
+                    }
+                }
+            }
+        }
+        components
+    }
+
     pub fn convert_shape(
         &self,
         shape: &NodeShape,
@@ -65,6 +92,10 @@ impl Shacl2ShEx {
             srdf::Object::Literal(lit) => Err(Shacl2ShExError::RDFNode2LabelLiteral {
                 literal: lit.clone(),
             }),
+            srdf::Object::Triple { .. } => Err(Shacl2ShExError::UnexpectedTripleTerm {
+                term: node.to_string(),
+                context: "shape label".to_string(),
+            }),
         }
     }
 
@@ -145,6 +176,10 @@ impl Shacl2ShEx {
                     Object::Literal(lit) => Err(Shacl2ShExError::UnexpectedLiteralForTargetClass {
                         literal: lit.clone(),
                     }),
+                    Object::Triple { .. } => Err(Shacl2ShExError::UnexpectedTripleTerm {
+                        term: cls.to_string(),
+                        context: "target class".to_string(),
+                    }),
                 }?;
                 let value_cls = ShapeExpr::node_constraint(
                     NodeConstraint::new().with_values(vec![value_set_value]),
@@ -330,6 +365,12 @@ impl Shacl2ShEx {
             Object::Iri(iri) => ValueSetValue::iri(IriRef::iri(iri.clone())),
             Object::BlankNode(_) => todo!(),
             Object::Literal(_) => todo!(),
+            Object::Triple { .. } => {
+                return Err(Shacl2ShExError::UnexpectedTripleTerm {
+                    term: cls.to_string(),
+                    context: "class".to_string(),
+                })
+            }
         };
         let cls = NodeConstraint::new().with_values(vec![value]);
         let te = TripleExpr::triple_constraint(
@@ -396,6 +437,15 @@ impl Shacl2ShEx {
                 qualified_max_count: _,
                 qualified_value_shapes_disjoint: _,
             } => todo!(),
+            Component::Custom {
+                component,
+                parameters,
+            } => {
+                if !parameters.is_empty() {
+                    debug!("External shapes don't have parameters, ignoring the parameters of {component}: {parameters:?}");
+                }
+                Ok(ShapeExpr::shape_ref(ShapeExprLabel::iri(component.clone())))
+            }
         }
     }
 
@@ -406,3 +456,51 @@ impl Shacl2ShEx {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shacl_ast::ShaclParser;
+    use srdf::{srdf_graph::SRDFGraph, RDFFormat, ReaderMode};
+
+    #[test]
+    fn test_shacl2shex_custom_component() {
+        let str = r#"prefix : <http://example.org/>
+            prefix sh: <http://www.w3.org/ns/shacl#>
+            :ISBNConstraintComponent a sh:ConstraintComponent ;
+                sh:parameter [ sh:path :isbn ] .
+            :Book a sh:NodeShape ;
+                sh:property [ sh:path :code ; :isbn true ] .
+            "#;
+        let rdf = SRDFGraph::from_str(str, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let schema = ShaclParser::new(rdf).parse().unwrap();
+        let mut converter = Shacl2ShEx::new(&Shacl2ShExConfig::default());
+        converter.convert(&schema).unwrap();
+        let component = ShapeExprLabel::iri(IriS::new_unchecked(
+            "http://example.org/ISBNConstraintComponent",
+        ));
+        let shapes = converter.current_shex().shapes().unwrap();
+        let external = shapes.iter().find(|sd| sd.id == component).unwrap();
+        assert_eq!(external.shape_expr, ShapeExpr::external());
+        let book = shapes
+            .iter()
+            .find(|sd| sd.id == ShapeExprLabel::iri(IriS::new_unchecked("http://example.org/Book")))
+            .unwrap();
+        let code = TripleExpr::triple_constraint(
+            None,
+            None,
+            IriRef::iri(IriS::new_unchecked("http://example.org/code")),
+            Some(ShapeExpr::shape_ref(component)),
+            None,
+            None,
+        );
+        assert_eq!(
+            book.shape_expr,
+            ShapeExpr::shape(ShExShape::new(
+                None,
+                None,
+                Some(TripleExpr::each_of(vec![code]))
+            ))
+        );
+    }
+}
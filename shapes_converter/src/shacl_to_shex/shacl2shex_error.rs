@@ -18,6 +18,9 @@ pub enum Shacl2ShExError {
 
     #[error("Unexpected literal in target class declaration: {literal:?}")]
     UnexpectedLiteralForTargetClass { literal: Literal },
+
+    #[error("Triple terms can't be represented as {context} in ShEx: {term}")]
+    UnexpectedTripleTerm { term: String, context: String },
 }
 
 impl Shacl2ShExError {
@@ -0,0 +1,10 @@
+//! SHACL to DCTAP converter
+//!
+//!
+pub mod shacl2tap;
+pub mod shacl2tap_config;
+pub mod shacl2tap_error;
+
+pub use shacl2tap::*;
+pub use shacl2tap_config::*;
+pub use shacl2tap_error::*;
@@ -0,0 +1,391 @@
+//! Struct that converts SHACL shapes graphs to DCTAP
+//!
+//! Each node shape is converted to a DCTAP shape and each property shape with a predicate path
+//! to a statement. The constructs that can't be represented are collected as warnings
+use dctap::{
+    BasicNodeType, DCTap, DatatypeId, NodeType, Number, PropertyId, ShapeId, TapShape,
+    TapStatement, Value, ValueConstraint,
+};
+use prefixmap::{IriRef, PrefixMap};
+use shacl_ast::{
+    component::Component, message_map::MessageMap, node_kind::NodeKind, node_shape::NodeShape,
+    property_shape::PropertyShape, shape::Shape as ShaclShape, value::Value as ShaclValue,
+    Schema as ShaclSchema,
+};
+use srdf::{literal::Literal, RDFNode, SHACLPath};
+
+use super::{Shacl2TapConfig, Shacl2TapError, Shacl2TapWarning};
+use crate::{iri2tap_str, numeric_literal2number};
+
+pub struct Shacl2Tap {
+    config: Shacl2TapConfig,
+    current_tap: DCTap,
+    warnings: Vec<Shacl2TapWarning>,
+}
+
+impl Shacl2Tap {
+    pub fn new(config: &Shacl2TapConfig) -> Shacl2Tap {
+        Shacl2Tap {
+            config: config.clone(),
+            current_tap: DCTap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn current_tap(&self) -> &DCTap {
+        &self.current_tap
+    }
+
+    /// Constructs of the last converted shapes graph that could not be represented in DCTAP
+    pub fn warnings(&self) -> impl Iterator<Item = &Shacl2TapWarning> {
+        self.warnings.iter()
+    }
+
+    pub fn convert(&mut self, schema: &ShaclSchema) -> Result<(), Shacl2TapError> {
+        let prefixmap = schema.prefix_map().without_rich_qualifying();
+        self.current_tap = DCTap::new();
+        self.warnings = Vec::new();
+        // Shapes are stored in a HashMap, they are sorted to obtain a stable output
+        let mut node_shapes = Vec::new();
+        for (_, shape) in schema.iter() {
+            if let ShaclShape::NodeShape(ns) = shape {
+                node_shapes.push((rdfnode2str(ns.id(), &prefixmap)?, ns))
+            }
+        }
+        node_shapes.sort_by(|(id1, _), (id2, _)| id1.cmp(id2));
+        for (shape_id, node_shape) in node_shapes {
+            let tap_shape = self.node_shape2tap_shape(&shape_id, node_shape, schema, &prefixmap)?;
+            self.current_tap.add_shape(&tap_shape)
+        }
+        Ok(())
+    }
+
+    fn node_shape2tap_shape(
+        &mut self,
+        shape_id: &str,
+        node_shape: &NodeShape,
+        schema: &ShaclSchema,
+        prefixmap: &PrefixMap,
+    ) -> Result<TapShape, Shacl2TapError> {
+        let mut tap_shape = TapShape::new(0);
+        tap_shape.set_shape_id(&ShapeId::new(shape_id, 0));
+        if !node_shape.targets().is_empty() {
+            self.warnings.push(Shacl2TapWarning::Targets {
+                shape: shape_id.to_string(),
+            })
+        }
+        for component in node_shape.components() {
+            self.warnings.push(Shacl2TapWarning::NodeShapeComponent {
+                shape: shape_id.to_string(),
+                component: component.to_string(),
+            })
+        }
+        for node in node_shape.property_shapes() {
+            match schema.get_shape(node) {
+                Some(ShaclShape::PropertyShape(ps)) => {
+                    if let Some(statement) =
+                        self.property_shape2statement(shape_id, ps, prefixmap)?
+                    {
+                        tap_shape.add_statement(statement)
+                    }
+                }
+                Some(ShaclShape::NodeShape(_)) => {
+                    self.warnings
+                        .push(Shacl2TapWarning::NodeShapeAsPropertyShape {
+                            shape: shape_id.to_string(),
+                            property_shape: rdfnode2str(node, prefixmap)?,
+                        })
+                }
+                None => self.warnings.push(Shacl2TapWarning::PropertyShapeNotFound {
+                    shape: shape_id.to_string(),
+                    property_shape: rdfnode2str(node, prefixmap)?,
+                }),
+            }
+        }
+        Ok(tap_shape)
+    }
+
+    fn property_shape2statement(
+        &mut self,
+        shape_id: &str,
+        property_shape: &PropertyShape,
+        prefixmap: &PrefixMap,
+    ) -> Result<Option<TapStatement>, Shacl2TapError> {
+        let property = match property_shape.path() {
+            SHACLPath::Predicate { pred } => iri2tap_str(pred, prefixmap),
+            path => {
+                self.warnings.push(Shacl2TapWarning::ComplexPath {
+                    shape: shape_id.to_string(),
+                    path: path.to_string(),
+                });
+                return Ok(None);
+            }
+        };
+        let mut statement = TapStatement::new(PropertyId::new(&property, 0));
+        if let Some(name) = first_message(property_shape.name()) {
+            statement.set_property_label(&name)
+        }
+        let mut warnings = Vec::new();
+        let unsupported = |component: &Component| Shacl2TapWarning::Component {
+            shape: shape_id.to_string(),
+            property: property.clone(),
+            component: component.to_string(),
+        };
+        let mut datatype = None;
+        let mut value_shape = None;
+        let mut min_count = 0;
+        let mut max_count = None;
+        let mut value_constraints = Vec::new();
+        for component in property_shape.components() {
+            match component {
+                Component::MinCount(n) => min_count = *n,
+                Component::MaxCount(n) => max_count = Some(*n),
+                Component::Datatype(dt) => datatype = Some(iri_ref2str(dt, prefixmap)),
+                Component::NodeKind(node_kind) => match node_kind2node_type(node_kind) {
+                    Some(node_type) => statement.set_value_nodetype(&node_type),
+                    None => warnings.push(unsupported(component)),
+                },
+                Component::Node { shape } => value_shape = Some(rdfnode2str(shape, prefixmap)?),
+                Component::In { values } => {
+                    let values = values
+                        .iter()
+                        .map(|v| shacl_value2value(v, prefixmap))
+                        .collect();
+                    value_constraints.push((component, ValueConstraint::PickList(values)))
+                }
+                Component::HasValue { value } => {
+                    let value = shacl_value2value(value, prefixmap);
+                    value_constraints.push((component, ValueConstraint::PickList(vec![value])))
+                }
+                Component::Pattern { pattern, flags } => {
+                    value_constraints.push((component, ValueConstraint::Pattern(pattern.clone())));
+                    if flags.is_some() {
+                        warnings.push(unsupported(component))
+                    }
+                }
+                Component::MinLength(n) if *n >= 0 => {
+                    value_constraints.push((component, ValueConstraint::MinLength(*n as usize)))
+                }
+                Component::MaxLength(n) if *n >= 0 => {
+                    value_constraints.push((component, ValueConstraint::MaxLength(*n as usize)))
+                }
+                Component::MinInclusive(lit) => match literal2number(lit) {
+                    Some(n) => {
+                        value_constraints.push((component, ValueConstraint::MinInclusive(n)))
+                    }
+                    None => warnings.push(unsupported(component)),
+                },
+                Component::MinExclusive(lit) => match literal2number(lit) {
+                    Some(n) => {
+                        value_constraints.push((component, ValueConstraint::MinExclusive(n)))
+                    }
+                    None => warnings.push(unsupported(component)),
+                },
+                Component::MaxInclusive(lit) => match literal2number(lit) {
+                    Some(n) => {
+                        value_constraints.push((component, ValueConstraint::MaxInclusive(n)))
+                    }
+                    None => warnings.push(unsupported(component)),
+                },
+                Component::MaxExclusive(lit) => match literal2number(lit) {
+                    Some(n) => {
+                        value_constraints.push((component, ValueConstraint::MaxExclusive(n)))
+                    }
+                    None => warnings.push(unsupported(component)),
+                },
+                _ => warnings.push(unsupported(component)),
+            }
+        }
+        statement.set_mandatory(min_count >= 1);
+        statement.set_repeatable(max_count.is_none_or(|max| max > 1));
+        if min_count > 1 || max_count.is_some_and(|max| max == 0 || max > 1) {
+            warnings.push(Shacl2TapWarning::Cardinality {
+                shape: shape_id.to_string(),
+                property: property.clone(),
+                min: min_count,
+                max: max_count.map_or("*".to_string(), |max| max.to_string()),
+            })
+        }
+        let mut value_constraints = value_constraints.into_iter();
+        if let Some((_, vc)) = value_constraints.next() {
+            statement.set_value_constraint(&vc)
+        }
+        for (component, _) in value_constraints {
+            warnings.push(Shacl2TapWarning::MultipleValueConstraints {
+                shape: shape_id.to_string(),
+                property: property.clone(),
+                component: component.to_string(),
+            })
+        }
+        match (datatype, value_shape) {
+            (Some(datatype), Some(value_shape)) => {
+                // DCTAP to ShEx conversion rejects statements with both a datatype and a value shape
+                statement.set_value_datatype(&DatatypeId::new(&datatype, 0));
+                warnings.push(Shacl2TapWarning::DatatypeAndValueShape {
+                    shape: shape_id.to_string(),
+                    property: property.clone(),
+                    datatype,
+                    value_shape,
+                })
+            }
+            (Some(datatype), None) => statement.set_value_datatype(&DatatypeId::new(&datatype, 0)),
+            (None, Some(value_shape)) => statement.set_value_shape(&ShapeId::new(&value_shape, 0)),
+            (None, None) => {}
+        }
+        let description = first_message(property_shape.description());
+        self.add_notes(&mut statement, description, warnings);
+        Ok(Some(statement))
+    }
+
+    fn add_notes(
+        &mut self,
+        statement: &mut TapStatement,
+        description: Option<String>,
+        warnings: Vec<Shacl2TapWarning>,
+    ) {
+        let mut notes: Vec<String> = description.into_iter().collect();
+        if self.config.add_warnings_as_notes() {
+            notes.extend(warnings.iter().map(|w| w.to_string()))
+        }
+        if !notes.is_empty() {
+            statement.set_note(notes.join("; ").as_str())
+        }
+        self.warnings.extend(warnings)
+    }
+}
+
+fn rdfnode2str(node: &RDFNode, prefixmap: &PrefixMap) -> Result<String, Shacl2TapError> {
+    match node {
+        RDFNode::Iri(iri) => Ok(iri2tap_str(iri, prefixmap)),
+        RDFNode::BlankNode(bnode) => Ok(format!("_:{bnode}")),
+        RDFNode::Literal(literal) => Err(Shacl2TapError::LiteralShapeId {
+            literal: literal.clone(),
+        }),
+        RDFNode::Triple { .. } => Err(Shacl2TapError::TripleTermShapeId {
+            term: node.to_string(),
+        }),
+    }
+}
+
+fn iri_ref2str(iri_ref: &IriRef, prefixmap: &PrefixMap) -> String {
+    match iri_ref {
+        IriRef::Iri(iri) => iri2tap_str(iri, prefixmap),
+        IriRef::Prefixed { prefix, local } => format!("{prefix}:{local}"),
+    }
+}
+
+// IRIs in picklists are written as prefixed names, like the rest of the cells
+fn shacl_value2value(value: &ShaclValue, prefixmap: &PrefixMap) -> Value {
+    match value {
+        ShaclValue::Iri(iri_ref) => Value::Str(iri_ref2str(iri_ref, prefixmap)),
+        ShaclValue::Literal(lit) => Value::Str(lit.lexical_form()),
+    }
+}
+
+fn literal2number(literal: &Literal) -> Option<Number> {
+    match literal {
+        Literal::NumericLiteral(n) => Some(numeric_literal2number(n)),
+        Literal::DatatypeLiteral { lexical_form, .. } => {
+            if let Ok(n) = lexical_form.parse() {
+                Some(Number::Int(n))
+            } else {
+                lexical_form.parse().ok().map(Number::Double)
+            }
+        }
+        _ => None,
+    }
+}
+
+// DCTAP has no node type for triple terms
+fn node_kind2node_type(node_kind: &NodeKind) -> Option<NodeType> {
+    match node_kind {
+        NodeKind::Iri => Some(NodeType::Basic(BasicNodeType::IRI)),
+        NodeKind::BlankNode => Some(NodeType::Basic(BasicNodeType::BNode)),
+        NodeKind::Literal => Some(NodeType::Basic(BasicNodeType::Literal)),
+        NodeKind::BlankNodeOrIri => {
+            Some(NodeType::Or(vec![BasicNodeType::IRI, BasicNodeType::BNode]))
+        }
+        NodeKind::BlankNodeOrLiteral => Some(NodeType::Or(vec![
+            BasicNodeType::BNode,
+            BasicNodeType::Literal,
+        ])),
+        NodeKind::IRIOrLiteral => Some(NodeType::Or(vec![
+            BasicNodeType::IRI,
+            BasicNodeType::Literal,
+        ])),
+        NodeKind::TripleTerm => None,
+    }
+}
+
+// Messages without language tag are preferred, otherwise the one with the first language tag
+fn first_message(messages: &MessageMap) -> Option<String> {
+    messages
+        .messages()
+        .iter()
+        .min_by_key(|(lang, _)| lang.as_ref().map(|lang| lang.value()))
+        .map(|(_, message)| message.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shacl_ast::ShaclParser;
+    use srdf::{srdf_graph::SRDFGraph, RDFFormat, ReaderMode};
+
+    fn shacl2tap(str: &str) -> Shacl2Tap {
+        let rdf = SRDFGraph::from_str(str, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let schema = ShaclParser::new(rdf).parse().unwrap();
+        let mut converter = Shacl2Tap::new(&Shacl2TapConfig::default());
+        converter.convert(&schema).unwrap();
+        converter
+    }
+
+    #[test]
+    fn test_shacl2tap_statements() {
+        let converter = shacl2tap(
+            r#"prefix : <http://example.org/>
+               prefix sh: <http://www.w3.org/ns/shacl#>
+               prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+               :Person a sh:NodeShape ;
+                  sh:property [
+                    sh:path :name ;
+                    sh:datatype xsd:string ;
+                    sh:minCount 1 ;
+                    sh:maxCount 1
+                  ] .
+               "#,
+        );
+        let shape = converter.current_tap().shapes().next().unwrap();
+        assert_eq!(shape.shape_id().unwrap().str(), ":Person");
+        let statement = shape.statements().next().unwrap();
+        assert_eq!(statement.property_id().str(), ":name");
+        assert_eq!(statement.value_datatype().unwrap().str(), "xsd:string");
+        assert_eq!(statement.mandatory(), Some(true));
+        assert_eq!(statement.repeatable(), Some(false));
+        assert_eq!(converter.warnings().count(), 0)
+    }
+
+    #[test]
+    fn test_shacl2tap_unsupported_component() {
+        let converter = shacl2tap(
+            r#"prefix : <http://example.org/>
+               prefix sh: <http://www.w3.org/ns/shacl#>
+               :Person a sh:NodeShape ;
+                  sh:property [ sh:path :knows ; sh:class :Person ] .
+               "#,
+        );
+        let statement = converter
+            .current_tap()
+            .shapes()
+            .next()
+            .unwrap()
+            .statements()
+            .next()
+            .unwrap();
+        assert!(statement.note().is_some());
+        assert!(matches!(
+            converter.warnings().next(),
+            Some(Shacl2TapWarning::Component { .. })
+        ))
+    }
+}
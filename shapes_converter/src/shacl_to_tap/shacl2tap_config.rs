@@ -0,0 +1,13 @@
+use serde_derive::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone, Default)]
+pub struct Shacl2TapConfig {
+    /// Add the warnings about constructs that can't be represented in DCTAP to the `note` column
+    add_warnings_as_notes: Option<bool>,
+}
+
+impl Shacl2TapConfig {
+    pub fn add_warnings_as_notes(&self) -> bool {
+        self.add_warnings_as_notes.unwrap_or(true)
+    }
+}
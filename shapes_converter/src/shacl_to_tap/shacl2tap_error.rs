@@ -0,0 +1,71 @@
+use srdf::literal::Literal;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Shacl2TapError {
+    #[error("Shacl2Tap error: Feature not implemented: {msg}")]
+    NotImplemented { msg: String },
+
+    #[error("Unexpected literal as shape identifier: {literal}")]
+    LiteralShapeId { literal: Literal },
+
+    #[error("Unexpected triple term as shape identifier: {term}")]
+    TripleTermShapeId { term: String },
+}
+
+/// Constructs of the SHACL shapes graph that can't be represented in DCTAP
+///
+/// The conversion goes on when they are found, generating an approximation of the original shapes
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum Shacl2TapWarning {
+    #[error("Shape {shape}: targets are not supported in DCTAP")]
+    Targets { shape: String },
+
+    #[error("Shape {shape}: component {component} is not supported in DCTAP")]
+    NodeShapeComponent { shape: String, component: String },
+
+    #[error("Shape {shape}: property shape {property_shape} not found")]
+    PropertyShapeNotFound {
+        shape: String,
+        property_shape: String,
+    },
+
+    #[error("Shape {shape}: property shape {property_shape} referenced as a node shape")]
+    NodeShapeAsPropertyShape {
+        shape: String,
+        property_shape: String,
+    },
+
+    #[error("Shape {shape}: complex path {path} is not supported in DCTAP")]
+    ComplexPath { shape: String, path: String },
+
+    #[error("Shape {shape}, property {property}: component {component} is not supported in DCTAP")]
+    Component {
+        shape: String,
+        property: String,
+        component: String,
+    },
+
+    #[error("Shape {shape}, property {property}: cardinality {{{min},{max}}} approximated by mandatory/repeatable")]
+    Cardinality {
+        shape: String,
+        property: String,
+        min: isize,
+        max: String,
+    },
+
+    #[error("Shape {shape}, property {property}: only one value constraint is supported in DCTAP, ignored {component}")]
+    MultipleValueConstraints {
+        shape: String,
+        property: String,
+        component: String,
+    },
+
+    #[error("Shape {shape}, property {property}: datatype {datatype} and value shape {value_shape} can't be declared together in DCTAP, ignored the value shape")]
+    DatatypeAndValueShape {
+        shape: String,
+        property: String,
+        datatype: String,
+        value_shape: String,
+    },
+}
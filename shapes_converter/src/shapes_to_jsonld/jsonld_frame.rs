@@ -0,0 +1,298 @@
+//! Frames that describe how the nodes of the shapes of a schema are laid out in JSON-LD
+//!
+//! A frame keeps, for each shape, the predicates of its triple constraints or property shapes,
+//! whether they can have several values, whether their values are IRIs and the shape of their values.
+//! It is obtained from ShEx schemas and SHACL shapes graphs and used by [`crate::JsonLdFramer`]
+use indexmap::IndexMap;
+use iri_s::IriS;
+use prefixmap::{IriRef, PrefixMap};
+use shacl_ast::{
+    component::Component, node_kind::NodeKind as ShaclNodeKind, shape::Shape as ShaclShape,
+    target::Target, Schema as ShaclSchema,
+};
+use shex_ast::{
+    NodeKind, ObjectValue, Schema, ShapeExpr, ShapeExprLabel, TripleExpr, ValueSetValue,
+};
+use srdf::{Object, SHACLPath};
+
+use super::JsonLdFramerError;
+
+type Result<T> = std::result::Result<T, JsonLdFramerError>;
+
+/// Maximum number of nested references followed when collecting the properties of a shape
+const MAX_REF_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Default)]
+pub struct JsonLdFrame {
+    prefixmap: PrefixMap,
+    shapes: IndexMap<Object, FrameShape>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FrameShape {
+    properties: Vec<FrameProperty>,
+    targets: Vec<FrameTarget>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FrameProperty {
+    pub predicate: IriS,
+
+    /// The property can have several values, so they are always written as an array
+    pub many: bool,
+
+    /// The values of the property are IRIs, so they are written as strings
+    pub iri_valued: bool,
+
+    /// Shape of the values, which are embedded as nested objects
+    pub shape: Option<Object>,
+}
+
+/// Nodes selected by the targets of a SHACL shape
+#[derive(Debug, Clone, PartialEq)]
+pub enum FrameTarget {
+    Node(Object),
+    Class(Object),
+    SubjectsOf(IriS),
+    ObjectsOf(IriS),
+}
+
+impl JsonLdFrame {
+    pub fn new(prefixmap: PrefixMap) -> JsonLdFrame {
+        JsonLdFrame {
+            prefixmap,
+            shapes: IndexMap::new(),
+        }
+    }
+
+    pub fn prefixmap(&self) -> &PrefixMap {
+        &self.prefixmap
+    }
+
+    pub fn add_shape(&mut self, id: Object, shape: FrameShape) {
+        self.shapes.insert(id, shape);
+    }
+
+    pub fn shape(&self, id: &Object) -> Option<&FrameShape> {
+        self.shapes.get(id)
+    }
+
+    pub fn shapes(&self) -> impl Iterator<Item = (&Object, &FrameShape)> {
+        self.shapes.iter()
+    }
+
+    /// Frame with the triple constraints of each shape declaration labelled by an IRI
+    ///
+    /// Conjunctions and disjunctions are flattened and references to other shape declarations are followed
+    pub fn from_shex(schema: &Schema) -> Result<JsonLdFrame> {
+        let prefixmap = schema.prefixmap().unwrap_or_default();
+        let mut decls = IndexMap::new();
+        for decl in schema.shapes().unwrap_or_default() {
+            if let ShapeExprLabel::IriRef { value } = &decl.id {
+                decls.insert(prefixmap.resolve_iriref(value)?, decl.shape_expr);
+            }
+        }
+        let collector = ShExCollector {
+            prefixmap: &prefixmap,
+            decls: &decls,
+        };
+        let mut frame = JsonLdFrame::new(prefixmap.clone());
+        for (label, se) in &decls {
+            let mut shape = FrameShape::default();
+            collector.shape_expr(se, &mut shape, 0)?;
+            frame.add_shape(Object::iri(label.clone()), shape)
+        }
+        Ok(frame)
+    }
+
+    /// Frame with the property shapes with a predicate path of each node shape
+    pub fn from_shacl(schema: &ShaclSchema) -> Result<JsonLdFrame> {
+        let prefixmap = schema.prefix_map();
+        // Shapes are stored in a HashMap, they are sorted to obtain a stable output
+        let mut node_shapes: Vec<_> = schema
+            .iter()
+            .filter_map(|(id, shape)| match shape {
+                ShaclShape::NodeShape(ns) => Some((id, ns)),
+                _ => None,
+            })
+            .collect();
+        node_shapes.sort_by_key(|(id, _)| id.to_string());
+        let mut frame = JsonLdFrame::new(prefixmap.clone());
+        for (id, node_shape) in node_shapes {
+            let mut shape = FrameShape::default();
+            for target in node_shape.targets() {
+                shape.add_target(shacl_target(target, &prefixmap)?)
+            }
+            for node in node_shape.property_shapes() {
+                if let Some(ShaclShape::PropertyShape(ps)) = schema.get_shape(node) {
+                    if let SHACLPath::Predicate { pred } = ps.path() {
+                        shape.add_property(shacl_property(pred, ps.components()))
+                    }
+                }
+            }
+            frame.add_shape(id.clone(), shape)
+        }
+        Ok(frame)
+    }
+}
+
+impl FrameShape {
+    pub fn properties(&self) -> impl Iterator<Item = &FrameProperty> {
+        self.properties.iter()
+    }
+
+    pub fn targets(&self) -> impl Iterator<Item = &FrameTarget> {
+        self.targets.iter()
+    }
+
+    pub fn add_target(&mut self, target: FrameTarget) {
+        self.targets.push(target)
+    }
+
+    /// Adds a property, merging it with a previous one with the same predicate
+    pub fn add_property(&mut self, property: FrameProperty) {
+        match self
+            .properties
+            .iter_mut()
+            .find(|p| p.predicate == property.predicate)
+        {
+            Some(p) => {
+                p.many = true;
+                p.iri_valued = p.iri_valued && property.iri_valued;
+                p.shape = p.shape.take().or(property.shape);
+            }
+            None => self.properties.push(property),
+        }
+    }
+}
+
+struct ShExCollector<'a> {
+    prefixmap: &'a PrefixMap,
+    decls: &'a IndexMap<IriS, ShapeExpr>,
+}
+
+impl ShExCollector<'_> {
+    fn shape_expr(&self, se: &ShapeExpr, shape: &mut FrameShape, depth: usize) -> Result<()> {
+        match se {
+            ShapeExpr::Shape(s) => {
+                if let Some(expr) = &s.expression {
+                    self.triple_expr(&expr.te, shape)?
+                }
+                Ok(())
+            }
+            ShapeExpr::ShapeAnd { shape_exprs } | ShapeExpr::ShapeOr { shape_exprs } => {
+                for e in shape_exprs {
+                    self.shape_expr(&e.se, shape, depth)?
+                }
+                Ok(())
+            }
+            ShapeExpr::Ref(ShapeExprLabel::IriRef { value }) if depth < MAX_REF_DEPTH => {
+                let iri = self.prefixmap.resolve_iriref(value)?;
+                if let Some(se) = self.decls.get(&iri) {
+                    self.shape_expr(se, shape, depth + 1)?
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn triple_expr(&self, te: &TripleExpr, shape: &mut FrameShape) -> Result<()> {
+        match te {
+            TripleExpr::EachOf { expressions, .. } | TripleExpr::OneOf { expressions, .. } => {
+                for e in expressions {
+                    self.triple_expr(&e.te, shape)?
+                }
+                Ok(())
+            }
+            TripleExpr::TripleConstraint {
+                inverse,
+                predicate,
+                value_expr,
+                max,
+                ..
+            } => {
+                if inverse.unwrap_or(false) {
+                    return Ok(());
+                }
+                let (value_shape, iri_valued) = match value_expr {
+                    Some(se) => self.value_expr(se)?,
+                    None => (None, false),
+                };
+                shape.add_property(FrameProperty {
+                    predicate: self.prefixmap.resolve_iriref(predicate)?,
+                    many: max.is_some_and(|max| max == -1 || max > 1),
+                    iri_valued,
+                    shape: value_shape,
+                });
+                Ok(())
+            }
+            TripleExpr::TripleExprRef(_) => Ok(()),
+        }
+    }
+
+    /// Shape referenced by the value expression of a triple constraint and whether its values are IRIs
+    fn value_expr(&self, se: &ShapeExpr) -> Result<(Option<Object>, bool)> {
+        match se {
+            ShapeExpr::Ref(ShapeExprLabel::IriRef { value }) => {
+                let iri = self.prefixmap.resolve_iriref(value)?;
+                Ok((Some(Object::iri(iri)), false))
+            }
+            ShapeExpr::NodeConstraint(nc) => {
+                let iri_kind = nc.node_kind() == Some(NodeKind::Iri);
+                let iri_values = nc.values().is_some_and(|values| {
+                    !values.is_empty() && values.iter().all(is_iri_value_set_value)
+                });
+                Ok((None, iri_kind || iri_values))
+            }
+            ShapeExpr::ShapeAnd { shape_exprs } => {
+                let mut value_shape = None;
+                let mut iri_valued = false;
+                for e in shape_exprs {
+                    let (s, iri) = self.value_expr(&e.se)?;
+                    value_shape = value_shape.or(s);
+                    iri_valued = iri_valued || iri;
+                }
+                Ok((value_shape, iri_valued))
+            }
+            _ => Ok((None, false)),
+        }
+    }
+}
+
+fn is_iri_value_set_value(value: &ValueSetValue) -> bool {
+    matches!(
+        value,
+        ValueSetValue::ObjectValue(ObjectValue::IriRef(_))
+            | ValueSetValue::IriStem { .. }
+            | ValueSetValue::IriStemRange { .. }
+    )
+}
+
+fn shacl_property(pred: &IriS, components: &[Component]) -> FrameProperty {
+    let mut property = FrameProperty {
+        predicate: pred.clone(),
+        many: true,
+        iri_valued: false,
+        shape: None,
+    };
+    for component in components {
+        match component {
+            Component::MaxCount(n) => property.many = *n > 1,
+            Component::NodeKind(ShaclNodeKind::Iri) => property.iri_valued = true,
+            Component::Node { shape } => property.shape = Some(shape.clone()),
+            _ => {}
+        }
+    }
+    property
+}
+
+fn shacl_target(target: &Target, prefixmap: &PrefixMap) -> Result<FrameTarget> {
+    let resolve = |iri_ref: &IriRef| prefixmap.resolve_iriref(iri_ref);
+    match target {
+        Target::TargetNode(node) => Ok(FrameTarget::Node(node.clone())),
+        Target::TargetClass(class) => Ok(FrameTarget::Class(class.clone())),
+        Target::TargetSubjectsOf(pred) => Ok(FrameTarget::SubjectsOf(resolve(pred)?)),
+        Target::TargetObjectsOf(pred) => Ok(FrameTarget::ObjectsOf(resolve(pred)?)),
+    }
+}
@@ -0,0 +1,518 @@
+//! Struct that writes RDF nodes as compacted JSON-LD following a [`JsonLdFrame`]
+//!
+//! Each framed node becomes a JSON object with its IRI in `@id`, its classes in `@type` and a member
+//! for each property of its shape, whose values are embedded objects when the property references another shape.
+//! The `@context` maps the local names of the predicates to their IRIs, so the result can be consumed as plain JSON
+//! and a JSON-LD processor obtains the same triples back.
+use std::collections::{HashMap, HashSet};
+
+use indexmap::{IndexMap, IndexSet};
+use iri_s::IriS;
+use prefixmap::{IriRef, PrefixMap};
+use serde_json::{json, Map, Value};
+use srdf::{
+    literal::Literal, numeric_literal::NumericLiteral, Object, RDF_TYPE, SRDF, XSD,
+    XSD_BOOLEAN_STR, XSD_DOUBLE_STR, XSD_INTEGER_STR,
+};
+
+use super::{FrameProperty, FrameTarget, JsonLdFrame, JsonLdFramerError};
+
+type Result<T> = std::result::Result<T, JsonLdFramerError>;
+
+pub struct JsonLdFramer {
+    frame: JsonLdFrame,
+    terms: IndexMap<IriS, TermDefinition>,
+}
+
+/// Term of the context for a predicate of the frame
+struct TermDefinition {
+    name: String,
+    many: bool,
+    iri_valued: bool,
+}
+
+impl JsonLdFramer {
+    /// Creates a framer whose context has a term for each predicate of the frame
+    ///
+    /// Terms are the local names of the predicates, or their prefixed names when two predicates share the local name
+    pub fn new(frame: &JsonLdFrame) -> JsonLdFramer {
+        let prefixmap = frame.prefixmap();
+        let mut terms: IndexMap<IriS, TermDefinition> = IndexMap::new();
+        for (_, shape) in frame.shapes() {
+            for property in shape.properties() {
+                if property.predicate == *RDF_TYPE {
+                    continue;
+                }
+                terms
+                    .entry(property.predicate.clone())
+                    .and_modify(|term| {
+                        term.many = term.many || property.many;
+                        term.iri_valued = term.iri_valued && property.iri_valued;
+                    })
+                    .or_insert(TermDefinition {
+                        name: local_name(&property.predicate).to_string(),
+                        many: property.many,
+                        iri_valued: property.iri_valued,
+                    });
+            }
+        }
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for term in terms.values() {
+            *counts.entry(term.name.clone()).or_default() += 1;
+        }
+        for (iri, term) in terms.iter_mut() {
+            let ambiguous = counts.get(&term.name).is_some_and(|n| *n > 1)
+                || term.name.is_empty()
+                || term.name.starts_with('@')
+                || prefixmap.find(&term.name).is_some();
+            if ambiguous {
+                term.name = compact_iri(iri, prefixmap)
+            }
+        }
+        JsonLdFramer {
+            frame: frame.clone(),
+            terms,
+        }
+    }
+
+    /// JSON-LD context with the prefixes of the frame and the terms of its predicates
+    pub fn context(&self) -> Value {
+        let mut context = Map::new();
+        let mut prefixes: Vec<_> = self
+            .frame
+            .prefixmap()
+            .iter()
+            .filter(|(alias, _)| !alias.is_empty())
+            .collect();
+        prefixes.sort_by_key(|(alias, _)| *alias);
+        for (alias, iri) in prefixes {
+            context.insert(alias.clone(), Value::String(iri.as_str().to_string()));
+        }
+        for (iri, term) in &self.terms {
+            let definition = if term.many || term.iri_valued {
+                let mut definition = Map::new();
+                definition.insert("@id".to_string(), Value::String(iri.as_str().to_string()));
+                if term.iri_valued {
+                    definition.insert("@type".to_string(), Value::String("@id".to_string()));
+                }
+                if term.many {
+                    definition.insert("@container".to_string(), Value::String("@set".to_string()));
+                }
+                Value::Object(definition)
+            } else {
+                Value::String(iri.as_str().to_string())
+            };
+            context.insert(term.name.clone(), definition);
+        }
+        Value::Object(context)
+    }
+
+    /// Node/shape pairs selected by the targets of the shapes of the frame
+    pub fn target_nodes<RDF>(&self, rdf: &RDF) -> Result<Vec<(Object, Object)>>
+    where
+        RDF: SRDF,
+    {
+        let rdf_error = |e: RDF::Err| JsonLdFramerError::RDFError {
+            error: e.to_string(),
+        };
+        let mut pairs = IndexSet::new();
+        for (id, shape) in self.frame.shapes() {
+            for target in shape.targets() {
+                let mut nodes = match target {
+                    FrameTarget::Node(node) => vec![node.clone()],
+                    FrameTarget::Class(class) => rdf
+                        .subjects_with_predicate_object(
+                            &RDF::iri_s2iri(&RDF_TYPE),
+                            &RDF::object_as_term(class),
+                        )
+                        .map_err(rdf_error)?
+                        .iter()
+                        .map(RDF::subject_as_object)
+                        .collect(),
+                    FrameTarget::SubjectsOf(pred) => rdf
+                        .triples_with_predicate(&RDF::iri_s2iri(pred))
+                        .map_err(rdf_error)?
+                        .iter()
+                        .map(|t| RDF::subject_as_object(&t.subj()))
+                        .collect(),
+                    FrameTarget::ObjectsOf(pred) => rdf
+                        .triples_with_predicate(&RDF::iri_s2iri(pred))
+                        .map_err(rdf_error)?
+                        .iter()
+                        .map(|t| RDF::term_as_object(&t.obj()))
+                        .collect(),
+                };
+                nodes.sort_by_key(|node| node.to_string());
+                pairs.extend(nodes.into_iter().map(|node| (node, id.clone())))
+            }
+        }
+        Ok(pairs.into_iter().collect())
+    }
+
+    /// JSON-LD document with a node object in `@graph` for each node/shape pair
+    pub fn frame<RDF>(&self, nodes: &[(Object, Object)], rdf: &RDF) -> Result<Value>
+    where
+        RDF: SRDF,
+    {
+        let mut graph = Vec::new();
+        let mut framed = HashSet::new();
+        for (node, shape) in nodes {
+            if framed.insert((node, shape)) {
+                graph.push(self.node_object(node, shape, rdf, &mut Vec::new())?)
+            }
+        }
+        Ok(json!({
+            "@context": self.context(),
+            "@graph": graph
+        }))
+    }
+
+    fn node_object<RDF>(
+        &self,
+        node: &Object,
+        shape_id: &Object,
+        rdf: &RDF,
+        visiting: &mut Vec<Object>,
+    ) -> Result<Value>
+    where
+        RDF: SRDF,
+    {
+        let shape = self
+            .frame
+            .shape(shape_id)
+            .ok_or_else(|| JsonLdFramerError::ShapeNotFound {
+                shape: shape_id.to_string(),
+            })?;
+        let subject =
+            RDF::object_as_subject(node).ok_or_else(|| JsonLdFramerError::LiteralNode {
+                node: node.to_string(),
+            })?;
+        visiting.push(node.clone());
+        let mut object = Map::new();
+        object.insert("@id".to_string(), Value::String(self.node_id(node)));
+        let types: Vec<_> = self
+            .values(&subject, &RDF_TYPE, rdf)?
+            .iter()
+            .map(|t| Value::String(self.node_id(t)))
+            .collect();
+        if let Some(types) = one_or_many(types, false) {
+            object.insert("@type".to_string(), types);
+        }
+        for property in shape.properties() {
+            let Some(term) = self.terms.get(&property.predicate) else {
+                continue;
+            };
+            let mut values = Vec::new();
+            for value in self.values(&subject, &property.predicate, rdf)? {
+                values.push(self.value(&value, property, term, rdf, visiting)?)
+            }
+            if let Some(values) = one_or_many(values, term.many) {
+                object.insert(term.name.clone(), values);
+            }
+        }
+        visiting.pop();
+        Ok(Value::Object(object))
+    }
+
+    /// Values of a predicate for a subject, sorted to obtain a stable output
+    fn values<RDF>(&self, subject: &RDF::Subject, pred: &IriS, rdf: &RDF) -> Result<Vec<Object>>
+    where
+        RDF: SRDF,
+    {
+        let mut values: Vec<_> = rdf
+            .objects_for_subject_predicate(subject, &RDF::iri_s2iri(pred))
+            .map_err(|e| JsonLdFramerError::RDFError {
+                error: e.to_string(),
+            })?
+            .iter()
+            .map(RDF::term_as_object)
+            .collect();
+        values.sort_by_key(|v| v.to_string());
+        Ok(values)
+    }
+
+    fn value<RDF>(
+        &self,
+        value: &Object,
+        property: &FrameProperty,
+        term: &TermDefinition,
+        rdf: &RDF,
+        visiting: &mut Vec<Object>,
+    ) -> Result<Value>
+    where
+        RDF: SRDF,
+    {
+        match (value, &property.shape) {
+            (Object::Literal(lit), _) => Ok(self.literal(lit)),
+            (Object::Triple { .. }, _) => Ok(json!({ "@id": self.node_ref(value) })),
+            // Nodes already being framed are written as references to avoid infinite nesting
+            (_, Some(shape)) if self.frame.shape(shape).is_some() && !visiting.contains(value) => {
+                self.node_object(value, shape, rdf, visiting)
+            }
+            _ if term.iri_valued => Ok(Value::String(self.node_id(value))),
+            _ => Ok(json!({ "@id": self.node_id(value) })),
+        }
+    }
+
+    fn node_id(&self, node: &Object) -> String {
+        match node {
+            Object::Iri(iri) => compact_iri(iri, self.frame.prefixmap()),
+            Object::BlankNode(id) => format!("_:{id}"),
+            Object::Literal(lit) => lit.to_string(),
+            Object::Triple { .. } => node.to_string(),
+        }
+    }
+
+    /// Reference to a node, where triple terms are written as embedded nodes as in JSON-LD-star
+    fn node_ref(&self, node: &Object) -> Value {
+        match node {
+            Object::Triple {
+                subject,
+                predicate,
+                object,
+            } => {
+                let object = match object.as_ref() {
+                    Object::Literal(lit) => self.literal(lit),
+                    _ => json!({ "@id": self.node_ref(object) }),
+                };
+                let mut embedded = Map::new();
+                embedded.insert("@id".to_string(), self.node_ref(subject));
+                embedded.insert(compact_iri(predicate, self.frame.prefixmap()), object);
+                Value::Object(embedded)
+            }
+            _ => Value::String(self.node_id(node)),
+        }
+    }
+
+    /// Literals with the datatypes that have a JSON counterpart are written as native JSON values
+    fn literal(&self, lit: &Literal) -> Value {
+        match lit {
+            Literal::StringLiteral {
+                lexical_form,
+                lang: None,
+            } => Value::String(lexical_form.clone()),
+            Literal::StringLiteral {
+                lexical_form,
+                lang: Some(lang),
+            } => json!({ "@value": lexical_form, "@language": lang.value() }),
+            Literal::DatatypeLiteral {
+                lexical_form,
+                datatype,
+            } => {
+                let datatype = match datatype {
+                    IriRef::Iri(iri) => iri.clone(),
+                    IriRef::Prefixed { .. } => {
+                        match self.frame.prefixmap().resolve_iriref(datatype) {
+                            Ok(iri) => iri,
+                            Err(_) => {
+                                return json!({ "@value": lexical_form, "@type": datatype.to_string() })
+                            }
+                        }
+                    }
+                };
+                self.typed_literal(lexical_form, &datatype)
+            }
+            Literal::NumericLiteral(NumericLiteral::Integer(n)) => json!(n),
+            Literal::NumericLiteral(NumericLiteral::Double(d)) => {
+                self.typed_literal(&d.to_string(), &IriS::new_unchecked(XSD_DOUBLE_STR))
+            }
+            Literal::NumericLiteral(NumericLiteral::Decimal(d)) => json!({
+                "@value": d.to_string(),
+                "@type": compact_iri(&IriS::new_unchecked(&format!("{XSD}decimal")), self.frame.prefixmap())
+            }),
+            Literal::BooleanLiteral(b) => Value::Bool(*b),
+        }
+    }
+
+    fn typed_literal(&self, lexical_form: &str, datatype: &IriS) -> Value {
+        let native = match datatype.as_str() {
+            XSD_INTEGER_STR => lexical_form.parse::<i64>().ok().map(|n| json!(n)),
+            XSD_BOOLEAN_STR => match lexical_form {
+                "true" => Some(Value::Bool(true)),
+                "false" => Some(Value::Bool(false)),
+                _ => None,
+            },
+            // JSON numbers without fractional part are read back as integers
+            XSD_DOUBLE_STR => lexical_form
+                .parse::<f64>()
+                .ok()
+                .filter(|d| d.is_finite() && d.fract() != 0.0)
+                .map(|d| json!(d)),
+            dt if dt == format!("{XSD}string") => Some(Value::String(lexical_form.to_string())),
+            _ => None,
+        };
+        native.unwrap_or_else(|| {
+            json!({ "@value": lexical_form, "@type": compact_iri(datatype, self.frame.prefixmap()) })
+        })
+    }
+}
+
+/// Single value, or an array if there are several values or the term is declared as a set
+fn one_or_many(mut values: Vec<Value>, many: bool) -> Option<Value> {
+    match values.len() {
+        0 => None,
+        1 if !many => values.pop(),
+        _ => Some(Value::Array(values)),
+    }
+}
+
+fn local_name(iri: &IriS) -> &str {
+    let str = iri.as_str();
+    match str.rfind(['#', '/']) {
+        Some(pos) => &str[pos + 1..],
+        None => str,
+    }
+}
+
+/// Compact IRI with the longest namespace of the prefixes with an alias, or the full IRI
+fn compact_iri(iri: &IriS, prefixmap: &PrefixMap) -> String {
+    prefixmap
+        .iter()
+        .filter(|(alias, _)| !alias.is_empty())
+        .filter_map(|(alias, ns)| {
+            iri.as_str()
+                .strip_prefix(ns.as_str())
+                .filter(|local| !local.is_empty() && !local.starts_with("//"))
+                .map(|local| (ns.as_str().len(), format!("{alias}:{local}")))
+        })
+        .max_by_key(|(len, _)| *len)
+        .map(|(_, compact)| compact)
+        .unwrap_or_else(|| iri.as_str().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shacl_ast::ShaclParser;
+    use shex_compact::ShExParser;
+    use srdf::{srdf_graph::SRDFGraph, RDFFormat, ReaderMode, SRDFBasic};
+
+    const DATA: &str = r#"prefix : <http://example.org/>
+prefix schema: <http://schema.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:alice a schema:Person ; schema:name "Alice"@en ; :age 30 ; :homepage <http://alice.example/> ;
+       :knows :bob, :carol ; :worksFor :acme .
+:bob a schema:Person ; schema:name "Bob"@en ; :knows :alice .
+:carol schema:name "Carol"@en ; :other "ignored" .
+:acme :name "ACME" ; :founded "2001-01-01"^^xsd:date .
+"#;
+
+    fn data() -> SRDFGraph {
+        SRDFGraph::from_str(DATA, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap()
+    }
+
+    fn iri(str: &str) -> Object {
+        Object::iri(IriS::new_unchecked(str))
+    }
+
+    #[test]
+    fn frame_from_shex() {
+        let schema = ShExParser::parse(
+            r#"prefix : <http://example.org/>
+prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#>
+prefix schema: <http://schema.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:Person { a [ schema:Person ] ? ; schema:name rdf:langString ; :age xsd:integer ? ;
+          :homepage IRI ? ; :knows @:Person * ; :worksFor @:Company ? }
+:Company { :name xsd:string ; :founded xsd:date }
+"#,
+            None,
+        )
+        .unwrap();
+        let framer = JsonLdFramer::new(&JsonLdFrame::from_shex(&schema).unwrap());
+        let framed = framer
+            .frame(
+                &[(
+                    iri("http://example.org/alice"),
+                    iri("http://example.org/Person"),
+                )],
+                &data(),
+            )
+            .unwrap();
+        assert_eq!(
+            framed,
+            json!({
+                "@context": {
+                    "rdf": "http://www.w3.org/1999/02/22-rdf-syntax-ns#",
+                    "schema": "http://schema.org/",
+                    "xsd": "http://www.w3.org/2001/XMLSchema#",
+                    "schema:name": "http://schema.org/name",
+                    "age": "http://example.org/age",
+                    "homepage": { "@id": "http://example.org/homepage", "@type": "@id" },
+                    "knows": { "@id": "http://example.org/knows", "@container": "@set" },
+                    "worksFor": "http://example.org/worksFor",
+                    "http://example.org/name": "http://example.org/name",
+                    "founded": "http://example.org/founded"
+                },
+                "@graph": [{
+                    "@id": "http://example.org/alice",
+                    "@type": "schema:Person",
+                    "schema:name": { "@value": "Alice", "@language": "en" },
+                    "age": 30,
+                    "homepage": "http://alice.example/",
+                    "knows": [
+                        {
+                            "@id": "http://example.org/bob",
+                            "@type": "schema:Person",
+                            "schema:name": { "@value": "Bob", "@language": "en" },
+                            "knows": [{ "@id": "http://example.org/alice" }]
+                        },
+                        {
+                            "@id": "http://example.org/carol",
+                            "schema:name": { "@value": "Carol", "@language": "en" }
+                        }
+                    ],
+                    "worksFor": {
+                        "@id": "http://example.org/acme",
+                        "http://example.org/name": "ACME",
+                        "founded": { "@value": "2001-01-01", "@type": "xsd:date" }
+                    }
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn frame_from_shacl_targets() {
+        let shapes = r#"prefix : <http://example.org/>
+prefix sh: <http://www.w3.org/ns/shacl#>
+prefix schema: <http://schema.org/>
+:PersonShape a sh:NodeShape ; sh:targetClass schema:Person ;
+  sh:property [ sh:path schema:name ; sh:maxCount 1 ] ;
+  sh:property [ sh:path :knows ; sh:nodeKind sh:IRI ] .
+"#;
+        let rdf =
+            SRDFGraph::from_str(shapes, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let schema = ShaclParser::new(rdf).parse().unwrap();
+        let framer = JsonLdFramer::new(&JsonLdFrame::from_shacl(&schema).unwrap());
+        let data = data();
+        let nodes = framer.target_nodes(&data).unwrap();
+        assert_eq!(
+            nodes.iter().map(|(n, _)| n.clone()).collect::<Vec<_>>(),
+            vec![
+                iri("http://example.org/alice"),
+                iri("http://example.org/bob")
+            ]
+        );
+        let framed = framer.frame(&nodes, &data).unwrap();
+        assert_eq!(
+            framed["@graph"][1],
+            json!({
+                "@id": "http://example.org/bob",
+                "@type": "schema:Person",
+                "name": { "@value": "Bob", "@language": "en" },
+                "knows": ["http://example.org/alice"]
+            })
+        );
+
+        // The framed document is read back by a JSON-LD parser
+        let jsonld = serde_json::to_string(&framed).unwrap();
+        let parsed =
+            SRDFGraph::from_str(&jsonld, &RDFFormat::JsonLd, None, &ReaderMode::Strict).unwrap();
+        let bob = SRDFGraph::iri_s2subject(&IriS::new_unchecked("http://example.org/bob"));
+        assert_eq!(
+            parsed.outgoing_arcs(&bob).unwrap(),
+            data.outgoing_arcs(&bob).unwrap()
+        );
+    }
+}
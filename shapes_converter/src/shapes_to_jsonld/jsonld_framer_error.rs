@@ -0,0 +1,20 @@
+use prefixmap::PrefixMapError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum JsonLdFramerError {
+    #[error("Shape {shape} not found in the frame")]
+    ShapeNotFound { shape: String },
+
+    #[error("Node {node} can't be framed because it is a literal")]
+    LiteralNode { node: String },
+
+    #[error("Error reading RDF data to frame: {error}")]
+    RDFError { error: String },
+
+    #[error(transparent)]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
+}
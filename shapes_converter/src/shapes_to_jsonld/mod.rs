@@ -0,0 +1,10 @@
+//! ShEx and SHACL to JSON-LD frames
+//!
+//!
+pub mod jsonld_frame;
+pub mod jsonld_framer;
+pub mod jsonld_framer_error;
+
+pub use jsonld_frame::*;
+pub use jsonld_framer::*;
+pub use jsonld_framer_error::*;
@@ -0,0 +1,10 @@
+//! ShEx to DCTAP converter
+//!
+//!
+pub mod shex2tap;
+pub mod shex2tap_config;
+pub mod shex2tap_error;
+
+pub use shex2tap::*;
+pub use shex2tap_config::*;
+pub use shex2tap_error::*;
@@ -0,0 +1,551 @@
+//! Struct that converts ShEx schemas to DCTAP
+//!
+//! DCTAP is less expressive than ShEx, so the constructs that can't be represented
+//! are collected as warnings and, optionally, added to the `note` column of the statements
+use dctap::{
+    BasicNodeType, DCTap, DatatypeId, NodeType, PropertyId, ShapeId, TapShape, TapStatement, Value,
+    ValueConstraint,
+};
+use iri_s::IriS;
+use prefixmap::{IriRef, PrefixMap};
+use shex_ast::{
+    Annotation, NodeConstraint, NodeKind, NumericFacet, ObjectValue, Pattern, Schema, Shape,
+    ShapeDecl, ShapeExpr, ShapeExprLabel, StringFacet, TripleExpr, ValueSetValue, XsFacet,
+};
+use srdf::{RDFS_COMMENT, RDFS_LABEL};
+
+use super::{ShEx2TapConfig, ShEx2TapError, ShEx2TapWarning};
+use crate::{iri2tap_str, numeric_literal2number};
+
+pub struct ShEx2Tap {
+    config: ShEx2TapConfig,
+    current_tap: DCTap,
+    warnings: Vec<ShEx2TapWarning>,
+}
+
+impl ShEx2Tap {
+    pub fn new(config: &ShEx2TapConfig) -> ShEx2Tap {
+        ShEx2Tap {
+            config: config.clone(),
+            current_tap: DCTap::new(),
+            warnings: Vec::new(),
+        }
+    }
+
+    pub fn current_tap(&self) -> &DCTap {
+        &self.current_tap
+    }
+
+    /// Constructs of the last converted schema that could not be represented in DCTAP
+    pub fn warnings(&self) -> impl Iterator<Item = &ShEx2TapWarning> {
+        self.warnings.iter()
+    }
+
+    pub fn convert(&mut self, schema: &Schema) -> Result<(), ShEx2TapError> {
+        let prefixmap = schema
+            .prefixmap()
+            .unwrap_or_default()
+            .without_rich_qualifying();
+        self.current_tap = DCTap::new();
+        self.warnings = Vec::new();
+        for shape_decl in schema.shapes().unwrap_or_default() {
+            let tap_shape = self.shape_decl2tap_shape(&shape_decl, &prefixmap)?;
+            self.current_tap.add_shape(&tap_shape)
+        }
+        Ok(())
+    }
+
+    fn shape_decl2tap_shape(
+        &mut self,
+        shape_decl: &ShapeDecl,
+        prefixmap: &PrefixMap,
+    ) -> Result<TapShape, ShEx2TapError> {
+        let shape_id = label2str(&shape_decl.id, prefixmap)?;
+        let mut tap_shape = TapShape::new(0);
+        tap_shape.set_shape_id(&ShapeId::new(&shape_id, 0));
+        match &shape_decl.shape_expr {
+            ShapeExpr::Shape(shape) => {
+                self.shape2tap_shape(&shape_id, shape, &mut tap_shape, prefixmap)?
+            }
+            _ => self
+                .warnings
+                .push(ShEx2TapWarning::NotShape { shape: shape_id }),
+        }
+        Ok(tap_shape)
+    }
+
+    fn shape2tap_shape(
+        &mut self,
+        shape_id: &str,
+        shape: &Shape,
+        tap_shape: &mut TapShape,
+        prefixmap: &PrefixMap,
+    ) -> Result<(), ShEx2TapError> {
+        if let Some(label) = find_annotation_str(&shape.annotations, &RDFS_LABEL, prefixmap) {
+            tap_shape.set_shape_label(&label)
+        }
+        if let Some(true) = shape.closed {
+            self.warnings.push(ShEx2TapWarning::ClosedShape {
+                shape: shape_id.to_string(),
+            })
+        }
+        if shape.extra.as_ref().is_some_and(|extra| !extra.is_empty()) {
+            self.warnings.push(ShEx2TapWarning::ExtraProperties {
+                shape: shape_id.to_string(),
+            })
+        }
+        if shape.sem_acts.as_ref().is_some_and(|sa| !sa.is_empty()) {
+            self.warnings.push(ShEx2TapWarning::SemActs {
+                shape: shape_id.to_string(),
+            })
+        }
+        let mut extends_ids = Vec::new();
+        for extend in shape.extends.iter().flatten() {
+            let extends_id = label2str(extend, prefixmap)?;
+            tap_shape.add_extends_id(&ShapeId::new(&extends_id, 0), 0);
+            extends_ids.push(extends_id)
+        }
+        if extends_ids.len() > 1 {
+            self.warnings.push(ShEx2TapWarning::MultipleExtends {
+                shape: shape_id.to_string(),
+                ignored: extends_ids[1..].join(", "),
+            })
+        }
+        if let Some(expression) = &shape.expression {
+            self.triple_expr2statements(shape_id, &expression.te, tap_shape, prefixmap)?
+        }
+        Ok(())
+    }
+
+    fn triple_expr2statements(
+        &mut self,
+        shape_id: &str,
+        triple_expr: &TripleExpr,
+        tap_shape: &mut TapShape,
+        prefixmap: &PrefixMap,
+    ) -> Result<(), ShEx2TapError> {
+        match triple_expr {
+            TripleExpr::EachOf {
+                expressions,
+                min,
+                max,
+                ..
+            } => {
+                self.check_group_cardinality(shape_id, min, max);
+                for e in expressions {
+                    self.triple_expr2statements(shape_id, &e.te, tap_shape, prefixmap)?
+                }
+                Ok(())
+            }
+            TripleExpr::OneOf {
+                expressions,
+                min,
+                max,
+                ..
+            } => {
+                self.warnings.push(ShEx2TapWarning::OneOf {
+                    shape: shape_id.to_string(),
+                });
+                self.check_group_cardinality(shape_id, min, max);
+                for e in expressions {
+                    self.triple_expr2statements(shape_id, &e.te, tap_shape, prefixmap)?
+                }
+                Ok(())
+            }
+            TripleExpr::TripleConstraint {
+                negated,
+                inverse,
+                predicate,
+                value_expr,
+                min,
+                max,
+                annotations,
+                ..
+            } => {
+                let property = iri_ref2str(predicate, prefixmap)?;
+                let mut statement = TapStatement::new(PropertyId::new(&property, 0));
+                let mut warnings = Vec::new();
+                if let Some(true) = negated {
+                    warnings.push(ShEx2TapWarning::Negated {
+                        shape: shape_id.to_string(),
+                        property: property.clone(),
+                    })
+                }
+                if let Some(true) = inverse {
+                    warnings.push(ShEx2TapWarning::Inverse {
+                        shape: shape_id.to_string(),
+                        property: property.clone(),
+                    })
+                }
+                let min = min.unwrap_or(1);
+                let max = max.unwrap_or(1);
+                statement.set_mandatory(min >= 1);
+                statement.set_repeatable(max == -1 || max > 1);
+                if min > 1 || max == 0 || max > 1 {
+                    warnings.push(ShEx2TapWarning::Cardinality {
+                        shape: shape_id.to_string(),
+                        property: property.clone(),
+                        min,
+                        max,
+                    })
+                }
+                if let Some(label) = find_annotation_str(annotations, &RDFS_LABEL, prefixmap) {
+                    statement.set_property_label(&label)
+                }
+                if let Some(value_expr) = value_expr {
+                    self.value_expr2statement(
+                        shape_id,
+                        &property,
+                        value_expr,
+                        &mut statement,
+                        &mut warnings,
+                        prefixmap,
+                    )?
+                }
+                let comment = find_annotation_str(annotations, &RDFS_COMMENT, prefixmap);
+                self.add_notes(&mut statement, comment, warnings);
+                tap_shape.add_statement(statement);
+                Ok(())
+            }
+            TripleExpr::TripleExprRef(label) => {
+                self.warnings.push(ShEx2TapWarning::TripleExprRef {
+                    shape: shape_id.to_string(),
+                    label: label.to_string(),
+                });
+                Ok(())
+            }
+        }
+    }
+
+    fn check_group_cardinality(&mut self, shape_id: &str, min: &Option<i32>, max: &Option<i32>) {
+        let min = min.unwrap_or(1);
+        let max = max.unwrap_or(1);
+        if min != 1 || max != 1 {
+            self.warnings.push(ShEx2TapWarning::GroupCardinality {
+                shape: shape_id.to_string(),
+                min,
+                max,
+            })
+        }
+    }
+
+    fn value_expr2statement(
+        &self,
+        shape_id: &str,
+        property: &str,
+        value_expr: &ShapeExpr,
+        statement: &mut TapStatement,
+        warnings: &mut Vec<ShEx2TapWarning>,
+        prefixmap: &PrefixMap,
+    ) -> Result<(), ShEx2TapError> {
+        let unsupported = |name: &str| ShEx2TapWarning::ValueExpr {
+            shape: shape_id.to_string(),
+            property: property.to_string(),
+            value_expr: name.to_string(),
+        };
+        match value_expr {
+            ShapeExpr::Ref(label) => {
+                let value_shape = label2str(label, prefixmap)?;
+                statement.set_value_shape(&ShapeId::new(&value_shape, 0));
+            }
+            ShapeExpr::NodeConstraint(nc) => self.node_constraint2statement(
+                shape_id, property, nc, statement, warnings, prefixmap,
+            )?,
+            ShapeExpr::Shape(_) => warnings.push(unsupported("inline shape")),
+            ShapeExpr::ShapeAnd { .. } => warnings.push(unsupported("AND")),
+            ShapeExpr::ShapeOr { .. } => warnings.push(unsupported("OR")),
+            ShapeExpr::ShapeNot { .. } => warnings.push(unsupported("NOT")),
+            ShapeExpr::External => warnings.push(unsupported("EXTERNAL")),
+        }
+        Ok(())
+    }
+
+    fn node_constraint2statement(
+        &self,
+        shape_id: &str,
+        property: &str,
+        node_constraint: &NodeConstraint,
+        statement: &mut TapStatement,
+        warnings: &mut Vec<ShEx2TapWarning>,
+        prefixmap: &PrefixMap,
+    ) -> Result<(), ShEx2TapError> {
+        let unsupported = |constraint: String| ShEx2TapWarning::ValueConstraint {
+            shape: shape_id.to_string(),
+            property: property.to_string(),
+            constraint,
+        };
+        if let Some(node_kind) = node_constraint.node_kind() {
+            match node_kind2node_type(&node_kind) {
+                Some(node_type) => statement.set_value_nodetype(&node_type),
+                None => warnings.push(unsupported(format!("node kind {node_kind}"))),
+            }
+        }
+        if let Some(datatype) = node_constraint.datatype() {
+            let datatype = iri_ref2str(&datatype, prefixmap)?;
+            statement.set_value_datatype(&DatatypeId::new(&datatype, 0))
+        }
+        let mut value_constraints = Vec::new();
+        if let Some(values) = node_constraint.values() {
+            match values2value_constraint(&values, prefixmap)? {
+                Some(vc) => value_constraints.push(vc),
+                None => warnings.push(unsupported("value set".to_string())),
+            }
+        }
+        for facet in node_constraint.xs_facet().unwrap_or_default() {
+            match facet {
+                XsFacet::StringFacet(StringFacet::Pattern(Pattern { str, flags })) => {
+                    value_constraints.push(ValueConstraint::Pattern(str));
+                    if let Some(flags) = flags {
+                        warnings.push(unsupported(format!("pattern flags {flags}")))
+                    }
+                }
+                XsFacet::StringFacet(StringFacet::MinLength(n)) => {
+                    value_constraints.push(ValueConstraint::MinLength(n))
+                }
+                XsFacet::StringFacet(StringFacet::MaxLength(n)) => {
+                    value_constraints.push(ValueConstraint::MaxLength(n))
+                }
+                XsFacet::StringFacet(StringFacet::Length(n)) => {
+                    warnings.push(unsupported(format!("LENGTH {n}")))
+                }
+                XsFacet::NumericFacet(NumericFacet::MinInclusive(n)) => value_constraints
+                    .push(ValueConstraint::MinInclusive(numeric_literal2number(&n))),
+                XsFacet::NumericFacet(NumericFacet::MinExclusive(n)) => value_constraints
+                    .push(ValueConstraint::MinExclusive(numeric_literal2number(&n))),
+                XsFacet::NumericFacet(NumericFacet::MaxInclusive(n)) => value_constraints
+                    .push(ValueConstraint::MaxInclusive(numeric_literal2number(&n))),
+                XsFacet::NumericFacet(NumericFacet::MaxExclusive(n)) => value_constraints
+                    .push(ValueConstraint::MaxExclusive(numeric_literal2number(&n))),
+                XsFacet::NumericFacet(NumericFacet::TotalDigits(n)) => {
+                    warnings.push(unsupported(format!("TOTALDIGITS {n}")))
+                }
+                XsFacet::NumericFacet(NumericFacet::FractionDigits(n)) => {
+                    warnings.push(unsupported(format!("FRACTIONDIGITS {n}")))
+                }
+            }
+        }
+        let mut value_constraints = value_constraints.into_iter();
+        if let Some(vc) = value_constraints.next() {
+            statement.set_value_constraint(&vc)
+        }
+        for vc in value_constraints {
+            warnings.push(ShEx2TapWarning::MultipleValueConstraints {
+                shape: shape_id.to_string(),
+                property: property.to_string(),
+                constraint: vc.to_string(),
+            })
+        }
+        Ok(())
+    }
+
+    fn add_notes(
+        &mut self,
+        statement: &mut TapStatement,
+        comment: Option<String>,
+        warnings: Vec<ShEx2TapWarning>,
+    ) {
+        let mut notes: Vec<String> = comment.into_iter().collect();
+        if self.config.add_warnings_as_notes() {
+            notes.extend(warnings.iter().map(|w| w.to_string()))
+        }
+        if !notes.is_empty() {
+            statement.set_note(notes.join("; ").as_str())
+        }
+        self.warnings.extend(warnings)
+    }
+}
+
+fn label2str(label: &ShapeExprLabel, prefixmap: &PrefixMap) -> Result<String, ShEx2TapError> {
+    match label {
+        ShapeExprLabel::IriRef { value } => iri_ref2str(value, prefixmap),
+        ShapeExprLabel::BNode { value } => Ok(value.to_string()),
+        ShapeExprLabel::Start => Ok("START".to_string()),
+    }
+}
+
+fn iri_ref2str(iri_ref: &IriRef, prefixmap: &PrefixMap) -> Result<String, ShEx2TapError> {
+    let iri = prefixmap.resolve_iriref(iri_ref)?;
+    Ok(iri2tap_str(&iri, prefixmap))
+}
+
+// Annotations whose predicate can't be resolved are ignored
+fn find_annotation_str(
+    annotations: &Option<Vec<Annotation>>,
+    predicate: &IriS,
+    prefixmap: &PrefixMap,
+) -> Option<String> {
+    annotations
+        .iter()
+        .flatten()
+        .find(|a| prefixmap.resolve_iriref(&a.predicate()).ok().as_ref() == Some(predicate))
+        .map(|a| match a.object() {
+            ObjectValue::IriRef(iri_ref) => iri_ref.to_string(),
+            ObjectValue::Literal(lit) => lit.lexical_form(),
+        })
+}
+
+// DCTAP has no node type for triple terms
+fn node_kind2node_type(node_kind: &NodeKind) -> Option<NodeType> {
+    match node_kind {
+        NodeKind::Iri => Some(NodeType::Basic(BasicNodeType::IRI)),
+        NodeKind::BNode => Some(NodeType::Basic(BasicNodeType::BNode)),
+        NodeKind::Literal => Some(NodeType::Basic(BasicNodeType::Literal)),
+        NodeKind::NonLiteral => Some(NodeType::Or(vec![BasicNodeType::IRI, BasicNodeType::BNode])),
+        NodeKind::Triple => None,
+    }
+}
+
+// Value sets can be represented as picklists when they only contain values (IRIs are
+// written as prefixed names), or as IRI stems and language tags when they contain
+// a single stem or language
+fn values2value_constraint(
+    values: &[ValueSetValue],
+    prefixmap: &PrefixMap,
+) -> Result<Option<ValueConstraint>, ShEx2TapError> {
+    match values {
+        [ValueSetValue::IriStem { stem }] => {
+            let iri = prefixmap.resolve_iriref(stem)?;
+            Ok(Some(ValueConstraint::IRIStem(iri)))
+        }
+        [ValueSetValue::Language { language_tag }] => {
+            Ok(Some(ValueConstraint::LanguageTag(language_tag.value())))
+        }
+        _ => {
+            let mut picklist = Vec::new();
+            for value in values {
+                match value {
+                    ValueSetValue::ObjectValue(ObjectValue::IriRef(iri_ref)) => {
+                        picklist.push(Value::Str(iri_ref2str(iri_ref, prefixmap)?))
+                    }
+                    ValueSetValue::ObjectValue(ObjectValue::Literal(lit)) => {
+                        picklist.push(Value::Str(lit.lexical_form()))
+                    }
+                    _ => return Ok(None),
+                }
+            }
+            Ok(Some(ValueConstraint::PickList(picklist)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Tap2ShEx, Tap2ShExConfig};
+    use dctap::{TapConfig, TapWriter};
+    use shex_compact::ShExParser;
+
+    fn shex2tap(str: &str) -> (Schema, ShEx2Tap) {
+        let schema = ShExParser::parse(str, None).unwrap();
+        let mut converter = ShEx2Tap::new(&ShEx2TapConfig::default());
+        converter.convert(&schema).unwrap();
+        (schema, converter)
+    }
+
+    #[test]
+    fn test_shex2tap_statements() {
+        let (_, converter) = shex2tap(
+            r#"prefix : <http://example.org/>
+               prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+               :Person {
+                 :name xsd:string ;
+                 :knows @:Person * ;
+                 :status [ :Active :Inactive ] ?
+               }"#,
+        );
+        let shape = converter.current_tap().shapes().next().unwrap();
+        assert_eq!(shape.shape_id().unwrap().str(), ":Person");
+        let statements: Vec<_> = shape.statements().collect();
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].property_id().str(), ":name");
+        assert_eq!(statements[0].value_datatype().unwrap().str(), "xsd:string");
+        assert_eq!(statements[0].mandatory(), Some(true));
+        assert_eq!(statements[1].value_shape().unwrap().str(), ":Person");
+        assert_eq!(statements[1].repeatable(), Some(true));
+        assert_eq!(statements[1].mandatory(), Some(false));
+        assert_eq!(
+            statements[2].value_constraint(),
+            &Some(ValueConstraint::PickList(vec![
+                Value::new(":Active"),
+                Value::new(":Inactive")
+            ]))
+        );
+        assert_eq!(converter.warnings().count(), 0)
+    }
+
+    #[test]
+    fn test_shex2tap_warnings_as_notes() {
+        let (_, converter) = shex2tap(
+            r#"prefix : <http://example.org/>
+               :Person CLOSED {
+                 :name . {2,3}
+               }"#,
+        );
+        let warnings: Vec<_> = converter.warnings().collect();
+        assert_eq!(warnings.len(), 2);
+        let statement = converter
+            .current_tap()
+            .shapes()
+            .next()
+            .unwrap()
+            .statements()
+            .next()
+            .unwrap();
+        assert_eq!(
+            statement.note(),
+            &Some(
+                "Shape :Person, property :name: cardinality {2,3} approximated by mandatory/repeatable"
+                    .to_string()
+            )
+        )
+    }
+
+    #[test]
+    fn test_shex2tap_round_trip() {
+        let (schema, converter) = shex2tap(
+            r#"prefix : <http://example.org/>
+               prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+               :Person {
+                 :name xsd:string ;
+                 :age xsd:integer ? ;
+                 :knows @:Person *
+               }
+               :Student extends @:Person {
+                 :school @:School
+               }
+               :School {}"#,
+        );
+        let tap = converter.current_tap();
+        let config = Tap2ShExConfig::default().with_prefixmap(schema.prefixmap().unwrap());
+        let shex = Tap2ShEx::new(&config).convert(tap).unwrap();
+        let mut converter2 = ShEx2Tap::new(&ShEx2TapConfig::default());
+        converter2.convert(&shex).unwrap();
+        assert_eq!(converter2.current_tap(), tap)
+    }
+
+    #[test]
+    fn test_shex2tap_multiple_extends() {
+        let (_, converter) = shex2tap(
+            r#"prefix : <http://example.org/>
+               :Person {}
+               :Employee {}
+               :Student {}
+               :Intern extends @:Person extends @:Employee extends @:Student {}"#,
+        );
+        let warnings: Vec<_> = converter.warnings().collect();
+        assert_eq!(
+            warnings,
+            vec![&ShEx2TapWarning::MultipleExtends {
+                shape: ":Intern".to_string(),
+                ignored: ":Employee, :Student".to_string()
+            }]
+        );
+        let mut csv = Vec::new();
+        TapWriter::new(&TapConfig::default())
+            .write_csv(converter.current_tap(), &mut csv)
+            .unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let intern = csv.lines().find(|line| line.starts_with(":Intern")).unwrap();
+        assert_eq!(intern, ":Intern,,,,,,,,,,,,:Person,");
+    }
+}
@@ -0,0 +1,87 @@
+use prefixmap::PrefixMapError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ShEx2TapError {
+    #[error("ShEx2Tap error: Feature not implemented: {msg}")]
+    NotImplemented { msg: String },
+
+    #[error(transparent)]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
+}
+
+/// Constructs of the ShEx schema that can't be represented in DCTAP
+///
+/// The conversion goes on when they are found, generating an approximation of the original schema
+#[derive(Error, Debug, PartialEq, Clone)]
+pub enum ShEx2TapWarning {
+    #[error(
+        "Shape {shape}: shape expression is not a shape, it has been converted as an empty shape"
+    )]
+    NotShape { shape: String },
+
+    #[error("Shape {shape}: CLOSED is not supported in DCTAP")]
+    ClosedShape { shape: String },
+
+    #[error("Shape {shape}: EXTRA properties are not supported in DCTAP")]
+    ExtraProperties { shape: String },
+
+    #[error("Shape {shape}: DCTAP tables only keep the first extended shape, ignored {ignored}")]
+    MultipleExtends { shape: String, ignored: String },
+
+    #[error("Shape {shape}: semantic actions are not supported in DCTAP")]
+    SemActs { shape: String },
+
+    #[error("Shape {shape}: alternatives (OneOf) are not supported in DCTAP, converted as a sequence of statements")]
+    OneOf { shape: String },
+
+    #[error("Shape {shape}: cardinality {{{min},{max}}} of a group of triple expressions is not supported in DCTAP")]
+    GroupCardinality { shape: String, min: i32, max: i32 },
+
+    #[error(
+        "Shape {shape}: references to triple expressions ({label}) are not supported in DCTAP"
+    )]
+    TripleExprRef { shape: String, label: String },
+
+    #[error(
+        "Shape {shape}, property {property}: inverse triple constraints are not supported in DCTAP"
+    )]
+    Inverse { shape: String, property: String },
+
+    #[error(
+        "Shape {shape}, property {property}: negated triple constraints are not supported in DCTAP"
+    )]
+    Negated { shape: String, property: String },
+
+    #[error("Shape {shape}, property {property}: cardinality {{{min},{max}}} approximated by mandatory/repeatable")]
+    Cardinality {
+        shape: String,
+        property: String,
+        min: i32,
+        max: i32,
+    },
+
+    #[error("Shape {shape}, property {property}: value expression {value_expr} is not supported in DCTAP")]
+    ValueExpr {
+        shape: String,
+        property: String,
+        value_expr: String,
+    },
+
+    #[error("Shape {shape}, property {property}: value constraint {constraint} is not supported in DCTAP")]
+    ValueConstraint {
+        shape: String,
+        property: String,
+        constraint: String,
+    },
+
+    #[error("Shape {shape}, property {property}: only one value constraint is supported in DCTAP, ignored {constraint}")]
+    MultipleValueConstraints {
+        shape: String,
+        property: String,
+        constraint: String,
+    },
+}
@@ -6,7 +6,8 @@ use dctap::{DCTap, DatatypeId, ExtendsId, PropertyId, ShapeId, TapShape, TapStat
 use iri_s::IriS;
 use prefixmap::IriRef;
 use shex_ast::{
-    Annotation, NodeConstraint, Schema, Shape, ShapeDecl, ShapeExpr, ShapeExprLabel, TripleExpr,
+    Annotation, NodeConstraint, ObjectValue, Schema, Shape, ShapeDecl, ShapeExpr, ShapeExprLabel,
+    TripleExpr,
 };
 
 use crate::{Tap2ShExConfig, Tap2ShExError};
@@ -47,6 +48,10 @@ fn tapshape_to_shape(
         if let Some(shape_label) = tap_shape.shape_label() {
             shape.add_annotation(Annotation::rdfs_label(shape_label.as_str()))
         }
+        for (column, value) in tap_shape.extensions() {
+            let annotation = extension2annotation(column, value, tap_shape.start_line(), config)?;
+            shape.add_annotation(annotation)
+        }
         Ok(shape)
     } else {
         Err(Tap2ShExError::NoShapeId {
@@ -131,9 +136,30 @@ fn statement_to_triple_expr(
     if let Some(label) = statement.property_label() {
         te.add_annotation(Annotation::rdfs_label(label))
     }
+    for (column, value) in statement.extensions() {
+        let annotation =
+            extension2annotation(column, value, statement.property_id().line(), config)?;
+        te.add_annotation(annotation)
+    }
     Ok(te)
 }
 
+// The values of extension columns are kept as annotations whose predicate is the column name
+// TODO: Added the following to make clippy happy...should we refactor Tap2ShExError ?
+#[allow(clippy::result_large_err)]
+fn extension2annotation(
+    column: &str,
+    value: &str,
+    line: u64,
+    config: &Tap2ShExConfig,
+) -> Result<Annotation, Tap2ShExError> {
+    let predicate = config.resolve_iri(column, line)?;
+    Ok(Annotation::new(
+        IriRef::iri(predicate),
+        ObjectValue::str(value),
+    ))
+}
+
 fn get_min(mandatory: Option<bool>) -> Option<i32> {
     match mandatory {
         Some(true) => Some(1),
@@ -169,3 +195,36 @@ fn property_id2iri<'a>(
     let iri = config.resolve_iri(property_id.str(), property_id.line())?;
     Ok(iri)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use dctap::TapConfig;
+
+    #[test]
+    fn test_extensions_as_annotations() {
+        let data = "shapeID,propertyID,severity\nPerson,name,Warning\n";
+        let tap_config = TapConfig::default().with_extension_columns(vec!["severity".to_string()]);
+        let dctap = DCTap::from_reader(data.as_bytes(), &tap_config).unwrap();
+        let schema = Tap2ShEx::new(&Tap2ShExConfig::default())
+            .convert(&dctap)
+            .unwrap();
+        let shape_decl = schema.shapes().unwrap().pop().unwrap();
+        let ShapeExpr::Shape(shape) = shape_decl.shape_expr else {
+            panic!("Expected a shape")
+        };
+        let TripleExpr::EachOf { expressions, .. } = shape.expression.unwrap().te else {
+            panic!("Expected an EachOf")
+        };
+        let TripleExpr::TripleConstraint { annotations, .. } = &expressions[0].te else {
+            panic!("Expected a triple constraint")
+        };
+        assert_eq!(
+            annotations,
+            &Some(vec![Annotation::new(
+                IriRef::iri(IriS::new_unchecked("http://example.org/severity")),
+                ObjectValue::str("Warning")
+            )])
+        )
+    }
+}
@@ -18,6 +18,11 @@ pub struct Tap2ShExConfig {
 }
 
 impl Tap2ShExConfig {
+    pub fn with_prefixmap(mut self, prefixmap: PrefixMap) -> Self {
+        self.prefixmap = Some(prefixmap);
+        self
+    }
+
     pub fn prefixmap(&self) -> PrefixMap {
         match &self.prefixmap {
             Some(pm) => pm.clone(),
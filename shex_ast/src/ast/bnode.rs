@@ -14,6 +14,10 @@ impl BNode {
             value: s.to_string(),
         }
     }
+
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
 }
 
 impl TryFrom<&str> for BNode {
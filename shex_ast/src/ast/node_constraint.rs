@@ -355,6 +355,7 @@ impl<'de> Deserialize<'de> for NodeConstraint {
                                 "bnode" => Some(NodeKind::BNode),
                                 "literal" => Some(NodeKind::Literal),
                                 "nonliteral" => Some(NodeKind::NonLiteral),
+                                "triple" => Some(NodeKind::Triple),
                                 _ => {
                                     return Err(de::Error::custom(format!(
                                         "Unexpected value for `nodeKind`: {value}"
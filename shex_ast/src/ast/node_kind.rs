@@ -9,6 +9,9 @@ pub enum NodeKind {
     BNode,
     NonLiteral,
     Literal,
+
+    /// Triple terms (quoted triples in RDF-star)
+    Triple,
 }
 
 impl Display for NodeKind {
@@ -18,6 +21,7 @@ impl Display for NodeKind {
             NodeKind::BNode => write!(f, "BNode"),
             NodeKind::NonLiteral => write!(f, "NonLiteral"),
             NodeKind::Literal => write!(f, "Literal"),
+            NodeKind::Triple => write!(f, "Triple"),
         }
     }
 }
@@ -181,6 +181,10 @@ impl Schema {
         self.prefixmap.clone()
     }
 
+    pub fn imports(&self) -> Option<Vec<Iri>> {
+        self.imports.clone()
+    }
+
     pub fn start_actions(&self) -> Option<Vec<SemAct>> {
         self.start_acts.clone()
     }
@@ -12,7 +12,7 @@ use super::shape_label::ShapeLabel;
 
 type Result<A> = std::result::Result<A, CompiledSchemaError>;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub struct CompiledSchema {
     shape_labels_map: HashMap<ShapeLabel, ShapeLabelIdx>,
     shapes: HashMap<ShapeLabelIdx, (ShapeLabel, ShapeExpr)>,
@@ -34,6 +34,10 @@ impl CompiledSchema {
         self.prefixmap = prefixmap.clone().unwrap_or_default();
     }
 
+    pub fn prefixmap(&self) -> PrefixMap {
+        self.prefixmap.clone()
+    }
+
     pub fn add_shape(&mut self, shape_label: ShapeLabel, se: ShapeExpr) {
         let idx = self.shape_label_counter;
         self.shape_labels_map.insert(shape_label.clone(), idx);
@@ -41,6 +45,10 @@ impl CompiledSchema {
         self.shape_label_counter.incr()
     }
 
+    pub fn set_start_idx(&mut self, idx: ShapeLabelIdx) {
+        self.shape_labels_map.insert(ShapeLabel::Start, idx);
+    }
+
     pub fn get_shape_expr(&self, shape_label: &ShapeLabel) -> Option<&ShapeExpr> {
         if let Some(idx) = self.find_shape_label_idx(shape_label) {
             self.shapes.get(idx).map(|(_label, se)| se)
@@ -113,7 +121,7 @@ impl CompiledSchema {
         }
     }*/
 
-    pub fn find_ref(&mut self, se_ref: &ShapeExprLabel) -> CResult<ShapeLabelIdx> {
+    pub fn find_ref(&self, se_ref: &ShapeExprLabel) -> CResult<ShapeLabelIdx> {
         let shape_label = match se_ref {
             ShapeExprLabel::IriRef { value } => match value {
                 IriRef::Iri(iri) => {
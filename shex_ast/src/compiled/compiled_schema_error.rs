@@ -40,6 +40,9 @@ pub enum CompiledSchemaError {
     #[error("NodeKind NonLiteral but found {node}")]
     NodeKindNonLiteral { node: Node },
 
+    #[error("NodeKind Triple but found {node}")]
+    NodeKindTriple { node: Node },
+
     #[error("Datatype expected {expected} but found {found} for literal with lexical form {lexical_form}")]
     DatatypeDontMatch {
         found: IriRef,
@@ -4,4 +4,5 @@ pub enum NodeKind {
     BNode,
     NonLiteral,
     Literal,
+    Triple,
 }
@@ -49,9 +49,33 @@ impl SchemaJsonCompiler {
         compiled_schema.set_prefixmap(schema_json.prefixmap());
         self.collect_shape_labels(schema_json, compiled_schema)?;
         self.collect_shape_exprs(schema_json, compiled_schema)?;
+        self.collect_start(schema_json, compiled_schema)?;
         Ok(())
     }
 
+    /// A start declaration which refers to a shape label is an alias of that label
+    pub fn collect_start(
+        &mut self,
+        schema_json: &SchemaJson,
+        compiled_schema: &mut CompiledSchema,
+    ) -> CResult<()> {
+        match schema_json.start() {
+            None => Ok(()),
+            Some(ast::ShapeExpr::Ref(label)) => {
+                let idx = compiled_schema.find_ref(&label)?;
+                compiled_schema.set_start_idx(idx);
+                Ok(())
+            }
+            Some(se) => {
+                compiled_schema.add_shape(ShapeLabel::Start, ShapeExpr::Empty);
+                let idx = compiled_schema.get_shape_label_idx(&ShapeLabel::Start)?;
+                let se = self.compile_shape_expr(&se, &idx, compiled_schema)?;
+                compiled_schema.replace_shape(&idx, se);
+                Ok(())
+            }
+        }
+    }
+
     pub fn collect_shape_labels(
         &mut self,
         schema_json: &SchemaJson,
@@ -190,7 +214,10 @@ impl SchemaJsonCompiler {
                         table
                     }
                 };
-                let preds = Self::get_preds_shape(shape);
+                let (inverse_preds, preds): (Vec<_>, Vec<_>) = Self::get_preds_shape(shape)
+                    .into_iter()
+                    .partition(|pred| pred.is_inverse());
+                let iri = |pred: Pred| pred.iri().clone();
 
                 let display = match compiled_schema.find_shape_idx(idx) {
                     None => "internal".to_string(),
@@ -203,9 +230,10 @@ impl SchemaJsonCompiler {
                     rbe_table,
                     Self::cnv_sem_acts(&shape.sem_acts),
                     Self::cnv_annotations(&shape.annotations),
-                    preds,
+                    preds.into_iter().map(iri).collect(),
                     display,
-                );
+                )
+                .with_inverse_preds(inverse_preds.into_iter().map(iri).collect());
                 Ok(ShapeExpr::Shape(shape))
             }
             ast::ShapeExpr::NodeConstraint(nc) => {
@@ -328,7 +356,7 @@ impl SchemaJsonCompiler {
             ast::TripleExpr::TripleConstraint {
                 id: _,
                 negated: _,
-                inverse: _,
+                inverse,
                 predicate,
                 value_expr,
                 min,
@@ -338,7 +366,7 @@ impl SchemaJsonCompiler {
             } => {
                 let min = self.cnv_min(min)?;
                 let max = self.cnv_max(max)?;
-                let iri = Self::cnv_predicate(predicate)?;
+                let iri = Self::cnv_predicate(predicate, inverse.unwrap_or(false))?;
                 let cond = self.value_expr2match_cond(value_expr, compiled_schema)?;
                 let c = current_table.add_component(iri, &cond);
                 Ok(Rbe::symbol(c, min.value, max))
@@ -349,8 +377,9 @@ impl SchemaJsonCompiler {
         }
     }
 
-    fn cnv_predicate(predicate: &IriRef) -> CResult<Pred> {
+    fn cnv_predicate(predicate: &IriRef, inverse: bool) -> CResult<Pred> {
         match predicate {
+            IriRef::Iri(iri) if inverse => Ok(Pred::inverse(iri.clone())),
             IriRef::Iri(iri) => Ok(Pred::from(iri.clone())),
             IriRef::Prefixed { prefix, local } => Err(CompiledSchemaError::Internal {
                 msg: format!(
@@ -437,14 +466,14 @@ impl SchemaJsonCompiler {
         todo("shape_expr2match_cond")
     }*/
 
-    fn get_preds_shape(shape: &ast::Shape) -> Vec<IriS> {
+    fn get_preds_shape(shape: &ast::Shape) -> Vec<Pred> {
         match shape.triple_expr() {
             None => Vec::new(),
             Some(te) => Self::get_preds_triple_expr(&te),
         }
     }
 
-    fn get_preds_triple_expr(te: &ast::TripleExpr) -> Vec<IriS> {
+    fn get_preds_triple_expr(te: &ast::TripleExpr) -> Vec<Pred> {
         match te {
             ast::TripleExpr::EachOf { expressions, .. } => expressions
                 .iter()
@@ -454,9 +483,15 @@ impl SchemaJsonCompiler {
                 .iter()
                 .flat_map(|te| Self::get_preds_triple_expr(&te.te))
                 .collect(),
-            ast::TripleExpr::TripleConstraint { predicate, .. } => {
-                let pred = iri_ref2iri_s(predicate);
-                vec![pred]
+            ast::TripleExpr::TripleConstraint {
+                predicate, inverse, ..
+            } => {
+                let iri = iri_ref2iri_s(predicate);
+                if inverse.unwrap_or(false) {
+                    vec![Pred::inverse(iri)]
+                } else {
+                    vec![Pred::from(iri)]
+                }
             }
             ast::TripleExpr::TripleExprRef(_) => todo!(),
         }
@@ -802,6 +837,10 @@ fn check_node_node_kind(node: &Node, nk: &ast::NodeKind) -> CResult<()> {
         (ast::NodeKind::NonLiteral, _) => {
             Err(CompiledSchemaError::NodeKindNonLiteral { node: node.clone() })
         }
+        (ast::NodeKind::Triple, Object::Triple { .. }) => Ok(()),
+        (ast::NodeKind::Triple, _) => {
+            Err(CompiledSchemaError::NodeKindTriple { node: node.clone() })
+        }
     }
 }
 
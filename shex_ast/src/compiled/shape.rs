@@ -14,6 +14,7 @@ pub struct Shape {
     sem_acts: Vec<SemAct>,
     annotations: Vec<Annotation>,
     preds: Vec<IriS>,
+    inverse_preds: Vec<IriS>,
     display: String,
 }
 
@@ -34,14 +35,25 @@ impl Shape {
             sem_acts,
             annotations,
             preds,
+            inverse_preds: Vec::new(),
             display,
         }
     }
 
+    /// Predicates of the inverse triple constraints, which are matched against the incoming arcs
+    pub fn with_inverse_preds(mut self, inverse_preds: Vec<IriS>) -> Self {
+        self.inverse_preds = inverse_preds;
+        self
+    }
+
     pub fn preds(&self) -> Vec<IriS> {
         self.preds.clone()
     }
 
+    pub fn inverse_preds(&self) -> Vec<IriS> {
+        self.inverse_preds.clone()
+    }
+
     pub fn rbe_table(&self) -> &RbeTable<Pred, Node, ShapeLabelIdx> {
         &self.rbe_table
     }
@@ -2,20 +2,45 @@ use iri_s::IriS;
 use rbe::Key;
 use std::fmt::Display;
 
+/// Predicate of a triple constraint
+///
+/// Inverse predicates (`^:p` in ShExC) match the incoming arcs of a node
 #[derive(PartialEq, Eq, Hash, Debug, Default, Clone)]
 pub struct Pred {
     iri: IriS,
+    inverse: bool,
+}
+
+impl Pred {
+    pub fn inverse(iri: IriS) -> Self {
+        Pred { iri, inverse: true }
+    }
+
+    pub fn iri(&self) -> &IriS {
+        &self.iri
+    }
+
+    pub fn is_inverse(&self) -> bool {
+        self.inverse
+    }
 }
 
 impl Display for Pred {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.iri)
+        if self.inverse {
+            write!(f, "^{}", self.iri)
+        } else {
+            write!(f, "{}", self.iri)
+        }
     }
 }
 
 impl From<IriS> for Pred {
     fn from(iri: IriS) -> Self {
-        Pred { iri }
+        Pred {
+            iri,
+            inverse: false,
+        }
     }
 }
 
@@ -1,4 +1,5 @@
 use iri_s::IriS;
+use prefixmap::PrefixMapError;
 use srdf::{literal::Literal, RDFParseError};
 use std::fmt::Display;
 use thiserror::Error;
@@ -22,8 +23,20 @@ pub enum ShExRError {
     #[error("Shape Label can not be a literal {lit}")]
     ShapeExprLabelLiteral { lit: Literal },
 
+    #[error("Triple terms can not appear in ShExR schemas: {term}")]
+    UnexpectedTripleTerm { term: String },
+
     #[error("Unexpected value for nodeKind: {iri}")]
     UnexpectedNodeKind { iri: IriS },
+
+    #[error("Error writing ShExR: {err}")]
+    SRDFError { err: String },
+
+    #[error("Error resolving IRI when writing ShExR: {err}")]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
 }
 
 #[derive(Debug)]
@@ -1,16 +1,22 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
 use super::shexr_error::ShExRError;
 use super::*;
 use crate::{
-    BNode, NodeConstraint, NodeKind, ObjectValue, Schema, Shape, ShapeDecl, ShapeExpr,
-    ShapeExprLabel, ValueSetValue,
+    Annotation, BNode, Iri, IriExclusion, IriRefOrWildcard, LangOrWildcard, LanguageExclusion,
+    LiteralExclusion, NodeConstraint, NodeKind, ObjectValue, Schema, SemAct, Shape, ShapeDecl,
+    ShapeExpr, ShapeExprLabel, StringOrWildcard, TripleExpr, TripleExprLabel, ValueSetValue,
 };
 use iri_s::IriS;
 use prefixmap::IriRef;
-use srdf::rdf_parser;
+use rust_decimal::Decimal;
+use srdf::lang::Lang;
+use srdf::literal::Literal;
+use srdf::numeric_literal::NumericLiteral;
 use srdf::srdf_parser::*;
-use srdf::FocusRDF;
-use srdf::RDFParseError;
-use srdf::{Object, RDFParser};
+use srdf::{FocusRDF, Object, RDFParseError, RDFParser};
+use srdf::{RDF_TYPE_STR, XSD_BOOLEAN_STR, XSD_DECIMAL_STR, XSD_DOUBLE_STR, XSD_INTEGER_STR};
 
 type Result<A> = std::result::Result<A, ShExRError>;
 
@@ -45,250 +51,689 @@ where
     }
 
     fn schema() -> impl RDFNodeParse<RDF, Output = Schema> {
-        property_value(&sx_shapes()).then(|ref node| {
-            set_focus(node)
-                .and(parse_rdf_list::<RDF, _>(Self::shape_decl()))
-                .map(|(_, vs)| Schema::new().with_shapes(Some(vs)))
+        apply_rdf(|rdf: &mut RDF| {
+            let node = rdf.get_focus_as_term()?.clone();
+            SchemaReader::new().schema(rdf, &node)
         })
     }
-
-    fn term_to_shape_label(term: &RDF::Term) -> Result<ShapeExprLabel> {
-        let object = RDF::term_as_object(term);
-        match object {
-            Object::Iri(iri) => Ok(ShapeExprLabel::iri(iri)),
-            Object::BlankNode(bnode) => Ok(ShapeExprLabel::bnode(BNode::new(bnode.as_str()))),
-            Object::Literal(lit) => Err(ShExRError::ShapeExprLabelLiteral { lit }),
-        }
-    }
-
-    fn shape_decl() -> impl RDFNodeParse<RDF, Output = ShapeDecl> {
-        (term().flat_map(move |ref t| {
-            let label = Self::term_to_shape_label(t).map_err(|e| RDFParseError::Custom {
-                msg: format!("Expected term to be a label: {t}: {e}"),
-            })?;
-            Ok(label)
-        }))
-        .and(Self::parse_shape_expr())
-        .map(|(label, se)| ShapeDecl::new(label, se, false))
-    }
-
-    fn parse_shape_expr() -> impl RDFNodeParse<RDF, Output = ShapeExpr> {
-        property_value(&sx_shape_expr()).then(|ref node| set_focus(node).then(|_| shape_expr()))
-    }
 }
 
-fn shape_expr_<RDF>() -> impl RDFNodeParse<RDF, Output = ShapeExpr>
+/// Reads the nodes of a ShExR graph recursively.
+///
+/// Shape expressions and triple expressions may refer to each other by their labels,
+/// so the reader keeps the labels of the declared shapes, and the labels of the triple
+/// expressions that have already been read, to parse the nodes that use them as references.
+struct SchemaReader<RDF>
 where
     RDF: FocusRDF,
 {
-    // I would like the following code to work...but it doesn't yet...
-    /*parse_by_type(vec![
-     (sx_shape_and(), shape_and()),
-     . . .
-     (sx_node_constraint(), node_constraint())
-    ], default) */
-
-    shape_and().or(shape_or()).or(shape()).or(node_constraint())
+    shape_labels: HashSet<RDF::Term>,
+    triple_expr_labels: HashSet<RDF::Term>,
 }
 
-fn shape<RDF>() -> impl RDFNodeParse<RDF, Output = ShapeExpr>
+impl<RDF> SchemaReader<RDF>
 where
     RDF: FocusRDF,
 {
-    has_type(sx_shape()).with({
-        closed().then(|maybe_closed| {
-            let extra = None; // TODO
-            let expression = None; // TODO
-            ok(&ShapeExpr::shape(Shape::new(
-                maybe_closed,
-                extra,
-                expression,
-            )))
-        })
-    })
-}
+    fn new() -> Self {
+        SchemaReader {
+            shape_labels: HashSet::new(),
+            triple_expr_labels: HashSet::new(),
+        }
+    }
 
-fn closed<RDF>() -> impl RDFNodeParse<RDF, Output = Option<bool>>
-where
-    RDF: FocusRDF,
-{
-    optional(property_bool(&sx_closed()))
-}
+    fn schema(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<Schema> {
+        let decls = list_of(rdf, node, SX_SHAPES)?;
+        if let Some(decls) = &decls {
+            self.shape_labels.extend(decls.iter().cloned());
+        }
+        let mut schema = Schema::new();
+        if let Some(imports) = list_of(rdf, node, SX_IMPORTS)? {
+            for import in imports {
+                schema = schema.with_import(Iri::new(term_as_iri::<RDF>(&import)?.as_str()));
+            }
+        }
+        let start_actions = self.sem_acts(rdf, node, SX_STARTACTS)?;
+        let start = match object(rdf, node, SX_START)? {
+            Some(start) => Some(self.shape_expr(rdf, &start)?),
+            None => None,
+        };
+        let shapes = match decls {
+            Some(decls) => {
+                let mut shapes = Vec::new();
+                for decl in decls {
+                    shapes.push(self.shape_decl(rdf, &decl)?)
+                }
+                Some(shapes)
+            }
+            None => None,
+        };
+        Ok(schema
+            .with_start_actions(start_actions)
+            .with_start(start)
+            .with_shapes(shapes))
+    }
+
+    fn shape_decl(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<ShapeDecl> {
+        let label = shape_label::<RDF>(node)?;
+        let is_abstract = bool_value(rdf, node, SX_ABSTRACT)?.unwrap_or(false);
+        let se = match object(rdf, node, SX_SHAPE_EXPR)? {
+            Some(se) => self.shape_expr(rdf, &se)?,
+            None if has_type(&types(rdf, node)?, SX_SHAPE_DECL) => {
+                return Err(RDFParseError::NoValuesPredicate {
+                    node: node.to_string(),
+                    pred: SX_SHAPE_EXPR.to_string(),
+                })
+            }
+            // ShEx 2.0 schemas declare the shape expressions directly, without sx:ShapeDecl
+            None => self.shape_expr_node(rdf, node)?,
+        };
+        Ok(ShapeDecl::new(label, se, is_abstract))
+    }
+
+    fn shape_expr(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<ShapeExpr> {
+        if self.shape_labels.contains(node) {
+            Ok(ShapeExpr::shape_ref(shape_label::<RDF>(node)?))
+        } else {
+            self.shape_expr_node(rdf, node)
+        }
+    }
+
+    fn shape_expr_node(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<ShapeExpr> {
+        let types = types(rdf, node)?;
+        if has_type(&types, SX_SHAPE_AND) {
+            Ok(ShapeExpr::and(self.shape_exprs(rdf, node)?))
+        } else if has_type(&types, SX_SHAPE_OR) {
+            Ok(ShapeExpr::or(self.shape_exprs(rdf, node)?))
+        } else if has_type(&types, SX_SHAPE_NOT) {
+            let se = required_object(rdf, node, SX_SHAPE_EXPR)?;
+            Ok(ShapeExpr::shape_not(self.shape_expr(rdf, &se)?))
+        } else if has_type(&types, SX_NODECONSTRAINT) {
+            Ok(ShapeExpr::node_constraint(self.node_constraint(rdf, node)?))
+        } else if has_type(&types, SX_SHAPE) {
+            Ok(ShapeExpr::shape(self.shape(rdf, node)?))
+        } else if has_type(&types, SX_SHAPE_EXTERNAL) {
+            Ok(ShapeExpr::external())
+        } else {
+            Ok(ShapeExpr::shape_ref(shape_label::<RDF>(node)?))
+        }
+    }
+
+    fn shape_exprs(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<Vec<ShapeExpr>> {
+        let mut ses = Vec::new();
+        for se in required_list(rdf, node, SX_SHAPE_EXPRS)? {
+            ses.push(self.shape_expr(rdf, &se)?)
+        }
+        Ok(ses)
+    }
+
+    fn shape(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<Shape> {
+        let closed = bool_value(rdf, node, SX_CLOSED)?;
+        let mut extra = Vec::new();
+        for iri in objects(rdf, node, SX_EXTRA)? {
+            extra.push(IriRef::iri(term_as_iri::<RDF>(&iri)?))
+        }
+        let extra = if extra.is_empty() { None } else { Some(extra) };
+        let expression = match object(rdf, node, SX_EXPRESSION)? {
+            Some(te) => Some(self.triple_expr(rdf, &te)?),
+            None => None,
+        };
+        let extends = match list_of(rdf, node, SX_EXTENDS)? {
+            Some(labels) => Some(
+                labels
+                    .iter()
+                    .map(shape_label::<RDF>)
+                    .collect::<PResult<Vec<_>>>()?,
+            ),
+            None => None,
+        };
+        Ok(Shape::new(closed, extra, expression)
+            .with_sem_acts(self.sem_acts(rdf, node, SX_SEMACTS)?)
+            .with_annotations(self.annotations(rdf, node)?)
+            .with_extends(extends))
+    }
 
-rdf_parser! {
-    pub fn shape_and[RDF]()(RDF) -> ShapeExpr where [] {
-        has_type(sx_shape_and()).with(
-        property_value(&sx_shape_exprs()).then(|ref node| {
-            set_focus(node).and(
-                   parse_rdf_list::<RDF, _>(shape_expr())
-                 ).map(|(_,vs)| { ShapeExpr::and(vs) })
-           }))
+    fn triple_expr(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<TripleExpr> {
+        if self.triple_expr_labels.contains(node) {
+            return Ok(TripleExpr::TripleExprRef(triple_expr_label::<RDF>(node)?));
+        }
+        let types = types(rdf, node)?;
+        if ![SX_EACHOF, SX_ONEOF, SX_TRIPLECONSTRAINT]
+            .iter()
+            .any(|t| has_type(&types, t))
+        {
+            return Ok(TripleExpr::TripleExprRef(triple_expr_label::<RDF>(node)?));
+        }
+        // The first appearance of a labelled triple expression is taken as its definition
+        let id = if RDF::term_is_iri(node) || is_shared(rdf, node)? {
+            self.triple_expr_labels.insert(node.clone());
+            Some(triple_expr_label::<RDF>(node)?)
+        } else {
+            None
+        };
+        let te = if has_type(&types, SX_EACHOF) {
+            TripleExpr::each_of(self.triple_exprs(rdf, node)?)
+        } else if has_type(&types, SX_ONEOF) {
+            TripleExpr::one_of(self.triple_exprs(rdf, node)?)
+        } else {
+            let predicate = term_as_iri::<RDF>(&required_object(rdf, node, SX_PREDICATE)?)?;
+            let value_expr = match object(rdf, node, SX_VALUEEXPR)? {
+                Some(se) => Some(self.shape_expr(rdf, &se)?),
+                None => None,
+            };
+            TripleExpr::triple_constraint(
+                bool_value(rdf, node, SX_NEGATED)?,
+                bool_value(rdf, node, SX_INVERSE)?,
+                IriRef::iri(predicate),
+                value_expr,
+                None,
+                None,
+            )
+        };
+        Ok(te
+            .with_id(id)
+            .with_min(cardinality(rdf, node, SX_MIN)?)
+            .with_max(cardinality(rdf, node, SX_MAX)?)
+            .with_sem_acts(self.sem_acts(rdf, node, SX_SEMACTS)?)
+            .with_annotations(self.annotations(rdf, node)?))
+    }
+
+    fn triple_exprs(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<Vec<TripleExpr>> {
+        let mut tes = Vec::new();
+        for te in required_list(rdf, node, SX_EXPRESSIONS)? {
+            tes.push(self.triple_expr(rdf, &te)?)
+        }
+        Ok(tes)
+    }
+
+    fn node_constraint(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<NodeConstraint> {
+        let mut nc = NodeConstraint::new();
+        if let Some(node_kind) = object(rdf, node, SX_NODEKIND)? {
+            nc = nc.with_node_kind(self.node_kind(&node_kind)?)
+        }
+        // Facets are added in the same order as the ShExJ deserializer does
+        if let Some(pattern) = string_value(rdf, node, SX_PATTERN)? {
+            nc = match string_value(rdf, node, SX_FLAGS)? {
+                Some(flags) => nc.with_pattern_flags(&pattern, &flags),
+                None => nc.with_pattern(&pattern),
+            }
+        }
+        if let Some(length) = usize_value(rdf, node, SX_LENGTH)? {
+            nc = nc.with_length(length)
+        }
+        if let Some(datatype) = object(rdf, node, SX_DATATYPE)? {
+            nc = nc.with_datatype(IriRef::iri(term_as_iri::<RDF>(&datatype)?))
+        }
+        if let Some(values) = list_of(rdf, node, SX_VALUES)? {
+            let mut vs = Vec::new();
+            for value in values {
+                vs.push(self.value_set_value(rdf, &value)?)
+            }
+            nc = nc.with_values(vs)
+        }
+        if let Some(minlength) = usize_value(rdf, node, SX_MINLENGTH)? {
+            nc = nc.with_minlength(minlength)
+        }
+        if let Some(maxlength) = usize_value(rdf, node, SX_MAXLENGTH)? {
+            nc = nc.with_maxlength(maxlength)
+        }
+        if let Some(n) = numeric_value(rdf, node, SX_MININCLUSIVE)? {
+            nc = nc.with_min_inclusive(n)
+        }
+        if let Some(n) = numeric_value(rdf, node, SX_MAXINCLUSIVE)? {
+            nc = nc.with_max_inclusive(n)
+        }
+        if let Some(n) = numeric_value(rdf, node, SX_MINEXCLUSIVE)? {
+            nc = nc.with_min_exclusive(n)
+        }
+        if let Some(n) = numeric_value(rdf, node, SX_MAXEXCLUSIVE)? {
+            nc = nc.with_max_exclusive(n)
+        }
+        if let Some(n) = usize_value(rdf, node, SX_TOTALDIGITS)? {
+            nc = nc.with_totaldigits(n)
+        }
+        if let Some(n) = usize_value(rdf, node, SX_FRACTIONDIGITS)? {
+            nc = nc.with_fractiondigits(n)
+        }
+        Ok(nc)
+    }
+
+    fn node_kind(&self, node: &RDF::Term) -> PResult<NodeKind> {
+        let iri = term_as_iri::<RDF>(node)?;
+        match iri.as_str() {
+            SX_IRI => Ok(NodeKind::Iri),
+            SX_BNODE => Ok(NodeKind::BNode),
+            SX_LITERAL => Ok(NodeKind::Literal),
+            SX_NONLITERAL => Ok(NodeKind::NonLiteral),
+            SX_TRIPLE => Ok(NodeKind::Triple),
+            _ => Err(RDFParseError::Custom {
+                msg: format!("{}", ShExRError::UnexpectedNodeKind { iri }),
+            }),
+        }
+    }
+
+    fn value_set_value(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<ValueSetValue> {
+        match RDF::term_as_object(node) {
+            Object::Iri(iri) => Ok(ValueSetValue::ObjectValue(ObjectValue::iri(iri))),
+            Object::Literal(lit) => Ok(ValueSetValue::ObjectValue(ObjectValue::Literal(
+                shex_literal(lit),
+            ))),
+            triple @ Object::Triple { .. } => Err(triple_term_error(triple)),
+            Object::BlankNode(_) => {
+                let types = types(rdf, node)?;
+                if has_type(&types, SX_IRISTEM) {
+                    Ok(ValueSetValue::IriStem {
+                        stem: IriRef::iri(stem_iri::<RDF>(&required_object(rdf, node, SX_STEM)?)?),
+                    })
+                } else if has_type(&types, SX_IRISTEMRANGE) {
+                    let stem = required_object(rdf, node, SX_STEM)?;
+                    let stem = if is_wildcard(rdf, &stem)? {
+                        IriRefOrWildcard::Wildcard
+                    } else {
+                        IriRefOrWildcard::IriRef(IriRef::iri(stem_iri::<RDF>(&stem)?))
+                    };
+                    let exclusions = match list_of(rdf, node, SX_EXCLUSION)? {
+                        Some(es) => {
+                            let mut exclusions = Vec::new();
+                            for e in es {
+                                exclusions.push(if RDF::term_is_bnode(&e) {
+                                    let stem = required_object(rdf, &e, SX_STEM)?;
+                                    IriExclusion::IriStem(IriRef::iri(stem_iri::<RDF>(&stem)?))
+                                } else {
+                                    IriExclusion::Iri(IriRef::iri(stem_iri::<RDF>(&e)?))
+                                })
+                            }
+                            Some(exclusions)
+                        }
+                        None => None,
+                    };
+                    Ok(ValueSetValue::IriStemRange { stem, exclusions })
+                } else if has_type(&types, SX_LITERALSTEM) {
+                    Ok(ValueSetValue::LiteralStem {
+                        stem: required_string(rdf, node, SX_STEM)?,
+                    })
+                } else if has_type(&types, SX_LITERALSTEMRANGE) {
+                    let stem = required_object(rdf, node, SX_STEM)?;
+                    let stem = if is_wildcard(rdf, &stem)? {
+                        StringOrWildcard::Wildcard
+                    } else {
+                        StringOrWildcard::String(term_as_string::<RDF>(&stem)?)
+                    };
+                    let exclusions = match list_of(rdf, node, SX_EXCLUSION)? {
+                        Some(es) => {
+                            let mut exclusions = Vec::new();
+                            for e in es {
+                                exclusions.push(if RDF::term_is_bnode(&e) {
+                                    LiteralExclusion::LiteralStem(required_string(
+                                        rdf, &e, SX_STEM,
+                                    )?)
+                                } else {
+                                    LiteralExclusion::Literal(term_as_string::<RDF>(&e)?)
+                                })
+                            }
+                            Some(exclusions)
+                        }
+                        None => None,
+                    };
+                    Ok(ValueSetValue::LiteralStemRange { stem, exclusions })
+                } else if has_type(&types, SX_LANGUAGE) {
+                    Ok(ValueSetValue::Language {
+                        language_tag: Lang::new(&required_string(rdf, node, SX_LANGUAGE_TAG)?),
+                    })
+                } else if has_type(&types, SX_LANGUAGESTEM) {
+                    Ok(ValueSetValue::LanguageStem {
+                        stem: Lang::new(&required_string(rdf, node, SX_STEM)?),
+                    })
+                } else if has_type(&types, SX_LANGUAGESTEMRANGE) {
+                    let stem = required_object(rdf, node, SX_STEM)?;
+                    let stem = if is_wildcard(rdf, &stem)? {
+                        LangOrWildcard::Wildcard
+                    } else {
+                        LangOrWildcard::Lang(Lang::new(&term_as_string::<RDF>(&stem)?))
+                    };
+                    let exclusions = match list_of(rdf, node, SX_EXCLUSION)? {
+                        Some(es) => {
+                            let mut exclusions = Vec::new();
+                            for e in es {
+                                exclusions.push(if RDF::term_is_bnode(&e) {
+                                    LanguageExclusion::LanguageStem(Lang::new(&required_string(
+                                        rdf, &e, SX_STEM,
+                                    )?))
+                                } else {
+                                    LanguageExclusion::Language(Lang::new(&term_as_string::<RDF>(
+                                        &e,
+                                    )?))
+                                })
+                            }
+                            Some(exclusions)
+                        }
+                        None => None,
+                    };
+                    Ok(ValueSetValue::LanguageStemRange { stem, exclusions })
+                } else {
+                    Err(RDFParseError::Custom {
+                        msg: format!("Unexpected value set value: {node}"),
+                    })
+                }
+            }
+        }
+    }
+
+    fn sem_acts(
+        &mut self,
+        rdf: &mut RDF,
+        node: &RDF::Term,
+        property: &str,
+    ) -> PResult<Option<Vec<SemAct>>> {
+        match list_of(rdf, node, property)? {
+            Some(nodes) => {
+                let mut sem_acts = Vec::new();
+                for sem_act in nodes {
+                    let name = term_as_iri::<RDF>(&required_object(rdf, &sem_act, SX_NAME)?)?;
+                    let code = string_value(rdf, &sem_act, SX_CODE)?;
+                    sem_acts.push(SemAct::new(IriRef::iri(name), code))
+                }
+                Ok(Some(sem_acts))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn annotations(&mut self, rdf: &mut RDF, node: &RDF::Term) -> PResult<Option<Vec<Annotation>>> {
+        match list_of(rdf, node, SX_ANNOTATION_PROP)? {
+            Some(nodes) => {
+                let mut annotations = Vec::new();
+                for annotation in nodes {
+                    let predicate =
+                        term_as_iri::<RDF>(&required_object(rdf, &annotation, SX_PREDICATE)?)?;
+                    let object =
+                        match RDF::term_as_object(&required_object(rdf, &annotation, SX_OBJECT)?) {
+                            Object::Iri(iri) => ObjectValue::iri(iri),
+                            Object::Literal(lit) => ObjectValue::Literal(shex_literal(lit)),
+                            Object::BlankNode(bnode) => {
+                                return Err(RDFParseError::BlankNodeNoValue { bnode })
+                            }
+                            triple @ Object::Triple { .. } => {
+                                return Err(triple_term_error(triple))
+                            }
+                        };
+                    annotations.push(Annotation::new(IriRef::iri(predicate), object))
+                }
+                Ok(Some(annotations))
+            }
+            None => Ok(None),
+        }
     }
 }
 
-rdf_parser! {
-    pub fn shape_or[RDF]()(RDF) -> ShapeExpr where [] {
-        has_type(sx_shape_or()).with(
-        property_value(&sx_shape_exprs()).then(|ref node| {
-            set_focus(node).and(
-                   parse_rdf_list::<RDF, _>(shape_expr())
-                 ).map(|(_,vs)| { ShapeExpr::or(vs) })
-           }))
+fn shape_label<RDF: FocusRDF>(term: &RDF::Term) -> PResult<ShapeExprLabel> {
+    match RDF::term_as_object(term) {
+        Object::Iri(iri) => Ok(ShapeExprLabel::iri(iri)),
+        Object::BlankNode(bnode) => Ok(ShapeExprLabel::bnode(BNode::new(bnode.as_str()))),
+        Object::Literal(lit) => Err(RDFParseError::Custom {
+            msg: format!("{}", ShExRError::ShapeExprLabelLiteral { lit }),
+        }),
+        triple @ Object::Triple { .. } => Err(triple_term_error(triple)),
     }
 }
 
-rdf_parser! {
-    pub fn shape_expr[RDF]()(RDF) -> ShapeExpr where [] {
-       shape_expr_()
+fn triple_expr_label<RDF: FocusRDF>(term: &RDF::Term) -> PResult<TripleExprLabel> {
+    match RDF::term_as_object(term) {
+        Object::Iri(iri) => Ok(TripleExprLabel::IriRef {
+            value: IriRef::iri(iri),
+        }),
+        Object::BlankNode(bnode) => Ok(TripleExprLabel::BNode {
+            value: BNode::new(bnode.as_str()),
+        }),
+        Object::Literal(lit) => Err(RDFParseError::Custom {
+            msg: format!("Triple expression label can not be a literal {lit}"),
+        }),
+        triple @ Object::Triple { .. } => Err(triple_term_error(triple)),
     }
 }
 
-/*
-#[inline]
-fn sx_schema<RDF>() -> RDF::Term
-where
-    RDF: FocusRDF,
-{
-    RDFParser::<RDF>::term_iri_unchecked(SX_SCHEMA)
+/// Converts the literals with a datatype that ShExJ represents natively
+fn shex_literal(lit: Literal) -> Literal {
+    if let Literal::DatatypeLiteral {
+        lexical_form,
+        datatype: IriRef::Iri(datatype),
+    } = &lit
+    {
+        let converted = match datatype.as_str() {
+            XSD_BOOLEAN_STR => match lexical_form.as_str() {
+                "true" | "1" => Some(Literal::boolean(true)),
+                "false" | "0" => Some(Literal::boolean(false)),
+                _ => None,
+            },
+            XSD_INTEGER_STR => isize::from_str(lexical_form).ok().map(Literal::integer),
+            XSD_DECIMAL_STR => Decimal::from_str(lexical_form).ok().map(Literal::decimal),
+            XSD_DOUBLE_STR => f64::from_str(lexical_form).ok().map(Literal::double),
+            _ => None,
+        };
+        if let Some(converted) = converted {
+            return converted;
+        }
+    }
+    lit
 }
-*/
 
-#[inline]
-fn sx_shapes() -> IriS {
-    IriS::new_unchecked(SX_SHAPES)
+fn objects<RDF: FocusRDF>(rdf: &RDF, node: &RDF::Term, property: &str) -> PResult<Vec<RDF::Term>> {
+    let subject = RDF::term_as_subject(node).ok_or_else(|| RDFParseError::ExpectedSubject {
+        node: node.to_string(),
+    })?;
+    let pred = RDF::iri_s2iri(&IriS::new_unchecked(property));
+    let values = rdf
+        .objects_for_subject_predicate(&subject, &pred)
+        .map_err(|e| RDFParseError::SRDFError { err: e.to_string() })?;
+    let mut values: Vec<_> = values.into_iter().collect();
+    values.sort_by_key(|v| v.to_string());
+    Ok(values)
 }
 
-fn sx_shape_expr() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_EXPR)
+fn object<RDF: FocusRDF>(
+    rdf: &RDF,
+    node: &RDF::Term,
+    property: &str,
+) -> PResult<Option<RDF::Term>> {
+    let mut values = objects(rdf, node, property)?.into_iter();
+    match (values.next(), values.next()) {
+        (Some(value1), Some(value2)) => Err(RDFParseError::MoreThanOneValuePredicate {
+            node: node.to_string(),
+            pred: property.to_string(),
+            value1: value1.to_string(),
+            value2: value2.to_string(),
+        }),
+        (value, _) => Ok(value),
+    }
 }
 
-fn sx_closed() -> IriS {
-    IriS::new_unchecked(SX_CLOSED)
+fn required_object<RDF: FocusRDF>(
+    rdf: &RDF,
+    node: &RDF::Term,
+    property: &str,
+) -> PResult<RDF::Term> {
+    object(rdf, node, property)?.ok_or_else(|| RDFParseError::NoValuesPredicate {
+        node: node.to_string(),
+        pred: property.to_string(),
+    })
 }
 
-#[inline]
-fn sx_values() -> IriS {
-    IriS::new_unchecked(SX_VALUES)
+fn list_of<RDF: FocusRDF>(
+    rdf: &mut RDF,
+    node: &RDF::Term,
+    property: &str,
+) -> PResult<Option<Vec<RDF::Term>>> {
+    match object(rdf, node, property)? {
+        Some(list) => {
+            rdf.set_focus(&list);
+            Ok(Some(rdf_list().parse_impl(rdf)?))
+        }
+        None => Ok(None),
+    }
 }
 
-#[inline]
-fn sx_shape() -> IriS {
-    IriS::new_unchecked(SX_SHAPE)
+fn required_list<RDF: FocusRDF>(
+    rdf: &mut RDF,
+    node: &RDF::Term,
+    property: &str,
+) -> PResult<Vec<RDF::Term>> {
+    list_of(rdf, node, property)?.ok_or_else(|| RDFParseError::NoValuesPredicate {
+        node: node.to_string(),
+        pred: property.to_string(),
+    })
 }
 
-#[inline]
-fn sx_shape_exprs() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_EXPRS)
+fn types<RDF: FocusRDF>(rdf: &RDF, node: &RDF::Term) -> PResult<Vec<IriS>> {
+    Ok(objects(rdf, node, RDF_TYPE_STR)?
+        .iter()
+        .filter_map(|t| RDF::term_as_iri(t).map(|iri| RDF::iri2iri_s(&iri)))
+        .collect())
 }
 
-#[inline]
-fn sx_node_kind() -> IriS {
-    IriS::new_unchecked(SX_NODEKIND)
+fn has_type(types: &[IriS], expected: &str) -> bool {
+    types.iter().any(|t| t.as_str() == expected)
 }
 
-fn sx_shape_and() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_AND)
+fn is_wildcard<RDF: FocusRDF>(rdf: &RDF, node: &RDF::Term) -> PResult<bool> {
+    Ok(RDF::term_is_bnode(node) && has_type(&types(rdf, node)?, SX_WILDCARD))
 }
 
-fn sx_shape_or() -> IriS {
-    IriS::new_unchecked(SX_SHAPE_OR)
+/// Checks if a node is the object of more than one triple, i.e. it is used as a label
+fn is_shared<RDF: FocusRDF>(rdf: &RDF, node: &RDF::Term) -> PResult<bool> {
+    let arcs = rdf
+        .incoming_arcs(node)
+        .map_err(|e| RDFParseError::SRDFError { err: e.to_string() })?;
+    Ok(arcs.values().map(|subjects| subjects.len()).sum::<usize>() > 1)
 }
 
-/*
-fn sx_node_constraint() -> IriS {
-    IriS::new_unchecked(SX_NODECONSTRAINT)
+fn term_as_iri<RDF: FocusRDF>(term: &RDF::Term) -> PResult<IriS> {
+    RDF::term_as_iri(term)
+        .map(|iri| RDF::iri2iri_s(&iri))
+        .ok_or_else(|| RDFParseError::ExpectedIRI {
+            term: term.to_string(),
+        })
 }
-*/
-
-rdf_parser! {
- fn node_constraint[RDF]()(RDF) -> ShapeExpr
- where [] {
-    parse_nodekind().then(|maybe_nodekind|
-        parse_value_set().then(move |maybe_valueset| {
-           let mut nc = NodeConstraint::new();
-           if let Some(nk) = &maybe_nodekind {
-              nc = nc.with_node_kind(nk.clone());
-           }
-           if let Some(vs) = &maybe_valueset {
-              nc = nc.with_values(vs.clone())
-           }
-           ok(&ShapeExpr::node_constraint(nc))
-         }
-        )
-    )
-  }
+
+/// IRI stems are represented as `xsd:anyURI` literals, but plain IRIs are also accepted
+fn stem_iri<RDF: FocusRDF>(term: &RDF::Term) -> PResult<IriS> {
+    match RDF::term_as_object(term) {
+        Object::Iri(iri) => Ok(iri),
+        Object::Literal(lit) => Ok(IriS::new_unchecked(lit.lexical_form().as_str())),
+        Object::BlankNode(bnode) => Err(RDFParseError::ExpectedIRIFoundBNode { bnode }),
+        triple @ Object::Triple { .. } => Err(triple_term_error(triple)),
+    }
 }
 
-fn parse_nodekind<RDF>() -> impl RDFNodeParse<RDF, Output = Option<NodeKind>>
-where
-    RDF: FocusRDF,
-{
-    optional(
-        property_value(&sx_node_kind())
-            .then(|ref node| set_focus(node).and(nodekind()).map(|(_, vs)| vs)),
-    )
+fn triple_term_error(triple: Object) -> RDFParseError {
+    RDFParseError::Custom {
+        msg: format!(
+            "{}",
+            ShExRError::UnexpectedTripleTerm {
+                term: triple.to_string()
+            }
+        ),
+    }
 }
 
-fn nodekind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    iri_kind()
-        .or(literal_kind())
-        .or(bnode_kind())
-        .or(nonliteral_kind())
+fn term_as_string<RDF: FocusRDF>(term: &RDF::Term) -> PResult<String> {
+    RDF::term_as_string(term).ok_or_else(|| RDFParseError::ExpectedString {
+        term: term.to_string(),
+    })
 }
 
-fn iri_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_iri()).map(|_| NodeKind::Iri)
+fn string_value<RDF: FocusRDF>(
+    rdf: &RDF,
+    node: &RDF::Term,
+    property: &str,
+) -> PResult<Option<String>> {
+    object(rdf, node, property)?
+        .map(|t| term_as_string::<RDF>(&t))
+        .transpose()
 }
 
-fn literal_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_literal()).map(|_| NodeKind::Literal)
+fn required_string<RDF: FocusRDF>(rdf: &RDF, node: &RDF::Term, property: &str) -> PResult<String> {
+    term_as_string::<RDF>(&required_object(rdf, node, property)?)
 }
 
-fn bnode_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_bnode()).map(|_| NodeKind::BNode)
+fn bool_value<RDF: FocusRDF>(rdf: &RDF, node: &RDF::Term, property: &str) -> PResult<Option<bool>> {
+    object(rdf, node, property)?
+        .map(|t| {
+            RDF::term_as_boolean(&t).ok_or_else(|| RDFParseError::ExpectedBoolean {
+                term: t.to_string(),
+            })
+        })
+        .transpose()
 }
 
-fn nonliteral_kind<RDF>() -> impl RDFNodeParse<RDF, Output = NodeKind>
-where
-    RDF: FocusRDF,
-{
-    is_iri(ShExRVocab::sx_nonliteral()).map(|_| NodeKind::NonLiteral)
+fn integer_value<RDF: FocusRDF>(
+    rdf: &RDF,
+    node: &RDF::Term,
+    property: &str,
+) -> PResult<Option<isize>> {
+    object(rdf, node, property)?
+        .map(|t| {
+            RDF::term_as_integer(&t).ok_or_else(|| RDFParseError::ExpectedInteger {
+                term: t.to_string(),
+            })
+        })
+        .transpose()
 }
 
-fn parse_value_set<RDF>() -> impl RDFNodeParse<RDF, Output = Option<Vec<ValueSetValue>>>
-where
-    RDF: FocusRDF,
-{
-    optional(property_value(&sx_values()).then(|ref node| {
-        set_focus(node)
-            .and(parse_rdf_list::<RDF, _>(parse_value()))
-            .map(|(_, vs)| vs)
-    }))
+fn usize_value<RDF: FocusRDF>(
+    rdf: &RDF,
+    node: &RDF::Term,
+    property: &str,
+) -> PResult<Option<usize>> {
+    match integer_value(rdf, node, property)? {
+        Some(n) => usize::try_from(n)
+            .map(Some)
+            .map_err(|_| RDFParseError::Custom {
+                msg: format!("Expected non-negative value for {property} but found {n}"),
+            }),
+        None => Ok(None),
+    }
 }
 
-fn parse_value<RDF>() -> impl RDFNodeParse<RDF, Output = ValueSetValue>
-where
-    RDF: FocusRDF,
-{
-    //firstOf(objectValue, )
-    object_value().map(ValueSetValue::ObjectValue)
+/// Cardinalities are integers where `-1` means unbounded, although `sx:INF` is also accepted
+fn cardinality<RDF: FocusRDF>(rdf: &RDF, node: &RDF::Term, property: &str) -> PResult<Option<i32>> {
+    match object(rdf, node, property)? {
+        Some(t)
+            if RDF::term_as_iri(&t).is_some_and(|iri| RDF::iri2iri_s(&iri).as_str() == SX_INF) =>
+        {
+            Ok(Some(-1))
+        }
+        Some(t) => RDF::term_as_integer(&t)
+            .and_then(|n| i32::try_from(n).ok())
+            .map(Some)
+            .ok_or_else(|| RDFParseError::ExpectedInteger {
+                term: t.to_string(),
+            }),
+        None => Ok(None),
+    }
 }
 
-fn object_value<RDF>() -> impl RDFNodeParse<RDF, Output = ObjectValue>
-where
-    RDF: FocusRDF,
-{
-    iri().map(|ref iri| ObjectValue::IriRef(IriRef::Iri(iri.clone())))
+/// ShExJ reads numeric facets as decimals
+fn numeric_value<RDF: FocusRDF>(
+    rdf: &RDF,
+    node: &RDF::Term,
+    property: &str,
+) -> PResult<Option<NumericLiteral>> {
+    match object(rdf, node, property)? {
+        Some(t) => {
+            let lexical_form = term_as_string::<RDF>(&t)?;
+            let n = match Decimal::from_str(&lexical_form) {
+                Ok(d) => NumericLiteral::decimal(d),
+                Err(_) => match f64::from_str(&lexical_form) {
+                    Ok(d) => NumericLiteral::decimal_from_f64(d),
+                    Err(_) => {
+                        return Err(RDFParseError::Custom {
+                            msg: format!("Expected numeric value for {property} but found {t}"),
+                        })
+                    }
+                },
+            };
+            Ok(Some(n))
+        }
+        None => Ok(None),
+    }
 }
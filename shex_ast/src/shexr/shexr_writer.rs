@@ -0,0 +1,791 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::str::FromStr;
+
+use super::shexr_error::ShExRError;
+use super::*;
+use crate::{
+    Annotation, IriExclusion, IriRefOrWildcard, LangOrWildcard, LanguageExclusion,
+    LiteralExclusion, NodeConstraint, NodeKind, NumericFacet, ObjectValue, Schema, SemAct, Shape,
+    ShapeDecl, ShapeExpr, ShapeExprLabel, StringFacet, StringOrWildcard, TripleExpr,
+    TripleExprLabel, ValueSetValue, XsFacet,
+};
+use iri_s::IriS;
+use prefixmap::{IriRef, PrefixMap};
+use srdf::literal::Literal;
+use srdf::numeric_literal::NumericLiteral;
+use srdf::{Object, RDFFormat, SRDFBuilder, RDF, RDF_FIRST, RDF_NIL, RDF_REST, RDF_TYPE, XSD};
+
+type Result<A> = std::result::Result<A, ShExRError>;
+
+/// Writes a ShEx schema as RDF using the ShExR vocabulary
+pub struct ShExRWriter<RDF>
+where
+    RDF: SRDFBuilder,
+{
+    rdf: RDF,
+    prefixmap: PrefixMap,
+    bnode_labels: HashSet<String>,
+    bnode_counter: usize,
+}
+
+impl<RDF> ShExRWriter<RDF>
+where
+    RDF: SRDFBuilder,
+{
+    pub fn new() -> Self {
+        Self {
+            rdf: RDF::empty(),
+            prefixmap: PrefixMap::new(),
+            bnode_labels: HashSet::new(),
+            bnode_counter: 0,
+        }
+    }
+
+    pub fn write(&mut self, schema: &Schema) -> Result<()> {
+        self.prefixmap = schema.prefixmap().unwrap_or_default();
+        self.bnode_labels = bnode_labels(schema);
+
+        let mut prefix_map = self.prefixmap.clone();
+        let _ = prefix_map.insert("sx", &IriS::from_str(SX).unwrap());
+        let _ = prefix_map.insert("rdf", &IriS::from_str(RDF).unwrap());
+        let _ = prefix_map.insert("xsd", &IriS::from_str(XSD).unwrap());
+        self.rdf
+            .add_prefix_map(prefix_map)
+            .map_err(cnv_err::<RDF>)?;
+        self.rdf.add_base(&schema.base()).map_err(cnv_err::<RDF>)?;
+
+        let node = self.fresh_bnode();
+        self.add_type(&node, SX_SCHEMA)?;
+        if let Some(imports) = schema.imports() {
+            let imports = imports
+                .iter()
+                .map(|i| RDF::iri_s2term(&IriS::new_unchecked(i.to_string().as_str())))
+                .collect::<Vec<_>>();
+            let list = self.write_list(imports)?;
+            self.add(&node, SX_IMPORTS, &list)?;
+        }
+        if let Some(start_actions) = schema.start_actions() {
+            let list = self.write_sem_acts(&start_actions)?;
+            self.add(&node, SX_STARTACTS, &list)?;
+        }
+        if let Some(start) = schema.start() {
+            let se = self.write_shape_expr(&start)?;
+            self.add(&node, SX_START, &se)?;
+        }
+        if let Some(shapes) = schema.shapes() {
+            let mut decls = Vec::new();
+            for decl in shapes.iter() {
+                decls.push(self.write_shape_decl(decl)?)
+            }
+            let list = self.write_list(decls)?;
+            self.add(&node, SX_SHAPES, &list)?;
+        }
+        Ok(())
+    }
+
+    pub fn serialize<W: Write>(&self, format: RDFFormat, writer: W) -> Result<()> {
+        self.rdf.serialize(format, writer).map_err(cnv_err::<RDF>)
+    }
+
+    fn write_shape_decl(&mut self, decl: &ShapeDecl) -> Result<RDF::Term> {
+        let node = self.shape_label(&decl.id)?;
+        self.add_type(&node, SX_SHAPE_DECL)?;
+        if decl.is_abstract {
+            self.add_literal(&node, SX_ABSTRACT, Literal::boolean(true))?;
+        }
+        let se = self.write_shape_expr(&decl.shape_expr)?;
+        self.add(&node, SX_SHAPE_EXPR, &se)?;
+        Ok(node)
+    }
+
+    fn write_shape_expr(&mut self, se: &ShapeExpr) -> Result<RDF::Term> {
+        match se {
+            ShapeExpr::Ref(label) => self.shape_label(label),
+            ShapeExpr::ShapeOr { shape_exprs } => {
+                self.write_shape_exprs(SX_SHAPE_OR, shape_exprs.iter().map(|s| &s.se))
+            }
+            ShapeExpr::ShapeAnd { shape_exprs } => {
+                self.write_shape_exprs(SX_SHAPE_AND, shape_exprs.iter().map(|s| &s.se))
+            }
+            ShapeExpr::ShapeNot { shape_expr } => {
+                let node = self.fresh_bnode();
+                self.add_type(&node, SX_SHAPE_NOT)?;
+                let se = self.write_shape_expr(&shape_expr.se)?;
+                self.add(&node, SX_SHAPE_EXPR, &se)?;
+                Ok(node)
+            }
+            ShapeExpr::NodeConstraint(nc) => self.write_node_constraint(nc),
+            ShapeExpr::Shape(shape) => self.write_shape(shape),
+            ShapeExpr::External => {
+                let node = self.fresh_bnode();
+                self.add_type(&node, SX_SHAPE_EXTERNAL)?;
+                Ok(node)
+            }
+        }
+    }
+
+    fn write_shape_exprs<'a>(
+        &mut self,
+        type_: &str,
+        ses: impl Iterator<Item = &'a ShapeExpr>,
+    ) -> Result<RDF::Term> {
+        let node = self.fresh_bnode();
+        self.add_type(&node, type_)?;
+        let mut terms = Vec::new();
+        for se in ses {
+            terms.push(self.write_shape_expr(se)?)
+        }
+        let list = self.write_list(terms)?;
+        self.add(&node, SX_SHAPE_EXPRS, &list)?;
+        Ok(node)
+    }
+
+    fn write_node_constraint(&mut self, nc: &NodeConstraint) -> Result<RDF::Term> {
+        let node = self.fresh_bnode();
+        self.add_type(&node, SX_NODECONSTRAINT)?;
+        if let Some(node_kind) = nc.node_kind() {
+            let node_kind = match node_kind {
+                NodeKind::Iri => SX_IRI,
+                NodeKind::BNode => SX_BNODE,
+                NodeKind::NonLiteral => SX_NONLITERAL,
+                NodeKind::Literal => SX_LITERAL,
+                NodeKind::Triple => SX_TRIPLE,
+            };
+            self.add(&node, SX_NODEKIND, &iri_term::<RDF>(node_kind))?;
+        }
+        if let Some(datatype) = nc.datatype() {
+            let datatype = RDF::iri_s2term(&self.resolve(&datatype)?);
+            self.add(&node, SX_DATATYPE, &datatype)?;
+        }
+        for facet in nc.xs_facet().unwrap_or_default() {
+            match facet {
+                XsFacet::StringFacet(StringFacet::Length(n)) => {
+                    self.add_literal(&node, SX_LENGTH, usize_literal(n))?
+                }
+                XsFacet::StringFacet(StringFacet::MinLength(n)) => {
+                    self.add_literal(&node, SX_MINLENGTH, usize_literal(n))?
+                }
+                XsFacet::StringFacet(StringFacet::MaxLength(n)) => {
+                    self.add_literal(&node, SX_MAXLENGTH, usize_literal(n))?
+                }
+                XsFacet::StringFacet(StringFacet::Pattern(pattern)) => {
+                    self.add_literal(&node, SX_PATTERN, Literal::str(&pattern.str))?;
+                    if let Some(flags) = &pattern.flags {
+                        self.add_literal(&node, SX_FLAGS, Literal::str(flags))?
+                    }
+                }
+                XsFacet::NumericFacet(NumericFacet::MinInclusive(n)) => {
+                    self.add_literal(&node, SX_MININCLUSIVE, Literal::NumericLiteral(n))?
+                }
+                XsFacet::NumericFacet(NumericFacet::MinExclusive(n)) => {
+                    self.add_literal(&node, SX_MINEXCLUSIVE, Literal::NumericLiteral(n))?
+                }
+                XsFacet::NumericFacet(NumericFacet::MaxInclusive(n)) => {
+                    self.add_literal(&node, SX_MAXINCLUSIVE, Literal::NumericLiteral(n))?
+                }
+                XsFacet::NumericFacet(NumericFacet::MaxExclusive(n)) => {
+                    self.add_literal(&node, SX_MAXEXCLUSIVE, Literal::NumericLiteral(n))?
+                }
+                XsFacet::NumericFacet(NumericFacet::TotalDigits(n)) => {
+                    self.add_literal(&node, SX_TOTALDIGITS, usize_literal(n))?
+                }
+                XsFacet::NumericFacet(NumericFacet::FractionDigits(n)) => {
+                    self.add_literal(&node, SX_FRACTIONDIGITS, usize_literal(n))?
+                }
+            }
+        }
+        if let Some(values) = nc.values() {
+            let mut terms = Vec::new();
+            for value in values.iter() {
+                terms.push(self.write_value_set_value(value)?)
+            }
+            let list = self.write_list(terms)?;
+            self.add(&node, SX_VALUES, &list)?;
+        }
+        Ok(node)
+    }
+
+    fn write_value_set_value(&mut self, value: &ValueSetValue) -> Result<RDF::Term> {
+        match value {
+            ValueSetValue::ObjectValue(ov) => self.object_value(ov),
+            ValueSetValue::IriStem { stem } => self.write_iri_stem(SX_IRISTEM, stem),
+            ValueSetValue::IriStemRange { stem, exclusions } => {
+                let node = self.fresh_bnode();
+                self.add_type(&node, SX_IRISTEMRANGE)?;
+                let stem = match stem {
+                    IriRefOrWildcard::IriRef(iri) => self.any_uri(iri)?,
+                    IriRefOrWildcard::Wildcard => self.wildcard()?,
+                };
+                self.add(&node, SX_STEM, &stem)?;
+                if let Some(exclusions) = exclusions {
+                    let mut terms = Vec::new();
+                    for exclusion in exclusions {
+                        terms.push(match exclusion {
+                            IriExclusion::Iri(iri) => self.any_uri(iri)?,
+                            IriExclusion::IriStem(stem) => self.write_iri_stem(SX_IRISTEM, stem)?,
+                        })
+                    }
+                    let list = self.write_list(terms)?;
+                    self.add(&node, SX_EXCLUSION, &list)?;
+                }
+                Ok(node)
+            }
+            ValueSetValue::LiteralStem { stem } => self.write_stem(SX_LITERALSTEM, stem),
+            ValueSetValue::LiteralStemRange { stem, exclusions } => {
+                let node = self.fresh_bnode();
+                self.add_type(&node, SX_LITERALSTEMRANGE)?;
+                let stem = match stem {
+                    StringOrWildcard::String(s) => literal_term::<RDF>(Literal::str(s)),
+                    StringOrWildcard::Wildcard => self.wildcard()?,
+                };
+                self.add(&node, SX_STEM, &stem)?;
+                if let Some(exclusions) = exclusions {
+                    let mut terms = Vec::new();
+                    for exclusion in exclusions {
+                        terms.push(match exclusion {
+                            LiteralExclusion::Literal(s) => literal_term::<RDF>(Literal::str(s)),
+                            LiteralExclusion::LiteralStem(s) => {
+                                self.write_stem(SX_LITERALSTEM, s)?
+                            }
+                        })
+                    }
+                    let list = self.write_list(terms)?;
+                    self.add(&node, SX_EXCLUSION, &list)?;
+                }
+                Ok(node)
+            }
+            ValueSetValue::Language { language_tag } => {
+                let node = self.fresh_bnode();
+                self.add_type(&node, SX_LANGUAGE)?;
+                self.add_literal(&node, SX_LANGUAGE_TAG, Literal::str(&language_tag.value()))?;
+                Ok(node)
+            }
+            ValueSetValue::LanguageStem { stem } => self.write_stem(SX_LANGUAGESTEM, &stem.value()),
+            ValueSetValue::LanguageStemRange { stem, exclusions } => {
+                let node = self.fresh_bnode();
+                self.add_type(&node, SX_LANGUAGESTEMRANGE)?;
+                let stem = match stem {
+                    LangOrWildcard::Lang(lang) => literal_term::<RDF>(Literal::str(&lang.value())),
+                    LangOrWildcard::Wildcard => self.wildcard()?,
+                };
+                self.add(&node, SX_STEM, &stem)?;
+                if let Some(exclusions) = exclusions {
+                    let mut terms = Vec::new();
+                    for exclusion in exclusions {
+                        terms.push(match exclusion {
+                            LanguageExclusion::Language(lang) => {
+                                literal_term::<RDF>(Literal::str(&lang.value()))
+                            }
+                            LanguageExclusion::LanguageStem(lang) => {
+                                self.write_stem(SX_LANGUAGESTEM, &lang.value())?
+                            }
+                        })
+                    }
+                    let list = self.write_list(terms)?;
+                    self.add(&node, SX_EXCLUSION, &list)?;
+                }
+                Ok(node)
+            }
+        }
+    }
+
+    fn write_iri_stem(&mut self, type_: &str, stem: &IriRef) -> Result<RDF::Term> {
+        let node = self.fresh_bnode();
+        self.add_type(&node, type_)?;
+        let stem = self.any_uri(stem)?;
+        self.add(&node, SX_STEM, &stem)?;
+        Ok(node)
+    }
+
+    fn write_stem(&mut self, type_: &str, stem: &str) -> Result<RDF::Term> {
+        let node = self.fresh_bnode();
+        self.add_type(&node, type_)?;
+        self.add_literal(&node, SX_STEM, Literal::str(stem))?;
+        Ok(node)
+    }
+
+    fn wildcard(&mut self) -> Result<RDF::Term> {
+        let node = self.fresh_bnode();
+        self.add_type(&node, SX_WILDCARD)?;
+        Ok(node)
+    }
+
+    fn write_shape(&mut self, shape: &Shape) -> Result<RDF::Term> {
+        let node = self.fresh_bnode();
+        self.add_type(&node, SX_SHAPE)?;
+        if let Some(closed) = shape.closed {
+            self.add_literal(&node, SX_CLOSED, Literal::boolean(closed))?;
+        }
+        for extra in shape.extra.iter().flatten() {
+            let extra = RDF::iri_s2term(&self.resolve(extra)?);
+            self.add(&node, SX_EXTRA, &extra)?;
+        }
+        if let Some(expression) = &shape.expression {
+            let te = self.write_triple_expr(&expression.te)?;
+            self.add(&node, SX_EXPRESSION, &te)?;
+        }
+        if let Some(extends) = &shape.extends {
+            let mut labels = Vec::new();
+            for label in extends {
+                labels.push(self.shape_label(label)?)
+            }
+            let list = self.write_list(labels)?;
+            self.add(&node, SX_EXTENDS, &list)?;
+        }
+        if let Some(sem_acts) = &shape.sem_acts {
+            let list = self.write_sem_acts(sem_acts)?;
+            self.add(&node, SX_SEMACTS, &list)?;
+        }
+        if let Some(annotations) = &shape.annotations {
+            let list = self.write_annotations(annotations)?;
+            self.add(&node, SX_ANNOTATION_PROP, &list)?;
+        }
+        Ok(node)
+    }
+
+    fn write_triple_expr(&mut self, te: &TripleExpr) -> Result<RDF::Term> {
+        let (node, min, max, sem_acts, annotations) = match te {
+            TripleExpr::TripleExprRef(label) => return self.triple_expr_label(label),
+            TripleExpr::EachOf {
+                id,
+                expressions,
+                min,
+                max,
+                sem_acts,
+                annotations,
+            }
+            | TripleExpr::OneOf {
+                id,
+                expressions,
+                min,
+                max,
+                sem_acts,
+                annotations,
+            } => {
+                let node = self.triple_expr_node(id)?;
+                let type_ = match te {
+                    TripleExpr::EachOf { .. } => SX_EACHOF,
+                    _ => SX_ONEOF,
+                };
+                self.add_type(&node, type_)?;
+                let mut terms = Vec::new();
+                for e in expressions {
+                    terms.push(self.write_triple_expr(&e.te)?)
+                }
+                let list = self.write_list(terms)?;
+                self.add(&node, SX_EXPRESSIONS, &list)?;
+                (node, min, max, sem_acts, annotations)
+            }
+            TripleExpr::TripleConstraint {
+                id,
+                negated,
+                inverse,
+                predicate,
+                value_expr,
+                min,
+                max,
+                sem_acts,
+                annotations,
+            } => {
+                let node = self.triple_expr_node(id)?;
+                self.add_type(&node, SX_TRIPLECONSTRAINT)?;
+                if let Some(negated) = negated {
+                    self.add_literal(&node, SX_NEGATED, Literal::boolean(*negated))?;
+                }
+                if let Some(inverse) = inverse {
+                    self.add_literal(&node, SX_INVERSE, Literal::boolean(*inverse))?;
+                }
+                let predicate = RDF::iri_s2term(&self.resolve(predicate)?);
+                self.add(&node, SX_PREDICATE, &predicate)?;
+                if let Some(value_expr) = value_expr {
+                    let se = self.write_shape_expr(value_expr)?;
+                    self.add(&node, SX_VALUEEXPR, &se)?;
+                }
+                (node, min, max, sem_acts, annotations)
+            }
+        };
+        if let Some(min) = min {
+            self.add_literal(&node, SX_MIN, Literal::integer(*min as isize))?;
+        }
+        if let Some(max) = max {
+            self.add_literal(&node, SX_MAX, Literal::integer(*max as isize))?;
+        }
+        if let Some(sem_acts) = sem_acts {
+            let list = self.write_sem_acts(sem_acts)?;
+            self.add(&node, SX_SEMACTS, &list)?;
+        }
+        if let Some(annotations) = annotations {
+            let list = self.write_annotations(annotations)?;
+            self.add(&node, SX_ANNOTATION_PROP, &list)?;
+        }
+        Ok(node)
+    }
+
+    fn triple_expr_node(&mut self, id: &Option<TripleExprLabel>) -> Result<RDF::Term> {
+        match id {
+            Some(label) => self.triple_expr_label(label),
+            None => Ok(self.fresh_bnode()),
+        }
+    }
+
+    fn write_sem_acts(&mut self, sem_acts: &[SemAct]) -> Result<RDF::Term> {
+        let mut terms = Vec::new();
+        for sem_act in sem_acts {
+            let node = self.fresh_bnode();
+            self.add_type(&node, SX_SEMACT)?;
+            let name = RDF::iri_s2term(&self.resolve(&sem_act.name())?);
+            self.add(&node, SX_NAME, &name)?;
+            if let Some(code) = sem_act.code() {
+                self.add_literal(&node, SX_CODE, Literal::str(&code))?;
+            }
+            terms.push(node)
+        }
+        self.write_list(terms)
+    }
+
+    fn write_annotations(&mut self, annotations: &[Annotation]) -> Result<RDF::Term> {
+        let mut terms = Vec::new();
+        for annotation in annotations {
+            let node = self.fresh_bnode();
+            self.add_type(&node, SX_ANNOTATION)?;
+            let predicate = RDF::iri_s2term(&self.resolve(&annotation.predicate())?);
+            self.add(&node, SX_PREDICATE, &predicate)?;
+            let object = self.object_value(&annotation.object())?;
+            self.add(&node, SX_OBJECT, &object)?;
+            terms.push(node)
+        }
+        self.write_list(terms)
+    }
+
+    /// Writes the terms as an RDF list
+    fn write_list(&mut self, terms: Vec<RDF::Term>) -> Result<RDF::Term> {
+        let mut list = RDF::iri_s2term(&RDF_NIL);
+        for first in terms.iter().rev() {
+            let node = self.fresh_bnode();
+            self.add(&node, RDF_FIRST.as_str(), first)?;
+            self.add(&node, RDF_REST.as_str(), &list)?;
+            list = node;
+        }
+        Ok(list)
+    }
+
+    fn object_value(&self, ov: &ObjectValue) -> Result<RDF::Term> {
+        match ov {
+            ObjectValue::IriRef(iri) => Ok(RDF::iri_s2term(&self.resolve(iri)?)),
+            ObjectValue::Literal(Literal::DatatypeLiteral {
+                lexical_form,
+                datatype,
+            }) => {
+                let datatype = IriRef::iri(self.resolve(datatype)?);
+                Ok(literal_term::<RDF>(Literal::datatype(
+                    lexical_form,
+                    &datatype,
+                )))
+            }
+            ObjectValue::Literal(lit) => Ok(literal_term::<RDF>(lit.clone())),
+        }
+    }
+
+    /// IRI stems are written as `xsd:anyURI` literals
+    fn any_uri(&self, iri: &IriRef) -> Result<RDF::Term> {
+        let iri = self.resolve(iri)?;
+        let any_uri = IriRef::iri(IriS::new_unchecked(&format!("{XSD}anyURI")));
+        Ok(literal_term::<RDF>(Literal::datatype(
+            iri.as_str(),
+            &any_uri,
+        )))
+    }
+
+    fn shape_label(&self, label: &ShapeExprLabel) -> Result<RDF::Term> {
+        match label {
+            ShapeExprLabel::IriRef { value } => Ok(RDF::iri_s2term(&self.resolve(value)?)),
+            ShapeExprLabel::BNode { value } => Ok(RDF::bnode_id2term(value.as_str())),
+            ShapeExprLabel::Start => Err(ShExRError::SRDFError {
+                err: "START can not be used as a shape label in ShExR".to_string(),
+            }),
+        }
+    }
+
+    fn triple_expr_label(&self, label: &TripleExprLabel) -> Result<RDF::Term> {
+        match label {
+            TripleExprLabel::IriRef { value } => Ok(RDF::iri_s2term(&self.resolve(value)?)),
+            TripleExprLabel::BNode { value } => Ok(RDF::bnode_id2term(value.as_str())),
+        }
+    }
+
+    fn resolve(&self, iri: &IriRef) -> Result<IriS> {
+        Ok(self.prefixmap.resolve_iriref(iri)?)
+    }
+
+    /// Returns a blank node whose label is not used in the schema
+    fn fresh_bnode(&mut self) -> RDF::Term {
+        loop {
+            self.bnode_counter += 1;
+            let id = format!("b{}", self.bnode_counter);
+            if !self.bnode_labels.contains(&id) {
+                return RDF::bnode_id2term(&id);
+            }
+        }
+    }
+
+    fn add_type(&mut self, node: &RDF::Term, type_: &str) -> Result<()> {
+        self.add(node, RDF_TYPE.as_str(), &iri_term::<RDF>(type_))
+    }
+
+    fn add_literal(&mut self, node: &RDF::Term, property: &str, value: Literal) -> Result<()> {
+        self.add(node, property, &literal_term::<RDF>(value))
+    }
+
+    fn add(&mut self, node: &RDF::Term, property: &str, value: &RDF::Term) -> Result<()> {
+        let subject = RDF::term_as_subject(node).ok_or_else(|| ShExRError::SRDFError {
+            err: format!("Node {node} can not be a subject"),
+        })?;
+        self.rdf
+            .add_triple(
+                &subject,
+                &RDF::iri_s2iri(&IriS::new_unchecked(property)),
+                value,
+            )
+            .map_err(cnv_err::<RDF>)
+    }
+}
+
+impl<RDF> Default for ShExRWriter<RDF>
+where
+    RDF: SRDFBuilder,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn cnv_err<RDF: SRDFBuilder>(err: RDF::Err) -> ShExRError {
+    ShExRError::SRDFError {
+        err: err.to_string(),
+    }
+}
+
+fn iri_term<RDF: SRDFBuilder>(iri: &str) -> RDF::Term {
+    RDF::iri_s2term(&IriS::new_unchecked(iri))
+}
+
+fn literal_term<RDF: SRDFBuilder>(lit: Literal) -> RDF::Term {
+    RDF::object_as_term(&Object::Literal(lit))
+}
+
+fn usize_literal(n: usize) -> Literal {
+    Literal::NumericLiteral(NumericLiteral::integer(n as isize))
+}
+
+/// Collects the blank node labels of shapes and triple expressions in a schema
+fn bnode_labels(schema: &Schema) -> HashSet<String> {
+    let mut labels = HashSet::new();
+    for decl in schema.shapes().unwrap_or_default() {
+        if let ShapeExprLabel::BNode { value } = &decl.id {
+            labels.insert(value.as_str().to_string());
+        }
+        shape_expr_bnode_labels(&decl.shape_expr, &mut labels)
+    }
+    if let Some(start) = schema.start() {
+        shape_expr_bnode_labels(&start, &mut labels)
+    }
+    labels
+}
+
+fn shape_expr_bnode_labels(se: &ShapeExpr, labels: &mut HashSet<String>) {
+    match se {
+        ShapeExpr::ShapeOr { shape_exprs } | ShapeExpr::ShapeAnd { shape_exprs } => shape_exprs
+            .iter()
+            .for_each(|s| shape_expr_bnode_labels(&s.se, labels)),
+        ShapeExpr::ShapeNot { shape_expr } => shape_expr_bnode_labels(&shape_expr.se, labels),
+        ShapeExpr::Shape(shape) => {
+            if let Some(expression) = &shape.expression {
+                triple_expr_bnode_labels(&expression.te, labels)
+            }
+        }
+        ShapeExpr::Ref(ShapeExprLabel::BNode { value }) => {
+            labels.insert(value.as_str().to_string());
+        }
+        _ => {}
+    }
+}
+
+fn triple_expr_bnode_labels(te: &TripleExpr, labels: &mut HashSet<String>) {
+    let id = match te {
+        TripleExpr::EachOf {
+            id, expressions, ..
+        }
+        | TripleExpr::OneOf {
+            id, expressions, ..
+        } => {
+            expressions
+                .iter()
+                .for_each(|e| triple_expr_bnode_labels(&e.te, labels));
+            id
+        }
+        TripleExpr::TripleConstraint { id, value_expr, .. } => {
+            if let Some(se) = value_expr {
+                shape_expr_bnode_labels(se, labels)
+            }
+            id
+        }
+        TripleExpr::TripleExprRef(TripleExprLabel::BNode { value }) => {
+            labels.insert(value.as_str().to_string());
+            return;
+        }
+        TripleExpr::TripleExprRef(_) => return,
+    };
+    if let Some(TripleExprLabel::BNode { value }) = id {
+        labels.insert(value.as_str().to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shexr::shexr_parser::ShExRParser;
+    use srdf::{ReaderMode, SRDFGraph};
+
+    const SCHEMA: &str = r#"{
+      "@context": "http://www.w3.org/ns/shex.jsonld",
+      "type": "Schema",
+      "start": "http://example.org/Person",
+      "shapes": [
+        {
+          "type": "ShapeDecl",
+          "id": "http://example.org/Person",
+          "shapeExpr": {
+            "type": "Shape",
+            "closed": true,
+            "extra": [ "http://example.org/knows" ],
+            "extends": [ "http://example.org/Agent" ],
+            "expression": {
+              "type": "EachOf",
+              "expressions": [
+                {
+                  "type": "TripleConstraint",
+                  "id": "http://example.org/nameTC",
+                  "predicate": "http://example.org/name",
+                  "valueExpr": {
+                    "type": "NodeConstraint",
+                    "datatype": "http://www.w3.org/2001/XMLSchema#string",
+                    "pattern": "^[A-Z]",
+                    "flags": "i",
+                    "minlength": 2
+                  },
+                  "annotations": [
+                    {
+                      "type": "Annotation",
+                      "predicate": "http://www.w3.org/2000/01/rdf-schema#label",
+                      "object": { "value": "Name" }
+                    }
+                  ]
+                },
+                {
+                  "type": "TripleConstraint",
+                  "predicate": "http://example.org/age",
+                  "valueExpr": {
+                    "type": "NodeConstraint",
+                    "datatype": "http://www.w3.org/2001/XMLSchema#integer",
+                    "mininclusive": 0
+                  },
+                  "min": 0,
+                  "max": 1
+                },
+                {
+                  "type": "TripleConstraint",
+                  "inverse": true,
+                  "predicate": "http://example.org/knows",
+                  "valueExpr": "http://example.org/Person",
+                  "min": 0,
+                  "max": -1,
+                  "semActs": [
+                    { "type": "SemAct", "name": "http://example.org/log", "code": "print(o)" }
+                  ]
+                }
+              ]
+            }
+          }
+        },
+        {
+          "type": "ShapeDecl",
+          "id": "http://example.org/Agent",
+          "abstract": true,
+          "shapeExpr": {
+            "type": "ShapeOr",
+            "shapeExprs": [
+              { "type": "NodeConstraint", "nodeKind": "iri" },
+              {
+                "type": "ShapeNot",
+                "shapeExpr": {
+                  "type": "NodeConstraint",
+                  "values": [
+                    "http://example.org/nobody",
+                    { "value": "23", "type": "http://www.w3.org/2001/XMLSchema#integer" },
+                    { "value": "hi", "language": "en" },
+                    { "type": "Language", "languageTag": "es" },
+                    { "type": "LanguageStem", "stem": "fr" },
+                    { "type": "LiteralStem", "stem": "abc" },
+                    {
+                      "type": "IriStemRange",
+                      "stem": "http://example.org/",
+                      "exclusions": [
+                        "http://example.org/a",
+                        { "type": "IriStem", "stem": "http://example.org/b" }
+                      ]
+                    },
+                    {
+                      "type": "LiteralStemRange",
+                      "stem": { "type": "Wildcard" },
+                      "exclusions": [ "x", { "type": "LiteralStem", "stem": "y" } ]
+                    }
+                  ]
+                }
+              }
+            ]
+          }
+        },
+        {
+          "type": "ShapeDecl",
+          "id": "http://example.org/Named",
+          "shapeExpr": {
+            "type": "Shape",
+            "expression": "http://example.org/nameTC"
+          }
+        },
+        {
+          "type": "ShapeDecl",
+          "id": "http://example.org/Remote",
+          "shapeExpr": { "type": "ShapeExternal" }
+        }
+      ]
+    }"#;
+
+    fn round_trip(format: RDFFormat) -> Schema {
+        let schema: Schema = serde_json::from_str(SCHEMA).unwrap();
+        let mut writer: ShExRWriter<SRDFGraph> = ShExRWriter::new();
+        writer.write(&schema).unwrap();
+        let mut output = Vec::new();
+        writer.serialize(format, &mut output).unwrap();
+        let str = String::from_utf8(output).unwrap();
+        let rdf = SRDFGraph::from_str(&str, &format, None, &ReaderMode::Strict).unwrap();
+        ShExRParser::new(rdf).parse().unwrap()
+    }
+
+    #[test]
+    fn round_trip_turtle() {
+        let schema: Schema = serde_json::from_str(SCHEMA).unwrap();
+        let parsed = round_trip(RDFFormat::Turtle);
+        assert_eq!(parsed.shapes(), schema.shapes());
+        assert_eq!(parsed.start(), schema.start());
+    }
+
+    #[test]
+    fn round_trip_ntriples() {
+        let schema: Schema = serde_json::from_str(SCHEMA).unwrap();
+        let parsed = round_trip(RDFFormat::NTriples);
+        assert_eq!(parsed.shapes(), schema.shapes());
+        assert_eq!(parsed.start(), schema.start());
+    }
+}
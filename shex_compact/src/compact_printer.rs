@@ -13,15 +13,37 @@ pub(crate) fn pp_object_value<'a, A>(
 ) -> DocBuilder<'a, Arena<'a, A>, A> {
     match v {
         ObjectValue::IriRef(i) => pp_iri_ref(i, doc, prefixmap),
-        ObjectValue::Literal(Literal::BooleanLiteral(_value)) => {
-            todo!()
+        ObjectValue::Literal(Literal::BooleanLiteral(value)) => doc.text(value.to_string()),
+        ObjectValue::Literal(Literal::NumericLiteral(n)) => doc.text(n.to_string()),
+        ObjectValue::Literal(Literal::DatatypeLiteral {
+            lexical_form,
+            datatype,
+        }) => doc
+            .text(format!("\"{}\"^^", escape_string(lexical_form)))
+            .append(pp_iri_ref(datatype, doc, prefixmap)),
+        ObjectValue::Literal(Literal::StringLiteral { lexical_form, lang }) => {
+            let lang = lang.as_ref().map(|l| l.to_string()).unwrap_or_default();
+            doc.text(format!("\"{}\"{lang}", escape_string(lexical_form)))
         }
-        ObjectValue::Literal(Literal::NumericLiteral(_)) => todo!(),
-        ObjectValue::Literal(Literal::DatatypeLiteral { .. }) => todo!(),
-        ObjectValue::Literal(Literal::StringLiteral { .. }) => todo!(),
     }
 }
 
+/// Escapes the characters that can't appear inside a double quoted string
+pub(crate) fn escape_string(str: &str) -> String {
+    let mut escaped = String::with_capacity(str.len());
+    for c in str.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 pub(crate) fn pp_label<'a, A>(
     label: &ShapeExprLabel,
     doc: &'a Arena<'a, A>,
@@ -1,4 +1,6 @@
-use crate::{shex_parser_error::ParseError as ShExParseError, IRes, Span};
+use crate::{
+    shex_comments::record_comment, shex_parser_error::ParseError as ShExParseError, IRes, Span,
+};
 use colored::*;
 use nom::{
     branch::alt,
@@ -69,14 +71,18 @@ where
 
 /// A combinator that recognises a comment, starting at a `#`
 /// character and ending at the end of the line.
+///
+/// The comment is recorded so it can be kept by [`ShExParser::parse_with_comments`](crate::ShExParser::parse_with_comments)
 fn comment(input: Span) -> IRes<()> {
-    alt((
+    let (rest, _) = alt((
         value((), pair(tag("#"), is_not("\n\r"))),
         // a comment that immediately precedes the end of the line –
         // this must come after the normal line comment above
         value((), tag("#")),
         value((), multi_comment),
-    ))(input)
+    ))(input)?;
+    record_comment(input.location_offset(), rest.location_offset());
+    Ok((rest, ()))
 }
 
 fn multi_comment(i: Span) -> IRes<()> {
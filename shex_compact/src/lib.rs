@@ -30,7 +30,10 @@ mod located_parse_error;
 pub mod shapemap_compact_printer;
 mod shapemap_grammar;
 pub mod shapemap_parser;
+pub mod shex_comments;
 pub mod shex_compact_printer;
+pub mod shex_diagnostic;
+pub mod shex_formatter_error;
 mod shex_grammar;
 pub mod shex_parser;
 pub mod shex_parser_error;
@@ -43,7 +46,10 @@ pub(crate) use crate::grammar::*;
 pub use crate::located_parse_error::*;
 pub use crate::shapemap_compact_printer::*;
 pub use crate::shapemap_parser::*;
+pub use crate::shex_comments::*;
 pub use crate::shex_compact_printer::*;
+pub use crate::shex_diagnostic::*;
+pub use crate::shex_formatter_error::*;
 pub use crate::shex_grammar::*;
 pub use crate::shex_parser::*;
 pub use crate::shex_parser_error::*;
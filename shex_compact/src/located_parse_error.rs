@@ -18,6 +18,8 @@ pub struct LocatedParseError {
     pub source: ShExParseError,
     pub line: u32,
     pub column: usize,
+    /// Offset in bytes from the beginning of the input
+    pub offset: usize,
     pub fragment: String,
     pub context: Vec<LocatedParseError>,
 }
@@ -27,6 +29,14 @@ impl LocatedParseError {
     pub(crate) fn append(&mut self, other: LocatedParseError) {
         self.context.push(other)
     }
+
+    /// Offset of the furthest position reached by this error or its context
+    pub fn deepest_offset(&self) -> usize {
+        self.context
+            .iter()
+            .map(|e| e.deepest_offset())
+            .fold(self.offset, usize::max)
+    }
 }
 
 pub(crate) fn format_parse_error_context(context: &[LocatedParseError]) -> String {
@@ -56,6 +66,15 @@ impl nom::error::ParseError<Span<'_>> for LocatedParseError {
         error.append(other);
         error
     }
+
+    // When all the alternatives fail, keep the error of the one that went further
+    fn or(self, other: Self) -> Self {
+        if self.deepest_offset() > other.deepest_offset() {
+            self
+        } else {
+            other
+        }
+    }
 }
 
 impl FromExternalError<Span<'_>, ParseIntError> for LocatedParseError {
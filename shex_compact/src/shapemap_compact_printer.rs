@@ -1,8 +1,11 @@
 use crate::{keyword, pp_label, pp_object_value};
 use colored::*;
-use prefixmap::PrefixMap;
+use prefixmap::{IriRef, PrefixMap};
 use pretty::{Arena, DocAllocator, DocBuilder};
-use shapemap::{query_shape_map::QueryShapeMap, Association, NodeSelector, ShapeSelector};
+use shapemap::{
+    query_shape_map::QueryShapeMap, Association, NodeSelector, Pattern, PropertyPath, ShapeSelector,
+};
+use shex_ast::object_value::ObjectValue;
 use std::marker::PhantomData;
 
 /// Struct that can be used to pretty print Shapemaps
@@ -143,7 +146,8 @@ where
         for a in self.shapemap.iter() {
             docs.push(self.pp_association(a))
         }
-        self.doc.intersperse(docs, self.doc.hardline())
+        self.doc
+            .intersperse(docs, self.doc.text(",").append(self.doc.hardline()))
     }
 
     fn pp_association(&self, assoc: &Association) -> DocBuilder<'a, Arena<'a, A>, A> {
@@ -155,13 +159,83 @@ where
     fn pp_node_selector(&self, ns: &NodeSelector) -> DocBuilder<'a, Arena<'a, A>, A> {
         match ns {
             NodeSelector::Node(v) => pp_object_value(v, self.doc, &self.nodes_prefixmap),
-            NodeSelector::TriplePattern { .. } => todo!(),
-            NodeSelector::TriplePatternPath { .. } => todo!(),
-            NodeSelector::Sparql { .. } => todo!(),
-            NodeSelector::Generic { .. } => todo!(),
+            NodeSelector::TriplePattern {
+                subject,
+                pred,
+                object,
+            } => self.pp_triple_pattern(subject, self.qualify_iri_ref(pred), object),
+            NodeSelector::TriplePatternPath {
+                subject,
+                pred,
+                object,
+            } => self.pp_triple_pattern(subject, self.pp_path(pred), object),
+            NodeSelector::Sparql { query } => keyword("SPARQL", self.doc, self.keyword_color)
+                .append(self.doc.space())
+                .append(self.pp_long_string(query)),
+            NodeSelector::Generic { iri, param } => self
+                .doc
+                .text(self.nodes_prefixmap.qualify(iri))
+                .append(self.doc.space())
+                .append(self.pp_long_string(param)),
         }
     }
 
+    fn pp_triple_pattern(
+        &self,
+        subject: &Pattern,
+        path: String,
+        object: &Pattern,
+    ) -> DocBuilder<'a, Arena<'a, A>, A> {
+        self.doc
+            .text("{")
+            .append(self.pp_pattern(subject))
+            .append(self.doc.space())
+            .append(self.doc.text(path))
+            .append(self.doc.space())
+            .append(self.pp_pattern(object))
+            .append(self.doc.text("}"))
+    }
+
+    fn pp_pattern(&self, pattern: &Pattern) -> DocBuilder<'a, Arena<'a, A>, A> {
+        match pattern {
+            Pattern::Focus => keyword("FOCUS", self.doc, self.keyword_color),
+            Pattern::Wildcard => self.doc.text("_"),
+            Pattern::Node(ObjectValue::IriRef(iri_ref)) => {
+                self.doc.text(self.qualify_iri_ref(iri_ref))
+            }
+            Pattern::Node(ObjectValue::Literal(lit)) => self.doc.text(lit.to_string()),
+        }
+    }
+
+    // Paths are printed following the syntax of SPARQL property paths
+    fn pp_path(&self, path: &PropertyPath) -> String {
+        let pp_paths = |paths: &Vec<PropertyPath>, separator: &str| {
+            let paths: Vec<String> = paths.iter().map(|p| self.pp_path(p)).collect();
+            format!("({})", paths.join(separator))
+        };
+        match path {
+            PropertyPath::Predicate(pred) => self.qualify_iri_ref(pred),
+            PropertyPath::Alternative(paths) => pp_paths(paths, " | "),
+            PropertyPath::Sequence(paths) => pp_paths(paths, " / "),
+            PropertyPath::Inverse(path) => format!("^{}", self.pp_path(path)),
+            PropertyPath::ZeroOrMore(path) => format!("{}*", self.pp_path(path)),
+            PropertyPath::OneOrMore(path) => format!("{}+", self.pp_path(path)),
+            PropertyPath::ZeroOrOne(path) => format!("{}?", self.pp_path(path)),
+        }
+    }
+
+    fn qualify_iri_ref(&self, iri_ref: &IriRef) -> String {
+        match iri_ref {
+            IriRef::Iri(iri) => self.nodes_prefixmap.qualify(iri),
+            IriRef::Prefixed { prefix, local } => format!("{prefix}:{local}"),
+        }
+    }
+
+    fn pp_long_string(&self, str: &str) -> DocBuilder<'a, Arena<'a, A>, A> {
+        let escaped = str.replace('\\', "\\\\").replace('"', "\\\"");
+        self.doc.text(format!("\"\"\"{escaped}\"\"\""))
+    }
+
     fn pp_shape_selector(&self, s: &ShapeSelector) -> DocBuilder<'a, Arena<'a, A>, A> {
         match s {
             ShapeSelector::Label(label) => {
@@ -173,4 +247,26 @@ where
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use crate::ShapeMapParser;
+
+    #[test]
+    fn node_selectors_round_trip() {
+        let str = r#"{FOCUS :worksFor _}@:Employee,
+{<http://example.org/alice> (:knows | ^:friend)+ FOCUS}@:S,
+SPARQL """select ?x where { ?x :name "Alice" }"""@START"#;
+        let shapemap = ShapeMapParser::parse(str, &None, &None).unwrap();
+        let formatted = ShapemapFormatter::default()
+            .without_colors()
+            .format_shapemap(&shapemap);
+        assert_eq!(
+            formatted,
+            r#"{FOCUS :worksFor _}@:Employee,
+{<http://example.org/alice> (:knows | ^:friend)+ FOCUS}@:S,
+SPARQL """select ?x where { ?x :name \"Alice\" }"""@START"#
+        );
+        let reparsed = ShapeMapParser::parse(&formatted, &None, &None).unwrap();
+        assert_eq!(reparsed, shapemap)
+    }
+}
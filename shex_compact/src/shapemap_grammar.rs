@@ -1,17 +1,23 @@
 use crate::{
-    grammar::{map_error, tag_no_case_tws, token_tws, traced, tws0},
+    grammar::{map_error, tag_no_case_tws, token, token_tws, traced, tws0},
     iri,
-    shex_grammar::shape_expr_label,
+    shex_grammar::{
+        blank_node, boolean_literal, iri_or_literal, iri_ref, lang_tag, numeric_literal, predicate,
+        shape_expr_label, string,
+    },
     IRes, ParseError, Span,
 };
 use nom::{
     branch::alt,
     character::complete::char,
-    combinator::{all_consuming, map, opt},
-    multi::many0,
-    sequence::tuple,
+    combinator::{all_consuming, map, opt, peek},
+    multi::{many0, separated_list0},
+    sequence::{delimited, preceded, terminated, tuple},
 };
-use shapemap::{NodeSelector, ShapeSelector};
+use prefixmap::IriRef;
+use shapemap::{NodeSelector, Pattern, PropertyPath, ShapeMapState, ShapeSelector};
+use shex_ast::BNode;
+use srdf::literal::Literal;
 
 #[derive(Debug, PartialEq)]
 pub(crate) enum ShapeMapStatement {
@@ -60,6 +66,107 @@ fn rest_associations(i: Span) -> IRes<Vec<ShapeMapStatement>> {
     Ok((i, r))
 }
 
+/// Association of a result shapemap
+#[derive(Debug, PartialEq)]
+pub(crate) struct ResultAssociation {
+    pub node: ResultNode,
+    pub status: ShapeMapState,
+    pub shape: ShapeSelector,
+    pub reason: Option<String>,
+}
+
+/// Node of a result shapemap, which can be any RDF term
+#[derive(Debug, PartialEq)]
+pub(crate) enum ResultNode {
+    Iri(IriRef),
+    BNode(BNode),
+    Literal(Literal),
+}
+
+/// `result_shapemap ::= (result_association ("," result_association)* ","?)?`
+pub(crate) fn result_shapemap<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, Vec<ResultAssociation>> {
+    traced(
+        "result_shapemap",
+        map_error(
+            move |i| {
+                let (i, (_, associations, _, _)) = all_consuming(tuple((
+                    tws0,
+                    separated_list0(token_tws(","), result_association),
+                    opt(token_tws(",")),
+                    tws0,
+                )))(i)?;
+                Ok((i, associations))
+            },
+            || ParseError::ExpectedShapeMapAssociation,
+        ),
+    )
+}
+
+/// `result_association ::= result_node "@" ("!" | "?")? shape_spec string?`
+///
+/// The marks `!` and `?` are the status of nonconformant and pending nodes,
+/// and the optional string is the reason of the status
+fn result_association(i: Span) -> IRes<ResultAssociation> {
+    let (i, (node, _, status, shape, reason)) = tuple((
+        result_node,
+        token_tws("@"),
+        status,
+        shape_spec(),
+        opt(preceded(tws0, string())),
+    ))(i)?;
+    let association = ResultAssociation {
+        node,
+        status,
+        shape,
+        reason,
+    };
+    Ok((i, association))
+}
+
+fn status(i: Span) -> IRes<ShapeMapState> {
+    let (i, (mark, _)) = tuple((opt(alt((char('!'), char('?')))), tws0))(i)?;
+    let status = match mark {
+        Some('!') => ShapeMapState::Fails,
+        Some(_) => ShapeMapState::Pending,
+        None => ShapeMapState::Conforms,
+    };
+    Ok((i, status))
+}
+
+/// `result_node ::= iri | blank_node | result_literal`
+fn result_node(i: Span) -> IRes<ResultNode> {
+    alt((
+        map(iri, ResultNode::Iri),
+        map(blank_node, ResultNode::BNode),
+        map(result_literal, ResultNode::Literal),
+    ))(i)
+}
+
+/// `result_literal ::= string (LANGTAG | "^^" iri)? | numericLiteral | booleanLiteral`
+///
+/// Language tags are only read when they are followed by the `@` of the shape,
+/// so `"x"@START` is the literal `"x"` associated with the start shape
+fn result_literal(i: Span) -> IRes<Literal> {
+    alt((
+        result_rdf_literal,
+        map(numeric_literal, Literal::NumericLiteral),
+        boolean_literal,
+    ))(i)
+}
+
+fn result_rdf_literal(i: Span) -> IRes<Literal> {
+    let (i, str) = string()(i)?;
+    let (i, value) = opt(alt((
+        map(terminated(lang_tag, peek(token_tws("@"))), |lang| {
+            Literal::lang_str(&str, lang)
+        }),
+        map(preceded(token("^^"), iri), |datatype| {
+            Literal::datatype(&str, &datatype)
+        }),
+    )))(i)?;
+    Ok((i, value.unwrap_or_else(|| Literal::str(&str))))
+}
+
 pub(crate) fn shape_spec<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, ShapeSelector> {
     traced(
         "shape_spec",
@@ -75,10 +182,14 @@ pub(crate) fn shape_spec<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, ShapeSelector
     )
 }
 
+/// `node_spec ::= triple_pattern | extended | object_term`
 pub(crate) fn node_spec<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, NodeSelector> {
     traced(
         "node_spec",
-        map_error(move |i| subject_term(i), || ParseError::ExpectedNodeSpec),
+        map_error(
+            move |i| alt((triple_pattern, extended, subject_term))(i),
+            || ParseError::ExpectedNodeSpec,
+        ),
     )
 }
 
@@ -87,6 +198,141 @@ fn subject_term(i: Span) -> IRes<NodeSelector> {
     Ok((i, NodeSelector::iri_ref(iri)))
 }
 
+/// `triple_pattern ::= "{" "FOCUS" path (object_term | "_") "}"`
+/// `                 | "{" (subject_term | "_") path "FOCUS" "}"`
+fn triple_pattern(i: Span) -> IRes<NodeSelector> {
+    let (i, (subject, path, object)) = map_error(
+        delimited(
+            token_tws("{"),
+            alt((
+                tuple((focus, path, alt((wildcard, object_term)))),
+                tuple((alt((wildcard, object_term)), path, focus)),
+            )),
+            token_tws("}"),
+        ),
+        || ParseError::ExpectedTriplePattern,
+    )(i)?;
+    Ok((i, make_triple_pattern(subject, path, object)))
+}
+
+// Triple patterns with a single predicate don't need a property path
+fn make_triple_pattern(subject: Pattern, path: PropertyPath, object: Pattern) -> NodeSelector {
+    match path {
+        PropertyPath::Predicate(pred) => NodeSelector::TriplePattern {
+            subject,
+            pred,
+            object,
+        },
+        path => NodeSelector::TriplePatternPath {
+            subject,
+            pred: path,
+            object,
+        },
+    }
+}
+
+fn focus(i: Span) -> IRes<Pattern> {
+    map(tag_no_case_tws("FOCUS"), |_| Pattern::Focus)(i)
+}
+
+fn wildcard(i: Span) -> IRes<Pattern> {
+    map(token_tws("_"), |_| Pattern::Wildcard)(i)
+}
+
+fn object_term(i: Span) -> IRes<Pattern> {
+    let (i, (value, _)) = tuple((iri_or_literal(), tws0))(i)?;
+    Ok((i, Pattern::Node(value)))
+}
+
+/// `extended ::= "SPARQL" string | IRIREF string`
+fn extended(i: Span) -> IRes<NodeSelector> {
+    alt((
+        map(
+            tuple((tag_no_case_tws("SPARQL"), string())),
+            |(_, query)| NodeSelector::Sparql { query },
+        ),
+        generic,
+    ))(i)
+}
+
+fn generic(i: Span) -> IRes<NodeSelector> {
+    let (i, (iri, _, param)) = tuple((iri_ref, tws0, string()))(i)?;
+    Ok((i, NodeSelector::Generic { iri, param }))
+}
+
+/// Property paths follow the syntax of [SPARQL property paths](https://www.w3.org/TR/sparql11-query/#pp-language)
+///
+/// `path ::= path_sequence ("|" path_sequence)*`
+pub(crate) fn path(i: Span) -> IRes<PropertyPath> {
+    traced(
+        "path",
+        map_error(
+            move |i| {
+                let (i, (first, rest)) =
+                    tuple((path_sequence, many0(tuple((token_tws("|"), path_sequence)))))(i)?;
+                Ok((i, make_path(first, rest, PropertyPath::Alternative)))
+            },
+            || ParseError::ExpectedPath,
+        ),
+    )(i)
+}
+
+/// `path_sequence ::= path_elt_or_inverse ("/" path_elt_or_inverse)*`
+fn path_sequence(i: Span) -> IRes<PropertyPath> {
+    let (i, (first, rest)) = tuple((
+        path_elt_or_inverse,
+        many0(tuple((token_tws("/"), path_elt_or_inverse))),
+    ))(i)?;
+    Ok((i, make_path(first, rest, PropertyPath::Sequence)))
+}
+
+fn make_path<T>(
+    first: PropertyPath,
+    rest: Vec<(T, PropertyPath)>,
+    make: impl FnOnce(Vec<PropertyPath>) -> PropertyPath,
+) -> PropertyPath {
+    if rest.is_empty() {
+        first
+    } else {
+        let mut paths = vec![first];
+        paths.extend(rest.into_iter().map(|(_, p)| p));
+        make(paths)
+    }
+}
+
+/// `path_elt_or_inverse ::= "^"? path_elt`
+fn path_elt_or_inverse(i: Span) -> IRes<PropertyPath> {
+    let (i, (inverse, elt)) = tuple((opt(token_tws("^")), path_elt))(i)?;
+    match inverse {
+        Some(_) => Ok((i, PropertyPath::Inverse(Box::new(elt)))),
+        None => Ok((i, elt)),
+    }
+}
+
+/// `path_elt ::= path_primary ("*" | "+" | "?")?`
+fn path_elt(i: Span) -> IRes<PropertyPath> {
+    let (i, (primary, modifier, _)) = tuple((
+        path_primary,
+        opt(alt((char('*'), char('+'), char('?')))),
+        tws0,
+    ))(i)?;
+    let path = match modifier {
+        Some('*') => PropertyPath::ZeroOrMore(Box::new(primary)),
+        Some('+') => PropertyPath::OneOrMore(Box::new(primary)),
+        Some(_) => PropertyPath::ZeroOrOne(Box::new(primary)),
+        None => primary,
+    };
+    Ok((i, path))
+}
+
+/// `path_primary ::= predicate | "(" path ")"`
+fn path_primary(i: Span) -> IRes<PropertyPath> {
+    alt((
+        map(predicate, PropertyPath::Predicate),
+        delimited(token_tws("("), path, token_tws(")")),
+    ))(i)
+}
+
 #[cfg(test)]
 mod tests {
     use shapemap::ShapeSelector;
@@ -1,22 +1,37 @@
 use crate::shapemap_grammar::shapemap_statement;
 use crate::shapemap_grammar::ShapeMapStatement;
-use crate::shapemap_grammar::{node_spec, shape_spec};
-use crate::shex_grammar::iri;
+use crate::shapemap_grammar::{node_spec, path, result_shapemap, shape_spec};
+use crate::shapemap_grammar::{ResultAssociation, ResultNode};
+use crate::shex_grammar::{iri, literal};
 use crate::tws0;
 use crate::ParseError;
 use crate::Span;
+use iri_s::IriS;
+use nom::combinator::all_consuming;
+use nom::sequence::delimited;
 use nom::Err;
+use prefixmap::Deref;
 use prefixmap::IriRef;
 use prefixmap::PrefixMap;
 use shapemap::query_shape_map::QueryShapeMap;
 use shapemap::NodeSelector;
+use shapemap::PropertyPath;
+use shapemap::ShapeMapState;
 use shapemap::ShapeSelector;
+use shex_ast::compiled::shape_label::ShapeLabel;
+use shex_ast::{Node, ShapeExprLabel};
+use srdf::literal::Literal;
+use srdf::numeric_literal::NumericLiteral;
+use srdf::{Object, XSD_BOOLEAN_STR, XSD_DECIMAL_STR, XSD_DOUBLE_STR, XSD_INTEGER_STR};
 use std::fs;
 use std::path::Path;
 use tracing::debug;
 
 type Result<A> = std::result::Result<A, ParseError>;
 
+/// Node, status, shape and reason of an association of a result ShapeMap
+pub type ResultMapEntry = (Node, ShapeMapState, ShapeLabel, Option<String>);
+
 pub struct ShapeMapParser<'a> {
     shapemap_statement_iterator: ShapeMapStatementIterator<'a>,
 }
@@ -66,6 +81,71 @@ impl<'a> ShapeMapParser<'a> {
         Ok(query_shapemap)
     }
 
+    /// Parse a result ShapeMap in compact syntax, where each association is `node@shape`,
+    /// `node@!shape` or `node@?shape` for conformant, nonconformant and pending nodes,
+    /// optionally followed by a string with the reason.
+    ///
+    /// The prefixed names of nodes and datatypes are resolved with `nodes_prefixmap`
+    /// and the prefixed names of shapes with `shapes_prefixmap`
+    pub fn parse_result_map(
+        src: &str,
+        nodes_prefixmap: &PrefixMap,
+        shapes_prefixmap: &PrefixMap,
+    ) -> Result<Vec<ResultMapEntry>> {
+        let span = Span::new(src);
+        let (_, associations) = result_shapemap()(span).map_err(|e| match e {
+            Err::Incomplete(s) => ParseError::Custom {
+                msg: format!("Incomplete input: needed {s:?}"),
+            },
+            Err::Error(e) => ParseError::NomError { err: Box::new(e) },
+            Err::Failure(f) => ParseError::NomError { err: Box::new(f) },
+        })?;
+        associations
+            .into_iter()
+            .map(|association| {
+                let ResultAssociation {
+                    node,
+                    status,
+                    shape,
+                    reason,
+                } = association;
+                let node = match node {
+                    ResultNode::Iri(iri) => Node::iri(nodes_prefixmap.resolve_iriref(&iri)?),
+                    ResultNode::BNode(bnode) => Object::bnode(bnode.as_str().to_string()).into(),
+                    ResultNode::Literal(lit) => {
+                        Object::literal(resolve_literal(&lit, nodes_prefixmap)?).into()
+                    }
+                };
+                let shape = match shape {
+                    ShapeSelector::Label(ShapeExprLabel::IriRef { value }) => {
+                        ShapeLabel::iri(shapes_prefixmap.resolve_iriref(&value)?)
+                    }
+                    ShapeSelector::Label(ShapeExprLabel::BNode { value }) => {
+                        ShapeLabel::from_bnode(value)
+                    }
+                    ShapeSelector::Label(ShapeExprLabel::Start) | ShapeSelector::Start => {
+                        ShapeLabel::Start
+                    }
+                };
+                Ok((node, status, shape, reason))
+            })
+            .collect()
+    }
+
+    /// Parse a literal like `"Alice"@en` or `"23"^^<http://www.w3.org/2001/XMLSchema#integer>`
+    pub fn parse_literal(str: &str, prefixmap: &PrefixMap) -> Result<Literal> {
+        let span = Span::new(str);
+        let (_, lit) =
+            all_consuming(delimited(tws0, literal(), tws0))(span).map_err(|e| match e {
+                Err::Incomplete(s) => ParseError::Custom {
+                    msg: format!("Incomplete input: needed {s:?}"),
+                },
+                Err::Error(e) => ParseError::NomError { err: Box::new(e) },
+                Err::Failure(f) => ParseError::NomError { err: Box::new(f) },
+            })?;
+        resolve_literal(&lit, prefixmap)
+    }
+
     pub fn parse_shape_selector(str: &str) -> Result<ShapeSelector> {
         let span = Span::new(str);
         let (_, ss) = shape_spec()(span).map_err(|e| match e {
@@ -90,6 +170,19 @@ impl<'a> ShapeMapParser<'a> {
         Ok(ns)
     }
 
+    /// Parse a [SHACL property path](https://www.w3.org/TR/shacl/#property-paths) using SPARQL path syntax
+    pub fn parse_path(str: &str) -> Result<PropertyPath> {
+        let span = Span::new(str);
+        let (_, path) = path(span).map_err(|e| match e {
+            Err::Incomplete(s) => ParseError::Custom {
+                msg: format!("Incomplete input: needed {s:?}"),
+            },
+            Err::Error(e) => ParseError::NomError { err: Box::new(e) },
+            Err::Failure(f) => ParseError::NomError { err: Box::new(f) },
+        })?;
+        Ok(path)
+    }
+
     pub fn parse_iri_ref(str: &str) -> Result<IriRef> {
         let span = Span::new(str);
         let (_, iri_ref) = iri(span).map_err(|e| match e {
@@ -103,6 +196,25 @@ impl<'a> ShapeMapParser<'a> {
     }
 }
 
+/// Literals of RDF data have the datatype IRI resolved, including numbers and booleans
+fn resolve_literal(lit: &Literal, prefixmap: &PrefixMap) -> Result<Literal> {
+    let datatype_literal = |lexical_form: &str, datatype: &str| {
+        Literal::datatype(lexical_form, &IriRef::iri(IriS::new_unchecked(datatype)))
+    };
+    match lit {
+        Literal::NumericLiteral(n) => {
+            let datatype = match n {
+                NumericLiteral::Integer(_) => XSD_INTEGER_STR,
+                NumericLiteral::Decimal(_) => XSD_DECIMAL_STR,
+                NumericLiteral::Double(_) => XSD_DOUBLE_STR,
+            };
+            Ok(datatype_literal(&n.lexical_form(), datatype))
+        }
+        Literal::BooleanLiteral(b) => Ok(datatype_literal(&b.to_string(), XSD_BOOLEAN_STR)),
+        _ => Ok(lit.deref(&None, &Some(prefixmap.clone()))?),
+    }
+}
+
 struct ShapeMapStatementIterator<'a> {
     src: Span<'a>,
     done: bool,
@@ -166,7 +278,7 @@ impl<'a> Iterator for ShapeMapStatementIterator<'a> {
 mod tests {
 
     use iri_s::IriS;
-    use shapemap::{NodeSelector, ShapeSelector};
+    use shapemap::{NodeSelector, Pattern, PropertyPath, ShapeSelector};
 
     use super::*;
 
@@ -200,4 +312,57 @@ mod tests {
         );
         assert_eq!(parsed_shapemap, expected)
     }
+
+    #[test]
+    fn test_node_selectors() {
+        let str = r#"{FOCUS :worksFor _}@:Employee,
+                     {_ ^:knows/:name? FOCUS}@:S,
+                     SPARQL """select ?x where { ?x a :Person }"""@:Person"#;
+        let shapemap = ShapeMapParser::parse(str, &None, &None).unwrap();
+        let node_selectors: Vec<_> = shapemap.iter().map(|a| &a.node_selector).collect();
+        assert_eq!(
+            node_selectors,
+            vec![
+                &NodeSelector::TriplePattern {
+                    subject: Pattern::Focus,
+                    pred: IriRef::prefixed("", "worksFor"),
+                    object: Pattern::Wildcard
+                },
+                &NodeSelector::TriplePatternPath {
+                    subject: Pattern::Wildcard,
+                    pred: PropertyPath::Sequence(vec![
+                        PropertyPath::Inverse(Box::new(PropertyPath::Predicate(IriRef::prefixed(
+                            "", "knows"
+                        )))),
+                        PropertyPath::ZeroOrOne(Box::new(PropertyPath::Predicate(
+                            IriRef::prefixed("", "name")
+                        )))
+                    ]),
+                    object: Pattern::Focus
+                },
+                &NodeSelector::Sparql {
+                    query: "select ?x where { ?x a :Person }".to_string()
+                }
+            ]
+        )
+    }
+
+    #[test]
+    fn test_parse_path() {
+        let path = ShapeMapParser::parse_path(":knows+/:name | ^:worksFor").unwrap();
+        assert_eq!(
+            path,
+            PropertyPath::Alternative(vec![
+                PropertyPath::Sequence(vec![
+                    PropertyPath::OneOrMore(Box::new(PropertyPath::Predicate(IriRef::prefixed(
+                        "", "knows"
+                    )))),
+                    PropertyPath::Predicate(IriRef::prefixed("", "name"))
+                ]),
+                PropertyPath::Inverse(Box::new(PropertyPath::Predicate(IriRef::prefixed(
+                    "", "worksFor"
+                ))))
+            ])
+        )
+    }
 }
@@ -0,0 +1,349 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use shex_ast::ShapeExprLabel;
+
+/// Comments attached to a shape declaration
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DeclComments {
+    /// Comments in the lines before the declaration
+    pub leading: Vec<String>,
+
+    /// Comments after the last triple constraint, before the end of the declaration
+    pub closing: Vec<String>,
+
+    /// Comments in the same line as the end of the declaration
+    pub trailing: Vec<String>,
+
+    /// The declaration was separated from the previous statement by a blank line
+    pub blank_line_before: bool,
+}
+
+/// Comments attached to a triple constraint
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TripleConstraintComments {
+    /// Comments in the lines before the triple constraint
+    pub leading: Vec<String>,
+
+    /// Comments in the same line after the triple constraint
+    pub trailing: Vec<String>,
+}
+
+/// Comments of a ShExC document attached to the declarations and triple constraints that they document
+///
+/// The triple constraints of a shape declaration are identified by their position
+/// in the declaration following the document order
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ShExComments {
+    header: Vec<String>,
+    start: Vec<String>,
+    shape_decls: HashMap<ShapeExprLabel, DeclComments>,
+    triple_constraints: HashMap<(ShapeExprLabel, usize), TripleConstraintComments>,
+    footer: Vec<String>,
+}
+
+/// Kind of a statement of a ShExC document
+#[derive(Debug, Clone, PartialEq)]
+pub enum StatementKind {
+    Directive,
+    Start,
+    ShapeDecl(ShapeExprLabel),
+}
+
+/// Statement found by the parser between the byte offsets `start` and `end`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatementSpan {
+    pub start: usize,
+    pub end: usize,
+    pub kind: StatementKind,
+}
+
+impl ShExComments {
+    pub fn new() -> ShExComments {
+        ShExComments::default()
+    }
+
+    /// Comments before the first shape declaration or between directives
+    pub fn header(&self) -> &Vec<String> {
+        &self.header
+    }
+
+    /// Comments attached to the `start` declaration
+    pub fn start(&self) -> &Vec<String> {
+        &self.start
+    }
+
+    /// Comments after the last statement
+    pub fn footer(&self) -> &Vec<String> {
+        &self.footer
+    }
+
+    pub fn shape_decl(&self, label: &ShapeExprLabel) -> Option<&DeclComments> {
+        self.shape_decls.get(label)
+    }
+
+    /// Comments of the triple constraint at position `index` in the shape declaration `label`
+    pub fn triple_constraint(
+        &self,
+        label: &ShapeExprLabel,
+        index: usize,
+    ) -> Option<&TripleConstraintComments> {
+        self.triple_constraints.get(&(label.clone(), index))
+    }
+
+    /// Checks if there are comments inside the shape declaration `label`
+    pub fn has_inner_comments(&self, label: &ShapeExprLabel) -> bool {
+        self.triple_constraints.keys().any(|(l, _)| l == label)
+            || self
+                .shape_decls
+                .get(label)
+                .map(|c| !c.closing.is_empty())
+                .unwrap_or(false)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.header.is_empty()
+            && self.start.is_empty()
+            && self.footer.is_empty()
+            && self.shape_decls.values().all(|c| {
+                c.leading.is_empty()
+                    && c.closing.is_empty()
+                    && c.trailing.is_empty()
+                    && !c.blank_line_before
+            })
+            && self.triple_constraints.is_empty()
+    }
+
+    /// Attaches the comments found by the parser in `src` to the statements that have been parsed from it
+    pub(crate) fn from_statements(
+        src: &str,
+        statements: &[StatementSpan],
+        trivia: &Trivia,
+    ) -> ShExComments {
+        let found: Vec<Comment> = trivia
+            .comments
+            .iter()
+            .map(|(start, end)| Comment {
+                start: *start,
+                end: *end,
+            })
+            .collect();
+        let mut comments = ShExComments::new();
+        let mut prev_end = 0;
+        let mut pending = Vec::new();
+        let mut pending_start = None;
+        let mut comment_iter = found.iter().peekable();
+        for (n, statement) in statements.iter().enumerate() {
+            let end = significant_end(src, statement.start, statement.end, &found);
+            // Comments before the statement
+            while let Some(comment) = comment_iter.next_if(|c| c.start < statement.start) {
+                if n > 0 && !has_newline(&src[prev_end..comment.start]) {
+                    comments.add_trailing(&statements[n - 1].kind, comment.text(src));
+                } else {
+                    pending_start.get_or_insert(comment.start);
+                    pending.push(comment.text(src));
+                }
+            }
+            let gap_end = pending_start.take().unwrap_or(statement.start);
+            let blank_line_before = n > 0 && has_blank_line(&src[prev_end..gap_end]);
+            comments.add_leading(
+                &statement.kind,
+                std::mem::take(&mut pending),
+                blank_line_before,
+            );
+
+            // Comments inside the statement
+            let tc_starts: Vec<usize> = trivia
+                .triple_constraints
+                .range(statement.start..end)
+                .copied()
+                .collect();
+            while let Some(comment) = comment_iter.next_if(|c| c.start < end) {
+                let text = comment.text(src);
+                match &statement.kind {
+                    StatementKind::ShapeDecl(label) => {
+                        let before = tc_starts.iter().filter(|pos| **pos < comment.start).count();
+                        let line_start = src[..comment.start]
+                            .rfind('\n')
+                            .map(|pos| pos + 1)
+                            .unwrap_or(0);
+                        let same_line = !src[line_start..comment.start].trim().is_empty();
+                        if same_line && before > 0 {
+                            comments
+                                .triple_constraints
+                                .entry((label.clone(), before - 1))
+                                .or_default()
+                                .trailing
+                                .push(text)
+                        } else if before < tc_starts.len() {
+                            comments
+                                .triple_constraints
+                                .entry((label.clone(), before))
+                                .or_default()
+                                .leading
+                                .push(text)
+                        } else {
+                            comments
+                                .shape_decls
+                                .entry(label.clone())
+                                .or_default()
+                                .closing
+                                .push(text)
+                        }
+                    }
+                    StatementKind::Start => comments.start.push(text),
+                    StatementKind::Directive => comments.header.push(text),
+                }
+            }
+            prev_end = end;
+        }
+        for comment in comment_iter {
+            if !statements.is_empty() && !has_newline(&src[prev_end..comment.start]) {
+                comments.add_trailing(&statements[statements.len() - 1].kind, comment.text(src));
+            } else {
+                comments.footer.push(comment.text(src))
+            }
+        }
+        comments
+    }
+
+    fn add_leading(&mut self, kind: &StatementKind, leading: Vec<String>, blank_line_before: bool) {
+        match kind {
+            StatementKind::Directive => self.header.extend(leading),
+            StatementKind::Start => self.start.extend(leading),
+            StatementKind::ShapeDecl(label) => {
+                let decl = self.shape_decls.entry(label.clone()).or_default();
+                decl.leading.extend(leading);
+                decl.blank_line_before = blank_line_before;
+            }
+        }
+    }
+
+    fn add_trailing(&mut self, kind: &StatementKind, comment: String) {
+        match kind {
+            StatementKind::Directive => self.header.push(comment),
+            StatementKind::Start => self.start.push(comment),
+            StatementKind::ShapeDecl(label) => self
+                .shape_decls
+                .entry(label.clone())
+                .or_default()
+                .trailing
+                .push(comment),
+        }
+    }
+}
+
+fn has_newline(str: &str) -> bool {
+    str.contains('\n')
+}
+
+fn has_blank_line(str: &str) -> bool {
+    str.split('\n')
+        .skip(1)
+        .collect::<Vec<_>>()
+        .split_last()
+        .map(|(_, middle)| middle.iter().any(|line| line.trim().is_empty()))
+        .unwrap_or(false)
+}
+
+// The parser consumes the whitespace and comments after a statement, so the end
+// of a statement is moved back to its last significant character
+fn significant_end(src: &str, start: usize, end: usize, comments: &[Comment]) -> usize {
+    let mut end = end;
+    loop {
+        let trimmed = src[start..end].trim_end().len() + start;
+        match comments
+            .iter()
+            .find(|c| c.end == trimmed && c.start >= start)
+        {
+            Some(comment) => end = comment.start,
+            None => return trimmed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Comment {
+    start: usize,
+    end: usize,
+}
+
+impl Comment {
+    fn text(&self, src: &str) -> String {
+        src[self.start..self.end].to_string()
+    }
+}
+
+/// Comments and triple constraints recognised by the grammar while a document is parsed
+///
+/// The parser backtracks, so the same element can be recognised several times.
+/// They are identified by their byte offsets to keep only one of them
+#[derive(Debug, Default)]
+pub(crate) struct Trivia {
+    /// Start and end of each comment
+    comments: BTreeMap<usize, usize>,
+
+    /// Start of each triple constraint
+    triple_constraints: BTreeSet<usize>,
+}
+
+thread_local! {
+    static TRIVIA: RefCell<Option<Trivia>> = const { RefCell::new(None) };
+}
+
+/// Runs `parse` collecting the comments and triple constraints recognised by the grammar
+pub(crate) fn with_trivia<T>(parse: impl FnOnce() -> T) -> (T, Trivia) {
+    TRIVIA.with(|trivia| trivia.replace(Some(Trivia::default())));
+    let result = parse();
+    let trivia = TRIVIA.with(|trivia| trivia.take()).unwrap_or_default();
+    (result, trivia)
+}
+
+/// Called by the grammar when it recognises a comment
+pub(crate) fn record_comment(start: usize, end: usize) {
+    TRIVIA.with(|trivia| {
+        if let Some(trivia) = trivia.borrow_mut().as_mut() {
+            trivia.comments.insert(start, end);
+        }
+    })
+}
+
+/// Called by the grammar when it recognises a triple constraint
+pub(crate) fn record_triple_constraint(start: usize) {
+    TRIVIA.with(|trivia| {
+        if let Some(trivia) = trivia.borrow_mut().as_mut() {
+            trivia.triple_constraints.insert(start);
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ShExParser;
+
+    #[test]
+    fn comments_are_recognised_by_the_grammar() {
+        let src = r#"prefix : <http://example.org/#>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:S { # c1
+  :p xsd:string {1,2} ; # c2
+  $<l> ^:q /a#b/ /* c3 */ |
+  ( :r [ "a;b#" ] ; :s { a . } ) %:act{ # code %}
+}"#;
+        let (_, comments) = ShExParser::parse_with_comments(src, None).unwrap();
+        let label = ShapeExprLabel::iri_unchecked("http://example.org/#S");
+        let first = comments.triple_constraint(&label, 0).unwrap();
+        assert_eq!(first.leading, vec!["# c1"]);
+        assert_eq!(first.trailing, vec!["# c2"]);
+        let second = comments.triple_constraint(&label, 1).unwrap();
+        assert!(second.leading.is_empty());
+        assert_eq!(second.trailing, vec!["/* c3 */"]);
+        for index in 2..5 {
+            assert_eq!(comments.triple_constraint(&label, index), None)
+        }
+        assert!(comments.header().is_empty());
+        assert!(comments.footer().is_empty());
+        assert!(comments.shape_decl(&label).unwrap().closing.is_empty());
+    }
+}
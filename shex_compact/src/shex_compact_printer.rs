@@ -5,14 +5,19 @@ use pretty::{Arena, DocAllocator, DocBuilder, RefDoc};
 use rust_decimal::Decimal;
 /// This file converts ShEx AST to ShEx compact syntax
 use shex_ast::{
-    value_set_value::ValueSetValue, Annotation, BNode, NodeConstraint, NodeKind, NumericFacet,
+    value_set_value::ValueSetValue, Annotation, BNode, IriExclusion, IriRefOrWildcard,
+    LangOrWildcard, LanguageExclusion, LiteralExclusion, NodeConstraint, NodeKind, NumericFacet,
     ObjectValue, Pattern, Schema, SemAct, Shape, ShapeDecl, ShapeExpr, ShapeExprLabel, StringFacet,
-    TripleExpr, XsFacet,
+    StringOrWildcard, TripleExpr, TripleExprLabel, XsFacet,
 };
 use srdf::{lang::Lang, literal::Literal, numeric_literal::NumericLiteral};
-use std::{borrow::Cow, marker::PhantomData};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+};
 
-use crate::pp_object_value;
+use crate::{escape_string, ShExComments, ShExFormatterError, TripleConstraintComments};
 
 /// Struct that can be used to pretty print ShEx schemas
 ///
@@ -29,10 +34,12 @@ use crate::pp_object_value;
 /// let expected = r#"prefix ex: <http://example.org/>
 /// ex:S {  }"#;
 ///
-/// assert_eq!(ShExFormatter::default().format_schema(&schema), expected);
+/// assert_eq!(ShExFormatter::default().format_schema(&schema).unwrap(), expected);
 /// ```
 #[derive(Debug, Clone)]
 pub struct ShExFormatter {
+    width: usize,
+    indent: isize,
     keyword_color: Option<Color>,
     string_color: Option<Color>,
     prefix_color: Option<Color>,
@@ -57,6 +64,18 @@ impl ShExFormatter {
         self.localname_color
     }
 
+    /// Maximum line width. Groups that don't fit are split in several lines
+    pub fn with_width(mut self, width: usize) -> ShExFormatter {
+        self.width = width;
+        self
+    }
+
+    /// Number of spaces used to indent nested elements
+    pub fn with_indent(mut self, indent: usize) -> ShExFormatter {
+        self.indent = indent as isize;
+        self
+    }
+
     pub fn with_keyword_color(mut self, color: Option<Color>) -> ShExFormatter {
         self.keyword_color = color;
         self
@@ -89,9 +108,50 @@ impl ShExFormatter {
             .with_semicolon_color(None)
     }
 
-    pub fn format_schema(&self, schema: &Schema) -> String {
+    /// Formats a schema in ShExC
+    ///
+    /// Fails if the schema contains values that can't be written in ShExC,
+    /// like wildcards without exclusions in value sets
+    pub fn format_schema(&self, schema: &Schema) -> Result<String, ShExFormatterError> {
+        self.format(schema, None)
+    }
+
+    /// Formats a schema emitting the comments obtained by [`ShExParser::parse_with_comments`](crate::ShExParser::parse_with_comments)
+    ///
+    /// ```
+    /// use shex_compact::{ShExFormatter, ShExParser};
+    ///
+    /// let src = r#"prefix : <http://example.org/>
+    /// ## A person
+    /// :Person { :name . # Full name
+    /// }"#;
+    /// let (schema, comments) = ShExParser::parse_with_comments(src, None).unwrap();
+    /// let expected = r#"prefix : <http://example.org/>
+    /// ## A person
+    /// :Person {
+    ///   :name . # Full name
+    /// }"#;
+    /// let formatter = ShExFormatter::default().without_colors().with_indent(2);
+    /// assert_eq!(formatter.format_schema_with_comments(&schema, &comments).unwrap(), expected);
+    /// ```
+    pub fn format_schema_with_comments(
+        &self,
+        schema: &Schema,
+        comments: &ShExComments,
+    ) -> Result<String, ShExFormatterError> {
+        self.format(schema, Some(comments))
+    }
+
+    fn format(
+        &self,
+        schema: &Schema,
+        comments: Option<&ShExComments>,
+    ) -> Result<String, ShExFormatterError> {
         let arena = Arena::<()>::new();
         let mut printer = ShExCompactPrinter::new(schema, &arena);
+        printer.width = self.width;
+        printer.indent = self.indent;
+        printer.comments = comments;
         printer = printer.with_keyword_color(self.keyword_color);
         printer = printer.with_string_color(self.string_color);
         printer = printer.with_qualify_localname_color(self.localname_color);
@@ -104,6 +164,8 @@ impl ShExFormatter {
 impl Default for ShExFormatter {
     fn default() -> Self {
         Self {
+            width: DEFAULT_WIDTH,
+            indent: DEFAULT_INDENT,
             keyword_color: DEFAULT_KEYWORD_COLOR,
             prefix_color: DEFAULT_QUALIFY_ALIAS_COLOR,
             semicolon_color: DEFAULT_QUALIFY_SEMICOLON_COLOR,
@@ -125,6 +187,26 @@ where
     doc: &'a Arena<'a, A>,
     marker: PhantomData<A>,
     prefixmap: PrefixMap,
+    comments: Option<&'a ShExComments>,
+
+    // State used to attach the comments to the triple constraints of the current shape declaration
+    current_decl: RefCell<Option<ShapeExprLabel>>,
+    tc_counter: Cell<usize>,
+    force_break: Cell<bool>,
+    pending_trailing: RefCell<Vec<String>>,
+    closing_comments: RefCell<Vec<String>>,
+    shape_depth: Cell<usize>,
+
+    // First element found that can't be written in ShExC
+    error: RefCell<Option<ShExFormatterError>>,
+}
+
+// Position of a triple expression, used to decide if it must be enclosed in parentheses
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TripleExprPosition {
+    Shape,
+    EachOf,
+    OneOf,
 }
 
 const DEFAULT_WIDTH: usize = 100;
@@ -154,6 +236,14 @@ where
                 .with_qualify_localname_color(DEFAULT_QUALIFY_LOCALNAME_COLOR)
                 .with_qualify_prefix_color(DEFAULT_QUALIFY_ALIAS_COLOR)
                 .with_qualify_semicolon_color(DEFAULT_QUALIFY_SEMICOLON_COLOR),
+            comments: None,
+            current_decl: RefCell::new(None),
+            tc_counter: Cell::new(0),
+            force_break: Cell::new(false),
+            pending_trailing: RefCell::new(Vec::new()),
+            closing_comments: RefCell::new(Vec::new()),
+            shape_depth: Cell::new(0),
+            error: RefCell::new(None),
         }
     }
 
@@ -182,17 +272,83 @@ where
         self
     }
 
-    pub fn pretty_print(&self) -> String {
+    pub fn pretty_print(&self) -> Result<String, ShExFormatterError> {
         let doc = self.pp_schema();
-        doc.pretty(self.width).to_string()
+        match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(doc.pretty(self.width).to_string()),
+        }
+    }
+
+    fn add_error(&self, err: ShExFormatterError) {
+        self.error.borrow_mut().get_or_insert(err);
     }
 
     fn pp_schema(&self) -> DocBuilder<'a, Arena<'a, A>, A> {
-        self.opt_pp(self.schema.prefixmap(), self.pp_prefix_map())
+        let (header, footer) = match self.comments {
+            Some(comments) => (
+                self.pp_leading_comments(comments.header()),
+                self.pp_footer(comments.footer()),
+            ),
+            None => (self.doc.nil(), self.doc.nil()),
+        };
+        header
+            .append(self.opt_pp(self.schema.prefixmap(), self.pp_prefix_map()))
             .append(self.opt_pp(self.schema.base(), self.pp_base()))
             .append(self.opt_pp(self.schema.start_actions(), self.pp_actions()))
             .append(self.opt_pp(self.schema.start(), self.pp_start()))
             .append(self.opt_pp(self.schema.shapes(), self.pp_shape_decls()))
+            .append(footer)
+    }
+
+    // Each comment is emitted in its own line
+    fn pp_leading_comments(&self, comments: &[String]) -> DocBuilder<'a, Arena<'a, A>, A> {
+        let mut doc = self.doc.nil();
+        for comment in comments {
+            doc = doc
+                .append(self.doc.text(comment.clone()))
+                .append(self.doc.hardline())
+        }
+        doc
+    }
+
+    fn pp_trailing_comments(&self, comments: &[String]) -> DocBuilder<'a, Arena<'a, A>, A> {
+        let mut docs = Vec::new();
+        for comment in comments {
+            docs.push(self.doc.text(comment.clone()))
+        }
+        if docs.is_empty() {
+            self.doc.nil()
+        } else {
+            self.doc
+                .space()
+                .append(self.doc.intersperse(docs, self.doc.hardline()))
+        }
+    }
+
+    fn pp_footer(&self, comments: &[String]) -> DocBuilder<'a, Arena<'a, A>, A> {
+        let mut doc = self.doc.nil();
+        for comment in comments {
+            doc = doc
+                .append(self.doc.hardline())
+                .append(self.doc.text(comment.clone()))
+        }
+        doc
+    }
+
+    // Emits the trailing comments of the last triple constraints after their separator
+    fn flush_trailing_comments(&self) -> DocBuilder<'a, Arena<'a, A>, A> {
+        let pending = self.pending_trailing.take();
+        self.pp_trailing_comments(&pending)
+    }
+
+    // A line break that is always emitted in shapes with commented triple constraints
+    fn line(&self) -> DocBuilder<'a, Arena<'a, A>, A> {
+        if self.force_break.get() {
+            self.doc.hardline()
+        } else {
+            self.doc.line()
+        }
     }
 
     fn pp_shape_decls(
@@ -209,17 +365,50 @@ where
     }
 
     fn pp_shape_decl(&self, sd: &ShapeDecl) -> DocBuilder<'a, Arena<'a, A>, A> {
-        self.pp_label(&sd.id)
+        let decl_comments = self.comments.and_then(|c| c.shape_decl(&sd.id));
+        self.current_decl.replace(Some(sd.id.clone()));
+        self.tc_counter.set(0);
+        self.force_break.set(
+            self.comments
+                .map(|c| c.has_inner_comments(&sd.id))
+                .unwrap_or(false),
+        );
+        let (blank_line, leading, trailing) = match decl_comments {
+            Some(dc) => {
+                self.closing_comments.replace(dc.closing.clone());
+                (
+                    if dc.blank_line_before {
+                        self.doc.hardline()
+                    } else {
+                        self.doc.nil()
+                    },
+                    self.pp_leading_comments(&dc.leading),
+                    self.pp_trailing_comments(&dc.trailing),
+                )
+            }
+            None => (self.doc.nil(), self.doc.nil(), self.doc.nil()),
+        };
+        let doc = blank_line
+            .append(leading)
+            .append(self.pp_label(&sd.id))
             .append(self.space())
             .append(self.pp_shape_expr(&sd.shape_expr))
+            .append(trailing);
+        self.current_decl.replace(None);
+        self.force_break.set(false);
+        doc
     }
 
     fn pp_start(
         &self,
     ) -> impl Fn(&ShapeExpr, &ShExCompactPrinter<'a, A>) -> DocBuilder<'a, Arena<'a, A>, A> {
         move |se, printer| {
-            printer
-                .keyword("start")
+            let comments = match printer.comments {
+                Some(comments) => printer.pp_leading_comments(comments.start()),
+                None => printer.doc.nil(),
+            };
+            comments
+                .append(printer.keyword("start"))
                 .append(printer.space())
                 .append("=")
                 .append(printer.space())
@@ -275,14 +464,31 @@ where
         let extra = self.opt_pp1(&s.extra, self.pp_extra());
         let extends = self.opt_pp1(&s.extends, self.pp_extends());
         let annotations = self.opt_pp1(&s.annotations, self.pp_annotations());
+        self.shape_depth.set(self.shape_depth.get() + 1);
+        let triple_expr = self.opt_pp(s.triple_expr(), self.pp_triple_expr());
+        self.shape_depth.set(self.shape_depth.get() - 1);
+        let closing = if self.shape_depth.get() == 0 {
+            let comments = self.closing_comments.take();
+            let mut doc = self.doc.nil();
+            for comment in comments {
+                doc = doc
+                    .append(self.doc.hardline())
+                    .append(self.doc.text(comment))
+            }
+            doc
+        } else {
+            self.doc.nil()
+        };
         closed
             .append(extra)
             .append(extends)
             .append(self.doc.text("{"))
-            .append(self.doc.line())
-            .append(self.opt_pp(s.triple_expr(), self.pp_triple_expr()))
+            .append(self.line())
+            .append(triple_expr)
+            .append(self.flush_trailing_comments())
+            .append(closing)
             .nest(self.indent)
-            .append(self.doc.line())
+            .append(self.line())
             .append(self.doc.text("}"))
             .append(annotations)
             .group()
@@ -328,19 +534,75 @@ where
         &self,
     ) -> impl Fn(&TripleExpr, &ShExCompactPrinter<'a, A>) -> DocBuilder<'a, Arena<'a, A>, A> + '_
     {
-        move |te, printer| match te {
-            TripleExpr::EachOf { expressions, .. } => {
+        move |te, printer| printer.pp_nested_triple_expr(te, TripleExprPosition::Shape)
+    }
+
+    fn pp_nested_triple_expr(
+        &self,
+        te: &TripleExpr,
+        position: TripleExprPosition,
+    ) -> DocBuilder<'a, Arena<'a, A>, A> {
+        match te {
+            TripleExpr::EachOf {
+                id,
+                expressions,
+                min,
+                max,
+                sem_acts,
+                annotations,
+            } => {
+                let last = expressions.len().saturating_sub(1);
                 let mut docs = Vec::new();
-                for e in expressions {
-                    let pp_te = printer.pp_triple_expr()(&e.te, printer);
+                for (n, e) in expressions.iter().enumerate() {
+                    let mut pp_te = self.pp_nested_triple_expr(&e.te, TripleExprPosition::EachOf);
+                    if n < last {
+                        pp_te = pp_te
+                            .append(self.doc.text(";"))
+                            .append(self.flush_trailing_comments())
+                    }
                     docs.push(pp_te)
                 }
-                printer
-                    .doc
-                    .intersperse(docs, printer.doc.text(";").append(printer.doc.line()))
+                let body = self.doc.intersperse(docs, self.line());
+                // Nested groups need parentheses to keep their structure
+                let bracketed = position == TripleExprPosition::EachOf
+                    || id.is_some()
+                    || min.is_some()
+                    || max.is_some()
+                    || sem_acts.is_some()
+                    || annotations.is_some();
+                self.pp_bracketed_triple_expr(id, body, bracketed, min, max, sem_acts, annotations)
+            }
+            TripleExpr::OneOf {
+                id,
+                expressions,
+                min,
+                max,
+                sem_acts,
+                annotations,
+            } => {
+                let last = expressions.len().saturating_sub(1);
+                let mut docs = Vec::new();
+                for (n, e) in expressions.iter().enumerate() {
+                    let mut pp_te = self.pp_nested_triple_expr(&e.te, TripleExprPosition::OneOf);
+                    if n < last {
+                        pp_te = pp_te
+                            .append(self.doc.text(" |"))
+                            .append(self.flush_trailing_comments())
+                    }
+                    docs.push(pp_te)
+                }
+                let body = self.doc.intersperse(docs, self.line());
+                // Groups bind tighter than one-of expressions, so only nested one-of expressions need parentheses
+                let bracketed = position != TripleExprPosition::Shape
+                    || id.is_some()
+                    || min.is_some()
+                    || max.is_some()
+                    || sem_acts.is_some()
+                    || annotations.is_some();
+                self.pp_bracketed_triple_expr(id, body, bracketed, min, max, sem_acts, annotations)
             }
-            TripleExpr::OneOf { .. } => todo!(),
             TripleExpr::TripleConstraint {
+                id,
                 negated,
                 inverse,
                 predicate,
@@ -349,15 +611,23 @@ where
                 max,
                 sem_acts,
                 annotations,
-                ..
             } => {
+                let tc_comments = self.triple_constraint_comments();
                 let doc_expr = match value_expr {
-                    Some(se) => printer.pp_shape_expr(se),
-                    None => printer.doc.text("."),
+                    Some(se) => self.pp_shape_expr(se),
+                    None => self.doc.text("."),
                 };
-                printer
-                    .doc
-                    .nil()
+                let leading = match tc_comments {
+                    Some(c) => {
+                        self.pending_trailing
+                            .borrow_mut()
+                            .extend(c.trailing.iter().cloned());
+                        self.pp_leading_comments(&c.leading)
+                    }
+                    None => self.doc.nil(),
+                };
+                leading
+                    .append(self.pp_triple_expr_id(id))
                     .append(self.pp_negated(negated))
                     .append(self.pp_inverse(inverse))
                     .append(self.pp_iri_ref(predicate))
@@ -367,12 +637,66 @@ where
                     .append(self.opt_pp1(sem_acts, self.pp_actions()))
                     .append(self.opt_pp1(annotations, self.pp_annotations()))
             }
-            TripleExpr::TripleExprRef(_) => todo!(),
+            TripleExpr::TripleExprRef(label) => {
+                self.doc.text("&").append(self.pp_triple_expr_label(label))
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn pp_bracketed_triple_expr(
+        &self,
+        id: &Option<TripleExprLabel>,
+        body: DocBuilder<'a, Arena<'a, A>, A>,
+        bracketed: bool,
+        min: &Option<i32>,
+        max: &Option<i32>,
+        sem_acts: &Option<Vec<SemAct>>,
+        annotations: &Option<Vec<Annotation>>,
+    ) -> DocBuilder<'a, Arena<'a, A>, A> {
+        if !bracketed {
+            return body;
+        }
+        self.pp_triple_expr_id(id)
+            .append(self.doc.text("("))
+            .append(self.line().append(body).nest(self.indent))
+            .append(self.line())
+            .append(self.doc.text(")"))
+            .group()
+            .append(self.pp_cardinality(min, max))
+            .append(self.opt_pp1(sem_acts, self.pp_actions()))
+            .append(self.opt_pp1(annotations, self.pp_annotations()))
+    }
+
+    fn pp_triple_expr_id(&self, id: &Option<TripleExprLabel>) -> DocBuilder<'a, Arena<'a, A>, A> {
+        match id {
+            Some(label) => self
+                .doc
+                .text("$")
+                .append(self.pp_triple_expr_label(label))
+                .append(self.doc.space()),
+            None => self.doc.nil(),
+        }
+    }
+
+    fn pp_triple_expr_label(&self, label: &TripleExprLabel) -> DocBuilder<'a, Arena<'a, A>, A> {
+        match label {
+            TripleExprLabel::IriRef { value } => self.pp_iri_ref(value),
+            TripleExprLabel::BNode { value } => self.pp_bnode(value),
         }
     }
 
     // type DB<'a, A> = DocBuilder<'a, Arena<'a, A>, A>;
 
+    // Comments of the next triple constraint in the current shape declaration
+    fn triple_constraint_comments(&self) -> Option<&'a TripleConstraintComments> {
+        let index = self.tc_counter.get();
+        self.tc_counter.set(index + 1);
+        let comments = self.comments?;
+        let label = self.current_decl.borrow();
+        comments.triple_constraint(label.as_ref()?, index)
+    }
+
     fn pp_negated(&self, negated: &Option<bool>) -> DocBuilder<'a, Arena<'a, A>, A> {
         match negated {
             Some(true) => self.doc.text("!"),
@@ -398,16 +722,10 @@ where
             (Some(0), Some(-1)) => self.doc.space().append(self.doc.text("*")),
             (Some(1), Some(-1)) => self.doc.space().append(self.doc.text("+")),
             (Some(1), None) => self.doc.space().append(self.doc.text("+")),
-            (Some(m), Some(n)) => self.doc.space().append(
-                self.enclose_space(
-                    "{",
-                    self.doc
-                        .text(m.to_string())
-                        .append(self.doc.text(","))
-                        .append(self.doc.text(n.to_string())),
-                    "}",
-                ),
-            ),
+            (Some(m), Some(n)) => self
+                .doc
+                .space()
+                .append(self.doc.text(format!("{{{m},{n}}}"))),
             (Some(m), None) => self
                 .doc
                 .space()
@@ -464,11 +782,15 @@ where
                 self.pp_string_literal(lexical_form, lang)
             }
             Literal::DatatypeLiteral {
-                lexical_form: _,
-                datatype: _,
-            } => todo!(),
+                lexical_form,
+                datatype,
+            } => self
+                .pp_string(lexical_form)
+                .append(self.doc.text("^^"))
+                .append(self.pp_iri_ref(datatype)),
             Literal::NumericLiteral(lit) => self.pp_numeric_literal(lit),
-            Literal::BooleanLiteral(_) => todo!(),
+            Literal::BooleanLiteral(true) => self.keyword("true"),
+            Literal::BooleanLiteral(false) => self.keyword("false"),
         }
     }
 
@@ -478,13 +800,15 @@ where
         lang: &Option<Lang>,
     ) -> DocBuilder<'a, Arena<'a, A>, A> {
         match lang {
-            Some(_) => todo!(),
+            Some(lang) => self
+                .pp_string(lexical_form)
+                .append(self.doc.text(lang.to_string())),
             None => self.pp_string(lexical_form),
         }
     }
 
     fn pp_string(&self, str: &str) -> DocBuilder<'a, Arena<'a, A>, A> {
-        let s = format!("\"{str}\"");
+        let s = format!("\"{}\"", escape_string(str));
         if let Some(color) = self.string_color {
             self.doc.text(s.as_str().color(color).to_string())
         } else {
@@ -493,10 +817,15 @@ where
     }
 
     fn pp_node_constraint(&self, nc: &NodeConstraint) -> DocBuilder<'a, Arena<'a, A>, A> {
-        self.opt_pp(nc.node_kind(), self.pp_node_kind())
-            .append(self.opt_pp(nc.datatype(), self.pp_datatype()))
-            .append(self.opt_pp(nc.values(), self.pp_value_set()))
-            .append(self.opt_pp(nc.xs_facet(), self.pp_xsfacets()))
+        let docs = [
+            self.opt_pp(nc.node_kind(), self.pp_node_kind()),
+            self.opt_pp(nc.datatype(), self.pp_datatype()),
+            self.opt_pp(nc.values(), self.pp_value_set()),
+            self.opt_pp(nc.xs_facet(), self.pp_xsfacets()),
+        ]
+        .into_iter()
+        .filter(|doc| !self.is_empty(doc));
+        self.doc.intersperse(docs, self.doc.space())
     }
 
     fn pp_node_kind(
@@ -507,6 +836,7 @@ where
             NodeKind::BNode => printer.keyword("BNODE"),
             NodeKind::NonLiteral => printer.keyword("NONLITERAL"),
             NodeKind::Literal => printer.keyword("LITERAL"),
+            NodeKind::Triple => printer.keyword("TRIPLE"),
         }
     }
 
@@ -519,11 +849,7 @@ where
             for v in values {
                 docs.push(printer.pp_value_set_value(v))
             }
-            printer.doc.space().append(printer.enclose_space(
-                "[",
-                printer.doc.intersperse(docs, printer.doc.space()),
-                "]",
-            ))
+            printer.enclose_space("[", printer.doc.intersperse(docs, printer.doc.space()), "]")
         }
     }
 
@@ -535,10 +861,7 @@ where
             for v in xsfacets {
                 docs.push(printer.pp_xsfacet(v))
             }
-            printer
-                .doc
-                .space()
-                .append(printer.doc.intersperse(docs, printer.doc.space()))
+            printer.doc.intersperse(docs, printer.doc.space())
         }
     }
 
@@ -617,15 +940,94 @@ where
 
     fn pp_value_set_value(&self, v: &ValueSetValue) -> DocBuilder<'a, Arena<'a, A>, A> {
         match v {
-            ValueSetValue::LanguageStem { .. } => todo!(),
-            ValueSetValue::LanguageStemRange { .. } => todo!(),
-            ValueSetValue::ObjectValue(ov) => pp_object_value(ov, self.doc, &self.prefixmap),
-            ValueSetValue::IriStem { .. } => todo!(),
-            ValueSetValue::IriStemRange { .. } => todo!(),
-            ValueSetValue::LiteralStem { .. } => todo!(),
-            ValueSetValue::LiteralStemRange { .. } => todo!(),
-            ValueSetValue::Language { .. } => todo!(),
+            ValueSetValue::ObjectValue(ov) => self.pp_object_value(ov),
+            ValueSetValue::IriStem { stem } => self.pp_iri_ref(stem).append(self.doc.text("~")),
+            ValueSetValue::IriStemRange { stem, exclusions } => {
+                let mut docs = Vec::new();
+                for e in exclusions.iter().flatten() {
+                    docs.push(match e {
+                        IriExclusion::Iri(iri) => self.pp_iri_ref(iri),
+                        IriExclusion::IriStem(iri) => {
+                            self.pp_iri_ref(iri).append(self.doc.text("~"))
+                        }
+                    })
+                }
+                let stem = match stem {
+                    IriRefOrWildcard::IriRef(iri) => Some(self.pp_iri_ref(iri)),
+                    IriRefOrWildcard::Wildcard => None,
+                };
+                self.pp_stem_range(v, stem, docs)
+            }
+            ValueSetValue::LiteralStem { stem } => self.pp_string(stem).append(self.doc.text("~")),
+            ValueSetValue::LiteralStemRange { stem, exclusions } => {
+                let mut docs = Vec::new();
+                for e in exclusions.iter().flatten() {
+                    docs.push(match e {
+                        LiteralExclusion::Literal(str) => self.pp_string(str),
+                        LiteralExclusion::LiteralStem(str) => {
+                            self.pp_string(str).append(self.doc.text("~"))
+                        }
+                    })
+                }
+                let stem = match stem {
+                    StringOrWildcard::String(str) => Some(self.pp_string(str)),
+                    StringOrWildcard::Wildcard => None,
+                };
+                self.pp_stem_range(v, stem, docs)
+            }
+            ValueSetValue::Language { language_tag } => self.pp_lang(language_tag),
+            ValueSetValue::LanguageStem { stem } => self.pp_lang(stem).append(self.doc.text("~")),
+            ValueSetValue::LanguageStemRange { stem, exclusions } => {
+                let mut docs = Vec::new();
+                for e in exclusions.iter().flatten() {
+                    docs.push(match e {
+                        LanguageExclusion::Language(lang) => self.pp_lang(lang),
+                        LanguageExclusion::LanguageStem(lang) => {
+                            self.pp_lang(lang).append(self.doc.text("~"))
+                        }
+                    })
+                }
+                let stem = match stem {
+                    LangOrWildcard::Lang(lang) => Some(self.pp_lang(lang)),
+                    LangOrWildcard::Wildcard => None,
+                };
+                self.pp_stem_range(v, stem, docs)
+            }
+        }
+    }
+
+    // A stem followed by its exclusions, or a wildcard `.` when there is no stem
+    fn pp_stem_range(
+        &self,
+        value: &ValueSetValue,
+        stem: Option<DocBuilder<'a, Arena<'a, A>, A>>,
+        exclusions: Vec<DocBuilder<'a, Arena<'a, A>, A>>,
+    ) -> DocBuilder<'a, Arena<'a, A>, A> {
+        let stem = match stem {
+            Some(stem) => stem.append(self.doc.text("~")),
+            None => {
+                if exclusions.is_empty() {
+                    self.add_error(ShExFormatterError::WildcardWithoutExclusions {
+                        value: format!("{value:?}"),
+                    })
+                }
+                self.doc.text(".")
+            }
+        };
+        let mut doc = stem;
+        for e in exclusions {
+            doc = doc
+                .append(self.doc.space())
+                .append(self.doc.text("-"))
+                .append(self.doc.space())
+                .append(e)
         }
+        doc
+    }
+
+    // The empty language tag is written as `@`, so its stem is `@~`
+    fn pp_lang(&self, lang: &Lang) -> DocBuilder<'a, Arena<'a, A>, A> {
+        self.doc.text(lang.to_string())
     }
 
     fn pp_label(&self, ref_: &ShapeExprLabel) -> DocBuilder<'a, Arena<'a, A>, A> {
@@ -644,7 +1046,7 @@ where
         match value {
             NumericLiteral::Integer(n) => self.pp_isize(n),
             NumericLiteral::Decimal(d) => self.pp_decimal(d),
-            NumericLiteral::Double(d) => self.pp_double(d), // TODO: Review
+            NumericLiteral::Double(d) => self.pp_double(d),
         }
     }
 
@@ -660,8 +1062,9 @@ where
         self.doc.text(value.to_string())
     }
 
+    // Doubles are written with an exponent so they are not read back as integers or decimals
     fn pp_double(&self, value: &f64) -> DocBuilder<'a, Arena<'a, A>, A> {
-        self.doc.text(value.to_string())
+        self.doc.text(format!("{value:e}"))
     }
 
     fn pp_usize(&self, value: &usize) -> DocBuilder<'a, Arena<'a, A>, A> {
@@ -857,10 +1260,165 @@ mod tests {
         let schema = Schema::new().with_prefixmap(Some(pm));
         let s = ShExFormatter::default()
             .without_colors()
-            .format_schema(&schema);
+            .format_schema(&schema)
+            .unwrap();
         assert_eq!(
             s,
             "prefix : <http://example.org/>\nprefix schema: <https://schema.org/>\n"
         );
     }
+
+    #[test]
+    fn format_with_comments_is_stable() {
+        let src = r#"# Header
+prefix : <http://example.org/>
+
+# Start shape
+start = @:Person
+
+# A person
+:Person {
+  # Name of the person
+  :name . ; # Full name
+  :knows @:Person * ;
+  :address {
+    # Street
+    :street .
+  }
+  # End of person
+}
+:Company { :employee @:Person + } # Companies
+# Footer
+"#;
+        let formatter = ShExFormatter::default().without_colors().with_indent(2);
+        let (schema, comments) = crate::ShExParser::parse_with_comments(src, None).unwrap();
+        let formatted = formatter
+            .format_schema_with_comments(&schema, &comments)
+            .unwrap();
+        for comment in [
+            "# Header",
+            "# Start shape",
+            "# A person",
+            "  # Name of the person",
+            ":name .; # Full name",
+            "    # Street",
+            "  # End of person",
+            "} # Companies",
+            "# Footer",
+        ] {
+            assert!(formatted.contains(comment), "{comment} not in {formatted}")
+        }
+        let (schema2, comments2) =
+            crate::ShExParser::parse_with_comments(&formatted, None).unwrap();
+        assert_eq!(schema, schema2);
+        assert_eq!(
+            formatter
+                .format_schema_with_comments(&schema2, &comments2)
+                .unwrap(),
+            formatted
+        );
+    }
+
+    #[test]
+    fn format_width() {
+        let src = "prefix : <http://example.org/>\n:S { :p . ; :q . }";
+        let schema = crate::ShExParser::parse(src, None).unwrap();
+        let narrow = ShExFormatter::default()
+            .without_colors()
+            .with_width(10)
+            .with_indent(2)
+            .format_schema(&schema)
+            .unwrap();
+        assert_eq!(
+            narrow,
+            "prefix : <http://example.org/>\n:S {\n  :p .;\n  :q .\n}"
+        );
+    }
+
+    // Formats the schema and checks that it is parsed again as the same schema
+    fn assert_round_trip(src: &str) -> String {
+        let schema = crate::ShExParser::parse(src, None).unwrap();
+        let formatted = ShExFormatter::default()
+            .without_colors()
+            .format_schema(&schema)
+            .unwrap();
+        let schema2 = crate::ShExParser::parse(&formatted, None)
+            .unwrap_or_else(|e| panic!("Error parsing {formatted}: {e}"));
+        assert_eq!(
+            schema, schema2,
+            "Different schema after formatting:\n{formatted}"
+        );
+        formatted
+    }
+
+    #[test]
+    fn format_one_of() {
+        let formatted =
+            assert_round_trip("prefix : <http://example.org/>\n:S { :p . | :q . ; :r . }");
+        assert_eq!(
+            formatted,
+            "prefix : <http://example.org/>\n:S { :p . | :q .; :r . }"
+        );
+        assert_round_trip(
+            "prefix : <http://example.org/>\n:S { :a . ; ( :p . | :q . ) * ; ( :r . ; :s . ) }",
+        );
+        assert_round_trip("prefix : <http://example.org/>\n:S { ( :p . | :q . ) | :r . }");
+    }
+
+    #[test]
+    fn format_one_of_with_comments() {
+        let src =
+            "prefix : <http://example.org/>\n:S {\n    :p . | # First\n    # Second\n    :q .\n}";
+        let (schema, comments) = crate::ShExParser::parse_with_comments(src, None).unwrap();
+        let formatted = ShExFormatter::default()
+            .without_colors()
+            .format_schema_with_comments(&schema, &comments)
+            .unwrap();
+        assert_eq!(formatted, src);
+    }
+
+    #[test]
+    fn format_triple_expr_labels_and_includes() {
+        assert_round_trip(
+            "prefix : <http://example.org/>\n:S { $:l ( :p . ; :q . ) {2,3} }\n:T { &:l ; $:m :r . }",
+        );
+    }
+
+    #[test]
+    fn format_literals() {
+        let formatted = assert_round_trip(
+            r#"prefix : <http://example.org/>
+prefix xsd: <http://www.w3.org/2001/XMLSchema#>
+:S { :p [ true false 1 2.5 1.5e3 "a\"b" "x"@en "1"^^xsd:integer ] }"#,
+        );
+        assert!(formatted.contains(r#"[ true false 1 2.5 1.5e3 "a\"b" "x"@en "1"^^xsd:integer ]"#));
+    }
+
+    #[test]
+    fn format_stems_and_language_ranges() {
+        let formatted = assert_round_trip(
+            r#"prefix : <http://example.org/>
+:S { :p [ :a~ :b~ - :b1 - :b2~ @en @en~ @fr~ - @fr-be @~ - @es . - :c "ab"~ - "abc" ] }"#,
+        );
+        assert!(formatted.contains("@en @en~ @fr~ - @fr-be @~ - @es"));
+    }
+
+    #[test]
+    fn wildcard_without_exclusions_is_an_error() {
+        let mut schema = Schema::new();
+        let values = vec![ValueSetValue::IriStemRange {
+            stem: IriRefOrWildcard::Wildcard,
+            exclusions: None,
+        }];
+        schema.add_shape(
+            ShapeExprLabel::iri_unchecked("http://example.org/S"),
+            ShapeExpr::NodeConstraint(NodeConstraint::new().with_values(values)),
+            false,
+        );
+        let result = ShExFormatter::default().format_schema(&schema);
+        assert!(matches!(
+            result,
+            Err(ShExFormatterError::WildcardWithoutExclusions { .. })
+        ));
+    }
 }
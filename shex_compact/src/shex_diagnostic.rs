@@ -0,0 +1,140 @@
+use std::fmt::Display;
+
+use crate::{shex_parser_error::ParseError as ShExParseError, LocatedParseError};
+
+/// Position in a ShExC document. Lines and columns start at 1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: u32,
+    pub column: usize,
+}
+
+impl Position {
+    /// Obtains the position of a byte offset in `src`
+    pub fn from_offset(src: &str, offset: usize) -> Position {
+        let offset = offset.min(src.len());
+        let before = &src[..offset];
+        let line = before.matches('\n').count() as u32 + 1;
+        let line_start = before.rfind('\n').map(|pos| pos + 1).unwrap_or(0);
+        let column = before[line_start..].chars().count() + 1;
+        Position { line, column }
+    }
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// Problem found while parsing a ShExC document in recovery mode
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShExDiagnostic {
+    pub message: String,
+    pub start: Position,
+    pub end: Position,
+
+    /// Tokens that the parser expected at the start position
+    pub expected: Vec<String>,
+}
+
+impl ShExDiagnostic {
+    /// Builds a diagnostic from a parse error. The location is the deepest position reached by the parser,
+    /// which is usually the place where the error is.
+    pub(crate) fn from_located_error(src: &str, err: &LocatedParseError) -> ShExDiagnostic {
+        let offset = err.deepest_offset();
+        let end = token_end(src, offset);
+        let mut errors = Vec::new();
+        errors_at(err, offset, &mut errors);
+        let mut expected = Vec::new();
+        for e in errors.iter() {
+            if let ShExParseError::ExpectedToken(token) = e {
+                if !expected.contains(token) {
+                    expected.push(token.clone())
+                }
+            }
+        }
+        let message = if !expected.is_empty() {
+            if offset < end {
+                format!("Unexpected \"{}\"", &src[offset..end])
+            } else {
+                "Unexpected end of input".to_string()
+            }
+        } else {
+            errors
+                .iter()
+                .find(|e| !matches!(e, ShExParseError::SyntaxError(_)))
+                .or(errors.first())
+                .map(|e| e.to_string())
+                .unwrap_or_else(|| err.source.to_string())
+        };
+        ShExDiagnostic {
+            message,
+            start: Position::from_offset(src, offset),
+            end: Position::from_offset(src, end),
+            expected,
+        }
+    }
+
+    /// Builds a diagnostic for the text between `start` and `end` offsets
+    pub(crate) fn from_range(
+        src: &str,
+        start: usize,
+        end: usize,
+        message: String,
+    ) -> ShExDiagnostic {
+        ShExDiagnostic {
+            message,
+            start: Position::from_offset(src, start),
+            end: Position::from_offset(src, end),
+            expected: Vec::new(),
+        }
+    }
+}
+
+impl Display for ShExDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Error at {}-{}: {}", self.start, self.end, self.message)?;
+        if !self.expected.is_empty() {
+            let expected: Vec<String> = self.expected.iter().map(|t| format!("\"{t}\"")).collect();
+            write!(f, ". Expected one of: {}", expected.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+fn errors_at<'a>(err: &'a LocatedParseError, offset: usize, result: &mut Vec<&'a ShExParseError>) {
+    if err.offset == offset {
+        result.push(&err.source)
+    }
+    for e in err.context.iter() {
+        errors_at(e, offset, result)
+    }
+}
+
+// The offending token ends at the next whitespace
+fn token_end(src: &str, offset: usize) -> usize {
+    let offset = offset.min(src.len());
+    src[offset..]
+        .find(char::is_whitespace)
+        .map(|pos| offset + pos)
+        .unwrap_or(src.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position_from_offset() {
+        let src = "prefix : <http://example.org/>\n:S { :p . }";
+        assert_eq!(
+            Position::from_offset(src, 0),
+            Position { line: 1, column: 1 }
+        );
+        assert_eq!(
+            Position::from_offset(src, 33),
+            Position { line: 2, column: 3 }
+        );
+    }
+}
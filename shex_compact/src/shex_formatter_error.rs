@@ -0,0 +1,7 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum ShExFormatterError {
+    #[error("Value set value {value} can't be written in ShExC: a wildcard needs at least one exclusion")]
+    WildcardWithoutExclusions { value: String },
+}
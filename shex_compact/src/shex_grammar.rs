@@ -1,6 +1,7 @@
 use crate::grammar_structs::{
     Cardinality, NumericLength, NumericRange, Qualifier, SenseFlags, ShExStatement,
 };
+use crate::shex_comments::record_triple_constraint;
 use crate::{
     map_error, shex_parser_error::ParseError as ShExParseError, tag_no_case_tws, token, token_tws,
     traced, tws0, IRes, Span,
@@ -10,11 +11,11 @@ use nom::{
     branch::alt,
     bytes::complete::{tag, tag_no_case, take_while, take_while1},
     character::complete::{alpha1, alphanumeric1, char, digit0, digit1, none_of, one_of, satisfy},
-    combinator::{cut, map, map_res, opt, recognize},
+    combinator::{cut, map, map_res, not, opt, recognize},
     error::ErrorKind,
     error_position,
     multi::{count, fold_many0, many0, many1},
-    sequence::{delimited, pair, preceded, tuple},
+    sequence::{delimited, pair, preceded, terminated, tuple},
     Err, InputTake,
 };
 use regex::Regex;
@@ -561,12 +562,15 @@ fn string_facets(i: Span) -> IRes<NodeConstraint> {
     Ok((i, NodeConstraint::new().with_xsfacets(facets)))
 }
 
-/// `[26] nonLiteralKind ::= "IRI" | "BNODE" | "NONLITERAL"`
+/// `[26] nonLiteralKind ::= "IRI" | "BNODE" | "NONLITERAL" | "TRIPLE"`
+///
+/// `TRIPLE` is an extension for triple terms (RDF-star)
 fn non_literal_kind(i: Span) -> IRes<NodeKind> {
     alt((
         map(token_tws("IRI"), |_| NodeKind::Iri),
         map(token_tws("BNODE"), |_| NodeKind::BNode),
         map(token_tws("NONLITERAL"), |_| NodeKind::NonLiteral),
+        map(token_tws("TRIPLE"), |_| NodeKind::Triple),
     ))(i)
 }
 
@@ -996,6 +1000,7 @@ fn triple_constraint<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, TripleExpr> {
         "triple_constraint",
         map_error(
             move |i| {
+                let start = i.location_offset();
                 let (
                     i,
                     (
@@ -1040,6 +1045,7 @@ fn triple_constraint<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, TripleExpr> {
                 let mut te = TripleExpr::triple_constraint(
                     negated, inverse, predicate, value_expr, min, max,
                 );
+                record_triple_constraint(start);
                 te = te.with_sem_acts(sem_acts);
                 if !annotations.is_empty() {
                     te = te.with_annotations(Some(annotations))
@@ -1384,7 +1390,7 @@ fn annotation<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, Annotation> {
 }
 
 /// From [58] iri_or_literal = (iri | literal)
-fn iri_or_literal<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, ObjectValue> {
+pub(crate) fn iri_or_literal<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, ObjectValue> {
     traced(
         "iri_or_literal",
         map_error(
@@ -1435,7 +1441,7 @@ fn percent_code(i: Span) -> IRes<Option<String>> {
 }
 
 /// `[13t] literal ::= rdfLiteral | numericLiteral | booleanLiteral`
-fn literal<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, Literal> {
+pub(crate) fn literal<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, Literal> {
     traced(
         "literal",
         map_error(
@@ -1452,7 +1458,7 @@ fn literal<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, Literal> {
 }
 
 /// `[16t] numericLiteral ::= INTEGER | DECIMAL | DOUBLE`
-fn numeric_literal(i: Span) -> IRes<NumericLiteral> {
+pub(crate) fn numeric_literal(i: Span) -> IRes<NumericLiteral> {
     alt((
         map(double, NumericLiteral::double),
         decimal,
@@ -1490,7 +1496,7 @@ fn integer_literal<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, NumericLiteral> {
     )
 }
 
-fn boolean_literal(i: Span) -> IRes<Literal> {
+pub(crate) fn boolean_literal(i: Span) -> IRes<Literal> {
     map(boolean_value, Literal::boolean)(i)
 }
 
@@ -1534,7 +1540,7 @@ fn datatype_iri(i: Span) -> IRes<IriRef> {
 
 /// `[135s] string ::= STRING_LITERAL1 | STRING_LITERAL_LONG1`
 /// `                  | STRING_LITERAL2 | STRING_LITERAL_LONG2`
-fn string<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, String> {
+pub(crate) fn string<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, String> {
     traced(
         "string",
         map_error(
@@ -1590,7 +1596,12 @@ fn single_quote_char<'a>() -> impl FnMut(Span<'a>) -> IRes<'a, char> {
 fn string_literal_long1(i: Span) -> IRes<String> {
     let (i, chars) = delimited(
         token("'''"),
-        cut(many0(alt((none_of(r"'\"), echar, uchar)))),
+        cut(many0(alt((
+            none_of(r"'\"),
+            echar,
+            uchar,
+            single_quote("''"),
+        )))),
         token("'''"),
     )(i)?;
     let str = chars.iter().collect();
@@ -1601,13 +1612,23 @@ fn string_literal_long1(i: Span) -> IRes<String> {
 fn string_literal_long2(i: Span) -> IRes<String> {
     let (i, chars) = delimited(
         token(r#"""""#),
-        cut(many0(alt((none_of(r#""\"#), echar, uchar)))),
+        cut(many0(alt((
+            none_of(r#""\"#),
+            echar,
+            uchar,
+            single_quote(r#""""#),
+        )))),
         token(r#"""""#),
     )(i)?;
     let str = chars.iter().collect();
     Ok((i, str))
 }
 
+// A quote inside a long string which doesn't start the closing delimiter
+fn single_quote<'a>(quotes: &'a str) -> impl FnMut(Span<'a>) -> IRes<'a, char> {
+    move |i| terminated(one_of("'\""), not(tag(quotes)))(i)
+}
+
 pub fn hex(input: Span) -> IRes<Span> {
     recognize(one_of(HEXDIGIT))(input)
 }
@@ -1675,7 +1696,7 @@ fn echar(i: Span) -> IRes<char> {
 }
 
 /// `[145s] <LANGTAG> ::= "@" ([a-zA-Z])+ ("-" ([a-zA-Z0-9])+)*`
-fn lang_tag(i: Span) -> IRes<Lang> {
+pub(crate) fn lang_tag(i: Span) -> IRes<Lang> {
     let (i, lang_str) = preceded(
         token("@"),
         recognize(tuple((alpha1, many0(preceded(token("-"), alphanumeric1))))),
@@ -1684,7 +1705,7 @@ fn lang_tag(i: Span) -> IRes<Lang> {
 }
 
 /// `[61] predicate ::= iri | RDF_TYPE`
-fn predicate(i: Span) -> IRes<IriRef> {
+pub(crate) fn predicate(i: Span) -> IRes<IriRef> {
     alt((iri, rdf_type))(i)
 }
 
@@ -2010,7 +2031,7 @@ fn pname_ns_iri_ref(i: Span) -> IRes<IriRef> {
 }
 
 /// `[138s] blankNode ::= BLANK_NODE_LABEL`
-fn blank_node(i: Span) -> IRes<BNode> {
+pub(crate) fn blank_node(i: Span) -> IRes<BNode> {
     map(blank_node_label, BNode::new)(i)
 }
 
@@ -2184,7 +2205,7 @@ fn is_hex(c: char) -> bool {
 
 /// `[18t] <IRIREF> ::= "<" ([^#0000- <>\"{}|^`\\] | UCHAR)* ">"`
 /// iri_chars = ([^#0000- <>\"{}|^`\\] | UCHAR)*
-fn iri_ref(i: Span) -> IRes<IriS> {
+pub(crate) fn iri_ref(i: Span) -> IRes<IriS> {
     let (i, str) = delimited(
         char('<'),
         // take_while(is_iri_ref),
@@ -2478,6 +2499,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_triple_node_kind() {
+        let s = shex_statement()(Span::new(":S TRIPLE")).unwrap();
+        assert_eq!(
+            s.1,
+            ShExStatement::ShapeDecl {
+                is_abstract: false,
+                shape_label: ShapeExprLabel::prefixed("", "S"),
+                shape_expr: ShapeExpr::node_constraint(
+                    NodeConstraint::new().with_node_kind(NodeKind::Triple)
+                )
+            }
+        );
+    }
+
     #[test]
     fn test_tws_statement() {
         assert!(shex_statement()(Span::new(" ")).is_err());
@@ -1,5 +1,6 @@
 use iri_s::IriS;
 use nom::Err;
+use nom::Slice;
 use prefixmap::Deref;
 use shex_ast::Iri;
 use shex_ast::Schema;
@@ -9,9 +10,12 @@ use std::path::Path;
 use tracing::debug;
 
 use crate::grammar_structs::ShExStatement;
+use crate::shex_comments::{with_trivia, StatementKind, StatementSpan};
 use crate::shex_statement;
 use crate::tws0;
 use crate::ParseError;
+use crate::ShExComments;
+use crate::ShExDiagnostic;
 use crate::Span;
 
 // This code is inspired from:
@@ -34,38 +38,100 @@ impl<'a> ShExParser<'a> {
         let mut parser = ShExParser {
             shex_statement_iterator: StatementIterator::new(Span::new(src))?,
         };
-        let mut shapes_counter = 0;
         for s in parser.shex_statement_iterator.by_ref() {
-            match s? {
-                ShExStatement::BaseDecl { iri } => {
-                    schema = schema.with_base(Some(iri));
-                }
-                ShExStatement::PrefixDecl { alias, iri } => {
-                    schema.add_prefix(alias, &iri)?;
-                }
-                ShExStatement::StartDecl { shape_expr } => {
-                    schema = schema.with_start(Some(shape_expr))
-                }
-                ShExStatement::ImportDecl { iri } => {
-                    schema = schema.with_import(Iri::new(iri.as_str()));
+            add_statement(&mut schema, s?)?;
+        }
+        Ok(schema)
+    }
+
+    /// Parse a ShEx schema in error recovery mode
+    ///
+    /// Instead of stopping at the first error, the parser skips to the next line that
+    /// looks like the beginning of a declaration and continues from there.
+    /// It returns the schema with the statements that could be parsed and the list of problems found
+    ///
+    /// ```
+    /// use shex_compact::ShExParser;
+    ///
+    /// let str = r#"prefix : <http://example.org/>
+    /// :S { :p @:T ;
+    ///      :q }
+    /// :T { :q . }
+    /// :U { :r . }
+    /// "#;
+    /// let (schema, diagnostics) = ShExParser::parse_with_recovery(str, None);
+    /// assert_eq!(diagnostics.len(), 1);
+    /// assert_eq!(diagnostics[0].start.line, 3);
+    /// assert_eq!(schema.shapes().map(|shapes| shapes.len()), Some(2));
+    /// ```
+    pub fn parse_with_recovery(src: &str, base: Option<IriS>) -> (Schema, Vec<ShExDiagnostic>) {
+        let (schema, diagnostics, _) = Self::parse_with_spans(src, base);
+        (schema, diagnostics)
+    }
+
+    /// Parse a ShEx schema in error recovery mode keeping the location of the statements that could be parsed
+    ///
+    /// The spans allow tools like editors to find the declaration of each shape in the source
+    ///
+    /// ```
+    /// use shex_compact::{ShExParser, StatementKind};
+    ///
+    /// let str = r#"prefix : <http://example.org/>
+    /// :S { :p @:T }
+    /// "#;
+    /// let (_, _, spans) = ShExParser::parse_with_spans(str, None);
+    /// assert!(matches!(spans[1].kind, StatementKind::ShapeDecl(_)));
+    /// assert_eq!(str[spans[1].start..spans[1].end].trim_end(), ":S { :p @:T }");
+    /// ```
+    pub fn parse_with_spans(
+        src: &str,
+        base: Option<IriS>,
+    ) -> (Schema, Vec<ShExDiagnostic>, Vec<StatementSpan>) {
+        let mut schema = Schema::new().with_base(base);
+        let mut diagnostics = Vec::new();
+        let mut statements = Vec::new();
+        let mut input = skip_whitespace(Span::new(src), src, &mut diagnostics);
+        while !input.is_empty() {
+            match shex_statement()(input) {
+                Ok((left, statement)) => {
+                    match add_statement(&mut schema, statement) {
+                        Ok(kind) => statements.push(StatementSpan {
+                            start: input.location_offset(),
+                            end: left.location_offset(),
+                            kind,
+                        }),
+                        Err(err) => diagnostics.push(ShExDiagnostic::from_range(
+                            src,
+                            input.location_offset(),
+                            left.location_offset(),
+                            err.to_string(),
+                        )),
+                    }
+                    input = left;
                 }
-                ShExStatement::ShapeDecl {
-                    is_abstract,
-                    shape_label,
-                    shape_expr,
-                } => {
-                    let shape_label = shape_label.deref(&schema.base(), &schema.prefixmap())?;
-                    let shape_expr = shape_expr.deref(&schema.base(), &schema.prefixmap())?;
-                    shapes_counter += 1;
-                    tracing::debug!("Shape decl #{shapes_counter}: {shape_label} ");
-                    schema.add_shape(shape_label, shape_expr, is_abstract);
+                Err(Err::Incomplete(needed)) => {
+                    debug!("Incomplete! shex_statement. Needed: {needed:?}");
+                    break;
                 }
-                ShExStatement::StartActions { actions } => {
-                    schema = schema.with_start_actions(Some(actions));
+                Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+                    diagnostics.push(ShExDiagnostic::from_located_error(src, &e));
+                    input = resynchronize(input);
                 }
             }
+            input = skip_whitespace(input, src, &mut diagnostics);
         }
-        Ok(schema)
+        (schema, diagnostics, statements)
+    }
+
+    /// Parse a ShEx schema keeping its comments
+    ///
+    /// The comments are attached to the declarations and triple constraints that follow them
+    /// so they can be emitted again by [`ShExFormatter::format_schema_with_comments`](crate::ShExFormatter::format_schema_with_comments)
+    pub fn parse_with_comments(src: &str, base: Option<IriS>) -> Result<(Schema, ShExComments)> {
+        let (result, trivia) = with_trivia(|| parse_statements(src, base));
+        let (schema, statements) = result?;
+        let comments = ShExComments::from_statements(src, &statements, &trivia);
+        Ok((schema, comments))
     }
 
     pub fn parse_buf(path: &Path, base: Option<IriS>) -> Result<Schema> {
@@ -81,6 +147,120 @@ impl<'a> ShExParser<'a> {
     }
 }
 
+// Parses the statements of a schema keeping their positions in the source
+fn parse_statements(src: &str, base: Option<IriS>) -> Result<(Schema, Vec<StatementSpan>)> {
+    let mut schema = Schema::new().with_base(base);
+    let mut statements = Vec::new();
+    let mut input = match tws0(Span::new(src)) {
+        Ok((left, _)) => left,
+        Err(e) => {
+            return Err(ParseError::Custom {
+                msg: format!("cannot start parsing. Error: {}", e),
+            })
+        }
+    };
+    while !input.is_empty() {
+        match shex_statement()(input) {
+            Ok((left, statement)) => {
+                let kind = add_statement(&mut schema, statement)?;
+                statements.push(StatementSpan {
+                    start: input.location_offset(),
+                    end: left.location_offset(),
+                    kind,
+                });
+                input = left;
+            }
+            Err(Err::Incomplete(needed)) => {
+                debug!("Incomplete! shex_statement. Needed: {needed:?}");
+                break;
+            }
+            Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+                return Err(ParseError::NomError { err: Box::new(e) })
+            }
+        }
+        input = match tws0(input) {
+            Ok((left, _)) => left,
+            Err(e) => {
+                return Err(ParseError::Custom {
+                    msg: format!("error parsing whitespace. Error: {}", e),
+                })
+            }
+        };
+    }
+    Ok((schema, statements))
+}
+
+fn add_statement(schema: &mut Schema, statement: ShExStatement) -> Result<StatementKind> {
+    let kind = match statement {
+        ShExStatement::BaseDecl { iri } => {
+            *schema = std::mem::take(schema).with_base(Some(iri));
+            StatementKind::Directive
+        }
+        ShExStatement::PrefixDecl { alias, iri } => {
+            schema.add_prefix(alias, &iri)?;
+            StatementKind::Directive
+        }
+        ShExStatement::StartDecl { shape_expr } => {
+            *schema = std::mem::take(schema).with_start(Some(shape_expr));
+            StatementKind::Start
+        }
+        ShExStatement::ImportDecl { iri } => {
+            *schema = std::mem::take(schema).with_import(Iri::new(iri.as_str()));
+            StatementKind::Directive
+        }
+        ShExStatement::ShapeDecl {
+            is_abstract,
+            shape_label,
+            shape_expr,
+        } => {
+            let shape_label = shape_label.deref(&schema.base(), &schema.prefixmap())?;
+            let shape_expr = shape_expr.deref(&schema.base(), &schema.prefixmap())?;
+            tracing::debug!("Shape decl: {shape_label} ");
+            schema.add_shape(shape_label.clone(), shape_expr, is_abstract);
+            StatementKind::ShapeDecl(shape_label)
+        }
+        ShExStatement::StartActions { actions } => {
+            *schema = std::mem::take(schema).with_start_actions(Some(actions));
+            StatementKind::Directive
+        }
+    };
+    Ok(kind)
+}
+
+fn skip_whitespace<'a>(
+    input: Span<'a>,
+    src: &str,
+    diagnostics: &mut Vec<ShExDiagnostic>,
+) -> Span<'a> {
+    match tws0(input) {
+        Ok((left, _)) => left,
+        Err(Err::Incomplete(_)) => input,
+        Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+            diagnostics.push(ShExDiagnostic::from_located_error(src, &e));
+            input.slice(input.len()..)
+        }
+    }
+}
+
+// Skips to the next line which starts with something that can begin a statement:
+// an IRI, a prefixed name, a blank node, a keyword or a semantic action.
+// The lines of a statement are usually indented, so a failing statement is skipped
+// without skipping the declarations that follow it
+fn resynchronize(input: Span) -> Span {
+    let text = *input.fragment();
+    let mut pos = 0;
+    while let Some(newline) = text[pos..].find('\n') {
+        pos += newline + 1;
+        match text[pos..].chars().next() {
+            Some(c) if c.is_alphabetic() || matches!(c, '<' | '_' | ':' | '%') => {
+                return input.slice(pos..);
+            }
+            _ => {}
+        }
+    }
+    input.slice(input.len()..)
+}
+
 struct StatementIterator<'a> {
     src: Span<'a>,
     done: bool,
@@ -160,6 +340,7 @@ mod tests {
     use shex_ast::{Shape, ShapeExpr, ShapeExprLabel};
 
     use super::*;
+    use crate::Position;
 
     #[test]
     fn test_prefix() {
@@ -179,4 +360,56 @@ mod tests {
         );
         assert_eq!(schema, expected)
     }
+
+    #[test]
+    fn test_recovery_reports_all_errors() {
+        let str = r#"prefix : <http://example.org/>
+
+:Person {
+  :name . ;
+  :knows @:Person *
+
+:Company {
+  :name .
+  :employee @:Person
+}
+
+:Good { :p . }
+"#;
+        let (schema, diagnostics) = ShExParser::parse_with_recovery(str, None);
+        let locations: Vec<_> = diagnostics.iter().map(|d| (d.start, d.end)).collect();
+        assert_eq!(
+            locations,
+            vec![
+                (
+                    Position { line: 7, column: 1 },
+                    Position { line: 7, column: 9 }
+                ),
+                (
+                    Position { line: 9, column: 3 },
+                    Position {
+                        line: 9,
+                        column: 12
+                    }
+                )
+            ]
+        );
+        assert_eq!(diagnostics[0].expected, vec!["}".to_string()]);
+        let labels: Vec<_> = schema
+            .shapes()
+            .unwrap()
+            .iter()
+            .map(|decl| decl.id.to_string())
+            .collect();
+        assert_eq!(labels, vec!["http://example.org/Good".to_string()]);
+    }
+
+    #[test]
+    fn test_recovery_deref_error() {
+        let str = "prefix : <http://example.org/>\n:S { ex:p . }\n:T {}\n";
+        let (schema, diagnostics) = ShExParser::parse_with_recovery(str, None);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].start, Position { line: 2, column: 1 });
+        assert_eq!(schema.shapes().map(|shapes| shapes.len()), Some(1));
+    }
 }
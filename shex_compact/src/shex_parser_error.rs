@@ -216,6 +216,12 @@ pub enum ParseError {
     #[error("Expected node selector specification")]
     ExpectedNodeSpec,
 
+    #[error("Expected triple pattern with FOCUS")]
+    ExpectedTriplePattern,
+
+    #[error("Expected property path")]
+    ExpectedPath,
+
     #[error("Failed regular expression, str: {str} doesn't match: {re}")]
     RegexFailed { re: String, str: String },
 }
@@ -245,6 +251,7 @@ impl ParseError {
             source: self,
             line: position.location_line(),
             column,
+            offset: position.location_offset(),
             fragment,
             context: Vec::new(),
         }
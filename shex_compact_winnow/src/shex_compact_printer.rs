@@ -411,6 +411,7 @@ where
             NodeKind::BNode => printer.keyword("BNODE"),
             NodeKind::NonLiteral => printer.keyword("NONLITERAL"),
             NodeKind::Literal => printer.keyword("LITERAL"),
+            NodeKind::Triple => printer.keyword("TRIPLE"),
         }
     }
 
@@ -542,12 +542,13 @@ fn string_facets(i: Span) -> IRes<NodeConstraint> {
     Ok((i, NodeConstraint::new().with_xsfacets(facets)))
 }
 
-/// `[26]   	nonLiteralKind	   ::=   	"IRI" | "BNODE" | "NONLITERAL"`
+/// `[26]   	nonLiteralKind	   ::=   	"IRI" | "BNODE" | "NONLITERAL" | "TRIPLE"`
 fn non_literal_kind(i: Span) -> IRes<NodeKind> {
     alt((
         map(token_tws("IRI"), |_| NodeKind::Iri),
         map(token_tws("BNODE"), |_| NodeKind::BNode),
         map(token_tws("NONLITERAL"), |_| NodeKind::NonLiteral),
+        map(token_tws("TRIPLE"), |_| NodeKind::Triple),
     ))(i)
 }
 
@@ -2,11 +2,13 @@
 //!
 //!
 mod result_map;
+mod result_map_error;
 mod result_value;
 // mod validation_state;
 pub mod atom;
 pub mod reason;
 pub mod rule;
+pub mod shape_match;
 pub mod solver;
 pub mod validator;
 pub mod validator_config;
@@ -16,8 +18,10 @@ pub mod validator_runner;
 pub use crate::atom::*;
 pub use crate::reason::*;
 pub use crate::result_map::*;
+pub use crate::result_map_error::*;
 pub use crate::result_value::*;
 pub use crate::rule::*;
+pub use crate::shape_match::*;
 pub use crate::validator::*;
 pub use crate::validator_config::*;
 pub use crate::validator_error::*;
@@ -1,16 +1,83 @@
 use std::fmt::Display;
 
+use iri_s::IriS;
 use shex_ast::{
     compiled::{node_constraint::NodeConstraint, shape::Shape, shape_expr::ShapeExpr},
-    Node,
+    Node, Pred, ShapeLabelIdx,
 };
 
 /// Reason represents justifications about why a node conforms to some shape
 #[derive(Debug, Clone)]
 pub enum Reason {
-    NodeConstraintPassed { node: Node, nc: NodeConstraint },
-    ShapeAndPassed { node: Node, se: ShapeExpr },
-    ShapePassed { node: Node, shape: Shape },
+    NodeConstraintPassed {
+        node: Node,
+        nc: NodeConstraint,
+    },
+    ShapeAndPassed {
+        node: Node,
+        se: ShapeExpr,
+        reasons: Vec<Reason>,
+    },
+    /// `arcs` are the arcs of the node matched by the triple constraints of the shape, which are incoming arcs for inverse predicates,
+    /// and `refs` the nodes that have to conform to the shapes referenced by those triple constraints
+    ShapePassed {
+        node: Node,
+        shape: Shape,
+        arcs: Vec<(Pred, Node)>,
+        refs: Vec<(Node, ShapeLabelIdx)>,
+    },
+    /// The node passed the check registered for the external shape
+    ExternalShapePassed {
+        node: Node,
+        shape: IriS,
+    },
+}
+
+impl Reason {
+    /// Triples of the RDF data that took part in the match
+    pub fn triples(&self) -> Vec<(Node, Pred, Node)> {
+        match self {
+            Reason::NodeConstraintPassed { .. } => Vec::new(),
+            Reason::ShapeAndPassed { reasons, .. } => {
+                reasons.iter().flat_map(|r| r.triples()).collect()
+            }
+            Reason::ShapePassed { node, arcs, .. } => arcs
+                .iter()
+                .map(|(pred, value)| {
+                    if pred.is_inverse() {
+                        (value.clone(), Pred::from(pred.iri().clone()), node.clone())
+                    } else {
+                        (node.clone(), pred.clone(), value.clone())
+                    }
+                })
+                .collect(),
+            Reason::ExternalShapePassed { .. } => Vec::new(),
+        }
+    }
+
+    /// Arcs of the node matched by the triple constraints, where inverse predicates are incoming arcs
+    pub fn arcs(&self) -> Vec<(Pred, Node)> {
+        match self {
+            Reason::NodeConstraintPassed { .. } => Vec::new(),
+            Reason::ShapeAndPassed { reasons, .. } => {
+                reasons.iter().flat_map(|r| r.arcs()).collect()
+            }
+            Reason::ShapePassed { arcs, .. } => arcs.clone(),
+            Reason::ExternalShapePassed { .. } => Vec::new(),
+        }
+    }
+
+    /// Node/shape pairs that the match depends on
+    pub fn refs(&self) -> Vec<(Node, ShapeLabelIdx)> {
+        match self {
+            Reason::NodeConstraintPassed { .. } => Vec::new(),
+            Reason::ShapeAndPassed { reasons, .. } => {
+                reasons.iter().flat_map(|r| r.refs()).collect()
+            }
+            Reason::ShapePassed { refs, .. } => refs.clone(),
+            Reason::ExternalShapePassed { .. } => Vec::new(),
+        }
+    }
 }
 
 impl Display for Reason {
@@ -19,12 +86,15 @@ impl Display for Reason {
             Reason::NodeConstraintPassed { node, nc } => {
                 write!(f, "Node constraint passed for node {node}: {nc}",)
             }
-            Reason::ShapeAndPassed { node, se } => {
+            Reason::ShapeAndPassed { node, se, .. } => {
                 write!(f, "AND passed for node {node}: {se}")
             }
-            Reason::ShapePassed { node, shape } => {
+            Reason::ShapePassed { node, shape, .. } => {
                 write!(f, "Shape passed for node {node}: {shape}")
             }
+            Reason::ExternalShapePassed { node, shape } => {
+                write!(f, "External shape {shape} passed for node {node}")
+            }
         }
     }
 }
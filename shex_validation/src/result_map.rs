@@ -1,15 +1,46 @@
 use colored::*;
+use iri_s::IriS;
 use prefixmap::PrefixMap;
+use serde_derive::{Deserialize, Serialize};
+use shapemap::ShapeMapState;
 use shex_ast::compiled::shape_label::ShapeLabel;
-use shex_ast::Node;
-use srdf::Object;
+use shex_ast::{BNode, Node};
+use shex_compact::ShapeMapParser;
+use srdf::literal::Literal;
+use srdf::numeric_literal::NumericLiteral;
+use srdf::{Object, XSD_BOOLEAN_STR, XSD_DECIMAL_STR, XSD_DOUBLE_STR, XSD_INTEGER_STR};
 use std::collections::hash_map::Entry;
+use std::str::FromStr;
 use std::{
     collections::{HashMap, HashSet},
     fmt::{Debug, Display, Formatter},
 };
 
-use crate::{Reason, ResultValue, ValidatorError};
+use crate::{Reason, ResultMapError, ResultValue, ValidatorError};
+
+/// Status of a node/shape association in a result shapemap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ResultStatus {
+    Conformant,
+    Nonconformant,
+    Pending,
+}
+
+/// Association of a result shapemap in the [JSON ShapeMap](https://shexspec.github.io/shape-map/#json-shapemap) format
+///
+/// Nodes and shapes are represented by their full IRIs, blank nodes as `_:id`
+/// and literals as `"lexical form"@lang` or `"lexical form"^^<datatype>`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResultAssociation {
+    pub node: String,
+    pub shape: String,
+    pub status: ResultStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appinfo: Option<serde_json::Value>,
+}
 
 #[derive(Debug, Default)]
 pub struct ResultMap {
@@ -109,6 +140,329 @@ impl ResultMap {
             false
         }
     }
+
+    /// Returns the status of every node/shape pair, ignoring reasons and errors
+    ///
+    /// It can be used to compare the results of a validation with an expected result shapemap
+    pub fn statuses(&self) -> HashSet<(&Node, &ShapeLabel, ResultStatus)> {
+        let oks = self
+            .ok_map
+            .iter()
+            .flat_map(|(n, hs)| hs.keys().map(move |s| (n, s, ResultStatus::Conformant)));
+        let fails = self
+            .fail_map
+            .iter()
+            .flat_map(|(n, hs)| hs.keys().map(move |s| (n, s, ResultStatus::Nonconformant)));
+        let pending = self
+            .pending
+            .iter()
+            .flat_map(|(n, hs)| hs.iter().map(move |s| (n, s, ResultStatus::Pending)));
+        oks.chain(fails).chain(pending).collect()
+    }
+
+    /// Returns the associations of the result map sorted by node and shape
+    ///
+    /// The reasons of conformant nodes and the errors of nonconformant nodes are kept in `appinfo`
+    pub fn associations(&self) -> Vec<ResultAssociation> {
+        let mut result = Vec::new();
+        for (n, hs) in &self.ok_map {
+            for (s, reasons) in hs {
+                result.push(ResultAssociation {
+                    node: json_node(n),
+                    shape: json_shape(s),
+                    status: ResultStatus::Conformant,
+                    reason: None,
+                    appinfo: appinfo(reasons),
+                })
+            }
+        }
+        for (n, hs) in &self.fail_map {
+            for (s, errs) in hs {
+                result.push(ResultAssociation {
+                    node: json_node(n),
+                    shape: json_shape(s),
+                    status: ResultStatus::Nonconformant,
+                    reason: reason_str(errs),
+                    appinfo: appinfo(errs),
+                })
+            }
+        }
+        for (n, hs) in &self.pending {
+            for s in hs {
+                result.push(ResultAssociation {
+                    node: json_node(n),
+                    shape: json_shape(s),
+                    status: ResultStatus::Pending,
+                    reason: None,
+                    appinfo: None,
+                })
+            }
+        }
+        result.sort_by(|a1, a2| (&a1.node, &a1.shape).cmp(&(&a2.node, &a2.shape)));
+        result
+    }
+
+    /// Serializes the result map in the JSON ShapeMap format
+    pub fn as_json(&self) -> Result<String, ResultMapError> {
+        serde_json::to_string_pretty(&self.associations()).map_err(|e| {
+            ResultMapError::JsonSerializationError {
+                error: e.to_string(),
+            }
+        })
+    }
+
+    /// Parses a result map in the JSON ShapeMap format
+    ///
+    /// The `reason` and `appinfo` fields are ignored
+    pub fn from_json_str(str: &str) -> Result<ResultMap, ResultMapError> {
+        let associations: Vec<ResultAssociation> =
+            serde_json::from_str(str).map_err(|e| ResultMapError::JsonError {
+                error: e.to_string(),
+            })?;
+        let mut result = ResultMap::new();
+        for association in associations {
+            let node = parse_json_node(&association.node)?;
+            let shape = parse_json_shape(&association.shape)?;
+            result.add_status(node, shape, association.status);
+        }
+        Ok(result)
+    }
+
+    /// Shows the result map in compact syntax: `node@shape` for conformant nodes,
+    /// `node@!shape` for nonconformant nodes and `node@?shape` for pending ones
+    ///
+    /// The errors of nonconformant nodes are shown as a string with the reason after the shape
+    pub fn show_compact(&self) -> String {
+        let nodes_prefixmap = self.nodes_prefixmap.clone().without_rich_qualifying();
+        let schema_prefixmap = self.schema_prefixmap.clone().without_rich_qualifying();
+        let mut entries: Vec<_> = self
+            .statuses()
+            .into_iter()
+            .map(|(n, s, status)| {
+                let mark = match status {
+                    ResultStatus::Conformant => "",
+                    ResultStatus::Nonconformant => "!",
+                    ResultStatus::Pending => "?",
+                };
+                let reason = match status {
+                    ResultStatus::Nonconformant => self.fail_map[n][s].as_slice(),
+                    _ => &[],
+                };
+                let reason = match reason_str(reason) {
+                    Some(reason) => format!(" \"{}\"", escape(&reason)),
+                    None => String::new(),
+                };
+                format!(
+                    "{}@{mark}{}{reason}",
+                    compact_node(n, &nodes_prefixmap),
+                    show_shapelabel(s, &schema_prefixmap)
+                )
+            })
+            .collect();
+        entries.sort();
+        entries.join(",\n")
+    }
+
+    /// Parses a result map in compact syntax with [`ShapeMapParser::parse_result_map`]
+    ///
+    /// Prefixed names are resolved with `nodes_prefixmap` for nodes and datatypes and with `schema_prefixmap` for shapes.
+    /// The reasons are ignored
+    pub fn from_compact_str(
+        str: &str,
+        nodes_prefixmap: &PrefixMap,
+        schema_prefixmap: &PrefixMap,
+    ) -> Result<ResultMap, ResultMapError> {
+        let associations = ShapeMapParser::parse_result_map(str, nodes_prefixmap, schema_prefixmap)
+            .map_err(|e| ResultMapError::CompactSyntaxError {
+                error: e.to_string(),
+            })?;
+        let mut result = ResultMap::new()
+            .with_nodes_prefixmap(nodes_prefixmap.clone())
+            .with_schema_prefixmap(schema_prefixmap.clone());
+        for (node, state, shape, _) in associations {
+            let status = match state {
+                ShapeMapState::Conforms => ResultStatus::Conformant,
+                ShapeMapState::Fails => ResultStatus::Nonconformant,
+                _ => ResultStatus::Pending,
+            };
+            result.add_status(node, shape, status);
+        }
+        Ok(result)
+    }
+
+    /// Removes the failures of the nodes which conform to some shape
+    ///
+    /// It is used to report the shapes that each node conforms to after trying all the shapes
+    pub fn best_shapes(mut self) -> Self {
+        let ok_map = &self.ok_map;
+        self.fail_map.retain(|node, _| !ok_map.contains_key(node));
+        self
+    }
+
+    fn add_status(&mut self, node: Node, shape: ShapeLabel, status: ResultStatus) {
+        match status {
+            ResultStatus::Conformant => self.add_ok(node, shape, Vec::new()),
+            ResultStatus::Nonconformant => self.add_fail(node, shape, Vec::new()),
+            ResultStatus::Pending => self.add_pending(node, shape),
+        }
+    }
+}
+
+/// Errors of a nonconformant node, one per line
+fn reason_str(errs: &[ValidatorError]) -> Option<String> {
+    if errs.is_empty() {
+        None
+    } else {
+        Some(
+            errs.iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+fn appinfo<T: Display>(values: &[T]) -> Option<serde_json::Value> {
+    if values.is_empty() {
+        None
+    } else {
+        Some(serde_json::Value::Array(
+            values
+                .iter()
+                .map(|v| serde_json::Value::String(v.to_string()))
+                .collect(),
+        ))
+    }
+}
+
+fn json_node(node: &Node) -> String {
+    json_object(node.as_object())
+}
+
+fn json_object(object: &Object) -> String {
+    match object {
+        Object::Iri(iri) => iri.as_str().to_string(),
+        Object::BlankNode(bnode) => format!("_:{bnode}"),
+        Object::Literal(lit) => show_literal(lit, |dt| format!("<{dt}>")),
+        Object::Triple {
+            subject,
+            predicate,
+            object,
+        } => format!(
+            "<< {} <{predicate}> {} >>",
+            json_object(subject),
+            json_object(object)
+        ),
+    }
+}
+
+fn compact_node(node: &Node, prefixmap: &PrefixMap) -> String {
+    compact_object(node.as_object(), prefixmap)
+}
+
+fn compact_object(object: &Object, prefixmap: &PrefixMap) -> String {
+    match object {
+        Object::Iri(iri) => prefixmap.qualify(iri),
+        Object::BlankNode(bnode) => format!("_:{bnode}"),
+        Object::Literal(lit) => show_literal(lit, |dt| prefixmap.qualify(dt)),
+        Object::Triple {
+            subject,
+            predicate,
+            object,
+        } => format!(
+            "<< {} {} {} >>",
+            compact_object(subject, prefixmap),
+            prefixmap.qualify(predicate),
+            compact_object(object, prefixmap)
+        ),
+    }
+}
+
+fn show_literal(lit: &Literal, show_datatype: impl Fn(&IriS) -> String) -> String {
+    let (lexical_form, suffix) = match lit {
+        Literal::StringLiteral {
+            lexical_form,
+            lang: None,
+        } => (lexical_form.clone(), String::new()),
+        Literal::StringLiteral {
+            lexical_form,
+            lang: Some(lang),
+        } => (lexical_form.clone(), lang.to_string()),
+        Literal::DatatypeLiteral {
+            lexical_form,
+            datatype,
+        } => {
+            let datatype = match datatype {
+                prefixmap::IriRef::Iri(iri) => show_datatype(iri),
+                prefixmap::IriRef::Prefixed { prefix, local } => format!("{prefix}:{local}"),
+            };
+            (lexical_form.clone(), format!("^^{datatype}"))
+        }
+        Literal::NumericLiteral(n) => {
+            let datatype = match n {
+                NumericLiteral::Integer(_) => XSD_INTEGER_STR,
+                NumericLiteral::Decimal(_) => XSD_DECIMAL_STR,
+                NumericLiteral::Double(_) => XSD_DOUBLE_STR,
+            };
+            let datatype = show_datatype(&IriS::new_unchecked(datatype));
+            (n.to_string(), format!("^^{datatype}"))
+        }
+        Literal::BooleanLiteral(b) => {
+            let datatype = show_datatype(&IriS::new_unchecked(XSD_BOOLEAN_STR));
+            (b.to_string(), format!("^^{datatype}"))
+        }
+    };
+    format!("\"{}\"{suffix}", escape(&lexical_form))
+}
+
+fn escape(str: &str) -> String {
+    str.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
+
+fn json_shape(shape: &ShapeLabel) -> String {
+    match shape {
+        ShapeLabel::Iri(iri) => iri.as_str().to_string(),
+        ShapeLabel::BNode(bnode) => bnode.to_string(),
+        ShapeLabel::Start => "START".to_string(),
+    }
+}
+
+fn parse_json_node(str: &str) -> Result<Node, ResultMapError> {
+    if let Some(bnode) = str.strip_prefix("_:") {
+        Ok(Object::bnode(bnode.to_string()).into())
+    } else if str.starts_with('"') {
+        let literal = ShapeMapParser::parse_literal(str, &PrefixMap::new()).map_err(|e| {
+            ResultMapError::NodeError {
+                node: str.to_string(),
+                error: e.to_string(),
+            }
+        })?;
+        Ok(Object::literal(literal).into())
+    } else {
+        let iri = IriS::from_str(str).map_err(|e| ResultMapError::NodeError {
+            node: str.to_string(),
+            error: e.to_string(),
+        })?;
+        Ok(Node::iri(iri))
+    }
+}
+
+fn parse_json_shape(str: &str) -> Result<ShapeLabel, ResultMapError> {
+    if let Some(bnode) = str.strip_prefix("_:") {
+        Ok(ShapeLabel::from_bnode(BNode::new(bnode)))
+    } else if str == "START" {
+        Ok(ShapeLabel::Start)
+    } else {
+        let iri = IriS::from_str(str).map_err(|e| ResultMapError::ShapeError {
+            shape: str.to_string(),
+            error: e.to_string(),
+        })?;
+        Ok(ShapeLabel::iri(iri))
+    }
 }
 
 fn show_node(node: &Node, prefixmap: &PrefixMap) -> String {
@@ -121,8 +475,8 @@ fn show_node(node: &Node, prefixmap: &PrefixMap) -> String {
 fn show_shapelabel(shapelabel: &ShapeLabel, prefixmap: &PrefixMap) -> String {
     match shapelabel {
         ShapeLabel::Iri(iri) => prefixmap.qualify(iri),
-        ShapeLabel::BNode(str) => format!("_:{str}"),
-        ShapeLabel::Start => "Start".to_owned(),
+        ShapeLabel::BNode(bnode) => bnode.to_string(),
+        ShapeLabel::Start => "START".to_owned(),
     }
 }
 
@@ -167,3 +521,122 @@ impl Display for ResultMap {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use srdf::lang::Lang;
+
+    fn example_prefixmap() -> PrefixMap {
+        let mut pm = PrefixMap::new();
+        pm.insert("", &IriS::new_unchecked("http://example.org/"))
+            .unwrap();
+        pm
+    }
+
+    fn example_result_map() -> ResultMap {
+        let mut result = ResultMap::new()
+            .with_nodes_prefixmap(example_prefixmap())
+            .with_schema_prefixmap(example_prefixmap());
+        let alice = Node::iri(IriS::new_unchecked("http://example.org/alice"));
+        let bob = Node::iri(IriS::new_unchecked("http://example.org/bob"));
+        let name: Node = Object::literal(Literal::lang_str("Bob \"B\"", Lang::new("en"))).into();
+        let person = ShapeLabel::iri(IriS::new_unchecked("http://example.org/Person"));
+        result.add_ok(alice, person.clone(), Vec::new());
+        result.add_fail(
+            bob.clone(),
+            person.clone(),
+            vec![ValidatorError::RbeFailed()],
+        );
+        result.add_pending(name, ShapeLabel::Start);
+        result
+    }
+
+    #[test]
+    fn json_round_trip() {
+        let result = example_result_map();
+        let json = result.as_json().unwrap();
+        let associations = result.associations();
+        assert_eq!(associations[0].node, "\"Bob \\\"B\\\"\"@en");
+        assert_eq!(associations[0].status, ResultStatus::Pending);
+        assert_eq!(associations[1].shape, "http://example.org/Person");
+        assert_eq!(
+            associations[2].reason,
+            Some("Failed regular expression".to_string())
+        );
+        let parsed = ResultMap::from_json_str(json.as_str()).unwrap();
+        assert_eq!(parsed.statuses(), result.statuses());
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let result = example_result_map();
+        let compact = result.show_compact();
+        assert_eq!(
+            compact,
+            "\"Bob \\\"B\\\"\"@en@?START,\n:alice@:Person,\n:bob@!:Person \"Failed regular expression\""
+        );
+        let parsed = ResultMap::from_compact_str(
+            compact.as_str(),
+            &example_prefixmap(),
+            &example_prefixmap(),
+        )
+        .unwrap();
+        assert_eq!(parsed.statuses(), result.statuses());
+    }
+
+    #[test]
+    fn parse_compact_literals() {
+        let str = r#"# Expected results
+            "23"^^<http://www.w3.org/2001/XMLSchema#integer>@:S,
+            23 @!:S "Not a string", "hi"@:T,
+            <http://example.org/x>@<http://example.org/T>"#;
+        let parsed =
+            ResultMap::from_compact_str(str, &example_prefixmap(), &example_prefixmap()).unwrap();
+        let s = ShapeLabel::iri(IriS::new_unchecked("http://example.org/S"));
+        let t = ShapeLabel::iri(IriS::new_unchecked("http://example.org/T"));
+        let n23: Node = Object::literal(Literal::datatype(
+            "23",
+            &prefixmap::IriRef::Iri(IriS::new_unchecked(XSD_INTEGER_STR)),
+        ))
+        .into();
+        let hi: Node = Object::literal(Literal::str("hi")).into();
+        assert!(parsed.is_ok(&n23, &s));
+        assert!(parsed.is_failed(&n23, &s));
+        assert!(parsed.is_ok(&hi, &t));
+        assert!(parsed.is_ok(&Node::iri(IriS::new_unchecked("http://example.org/x")), &t));
+    }
+
+    #[test]
+    fn compact_round_trip_literals_and_start() {
+        let mut result = ResultMap::new()
+            .with_nodes_prefixmap(example_prefixmap())
+            .with_schema_prefixmap(example_prefixmap());
+        let x: Node = Object::literal(Literal::str("x")).into();
+        let start: Node = Object::literal(Literal::lang_str("y", Lang::new("START"))).into();
+        let n23: Node = Object::literal(Literal::datatype(
+            "23",
+            &prefixmap::IriRef::Iri(IriS::new_unchecked(XSD_INTEGER_STR)),
+        ))
+        .into();
+        let bnode: Node = Object::bnode("b0".to_string()).into();
+        let s = ShapeLabel::iri(IriS::new_unchecked("http://example.org/S"));
+        result.add_ok(x.clone(), ShapeLabel::Start, Vec::new());
+        result.add_pending(x, s.clone());
+        result.add_fail(start, ShapeLabel::Start, Vec::new());
+        result.add_ok(n23, s.clone(), Vec::new());
+        result.add_pending(bnode, s);
+        let compact = result.show_compact();
+        assert_eq!(
+            compact,
+            "\"23\"^^<http://www.w3.org/2001/XMLSchema#integer>@:S,\n\"x\"@?:S,\n\"x\"@START,\n\"y\"@start@!START,\n_:b0@?:S"
+        );
+        let parsed = ResultMap::from_compact_str(
+            compact.as_str(),
+            &example_prefixmap(),
+            &example_prefixmap(),
+        )
+        .unwrap();
+        assert_eq!(parsed.statuses(), result.statuses());
+    }
+}
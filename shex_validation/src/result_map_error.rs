@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone)]
+pub enum ResultMapError {
+    #[error("Error parsing JSON result shapemap: {error}")]
+    JsonError { error: String },
+
+    #[error("Error serializing result shapemap to JSON: {error}")]
+    JsonSerializationError { error: String },
+
+    #[error("Error parsing result shapemap: {error}")]
+    CompactSyntaxError { error: String },
+
+    #[error("Error parsing node {node} in result shapemap: {error}")]
+    NodeError { node: String, error: String },
+
+    #[error("Error parsing shape {shape} in result shapemap: {error}")]
+    ShapeError { shape: String, error: String },
+}
@@ -0,0 +1,33 @@
+use std::fmt::Display;
+
+use shex_ast::{compiled::shape_label::ShapeLabel, Node, Pred};
+
+/// Successful match of a node against a shape of the schema
+///
+/// `arcs` are the outgoing arcs of the node matched by the triple constraints of the shape,
+/// `inverse_arcs` the incoming arcs matched by inverse triple constraints as pairs of predicate and subject,
+/// and `refs` the nodes that conform to the shapes referenced by those triple constraints
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShapeMatch {
+    pub node: Node,
+    pub shape: ShapeLabel,
+    pub arcs: Vec<(Pred, Node)>,
+    pub inverse_arcs: Vec<(Pred, Node)>,
+    pub refs: Vec<(Node, ShapeLabel)>,
+}
+
+impl ShapeMatch {
+    /// Shape that the value of an arc has been matched against, if the triple constraint references one
+    pub fn ref_shape(&self, value: &Node) -> Option<&ShapeLabel> {
+        self.refs
+            .iter()
+            .find(|(node, _)| node == value)
+            .map(|(_, shape)| shape)
+    }
+}
+
+impl Display for ShapeMatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}@{}", self.node, self.shape)
+    }
+}
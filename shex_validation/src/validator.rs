@@ -5,37 +5,59 @@ use crate::validator_runner::ValidatorRunner;
 use crate::PosAtom;
 use crate::Reason;
 use crate::ResultValue;
+use crate::ShapeMatch;
+use crate::TypeShape;
 use crate::ValidatorConfig;
 use either::Either;
-use prefixmap::IriRef;
+use indexmap::IndexSet;
+use iri_s::IriS;
 use prefixmap::PrefixMap;
 use shapemap::query_shape_map::QueryShapeMap;
 use shex_ast::compiled::compiled_schema::CompiledSchema;
 use shex_ast::compiled::shape_expr::ShapeExpr;
 use shex_ast::compiled::shape_label::ShapeLabel;
-use shex_ast::object_value::ObjectValue;
+use shex_ast::BNode;
 use shex_ast::Node;
+use shex_ast::Pred;
 use shex_ast::ShapeExprLabel;
 use shex_ast::ShapeLabelIdx;
-use srdf::SRDF;
+use srdf::{NodeChecks, QuerySRDF, RDF_TYPE, SRDF};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Arc;
 use tracing::debug;
 
 type Result<T> = std::result::Result<T, ValidatorError>;
 type Atom = atom::Atom<(Node, ShapeLabelIdx)>;
 
 pub struct Validator {
-    schema: CompiledSchema,
+    schema: Arc<CompiledSchema>,
     runner: ValidatorRunner,
+    type_shapes: Vec<TypeShape>,
+    external_shapes: NodeChecks,
 }
 
 impl Validator {
-    pub fn new(schema: CompiledSchema, config: &ValidatorConfig) -> Validator {
+    /// Creates a validator for a compiled schema
+    ///
+    /// The schema can be given in an [`Arc`] to share it between validators without copying it
+    pub fn new(schema: impl Into<Arc<CompiledSchema>>, config: &ValidatorConfig) -> Validator {
         Validator {
-            schema,
+            schema: schema.into(),
             runner: ValidatorRunner::new(config),
+            type_shapes: config.type_shapes().clone(),
+            external_shapes: NodeChecks::new(),
         }
     }
 
+    /// Checks implemented in Rust for the `EXTERNAL` shapes of the schema, keyed by their labels
+    ///
+    /// External shapes without a check are assumed to pass
+    pub fn with_external_shapes(mut self, external_shapes: NodeChecks) -> Self {
+        self.external_shapes = external_shapes;
+        self
+    }
+
     /// validate a node against a shape label
     pub fn validate_node_shape<S>(&mut self, node: &Node, shape: &ShapeLabel, rdf: &S) -> Result<()>
     where
@@ -57,39 +79,124 @@ impl Validator {
             })
     }
 
+    /// validate the associations of a shapemap, expanding its node selectors in `rdf`
     pub fn validate_shapemap<S>(&mut self, shapemap: &QueryShapeMap, rdf: &S) -> Result<()>
     where
         S: SRDF,
     {
-        self.fill_pending(shapemap, rdf)?;
+        let node_shapes = shapemap.select_node_shape(rdf)?;
+        self.fill_pending(node_shapes)?;
+        self.loop_validating(rdf)?;
+        Ok(())
+    }
+
+    /// Same as [`Validator::validate_shapemap`] for RDF sources that support SPARQL node selectors
+    pub fn validate_shapemap_query<S>(&mut self, shapemap: &QueryShapeMap, rdf: &S) -> Result<()>
+    where
+        S: SRDF + QuerySRDF,
+    {
+        let node_shapes = shapemap.select_node_shape_query(rdf)?;
+        self.fill_pending(node_shapes)?;
         self.loop_validating(rdf)?;
         Ok(())
     }
 
-    fn fill_pending<S>(&mut self, shapemap: &QueryShapeMap, rdf: &S) -> Result<()>
+    /// validate every node whose `rdf:type` is declared in the type/shape mappings of the config
+    pub fn validate_type_shapes<S>(&mut self, rdf: &S) -> Result<()>
     where
         S: SRDF,
     {
-        for (node_value, label) in shapemap.iter_node_shape(rdf) {
-            let idx = self.get_shape_expr_label(label)?;
-            let node = self.node_from_object_value(node_value, rdf)?;
-            self.runner.add_pending(node.clone(), idx);
+        if self.type_shapes.is_empty() {
+            return Err(ValidatorError::NoTypeShapes);
         }
+        let rdf_type = S::iri_s2iri(&RDF_TYPE);
+        for type_shape in self.type_shapes.clone() {
+            let type_iri = self.resolve_iri(&type_shape.type_)?;
+            let label = self.resolve_shape_label(&type_shape.shape)?;
+            let idx = self.get_shape_expr_label(&label)?;
+            let instances = rdf
+                .subjects_with_predicate_object(&rdf_type, &S::iri_s2term(&type_iri))
+                .map_err(|e| ValidatorError::SRDFError {
+                    error: e.to_string(),
+                })?;
+            for subject in instances {
+                let node = Node::from(S::subject_as_object(&subject));
+                self.runner.add_pending(node, idx);
+            }
+        }
+        self.loop_validating(rdf)?;
         Ok(())
     }
 
-    fn node_from_object_value<S>(&mut self, value: &ObjectValue, rdf: &S) -> Result<Node>
+    /// validate every subject of `rdf` against the START shape
+    pub fn validate_subjects_start<S>(&mut self, rdf: &S) -> Result<()>
     where
         S: SRDF,
     {
-        match value {
-            ObjectValue::IriRef(IriRef::Iri(iri)) => Ok(Node::iri(iri.clone())),
-            ObjectValue::IriRef(IriRef::Prefixed { prefix, local }) => {
-                let iri = rdf.resolve_prefix_local(prefix, local)?;
-                Ok(Node::iri(iri.clone()))
+        let idx = self.get_shape_expr_label(&ShapeExprLabel::Start)?;
+        for node in subject_nodes(rdf)? {
+            self.runner.add_pending(node, idx);
+        }
+        self.loop_validating(rdf)?;
+        Ok(())
+    }
+
+    /// validate every subject of `rdf` against every shape of the schema
+    ///
+    /// [`ResultMap::best_shapes`] can be used to keep only the shapes that each node conforms to
+    pub fn validate_best_shapes<S>(&mut self, rdf: &S) -> Result<()>
+    where
+        S: SRDF,
+    {
+        let idxs: Vec<ShapeLabelIdx> = self
+            .schema
+            .existing_labels()
+            .into_iter()
+            .filter(|label| **label != ShapeLabel::Start)
+            .filter_map(|label| self.schema.find_shape_label_idx(label).copied())
+            .collect();
+        for node in subject_nodes(rdf)? {
+            for idx in &idxs {
+                self.runner.add_pending(node.clone(), *idx);
+            }
+        }
+        self.loop_validating(rdf)?;
+        Ok(())
+    }
+
+    fn resolve_iri(&self, str: &str) -> Result<IriS> {
+        let str = str
+            .strip_prefix('<')
+            .and_then(|s| s.strip_suffix('>'))
+            .unwrap_or(str);
+        let prefixmap = self.schema.prefixmap();
+        if let Some((prefix, local)) = str.split_once(':') {
+            if let Ok(iri) = prefixmap.resolve_prefix_local(prefix, local) {
+                return Ok(iri);
             }
-            ObjectValue::Literal(_lit) => todo!(),
         }
+        IriS::from_str(str).map_err(|e| ValidatorError::TypeShapeIriError {
+            value: str.to_string(),
+            error: e.to_string(),
+        })
+    }
+
+    fn resolve_shape_label(&self, str: &str) -> Result<ShapeExprLabel> {
+        if str.eq_ignore_ascii_case("START") {
+            Ok(ShapeExprLabel::Start)
+        } else if let Some(bnode) = str.strip_prefix("_:") {
+            Ok(ShapeExprLabel::bnode(BNode::new(bnode)))
+        } else {
+            Ok(ShapeExprLabel::iri(self.resolve_iri(str)?))
+        }
+    }
+
+    fn fill_pending(&mut self, node_shapes: Vec<(Node, &ShapeExprLabel)>) -> Result<()> {
+        for (node, label) in node_shapes {
+            let idx = self.get_shape_expr_label(label)?;
+            self.runner.add_pending(node, idx);
+        }
+        Ok(())
     }
 
     fn loop_validating<S>(&mut self, rdf: &S) -> Result<()>
@@ -126,7 +233,12 @@ impl Validator {
         let (node, idx) = atom.get_value();
         let se = find_shape_idx(idx, &self.schema);
         match atom {
-            Atom::Pos { .. } => self.runner.check_node_shape_expr(node, se, rdf),
+            Atom::Pos { .. } => match se {
+                ShapeExpr::External {} => {
+                    Ok(self.check_node_external(node, self.get_shape_label(idx)?))
+                }
+                _ => self.runner.check_node_shape_expr(node, se, rdf),
+            },
             Atom::Neg { .. } => {
                 // Check if a node doesn't conform to a shape expr
                 todo!()
@@ -134,6 +246,30 @@ impl Validator {
         }
     }
 
+    fn check_node_external(
+        &self,
+        node: &Node,
+        label: &ShapeLabel,
+    ) -> Either<Vec<ValidatorError>, Vec<Reason>> {
+        let ShapeLabel::Iri(shape) = label else {
+            return Either::Right(Vec::new());
+        };
+        let Some(check) = self.external_shapes.get(shape) else {
+            return Either::Right(Vec::new());
+        };
+        match check.check(node.as_object(), &HashMap::new()) {
+            Ok(()) => Either::Right(vec![Reason::ExternalShapePassed {
+                node: node.clone(),
+                shape: shape.clone(),
+            }]),
+            Err(msg) => Either::Left(vec![ValidatorError::ExternalShapeFailed {
+                node: node.clone(),
+                shape: shape.clone(),
+                msg,
+            }]),
+        }
+    }
+
     pub fn get_result(&self, node: &Node, shape: &ShapeLabel) -> Result<ResultValue> {
         if let Some(idx) = self.schema.find_shape_label_idx(shape) {
             let pos_atom = PosAtom::new((node.clone(), *idx));
@@ -169,7 +305,8 @@ impl Validator {
         let mut result = match maybe_nodes_prefixmap {
             None => ResultMap::new(),
             Some(pm) => ResultMap::new().with_nodes_prefixmap(pm),
-        };
+        }
+        .with_schema_prefixmap(self.schema.prefixmap());
         for atom in &self.runner.checked() {
             let (node, idx) = atom.get_value();
             let label = self.get_shape_label(idx)?;
@@ -192,6 +329,110 @@ impl Validator {
         // TODO: Should I also add processing nodes as pending?
         Ok(result)
     }
+
+    /// Triples that took part in the successful matches of the validated nodes
+    ///
+    /// Only the node/shape pairs requested to the validator that conform are taken into account,
+    /// and the references to other shapes are followed recursively, so the result is the subgraph selected by the schema.
+    /// A node is skipped if some node it references doesn't conform to the referenced shape
+    pub fn conforming_triples(&self) -> Vec<(Node, Pred, Node)> {
+        let mut triples = IndexSet::new();
+        for pa in self.conforming_atoms() {
+            for reason in self.runner.find_reasons(&pa) {
+                triples.extend(reason.triples())
+            }
+        }
+        triples.into_iter().collect()
+    }
+
+    /// Successful matches of the validated nodes and of the nodes they reference
+    ///
+    /// It follows the same rules as [`Validator::conforming_triples`], so only the matches of conforming roots are returned
+    pub fn shape_matches(&self) -> Result<Vec<ShapeMatch>> {
+        let mut matches = Vec::new();
+        for pa in self.conforming_atoms() {
+            let atom = Atom::pos(&pa);
+            let (node, idx) = atom.get_value();
+            let mut arcs = Vec::new();
+            let mut inverse_arcs = Vec::new();
+            let mut refs = Vec::new();
+            for reason in self.runner.find_reasons(&pa) {
+                for (pred, value) in reason.arcs() {
+                    if pred.is_inverse() {
+                        inverse_arcs.push((Pred::from(pred.iri().clone()), value))
+                    } else {
+                        arcs.push((pred, value))
+                    }
+                }
+                for (value, ref_idx) in reason.refs() {
+                    refs.push((value, self.get_shape_label(&ref_idx)?.clone()))
+                }
+            }
+            matches.push(ShapeMatch {
+                node: node.clone(),
+                shape: self.get_shape_label(idx)?.clone(),
+                arcs,
+                inverse_arcs,
+                refs,
+            })
+        }
+        Ok(matches)
+    }
+
+    /// Node/shape pairs requested to the validator that conform, following the same rules as [`Validator::conforming_triples`]
+    pub fn conforming_roots(&self) -> Result<Vec<(Node, ShapeLabel)>> {
+        let mut roots = Vec::new();
+        for root in self.runner.roots() {
+            if self.matched_atoms(root).is_some() {
+                let atom = Atom::pos(root);
+                let (node, idx) = atom.get_value();
+                roots.push((node.clone(), self.get_shape_label(idx)?.clone()))
+            }
+        }
+        Ok(roots)
+    }
+
+    /// Node/shape pairs reachable from the conforming roots
+    fn conforming_atoms(&self) -> IndexSet<PosAtom<(Node, ShapeLabelIdx)>> {
+        let mut atoms = IndexSet::new();
+        for root in self.runner.roots() {
+            if let Some(root_atoms) = self.matched_atoms(root) {
+                atoms.extend(root_atoms)
+            }
+        }
+        atoms
+    }
+
+    /// Node/shape pairs referenced transitively by a node/shape pair, or `None` if some of them doesn't conform
+    fn matched_atoms(
+        &self,
+        root: &PosAtom<(Node, ShapeLabelIdx)>,
+    ) -> Option<IndexSet<PosAtom<(Node, ShapeLabelIdx)>>> {
+        let mut visited = IndexSet::new();
+        let mut todo = vec![root.clone()];
+        while let Some(pa) = todo.pop() {
+            if !visited.insert(pa.clone()) {
+                continue;
+            }
+            if self.runner.get_result(&Atom::pos(&pa)) != ResultValue::Ok {
+                return None;
+            }
+            for reason in self.runner.find_reasons(&pa) {
+                todo.extend(reason.refs().into_iter().map(PosAtom::new));
+            }
+        }
+        Some(visited)
+    }
+}
+
+fn subject_nodes<S: SRDF>(rdf: &S) -> Result<Vec<Node>> {
+    let subjects = rdf.subjects().map_err(|e| ValidatorError::SRDFError {
+        error: e.to_string(),
+    })?;
+    Ok(subjects
+        .iter()
+        .map(|subject| Node::from(S::subject_as_object(subject)))
+        .collect())
 }
 
 fn find_shape_idx<'a>(idx: &'a ShapeLabelIdx, schema: &'a CompiledSchema) -> &'a ShapeExpr {
@@ -200,4 +441,304 @@ fn find_shape_idx<'a>(idx: &'a ShapeLabelIdx, schema: &'a CompiledSchema) -> &'a
 }
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use super::*;
+    use shex_ast::ast::Schema as SchemaJson;
+    use srdf::{srdf_graph::SRDFGraph, RDFFormat, ReaderMode};
+
+    fn example_validator(config: &ValidatorConfig) -> Validator {
+        let str = r#"{
+            "@context": "http://www.w3.org/ns/shex.jsonld",
+            "type": "Schema",
+            "start": "http://example.org/Named",
+            "shapes": [
+              { "type": "ShapeDecl",
+                "id": "http://example.org/Named",
+                "shapeExpr": {
+                  "type": "Shape",
+                  "expression": { "type": "TripleConstraint", "predicate": "http://example.org/name" }
+                }
+              },
+              { "type": "ShapeDecl",
+                "id": "http://example.org/Worker",
+                "shapeExpr": {
+                  "type": "Shape",
+                  "expression": { "type": "TripleConstraint", "predicate": "http://example.org/worksFor" }
+                }
+              }
+            ]
+        }"#;
+        let schema_json: SchemaJson = serde_json::from_str(str).unwrap();
+        let mut schema = CompiledSchema::new();
+        schema.from_schema_json(&schema_json).unwrap();
+        Validator::new(schema, config)
+    }
+
+    fn example_data() -> SRDFGraph {
+        let str = r#"prefix : <http://example.org/>
+            :alice a :Person ; :name "Alice" ; :worksFor :acme .
+            :bob a :Person ; :worksFor :acme ."#;
+        SRDFGraph::from_str(str, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap()
+    }
+
+    fn ex(name: &str) -> IriS {
+        IriS::new_unchecked(format!("http://example.org/{name}").as_str())
+    }
+
+    #[test]
+    fn validate_type_shapes() {
+        let mut config = ValidatorConfig::default();
+        config.add_type_shape(TypeShape::new(
+            "http://example.org/Person",
+            "<http://example.org/Named>",
+        ));
+        let mut validator = example_validator(&config);
+        validator.validate_type_shapes(&example_data()).unwrap();
+        let named = ShapeLabel::iri(ex("Named"));
+        assert_eq!(
+            validator
+                .get_result(&Node::iri(ex("alice")), &named)
+                .unwrap(),
+            ResultValue::Ok
+        );
+        assert_eq!(
+            validator.get_result(&Node::iri(ex("bob")), &named).unwrap(),
+            ResultValue::Failed
+        );
+    }
+
+    #[test]
+    fn validate_subjects_start() {
+        let mut validator = example_validator(&ValidatorConfig::default());
+        validator.validate_subjects_start(&example_data()).unwrap();
+        let result_map = validator.result_map(None).unwrap();
+        assert_eq!(result_map.statuses().len(), 2);
+        assert!(result_map.is_ok(&Node::iri(ex("alice")), &ShapeLabel::iri(ex("Named"))));
+    }
+
+    #[test]
+    fn validate_best_shapes() {
+        let mut validator = example_validator(&ValidatorConfig::default());
+        validator.validate_best_shapes(&example_data()).unwrap();
+        let result_map = validator.result_map(None).unwrap().best_shapes();
+        let bob = Node::iri(ex("bob"));
+        assert!(result_map.is_ok(&bob, &ShapeLabel::iri(ex("Worker"))));
+        assert!(!result_map.is_failed(&bob, &ShapeLabel::iri(ex("Named"))));
+    }
+
+    #[test]
+    fn conforming_triples_follow_references() {
+        let str = r#"{
+            "@context": "http://www.w3.org/ns/shex.jsonld",
+            "type": "Schema",
+            "shapes": [
+              { "type": "ShapeDecl",
+                "id": "http://example.org/Person",
+                "shapeExpr": {
+                  "type": "Shape",
+                  "expression": { "type": "EachOf", "expressions": [
+                    { "type": "TripleConstraint", "predicate": "http://example.org/name" },
+                    { "type": "TripleConstraint", "predicate": "http://example.org/worksFor",
+                      "valueExpr": "http://example.org/Company" }
+                  ]}
+                }
+              },
+              { "type": "ShapeDecl",
+                "id": "http://example.org/Company",
+                "shapeExpr": {
+                  "type": "Shape",
+                  "expression": { "type": "TripleConstraint", "predicate": "http://example.org/name" }
+                }
+              }
+            ]
+        }"#;
+        let schema_json: SchemaJson = serde_json::from_str(str).unwrap();
+        let mut schema = CompiledSchema::new();
+        schema.from_schema_json(&schema_json).unwrap();
+        let mut validator = Validator::new(schema, &ValidatorConfig::default());
+        let data = r#"prefix : <http://example.org/>
+            :alice :name "Alice" ; :worksFor :acme ; :age 30 .
+            :acme :name "ACME" ; :location :spain .
+            :bob :name "Bob" .
+            :carol :name "Carol" ; :worksFor :unknown ."#;
+        let rdf = SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        for name in ["alice", "carol"] {
+            validator
+                .validate_node_shape(&Node::iri(ex(name)), &ShapeLabel::iri(ex("Person")), &rdf)
+                .unwrap();
+        }
+        let mut triples: Vec<String> = validator
+            .conforming_triples()
+            .iter()
+            .map(|(s, p, o)| format!("{s} {p} {o}"))
+            .collect();
+        triples.sort();
+        assert_eq!(
+            triples,
+            vec![
+                "http://example.org/acme http://example.org/name \"ACME\"",
+                "http://example.org/alice http://example.org/name \"Alice\"",
+                "http://example.org/alice http://example.org/worksFor http://example.org/acme",
+            ]
+        );
+        let matches = validator.shape_matches().unwrap();
+        let alice = matches
+            .iter()
+            .find(|m| m.node == Node::iri(ex("alice")))
+            .unwrap();
+        assert_eq!(alice.shape, ShapeLabel::iri(ex("Person")));
+        assert_eq!(alice.arcs.len(), 2);
+        assert_eq!(
+            alice.ref_shape(&Node::iri(ex("acme"))),
+            Some(&ShapeLabel::iri(ex("Company")))
+        );
+        assert!(matches.iter().all(|m| m.node != Node::iri(ex("carol"))));
+    }
+
+    #[test]
+    fn validate_external_shapes() {
+        let str = r#"{
+            "@context": "http://www.w3.org/ns/shex.jsonld",
+            "type": "Schema",
+            "shapes": [
+              { "type": "ShapeDecl",
+                "id": "http://example.org/Book",
+                "shapeExpr": {
+                  "type": "Shape",
+                  "expression": { "type": "TripleConstraint", "predicate": "http://example.org/isbn",
+                    "valueExpr": "http://example.org/ISBN" }
+                }
+              },
+              { "type": "ShapeDecl",
+                "id": "http://example.org/ISBN",
+                "shapeExpr": { "type": "ShapeExternal" }
+              }
+            ]
+        }"#;
+        let schema_json: SchemaJson = serde_json::from_str(str).unwrap();
+        let mut schema = CompiledSchema::new();
+        schema.from_schema_json(&schema_json).unwrap();
+        let isbn = NodeChecks::new().with_check(
+            ex("ISBN"),
+            |node: &srdf::Object, _: &HashMap<IriS, srdf::Object>| match node {
+                srdf::Object::Iri(iri) if iri.as_str().starts_with("urn:isbn:") => Ok(()),
+                _ => Err(format!("{node} is not an ISBN URN")),
+            },
+        );
+        let mut validator =
+            Validator::new(schema, &ValidatorConfig::default()).with_external_shapes(isbn);
+        let data = r#"prefix : <http://example.org/>
+            :dune :isbn <urn:isbn:0441013597> .
+            :draft :isbn :unknown ."#;
+        let rdf = SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let book = ShapeLabel::iri(ex("Book"));
+        for name in ["dune", "draft"] {
+            validator
+                .validate_node_shape(&Node::iri(ex(name)), &book, &rdf)
+                .unwrap();
+        }
+        let result_map = validator.result_map(None).unwrap();
+        let isbn = ShapeLabel::iri(ex("ISBN"));
+        let urn = Node::iri(IriS::new_unchecked("urn:isbn:0441013597"));
+        assert!(result_map.is_ok(&urn, &isbn));
+        assert!(result_map.is_failed(&Node::iri(ex("unknown")), &isbn));
+        let matches = validator.shape_matches().unwrap();
+        assert!(matches.iter().any(|m| m.node == Node::iri(ex("dune"))));
+        assert!(matches.iter().all(|m| m.node != Node::iri(ex("draft"))));
+    }
+
+    #[test]
+    fn validate_triple_node_kind() {
+        let str = r#"{
+            "@context": "http://www.w3.org/ns/shex.jsonld",
+            "type": "Schema",
+            "shapes": [
+              { "type": "ShapeDecl",
+                "id": "http://example.org/Claimant",
+                "shapeExpr": {
+                  "type": "Shape",
+                  "expression": { "type": "TripleConstraint", "predicate": "http://example.org/claims",
+                    "valueExpr": { "type": "NodeConstraint", "nodeKind": "triple" } }
+                }
+              }
+            ]
+        }"#;
+        let schema_json: SchemaJson = serde_json::from_str(str).unwrap();
+        let mut schema = CompiledSchema::new();
+        schema.from_schema_json(&schema_json).unwrap();
+        let mut validator = Validator::new(schema, &ValidatorConfig::default());
+        let data = r#"prefix : <http://example.org/>
+            :alice :claims << :bob :name "Bob" >> .
+            :carol :claims :bob ."#;
+        let rdf = SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let claimant = ShapeLabel::iri(ex("Claimant"));
+        for name in ["alice", "carol"] {
+            validator
+                .validate_node_shape(&Node::iri(ex(name)), &claimant, &rdf)
+                .unwrap();
+        }
+        let result_map = validator.result_map(None).unwrap();
+        assert!(result_map.is_ok(&Node::iri(ex("alice")), &claimant));
+        assert!(result_map.is_failed(&Node::iri(ex("carol")), &claimant));
+    }
+
+    #[test]
+    fn validate_inverse_triple_constraints() {
+        let str = r#"{
+            "@context": "http://www.w3.org/ns/shex.jsonld",
+            "type": "Schema",
+            "shapes": [
+              { "type": "ShapeDecl",
+                "id": "http://example.org/Company",
+                "shapeExpr": {
+                  "type": "Shape",
+                  "closed": true,
+                  "expression": { "type": "EachOf", "expressions": [
+                    { "type": "TripleConstraint", "predicate": "http://example.org/name" },
+                    { "type": "TripleConstraint", "inverse": true, "predicate": "http://example.org/worksFor",
+                      "min": 1, "max": -1 }
+                  ]}
+                }
+              }
+            ]
+        }"#;
+        let schema_json: SchemaJson = serde_json::from_str(str).unwrap();
+        let mut schema = CompiledSchema::new();
+        schema.from_schema_json(&schema_json).unwrap();
+        let mut validator = Validator::new(schema, &ValidatorConfig::default());
+        let data = r#"prefix : <http://example.org/>
+            :alice :worksFor :acme .
+            :bob :worksFor :acme .
+            :acme :name "ACME" .
+            :empty :name "Empty" ."#;
+        let rdf = SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let company = ShapeLabel::iri(ex("Company"));
+        for name in ["acme", "empty"] {
+            validator
+                .validate_node_shape(&Node::iri(ex(name)), &company, &rdf)
+                .unwrap();
+        }
+        // Incoming arcs don't count for closed shapes
+        let result_map = validator.result_map(None).unwrap();
+        assert!(result_map.is_ok(&Node::iri(ex("acme")), &company));
+        assert!(result_map.is_failed(&Node::iri(ex("empty")), &company));
+        let mut triples: Vec<String> = validator
+            .conforming_triples()
+            .iter()
+            .map(|(s, p, o)| format!("{s} {p} {o}"))
+            .collect();
+        triples.sort();
+        assert_eq!(
+            triples,
+            vec![
+                "http://example.org/acme http://example.org/name \"ACME\"",
+                "http://example.org/alice http://example.org/worksFor http://example.org/acme",
+                "http://example.org/bob http://example.org/worksFor http://example.org/acme",
+            ]
+        );
+        let matches = validator.shape_matches().unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].arcs.len(), 1);
+        assert_eq!(matches[0].inverse_arcs.len(), 2);
+    }
+}
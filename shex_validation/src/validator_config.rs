@@ -8,12 +8,37 @@ use crate::{ValidatorError, MAX_STEPS};
 
 pub struct ValidatorConfig {
     max_steps: usize,
+
+    /// Shapes that the instances of some classes must conform to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    type_shapes: Vec<TypeShape>,
+}
+
+/// Declares that every node with `rdf:type` `type` must conform to `shape`
+///
+/// Both values can be IRIs, IRIs between `<` and `>` or prefixed names declared in the schema.
+/// The shape can also be `START`.
+#[derive(Deserialize, Serialize, Debug, PartialEq, Clone)]
+pub struct TypeShape {
+    #[serde(rename = "type")]
+    pub type_: String,
+    pub shape: String,
+}
+
+impl TypeShape {
+    pub fn new(type_: &str, shape: &str) -> TypeShape {
+        TypeShape {
+            type_: type_.to_string(),
+            shape: shape.to_string(),
+        }
+    }
 }
 
 impl Default for ValidatorConfig {
     fn default() -> Self {
         Self {
             max_steps: MAX_STEPS,
+            type_shapes: Vec::new(),
         }
     }
 }
@@ -43,4 +68,12 @@ impl ValidatorConfig {
     pub fn max_steps(&self) -> usize {
         self.max_steps
     }
+
+    pub fn add_type_shape(&mut self, type_shape: TypeShape) {
+        self.type_shapes.push(type_shape);
+    }
+
+    pub fn type_shapes(&self) -> &Vec<TypeShape> {
+        &self.type_shapes
+    }
 }
@@ -1,7 +1,9 @@
 use std::fmt::Display;
 
+use iri_s::IriS;
 use prefixmap::PrefixMapError;
 use rbe::RbeError;
+use shapemap::NodeSelectorError;
 use shex_ast::compiled::preds::Preds;
 use shex_ast::compiled::shape_expr::ShapeExpr;
 use shex_ast::{
@@ -37,12 +39,22 @@ pub enum ValidatorError {
     #[error(transparent)]
     PrefixMapError(#[from] PrefixMapError),
 
+    #[error(transparent)]
+    NodeSelectorError(#[from] NodeSelectorError),
+
     #[error("ShapeLabel not found {shape_label:?}: {err}")]
     ShapeLabelNotFoundError {
         shape_label: ShapeExprLabel,
         err: Box<CompiledSchemaError>,
     },
 
+    #[error("External shape {shape} failed for node {node}: {msg}")]
+    ExternalShapeFailed {
+        node: Node,
+        shape: IriS,
+        msg: String,
+    },
+
     #[error("And error: shape expression {shape_expr} failed for node {node}: {errors}")]
     ShapeAndError {
         shape_expr: ShapeExpr,
@@ -55,6 +67,12 @@ pub enum ValidatorError {
 
     #[error("Error reading config file from path {path}: {error}")]
     ValidatorConfigYamlError { path: String, error: String },
+
+    #[error("Error parsing {value} from type/shape mappings as IRI: {error}")]
+    TypeShapeIriError { value: String, error: String },
+
+    #[error("No type/shape mappings declared in the validator config")]
+    NoTypeShapes,
 }
 
 #[derive(Debug, Clone)]
@@ -5,7 +5,6 @@ use crate::ResultValue;
 use crate::ValidatorConfig;
 use either::Either;
 use indexmap::IndexSet;
-use iri_s::IriS;
 use rbe::MatchTableIter;
 use shex_ast::compiled::preds::Preds;
 use shex_ast::compiled::shape::Shape;
@@ -13,7 +12,7 @@ use shex_ast::compiled::shape_expr::ShapeExpr;
 use shex_ast::Node;
 use shex_ast::Pred;
 use shex_ast::ShapeLabelIdx;
-use srdf::{Object, SRDF};
+use srdf::SRDF;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use tracing::debug;
@@ -30,6 +29,7 @@ pub struct ValidatorRunner {
     checked: IndexSet<Atom>,
     processing: IndexSet<Atom>,
     pending: IndexSet<Atom>,
+    roots: IndexSet<PosAtom>,
     //rules: Vec<Rule>,
     alternative_match_iterators: Vec<MatchTableIter<Pred, Node, ShapeLabelIdx>>,
     // alternatives: Vec<ResultMap<Node, ShapeLabelIdx>>,
@@ -51,6 +51,7 @@ impl ValidatorRunner {
             checked: IndexSet::new(),
             processing: IndexSet::new(),
             pending: IndexSet::new(),
+            roots: IndexSet::new(),
             //rules: Vec::new(),
             alternative_match_iterators: Vec::new(),
             config: config.clone(),
@@ -170,6 +171,12 @@ impl ValidatorRunner {
         // self.result_map.add_pending(n, s);
         let pos_atom = PosAtom::new((n, s));
         self.pending.insert(Atom::pos(&pos_atom));
+        self.roots.insert(pos_atom);
+    }
+
+    /// Node/shape pairs requested to the validator, the other ones are added while checking references
+    pub(crate) fn roots(&self) -> &IndexSet<PosAtom> {
+        &self.roots
     }
 
     pub fn pop_pending(&mut self) -> Option<Atom> {
@@ -232,19 +239,24 @@ impl ValidatorRunner {
                 todo!()
             }
             ShapeExpr::ShapeAnd { exprs, .. } => {
+                let mut reasons = Vec::new();
                 for e in exprs {
                     let result = self.check_node_shape_expr(node, e, rdf)?;
-                    if let Some(errors) = result.left() {
-                        return Ok(Either::Left(vec![ValidatorError::ShapeAndError {
-                            shape_expr: e.clone(),
-                            node: node.clone(),
-                            errors: ValidatorErrors::new(errors),
-                        }]));
+                    match result {
+                        Either::Left(errors) => {
+                            return Ok(Either::Left(vec![ValidatorError::ShapeAndError {
+                                shape_expr: e.clone(),
+                                node: node.clone(),
+                                errors: ValidatorErrors::new(errors),
+                            }]));
+                        }
+                        Either::Right(rs) => reasons.extend(rs),
                     }
                 }
                 Ok(Either::Right(vec![Reason::ShapeAndPassed {
                     node: node.clone(),
                     se: se.clone(),
+                    reasons,
                 }]))
             }
             ShapeExpr::ShapeNot { expr, .. } => {
@@ -283,7 +295,7 @@ impl ValidatorRunner {
     where
         S: SRDF,
     {
-        let (values, remainder) = self.neighs(node, shape.preds(), rdf)?;
+        let (values, remainder) = self.neighs(node, shape, rdf)?;
         if shape.is_closed() && !remainder.is_empty() {
             let errs = vec![ValidatorError::ClosedShapeWithRemainderPreds {
                 remainder: Preds::new(remainder),
@@ -301,10 +313,11 @@ impl ValidatorRunner {
             return Ok(Either::Left(errs));
         };
         debug!("Neighs of {node}: {values:?}");
-        let mut result_iter = shape.rbe_table().matches(values)?;
+        let mut result_iter = shape.rbe_table().matches(values.clone())?;
         let mut current_err = None;
         let counter = self.step_counter;
         let mut found = false;
+        let mut refs = Vec::new();
         let mut iter_count = 0;
 
         // Search for the first result which is not an err
@@ -315,6 +328,7 @@ impl ValidatorRunner {
                     debug!("Found result, iteration {iter_count}");
                     for (p, v) in pending_values.iter() {
                         debug!("Step {counter}: Value in pending: {p}/{v}");
+                        refs.push(((*p).clone(), *v));
                         let pos_atom = PosAtom::new(((*p).clone(), *v));
                         let atom = Atom::pos(&pos_atom);
                         if self.is_processing(&atom) {
@@ -352,6 +366,8 @@ impl ValidatorRunner {
             Ok(Either::Right(vec![Reason::ShapePassed {
                 node: node.clone(),
                 shape: shape.clone(),
+                arcs: values,
+                refs,
             }]))
         }
     }
@@ -372,17 +388,24 @@ impl ValidatorRunner {
         Node::from(object)
     }
 
-    fn neighs<S>(&self, node: &Node, preds: Vec<IriS>, rdf: &S) -> Result<Neighs>
+    /// Outgoing arcs of the node with `preds` and incoming arcs with the predicates of the inverse triple constraints
+    fn neighs<S>(&self, node: &Node, shape: &Shape, rdf: &S) -> Result<Neighs>
     where
         S: SRDF,
     {
         let node = self.get_rdf_node(node, rdf);
-        let list = preds.iter().map(|pred| S::iri_s2iri(pred)).collect();
+        let list = shape
+            .preds()
+            .iter()
+            .map(|pred| S::iri_s2iri(pred))
+            .collect();
+        let mut result = Vec::new();
+        let mut remainder_preds = Vec::new();
+        // Literals have no outgoing arcs
         if let Some(subject) = S::term_as_subject(&node) {
             let (outgoing_arcs, remainder) = rdf
                 .outgoing_arcs_from_list(&subject, list)
                 .map_err(|e| self.cnv_err::<S>(e))?;
-            let mut result = Vec::new();
             for (pred, values) in outgoing_arcs.into_iter() {
                 for obj in values.into_iter() {
                     let iri = self.cnv_iri::<S>(pred.clone());
@@ -390,15 +413,21 @@ impl ValidatorRunner {
                     result.push((iri.clone(), object))
                 }
             }
-            let mut remainder_preds = Vec::new();
             for r in remainder {
                 let iri_r = self.cnv_iri::<S>(r.clone());
                 remainder_preds.push(iri_r)
             }
-            Ok((result, remainder_preds))
-        } else {
-            todo!()
         }
+        for pred in shape.inverse_preds() {
+            let subjects = rdf
+                .subjects_with_predicate_object(&S::iri_s2iri(&pred), &node)
+                .map_err(|e| self.cnv_err::<S>(e))?;
+            for subject in subjects {
+                let subject = S::subject_as_term(&subject);
+                result.push((Pred::inverse(pred.clone()), self.cnv_object::<S>(&subject)))
+            }
+        }
+        Ok((result, remainder_preds))
     }
 
     fn cnv_err<S>(&self, _err: S::Err) -> ValidatorError
@@ -412,18 +441,7 @@ impl ValidatorRunner {
     where
         S: SRDF,
     {
-        match node.as_object() {
-            Object::Iri(iri) => {
-                let i = S::iri_s2iri(iri);
-                S::iri_as_term(i)
-            }
-            Object::BlankNode(_id) => {
-                todo!()
-            }
-            Object::Literal(_lit) => {
-                todo!()
-            }
-        }
+        S::object_as_term(node.as_object())
     }
 
     pub fn insert_pending(&mut self, atom: &Atom) {
@@ -4,30 +4,36 @@
 //! - [`SRDFBasic`]: Basic comparisons on RDF nodes
 //! - [`SRDF`]: Definitions on RDF graphs
 //! - [`FocusRDF`]: RDF graphs with a focus node
+//! - [`SRDFDataset`]: RDF datasets with named graphs
 //! - [`RDFNodeParse`]: RDF graphs that can be parsed
 pub mod async_srdf;
 pub mod bnode;
 pub mod lang;
 pub mod literal;
 pub mod neighs;
+pub mod node_check;
 pub mod numeric_literal;
 pub mod object;
 pub mod query_srdf;
 pub mod rdf;
 pub mod rdf_format;
 pub mod shacl_path;
+pub mod sparql_eval;
 pub mod srdf;
 pub mod srdf_basic;
 pub mod srdf_builder;
+pub mod srdf_dataset;
 pub mod srdf_graph;
 pub mod srdf_parser;
 pub mod srdf_sparql;
+pub mod srdf_store;
 pub mod subject;
 pub mod triple;
 pub mod vocab;
 
 pub use crate::async_srdf::*;
 pub use crate::neighs::*;
+pub use crate::node_check::*;
 pub use crate::query_srdf::*;
 pub use crate::srdf::*;
 pub use crate::srdf_basic::*;
@@ -36,10 +42,13 @@ pub use object::*;
 pub use rdf::*;
 pub use rdf_format::*;
 pub use shacl_path::*;
+pub use sparql_eval::*;
 pub use srdf_builder::*;
+pub use srdf_dataset::*;
 pub use srdf_graph::*;
 pub use srdf_parser::*;
 pub use srdf_sparql::*;
+pub use srdf_store::*;
 pub use subject::*;
 pub use triple::*;
 pub use vocab::*;
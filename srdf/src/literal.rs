@@ -158,7 +158,7 @@ impl Into<oxrdf::Literal> for Literal {
             Literal::StringLiteral { lexical_form, lang } => match lang {
                 Some(lang) => oxrdf::Literal::new_language_tagged_literal_unchecked(
                     lexical_form,
-                    lang.to_string(),
+                    lang.value(),
                 ),
                 None => lexical_form.into(),
             },
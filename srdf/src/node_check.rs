@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use iri_s::IriS;
+
+use crate::Object;
+
+/// Check of RDF nodes implemented in Rust
+///
+/// Checks are used as custom SHACL constraint components and as ShEx external shapes, so they can
+/// express conditions that are hard to write with shapes, like the checksums of identifiers.
+pub trait NodeCheck {
+    /// Checks a node with the values of the parameters of the check.
+    ///
+    /// The parameters are the values of the parameters of a SHACL constraint component,
+    /// external shapes of ShEx don't have parameters.
+    /// It returns a message that explains why the node doesn't pass the check
+    fn check(&self, node: &Object, parameters: &HashMap<IriS, Object>) -> Result<(), String>;
+}
+
+impl<F> NodeCheck for F
+where
+    F: Fn(&Object, &HashMap<IriS, Object>) -> Result<(), String>,
+{
+    fn check(&self, node: &Object, parameters: &HashMap<IriS, Object>) -> Result<(), String> {
+        self(node, parameters)
+    }
+}
+
+/// Checks of RDF nodes identified by IRIs
+///
+/// The IRIs are the IRIs of the SHACL constraint components or the labels of the ShEx external
+/// shapes, so the same checks can be used by both validators.
+#[derive(Clone, Default)]
+pub struct NodeChecks {
+    checks: HashMap<IriS, Arc<dyn NodeCheck + Send + Sync>>,
+}
+
+impl NodeChecks {
+    pub fn new() -> NodeChecks {
+        Self::default()
+    }
+
+    pub fn with_check(mut self, iri: IriS, check: impl NodeCheck + Send + Sync + 'static) -> Self {
+        self.register(iri, check);
+        self
+    }
+
+    pub fn register(&mut self, iri: IriS, check: impl NodeCheck + Send + Sync + 'static) {
+        self.checks.insert(iri, Arc::new(check));
+    }
+
+    pub fn get(&self, iri: &IriS) -> Option<&(dyn NodeCheck + Send + Sync)> {
+        self.checks.get(iri).map(|check| check.as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+}
+
+impl Debug for NodeChecks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.checks.keys()).finish()
+    }
+}
@@ -5,14 +5,18 @@ use crate::numeric_literal::NumericLiteral;
 use iri_s::IriS;
 use serde_derive::{Deserialize, Serialize};
 
-/// Concrete representation of RDF objects which can be IRIs, Blank nodes or literals
-///
-/// Note: We plan to support triple terms as in RDF-star in the future
+/// Concrete representation of RDF objects which can be IRIs, Blank nodes, literals
+/// or triple terms (quoted triples as in RDF-star)
 #[derive(Clone, Hash, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Object {
     Iri(IriS),
     BlankNode(String),
     Literal(Literal),
+    Triple {
+        subject: Box<Object>,
+        predicate: IriS,
+        object: Box<Object>,
+    },
 }
 
 impl Object {
@@ -28,17 +32,30 @@ impl Object {
         Object::Literal(lit)
     }
 
+    pub fn triple(subject: Object, predicate: IriS, object: Object) -> Object {
+        Object::Triple {
+            subject: Box::new(subject),
+            predicate,
+            object: Box::new(object),
+        }
+    }
+
+    pub fn is_triple(&self) -> bool {
+        matches!(self, Object::Triple { .. })
+    }
+
     pub fn length(&self) -> usize {
         match self {
             Object::Iri(iri) => iri.as_str().len(),
             Object::BlankNode(bn) => bn.len(),
             Object::Literal(lit) => lit.lexical_form().len(),
+            Object::Triple { .. } => self.to_string().len(),
         }
     }
 
     pub fn numeric_value(&self) -> Option<NumericLiteral> {
         match self {
-            Object::Iri(_) | Object::BlankNode(_) => None,
+            Object::Iri(_) | Object::BlankNode(_) | Object::Triple { .. } => None,
             Object::Literal(lit) => lit.numeric_value(),
         }
     }
@@ -68,6 +85,11 @@ impl Display for Object {
             Object::Iri(iri) => write!(f, "{iri}"),
             Object::BlankNode(bnode) => write!(f, "_{bnode}"),
             Object::Literal(lit) => write!(f, "{lit}"),
+            Object::Triple {
+                subject,
+                predicate,
+                object,
+            } => write!(f, "<< {subject} {predicate} {object} >>"),
         }
     }
 }
@@ -78,6 +100,11 @@ impl Debug for Object {
             Object::Iri(iri) => write!(f, "Iri {{{iri:?}}}"),
             Object::BlankNode(bnode) => write!(f, "Bnode{{{bnode:?}}}"),
             Object::Literal(lit) => write!(f, "Literal{{{lit:?}}}"),
+            Object::Triple {
+                subject,
+                predicate,
+                object,
+            } => write!(f, "Triple{{{subject:?}, {predicate:?}, {object:?}}}"),
         }
     }
 }
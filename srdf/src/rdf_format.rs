@@ -11,6 +11,7 @@ pub enum RDFFormat {
     TriG,
     N3,
     NQuads,
+    JsonLd,
 }
 
 impl FromStr for RDFFormat {
@@ -24,6 +25,7 @@ impl FromStr for RDFFormat {
             "trig" => Ok(RDFFormat::TriG),
             "n3" => Ok(RDFFormat::N3),
             "nq" => Ok(RDFFormat::NQuads),
+            "jsonld" => Ok(RDFFormat::JsonLd),
             _ => Err(RDFParseError::SRDFError {
                 err: format!("Format {} not supported", s).to_string(),
             }),
@@ -22,16 +22,116 @@ impl SHACLPath {
     }
 }
 
+/// Paths are displayed with the syntax of [SPARQL property paths](https://www.w3.org/TR/sparql11-query/#propertypaths),
+/// adding parentheses only where the precedence of the operators requires them
 impl Display for SHACLPath {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            SHACLPath::Predicate { pred } => write!(f, "{pred}"),
-            SHACLPath::Alternative { .. } => todo!(),
-            SHACLPath::Sequence { .. } => todo!(),
-            SHACLPath::Inverse { .. } => todo!(),
-            SHACLPath::ZeroOrMore { .. } => todo!(),
-            SHACLPath::OneOrMore { .. } => todo!(),
-            SHACLPath::ZeroOrOne { .. } => todo!(),
+            SHACLPath::Predicate { pred } => write!(f, "<{pred}>"),
+            SHACLPath::Alternative { paths } => write_paths(f, paths, " | ", Precedence::Sequence),
+            SHACLPath::Sequence { paths } => write_paths(f, paths, " / ", Precedence::Inverse),
+            SHACLPath::Inverse { path } => {
+                write!(f, "^")?;
+                write_path(f, path, Precedence::Modifier)
+            }
+            SHACLPath::ZeroOrMore { path } => write_modified(f, path, '*'),
+            SHACLPath::OneOrMore { path } => write_modified(f, path, '+'),
+            SHACLPath::ZeroOrOne { path } => write_modified(f, path, '?'),
         }
     }
 }
+
+/// Precedence of the operators of SPARQL property paths, from lowest to highest
+#[derive(Clone, Copy, PartialEq, PartialOrd)]
+enum Precedence {
+    Alternative,
+    Sequence,
+    Inverse,
+    Modifier,
+    Primary,
+}
+
+impl SHACLPath {
+    fn precedence(&self) -> Precedence {
+        match self {
+            SHACLPath::Predicate { .. } => Precedence::Primary,
+            SHACLPath::Alternative { .. } => Precedence::Alternative,
+            SHACLPath::Sequence { .. } => Precedence::Sequence,
+            SHACLPath::Inverse { .. } => Precedence::Inverse,
+            SHACLPath::ZeroOrMore { .. }
+            | SHACLPath::OneOrMore { .. }
+            | SHACLPath::ZeroOrOne { .. } => Precedence::Modifier,
+        }
+    }
+}
+
+// Writes `path` between parentheses if its operator binds less than `min`
+fn write_path(
+    f: &mut std::fmt::Formatter<'_>,
+    path: &SHACLPath,
+    min: Precedence,
+) -> std::fmt::Result {
+    if path.precedence() < min {
+        write!(f, "({path})")
+    } else {
+        write!(f, "{path}")
+    }
+}
+
+fn write_modified(
+    f: &mut std::fmt::Formatter<'_>,
+    path: &SHACLPath,
+    modifier: char,
+) -> std::fmt::Result {
+    write_path(f, path, Precedence::Primary)?;
+    write!(f, "{modifier}")
+}
+
+fn write_paths(
+    f: &mut std::fmt::Formatter<'_>,
+    paths: &[SHACLPath],
+    separator: &str,
+    min: Precedence,
+) -> std::fmt::Result {
+    for (n, path) in paths.iter().enumerate() {
+        if n > 0 {
+            write!(f, "{separator}")?;
+        }
+        write_path(f, path, min)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p(name: &str) -> SHACLPath {
+        SHACLPath::iri(IriS::new_unchecked(&format!("http://example.org/{name}")))
+    }
+
+    #[test]
+    fn display_as_sparql_property_path() {
+        let path = SHACLPath::Sequence {
+            paths: vec![
+                SHACLPath::Alternative {
+                    paths: vec![p("a"), p("b")],
+                },
+                SHACLPath::Inverse {
+                    path: Box::new(SHACLPath::ZeroOrMore {
+                        path: Box::new(p("c")),
+                    }),
+                },
+                SHACLPath::ZeroOrOne {
+                    path: Box::new(SHACLPath::Inverse {
+                        path: Box::new(p("d")),
+                    }),
+                },
+            ],
+        };
+        assert_eq!(
+            path.to_string(),
+            "(<http://example.org/a> | <http://example.org/b>) / ^<http://example.org/c>* / (^<http://example.org/d>)?"
+        );
+    }
+}
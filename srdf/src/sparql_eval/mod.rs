@@ -0,0 +1,8 @@
+//! Evaluation of SPARQL queries over any implementation of the [`SRDF`](crate::SRDF) trait
+//!
+//! It is used by the RDF sources that are not SPARQL endpoints to implement [`QuerySRDF`](crate::QuerySRDF)
+pub mod sparql_eval_error;
+pub mod sparql_evaluator;
+
+pub use sparql_eval_error::*;
+pub use sparql_evaluator::*;
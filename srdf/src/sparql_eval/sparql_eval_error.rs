@@ -0,0 +1,23 @@
+use spargebra::SparqlSyntaxError;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SparqlEvalError {
+    #[error(transparent)]
+    SparqlSyntaxError {
+        #[from]
+        err: SparqlSyntaxError,
+    },
+
+    #[error("Expected a {expected} query: {query}")]
+    UnexpectedQueryForm { expected: String, query: String },
+
+    #[error("SPARQL feature not supported: {feature}")]
+    Unsupported { feature: String },
+
+    #[error("Variable ?{variable} of BIND is already bound")]
+    BoundVariable { variable: String },
+
+    #[error("Error accessing the RDF data: {err}")]
+    Srdf { err: String },
+}
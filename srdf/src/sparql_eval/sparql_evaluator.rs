@@ -0,0 +1,1004 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::rc::Rc;
+
+use iri_s::IriS;
+use oxrdf::{BlankNode, Literal as OxLiteral, NamedNode, Term as OxTerm};
+use spargebra::algebra::{
+    Expression, Function, GraphPattern, OrderExpression, PropertyPathExpression,
+};
+use spargebra::term::{GroundTerm, NamedNodePattern, TermPattern, TriplePattern};
+use spargebra::Query;
+
+use crate::{QuerySolutionIter, SHACLPath, SRDFBasic, SparqlEvalError, VarName, SRDF};
+
+/// Values of the variables of a query solution, blank nodes of the patterns are bound as `_:id`
+pub type Bindings<S> = HashMap<String, <S as SRDFBasic>::Term>;
+
+/// Triple built from the template of a `CONSTRUCT` query
+pub type ConstructedTriple<S> = (
+    <S as SRDFBasic>::Subject,
+    <S as SRDFBasic>::IRI,
+    <S as SRDFBasic>::Term,
+);
+
+/// Triple of the data, with the predicate as a term so that it can be bound to a variable
+type DataTriple<S> = (
+    <S as SRDFBasic>::Term,
+    <S as SRDFBasic>::Term,
+    <S as SRDFBasic>::Term,
+);
+
+type Operands<S> = (<S as SRDFBasic>::Term, <S as SRDFBasic>::Term);
+
+type Result<A> = std::result::Result<A, SparqlEvalError>;
+
+/// Evaluates SPARQL queries directly over the triples of an RDF source
+///
+/// It supports basic graph patterns, property paths, `OPTIONAL`, `UNION`, `MINUS`, `FILTER`,
+/// `BIND`, `VALUES`, subqueries, `ORDER BY`, `DISTINCT`, `LIMIT`, `OFFSET` and the most common
+/// functions. Named graphs, `SERVICE` and aggregates are not supported.
+pub struct SparqlEvaluator<'a, S: SRDF> {
+    store: &'a S,
+}
+
+impl<'a, S: SRDF> SparqlEvaluator<'a, S> {
+    pub fn new(store: &'a S) -> Self {
+        SparqlEvaluator { store }
+    }
+
+    /// Solutions of a `SELECT` query, with the values of the projected variables
+    pub fn select(&self, query: &str) -> Result<QuerySolutionIter<S>>
+    where
+        S: 'static,
+    {
+        match Query::parse(query, None)? {
+            Query::Select {
+                dataset: None,
+                pattern,
+                ..
+            } => {
+                let variables = projected_variables(&pattern);
+                let values: Vec<_> = self
+                    .eval(&pattern, &Bindings::<S>::new())?
+                    .into_iter()
+                    .map(|solution| {
+                        Ok(variables.iter().map(|v| solution.get(v).cloned()).collect())
+                    })
+                    .collect();
+                let variables: Vec<VarName> = variables.into_iter().map(VarName::from).collect();
+                Ok(QuerySolutionIter::new(
+                    Rc::new(variables),
+                    values.into_iter(),
+                ))
+            }
+            Query::Select { .. } => Err(unsupported("FROM")),
+            _ => Err(unexpected_form("SELECT", query)),
+        }
+    }
+
+    /// Checks if an `ASK` query has some solution
+    pub fn ask(&self, query: &str) -> Result<bool> {
+        match Query::parse(query, None)? {
+            Query::Ask {
+                dataset: None,
+                pattern,
+                ..
+            } => Ok(!self.eval(&pattern, &Bindings::<S>::new())?.is_empty()),
+            Query::Ask { .. } => Err(unsupported("FROM")),
+            _ => Err(unexpected_form("ASK", query)),
+        }
+    }
+
+    /// Triples of the template of a `CONSTRUCT` query for the solutions that extend some bindings
+    ///
+    /// Triples with unbound variables or with terms that can't be in their position are skipped,
+    /// and the blank nodes of the template are fresh for each solution.
+    pub fn construct(
+        &self,
+        template: &[TriplePattern],
+        pattern: &GraphPattern,
+        input: &Bindings<S>,
+    ) -> Result<Vec<ConstructedTriple<S>>> {
+        let mut triples = Vec::new();
+        for solution in self.eval(pattern, input)? {
+            let mut bnodes = HashMap::new();
+            for pattern in template.iter() {
+                let subject = template_term::<S>(&pattern.subject, &solution, &mut bnodes)
+                    .and_then(|term| S::term_as_subject(&term));
+                let predicate = match &pattern.predicate {
+                    NamedNodePattern::NamedNode(iri) => Some(S::iri_s2iri(&iri_s(iri))),
+                    NamedNodePattern::Variable(var) => {
+                        solution.get(var.as_str()).and_then(S::term_as_iri)
+                    }
+                };
+                let object = template_term::<S>(&pattern.object, &solution, &mut bnodes);
+                if let (Some(subject), Some(predicate), Some(object)) = (subject, predicate, object)
+                {
+                    triples.push((subject, predicate, object))
+                }
+            }
+        }
+        Ok(triples)
+    }
+
+    /// Solutions of a graph pattern that extend the input bindings
+    ///
+    /// The input bindings are pre-bound values, like `$this` in SHACL, which are replaced in every
+    /// part of the pattern. The sides of joins and `OPTIONAL` are evaluated independently with them
+    /// and then merged, and binding a variable that is already bound with `BIND` is an error.
+    pub fn eval(&self, pattern: &GraphPattern, input: &Bindings<S>) -> Result<Vec<Bindings<S>>> {
+        match pattern {
+            GraphPattern::Bgp { patterns } => {
+                let mut solutions = vec![input.clone()];
+                for pattern in patterns {
+                    let mut next = Vec::new();
+                    for solution in solutions.iter() {
+                        next.extend(self.match_triple(pattern, solution)?)
+                    }
+                    solutions = next
+                }
+                Ok(solutions)
+            }
+            GraphPattern::Path {
+                subject,
+                path,
+                object,
+            } => self.match_path(subject, &shacl_path(path)?, object, input),
+            GraphPattern::Join { left, right } => {
+                let right = self.eval(right, input)?;
+                let mut solutions = Vec::new();
+                for solution in self.eval(left, input)? {
+                    solutions.extend(
+                        right
+                            .iter()
+                            .filter_map(|other| merge::<S>(&solution, other)),
+                    )
+                }
+                Ok(solutions)
+            }
+            GraphPattern::LeftJoin {
+                left,
+                right,
+                expression,
+            } => {
+                let right = self.eval(right, input)?;
+                let mut solutions = Vec::new();
+                for solution in self.eval(left, input)? {
+                    let mut extended = Vec::new();
+                    for candidate in right
+                        .iter()
+                        .filter_map(|other| merge::<S>(&solution, other))
+                    {
+                        let accepted = match expression {
+                            Some(expr) => self.ebv(expr, &candidate)? == Some(true),
+                            None => true,
+                        };
+                        if accepted {
+                            extended.push(candidate)
+                        }
+                    }
+                    if extended.is_empty() {
+                        solutions.push(solution)
+                    } else {
+                        solutions.extend(extended)
+                    }
+                }
+                Ok(solutions)
+            }
+            GraphPattern::Filter { expr, inner } => {
+                let mut solutions = Vec::new();
+                for solution in self.eval(inner, input)? {
+                    if self.ebv(expr, &solution)? == Some(true) {
+                        solutions.push(solution)
+                    }
+                }
+                Ok(solutions)
+            }
+            GraphPattern::Union { left, right } => {
+                let mut solutions = self.eval(left, input)?;
+                solutions.extend(self.eval(right, input)?);
+                Ok(solutions)
+            }
+            GraphPattern::Extend {
+                inner,
+                variable,
+                expression,
+            } => {
+                let mut solutions = Vec::new();
+                for mut solution in self.eval(inner, input)? {
+                    if solution.contains_key(variable.as_str()) {
+                        return Err(SparqlEvalError::BoundVariable {
+                            variable: variable.as_str().to_string(),
+                        });
+                    }
+                    if let Some(value) = self.eval_expr(expression, &solution)? {
+                        solution.insert(variable.as_str().to_string(), value);
+                    }
+                    solutions.push(solution)
+                }
+                Ok(solutions)
+            }
+            GraphPattern::Minus { left, right } => {
+                let excluded = self.eval(right, &Bindings::<S>::new())?;
+                let solutions = self
+                    .eval(left, input)?
+                    .into_iter()
+                    .filter(|solution| {
+                        !excluded.iter().any(|other| {
+                            other.keys().any(|var| solution.contains_key(var))
+                                && compatible::<S>(other, solution)
+                        })
+                    })
+                    .collect();
+                Ok(solutions)
+            }
+            GraphPattern::Values {
+                variables,
+                bindings,
+            } => {
+                let mut solutions = Vec::new();
+                for row in bindings {
+                    let mut row_solution = Bindings::<S>::new();
+                    for (variable, value) in variables.iter().zip(row) {
+                        if let Some(value) = value {
+                            row_solution
+                                .insert(variable.as_str().to_string(), ground_term::<S>(value)?);
+                        }
+                    }
+                    if compatible::<S>(&row_solution, input) {
+                        let mut solution = input.clone();
+                        solution.extend(row_solution);
+                        solutions.push(solution)
+                    }
+                }
+                Ok(solutions)
+            }
+            GraphPattern::OrderBy { inner, expression } => {
+                let mut keyed = Vec::new();
+                for solution in self.eval(inner, input)? {
+                    let mut keys = Vec::new();
+                    for order in expression {
+                        let (OrderExpression::Asc(expr) | OrderExpression::Desc(expr)) = order;
+                        keys.push(self.eval_expr(expr, &solution)?)
+                    }
+                    keyed.push((keys, solution))
+                }
+                keyed.sort_by(|(a, _), (b, _)| {
+                    expression
+                        .iter()
+                        .zip(a.iter().zip(b.iter()))
+                        .map(|(order, (a, b))| match order {
+                            OrderExpression::Asc(_) => sort_order::<S>(a, b),
+                            OrderExpression::Desc(_) => sort_order::<S>(b, a),
+                        })
+                        .find(|ordering| ordering.is_ne())
+                        .unwrap_or(Ordering::Equal)
+                });
+                Ok(keyed.into_iter().map(|(_, solution)| solution).collect())
+            }
+            GraphPattern::Project { inner, variables } => {
+                let solutions = self
+                    .eval(inner, input)?
+                    .into_iter()
+                    .map(|mut solution| {
+                        solution.retain(|var, _| {
+                            input.contains_key(var)
+                                || variables.iter().any(|variable| variable.as_str() == var)
+                        });
+                        solution
+                    })
+                    .collect();
+                Ok(solutions)
+            }
+            GraphPattern::Distinct { inner } | GraphPattern::Reduced { inner } => {
+                let mut solutions = Vec::new();
+                for solution in self.eval(inner, input)? {
+                    if !solutions.contains(&solution) {
+                        solutions.push(solution)
+                    }
+                }
+                Ok(solutions)
+            }
+            GraphPattern::Slice {
+                inner,
+                start,
+                length,
+            } => {
+                let solutions = self.eval(inner, input)?.into_iter().skip(*start);
+                Ok(match length {
+                    Some(length) => solutions.take(*length).collect(),
+                    None => solutions.collect(),
+                })
+            }
+            GraphPattern::Graph { .. } => Err(unsupported("GRAPH")),
+            GraphPattern::Group { .. } => Err(unsupported("GROUP BY and aggregates")),
+            GraphPattern::Service { .. } => Err(unsupported("SERVICE")),
+            #[allow(unreachable_patterns)]
+            _ => Err(unsupported(pattern)),
+        }
+    }
+
+    fn match_triple(
+        &self,
+        pattern: &TriplePattern,
+        solution: &Bindings<S>,
+    ) -> Result<Vec<Bindings<S>>> {
+        let subject = bound_term::<S>(&pattern.subject, solution);
+        let predicate = match &pattern.predicate {
+            NamedNodePattern::NamedNode(iri) => Some(S::iri_s2term(&iri_s(iri))),
+            NamedNodePattern::Variable(var) => solution.get(var.as_str()).cloned(),
+        };
+        let object = bound_term::<S>(&pattern.object, solution);
+        let predicate_pattern = match &pattern.predicate {
+            NamedNodePattern::NamedNode(iri) => TermPattern::NamedNode(iri.clone()),
+            NamedNodePattern::Variable(var) => TermPattern::Variable(var.clone()),
+        };
+
+        let mut solutions = Vec::new();
+        for (s, p, o) in self.triples(subject.as_ref(), predicate.as_ref(), object.as_ref())? {
+            let values = [
+                (&pattern.subject, s),
+                (&predicate_pattern, p),
+                (&pattern.object, o),
+            ];
+            if let Some(solution) = bind::<S>(solution, &values) {
+                solutions.push(solution)
+            }
+        }
+        Ok(solutions)
+    }
+
+    /// Triples of the data that match the bound parts, with the predicate as a term
+    fn triples(
+        &self,
+        subject: Option<&S::Term>,
+        predicate: Option<&S::Term>,
+        object: Option<&S::Term>,
+    ) -> Result<Vec<DataTriple<S>>> {
+        let predicate = match predicate {
+            Some(predicate) => match S::term_as_iri(predicate) {
+                Some(iri) => Some(iri),
+                None => return Ok(Vec::new()),
+            },
+            None => None,
+        };
+        let mut triples = Vec::new();
+        match (subject, &predicate) {
+            (Some(subject), _) => {
+                let Some(subj) = S::term_as_subject(subject) else {
+                    return Ok(Vec::new());
+                };
+                let predicates = match &predicate {
+                    Some(predicate) => vec![predicate.clone()],
+                    None => self
+                        .store
+                        .predicates_for_subject(&subj)
+                        .map_err(srdf_error)?
+                        .into_iter()
+                        .collect(),
+                };
+                for predicate in predicates {
+                    for o in self
+                        .store
+                        .objects_for_subject_predicate(&subj, &predicate)
+                        .map_err(srdf_error)?
+                    {
+                        if object.is_none_or(|object| *object == o) {
+                            triples.push((subject.clone(), S::iri_as_term(predicate.clone()), o))
+                        }
+                    }
+                }
+            }
+            (None, Some(predicate)) => match object {
+                Some(object) => {
+                    for s in self
+                        .store
+                        .subjects_with_predicate_object(predicate, object)
+                        .map_err(srdf_error)?
+                    {
+                        triples.push((
+                            S::subject_as_term(&s),
+                            S::iri_as_term(predicate.clone()),
+                            object.clone(),
+                        ))
+                    }
+                }
+                None => {
+                    for triple in self
+                        .store
+                        .triples_with_predicate(predicate)
+                        .map_err(srdf_error)?
+                    {
+                        triples.push((
+                            S::subject_as_term(&triple.subj()),
+                            S::iri_as_term(triple.pred()),
+                            triple.obj(),
+                        ))
+                    }
+                }
+            },
+            (None, None) => match object {
+                Some(object) => {
+                    for (predicate, subjects) in
+                        self.store.incoming_arcs(object).map_err(srdf_error)?
+                    {
+                        for s in subjects {
+                            triples.push((
+                                S::subject_as_term(&s),
+                                S::iri_as_term(predicate.clone()),
+                                object.clone(),
+                            ))
+                        }
+                    }
+                }
+                None => {
+                    for s in self.store.subjects().map_err(srdf_error)? {
+                        triples.extend(self.triples(Some(&S::subject_as_term(&s)), None, None)?)
+                    }
+                }
+            },
+        }
+        Ok(triples)
+    }
+
+    fn match_path(
+        &self,
+        subject: &TermPattern,
+        path: &SHACLPath,
+        object: &TermPattern,
+        solution: &Bindings<S>,
+    ) -> Result<Vec<Bindings<S>>> {
+        let pairs = match (
+            bound_term::<S>(subject, solution),
+            bound_term::<S>(object, solution),
+        ) {
+            (Some(s), _) => self
+                .store
+                .objects_for_shacl_path(&s, path)
+                .map_err(srdf_error)?
+                .into_iter()
+                .map(|o| (s.clone(), o))
+                .collect(),
+            (None, Some(o)) => self
+                .store
+                .subjects_for_shacl_path(&o, path)
+                .map_err(srdf_error)?
+                .into_iter()
+                .map(|s| (s, o.clone()))
+                .collect(),
+            (None, None) => {
+                let mut pairs = Vec::new();
+                for s in self.store.subjects().map_err(srdf_error)? {
+                    let s = S::subject_as_term(&s);
+                    for o in self
+                        .store
+                        .objects_for_shacl_path(&s, path)
+                        .map_err(srdf_error)?
+                    {
+                        pairs.push((s.clone(), o))
+                    }
+                }
+                pairs
+            }
+        };
+        Ok(pairs
+            .into_iter()
+            .filter_map(|(s, o)| bind::<S>(solution, &[(subject, s), (object, o)]))
+            .collect())
+    }
+
+    /// [Effective boolean value](https://www.w3.org/TR/sparql11-query/#ebv) of an expression,
+    /// `None` if it is an error
+    fn ebv(&self, expr: &Expression, solution: &Bindings<S>) -> Result<Option<bool>> {
+        Ok(self
+            .eval_expr(expr, solution)?
+            .and_then(|value| effective_boolean_value::<S>(&value)))
+    }
+
+    /// Value of an expression, `None` if it is an error or an unbound variable
+    fn eval_expr(&self, expr: &Expression, solution: &Bindings<S>) -> Result<Option<S::Term>> {
+        let value = match expr {
+            Expression::NamedNode(iri) => Some(S::iri_s2term(&iri_s(iri))),
+            Expression::Literal(literal) => Some(S::term_s2term(&OxTerm::Literal(literal.clone()))),
+            Expression::Variable(var) => solution.get(var.as_str()).cloned(),
+            Expression::Or(a, b) => {
+                let (a, b) = (self.ebv(a, solution)?, self.ebv(b, solution)?);
+                match (a, b) {
+                    (Some(true), _) | (_, Some(true)) => Some(boolean::<S>(true)),
+                    (Some(false), Some(false)) => Some(boolean::<S>(false)),
+                    _ => None,
+                }
+            }
+            Expression::And(a, b) => {
+                let (a, b) = (self.ebv(a, solution)?, self.ebv(b, solution)?);
+                match (a, b) {
+                    (Some(false), _) | (_, Some(false)) => Some(boolean::<S>(false)),
+                    (Some(true), Some(true)) => Some(boolean::<S>(true)),
+                    _ => None,
+                }
+            }
+            Expression::Not(a) => self.ebv(a, solution)?.map(|b| boolean::<S>(!b)),
+            Expression::Equal(a, b) => self
+                .operands(a, b, solution)?
+                .map(|(a, b)| boolean::<S>(equal::<S>(&a, &b))),
+            Expression::SameTerm(a, b) => self
+                .operands(a, b, solution)?
+                .map(|(a, b)| boolean::<S>(a == b)),
+            Expression::Greater(a, b) => self.compare(a, b, solution, |o| o.is_gt())?,
+            Expression::GreaterOrEqual(a, b) => self.compare(a, b, solution, |o| o.is_ge())?,
+            Expression::Less(a, b) => self.compare(a, b, solution, |o| o.is_lt())?,
+            Expression::LessOrEqual(a, b) => self.compare(a, b, solution, |o| o.is_le())?,
+            Expression::In(a, list) => match self.eval_expr(a, solution)? {
+                Some(a) => {
+                    let mut found = false;
+                    for b in list {
+                        if let Some(b) = self.eval_expr(b, solution)? {
+                            found = found || equal::<S>(&a, &b)
+                        }
+                    }
+                    Some(boolean::<S>(found))
+                }
+                None => None,
+            },
+            Expression::Add(a, b) => self.arithmetic(a, b, solution, |a, b| a + b)?,
+            Expression::Subtract(a, b) => self.arithmetic(a, b, solution, |a, b| a - b)?,
+            Expression::Multiply(a, b) => self.arithmetic(a, b, solution, |a, b| a * b)?,
+            Expression::Divide(a, b) => match self.operands(a, b, solution)? {
+                Some((a, b)) => match (numeric::<S>(&a), numeric::<S>(&b)) {
+                    (Some((a, _)), Some((b, _))) if b != 0.0 => Some(double::<S>(a / b)),
+                    _ => None,
+                },
+                None => None,
+            },
+            Expression::UnaryPlus(a) => self
+                .eval_expr(a, solution)?
+                .filter(|a| numeric::<S>(a).is_some()),
+            Expression::UnaryMinus(a) => self
+                .eval_expr(a, solution)?
+                .and_then(|a| numeric::<S>(&a))
+                .map(|(n, integer)| number::<S>(-n, integer)),
+            Expression::Exists(pattern) => {
+                Some(boolean::<S>(!self.eval(pattern, solution)?.is_empty()))
+            }
+            Expression::Bound(var) => Some(boolean::<S>(solution.contains_key(var.as_str()))),
+            Expression::If(condition, then, otherwise) => match self.ebv(condition, solution)? {
+                Some(true) => self.eval_expr(then, solution)?,
+                Some(false) => self.eval_expr(otherwise, solution)?,
+                None => None,
+            },
+            Expression::Coalesce(exprs) => {
+                let mut value = None;
+                for expr in exprs {
+                    value = self.eval_expr(expr, solution)?;
+                    if value.is_some() {
+                        break;
+                    }
+                }
+                value
+            }
+            Expression::FunctionCall(function, args) => {
+                let mut values = Vec::new();
+                for arg in args {
+                    match self.eval_expr(arg, solution)? {
+                        Some(value) => values.push(value),
+                        None => return Ok(None),
+                    }
+                }
+                call::<S>(function, &values)?
+            }
+        };
+        Ok(value)
+    }
+
+    fn operands(
+        &self,
+        a: &Expression,
+        b: &Expression,
+        solution: &Bindings<S>,
+    ) -> Result<Option<Operands<S>>> {
+        Ok(
+            match (self.eval_expr(a, solution)?, self.eval_expr(b, solution)?) {
+                (Some(a), Some(b)) => Some((a, b)),
+                _ => None,
+            },
+        )
+    }
+
+    fn compare(
+        &self,
+        a: &Expression,
+        b: &Expression,
+        solution: &Bindings<S>,
+        check: impl Fn(Ordering) -> bool,
+    ) -> Result<Option<S::Term>> {
+        Ok(self
+            .operands(a, b, solution)?
+            .and_then(|(a, b)| order::<S>(&a, &b))
+            .map(|ordering| boolean::<S>(check(ordering))))
+    }
+
+    fn arithmetic(
+        &self,
+        a: &Expression,
+        b: &Expression,
+        solution: &Bindings<S>,
+        op: impl Fn(f64, f64) -> f64,
+    ) -> Result<Option<S::Term>> {
+        Ok(self.operands(a, b, solution)?.and_then(|(a, b)| {
+            match (numeric::<S>(&a), numeric::<S>(&b)) {
+                (Some((a, a_integer)), Some((b, b_integer))) => {
+                    Some(number::<S>(op(a, b), a_integer && b_integer))
+                }
+                _ => None,
+            }
+        }))
+    }
+}
+
+/// Variables of the solutions of a `SELECT` query, in the order of the projection
+fn projected_variables(pattern: &GraphPattern) -> Vec<String> {
+    match pattern {
+        GraphPattern::Project { variables, .. } => {
+            variables.iter().map(|v| v.as_str().to_string()).collect()
+        }
+        GraphPattern::Distinct { inner }
+        | GraphPattern::Reduced { inner }
+        | GraphPattern::OrderBy { inner, .. }
+        | GraphPattern::Slice { inner, .. } => projected_variables(inner),
+        _ => Vec::new(),
+    }
+}
+
+fn template_term<S: SRDF>(
+    pattern: &TermPattern,
+    solution: &Bindings<S>,
+    bnodes: &mut HashMap<String, S::Term>,
+) -> Option<S::Term> {
+    match pattern {
+        TermPattern::BlankNode(bnode) => Some(
+            bnodes
+                .entry(bnode.as_str().to_string())
+                .or_insert_with(|| S::bnode_id2term(BlankNode::default().as_str()))
+                .clone(),
+        ),
+        _ => bound_term::<S>(pattern, solution),
+    }
+}
+
+/// Value of a term pattern for a solution, `None` if it is an unbound variable
+fn bound_term<S: SRDF>(pattern: &TermPattern, solution: &Bindings<S>) -> Option<S::Term> {
+    match pattern {
+        TermPattern::NamedNode(iri) => Some(S::iri_s2term(&iri_s(iri))),
+        TermPattern::Literal(literal) => Some(S::term_s2term(&OxTerm::Literal(literal.clone()))),
+        TermPattern::BlankNode(bnode) => solution.get(&bnode_var(bnode.as_str())).cloned(),
+        TermPattern::Variable(var) => solution.get(var.as_str()).cloned(),
+        #[allow(unreachable_patterns)]
+        _ => None,
+    }
+}
+
+/// Name of the variable of a term pattern, blank nodes in patterns act as variables
+fn pattern_var(pattern: &TermPattern) -> Option<String> {
+    match pattern {
+        TermPattern::Variable(var) => Some(var.as_str().to_string()),
+        TermPattern::BlankNode(bnode) => Some(bnode_var(bnode.as_str())),
+        _ => None,
+    }
+}
+
+fn bnode_var(id: &str) -> String {
+    format!("_:{id}")
+}
+
+fn iri_s(iri: &NamedNode) -> IriS {
+    IriS::new_unchecked(iri.as_str())
+}
+
+fn srdf_error(err: impl Display) -> SparqlEvalError {
+    SparqlEvalError::Srdf {
+        err: err.to_string(),
+    }
+}
+
+fn unsupported(feature: impl Display) -> SparqlEvalError {
+    SparqlEvalError::Unsupported {
+        feature: feature.to_string(),
+    }
+}
+
+fn unexpected_form(expected: &str, query: &str) -> SparqlEvalError {
+    SparqlEvalError::UnexpectedQueryForm {
+        expected: expected.to_string(),
+        query: query.to_string(),
+    }
+}
+
+/// Binds the variables of `patterns` to `values` if they are compatible with the solution
+fn bind<S: SRDF>(
+    solution: &Bindings<S>,
+    patterns: &[(&TermPattern, S::Term)],
+) -> Option<Bindings<S>> {
+    let mut result = solution.clone();
+    for (pattern, value) in patterns {
+        match pattern_var(pattern) {
+            Some(var) => match result.get(&var) {
+                Some(bound) if bound != value => return None,
+                Some(_) => {}
+                None => {
+                    result.insert(var, value.clone());
+                }
+            },
+            None => {
+                if bound_term::<S>(pattern, solution).as_ref() != Some(value) {
+                    return None;
+                }
+            }
+        }
+    }
+    Some(result)
+}
+
+fn compatible<S: SRDF>(a: &Bindings<S>, b: &Bindings<S>) -> bool {
+    a.iter()
+        .all(|(var, value)| b.get(var).is_none_or(|other| other == value))
+}
+
+/// Union of two compatible solutions
+fn merge<S: SRDF>(a: &Bindings<S>, b: &Bindings<S>) -> Option<Bindings<S>> {
+    compatible::<S>(a, b).then(|| {
+        let mut solution = a.clone();
+        solution.extend(b.iter().map(|(var, value)| (var.clone(), value.clone())));
+        solution
+    })
+}
+
+fn shacl_path(path: &PropertyPathExpression) -> Result<SHACLPath> {
+    let boxed = |path| shacl_path(path).map(Box::new);
+    Ok(match path {
+        PropertyPathExpression::NamedNode(iri) => SHACLPath::iri(iri_s(iri)),
+        PropertyPathExpression::Reverse(path) => SHACLPath::Inverse { path: boxed(path)? },
+        PropertyPathExpression::Sequence(a, b) => SHACLPath::Sequence {
+            paths: vec![shacl_path(a)?, shacl_path(b)?],
+        },
+        PropertyPathExpression::Alternative(a, b) => SHACLPath::Alternative {
+            paths: vec![shacl_path(a)?, shacl_path(b)?],
+        },
+        PropertyPathExpression::ZeroOrMore(path) => SHACLPath::ZeroOrMore { path: boxed(path)? },
+        PropertyPathExpression::OneOrMore(path) => SHACLPath::OneOrMore { path: boxed(path)? },
+        PropertyPathExpression::ZeroOrOne(path) => SHACLPath::ZeroOrOne { path: boxed(path)? },
+        PropertyPathExpression::NegatedPropertySet(_) => {
+            return Err(unsupported("negated property sets"))
+        }
+    })
+}
+
+fn ground_term<S: SRDF>(term: &GroundTerm) -> Result<S::Term> {
+    match term {
+        GroundTerm::NamedNode(iri) => Ok(S::iri_s2term(&iri_s(iri))),
+        GroundTerm::Literal(literal) => Ok(S::term_s2term(&OxTerm::Literal(literal.clone()))),
+        #[allow(unreachable_patterns)]
+        _ => Err(unsupported(term)),
+    }
+}
+
+fn call<S: SRDF>(function: &Function, args: &[S::Term]) -> Result<Option<S::Term>> {
+    let value = match (function, args) {
+        (Function::Str, [a]) => lexical_form::<S>(a).map(|str| string::<S>(&str, None)),
+        (Function::Lang, [a]) => S::term_as_literal(a)
+            .map(|literal| string::<S>(&S::lang(&literal).unwrap_or_default(), None)),
+        (Function::Datatype, [a]) => {
+            S::term_as_literal(a).map(|literal| S::iri_as_term(S::datatype(&literal)))
+        }
+        (Function::Iri, [a]) => S::term_as_string(a)
+            .and_then(|str| NamedNode::new(str).ok())
+            .map(|iri| S::iri_s2term(&iri_s(&iri)))
+            .or_else(|| S::term_as_iri(a).map(S::iri_as_term)),
+        (Function::IsIri, [a]) => Some(boolean::<S>(S::term_is_iri(a))),
+        (Function::IsBlank, [a]) => Some(boolean::<S>(S::term_is_bnode(a))),
+        (Function::IsLiteral, [a]) => Some(boolean::<S>(S::term_is_literal(a))),
+        (Function::IsNumeric, [a]) => Some(boolean::<S>(numeric::<S>(a).is_some())),
+        (Function::Concat, args) => {
+            let strs: Option<Vec<_>> = args.iter().map(lexical_form::<S>).collect();
+            strs.map(|strs| string::<S>(&strs.concat(), None))
+        }
+        (Function::UCase, [a]) => {
+            lexical_form::<S>(a).map(|str| string::<S>(&str.to_uppercase(), language::<S>(a)))
+        }
+        (Function::LCase, [a]) => {
+            lexical_form::<S>(a).map(|str| string::<S>(&str.to_lowercase(), language::<S>(a)))
+        }
+        (Function::StrLen, [a]) => {
+            lexical_form::<S>(a).map(|str| number::<S>(str.chars().count() as f64, true))
+        }
+        (Function::Contains, [a, b]) => string_test::<S>(a, b, |a, b| a.contains(b)),
+        (Function::StrStarts, [a, b]) => string_test::<S>(a, b, |a, b| a.starts_with(b)),
+        (Function::StrEnds, [a, b]) => string_test::<S>(a, b, |a, b| a.ends_with(b)),
+        (Function::Abs, [a]) => numeric::<S>(a).map(|(n, integer)| number::<S>(n.abs(), integer)),
+        (Function::Ceil, [a]) => numeric::<S>(a).map(|(n, integer)| number::<S>(n.ceil(), integer)),
+        (Function::Floor, [a]) => {
+            numeric::<S>(a).map(|(n, integer)| number::<S>(n.floor(), integer))
+        }
+        (Function::Round, [a]) => {
+            numeric::<S>(a).map(|(n, integer)| number::<S>(n.round(), integer))
+        }
+        _ => return Err(unsupported(format!("function {function}"))),
+    };
+    Ok(value)
+}
+
+fn lexical_form<S: SRDF>(term: &S::Term) -> Option<String> {
+    match S::term_as_literal(term) {
+        Some(literal) => Some(S::lexical_form(&literal).to_string()),
+        None => S::term_as_iri(term).map(|iri| S::iri2iri_s(&iri).as_str().to_string()),
+    }
+}
+
+fn language<S: SRDF>(term: &S::Term) -> Option<String> {
+    S::term_as_literal(term).and_then(|literal| S::lang(&literal))
+}
+
+fn string_test<S: SRDF>(
+    a: &S::Term,
+    b: &S::Term,
+    test: impl Fn(&str, &str) -> bool,
+) -> Option<S::Term> {
+    match (lexical_form::<S>(a), lexical_form::<S>(b)) {
+        (Some(a), Some(b)) => Some(boolean::<S>(test(&a, &b))),
+        _ => None,
+    }
+}
+
+/// Value of a numeric literal, and whether it is an integer
+fn numeric<S: SRDF>(term: &S::Term) -> Option<(f64, bool)> {
+    let literal = S::term_as_literal(term)?;
+    let value = S::term_as_object(term).numeric_value()?;
+    let integer = S::datatype_str(&literal).ends_with("#integer");
+    value
+        .lexical_form()
+        .parse()
+        .ok()
+        .map(|value| (value, integer))
+}
+
+fn effective_boolean_value<S: SRDF>(term: &S::Term) -> Option<bool> {
+    if let Some(b) = S::term_as_boolean(term) {
+        return Some(b);
+    }
+    if let Some((n, _)) = numeric::<S>(term) {
+        return Some(n != 0.0 && !n.is_nan());
+    }
+    S::term_as_literal(term).map(|literal| !S::lexical_form(&literal).is_empty())
+}
+
+fn equal<S: SRDF>(a: &S::Term, b: &S::Term) -> bool {
+    match (numeric::<S>(a), numeric::<S>(b)) {
+        (Some((a, _)), Some((b, _))) => a == b,
+        _ => a == b,
+    }
+}
+
+fn order<S: SRDF>(a: &S::Term, b: &S::Term) -> Option<Ordering> {
+    match (numeric::<S>(a), numeric::<S>(b)) {
+        (Some((a, _)), Some((b, _))) => a.partial_cmp(&b),
+        _ => match (S::term_as_literal(a), S::term_as_literal(b)) {
+            (Some(a), Some(b)) => Some(S::lexical_form(&a).cmp(S::lexical_form(&b))),
+            _ => None,
+        },
+    }
+}
+
+/// Order of the values of an `ORDER BY` expression, where unbound values go first
+fn sort_order<S: SRDF>(a: &Option<S::Term>, b: &Option<S::Term>) -> Ordering {
+    match (a, b) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Less,
+        (Some(_), None) => Ordering::Greater,
+        (Some(a), Some(b)) => order::<S>(a, b).unwrap_or_else(|| a.to_string().cmp(&b.to_string())),
+    }
+}
+
+fn boolean<S: SRDF>(b: bool) -> S::Term {
+    S::term_s2term(&OxTerm::Literal(OxLiteral::from(b)))
+}
+
+fn double<S: SRDF>(n: f64) -> S::Term {
+    S::term_s2term(&OxTerm::Literal(OxLiteral::from(n)))
+}
+
+fn number<S: SRDF>(n: f64, integer: bool) -> S::Term {
+    if integer && n.fract() == 0.0 {
+        S::term_s2term(&OxTerm::Literal(OxLiteral::from(n as i64)))
+    } else {
+        double::<S>(n)
+    }
+}
+
+fn string<S: SRDF>(str: &str, lang: Option<String>) -> S::Term {
+    let literal = match lang {
+        Some(lang) => OxLiteral::new_language_tagged_literal_unchecked(str, lang),
+        None => OxLiteral::new_simple_literal(str),
+    };
+    S::term_s2term(&OxTerm::Literal(literal))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RDFFormat, ReaderMode, SRDFGraph};
+    use iri_s::iri;
+
+    fn graph() -> SRDFGraph {
+        let data = r#"prefix : <http://example.org/>
+            :a :p 1 .
+            :b :p 2 .
+        "#;
+        SRDFGraph::from_str(data, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap()
+    }
+
+    fn pattern(query: &str) -> GraphPattern {
+        match Query::parse(query, None).unwrap() {
+            Query::Select { pattern, .. } => pattern,
+            _ => panic!("Expected a SELECT query"),
+        }
+    }
+
+    fn values(solutions: &[Bindings<SRDFGraph>], var: &str) -> Vec<String> {
+        let mut values: Vec<_> = solutions
+            .iter()
+            .map(|solution| solution.get(var).unwrap().to_string())
+            .collect();
+        values.sort();
+        values
+    }
+
+    #[test]
+    fn bind_of_a_bound_variable_fails() {
+        let graph = graph();
+        let evaluator = SparqlEvaluator::new(&graph);
+        let mut input = Bindings::<SRDFGraph>::new();
+        input.insert(
+            "this".to_string(),
+            SRDFGraph::iri_s2term(&iri!("http://example.org/a")),
+        );
+        let query = "prefix : <http://example.org/> select * where { ?x :p ?y BIND (?x AS $this) }";
+        assert!(matches!(
+            evaluator.eval(&pattern(query), &input),
+            Err(SparqlEvalError::BoundVariable { variable }) if variable == "this"
+        ));
+        let query =
+            "prefix : <http://example.org/> select * where { $this :p ?y BIND (?y + 1 AS ?z) }";
+        let solutions = evaluator.eval(&pattern(query), &input).unwrap();
+        assert_eq!(values(&solutions, "this"), vec!["<http://example.org/a>"]);
+    }
+
+    #[test]
+    fn joins_merge_independent_solutions() {
+        let graph = graph();
+        let evaluator = SparqlEvaluator::new(&graph);
+        let query = "prefix : <http://example.org/> select * where { ?x :p ?y { BIND (1 AS ?y) } }";
+        let solutions = evaluator
+            .eval(&pattern(query), &Bindings::<SRDFGraph>::new())
+            .unwrap();
+        assert_eq!(values(&solutions, "x"), vec!["<http://example.org/a>"]);
+
+        // The variables of the left side are not bound in the right side
+        let query =
+            "prefix : <http://example.org/> select * where { ?x :p ?y { FILTER (?y > 1) } }";
+        let solutions = evaluator
+            .eval(&pattern(query), &Bindings::<SRDFGraph>::new())
+            .unwrap();
+        assert!(solutions.is_empty());
+
+        let query = "prefix : <http://example.org/> select * where { ?x :p ?y OPTIONAL { BIND (2 AS ?y) BIND (true AS ?two) } }";
+        let solutions = evaluator
+            .eval(&pattern(query), &Bindings::<SRDFGraph>::new())
+            .unwrap();
+        let two: Vec<_> = solutions
+            .into_iter()
+            .filter(|solution| solution.contains_key("two"))
+            .collect();
+        assert_eq!(values(&two, "x"), vec!["<http://example.org/b>"]);
+    }
+}
@@ -1,7 +1,7 @@
 use std::collections::{HashMap, HashSet};
 //use std::hash::Hash;
 
-use crate::{SRDFBasic, Triple};
+use crate::{SHACLPath, SRDFBasic, Triple};
 
 type ListOfIriAndTerms<I, T> = Vec<(I, HashSet<T>)>;
 type HasMapOfIriAndItem<I, T> = HashMap<I, HashSet<T>>;
@@ -31,6 +31,9 @@ pub trait SRDF: SRDFBasic {
 
     fn triples_with_predicate(&self, pred: &Self::IRI) -> Result<Vec<Triple<Self>>, Self::Err>;
 
+    /// Returns all the subjects of the triples in the RDF source
+    fn subjects(&self) -> Result<HashSet<Self::Subject>, Self::Err>;
+
     /*fn get_subjects_for_predicate_any_object(
         &self,
         pred: &Self::IRI,
@@ -78,4 +81,121 @@ pub trait SRDF: SRDFBasic {
         subject: &Self::Subject,
         preds: Vec<Self::IRI>,
     ) -> Result<OutgoingArcs<Self::IRI, Self::Term>, Self::Err>;
+
+    /// Get the nodes that can be reached from `node` following a [SHACL path](https://www.w3.org/TR/shacl/#property-paths)
+    fn objects_for_shacl_path(
+        &self,
+        node: &Self::Term,
+        path: &SHACLPath,
+    ) -> Result<HashSet<Self::Term>, Self::Err> {
+        match path {
+            SHACLPath::Predicate { pred } => match Self::term_as_subject(node) {
+                None => Ok(HashSet::new()),
+                Some(subject) => {
+                    self.objects_for_subject_predicate(&subject, &Self::iri_s2iri(pred))
+                }
+            },
+            SHACLPath::Alternative { paths } => {
+                let mut result = HashSet::new();
+                for path in paths {
+                    result.extend(self.objects_for_shacl_path(node, path)?)
+                }
+                Ok(result)
+            }
+            SHACLPath::Sequence { paths } => {
+                let mut current = HashSet::from([node.clone()]);
+                for path in paths {
+                    let mut next = HashSet::new();
+                    for node in current.iter() {
+                        next.extend(self.objects_for_shacl_path(node, path)?)
+                    }
+                    current = next
+                }
+                Ok(current)
+            }
+            SHACLPath::Inverse { path } => self.subjects_for_shacl_path(node, path),
+            SHACLPath::ZeroOrMore { path } => {
+                let mut result = path_closure(self, node, path, false)?;
+                result.insert(node.clone());
+                Ok(result)
+            }
+            SHACLPath::OneOrMore { path } => path_closure(self, node, path, false),
+            SHACLPath::ZeroOrOne { path } => {
+                let mut result = self.objects_for_shacl_path(node, path)?;
+                result.insert(node.clone());
+                Ok(result)
+            }
+        }
+    }
+
+    /// Get the nodes from which `node` can be reached following a [SHACL path](https://www.w3.org/TR/shacl/#property-paths)
+    fn subjects_for_shacl_path(
+        &self,
+        node: &Self::Term,
+        path: &SHACLPath,
+    ) -> Result<HashSet<Self::Term>, Self::Err> {
+        match path {
+            SHACLPath::Predicate { pred } => {
+                let subjects = self.subjects_with_predicate_object(&Self::iri_s2iri(pred), node)?;
+                Ok(subjects.iter().map(Self::subject_as_term).collect())
+            }
+            SHACLPath::Alternative { paths } => {
+                let mut result = HashSet::new();
+                for path in paths {
+                    result.extend(self.subjects_for_shacl_path(node, path)?)
+                }
+                Ok(result)
+            }
+            SHACLPath::Sequence { paths } => {
+                let mut current = HashSet::from([node.clone()]);
+                for path in paths.iter().rev() {
+                    let mut next = HashSet::new();
+                    for node in current.iter() {
+                        next.extend(self.subjects_for_shacl_path(node, path)?)
+                    }
+                    current = next
+                }
+                Ok(current)
+            }
+            SHACLPath::Inverse { path } => self.objects_for_shacl_path(node, path),
+            SHACLPath::ZeroOrMore { path } => {
+                let mut result = path_closure(self, node, path, true)?;
+                result.insert(node.clone());
+                Ok(result)
+            }
+            SHACLPath::OneOrMore { path } => path_closure(self, node, path, true),
+            SHACLPath::ZeroOrOne { path } => {
+                let mut result = self.subjects_for_shacl_path(node, path)?;
+                result.insert(node.clone());
+                Ok(result)
+            }
+        }
+    }
+}
+
+// Nodes reachable from `node` following `path` one or more times, backwards if `inverse` is true
+fn path_closure<S>(
+    rdf: &S,
+    node: &S::Term,
+    path: &SHACLPath,
+    inverse: bool,
+) -> Result<HashSet<S::Term>, S::Err>
+where
+    S: SRDF + ?Sized,
+{
+    let mut visited = HashSet::new();
+    let mut pending = vec![node.clone()];
+    while let Some(current) = pending.pop() {
+        let next = if inverse {
+            rdf.subjects_for_shacl_path(&current, path)?
+        } else {
+            rdf.objects_for_shacl_path(&current, path)?
+        };
+        for n in next {
+            if visited.insert(n.clone()) {
+                pending.push(n)
+            }
+        }
+    }
+    Ok(visited)
 }
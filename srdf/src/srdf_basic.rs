@@ -5,7 +5,7 @@ use iri_s::IriS;
 use oxrdf::Term as OxTerm;
 use prefixmap::{PrefixMap, PrefixMapError};
 
-use crate::Object;
+use crate::{Object, Triple};
 
 /// Types that implement this trait contain basic comparisons and conversions between nodes in RDF graphs
 ///
@@ -40,6 +40,9 @@ pub trait SRDFBasic {
     /// Returns `true` if the subject is a Blank Node
     fn subject_is_bnode(subject: &Self::Subject) -> bool;
 
+    /// Returns `true` if the subject is a quoted triple (RDF-star)
+    fn subject_is_triple(subject: &Self::Subject) -> bool;
+
     fn term_as_iri(object: &Self::Term) -> Option<Self::IRI>;
     fn term_as_bnode(object: &Self::Term) -> Option<Self::BNode>;
     fn term_as_literal(object: &Self::Term) -> Option<Self::Literal>;
@@ -86,6 +89,14 @@ pub trait SRDFBasic {
     fn term_is_bnode(object: &Self::Term) -> bool;
     fn term_is_literal(object: &Self::Term) -> bool;
 
+    /// Returns `true` if the term is a triple term (RDF-star)
+    fn term_is_triple(object: &Self::Term) -> bool;
+
+    /// Returns the subject, predicate and object of a triple term, None if it isn't a triple term
+    fn term_as_triple(object: &Self::Term) -> Option<Triple<Self>>
+    where
+        Self: Sized;
+
     fn term_as_subject(object: &Self::Term) -> Option<Self::Subject>;
 
     fn subject_as_term(subject: &Self::Subject) -> Self::Term;
@@ -0,0 +1,24 @@
+use iri_s::IriS;
+
+use crate::SRDF;
+
+/// Types that implement this trait contain RDF datasets, i.e. a default graph and a set of named graphs.
+///
+/// The neighbourhood queries from [`SRDF`] are evaluated on the active graph,
+/// which is the default graph unless a named graph has been selected.
+pub trait SRDFDataset: SRDF {
+    /// Returns the names of the named graphs in the dataset
+    fn graph_names(&self) -> Result<Vec<Self::Subject>, Self::Err>;
+
+    /// Returns the name of the active graph or `None` if it is the default graph
+    fn active_graph(&self) -> Option<Self::Subject>;
+
+    /// Selects the graph on which the queries are evaluated. `None` selects the default graph
+    fn set_active_graph(&mut self, graph_name: Option<&Self::Subject>) -> Result<(), Self::Err>;
+
+    /// Selects the named graph identified by `iri`
+    fn set_active_graph_iri(&mut self, iri: &IriS) -> Result<(), Self::Err> {
+        let graph_name = Self::iri_s2subject(iri);
+        self.set_active_graph(Some(&graph_name))
+    }
+}
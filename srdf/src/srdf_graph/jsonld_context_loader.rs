@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use oxjsonld::{JsonLdLoadDocumentOptions, JsonLdRemoteDocument};
+
+use crate::srdfgraph_error::SRDFGraphError;
+
+/// Extensions tried when a context is looked for in the context folders
+const CONTEXT_EXTENSIONS: [&str; 2] = ["jsonld", "json"];
+
+/// Offline loader of the JSON-LD contexts referenced with `@context`.
+///
+/// Contexts are never fetched from the network. A context IRI is resolved to a local file:
+/// - registered for that IRI with [`JsonLdContextLoader::with_context`],
+/// - given by the IRI itself if it is a `file:` IRI,
+/// - named as the last segment of the IRI, with or without the `.jsonld` or `.json` extension,
+///   in one of the folders added with [`JsonLdContextLoader::with_folder`].
+///
+/// Other contexts make the parsing fail.
+#[derive(Debug, Default, Clone)]
+pub struct JsonLdContextLoader {
+    contexts: HashMap<String, PathBuf>,
+    folders: Vec<PathBuf>,
+}
+
+impl JsonLdContextLoader {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the context identified by `iri` from the file in `path`
+    pub fn with_context(mut self, iri: &str, path: &Path) -> Self {
+        self.contexts.insert(iri.to_string(), path.to_path_buf());
+        self
+    }
+
+    /// Adds a folder in which contexts are looked for by the last segment of their IRI
+    pub fn with_folder(mut self, folder: &Path) -> Self {
+        self.folders.push(folder.to_path_buf());
+        self
+    }
+
+    /// Local file that contains the context identified by `iri`
+    pub fn context_path(&self, iri: &str) -> Result<PathBuf, SRDFGraphError> {
+        if let Some(path) = self.contexts.get(iri) {
+            return Ok(path.clone());
+        }
+        if let Some(path) = iri.strip_prefix("file://") {
+            return Ok(PathBuf::from(path));
+        }
+        let name = iri
+            .trim_end_matches('/')
+            .rsplit(['/', '#'])
+            .next()
+            .unwrap_or(iri);
+        for folder in &self.folders {
+            let path = folder.join(name);
+            if path.is_file() {
+                return Ok(path);
+            }
+            for extension in CONTEXT_EXTENSIONS {
+                let path = folder.join(format!("{name}.{extension}"));
+                if path.is_file() {
+                    return Ok(path);
+                }
+            }
+        }
+        Err(SRDFGraphError::JsonLdContextNotFound {
+            iri: iri.to_string(),
+        })
+    }
+
+    /// Loads the context identified by `iri`. It is used as `documentLoader` by the JSON-LD parser
+    pub fn load(
+        &self,
+        iri: &str,
+        _options: &JsonLdLoadDocumentOptions,
+    ) -> Result<JsonLdRemoteDocument, SRDFGraphError> {
+        let path = self.context_path(iri)?;
+        let document = std::fs::read(&path).map_err(|e| SRDFGraphError::ReadingPathError {
+            path_name: path.display().to_string(),
+            error: e,
+        })?;
+        Ok(JsonLdRemoteDocument {
+            document,
+            document_url: iri.to_string(),
+        })
+    }
+}
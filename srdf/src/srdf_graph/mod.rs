@@ -1,6 +1,8 @@
 //! Implementation of the SRDF traits using [OxRDF](https://crates.io/crates/oxrdf).
+pub mod jsonld_context_loader;
 pub mod srdfgraph;
 pub mod srdfgraph_error;
 
+pub use jsonld_context_loader::*;
 pub use srdfgraph::*;
 pub use srdfgraph_error::*;
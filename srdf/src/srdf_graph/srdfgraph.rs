@@ -6,9 +6,13 @@ use tracing::debug;
 use crate::async_srdf::AsyncSRDF;
 use crate::literal::Literal;
 use crate::numeric_literal::NumericLiteral;
-use crate::{FocusRDF, RDFFormat, SRDFBasic, SRDFBuilder, Triple as STriple, RDF_TYPE_STR, SRDF};
+use crate::{
+    FocusRDF, QuerySRDF, QuerySolutionIter, RDFFormat, SRDFBasic, SRDFBuilder, SRDFDataset,
+    SparqlEvaluator, Triple as STriple, RDF_TYPE_STR, SRDF,
+};
 use oxiri::Iri;
-use oxrdfio::{RdfFormat, RdfSerializer};
+use oxjsonld::JsonLdParser;
+use oxrdfio::{JsonLdProfileSet, RdfFormat, RdfSerializer};
 use oxrdfxml::RdfXmlParser;
 use rust_decimal::Decimal;
 use std::collections::hash_map::Entry;
@@ -20,21 +24,43 @@ use std::str::FromStr;
 
 use crate::lang::Lang;
 use crate::srdfgraph_error::SRDFGraphError;
+use crate::JsonLdContextLoader;
 use crate::Object;
 use oxrdf::{
-    BlankNode as OxBlankNode, Graph, Literal as OxLiteral, NamedNode as OxNamedNode,
-    Subject as OxSubject, Term as OxTerm, Triple as OxTriple,
+    dataset::GraphView, BlankNode as OxBlankNode, Dataset, GraphName, GraphNameRef,
+    Literal as OxLiteral, NamedNode as OxNamedNode, Quad as OxQuad, Subject as OxSubject,
+    Term as OxTerm, Triple as OxTriple,
 };
 use oxsdatatypes::Decimal as OxDecimal;
-use oxttl::{NQuadsParser, NTriplesParser, TurtleParser};
+use oxttl::n3::{N3Quad, N3Term};
+use oxttl::{N3Parser, NQuadsParser, NTriplesParser, TriGParser, TurtleParser};
 use prefixmap::{prefixmap::*, IriRef, PrefixMapError};
 
+/// Enables quoted triples (RDF-star) in the parsers of the Turtle family
+/// when the `rdf-star` feature is enabled.
+/// The parsers follow the RDF-star community group syntax, so RDF 1.2 triple terms are rejected
+macro_rules! quoted_triples {
+    ($parser: expr) => {{
+        #[cfg(feature = "rdf-star")]
+        let parser = $parser.with_quoted_triples();
+        #[cfg(not(feature = "rdf-star"))]
+        let parser = $parser;
+        parser
+    }};
+}
+
+/// RDF dataset made of a default graph and a set of named graphs.
+///
+/// The [`SRDF`] queries are evaluated on the active graph, which is the default graph
+/// unless another one is selected with [`SRDFDataset::set_active_graph`].
 #[derive(Debug, Default, Clone)]
 pub struct SRDFGraph {
     focus: Option<OxTerm>,
-    graph: Graph,
+    dataset: Dataset,
+    active_graph: GraphName,
     pm: PrefixMap,
     base: Option<IriS>,
+    jsonld_contexts: JsonLdContextLoader,
 }
 
 impl SRDFGraph {
@@ -42,12 +68,41 @@ impl SRDFGraph {
         Self::default()
     }
 
+    /// Sets the loader of the contexts referenced by JSON-LD documents
+    pub fn with_jsonld_contexts(mut self, jsonld_contexts: JsonLdContextLoader) -> Self {
+        self.jsonld_contexts = jsonld_contexts;
+        self
+    }
+
+    /// Number of triples in the active graph
     pub fn len(&self) -> usize {
-        self.graph.len()
+        self.graph().len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.graph.is_empty()
+        self.graph().is_empty()
+    }
+
+    /// Number of quads in the whole dataset
+    pub fn quads_len(&self) -> usize {
+        self.dataset.len()
+    }
+
+    fn graph(&self) -> GraphView<'_> {
+        self.dataset.graph(self.active_graph.as_ref())
+    }
+
+    fn insert_triple(&mut self, triple: &OxTriple) {
+        self.dataset
+            .insert(triple.as_ref().in_graph(GraphNameRef::DefaultGraph));
+    }
+
+    fn merge_base(&mut self, base: Option<Iri<String>>) {
+        self.base = match (&self.base, base) {
+            (None, None) => None,
+            (Some(b), None) => Some(b.clone()),
+            (_, Some(b)) => Some(IriS::new_unchecked(b.as_str())),
+        };
     }
 
     pub fn merge_from_reader<R: BufRead>(
@@ -60,26 +115,24 @@ impl SRDFGraph {
         match format {
             RDFFormat::Turtle => {
                 let turtle_parser = match base {
-                    None => TurtleParser::new(),
-                    Some(ref iri) => TurtleParser::new().with_base_iri(iri.as_str())?,
+                    None => quoted_triples!(TurtleParser::new()),
+                    Some(ref iri) => {
+                        quoted_triples!(TurtleParser::new()).with_base_iri(iri.as_str())?
+                    }
                 };
                 // let mut graph = Graph::default();
-                let mut reader = turtle_parser.parse_read(read);
+                let mut reader = turtle_parser.for_reader(read);
                 for triple_result in reader.by_ref() {
-                    self.graph.insert(triple_result?.as_ref());
+                    self.insert_triple(&triple_result?);
                 }
                 let prefixes: HashMap<&str, &str> = reader.prefixes().collect();
-                self.base = match (&self.base, base) {
-                    (None, None) => None,
-                    (Some(b), None) => Some(b.clone()),
-                    (_, Some(b)) => Some(IriS::new_unchecked(b.as_str())),
-                };
                 let pm = PrefixMap::from_hashmap(&prefixes)?;
+                self.merge_base(base);
                 self.merge_prefixes(pm)?;
             }
             RDFFormat::NTriples => {
-                let parser = NTriplesParser::new();
-                let mut reader = parser.parse_read(read);
+                let parser = quoted_triples!(NTriplesParser::new());
+                let mut reader = parser.for_reader(read);
                 for triple_result in reader.by_ref() {
                     match triple_result {
                         Err(e) => {
@@ -93,37 +146,135 @@ impl SRDFGraph {
                             }
                         }
                         Ok(t) => {
-                            self.graph.insert(t.as_ref());
+                            self.insert_triple(&t);
                         }
                     }
                 }
             }
             RDFFormat::RDFXML => {
                 let parser = RdfXmlParser::new();
-                let mut reader = parser.parse_read(read);
+                let mut reader = parser.for_reader(read);
                 for triple_result in reader.by_ref() {
                     match triple_result {
                         Err(e) => {
                             debug!("Error captured: {e:?}")
                         }
                         Ok(t) => {
-                            self.graph.insert(t.as_ref());
+                            self.insert_triple(&t);
+                        }
+                    }
+                }
+            }
+            RDFFormat::TriG => {
+                let parser = match base {
+                    None => quoted_triples!(TriGParser::new()),
+                    Some(ref iri) => {
+                        quoted_triples!(TriGParser::new()).with_base_iri(iri.as_str())?
+                    }
+                };
+                let mut reader = parser.for_reader(read);
+                for quad_result in reader.by_ref() {
+                    match quad_result {
+                        Err(e) => {
+                            if reader_mode.is_strict() {
+                                return Err(SRDFGraphError::TurtleError {
+                                    data: "Reading TriG".to_string(),
+                                    turtle_error: e,
+                                });
+                            } else {
+                                debug!("Error captured: {e:?}")
+                            }
+                        }
+                        Ok(q) => {
+                            self.dataset.insert(&q);
                         }
                     }
                 }
+                let prefixes: HashMap<&str, &str> = reader.prefixes().collect();
+                let pm = PrefixMap::from_hashmap(&prefixes)?;
+                self.merge_base(base);
+                self.merge_prefixes(pm)?;
+            }
+            RDFFormat::N3 => {
+                let parser = match base {
+                    None => N3Parser::new(),
+                    Some(ref iri) => N3Parser::new().with_base_iri(iri.as_str())?,
+                };
+                let mut reader = parser.for_reader(read);
+                for quad_result in reader.by_ref() {
+                    match quad_result {
+                        Err(e) => {
+                            if reader_mode.is_strict() {
+                                return Err(SRDFGraphError::TurtleError {
+                                    data: "Reading N3".to_string(),
+                                    turtle_error: e,
+                                });
+                            } else {
+                                debug!("Error captured: {e:?}")
+                            }
+                        }
+                        Ok(n3_quad) => match cnv_n3_quad(&n3_quad) {
+                            Some(q) => {
+                                self.dataset.insert(&q);
+                            }
+                            None => {
+                                if reader_mode.is_strict() {
+                                    return Err(SRDFGraphError::N3NotRDF {
+                                        quad: n3_quad.to_string(),
+                                    });
+                                } else {
+                                    debug!("N3 statement ignored because it is not RDF: {n3_quad}")
+                                }
+                            }
+                        },
+                    }
+                }
+                let prefixes: HashMap<&str, &str> = reader.prefixes().collect();
+                let pm = PrefixMap::from_hashmap(&prefixes)?;
+                self.merge_base(base);
+                self.merge_prefixes(pm)?;
+            }
+            RDFFormat::JsonLd => {
+                let parser = match base {
+                    None => JsonLdParser::new(),
+                    Some(ref iri) => JsonLdParser::new().with_base_iri(iri.as_str())?,
+                };
+                let loader = self.jsonld_contexts.clone();
+                let mut reader =
+                    parser
+                        .for_reader(read)
+                        .with_load_document_callback(move |iri, options| {
+                            Ok(loader.load(iri, options)?)
+                        });
+                for quad_result in reader.by_ref() {
+                    match quad_result {
+                        Err(e) => {
+                            if reader_mode.is_strict() {
+                                return Err(e.into());
+                            } else {
+                                debug!("Error captured: {e:?}")
+                            }
+                        }
+                        Ok(q) => {
+                            self.dataset.insert(&q);
+                        }
+                    }
+                }
+                let prefixes: HashMap<&str, &str> = reader.prefixes().collect();
+                let pm = PrefixMap::from_hashmap(&prefixes)?;
+                self.merge_base(base);
+                self.merge_prefixes(pm)?;
             }
-            RDFFormat::TriG => todo!(),
-            RDFFormat::N3 => todo!(),
             RDFFormat::NQuads => {
-                let parser = NQuadsParser::new();
-                let mut reader = parser.parse_read(read);
-                for triple_result in reader.by_ref() {
-                    match triple_result {
+                let parser = quoted_triples!(NQuadsParser::new());
+                let mut reader = parser.for_reader(read);
+                for quad_result in reader.by_ref() {
+                    match quad_result {
                         Err(e) => {
                             debug!("Error captured: {e:?}")
                         }
-                        Ok(t) => {
-                            self.graph.insert(t.as_ref());
+                        Ok(q) => {
+                            self.dataset.insert(&q);
                         }
                     }
                 }
@@ -152,7 +303,7 @@ impl SRDFGraph {
             Some(ref iri) => TurtleParser::new().with_base_iri(iri.as_str())?,
         };
         let mut graph = Graph::default();
-        let mut reader = turtle_parser.parse_read(read);
+        let mut reader = turtle_parser.for_reader(read);
         for triple_result in reader.by_ref() {
             graph.insert(triple_result?.as_ref());
         }
@@ -182,6 +333,16 @@ impl SRDFGraph {
         format!("{}", str.red())
     }
 
+    #[cfg(feature = "rdf-star")]
+    pub fn qualify_triple(&self, triple: &OxTriple) -> String {
+        format!(
+            "<< {} {} {} >>",
+            self.qualify_subject(&triple.subject),
+            self.qualify_iri(&triple.predicate),
+            self.qualify_term(&triple.object)
+        )
+    }
+
     pub fn from_str(
         data: &str,
         format: &RDFFormat,
@@ -272,6 +433,13 @@ impl SRDFBasic for SRDFGraph {
     fn subject_is_bnode(subject: &OxSubject) -> bool {
         matches!(subject, OxSubject::BlankNode(_))
     }
+    fn subject_is_triple(subject: &OxSubject) -> bool {
+        match subject {
+            #[cfg(feature = "rdf-star")]
+            OxSubject::Triple(_) => true,
+            _ => false,
+        }
+    }
 
     fn term_as_iri(object: &OxTerm) -> Option<OxNamedNode> {
         match object {
@@ -286,6 +454,18 @@ impl SRDFBasic for SRDFGraph {
         }
     }
 
+    fn term_as_triple(object: &OxTerm) -> Option<STriple<Self>> {
+        match object {
+            #[cfg(feature = "rdf-star")]
+            OxTerm::Triple(t) => Some(STriple::new(
+                t.subject.clone(),
+                t.predicate.clone(),
+                t.object.clone(),
+            )),
+            _ => None,
+        }
+    }
+
     fn term_as_literal(object: &OxTerm) -> Option<OxLiteral> {
         match object {
             OxTerm::Literal(l) => Some(l.clone()),
@@ -304,12 +484,20 @@ impl SRDFBasic for SRDFGraph {
         matches!(object, OxTerm::Literal(_))
     }
 
+    fn term_is_triple(object: &OxTerm) -> bool {
+        match object {
+            #[cfg(feature = "rdf-star")]
+            OxTerm::Triple(_) => true,
+            _ => false,
+        }
+    }
+
     fn subject_as_term(subject: &Self::Subject) -> Self::Term {
         match subject {
             OxSubject::NamedNode(n) => OxTerm::NamedNode(n.clone()),
             OxSubject::BlankNode(b) => OxTerm::BlankNode(b.clone()),
             #[cfg(feature = "rdf-star")]
-            OxSubject::Triple(_) => unimplemented!(),
+            OxSubject::Triple(t) => OxTerm::Triple(t.clone()),
         }
     }
 
@@ -317,6 +505,8 @@ impl SRDFBasic for SRDFGraph {
         match object {
             OxTerm::NamedNode(n) => Some(OxSubject::NamedNode(n.clone())),
             OxTerm::BlankNode(b) => Some(OxSubject::BlankNode(b.clone())),
+            #[cfg(feature = "rdf-star")]
+            OxTerm::Triple(t) => Some(OxSubject::Triple(t.clone())),
             _ => None,
         }
     }
@@ -378,7 +568,11 @@ impl SRDFBasic for SRDFGraph {
             }
             OxTerm::NamedNode(iri) => Object::Iri(Self::iri2iri_s(iri)),
             #[cfg(feature = "rdf-star")]
-            OxTerm::Triple(_) => unimplemented!(),
+            OxTerm::Triple(t) => Object::triple(
+                Self::subject_as_object(&t.subject),
+                Self::iri2iri_s(&t.predicate),
+                Self::term_as_object(&t.object),
+            ),
         }
     }
 
@@ -397,7 +591,7 @@ impl SRDFBasic for SRDFGraph {
             OxSubject::BlankNode(bn) => self.show_blanknode(bn),
             OxSubject::NamedNode(n) => self.qualify_iri(n),
             #[cfg(feature = "rdf-star")]
-            OxSubject::Triple(_) => unimplemented!(),
+            OxSubject::Triple(t) => self.qualify_triple(t),
         }
     }
 
@@ -407,7 +601,7 @@ impl SRDFBasic for SRDFGraph {
             OxTerm::Literal(lit) => self.show_literal(lit),
             OxTerm::NamedNode(n) => self.qualify_iri(n),
             #[cfg(feature = "rdf-star")]
-            OxTerm::Triple(_) => unimplemented!(),
+            OxTerm::Triple(t) => self.qualify_triple(t),
         }
     }
 
@@ -432,7 +626,7 @@ impl SRDFBasic for SRDFGraph {
                     Literal::StringLiteral { lexical_form, lang } => match lang {
                         Some(lang) => OxLiteral::new_language_tagged_literal_unchecked(
                             lexical_form,
-                            lang.to_string(),
+                            lang.value(),
                         ),
                         None => OxLiteral::new_simple_literal(lexical_form),
                     },
@@ -455,6 +649,16 @@ impl SRDFBasic for SRDFGraph {
                 };
                 OxTerm::Literal(literal)
             }
+            Object::Triple {
+                subject,
+                predicate,
+                object,
+            } => cnv_quoted_triple(
+                Self::object_as_subject(subject),
+                Self::iri_s2iri(predicate),
+                Self::object_as_term(object),
+                obj,
+            ),
         }
     }
 
@@ -467,8 +671,34 @@ fn cnv_iri_ref(iri_ref: &IriRef) -> OxNamedNode {
     OxNamedNode::new_unchecked(iri_ref.to_string())
 }
 
-fn cnv_decimal(_d: &Decimal) -> OxDecimal {
-    todo!()
+/// Builds the oxrdf triple term of a quoted triple.
+/// It panics if the subject can't act as a subject or if the `rdf-star` feature is not enabled
+#[cfg(feature = "rdf-star")]
+pub(crate) fn cnv_quoted_triple(
+    subject: Option<OxSubject>,
+    predicate: OxNamedNode,
+    object: OxTerm,
+    triple: &Object,
+) -> OxTerm {
+    let subject = subject.unwrap_or_else(|| {
+        panic!("Subject of quoted triple {triple} must be an IRI, a blank node or a triple")
+    });
+    OxTerm::Triple(Box::new(OxTriple::new(subject, predicate, object)))
+}
+
+#[cfg(not(feature = "rdf-star"))]
+pub(crate) fn cnv_quoted_triple(
+    _subject: Option<OxSubject>,
+    _predicate: OxNamedNode,
+    _object: OxTerm,
+    triple: &Object,
+) -> OxTerm {
+    panic!("Quoted triple {triple} requires the rdf-star feature of srdf")
+}
+
+fn cnv_decimal(d: &Decimal) -> OxDecimal {
+    OxDecimal::from_str(&d.to_string())
+        .unwrap_or_else(|e| panic!("Decimal {d} is out of the range of xsd:decimal: {e}"))
 }
 
 impl SRDF for SRDFGraph {
@@ -477,7 +707,7 @@ impl SRDF for SRDFGraph {
         subject: &Self::Subject,
     ) -> Result<HashSet<Self::IRI>, Self::Err> {
         let mut ps = HashSet::new();
-        for triple in self.graph.triples_for_subject(subject) {
+        for triple in self.graph().triples_for_subject(subject) {
             let pred = triple.predicate.into_owned();
             ps.insert(pred);
         }
@@ -491,7 +721,10 @@ impl SRDF for SRDFGraph {
     ) -> Result<HashSet<Self::Term>, Self::Err> {
         let predicate = pred.as_ref();
         let mut result = HashSet::new();
-        for o in self.graph.objects_for_subject_predicate(subject, predicate) {
+        for o in self
+            .graph()
+            .objects_for_subject_predicate(subject, predicate)
+        {
             result.insert(o.into_owned());
         }
         Ok(result)
@@ -504,7 +737,7 @@ impl SRDF for SRDFGraph {
     ) -> Result<HashSet<Self::Subject>, Self::Err> {
         let mut result = HashSet::new();
         for subj in self
-            .graph
+            .graph()
             .subjects_for_predicate_object(pred.as_ref(), object.as_ref())
         {
             result.insert(subj.into_owned());
@@ -517,7 +750,7 @@ impl SRDF for SRDFGraph {
         subject: &Self::Subject,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Term>>, Self::Err> {
         let mut results: HashMap<Self::IRI, HashSet<Self::Term>> = HashMap::new();
-        for triple in self.graph.triples_for_subject(subject) {
+        for triple in self.graph().triples_for_subject(subject) {
             let pred = triple.predicate.into_owned();
             let term = triple.object.into_owned();
             match results.entry(pred) {
@@ -537,7 +770,7 @@ impl SRDF for SRDFGraph {
         object: &Self::Term,
     ) -> Result<HashMap<Self::IRI, HashSet<Self::Subject>>, Self::Err> {
         let mut results: HashMap<Self::IRI, HashSet<Self::Subject>> = HashMap::new();
-        for triple in self.graph.triples_for_object(object) {
+        for triple in self.graph().triples_for_object(object) {
             let pred = triple.predicate.into_owned();
             let subj = triple.subject.into_owned();
             match results.entry(pred) {
@@ -559,7 +792,7 @@ impl SRDF for SRDFGraph {
     ) -> Result<(HashMap<Self::IRI, HashSet<Self::Term>>, Vec<Self::IRI>), Self::Err> {
         let mut results: HashMap<Self::IRI, HashSet<Self::Term>> = HashMap::new();
         let mut remainder = Vec::new();
-        for triple in self.graph.triples_for_subject(subject) {
+        for triple in self.graph().triples_for_subject(subject) {
             let pred = triple.predicate.into_owned();
             let term = triple.object.into_owned();
             if preds.contains(&pred) {
@@ -583,7 +816,7 @@ impl SRDF for SRDFGraph {
         pred: &Self::IRI,
     ) -> Result<Vec<crate::Triple<Self>>, Self::Err> {
         let mut result = Vec::new();
-        for triple in self.graph.triples_for_predicate(pred) {
+        for triple in self.graph().triples_for_predicate(pred) {
             let subj = triple.subject.into_owned();
             let pred = triple.predicate.into_owned();
             let obj = triple.object.into_owned();
@@ -591,6 +824,15 @@ impl SRDF for SRDFGraph {
         }
         Ok(result)
     }
+
+    fn subjects(&self) -> Result<HashSet<Self::Subject>, Self::Err> {
+        let subjects = self
+            .graph()
+            .iter()
+            .map(|triple| triple.subject.into_owned())
+            .collect();
+        Ok(subjects)
+    }
 }
 
 #[async_trait]
@@ -607,7 +849,7 @@ impl AsyncSRDF for SRDFGraph {
         subject: &OxSubject,
     ) -> Result<HashSet<OxNamedNode>, SRDFGraphError> {
         let mut results = HashSet::new();
-        for triple in self.graph.triples_for_subject(subject) {
+        for triple in self.graph().triples_for_subject(subject) {
             let predicate: OxNamedNode = triple.predicate.to_owned().into();
             results.insert(predicate);
         }
@@ -620,7 +862,7 @@ impl AsyncSRDF for SRDFGraph {
         pred: &OxNamedNode,
     ) -> Result<HashSet<OxTerm>, SRDFGraphError> {
         let mut results = HashSet::new();
-        for triple in self.graph.triples_for_subject(subject) {
+        for triple in self.graph().triples_for_subject(subject) {
             let predicate: OxNamedNode = triple.predicate.to_owned().into();
             if predicate.eq(pred) {
                 let object: OxTerm = triple.object.to_owned().into();
@@ -636,7 +878,7 @@ impl AsyncSRDF for SRDFGraph {
         pred: &OxNamedNode,
     ) -> Result<HashSet<OxSubject>, SRDFGraphError> {
         let mut results = HashSet::new();
-        for triple in self.graph.triples_for_object(object) {
+        for triple in self.graph().triples_for_object(object) {
             let predicate: OxNamedNode = triple.predicate.to_owned().into();
             if predicate.eq(pred) {
                 let subject: OxSubject = triple.subject.to_owned().into();
@@ -680,7 +922,8 @@ impl SRDFBuilder for SRDFGraph {
         obj: &Self::Term,
     ) -> Result<(), Self::Err> {
         let triple = OxTriple::new(subj.clone(), pred.clone(), obj.clone());
-        self.graph.insert(&triple);
+        self.dataset
+            .insert(triple.as_ref().in_graph(self.active_graph.as_ref()));
         Ok(())
     }
 
@@ -691,7 +934,8 @@ impl SRDFBuilder for SRDFGraph {
         obj: &Self::Term,
     ) -> Result<(), Self::Err> {
         let triple = OxTriple::new(subj.clone(), pred.clone(), obj.clone());
-        self.graph.remove(&triple);
+        self.dataset
+            .remove(triple.as_ref().in_graph(self.active_graph.as_ref()));
         Ok(())
     }
 
@@ -699,7 +943,8 @@ impl SRDFBuilder for SRDFGraph {
         match Self::object_as_subject(node) {
             Some(subj) => {
                 let triple = OxTriple::new(subj, rdf_type(), r#type.clone());
-                self.graph.insert(&triple);
+                self.dataset
+                    .insert(triple.as_ref().in_graph(self.active_graph.as_ref()));
                 Ok(())
             }
             None => {
@@ -711,29 +956,128 @@ impl SRDFBuilder for SRDFGraph {
     fn empty() -> Self {
         SRDFGraph {
             focus: None,
-            graph: Graph::new(),
+            dataset: Dataset::new(),
+            active_graph: GraphName::DefaultGraph,
             pm: PrefixMap::new(),
             base: None,
+            jsonld_contexts: JsonLdContextLoader::new(),
         }
     }
 
+    /// Serializes the active graph or, if the format supports datasets, every graph of the dataset
     fn serialize<W: Write>(&self, format: RDFFormat, write: W) -> Result<(), Self::Err> {
-        let mut serializer = RdfSerializer::from_format(cnv_rdf_format(format));
+        let rdf_format = cnv_rdf_format(format);
+        let mut serializer = RdfSerializer::from_format(rdf_format);
 
         for (prefix, iri) in &self.pm.map {
             serializer = serializer.with_prefix(prefix, iri.as_str()).unwrap();
         }
 
-        let mut writer = serializer.serialize_to_write(write);
-        for triple in self.graph.iter() {
-            writer.write_triple(triple)?;
+        let mut writer = serializer.for_writer(write);
+        if rdf_format.supports_datasets() {
+            for quad in self.dataset.iter() {
+                writer.serialize_quad(quad)?;
+            }
+        } else {
+            for triple in self.graph().iter() {
+                writer.serialize_triple(triple)?;
+            }
         }
         writer.finish()?;
         Ok(())
     }
 }
 
-fn cnv_rdf_format(rdf_format: RDFFormat) -> RdfFormat {
+/// Evaluates SPARQL queries over the triples of the graph with [`SparqlEvaluator`]
+impl QuerySRDF for SRDFGraph {
+    fn query_select(&self, query: &str) -> Result<QuerySolutionIter<SRDFGraph>, SRDFGraphError> {
+        Ok(SparqlEvaluator::new(self).select(query)?)
+    }
+
+    fn query_ask(&self, query: &str) -> Result<bool, SRDFGraphError> {
+        Ok(SparqlEvaluator::new(self).ask(query)?)
+    }
+}
+
+impl SRDFDataset for SRDFGraph {
+    fn graph_names(&self) -> Result<Vec<Self::Subject>, Self::Err> {
+        let names: HashSet<OxSubject> = self
+            .dataset
+            .iter()
+            .filter_map(|quad| match quad.graph_name {
+                GraphNameRef::NamedNode(n) => Some(OxSubject::NamedNode(n.into_owned())),
+                GraphNameRef::BlankNode(b) => Some(OxSubject::BlankNode(b.into_owned())),
+                GraphNameRef::DefaultGraph => None,
+            })
+            .collect();
+        let mut names: Vec<_> = names.into_iter().collect();
+        names.sort_by_key(|name| name.to_string());
+        Ok(names)
+    }
+
+    fn active_graph(&self) -> Option<Self::Subject> {
+        match &self.active_graph {
+            GraphName::NamedNode(n) => Some(OxSubject::NamedNode(n.clone())),
+            GraphName::BlankNode(b) => Some(OxSubject::BlankNode(b.clone())),
+            GraphName::DefaultGraph => None,
+        }
+    }
+
+    fn set_active_graph(&mut self, graph_name: Option<&Self::Subject>) -> Result<(), Self::Err> {
+        let graph_name = match graph_name {
+            None => GraphName::DefaultGraph,
+            Some(OxSubject::NamedNode(n)) => GraphName::NamedNode(n.clone()),
+            Some(OxSubject::BlankNode(b)) => GraphName::BlankNode(b.clone()),
+            #[cfg(feature = "rdf-star")]
+            Some(OxSubject::Triple(t)) => {
+                return Err(SRDFGraphError::GraphNotFound {
+                    graph_name: t.to_string(),
+                })
+            }
+        };
+        if graph_name != GraphName::DefaultGraph
+            && self
+                .dataset
+                .quads_for_graph_name(graph_name.as_ref())
+                .next()
+                .is_none()
+        {
+            return Err(SRDFGraphError::GraphNotFound {
+                graph_name: graph_name.to_string(),
+            });
+        }
+        self.active_graph = graph_name;
+        Ok(())
+    }
+}
+
+/// Converts an N3 statement to an RDF quad.
+/// It returns `None` if the statement contains variables, literals as subjects or non IRI predicates
+fn cnv_n3_quad(quad: &N3Quad) -> Option<OxQuad> {
+    let subject = match &quad.subject {
+        N3Term::NamedNode(n) => OxSubject::NamedNode(n.clone()),
+        N3Term::BlankNode(b) => OxSubject::BlankNode(b.clone()),
+        _ => return None,
+    };
+    let predicate = match &quad.predicate {
+        N3Term::NamedNode(n) => n.clone(),
+        _ => return None,
+    };
+    let object = match &quad.object {
+        N3Term::NamedNode(n) => OxTerm::NamedNode(n.clone()),
+        N3Term::BlankNode(b) => OxTerm::BlankNode(b.clone()),
+        N3Term::Literal(l) => OxTerm::Literal(l.clone()),
+        _ => return None,
+    };
+    Some(OxQuad::new(
+        subject,
+        predicate,
+        object,
+        quad.graph_name.clone(),
+    ))
+}
+
+pub(crate) fn cnv_rdf_format(rdf_format: RDFFormat) -> RdfFormat {
     match rdf_format {
         RDFFormat::NTriples => RdfFormat::NTriples,
         RDFFormat::Turtle => RdfFormat::Turtle,
@@ -741,6 +1085,9 @@ fn cnv_rdf_format(rdf_format: RDFFormat) -> RdfFormat {
         RDFFormat::TriG => RdfFormat::TriG,
         RDFFormat::N3 => RdfFormat::N3,
         RDFFormat::NQuads => RdfFormat::NQuads,
+        RDFFormat::JsonLd => RdfFormat::JsonLd {
+            profile: JsonLdProfileSet::empty(),
+        },
     }
 }
 
@@ -751,7 +1098,7 @@ fn rdf_type() -> OxNamedNode {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{int, srdf, SRDFGraph, SRDF};
+    use crate::{int, srdf, SHACLPath, SRDFGraph, SRDF};
     use iri_s::iri;
 
     #[tokio::test]
@@ -818,6 +1165,274 @@ mod tests {
         assert_eq!(outgoing.get(&p), Some(&HashSet::from([one])))
     }
 
+    #[test]
+    fn test_objects_for_shacl_path() {
+        let s = r#"prefix : <http://example.org/>
+        :a :p :b .
+        :b :p :c .
+        :c :q :d .
+        "#;
+        let graph = SRDFGraph::from_str(s, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let term = |name: &str| {
+            <SRDFGraph as SRDFBasic>::iri_s2term(&IriS::new_unchecked(
+                format!("http://example.org/{name}").as_str(),
+            ))
+        };
+        let p = SHACLPath::iri(iri!("http://example.org/p"));
+        let q = SHACLPath::iri(iri!("http://example.org/q"));
+        let path = SHACLPath::Sequence {
+            paths: vec![
+                SHACLPath::OneOrMore {
+                    path: Box::new(p.clone()),
+                },
+                q,
+            ],
+        };
+        assert_eq!(
+            graph.objects_for_shacl_path(&term("a"), &path).unwrap(),
+            HashSet::from([term("d")])
+        );
+        assert_eq!(
+            graph.subjects_for_shacl_path(&term("d"), &path).unwrap(),
+            HashSet::from([term("a"), term("b")])
+        );
+        let inverse = SHACLPath::ZeroOrMore {
+            path: Box::new(SHACLPath::Inverse { path: Box::new(p) }),
+        };
+        assert_eq!(
+            graph.objects_for_shacl_path(&term("c"), &inverse).unwrap(),
+            HashSet::from([term("a"), term("b"), term("c")])
+        );
+    }
+
+    #[test]
+    fn test_trig_named_graphs() {
+        let s = r#"prefix : <http://example.org/>
+        :alice :knows :bob .
+        :g1 { :alice :knows :carol . }
+        :g2 { :alice :name "Alice" . }
+        "#;
+        let mut graph =
+            SRDFGraph::from_str(s, &RDFFormat::TriG, None, &ReaderMode::Strict).unwrap();
+        let subject = |name: &str| {
+            <SRDFGraph as SRDFBasic>::iri_s2subject(&IriS::new_unchecked(
+                format!("http://example.org/{name}").as_str(),
+            ))
+        };
+        let knows = <SRDFGraph as SRDFBasic>::iri_s2iri(&iri!("http://example.org/knows"));
+        let carol = <SRDFGraph as SRDFBasic>::iri_s2term(&iri!("http://example.org/carol"));
+        assert_eq!(graph.len(), 1);
+        assert_eq!(graph.quads_len(), 3);
+        assert_eq!(
+            graph.graph_names().unwrap(),
+            vec![subject("g1"), subject("g2")]
+        );
+
+        graph
+            .set_active_graph_iri(&iri!("http://example.org/g1"))
+            .unwrap();
+        assert_eq!(graph.active_graph(), Some(subject("g1")));
+        assert_eq!(
+            graph
+                .objects_for_subject_predicate(&subject("alice"), &knows)
+                .unwrap(),
+            HashSet::from([carol])
+        );
+        assert!(graph.set_active_graph(Some(&subject("g3"))).is_err());
+
+        let mut nquads = Vec::new();
+        graph.serialize(RDFFormat::NQuads, &mut nquads).unwrap();
+        let parsed = SRDFGraph::from_reader(
+            nquads.as_slice(),
+            &RDFFormat::NQuads,
+            None,
+            &ReaderMode::Strict,
+        )
+        .unwrap();
+        assert_eq!(parsed.quads_len(), 3);
+    }
+
+    #[test]
+    fn test_query_select_and_ask() {
+        let s = r#"prefix : <http://example.org/>
+            :alice :knows :bob ; :age 30 .
+            :bob   :knows :carol ; :age 12 .
+            :carol :name "Carol" .
+        "#;
+        let graph = SRDFGraph::from_str(s, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let query = r#"prefix : <http://example.org/>
+            select ?x ?age where {
+                ?x :knows+ :carol
+                OPTIONAL { ?x :age ?age FILTER (?age > 18) }
+            } order by desc(?x)"#;
+        let solutions: Vec<_> = graph
+            .query_select(query)
+            .unwrap()
+            .map(|solution| {
+                let solution = solution.unwrap();
+                (
+                    solution.find_solution("x").unwrap().to_string(),
+                    solution.find_solution("age").map(|age| age.to_string()),
+                )
+            })
+            .collect();
+        assert_eq!(
+            solutions,
+            vec![
+                ("<http://example.org/bob>".to_string(), None),
+                (
+                    "<http://example.org/alice>".to_string(),
+                    Some("\"30\"^^<http://www.w3.org/2001/XMLSchema#integer>".to_string())
+                ),
+            ]
+        );
+        assert!(graph
+            .query_ask("ask { <http://example.org/carol> ?p \"Carol\" }")
+            .unwrap());
+        assert!(matches!(
+            graph.query_ask("select ?x where { ?x ?p ?o }"),
+            Err(SRDFGraphError::SparqlEvalError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_jsonld_round_trip() {
+        let s = r#"{
+          "@context": { "schema": "http://schema.org/", "@base": "http://example.org/" },
+          "@id": "alice",
+          "@type": "schema:Person",
+          "schema:name": "Alice",
+          "schema:knows": { "@id": "bob", "schema:name": { "@value": "Bob", "@language": "en" } }
+        }"#;
+        let graph = SRDFGraph::from_str(s, &RDFFormat::JsonLd, None, &ReaderMode::Strict).unwrap();
+        assert_eq!(graph.len(), 4);
+        assert_eq!(
+            graph.prefixmap().find("schema"),
+            Some(&iri!("http://schema.org/"))
+        );
+        let mut jsonld = Vec::new();
+        graph.serialize(RDFFormat::JsonLd, &mut jsonld).unwrap();
+        let parsed = SRDFGraph::from_reader(
+            jsonld.as_slice(),
+            &RDFFormat::JsonLd,
+            None,
+            &ReaderMode::Strict,
+        )
+        .unwrap();
+        let alice = <SRDFGraph as SRDFBasic>::iri_s2subject(&iri!("http://example.org/alice"));
+        assert_eq!(
+            parsed.outgoing_arcs(&alice).unwrap(),
+            graph.outgoing_arcs(&alice).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_jsonld_local_contexts() {
+        let s = r#"{
+          "@context": "https://example.org/contexts/person.jsonld",
+          "@id": "http://example.org/alice",
+          "name": "Alice"
+        }"#;
+        assert!(matches!(
+            SRDFGraph::from_str(s, &RDFFormat::JsonLd, None, &ReaderMode::Strict),
+            Err(SRDFGraphError::JsonLdError { .. })
+        ));
+
+        let folder = std::env::temp_dir().join(format!("srdf_contexts_{}", std::process::id()));
+        std::fs::create_dir_all(&folder).unwrap();
+        std::fs::write(
+            folder.join("person.jsonld"),
+            r#"{ "@context": { "name": "http://schema.org/name" } }"#,
+        )
+        .unwrap();
+        let mut graph =
+            SRDFGraph::new().with_jsonld_contexts(JsonLdContextLoader::new().with_folder(&folder));
+        graph
+            .merge_from_reader(s.as_bytes(), &RDFFormat::JsonLd, None, &ReaderMode::Strict)
+            .unwrap();
+        std::fs::remove_dir_all(&folder).unwrap();
+        let alice = <SRDFGraph as SRDFBasic>::iri_s2subject(&iri!("http://example.org/alice"));
+        let name = <SRDFGraph as SRDFBasic>::iri_s2iri(&iri!("http://schema.org/name"));
+        assert_eq!(
+            graph.objects_for_subject_predicate(&alice, &name).unwrap(),
+            HashSet::from([OxTerm::from(OxLiteral::new_simple_literal("Alice"))])
+        );
+    }
+
+    #[cfg(feature = "rdf-star")]
+    #[test]
+    fn test_turtle_star_round_trip() {
+        let s = r#"prefix : <http://example.org/>
+        :alice :claims << :bob :name "Bob" >> .
+        << :alice :knows :bob >> :source :wikipedia .
+        "#;
+        let graph = SRDFGraph::from_str(s, &RDFFormat::Turtle, None, &ReaderMode::Strict).unwrap();
+        let ex = |name: &str| IriS::new_unchecked(format!("http://example.org/{name}").as_str());
+        let alice = <SRDFGraph as SRDFBasic>::iri_s2subject(&ex("alice"));
+        let claims = <SRDFGraph as SRDFBasic>::iri_s2iri(&ex("claims"));
+        let claim = graph
+            .objects_for_subject_predicate(&alice, &claims)
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap();
+        assert!(SRDFGraph::term_is_triple(&claim));
+        let object = SRDFGraph::term_as_object(&claim);
+        assert_eq!(
+            object,
+            Object::triple(
+                Object::iri(ex("bob")),
+                ex("name"),
+                Object::literal(crate::literal::Literal::str("Bob"))
+            )
+        );
+        assert_eq!(SRDFGraph::object_as_term(&object), claim);
+        let triple = SRDFGraph::term_as_triple(&claim).unwrap();
+        assert_eq!(SRDFGraph::iri2iri_s(&triple.pred()), ex("name"));
+
+        let source = <SRDFGraph as SRDFBasic>::iri_s2iri(&ex("source"));
+        let wikipedia = <SRDFGraph as SRDFBasic>::iri_s2term(&ex("wikipedia"));
+        let annotated = graph
+            .subjects_with_predicate_object(&source, &wikipedia)
+            .unwrap();
+        assert!(annotated.iter().all(SRDFGraph::subject_is_triple));
+        assert_eq!(annotated.len(), 1);
+
+        let mut turtle = Vec::new();
+        graph.serialize(RDFFormat::Turtle, &mut turtle).unwrap();
+        let parsed = SRDFGraph::from_reader(
+            turtle.as_slice(),
+            &RDFFormat::Turtle,
+            None,
+            &ReaderMode::Strict,
+        )
+        .unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(
+            parsed
+                .objects_for_subject_predicate(&alice, &claims)
+                .unwrap(),
+            HashSet::from([claim])
+        );
+
+        let rdf12 = r#"prefix : <http://example.org/>
+        :alice :claims <<( :bob :name "Bob" )>> .
+        "#;
+        assert!(SRDFGraph::from_str(rdf12, &RDFFormat::Turtle, None, &ReaderMode::Strict).is_err());
+    }
+
+    #[test]
+    fn test_n3_formulas_are_not_rdf() {
+        let s = r#"@prefix : <http://example.org/> .
+        :alice :knows :bob .
+        { ?x :knows ?y } => { ?y :knows ?x } .
+        "#;
+        assert!(SRDFGraph::from_str(s, &RDFFormat::N3, None, &ReaderMode::Strict).is_err());
+        let graph = SRDFGraph::from_str(s, &RDFFormat::N3, None, &ReaderMode::Lax).unwrap();
+        // The default graph keeps the `log:implies` triple between both formulas
+        assert_eq!(graph.len(), 2);
+    }
+
     #[test]
     fn test_parser() {
         use crate::{ok, rdf_parser, RDFNodeParse};
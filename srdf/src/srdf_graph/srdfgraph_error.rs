@@ -1,5 +1,7 @@
+use crate::SparqlEvalError;
 use iri_s::IriSError;
 use oxiri::IriParseError;
+use oxjsonld::JsonLdParseError;
 use oxttl::TurtleParseError;
 use prefixmap::PrefixMapError;
 use std::io;
@@ -29,6 +31,21 @@ pub enum SRDFGraphError {
         turtle_error: TurtleParseError,
     },
 
+    #[error(transparent)]
+    JsonLdError {
+        #[from]
+        err: JsonLdParseError,
+    },
+
+    #[error("JSON-LD context {iri} is not available offline")]
+    JsonLdContextNotFound { iri: String },
+
+    #[error("N3 statement can't be represented in RDF: {quad}")]
+    N3NotRDF { quad: String },
+
+    #[error("Graph {graph_name} not found in the dataset")]
+    GraphNotFound { graph_name: String },
+
     #[error(transparent)]
     IriParseError {
         #[from]
@@ -41,6 +58,12 @@ pub enum SRDFGraphError {
         err: IriSError,
     },
 
+    #[error(transparent)]
+    SparqlEvalError {
+        #[from]
+        err: SparqlEvalError,
+    },
+
     #[error(transparent)]
     PrefixMapError {
         #[from]
@@ -114,6 +114,9 @@ where
             Object::Iri(iri) => Ok(iri),
             Object::BlankNode(bnode) => Err(RDFParseError::ExpectedIRIFoundBNode { bnode }),
             Object::Literal(lit) => Err(RDFParseError::ExpectedIRIFoundLiteral { lit }),
+            Object::Triple { .. } => Err(RDFParseError::ExpectedIRI {
+                term: obj.to_string(),
+            }),
         }
     }
 
@@ -1,8 +1,10 @@
 use crate::{lang::Lang, literal::Literal, Object, SRDFSparqlError};
-use crate::{AsyncSRDF, QuerySRDF, QuerySolutionIter, SRDFBasic, SRDF};
+use crate::{AsyncSRDF, QuerySRDF, QuerySolutionIter, SRDFBasic, SRDFGraph, Triple, SRDF};
 use async_trait::async_trait;
 use colored::*;
 use iri_s::IriS;
+#[cfg(feature = "rdf-star")]
+use oxrdf::Triple as OxTriple;
 use oxrdf::{
     BlankNode as OxBlankNode, Literal as OxLiteral, NamedNode as OxNamedNode, Subject as OxSubject,
     Term as OxTerm,
@@ -15,7 +17,7 @@ use reqwest::{
     Url,
 };
 use sparesults::{
-    FromReadQueryResultsReader, QueryResultsFormat, QueryResultsParser, QuerySolution,
+    QueryResultsFormat, QueryResultsParser, QuerySolution, ReaderQueryResultsParserOutput,
 };
 use std::rc::Rc;
 use std::{
@@ -63,6 +65,16 @@ impl SRDFSparql {
         let str: String = format!("{}", lit);
         format!("{}", str.red())
     }
+
+    #[cfg(feature = "rdf-star")]
+    pub fn qualify_triple(&self, triple: &OxTriple) -> String {
+        format!(
+            "<< {} {} {} >>",
+            self.qualify_subject(&triple.subject),
+            self.qualify_iri(&triple.predicate),
+            self.qualify_term(&triple.object)
+        )
+    }
 }
 
 impl FromStr for SRDFSparql {
@@ -115,6 +127,9 @@ impl SRDFBasic for SRDFSparql {
     fn subject_is_bnode(subject: &OxSubject) -> bool {
         matches!(subject, OxSubject::BlankNode(_))
     }
+    fn subject_is_triple(subject: &OxSubject) -> bool {
+        SRDFGraph::subject_is_triple(subject)
+    }
 
     fn term_as_iri(object: &OxTerm) -> Option<OxNamedNode> {
         match object {
@@ -147,6 +162,22 @@ impl SRDFBasic for SRDFSparql {
         matches!(object, OxTerm::Literal(_))
     }
 
+    fn term_is_triple(object: &OxTerm) -> bool {
+        SRDFGraph::term_is_triple(object)
+    }
+
+    fn term_as_triple(object: &OxTerm) -> Option<Triple<Self>> {
+        match object {
+            #[cfg(feature = "rdf-star")]
+            OxTerm::Triple(t) => Some(Triple::new(
+                t.subject.clone(),
+                t.predicate.clone(),
+                t.object.clone(),
+            )),
+            _ => None,
+        }
+    }
+
     fn term_as_subject(object: &Self::Term) -> Option<OxSubject> {
         term_as_subject(object)
     }
@@ -200,7 +231,11 @@ impl SRDFBasic for SRDFSparql {
             Self::Term::NamedNode(iri) => Object::Iri(Self::iri2iri_s(iri)),
 
             #[cfg(feature = "rdf-star")]
-            OxTerm::Triple(_) => unimplemented!(),
+            OxTerm::Triple(t) => Object::triple(
+                Self::subject_as_object(&t.subject),
+                Self::iri2iri_s(&t.predicate),
+                Self::term_as_object(&t.object),
+            ),
         }
     }
 
@@ -226,7 +261,7 @@ impl SRDFBasic for SRDFSparql {
             OxSubject::BlankNode(bn) => self.show_blanknode(bn),
             OxSubject::NamedNode(n) => self.qualify_iri(n),
             #[cfg(feature = "rdf-star")]
-            OxSubject::Triple(_) => unimplemented!(),
+            OxSubject::Triple(t) => self.qualify_triple(t),
         }
     }
 
@@ -236,7 +271,7 @@ impl SRDFBasic for SRDFSparql {
             OxTerm::Literal(lit) => self.show_literal(lit),
             OxTerm::NamedNode(n) => self.qualify_iri(n),
             #[cfg(feature = "rdf-star")]
-            OxTerm::Triple(_) => unimplemented!(),
+            OxTerm::Triple(t) => self.qualify_triple(t),
         }
     }
 
@@ -256,8 +291,8 @@ impl SRDFBasic for SRDFSparql {
         OxTerm::BlankNode(bnode)
     }
 
-    fn object_as_term(_obj: &Object) -> Self::Term {
-        todo!()
+    fn object_as_term(obj: &Object) -> Self::Term {
+        SRDFGraph::object_as_term(obj)
     }
 
     fn bnode_as_subject(_bnode: Self::BNode) -> Self::Subject {
@@ -279,7 +314,7 @@ impl AsyncSRDF for SRDFSparql {
         let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_iri_solution(solution, "pred")?;
+            let n = get_iri_solution(&solution, "pred")?;
             results.insert(n.clone());
         }
         Ok(results)
@@ -312,7 +347,7 @@ impl SRDF for SRDFSparql {
         let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_iri_solution(solution, "pred")?;
+            let n = get_iri_solution(&solution, "pred")?;
             results.insert(n.clone());
         }
         Ok(results)
@@ -327,7 +362,7 @@ impl SRDF for SRDFSparql {
         let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_object_solution(solution, "obj")?;
+            let n = get_object_solution(&solution, "obj")?;
             results.insert(n.clone());
         }
         Ok(results)
@@ -342,7 +377,7 @@ impl SRDF for SRDFSparql {
         let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
         let mut results = HashSet::new();
         for solution in solutions {
-            let n = get_subject_solution(solution, "subj")?;
+            let n = get_subject_solution(&solution, "subj")?;
             results.insert(n.clone());
         }
         Ok(results)
@@ -383,9 +418,28 @@ impl SRDF for SRDFSparql {
 
     fn triples_with_predicate(
         &self,
-        _pred: &Self::IRI,
+        pred: &Self::IRI,
     ) -> std::prelude::v1::Result<Vec<crate::Triple<Self>>, Self::Err> {
-        todo!()
+        let query = format!(r#"select ?subj ?obj where {{ ?subj {} ?obj . }}"#, pred);
+        let solutions = make_sparql_query(query.as_str(), &self.client, &self.endpoint_iri)?;
+        let mut results = Vec::new();
+        for solution in solutions {
+            let subj = get_subject_solution(&solution, "subj")?;
+            let obj = get_object_solution(&solution, "obj")?;
+            results.push(crate::Triple::new(subj, pred.clone(), obj))
+        }
+        Ok(results)
+    }
+
+    fn subjects(&self) -> Result<HashSet<Self::Subject>> {
+        let query = r#"select distinct ?subj where { ?subj ?pred ?obj . }"#;
+        let solutions = make_sparql_query(query, &self.client, &self.endpoint_iri)?;
+        let mut results = HashSet::new();
+        for solution in solutions {
+            let subj = get_subject_solution(&solution, "subj")?;
+            results.insert(subj);
+        }
+        Ok(results)
     }
 }
 
@@ -442,8 +496,8 @@ fn make_sparql_query(
     let body = client.get(url).send()?.text()?;
     let mut results = Vec::new();
     let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
-    if let FromReadQueryResultsReader::Solutions(solutions) =
-        json_parser.parse_read(body.as_bytes())?
+    if let ReaderQueryResultsParserOutput::Solutions(solutions) =
+        json_parser.for_reader(body.as_bytes())?
     {
         for solution in solutions {
             let sol = solution?;
@@ -484,8 +538,8 @@ fn outgoing_neighs(
     let body = client.get(url).send()?.text()?;
     let mut results: HashMap<OxNamedNode, HashSet<OxTerm>> = HashMap::new();
     let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
-    if let FromReadQueryResultsReader::Solutions(solutions) =
-        json_parser.parse_read(body.as_bytes())?
+    if let ReaderQueryResultsParserOutput::Solutions(solutions) =
+        json_parser.for_reader(body.as_bytes())?
     {
         for solution in solutions {
             let sol = solution?;
@@ -566,8 +620,8 @@ fn incoming_neighs(
     let body = client.get(url).send()?.text()?;
     let mut results: HashMap<OxNamedNode, HashSet<OxSubject>> = HashMap::new();
     let json_parser = QueryResultsParser::from_format(QueryResultsFormat::Json);
-    if let FromReadQueryResultsReader::Solutions(solutions) =
-        json_parser.parse_read(body.as_bytes())?
+    if let ReaderQueryResultsParserOutput::Solutions(solutions) =
+        json_parser.for_reader(body.as_bytes())?
     {
         for solution in solutions {
             let sol = solution?;
@@ -614,7 +668,7 @@ fn incoming_neighs(
     }
 }
 
-fn get_iri_solution(solution: QuerySolution, name: &str) -> Result<OxNamedNode> {
+fn get_iri_solution(solution: &QuerySolution, name: &str) -> Result<OxNamedNode> {
     match solution.get(name) {
         Some(v) => match v {
             OxTerm::NamedNode(n) => Ok(n.clone()),
@@ -627,7 +681,7 @@ fn get_iri_solution(solution: QuerySolution, name: &str) -> Result<OxNamedNode>
     }
 }
 
-fn get_object_solution(solution: QuerySolution, name: &str) -> Result<OxTerm> {
+fn get_object_solution(solution: &QuerySolution, name: &str) -> Result<OxTerm> {
     match solution.get(name) {
         Some(v) => Ok(v.clone()),
         None => Err(SRDFSparqlError::NotFoundInSolution {
@@ -637,7 +691,7 @@ fn get_object_solution(solution: QuerySolution, name: &str) -> Result<OxTerm> {
     }
 }
 
-fn get_subject_solution(solution: QuerySolution, name: &str) -> Result<OxSubject> {
+fn get_subject_solution(solution: &QuerySolution, name: &str) -> Result<OxSubject> {
     match solution.get(name) {
         Some(v) => match term_as_subject(v) {
             Some(s) => Ok(s),
@@ -654,6 +708,8 @@ fn term_as_subject(object: &OxTerm) -> Option<OxSubject> {
     match object {
         OxTerm::NamedNode(n) => Some(OxSubject::NamedNode(n.clone())),
         OxTerm::BlankNode(b) => Some(OxSubject::BlankNode(b.clone())),
+        #[cfg(feature = "rdf-star")]
+        OxTerm::Triple(t) => Some(OxSubject::Triple(t.clone())),
         _ => None,
     }
 }
@@ -663,8 +719,7 @@ fn subject_as_term(subject: &OxSubject) -> OxTerm {
         OxSubject::NamedNode(n) => OxTerm::NamedNode(n.clone()),
         OxSubject::BlankNode(b) => OxTerm::BlankNode(b.clone()),
         #[cfg(feature = "rdf-star")]
-        #[cfg(feature = "rdf-star")]
-        OxSubject::Triple(_) => unimplemented!(),
+        OxSubject::Triple(t) => OxTerm::Triple(t.clone()),
     }
 }
 
@@ -0,0 +1,9 @@
+//! Implementation of the SRDF traits as a persistent store of RDF data on disk
+//!
+//! The data is kept in memory mapped indexes, which can be used with RDF graphs that don't fit in memory
+pub mod srdfstore;
+pub mod srdfstore_error;
+mod store_files;
+
+pub use srdfstore::*;
+pub use srdfstore_error::*;
@@ -0,0 +1,929 @@
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+use colored::*;
+use iri_s::IriS;
+use oxiri::Iri;
+use oxrdf::{
+    BlankNode as OxBlankNode, Graph, Literal as OxLiteral, NamedNode as OxNamedNode,
+    Subject as OxSubject, Term as OxTerm, Triple as OxTriple,
+};
+use oxrdfio::{RdfParser, RdfSerializer};
+use prefixmap::PrefixMap;
+
+use crate::srdf_graph::srdfgraph::cnv_rdf_format;
+use crate::srdf_store::store_files::{Id, Loader, StoreFiles};
+use crate::{
+    Object, QuerySRDF, QuerySolutionIter, RDFFormat, RDFNode, SRDFBasic, SRDFBuilder, SRDFGraph,
+    SRDFStoreError, SparqlEvaluator, Triple, RDF_TYPE_STR, SRDF,
+};
+
+type Result<A> = std::result::Result<A, SRDFStoreError>;
+
+const PREFIXES_FILE: &str = "prefixes";
+const SOURCES_FILE: &str = "sources";
+
+/// Triples sorted in memory before they are merged with the indexes in a bulk load
+const SORT_CHUNK: usize = 1 << 22;
+
+/// Implements SRDF interface as a persistent store of RDF data in a folder
+///
+/// The store keeps a dictionary of terms and three sorted indexes of triples in memory mapped
+/// files, so the RDF data is not loaded in memory and the indexes are reused by later runs.
+/// The data is loaded in bulk with [`SRDFStore::load_reader`], which sorts the triples in
+/// chunks on disk, and the changes made with [`SRDFBuilder`] are kept in memory until
+/// [`SRDFStore::commit`] is called.
+/// The stores created with [`SRDFBuilder::empty`] are not backed by a folder and only keep
+/// those changes.
+#[derive(Debug)]
+pub struct SRDFStore {
+    files: Option<StoreFiles>,
+    pm: PrefixMap,
+    base: Option<IriS>,
+    added: Graph,
+    removed: HashSet<OxTriple>,
+    sort_chunk: usize,
+    sources: HashSet<String>,
+}
+
+impl SRDFStore {
+    /// Opens the store in the folder `dir`, creating an empty one if it doesn't exist
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<SRDFStore> {
+        let dir = dir.as_ref();
+        let opening_error = |error| SRDFStoreError::OpeningStore {
+            path: dir.display().to_string(),
+            error,
+        };
+        fs::create_dir_all(dir).map_err(opening_error)?;
+        let files = StoreFiles::open(dir).map_err(opening_error)?;
+        let pm = read_prefixes(&dir.join(PREFIXES_FILE))?;
+        let sources = read_sources(&dir.join(SOURCES_FILE))?;
+        Ok(SRDFStore {
+            files: Some(files),
+            pm,
+            base: None,
+            added: Graph::new(),
+            removed: HashSet::new(),
+            sort_chunk: SORT_CHUNK,
+            sources,
+        })
+    }
+
+    /// Number of triples that are sorted in memory at once when the data is loaded,
+    /// which is also the number of new terms kept in memory before they are written in a run file
+    pub fn with_sort_chunk(mut self, triples: usize) -> Self {
+        self.sort_chunk = triples;
+        self
+    }
+
+    /// Loads the triples of a file, see [`SRDFStore::load_reader`]
+    pub fn load_path<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        format: &RDFFormat,
+        base: Option<Iri<String>>,
+    ) -> Result<()> {
+        let file = File::open(path.as_ref()).map_err(|error| SRDFStoreError::OpeningStore {
+            path: path.as_ref().display().to_string(),
+            error,
+        })?;
+        self.load_reader(BufReader::new(file), format, base)
+    }
+
+    /// Loads RDF data in bulk and writes it to the store together with the pending changes
+    ///
+    /// The triples of all the graphs of a dataset are loaded in the same graph.
+    /// The blank nodes of each load are renamed, so the labels of different files don't merge their nodes.
+    /// The triples and the new terms are kept in memory in chunks that are sorted in files,
+    /// and if the data can't be parsed the store is left as it was.
+    pub fn load_reader<R: Read>(
+        &mut self,
+        read: R,
+        format: &RDFFormat,
+        base: Option<Iri<String>>,
+    ) -> Result<()> {
+        let mut parser = RdfParser::from_format(cnv_rdf_format(*format)).rename_blank_nodes();
+        if let Some(base) = base {
+            parser = parser.with_base_iri(base.as_str())?;
+        }
+        let mut loader = Loader::new(self.files()?, self.sort_chunk)?;
+        let mut reader = parser.for_reader(read);
+        for quad in reader.by_ref() {
+            let quad = quad?;
+            let triple = [
+                loader.encode(&quad.subject.to_string())?,
+                loader.encode(&quad.predicate.to_string())?,
+                loader.encode(&quad.object.to_string())?,
+            ];
+            loader.insert(triple)?;
+        }
+        let mut pm = PrefixMap::new();
+        for (alias, iri) in reader.prefixes() {
+            pm.insert(alias, &IriS::from_str(iri)?)?;
+        }
+        write_pending(&mut loader, &self.added, &self.removed)?;
+        loader.finish()?;
+        self.pm.merge(pm)?;
+        self.reopen()
+    }
+
+    /// Loads the triples of a reader, see [`SRDFStore::load_reader`], and records that the
+    /// data of `source` is in the store
+    ///
+    /// Loading the same data again would add new copies of its blank nodes,
+    /// so the sources that have been loaded can be skipped with [`SRDFStore::is_loaded`]
+    pub fn load_source<R: Read>(
+        &mut self,
+        source: &str,
+        read: R,
+        format: &RDFFormat,
+        base: Option<Iri<String>>,
+    ) -> Result<()> {
+        self.load_reader(read, format, base)?;
+        let path = self.files()?.dir().join(SOURCES_FILE);
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        writeln!(file, "{source}")?;
+        self.sources.insert(source.to_string());
+        Ok(())
+    }
+
+    /// Whether the data of `source` has been loaded with [`SRDFStore::load_source`]
+    pub fn is_loaded(&self, source: &str) -> bool {
+        self.sources.contains(source)
+    }
+
+    /// Writes the triples added and removed with [`SRDFBuilder`] and the prefix map to the store
+    pub fn commit(&mut self) -> Result<()> {
+        let mut loader = Loader::new(self.files()?, self.sort_chunk)?;
+        write_pending(&mut loader, &self.added, &self.removed)?;
+        loader.finish()?;
+        self.reopen()
+    }
+
+    fn reopen(&mut self) -> Result<()> {
+        let dir = self.files()?.dir().to_path_buf();
+        self.files = Some(StoreFiles::open(&dir)?);
+        self.added.clear();
+        self.removed.clear();
+        write_prefixes(&dir.join(PREFIXES_FILE), &self.pm)
+    }
+
+    fn files(&self) -> Result<&StoreFiles> {
+        self.files.as_ref().ok_or(SRDFStoreError::NoFolder)
+    }
+
+    /// Number of triples, including the changes that haven't been committed
+    pub fn len(&self) -> usize {
+        self.files.as_ref().map_or(0, StoreFiles::len) + self.added.len() - self.removed.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn prefixmap(&self) -> PrefixMap {
+        self.pm.clone()
+    }
+
+    fn show_blanknode(&self, bn: &OxBlankNode) -> String {
+        let str: String = format!("{}", bn);
+        format!("{}", str.green())
+    }
+
+    pub fn show_literal(&self, lit: &OxLiteral) -> String {
+        let str: String = format!("{}", lit);
+        format!("{}", str.red())
+    }
+
+    #[cfg(feature = "rdf-star")]
+    pub fn qualify_triple(&self, triple: &OxTriple) -> String {
+        format!(
+            "<< {} {} {} >>",
+            self.qualify_subject(&triple.subject),
+            self.qualify_iri(&triple.predicate),
+            self.qualify_term(&triple.object)
+        )
+    }
+
+    /// Triples that match a pattern, where `None` matches any term
+    fn triples_matching(
+        &self,
+        subject: Option<&OxSubject>,
+        predicate: Option<&OxNamedNode>,
+        object: Option<&OxTerm>,
+    ) -> Result<Vec<OxTriple>> {
+        let mut result = Vec::new();
+        let pattern = [
+            subject.map(|s| self.id(&s.to_string())),
+            predicate.map(|p| self.id(&p.to_string())),
+            object.map(|o| self.id(&o.to_string())),
+        ];
+        // A term that is not in the dictionary doesn't match any stored triple
+        if !pattern.contains(&Some(None)) {
+            for ids in self.stored_triples(pattern.map(Option::flatten)) {
+                let triple = self.decode_triple(ids)?;
+                if !self.removed.contains(&triple) {
+                    result.push(triple)
+                }
+            }
+        }
+        let added: Box<dyn Iterator<Item = _>> = match (subject, object) {
+            (Some(s), _) => Box::new(self.added.triples_for_subject(s)),
+            (None, Some(o)) => Box::new(self.added.triples_for_object(o)),
+            (None, None) => Box::new(self.added.iter()),
+        };
+        result.extend(
+            added
+                .filter(|t| predicate.is_none_or(|p| t.predicate == p.as_ref()))
+                .filter(|t| object.is_none_or(|o| t.object == o.as_ref()))
+                .map(|t| t.into_owned()),
+        );
+        Ok(result)
+    }
+
+    /// Checks if a triple is in the indexes, without the changes that haven't been committed
+    fn is_stored(&self, triple: &OxTriple) -> bool {
+        let ids = [
+            self.id(&triple.subject.to_string()),
+            self.id(&triple.predicate.to_string()),
+            self.id(&triple.object.to_string()),
+        ];
+        !ids.contains(&None) && self.stored_triples(ids).next().is_some()
+    }
+
+    /// Identifier of a term in the dictionary, which is empty if the store is not backed by a folder
+    fn id(&self, term: &str) -> Option<Id> {
+        self.files.as_ref().and_then(|files| files.id(term))
+    }
+
+    /// Encoded triples of the indexes that match a pattern of identifiers
+    fn stored_triples(&self, pattern: [Option<Id>; 3]) -> impl Iterator<Item = [Id; 3]> + '_ {
+        self.files
+            .iter()
+            .flat_map(move |files| files.triples(pattern))
+    }
+
+    fn decode_term(&self, id: Id) -> Result<OxTerm> {
+        let files = self.files()?;
+        let term = files.term(id).ok_or_else(|| SRDFStoreError::Corrupted {
+            file: files.dir().display().to_string(),
+            msg: format!("term {id} not found in the dictionary"),
+        })?;
+        OxTerm::from_str(term).map_err(|err| SRDFStoreError::TermParse {
+            term: term.to_string(),
+            err,
+        })
+    }
+
+    fn decode_triple(&self, [s, p, o]: [Id; 3]) -> Result<OxTriple> {
+        let subject = self.decode_term(s)?;
+        let subject =
+            Self::term_as_subject(&subject).ok_or_else(|| SRDFStoreError::UnexpectedTerm {
+                term: subject.to_string(),
+                expected: "a subject".to_string(),
+            })?;
+        let predicate = match self.decode_term(p)? {
+            OxTerm::NamedNode(iri) => iri,
+            other => {
+                return Err(SRDFStoreError::UnexpectedTerm {
+                    term: other.to_string(),
+                    expected: "an IRI".to_string(),
+                })
+            }
+        };
+        Ok(OxTriple::new(subject, predicate, self.decode_term(o)?))
+    }
+}
+
+impl SRDFBasic for SRDFStore {
+    type IRI = OxNamedNode;
+    type BNode = OxBlankNode;
+    type Literal = OxLiteral;
+    type Subject = OxSubject;
+    type Term = OxTerm;
+    type Err = SRDFStoreError;
+
+    fn subject_as_iri(subject: &OxSubject) -> Option<OxNamedNode> {
+        SRDFGraph::subject_as_iri(subject)
+    }
+    fn subject_as_bnode(subject: &OxSubject) -> Option<OxBlankNode> {
+        SRDFGraph::subject_as_bnode(subject)
+    }
+    fn subject_is_iri(subject: &OxSubject) -> bool {
+        SRDFGraph::subject_is_iri(subject)
+    }
+    fn subject_is_bnode(subject: &OxSubject) -> bool {
+        SRDFGraph::subject_is_bnode(subject)
+    }
+    fn subject_is_triple(subject: &OxSubject) -> bool {
+        SRDFGraph::subject_is_triple(subject)
+    }
+
+    fn term_as_iri(object: &OxTerm) -> Option<OxNamedNode> {
+        SRDFGraph::term_as_iri(object)
+    }
+    fn term_as_bnode(object: &OxTerm) -> Option<OxBlankNode> {
+        SRDFGraph::term_as_bnode(object)
+    }
+    fn term_as_literal(object: &OxTerm) -> Option<OxLiteral> {
+        SRDFGraph::term_as_literal(object)
+    }
+    fn term_is_iri(object: &OxTerm) -> bool {
+        SRDFGraph::term_is_iri(object)
+    }
+    fn term_is_bnode(object: &OxTerm) -> bool {
+        SRDFGraph::term_is_bnode(object)
+    }
+    fn term_is_literal(object: &OxTerm) -> bool {
+        SRDFGraph::term_is_literal(object)
+    }
+    fn term_is_triple(object: &OxTerm) -> bool {
+        SRDFGraph::term_is_triple(object)
+    }
+
+    fn term_as_triple(object: &OxTerm) -> Option<Triple<Self>> {
+        SRDFGraph::term_as_triple(object).map(|t| Triple::new(t.subj(), t.pred(), t.obj()))
+    }
+
+    fn term_as_subject(object: &OxTerm) -> Option<OxSubject> {
+        SRDFGraph::term_as_subject(object)
+    }
+
+    fn subject_as_term(subject: &OxSubject) -> OxTerm {
+        SRDFGraph::subject_as_term(subject)
+    }
+
+    fn lexical_form(literal: &OxLiteral) -> &str {
+        literal.value()
+    }
+    fn lang(literal: &OxLiteral) -> Option<String> {
+        SRDFGraph::lang(literal)
+    }
+    fn datatype(literal: &OxLiteral) -> OxNamedNode {
+        SRDFGraph::datatype(literal)
+    }
+
+    fn iri_s2iri(iri_s: &IriS) -> OxNamedNode {
+        SRDFGraph::iri_s2iri(iri_s)
+    }
+
+    fn term_s2term(term: &OxTerm) -> OxTerm {
+        term.clone()
+    }
+
+    fn bnode_id2bnode(id: &str) -> OxBlankNode {
+        SRDFGraph::bnode_id2bnode(id)
+    }
+
+    fn iri_as_term(iri: OxNamedNode) -> OxTerm {
+        SRDFGraph::iri_as_term(iri)
+    }
+
+    fn iri_as_subject(iri: OxNamedNode) -> OxSubject {
+        SRDFGraph::iri_as_subject(iri)
+    }
+
+    fn bnode_as_term(bnode: OxBlankNode) -> OxTerm {
+        SRDFGraph::bnode_as_term(bnode)
+    }
+
+    fn bnode_as_subject(bnode: OxBlankNode) -> OxSubject {
+        SRDFGraph::bnode_as_subject(bnode)
+    }
+
+    fn iri2iri_s(iri: &OxNamedNode) -> IriS {
+        SRDFGraph::iri2iri_s(iri)
+    }
+
+    fn term_as_object(term: &OxTerm) -> Object {
+        SRDFGraph::term_as_object(term)
+    }
+
+    fn object_as_term(obj: &Object) -> OxTerm {
+        SRDFGraph::object_as_term(obj)
+    }
+
+    fn qualify_iri(&self, node: &OxNamedNode) -> String {
+        let iri = IriS::from_str(node.as_str()).unwrap();
+        self.pm.qualify(&iri)
+    }
+
+    fn qualify_subject(&self, subj: &OxSubject) -> String {
+        match subj {
+            OxSubject::BlankNode(bn) => self.show_blanknode(bn),
+            OxSubject::NamedNode(n) => self.qualify_iri(n),
+            #[cfg(feature = "rdf-star")]
+            OxSubject::Triple(t) => self.qualify_triple(t),
+        }
+    }
+
+    fn qualify_term(&self, term: &OxTerm) -> String {
+        match term {
+            OxTerm::BlankNode(bn) => self.show_blanknode(bn),
+            OxTerm::Literal(lit) => self.show_literal(lit),
+            OxTerm::NamedNode(n) => self.qualify_iri(n),
+            #[cfg(feature = "rdf-star")]
+            OxTerm::Triple(t) => self.qualify_triple(t),
+        }
+    }
+
+    fn prefixmap(&self) -> Option<PrefixMap> {
+        Some(self.pm.clone())
+    }
+
+    fn resolve_prefix_local(
+        &self,
+        prefix: &str,
+        local: &str,
+    ) -> std::result::Result<IriS, prefixmap::PrefixMapError> {
+        self.pm.resolve_prefix_local(prefix, local)
+    }
+}
+
+impl SRDF for SRDFStore {
+    fn predicates_for_subject(&self, subject: &OxSubject) -> Result<HashSet<OxNamedNode>> {
+        let triples = self.triples_matching(Some(subject), None, None)?;
+        Ok(triples.into_iter().map(|t| t.predicate).collect())
+    }
+
+    fn objects_for_subject_predicate(
+        &self,
+        subject: &OxSubject,
+        pred: &OxNamedNode,
+    ) -> Result<HashSet<OxTerm>> {
+        let triples = self.triples_matching(Some(subject), Some(pred), None)?;
+        Ok(triples.into_iter().map(|t| t.object).collect())
+    }
+
+    fn subjects_with_predicate_object(
+        &self,
+        pred: &OxNamedNode,
+        object: &OxTerm,
+    ) -> Result<HashSet<OxSubject>> {
+        let triples = self.triples_matching(None, Some(pred), Some(object))?;
+        Ok(triples.into_iter().map(|t| t.subject).collect())
+    }
+
+    fn triples_with_predicate(&self, pred: &OxNamedNode) -> Result<Vec<Triple<Self>>> {
+        let triples = self.triples_matching(None, Some(pred), None)?;
+        Ok(triples
+            .into_iter()
+            .map(|t| Triple::new(t.subject, t.predicate, t.object))
+            .collect())
+    }
+
+    fn subjects(&self) -> Result<HashSet<OxSubject>> {
+        let mut subjects = HashSet::new();
+        let mut last = None;
+        for [s, p, o] in self.stored_triples([None, None, None]) {
+            if last != Some(s) {
+                let triple = self.decode_triple([s, p, o])?;
+                if self.removed.contains(&triple) {
+                    // Other triples of the subject may not have been removed
+                    continue;
+                }
+                subjects.insert(triple.subject);
+                last = Some(s);
+            }
+        }
+        subjects.extend(self.added.iter().map(|t| t.subject.into_owned()));
+        Ok(subjects)
+    }
+
+    fn outgoing_arcs(&self, subject: &OxSubject) -> Result<HashMap<OxNamedNode, HashSet<OxTerm>>> {
+        let mut results: HashMap<OxNamedNode, HashSet<OxTerm>> = HashMap::new();
+        for triple in self.triples_matching(Some(subject), None, None)? {
+            results
+                .entry(triple.predicate)
+                .or_default()
+                .insert(triple.object);
+        }
+        Ok(results)
+    }
+
+    fn incoming_arcs(&self, object: &OxTerm) -> Result<HashMap<OxNamedNode, HashSet<OxSubject>>> {
+        let mut results: HashMap<OxNamedNode, HashSet<OxSubject>> = HashMap::new();
+        for triple in self.triples_matching(None, None, Some(object))? {
+            results
+                .entry(triple.predicate)
+                .or_default()
+                .insert(triple.subject);
+        }
+        Ok(results)
+    }
+
+    fn outgoing_arcs_from_list(
+        &self,
+        subject: &OxSubject,
+        preds: Vec<OxNamedNode>,
+    ) -> Result<(HashMap<OxNamedNode, HashSet<OxTerm>>, Vec<OxNamedNode>)> {
+        let mut results: HashMap<OxNamedNode, HashSet<OxTerm>> = HashMap::new();
+        let mut remainder = Vec::new();
+        for triple in self.triples_matching(Some(subject), None, None)? {
+            if preds.contains(&triple.predicate) {
+                match results.entry(triple.predicate) {
+                    Entry::Occupied(mut vs) => {
+                        vs.get_mut().insert(triple.object);
+                    }
+                    Entry::Vacant(vacant) => {
+                        vacant.insert(HashSet::from([triple.object]));
+                    }
+                }
+            } else {
+                remainder.push(triple.predicate)
+            }
+        }
+        Ok((results, remainder))
+    }
+}
+
+impl SRDFBuilder for SRDFStore {
+    /// Returns an empty store that is not backed by a folder
+    ///
+    /// The triples added to it are kept in memory and it can't be loaded or committed,
+    /// use [`SRDFStore::open`] to create a persistent store.
+    fn empty() -> Self {
+        SRDFStore {
+            files: None,
+            pm: PrefixMap::new(),
+            base: None,
+            added: Graph::new(),
+            removed: HashSet::new(),
+            sort_chunk: SORT_CHUNK,
+            sources: HashSet::new(),
+        }
+    }
+
+    fn add_base(&mut self, base: &Option<IriS>) -> Result<()> {
+        self.base.clone_from(base);
+        Ok(())
+    }
+
+    fn add_prefix(&mut self, alias: &str, iri: &IriS) -> Result<()> {
+        self.pm.insert(alias, iri)?;
+        Ok(())
+    }
+
+    fn add_prefix_map(&mut self, prefix_map: PrefixMap) -> Result<()> {
+        self.pm = prefix_map.clone();
+        Ok(())
+    }
+
+    fn add_triple(&mut self, subj: &OxSubject, pred: &OxNamedNode, obj: &OxTerm) -> Result<()> {
+        let triple = OxTriple::new(subj.clone(), pred.clone(), obj.clone());
+        if !self.removed.remove(&triple) && !self.is_stored(&triple) {
+            self.added.insert(&triple);
+        }
+        Ok(())
+    }
+
+    fn remove_triple(&mut self, subj: &OxSubject, pred: &OxNamedNode, obj: &OxTerm) -> Result<()> {
+        let triple = OxTriple::new(subj.clone(), pred.clone(), obj.clone());
+        if !self.added.remove(&triple) && self.is_stored(&triple) {
+            self.removed.insert(triple);
+        }
+        Ok(())
+    }
+
+    fn add_type(&mut self, node: &RDFNode, r#type: OxTerm) -> Result<()> {
+        match Self::object_as_subject(node) {
+            Some(subj) => {
+                self.add_triple(&subj, &OxNamedNode::new_unchecked(RDF_TYPE_STR), &r#type)
+            }
+            None => {
+                panic!("Error adding type to {node} because it can't be converted to a subject")
+            }
+        }
+    }
+
+    fn serialize<W: Write>(&self, format: RDFFormat, write: W) -> Result<()> {
+        let mut serializer = RdfSerializer::from_format(cnv_rdf_format(format));
+        for (prefix, iri) in &self.pm.map {
+            serializer = serializer.with_prefix(prefix, iri.as_str())?;
+        }
+        let mut writer = serializer.for_writer(write);
+        for ids in self.stored_triples([None, None, None]) {
+            let triple = self.decode_triple(ids)?;
+            if !self.removed.contains(&triple) {
+                writer.serialize_triple(&triple)?;
+            }
+        }
+        for triple in self.added.iter() {
+            writer.serialize_triple(triple)?;
+        }
+        writer.finish()?;
+        Ok(())
+    }
+}
+
+/// Evaluates SPARQL queries over the triples of the store with [`SparqlEvaluator`]
+impl QuerySRDF for SRDFStore {
+    fn query_select(&self, query: &str) -> Result<QuerySolutionIter<SRDFStore>> {
+        Ok(SparqlEvaluator::new(self).select(query)?)
+    }
+
+    fn query_ask(&self, query: &str) -> Result<bool> {
+        Ok(SparqlEvaluator::new(self).ask(query)?)
+    }
+}
+
+/// Adds the changes that haven't been committed to a loader
+fn write_pending(loader: &mut Loader, added: &Graph, removed: &HashSet<OxTriple>) -> Result<()> {
+    for triple in added.iter() {
+        let ids = [
+            loader.encode(&triple.subject.to_string())?,
+            loader.encode(&triple.predicate.to_string())?,
+            loader.encode(&triple.object.to_string())?,
+        ];
+        loader.insert(ids)?;
+    }
+    for triple in removed {
+        if let (Some(s), Some(p), Some(o)) = (
+            loader.lookup(&triple.subject.to_string()),
+            loader.lookup(&triple.predicate.to_string()),
+            loader.lookup(&triple.object.to_string()),
+        ) {
+            loader.remove([s, p, o])
+        }
+    }
+    Ok(())
+}
+
+/// Reads the prefix map of a store, which is kept with one `alias IRI` declaration per line
+fn read_prefixes(path: &Path) -> Result<PrefixMap> {
+    let mut pm = PrefixMap::new();
+    if path.exists() {
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if let Some((alias, iri)) = line.split_once(' ') {
+                pm.insert(alias, &IriS::from_str(iri)?)?;
+            }
+        }
+    }
+    Ok(pm)
+}
+
+fn read_sources(path: &Path) -> Result<HashSet<String>> {
+    let mut sources = HashSet::new();
+    if path.exists() {
+        for line in BufReader::new(File::open(path)?).lines() {
+            sources.insert(line?);
+        }
+    }
+    Ok(sources)
+}
+
+fn write_prefixes(path: &Path, pm: &PrefixMap) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for (alias, iri) in pm.iter() {
+        writeln!(writer, "{alias} {}", iri.as_str())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::QuerySolution;
+    use tempfile::TempDir;
+
+    fn iri(str: &str) -> OxNamedNode {
+        OxNamedNode::new_unchecked(format!("http://example.org/{str}"))
+    }
+
+    fn subject(str: &str) -> OxSubject {
+        OxSubject::NamedNode(iri(str))
+    }
+
+    #[test]
+    fn load_and_reopen_store() {
+        let dir = TempDir::new().unwrap();
+        let data = r#"<http://example.org/alice> <http://example.org/knows> <http://example.org/bob> .
+<http://example.org/alice> <http://example.org/name> "Alice" .
+<http://example.org/bob> <http://example.org/knows> <http://example.org/carol> .
+<http://example.org/bob> <http://example.org/name> "Bob"@en .
+"#;
+        let mut store = SRDFStore::open(dir.path()).unwrap().with_sort_chunk(2);
+        store
+            .load_reader(data.as_bytes(), &RDFFormat::NTriples, None)
+            .unwrap();
+        assert_eq!(store.len(), 4);
+        drop(store);
+
+        let mut store = SRDFStore::open(dir.path()).unwrap().with_sort_chunk(2);
+        let more = r#"<http://example.org/alice> <http://example.org/knows> <http://example.org/bob> .
+<http://example.org/carol> <http://example.org/knows> <http://example.org/alice> <http://example.org/g> .
+"#;
+        store
+            .load_reader(more.as_bytes(), &RDFFormat::NQuads, None)
+            .unwrap();
+        assert_eq!(store.len(), 5);
+        assert_eq!(
+            store
+                .objects_for_subject_predicate(&subject("alice"), &iri("knows"))
+                .unwrap(),
+            HashSet::from([iri("bob").into()])
+        );
+        assert_eq!(
+            store
+                .subjects_with_predicate_object(&iri("knows"), &iri("alice").into())
+                .unwrap(),
+            HashSet::from([subject("carol")])
+        );
+        assert_eq!(
+            store.predicates_for_subject(&subject("bob")).unwrap(),
+            HashSet::from([iri("knows"), iri("name")])
+        );
+        assert_eq!(store.triples_with_predicate(&iri("name")).unwrap().len(), 2);
+        assert_eq!(
+            store.subjects().unwrap(),
+            HashSet::from([subject("alice"), subject("bob"), subject("carol")])
+        );
+    }
+
+    #[test]
+    fn new_terms_are_found_in_runs() {
+        let dir = TempDir::new().unwrap();
+        let data = r#"<http://example.org/alice> <http://example.org/knows> <http://example.org/bob> .
+<http://example.org/bob> <http://example.org/knows> <http://example.org/carol> .
+<http://example.org/carol> <http://example.org/knows> <http://example.org/alice> .
+<http://example.org/alice> <http://example.org/name> "Alice" .
+"#;
+        let mut store = SRDFStore::open(dir.path()).unwrap().with_sort_chunk(1);
+        store
+            .load_reader(data.as_bytes(), &RDFFormat::NTriples, None)
+            .unwrap();
+        assert_eq!(store.len(), 4);
+        // Each term is written once in the dictionary, whose offsets take 16 bytes per term
+        let offsets = fs::metadata(dir.path().join("terms.idx")).unwrap().len();
+        assert_eq!(offsets, 6 * 16);
+        let temporary: Vec<_> = fs::read_dir(dir.path())
+            .unwrap()
+            .filter(|entry| entry.as_ref().unwrap().path().extension() == Some("tmp".as_ref()))
+            .collect();
+        assert!(temporary.is_empty());
+        drop(store);
+
+        let store = SRDFStore::open(dir.path()).unwrap();
+        assert_eq!(
+            store
+                .subjects_with_predicate_object(&iri("knows"), &iri("alice").into())
+                .unwrap(),
+            HashSet::from([subject("carol")])
+        );
+        assert_eq!(
+            store
+                .objects_for_subject_predicate(&subject("alice"), &iri("name"))
+                .unwrap(),
+            HashSet::from([OxLiteral::from("Alice").into()])
+        );
+    }
+
+    #[test]
+    fn loaded_sources_are_recorded() {
+        let dir = TempDir::new().unwrap();
+        let data = r#"<http://example.org/alice> <http://example.org/address> _:b0 .
+_:b0 <http://example.org/city> "Oviedo" .
+"#;
+        let mut store = SRDFStore::open(dir.path()).unwrap();
+        assert!(!store.is_loaded("alice.nt"));
+        store
+            .load_source("alice.nt", data.as_bytes(), &RDFFormat::NTriples, None)
+            .unwrap();
+        assert!(store.is_loaded("alice.nt"));
+        drop(store);
+
+        let store = SRDFStore::open(dir.path()).unwrap();
+        assert!(store.is_loaded("alice.nt"));
+        assert!(!store.is_loaded("bob.nt"));
+        assert_eq!(store.len(), 2);
+    }
+
+    #[test]
+    fn blank_nodes_of_different_loads() {
+        let dir = TempDir::new().unwrap();
+        let mut store = SRDFStore::open(dir.path()).unwrap();
+        let alice = r#"<http://example.org/alice> <http://example.org/address> _:b0 .
+_:b0 <http://example.org/city> "Oviedo" .
+"#;
+        let bob = r#"<http://example.org/bob> <http://example.org/address> _:b0 .
+_:b0 <http://example.org/city> "Gijon" .
+"#;
+        for data in [alice, bob] {
+            store
+                .load_reader(data.as_bytes(), &RDFFormat::NTriples, None)
+                .unwrap();
+        }
+        assert_eq!(store.len(), 4);
+        let address = |person| {
+            let addresses = store
+                .objects_for_subject_predicate(&subject(person), &iri("address"))
+                .unwrap();
+            assert_eq!(addresses.len(), 1);
+            addresses.into_iter().next().unwrap()
+        };
+        let (alice_address, bob_address) = (address("alice"), address("bob"));
+        assert_ne!(alice_address, bob_address);
+        let cities = store
+            .objects_for_subject_predicate(
+                &SRDFStore::term_as_subject(&alice_address).unwrap(),
+                &iri("city"),
+            )
+            .unwrap();
+        assert_eq!(cities, HashSet::from([OxLiteral::from("Oviedo").into()]));
+    }
+
+    #[test]
+    fn commit_changes() {
+        let dir = TempDir::new().unwrap();
+        let mut store = SRDFStore::open(dir.path()).unwrap();
+        store
+            .add_prefix("", &IriS::new_unchecked("http://example.org/"))
+            .unwrap();
+        store
+            .add_triple(&subject("alice"), &iri("knows"), &iri("bob").into())
+            .unwrap();
+        store
+            .add_triple(&subject("bob"), &iri("knows"), &iri("alice").into())
+            .unwrap();
+        store.commit().unwrap();
+        store
+            .remove_triple(&subject("bob"), &iri("knows"), &iri("alice").into())
+            .unwrap();
+        store
+            .add_triple(&subject("bob"), &iri("knows"), &iri("carol").into())
+            .unwrap();
+        assert_eq!(
+            store.incoming_arcs(&iri("alice").into()).unwrap(),
+            HashMap::new()
+        );
+        store.commit().unwrap();
+        drop(store);
+
+        let store = SRDFStore::open(dir.path()).unwrap();
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.qualify_iri(&iri("carol")), ":carol");
+        assert_eq!(
+            store.outgoing_arcs(&subject("bob")).unwrap(),
+            HashMap::from([(iri("knows"), HashSet::from([iri("carol").into()]))])
+        );
+    }
+
+    #[test]
+    fn empty_store_in_memory() {
+        let mut store = SRDFStore::empty();
+        store
+            .add_triple(&subject("alice"), &iri("knows"), &iri("bob").into())
+            .unwrap();
+        assert_eq!(store.len(), 1);
+        assert_eq!(
+            store.outgoing_arcs(&subject("alice")).unwrap(),
+            HashMap::from([(iri("knows"), HashSet::from([iri("bob").into()]))])
+        );
+        assert!(matches!(store.commit(), Err(SRDFStoreError::NoFolder)));
+    }
+
+    #[test]
+    fn query_store() {
+        let data = r#"PREFIX : <http://example.org/>
+            :alice :knows :bob, :carol .
+            :bob :knows :carol .
+            :carol :name "Carol" .
+        "#;
+        let dir = TempDir::new().unwrap();
+        let mut store = SRDFStore::open(dir.path()).unwrap();
+        store
+            .load_reader(data.as_bytes(), &RDFFormat::Turtle, None)
+            .unwrap();
+        let query = r#"PREFIX : <http://example.org/>
+            SELECT DISTINCT ?x WHERE { ?x :knows ?y . ?y :name "Carol" }"#;
+        let solutions: Vec<QuerySolution<SRDFStore>> = store
+            .query_select(query)
+            .unwrap()
+            .map(|s| s.unwrap())
+            .collect();
+        let mut nodes: Vec<_> = solutions
+            .iter()
+            .map(|s| s.find_solution("x").unwrap().to_string())
+            .collect();
+        nodes.sort();
+        assert_eq!(
+            nodes,
+            vec!["<http://example.org/alice>", "<http://example.org/bob>"]
+        );
+        assert!(store
+            .query_ask("ASK { <http://example.org/bob> ?p <http://example.org/carol> }")
+            .unwrap());
+        let query = r#"PREFIX : <http://example.org/>
+            ASK { :alice :knows+/:name ?name FILTER (STRSTARTS(?name, "C")) }"#;
+        assert!(store.query_ask(query).unwrap());
+        assert!(matches!(
+            store.query_select("SELECT ?x WHERE { GRAPH ?g { ?x ?p ?o } }"),
+            Err(SRDFStoreError::SparqlEvalError { .. })
+        ));
+    }
+}
@@ -0,0 +1,64 @@
+use crate::SparqlEvalError;
+use iri_s::IriSError;
+use oxiri::IriParseError;
+use oxrdf::TermParseError;
+use oxrdfio::RdfParseError;
+use prefixmap::PrefixMapError;
+use std::io;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum SRDFStoreError {
+    #[error("Opening store at {path:?} error: {error:?}")]
+    OpeningStore { path: String, error: io::Error },
+
+    #[error(transparent)]
+    IOError {
+        #[from]
+        err: io::Error,
+    },
+
+    #[error(transparent)]
+    RdfParseError {
+        #[from]
+        err: RdfParseError,
+    },
+
+    #[error(
+        "The store is not backed by a folder, open it with SRDFStore::open to load or commit data"
+    )]
+    NoFolder,
+
+    #[error("Store file {file} is corrupted: {msg}")]
+    Corrupted { file: String, msg: String },
+
+    #[error("Term {term} of the store can't be parsed: {err}")]
+    TermParse { term: String, err: TermParseError },
+
+    #[error("Expected term {term} to be {expected}")]
+    UnexpectedTerm { term: String, expected: String },
+
+    #[error(transparent)]
+    SparqlEvalError {
+        #[from]
+        err: SparqlEvalError,
+    },
+
+    #[error(transparent)]
+    IriParseError {
+        #[from]
+        err: IriParseError,
+    },
+
+    #[error(transparent)]
+    IriSError {
+        #[from]
+        err: IriSError,
+    },
+
+    #[error(transparent)]
+    PrefixMapError {
+        #[from]
+        err: PrefixMapError,
+    },
+}
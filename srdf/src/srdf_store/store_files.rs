@@ -0,0 +1,503 @@
+//! Files of a [`crate::SRDFStore`]
+//!
+//! The terms are encoded as integers by a dictionary, made of the terms in N-Triples syntax
+//! (`terms.bin`), the position of each term in that file (`terms.idx`) and the hash of each term
+//! sorted to find its identifier (`terms.hash`).
+//! The encoded triples are kept sorted in three indexes (`spo.idx`, `pos.idx` and `osp.idx`),
+//! so the triples that match any pattern are a range of one of them.
+//! All the files are memory mapped, the terms files are only appended to and the other files
+//! are replaced by renaming, so the maps are never modified while they are in use.
+use memmap2::Mmap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufReader, BufWriter, ErrorKind, Read, Write};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Identifier of a term in the dictionary
+pub(crate) type Id = u64;
+
+/// Encoded triple in the order of the index that contains it
+type Key = [Id; 3];
+
+const ID_SIZE: usize = 8;
+const KEY_SIZE: usize = 3 * ID_SIZE;
+const ENTRY_SIZE: usize = 2 * ID_SIZE;
+
+const TERMS_FILE: &str = "terms.bin";
+const OFFSETS_FILE: &str = "terms.idx";
+const HASHES_FILE: &str = "terms.hash";
+
+/// Orders of the indexes of the triples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Order {
+    Spo,
+    Pos,
+    Osp,
+}
+
+impl Order {
+    const ALL: [Order; 3] = [Order::Spo, Order::Pos, Order::Osp];
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            Order::Spo => "spo.idx",
+            Order::Pos => "pos.idx",
+            Order::Osp => "osp.idx",
+        }
+    }
+
+    fn key(&self, [s, p, o]: [Id; 3]) -> Key {
+        match self {
+            Order::Spo => [s, p, o],
+            Order::Pos => [p, o, s],
+            Order::Osp => [o, s, p],
+        }
+    }
+
+    fn triple(&self, key: Key) -> [Id; 3] {
+        match self {
+            Order::Spo => key,
+            Order::Pos => [key[2], key[0], key[1]],
+            Order::Osp => [key[1], key[2], key[0]],
+        }
+    }
+}
+
+/// Memory maps of the files of a store
+#[derive(Debug)]
+pub(crate) struct StoreFiles {
+    dir: PathBuf,
+    terms: Mmap,
+    offsets: Mmap,
+    hashes: Mmap,
+    indexes: [Mmap; 3],
+}
+
+impl StoreFiles {
+    /// Maps the files of the store in `dir`, creating the ones that don't exist
+    pub(crate) fn open(dir: &Path) -> io::Result<StoreFiles> {
+        Ok(StoreFiles {
+            dir: dir.to_path_buf(),
+            terms: map_file(&dir.join(TERMS_FILE))?,
+            offsets: map_file(&dir.join(OFFSETS_FILE))?,
+            hashes: map_file(&dir.join(HASHES_FILE))?,
+            indexes: [
+                map_file(&dir.join(Order::Spo.file_name()))?,
+                map_file(&dir.join(Order::Pos.file_name()))?,
+                map_file(&dir.join(Order::Osp.file_name()))?,
+            ],
+        })
+    }
+
+    /// Folder of the store
+    pub(crate) fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Number of triples
+    pub(crate) fn len(&self) -> usize {
+        self.index(Order::Spo).len() / KEY_SIZE
+    }
+
+    /// Number of terms in the dictionary
+    fn terms_len(&self) -> Id {
+        (self.offsets.len() / ENTRY_SIZE) as Id
+    }
+
+    /// Term with identifier `id` in N-Triples syntax
+    pub(crate) fn term(&self, id: Id) -> Option<&str> {
+        read_term(&self.terms, &self.offsets, id)
+    }
+
+    /// Identifier of a term in N-Triples syntax
+    pub(crate) fn id(&self, term: &str) -> Option<Id> {
+        find_term(&self.hashes, term, |id| self.term(id))
+    }
+
+    /// Encoded triples that match a pattern of identifiers, where `None` matches any term
+    pub(crate) fn triples(&self, pattern: [Option<Id>; 3]) -> impl Iterator<Item = [Id; 3]> + '_ {
+        let (order, prefix) = match pattern {
+            [Some(s), Some(p), Some(o)] => (Order::Spo, vec![s, p, o]),
+            [Some(s), Some(p), None] => (Order::Spo, vec![s, p]),
+            [Some(s), None, Some(o)] => (Order::Osp, vec![o, s]),
+            [Some(s), None, None] => (Order::Spo, vec![s]),
+            [None, Some(p), Some(o)] => (Order::Pos, vec![p, o]),
+            [None, Some(p), None] => (Order::Pos, vec![p]),
+            [None, None, Some(o)] => (Order::Osp, vec![o]),
+            [None, None, None] => (Order::Spo, Vec::new()),
+        };
+        let index = self.index(order);
+        key_range(index, &prefix).map(move |pos| order.triple(read_key(index, pos)))
+    }
+
+    fn index(&self, order: Order) -> &Mmap {
+        match order {
+            Order::Spo => &self.indexes[0],
+            Order::Pos => &self.indexes[1],
+            Order::Osp => &self.indexes[2],
+        }
+    }
+}
+
+/// Changes to the files of a store
+///
+/// The new terms are appended to the dictionary as they are found and the triples are sorted in
+/// chunks of `chunk_size` triples, which are merged with the indexes when the loader is finished.
+/// The hashes of the new terms are also kept in memory in chunks of `chunk_size` terms, which are
+/// written sorted in run files that are searched to find the terms and merged with the hashes of
+/// the dictionary when the loader is finished.
+/// If the loader is dropped before finishing, the files are left as they were.
+pub(crate) struct Loader<'a> {
+    files: &'a StoreFiles,
+    terms: BufWriter<File>,
+    offsets: BufWriter<File>,
+    terms_start: u64,
+    offsets_start: u64,
+    terms_end: u64,
+    next_id: Id,
+    new_terms: HashMap<String, Id>,
+    terms_runs: Vec<(PathBuf, Mmap)>,
+    /// Maps of the terms and offsets files including the terms of the runs
+    written_terms: Option<(Mmap, Mmap)>,
+    chunk: Vec<[Id; 3]>,
+    chunk_size: usize,
+    runs: Vec<PathBuf>,
+    removed: HashSet<[Id; 3]>,
+    finished: bool,
+}
+
+impl<'a> Loader<'a> {
+    pub(crate) fn new(files: &'a StoreFiles, chunk_size: usize) -> io::Result<Loader<'a>> {
+        let terms = OpenOptions::new()
+            .append(true)
+            .open(files.dir.join(TERMS_FILE))?;
+        let offsets = OpenOptions::new()
+            .append(true)
+            .open(files.dir.join(OFFSETS_FILE))?;
+        let terms_start = terms.metadata()?.len();
+        let offsets_start = offsets.metadata()?.len();
+        Ok(Loader {
+            files,
+            terms: BufWriter::new(terms),
+            offsets: BufWriter::new(offsets),
+            terms_start,
+            offsets_start,
+            terms_end: terms_start,
+            next_id: files.terms_len(),
+            new_terms: HashMap::new(),
+            terms_runs: Vec::new(),
+            written_terms: None,
+            chunk: Vec::new(),
+            chunk_size: chunk_size.max(1),
+            runs: Vec::new(),
+            removed: HashSet::new(),
+            finished: false,
+        })
+    }
+
+    /// Identifier of a term in N-Triples syntax, which is added to the dictionary if it is new
+    pub(crate) fn encode(&mut self, term: &str) -> io::Result<Id> {
+        if let Some(id) = self.lookup(term) {
+            return Ok(id);
+        }
+        let id = self.next_id;
+        self.terms.write_all(term.as_bytes())?;
+        self.offsets.write_all(&self.terms_end.to_le_bytes())?;
+        self.terms_end += term.len() as u64;
+        self.offsets.write_all(&self.terms_end.to_le_bytes())?;
+        self.new_terms.insert(term.to_string(), id);
+        self.next_id += 1;
+        if self.new_terms.len() >= self.chunk_size {
+            self.write_terms_run()?
+        }
+        Ok(id)
+    }
+
+    /// Identifier of a term in N-Triples syntax if it is in the dictionary
+    pub(crate) fn lookup(&self, term: &str) -> Option<Id> {
+        self.files
+            .id(term)
+            .or_else(|| self.new_terms.get(term).copied())
+            .or_else(|| {
+                let (terms, offsets) = self.written_terms.as_ref()?;
+                self.terms_runs.iter().find_map(|(_, hashes)| {
+                    find_term(hashes, term, |id| read_term(terms, offsets, id))
+                })
+            })
+    }
+
+    /// Writes the sorted hashes of the new terms in memory in a run file
+    fn write_terms_run(&mut self) -> io::Result<()> {
+        self.terms.flush()?;
+        self.offsets.flush()?;
+        let mut hashes: Vec<(u64, Id)> = self
+            .new_terms
+            .drain()
+            .map(|(term, id)| (hash(&term), id))
+            .collect();
+        hashes.sort_unstable();
+        let path = self
+            .files
+            .dir
+            .join(format!("run-{}-{HASHES_FILE}.tmp", self.terms_runs.len()));
+        let mut writer = BufWriter::new(File::create(&path)?);
+        for entry in hashes {
+            write_entry(&mut writer, entry)?;
+        }
+        writer.flush()?;
+        let hashes = map_file(&path)?;
+        self.terms_runs.push((path, hashes));
+        self.written_terms = Some((
+            map_file(&self.files.dir.join(TERMS_FILE))?,
+            map_file(&self.files.dir.join(OFFSETS_FILE))?,
+        ));
+        Ok(())
+    }
+
+    pub(crate) fn insert(&mut self, triple: [Id; 3]) -> io::Result<()> {
+        self.chunk.push(triple);
+        if self.chunk.len() >= self.chunk_size {
+            self.write_chunk()?
+        }
+        Ok(())
+    }
+
+    pub(crate) fn remove(&mut self, triple: [Id; 3]) {
+        self.removed.insert(triple);
+    }
+
+    /// Writes the sorted chunk of triples in one run file per index
+    fn write_chunk(&mut self) -> io::Result<()> {
+        let run = self.runs.len() / Order::ALL.len();
+        for order in Order::ALL {
+            let mut keys: Vec<Key> = self.chunk.iter().map(|t| order.key(*t)).collect();
+            keys.sort_unstable();
+            keys.dedup();
+            let path = self
+                .files
+                .dir
+                .join(format!("run-{run}-{}.tmp", order.file_name()));
+            let mut writer = BufWriter::new(File::create(&path)?);
+            for key in keys {
+                write_key(&mut writer, key)?;
+            }
+            writer.flush()?;
+            self.runs.push(path);
+        }
+        self.chunk.clear();
+        Ok(())
+    }
+
+    /// Writes the new terms and merges the triples with the indexes
+    ///
+    /// The files must be mapped again to see the changes
+    pub(crate) fn finish(mut self) -> io::Result<()> {
+        if !self.chunk.is_empty() {
+            self.write_chunk()?
+        }
+        self.terms.flush()?;
+        self.offsets.flush()?;
+        self.write_hashes()?;
+        if !self.runs.is_empty() || !self.removed.is_empty() {
+            for (n, order) in Order::ALL.iter().enumerate() {
+                let index = self.files.dir.join(order.file_name());
+                let mut sources = vec![index.clone()];
+                sources.extend(self.runs.iter().skip(n).step_by(Order::ALL.len()).cloned());
+                let target = self.files.dir.join(format!("{}.tmp", order.file_name()));
+                merge(&sources, *order, &self.removed, &target)?;
+                fs::rename(&target, &index)?;
+            }
+        }
+        self.finished = true;
+        Ok(())
+    }
+
+    /// Merges the hashes of the new terms with the ones of the dictionary
+    fn write_hashes(&mut self) -> io::Result<()> {
+        if !self.new_terms.is_empty() {
+            self.write_terms_run()?
+        }
+        if self.terms_runs.is_empty() {
+            return Ok(());
+        }
+        let mut readers = Vec::new();
+        let mut heap = BinaryHeap::new();
+        let runs = self.terms_runs.iter().map(|(path, _)| path);
+        for source in std::iter::once(&self.files.dir.join(HASHES_FILE)).chain(runs) {
+            let mut reader = BufReader::new(File::open(source)?);
+            if let Some(entry) = next_entry(&mut reader)? {
+                heap.push(Reverse((entry, readers.len())));
+            }
+            readers.push(reader);
+        }
+        let target = self.files.dir.join(format!("{HASHES_FILE}.tmp"));
+        let mut writer = BufWriter::new(File::create(&target)?);
+        while let Some(Reverse((entry, n))) = heap.pop() {
+            write_entry(&mut writer, entry)?;
+            if let Some(next) = next_entry(&mut readers[n])? {
+                heap.push(Reverse((next, n)));
+            }
+        }
+        writer.flush()?;
+        fs::rename(&target, self.files.dir.join(HASHES_FILE))
+    }
+}
+
+impl Drop for Loader<'_> {
+    fn drop(&mut self) {
+        let terms_runs = self.terms_runs.iter().map(|(path, _)| path);
+        for run in self.runs.iter().chain(terms_runs) {
+            let _ = fs::remove_file(run);
+        }
+        if !self.finished {
+            // The terms appended by this loader are not referenced by the indexes
+            let _ = self.terms.flush();
+            let _ = self.offsets.flush();
+            let _ = self.terms.get_ref().set_len(self.terms_start);
+            let _ = self.offsets.get_ref().set_len(self.offsets_start);
+        }
+    }
+}
+
+fn map_file(path: &Path) -> io::Result<Mmap> {
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)?;
+    // SAFETY: the files of a store are only appended to or replaced by renaming a new file,
+    // so the mapped bytes don't change while the map is alive
+    unsafe { Mmap::map(&file) }
+}
+
+/// FNV-1a hash of a term, which unlike the hashers of the standard library is stable across runs
+fn hash(term: &str) -> u64 {
+    term.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Term with identifier `id` of the maps of the terms and offsets files
+fn read_term<'a>(terms: &'a [u8], offsets: &[u8], id: Id) -> Option<&'a str> {
+    let entry = id as usize * ENTRY_SIZE;
+    let start = read_id(offsets, entry)? as usize;
+    let end = read_id(offsets, entry + ID_SIZE)? as usize;
+    std::str::from_utf8(terms.get(start..end)?).ok()
+}
+
+/// Identifier of a term in a file of sorted hashes, where `read` gets the term of an identifier
+fn find_term<'a>(hashes: &[u8], term: &str, read: impl Fn(Id) -> Option<&'a str>) -> Option<Id> {
+    let hash = hash(term);
+    let len = hashes.len() / ENTRY_SIZE;
+    let mut pos = partition_point(len, |i| read_id(hashes, i * ENTRY_SIZE) < Some(hash));
+    while pos < len && read_id(hashes, pos * ENTRY_SIZE) == Some(hash) {
+        let id = read_id(hashes, pos * ENTRY_SIZE + ID_SIZE)?;
+        if read(id) == Some(term) {
+            return Some(id);
+        }
+        pos += 1
+    }
+    None
+}
+
+fn read_id(bytes: &[u8], pos: usize) -> Option<Id> {
+    let mut id = [0; ID_SIZE];
+    id.copy_from_slice(bytes.get(pos..pos + ID_SIZE)?);
+    Some(Id::from_le_bytes(id))
+}
+
+fn read_key(index: &[u8], pos: usize) -> Key {
+    let start = pos * KEY_SIZE;
+    [0, 1, 2].map(|n| read_id(index, start + n * ID_SIZE).unwrap_or_default())
+}
+
+/// Positions of the keys of an index that start with `prefix`
+fn key_range(index: &[u8], prefix: &[Id]) -> Range<usize> {
+    let len = index.len() / KEY_SIZE;
+    let start = partition_point(len, |pos| read_key(index, pos)[..prefix.len()] < *prefix);
+    let end = partition_point(len, |pos| read_key(index, pos)[..prefix.len()] <= *prefix);
+    start..end
+}
+
+/// First position in `0..len` for which `pred` is false, assuming it is true for the ones before
+fn partition_point(len: usize, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pred(mid) {
+            low = mid + 1
+        } else {
+            high = mid
+        }
+    }
+    low
+}
+
+/// Merges sorted files of keys into `target` without duplicates, where the `removed` triples
+/// are only taken out of the first file, which is the current index
+fn merge(
+    sources: &[PathBuf],
+    order: Order,
+    removed: &HashSet<[Id; 3]>,
+    target: &Path,
+) -> io::Result<()> {
+    let mut readers = Vec::new();
+    let mut heap = BinaryHeap::new();
+    for source in sources {
+        let mut reader = BufReader::new(File::open(source)?);
+        if let Some(key) = next_key(&mut reader)? {
+            heap.push(Reverse((key, readers.len())));
+        }
+        readers.push(reader);
+    }
+    let mut writer = BufWriter::new(File::create(target)?);
+    let mut last = None;
+    while let Some(Reverse((key, n))) = heap.pop() {
+        if last != Some(key) && (n > 0 || !removed.contains(&order.triple(key))) {
+            write_key(&mut writer, key)?;
+            last = Some(key);
+        }
+        if let Some(next) = next_key(&mut readers[n])? {
+            heap.push(Reverse((next, n)));
+        }
+    }
+    writer.flush()
+}
+
+fn next_key<R: Read>(reader: &mut R) -> io::Result<Option<Key>> {
+    let mut bytes = [0; KEY_SIZE];
+    match reader.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some(
+            [0, 1, 2].map(|n| read_id(&bytes, n * ID_SIZE).unwrap_or_default()),
+        )),
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn next_entry<R: Read>(reader: &mut R) -> io::Result<Option<(u64, Id)>> {
+    let mut bytes = [0; ENTRY_SIZE];
+    match reader.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some((
+            read_id(&bytes, 0).unwrap_or_default(),
+            read_id(&bytes, ID_SIZE).unwrap_or_default(),
+        ))),
+        Err(err) if err.kind() == ErrorKind::UnexpectedEof => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+fn write_key<W: Write>(writer: &mut W, key: Key) -> io::Result<()> {
+    for id in key {
+        writer.write_all(&id.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn write_entry<W: Write>(writer: &mut W, (hash, id): (u64, Id)) -> io::Result<()> {
+    writer.write_all(&hash.to_le_bytes())?;
+    writer.write_all(&id.to_le_bytes())
+}
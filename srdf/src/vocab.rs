@@ -6,13 +6,18 @@ use lazy_static::lazy_static;
 pub const RDF: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
 pub const RDFS: &str = "http://www.w3.org/2000/01/rdf-schema#";
 pub const XSD: &str = "http://www.w3.org/2001/XMLSchema#";
+pub const OWL: &str = "http://www.w3.org/2002/07/owl#";
 pub const RDF_TYPE_STR: &str = concatcp!(RDF, "type");
 pub const RDF_FIRST_STR: &str = concatcp!(RDF, "first");
 pub const RDF_REST_STR: &str = concatcp!(RDF, "rest");
 pub const RDF_NIL_STR: &str = concatcp!(RDF, "nil");
 pub const RDFS_LABEL_STR: &str = concatcp!(RDFS, "label");
+pub const RDFS_COMMENT_STR: &str = concatcp!(RDFS, "comment");
 pub const RDFS_SUBCLASS_OF_STR: &str = concatcp!(RDFS, "subClassOf");
 pub const RDFS_CLASS_STR: &str = concatcp!(RDFS, "Class");
+pub const RDFS_DOMAIN_STR: &str = concatcp!(RDFS, "domain");
+pub const RDFS_RANGE_STR: &str = concatcp!(RDFS, "range");
+pub const OWL_IMPORTS_STR: &str = concatcp!(OWL, "imports");
 pub const XSD_BOOLEAN_STR: &str = concatcp!(XSD, "boolean");
 pub const XSD_INTEGER_STR: &str = concatcp!(XSD, "integer");
 pub const XSD_DECIMAL_STR: &str = concatcp!(XSD, "decimal");
@@ -24,8 +29,12 @@ lazy_static! {
     pub static ref RDF_REST: IriS = IriS::new_unchecked(RDF_REST_STR);
     pub static ref RDF_NIL: IriS = IriS::new_unchecked(RDF_NIL_STR);
     pub static ref RDFS_LABEL: IriS = IriS::new_unchecked(RDFS_LABEL_STR);
+    pub static ref RDFS_COMMENT: IriS = IriS::new_unchecked(RDFS_COMMENT_STR);
     pub static ref RDFS_SUBCLASS_OF: IriS = IriS::new_unchecked(RDFS_SUBCLASS_OF_STR);
     pub static ref RDFS_CLASS: IriS = IriS::new_unchecked(RDFS_CLASS_STR);
+    pub static ref RDFS_DOMAIN: IriS = IriS::new_unchecked(RDFS_DOMAIN_STR);
+    pub static ref RDFS_RANGE: IriS = IriS::new_unchecked(RDFS_RANGE_STR);
+    pub static ref OWL_IMPORTS: IriS = IriS::new_unchecked(OWL_IMPORTS_STR);
     pub static ref XSD_BOOLEAN: IriS = IriS::new_unchecked(XSD_BOOLEAN_STR);
     pub static ref XSD_INTEGER: IriS = IriS::new_unchecked(XSD_INTEGER_STR);
     pub static ref XSD_DECIMAL: IriS = IriS::new_unchecked(XSD_DECIMAL_STR);